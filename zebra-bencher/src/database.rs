@@ -1,4 +1,9 @@
-use zebra_chain::IndexedBlock;
+use tempdir::TempDir;
+use zebra_chain::{IndexedBlock, Transaction};
+use zebra_db::kv::{
+    DatabaseConfig, DiskDatabase, Key, KeyValue, KeyValueDatabase, Transaction as DBTransaction,
+    COL_COUNT,
+};
 use zebra_db::BlockChainDatabase;
 use zebra_storage::{BlockOrigin, BlockProvider, BlockRef, ForkChain};
 use zebra_test_data;
@@ -54,6 +59,73 @@ pub fn fetch(benchmark: &mut Benchmark) {
     benchmark.stop();
 }
 
+/// Opens disk database with `transactions` transactions and returns keys of these transactions.
+fn transactions_database(path: &TempDir, transactions: usize) -> (DiskDatabase, Vec<Key>) {
+    let db = DiskDatabase::open(DatabaseConfig::with_columns(Some(COL_COUNT)), path.path())
+        .expect("cannot open database");
+
+    let mut update = DBTransaction::new();
+    let mut keys = Vec::new();
+    for x in 0..transactions {
+        let transaction: Transaction = zebra_test_data::TransactionBuilder::with_output(x as u64)
+            .add_default_input(0)
+            .into();
+        let hash = transaction.hash();
+        update.insert(KeyValue::Transaction(hash.clone(), transaction));
+        keys.push(Key::Transaction(hash));
+    }
+    db.write(update).expect("cannot write transactions");
+
+    (db, keys)
+}
+
+// 1. write 2000 transactions
+// 2. read all transactions one by one
+pub fn fetch_transactions(benchmark: &mut Benchmark) {
+    // params
+    const TRANSACTIONS: usize = 2000;
+    const ROUNDS: usize = 100;
+
+    benchmark.samples(ROUNDS);
+
+    // test setup
+    let path = TempDir::new("fetch_transactions").unwrap();
+    let (db, keys) = transactions_database(&path, TRANSACTIONS);
+
+    // bench
+    benchmark.start();
+    for _ in 0..ROUNDS {
+        for key in &keys {
+            assert!(db.get(key).unwrap().into_option().is_some());
+        }
+    }
+    benchmark.stop();
+}
+
+// 1. write 2000 transactions
+// 2. read all transactions with single call
+pub fn fetch_transactions_batched(benchmark: &mut Benchmark) {
+    // params
+    const TRANSACTIONS: usize = 2000;
+    const ROUNDS: usize = 100;
+
+    benchmark.samples(ROUNDS);
+
+    // test setup
+    let path = TempDir::new("fetch_transactions_batched").unwrap();
+    let (db, keys) = transactions_database(&path, TRANSACTIONS);
+
+    // bench
+    benchmark.start();
+    for _ in 0..ROUNDS {
+        let values = db.get_many(&keys).unwrap();
+        assert!(values
+            .into_iter()
+            .all(|value| value.into_option().is_some()));
+    }
+    benchmark.stop();
+}
+
 pub fn write(benchmark: &mut Benchmark) {
     // params
     const BLOCKS: usize = 1000;
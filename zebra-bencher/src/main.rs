@@ -1,4 +1,5 @@
 extern crate byteorder;
+extern crate tempdir;
 extern crate time;
 extern crate zebra_chain;
 extern crate zebra_db;
@@ -88,6 +89,8 @@ macro_rules! benchmark {
 
 fn main() {
     benchmark!(database::fetch);
+    benchmark!(database::fetch_transactions);
+    benchmark!(database::fetch_transactions_batched);
     benchmark!(database::write);
     benchmark!(database::reorg_short);
     benchmark!(database::write_heavy);
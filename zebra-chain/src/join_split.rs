@@ -1,7 +1,8 @@
 use hash::{H256, H512};
+use heapsize::HeapSizeOf;
 use hex::ToHex;
 use ser::{CompactInteger, Error, Reader, Serializable, Stream};
-use std::{fmt, io};
+use std::{fmt, io, mem};
 use zebra_crypto::Groth16Proof;
 
 #[derive(Clone)]
@@ -31,6 +32,13 @@ pub struct JoinSplitDescription {
     pub ciphertexts: [[u8; 601]; 2],
 }
 
+impl HeapSizeOf for JoinSplit {
+    fn heap_size_of_children(&self) -> usize {
+        // descriptions have no heap-allocated data
+        self.descriptions.capacity() * mem::size_of::<JoinSplitDescription>()
+    }
+}
+
 impl Default for JoinSplitDescription {
     fn default() -> Self {
         JoinSplitDescription {
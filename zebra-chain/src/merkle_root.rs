@@ -22,7 +22,7 @@ where
         return hashes[0].as_ref().clone();
     }
 
-    let mut row = Vec::with_capacity(hashes.len() / 2);
+    let mut row = Vec::with_capacity((hashes.len() + 1) / 2);
     let mut i = 0;
     while i + 1 < hashes.len() {
         row.push(merkle_node_hash(&hashes[i], &hashes[i + 1]));
@@ -35,7 +35,22 @@ where
         row.push(merkle_node_hash(last, last));
     }
 
-    merkle_root(&row)
+    // upper rows are computed in place, reusing the same buffer
+    while row.len() > 1 {
+        if row.len() % 2 == 1 {
+            let last = row[row.len() - 1].clone();
+            row.push(last);
+        }
+
+        let len = row.len() / 2;
+        for i in 0..len {
+            let hash = merkle_node_hash(&row[2 * i], &row[2 * i + 1]);
+            row[i] = hash;
+        }
+        row.truncate(len);
+    }
+
+    row.pop().unwrap_or_default()
 }
 
 /// Calculate merkle tree node hash
@@ -48,7 +63,7 @@ where
 
 #[cfg(test)]
 mod tests {
-    use super::merkle_root;
+    use super::{merkle_node_hash, merkle_root};
     use hash::H256;
 
     // block 80_000
@@ -70,4 +85,17 @@ mod tests {
         assert_eq!(result, expected);
         assert_eq!(result2, expected);
     }
+
+    #[test]
+    fn test_merkle_root_with_odd_rows() {
+        let hashes: Vec<H256> = (1..6u8).map(H256::from).collect();
+        let h01 = merkle_node_hash(&hashes[0], &hashes[1]);
+        let h23 = merkle_node_hash(&hashes[2], &hashes[3]);
+        let h44 = merkle_node_hash(&hashes[4], &hashes[4]);
+        let h0123 = merkle_node_hash(&h01, &h23);
+        let h4444 = merkle_node_hash(&h44, &h44);
+        let expected = merkle_node_hash(&h0123, &h4444);
+
+        assert_eq!(merkle_root(&hashes), expected);
+    }
 }
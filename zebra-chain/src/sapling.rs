@@ -1,5 +1,6 @@
+use heapsize::HeapSizeOf;
 use hex::ToHex;
-use std::fmt;
+use std::{fmt, mem};
 
 ///
 #[derive(Clone)]
@@ -74,6 +75,14 @@ pub struct SaplingOutputDescription {
     pub zkproof: [u8; 192],
 }
 
+impl HeapSizeOf for Sapling {
+    fn heap_size_of_children(&self) -> usize {
+        // descriptions have no heap-allocated data
+        self.spends.capacity() * mem::size_of::<SaplingSpendDescription>()
+            + self.outputs.capacity() * mem::size_of::<SaplingOutputDescription>()
+    }
+}
+
 impl Default for Sapling {
     fn default() -> Self {
         Sapling {
@@ -8,6 +8,75 @@ pub const SOLUTION_SIZE: usize = 1344;
 #[derive(Clone)]
 pub struct EquihashSolution([u8; SOLUTION_SIZE]);
 
+impl EquihashSolution {
+    /// Pack Equihash (`n`, `k`) solution indices into the compact (minimal) representation.
+    /// Every index is stored as BE-encoded `n / (k + 1) + 1` bits string.
+    /// Returns None if indices do not fit into the solution of SOLUTION_SIZE bytes.
+    pub fn from_indices(indices: &[u32], n: u32, k: u32) -> Option<Self> {
+        let index_bits = index_bits(n, k);
+        if index_bits == 0 || index_bits > 32 || k >= 32 || indices.len() != 1usize << k {
+            return None;
+        }
+        if indices.len() * index_bits != SOLUTION_SIZE * 8 {
+            return None;
+        }
+        if index_bits < 32 && indices.iter().any(|index| *index >> index_bits != 0) {
+            return None;
+        }
+
+        let mut solution = [0; SOLUTION_SIZE];
+        let mut acc_value = 0u64;
+        let mut acc_bits = 0usize;
+        let mut position = 0;
+        for index in indices {
+            acc_value = (acc_value << index_bits) | u64::from(*index);
+            acc_bits += index_bits;
+            while acc_bits >= 8 {
+                acc_bits -= 8;
+                solution[position] = (acc_value >> acc_bits) as u8;
+                position += 1;
+            }
+        }
+
+        Some(EquihashSolution(solution))
+    }
+
+    /// Unpack Equihash (`n`, `k`) solution indices from the compact (minimal) representation.
+    /// Returns empty vector if solution can not be interpreted as (`n`, `k`) solution.
+    pub fn to_indices(&self, n: u32, k: u32) -> Vec<u32> {
+        let index_bits = index_bits(n, k);
+        if index_bits == 0 || index_bits > 32 || k >= 32 {
+            return Vec::new();
+        }
+        if (1usize << k) * index_bits != SOLUTION_SIZE * 8 {
+            return Vec::new();
+        }
+
+        let index_mask = (1u64 << index_bits) - 1;
+        let mut indices = Vec::with_capacity(1usize << k);
+        let mut acc_value = 0u64;
+        let mut acc_bits = 0usize;
+        for byte in self.0.iter() {
+            acc_value = (acc_value << 8) | u64::from(*byte);
+            acc_bits += 8;
+            if acc_bits >= index_bits {
+                acc_bits -= index_bits;
+                indices.push(((acc_value >> acc_bits) & index_mask) as u32);
+            }
+        }
+
+        indices
+    }
+}
+
+/// Number of bits, required to store single solution index of Equihash (`n`, `k`).
+fn index_bits(n: u32, k: u32) -> usize {
+    match k.checked_add(1) {
+        Some(k_plus_one) => (n / k_plus_one) as usize + 1,
+        None => 0,
+    }
+}
+
 impl AsRef<[u8]> for EquihashSolution {
     fn as_ref(&self) -> &[u8] {
         &self.0
@@ -50,3 +119,57 @@ impl Deserializable for EquihashSolution {
         Ok(EquihashSolution(sol))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{EquihashSolution, SOLUTION_SIZE};
+
+    #[test]
+    fn from_indices_to_indices_roundtrip() {
+        // simple xorshift generator => deterministic set of solutions
+        let mut seed = 0x2545f491u32;
+        let mut next = move || {
+            seed ^= seed << 13;
+            seed ^= seed >> 17;
+            seed ^= seed << 5;
+            seed
+        };
+
+        for _ in 0..32 {
+            let indices: Vec<u32> = (0..512).map(|_| next() & 0x1fffff).collect();
+            let solution = EquihashSolution::from_indices(&indices, 200, 9).unwrap();
+            assert_eq!(solution.to_indices(200, 9), indices);
+
+            let mut bytes = [0u8; SOLUTION_SIZE];
+            for byte in bytes.iter_mut() {
+                *byte = next() as u8;
+            }
+            let solution = EquihashSolution(bytes);
+            assert_eq!(
+                EquihashSolution::from_indices(&solution.to_indices(200, 9), 200, 9),
+                Some(solution)
+            );
+        }
+    }
+
+    #[test]
+    fn from_indices_packs_big_endian_bits() {
+        let mut indices = vec![0; 512];
+        indices[0] = 1;
+        indices[511] = 0x1fffff;
+        let solution = EquihashSolution::from_indices(&indices, 200, 9).unwrap();
+        assert_eq!(&solution.as_ref()[..3], &[0x00, 0x00, 0x08]);
+        assert_eq!(&solution.as_ref()[SOLUTION_SIZE - 3..], &[0x1f, 0xff, 0xff]);
+    }
+
+    #[test]
+    fn from_indices_rejects_invalid_input() {
+        assert_eq!(EquihashSolution::from_indices(&[0; 511], 200, 9), None);
+        assert_eq!(
+            EquihashSolution::from_indices(&[0x200000; 512], 200, 9),
+            None
+        );
+        assert_eq!(EquihashSolution::from_indices(&[0; 32], 96, 5), None);
+        assert!(EquihashSolution::default().to_indices(96, 5).is_empty());
+    }
+}
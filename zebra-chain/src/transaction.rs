@@ -115,7 +115,10 @@ impl From<&'static str> for Transaction {
 
 impl HeapSizeOf for Transaction {
     fn heap_size_of_children(&self) -> usize {
-        self.inputs.heap_size_of_children() + self.outputs.heap_size_of_children()
+        self.inputs.heap_size_of_children()
+            + self.outputs.heap_size_of_children()
+            + self.join_split.heap_size_of_children()
+            + self.sapling.heap_size_of_children()
     }
 }
 
@@ -184,6 +187,13 @@ impl Transaction {
         self.inputs.iter().all(TransactionInput::is_final)
     }
 
+    /// Returns size of the serialized transaction, including all shielded (JoinSplit && Sapling) data.
+    ///
+    /// This is the size that should be used when computing transaction fee rate.
+    pub fn total_size(&self) -> usize {
+        self.serialized_size()
+    }
+
     pub fn total_spends(&self) -> u64 {
         let mut result = 0u64;
         for output in self.outputs.iter() {
@@ -440,6 +450,7 @@ mod tests {
         assert!(t.sapling.is_some());
 
         // serialize && check tx
+        assert_eq!(t.total_size(), hex.len() / 2);
         let t: String = serialize(&t).to_hex();
         assert_eq!(t, hex);
     }
@@ -456,6 +467,8 @@ mod tests {
         assert!(t.sapling.is_some());
         assert_eq!(t.sapling.as_ref().unwrap().spends.len(), 1);
         assert_eq!(t.sapling.as_ref().unwrap().outputs.len(), 1);
+        assert_eq!(t.total_size(), hex.len() / 2);
+        assert_eq!(t.total_size(), serialize(&t).len());
     }
 
     #[test]
@@ -1,9 +1,9 @@
 use bytes::Bytes;
 use hash::H256;
 use kv::{
-    AutoFlushingOverlayDatabase, CacheDatabase, DatabaseConfig, DiskDatabase, Key, KeyState,
-    KeyValue, KeyValueDatabase, MemoryDatabase, OverlayDatabase, Transaction as DBTransaction,
-    Value,
+    AutoFlushingOverlayDatabase, CacheDatabase, DatabaseConfig, DatabaseSnapshot, DiskDatabase,
+    Key, KeyState, KeyValue, KeyValueDatabase, MemoryDatabase, OverlayDatabase, SnapshotDatabase,
+    Transaction as DBTransaction, Value, AUTO_FLUSH_MAX_BYTES, AUTO_FLUSH_MAX_OPERATIONS,
 };
 use kv::{
     COL_BLOCK_HASHES, COL_BLOCK_HEADERS, COL_BLOCK_NUMBERS, COL_BLOCK_TRANSACTIONS, COL_COUNT,
@@ -11,38 +11,58 @@ use kv::{
     COL_TRANSACTIONS_META, COL_TREE_STATES,
 };
 use parking_lot::RwLock;
-use ser::{deserialize, serialize, List};
-use std::collections::HashMap;
+use ser::{deserialize, serialize, List, Serializable};
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::Path;
 use zebra_chain::{
     IndexedBlock, IndexedBlockHeader, IndexedTransaction, OutPoint, TransactionOutput,
 };
+use zebra_keys;
+use zebra_script::{Script, ScriptType};
 use zebra_storage::{
-    BestBlock, BlockChain, BlockHeaderProvider, BlockOrigin, BlockProvider, BlockRef, CanonStore,
-    EpochRef, EpochTag, Error, ForkChain, Forkable, NullifierTracker, SaplingTreeState,
-    SideChainOrigin, SproutTreeState, Store, TransactionMeta, TransactionMetaProvider,
-    TransactionOutputProvider, TransactionProvider, TreeStateProvider,
+    AddressIndexProvider, AddressOutput, AddressSpend, BestBlock, BlockChain, BlockHeaderProvider,
+    BlockOrigin, BlockProvider, BlockRef, CanonStore, ColumnStat, DuplexTransactionOutputProvider,
+    EpochRef, EpochTag, Error, ForkChain, Forkable, IndexedAddress, NullifierTracker,
+    SaplingTreeState, SideChainOrigin, SproutTreeState, Store, TransactionIndexProvider,
+    TransactionMeta, TransactionMetaProvider, TransactionOutputProvider, TransactionProvider,
+    TreeStateProvider,
 };
 
 const KEY_BEST_BLOCK_NUMBER: &'static str = "best_block_number";
 const KEY_BEST_BLOCK_HASH: &'static str = "best_block_hash";
+const KEY_PRUNE_HEIGHT: &'static str = "prune_height";
 
-const MAX_FORK_ROUTE_PRESET: usize = 2048;
+const KEY_ADDRESS_INDEX: &'static str = "address_index";
+const KEY_TRANSACTION_INDEX: &'static str = "transaction_index";
+
+/// Number of blocks, which heights are stored under single address index heights key.
+const ADDRESS_INDEX_BUCKET: u32 = 1000;
+
+/// Maximal number of transactions counters, written in single database transaction when backfilling.
+const CHAIN_TX_COUNT_BACKFILL_BATCH: usize = 10_000;
+
+/// Default maximal number of side chain blocks, which are checked when searching for the fork point.
+pub const MAX_FORK_ROUTE_PRESET: usize = 2048;
 
 pub struct BlockChainDatabase<T>
 where
     T: KeyValueDatabase,
 {
     best_block: RwLock<BestBlock>,
-    db: T,
+    /// Block, which chain is preferred over other chains of the same length (not persisted).
+    precious_block: RwLock<Option<H256>>,
+    max_fork_route: usize,
+    address_index: bool,
+    transaction_index: bool,
+    db: SnapshotDatabase<T>,
 }
 
 pub struct ForkChainDatabase<'a, T>
 where
     T: 'a + KeyValueDatabase,
 {
-    blockchain: BlockChainDatabase<OverlayDatabase<'a, T>>,
+    blockchain: BlockChainDatabase<OverlayDatabase<'a, SnapshotDatabase<T>>>,
 }
 
 impl<'a, T> ForkChain for ForkChainDatabase<'a, T>
@@ -53,8 +73,16 @@ where
         &self.blockchain
     }
 
+    fn decanonize(&self) -> Result<H256, Error> {
+        self.blockchain.decanonize()
+    }
+
     fn flush(&self) -> Result<(), Error> {
-        self.blockchain.db.flush().map_err(Error::DatabaseError)
+        self.blockchain
+            .db
+            .inner()
+            .flush()
+            .map_err(Error::DatabaseError)
     }
 }
 
@@ -171,7 +199,11 @@ impl BlockChainDatabase<CacheDatabase<AutoFlushingOverlayDatabase<DiskDatabase>>
         cfg.bloom_filters.insert(Some(COL_TRANSACTIONS_META), 32);
 
         match DiskDatabase::open(cfg, path) {
-            Ok(db) => Ok(Self::open_with_cache(db)),
+            Ok(db) => {
+                let db = Self::open_with_cache(db);
+                db.backfill_chain_tx_count()?;
+                Ok(db)
+            }
             Err(err) => Err(Error::DatabaseError(err)),
         }
     }
@@ -195,11 +227,19 @@ where
     T: KeyValueDatabase,
 {
     pub fn open_with_cache(db: T) -> Self {
-        let db = CacheDatabase::new(AutoFlushingOverlayDatabase::new(db, 50));
+        let db = CacheDatabase::new(AutoFlushingOverlayDatabase::new(
+            db,
+            AUTO_FLUSH_MAX_OPERATIONS,
+            AUTO_FLUSH_MAX_BYTES,
+        ));
         let best_block = Self::read_best_block(&db).unwrap_or_default();
         BlockChainDatabase {
             best_block: RwLock::new(best_block),
-            db: db,
+            precious_block: RwLock::new(None),
+            max_fork_route: MAX_FORK_ROUTE_PRESET,
+            address_index: false,
+            transaction_index: false,
+            db: SnapshotDatabase::new(db),
         }
     }
 }
@@ -233,16 +273,115 @@ where
         let best_block = Self::read_best_block(&db).unwrap_or_default();
         BlockChainDatabase {
             best_block: RwLock::new(best_block),
-            db: db,
+            precious_block: RwLock::new(None),
+            max_fork_route: MAX_FORK_ROUTE_PRESET,
+            address_index: false,
+            transaction_index: false,
+            db: SnapshotDatabase::new(db),
         }
     }
 
+    /// Sets maximal number of side chain blocks, which are checked when searching for the fork point.
+    /// Blocks of longer forks are rejected with `Error::AncientFork`.
+    pub fn set_max_fork_route(&mut self, max_fork_route: usize) {
+        self.max_fork_route = max_fork_route;
+    }
+
+    /// Enables or disables the transparent address index.
+    /// Index is updated when blocks are canonized/decanonized, so it can only be enabled on an empty
+    /// database (or on the database, where it has been enabled since the beginning).
+    pub fn set_address_index(&mut self, enabled: bool) -> Result<(), Error> {
+        self.configure_index(KEY_ADDRESS_INDEX, enabled)?;
+        self.address_index = enabled;
+        Ok(())
+    }
+
+    /// Enables or disables the transaction index.
+    /// Index is updated when blocks are inserted, so it can only be enabled on an empty
+    /// database (or on the database, where it has been enabled since the beginning).
+    pub fn set_transaction_index(&mut self, enabled: bool) -> Result<(), Error> {
+        self.configure_index(KEY_TRANSACTION_INDEX, enabled)?;
+        self.transaction_index = enabled;
+        Ok(())
+    }
+
+    /// Persists index flag, so that disabled (and thus incomplete) index is never re-enabled.
+    fn configure_index(&self, key: &'static str, enabled: bool) -> Result<(), Error> {
+        let is_enabled = self.get(Key::Configuration(key)).is_some();
+        if enabled == is_enabled {
+            return Ok(());
+        }
+
+        let mut update = DBTransaction::new();
+        if enabled {
+            if !self.best_block().hash.is_zero() {
+                return Err(Error::IncompleteIndex);
+            }
+            update.insert(KeyValue::Configuration(key, Bytes::new()));
+        } else {
+            update.delete(Key::Configuration(key));
+        }
+        self.db.write(update).map_err(Error::DatabaseError)
+    }
+
+    /// Computes cumulative transactions counters of canonized blocks, which have been canonized by
+    /// the database without the counters column. Counters of pruned blocks (and their descendants)
+    /// can't be computed, so they stay missing.
+    pub fn backfill_chain_tx_count(&self) -> Result<(), Error> {
+        let best_block = self.best_block();
+        if best_block.hash.is_zero() || self.chain_tx_count(best_block.number.into()).is_some() {
+            return Ok(());
+        }
+
+        // counters are either known for all ancestors of the block, or for none of its descendants
+        // => find the first block without counter
+        let (mut lo, mut hi) = (0, best_block.number);
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            if self.chain_tx_count(mid.into()).is_some() {
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
+        }
+
+        let mut tx_count = match lo {
+            0 => 0,
+            _ => self
+                .chain_tx_count((lo - 1).into())
+                .expect("checked during search; qed"),
+        };
+        info!(target: "db", "Computing transactions counters of blocks {}..{}", lo, best_block.number);
+
+        let mut update = DBTransaction::new();
+        for number in lo..best_block.number + 1 {
+            if self.is_pruned(number.into()) {
+                warn!(target: "db", "Transactions counters are not available for pruned blocks since {}", number);
+                break;
+            }
+
+            let hash = self.block_hash(number).ok_or_else(|| {
+                Error::DatabaseError(format!("Missing block at height {}", number))
+            })?;
+            tx_count += self.block_transaction_hashes(hash.clone().into()).len() as u64;
+            update.insert(KeyValue::ChainTxCount(hash, tx_count));
+            if update.operations.len() >= CHAIN_TX_COUNT_BACKFILL_BATCH {
+                let full_update = ::std::mem::replace(&mut update, DBTransaction::new());
+                self.db.write(full_update).map_err(Error::DatabaseError)?;
+            }
+        }
+        self.db.write(update).map_err(Error::DatabaseError)
+    }
+
     pub fn best_block(&self) -> BestBlock {
         self.best_block.read().clone()
     }
 
     pub fn fork(&self, side_chain: SideChainOrigin) -> Result<ForkChainDatabase<T>, Error> {
-        let overlay = BlockChainDatabase::open(OverlayDatabase::new(&self.db));
+        let mut overlay = BlockChainDatabase::open(OverlayDatabase::new(&self.db));
+        overlay.set_max_fork_route(self.max_fork_route);
+        overlay.address_index = self.address_index;
+        overlay.transaction_index = self.transaction_index;
 
         for hash in side_chain.decanonized_route.into_iter().rev() {
             let decanonized_hash = overlay.decanonize()?;
@@ -263,7 +402,34 @@ where
     pub fn switch_to_fork(&self, fork: ForkChainDatabase<T>) -> Result<(), Error> {
         let mut best_block = self.best_block.write();
         *best_block = fork.blockchain.best_block.read().clone();
-        fork.blockchain.db.flush().map_err(Error::DatabaseError)
+        fork.blockchain
+            .db
+            .inner()
+            .flush()
+            .map_err(Error::DatabaseError)
+    }
+
+    /// Writes all changes, buffered in memory, to the persistent storage.
+    ///
+    /// Waits for the (de)canonization in progress to complete, so that the stored best block
+    /// is consistent with the stored chain.
+    pub fn flush(&self) -> Result<(), Error> {
+        let _best_block = self.best_block.write();
+        self.db.persist().map_err(Error::DatabaseError)
+    }
+
+    /// Returns read-only view of the database state at the moment of the call.
+    ///
+    /// Snapshot (including its best block) is not affected by blocks, (de)canonized after it has
+    /// been created, and it never blocks writes. But while snapshot is alive, every write copies
+    /// original values of modified keys into memory, so snapshot should be dropped as soon as
+    /// consistent reads are finished.
+    pub fn snapshot(&self) -> BlockChainDatabase<DatabaseSnapshot<T>> {
+        let mut snapshot = BlockChainDatabase::open(self.db.snapshot());
+        snapshot.set_max_fork_route(self.max_fork_route);
+        snapshot.address_index = self.address_index;
+        snapshot.transaction_index = self.transaction_index;
+        snapshot
     }
 
     pub fn block_origin(&self, header: &IndexedBlockHeader) -> Result<BlockOrigin, Error> {
@@ -287,13 +453,80 @@ where
             return Err(Error::UnknownParent);
         }
 
+        self.side_chain_origin(&best_block, header)
+    }
+
+    /// Marks block as precious, so that its chain is preferred over other chains of the same length.
+    /// The mark is only kept in memory and is lost on restart.
+    /// Returns `SideChainBecomingCanonChain` origin if the best chain should be switched to the
+    /// block chain, or `KnownBlock` if the block is already canonical.
+    pub fn precious_block(&self, block_hash: &H256) -> Result<BlockOrigin, Error> {
+        if !self.contains_block(block_hash.clone().into()) {
+            return Err(Error::UnknownParent);
+        }
+        *self.precious_block.write() = Some(block_hash.clone());
+
+        self.stored_block_origin(block_hash)
+    }
+
+    /// Checks origin of the already stored block.
+    /// Returns `KnownBlock` if the block is canonical.
+    pub fn stored_block_origin(&self, block_hash: &H256) -> Result<BlockOrigin, Error> {
+        let best_block = self.best_block.read();
+        let header = self
+            .block_header(block_hash.clone().into())
+            .ok_or(Error::UnknownParent)?;
+
+        if self.block_number(block_hash).is_some() {
+            return Ok(BlockOrigin::KnownBlock);
+        }
+
+        self.side_chain_origin(&best_block, &header)
+    }
+
+    /// Returns origin of the chain, which ends with the parent of given canonical block, i.e.
+    /// the route to decanonize the block and all its canonical descendants.
+    /// Fails with `AncientFork` if more than `max_fork_route` blocks should be decanonized.
+    pub fn rollback_origin(&self, block_hash: &H256) -> Result<SideChainOrigin, Error> {
+        let best_block = self.best_block.read();
+        let number = match self.block_number(block_hash) {
+            Some(number) if number != 0 => number,
+            _ => return Err(Error::CannotDecanonize),
+        };
+        if (best_block.number - number) as usize >= self.max_fork_route {
+            return Err(Error::AncientFork);
+        }
+
+        Ok(SideChainOrigin {
+            ancestor: number - 1,
+            canonized_route: Vec::new(),
+            decanonized_route: (number..best_block.number + 1)
+                .filter_map(|decanonized_bn| self.block_hash(decanonized_bn))
+                .collect(),
+            block_number: number - 1,
+        })
+    }
+
+    /// Checks origin of the block, which parent is a known block, but not the best block.
+    fn side_chain_origin(
+        &self,
+        best_block: &BestBlock,
+        header: &IndexedBlockHeader,
+    ) -> Result<BlockOrigin, Error> {
         let mut sidechain_route = Vec::new();
         let mut next_hash = header.raw.previous_header_hash.clone();
 
-        for fork_len in 0..MAX_FORK_ROUTE_PRESET {
+        for fork_len in 0..self.max_fork_route {
             match self.block_number(&next_hash) {
                 Some(number) => {
                     let block_number = number + fork_len as u32 + 1;
+                    // precious chain wins if chains are of the same length
+                    let is_precious = match *self.precious_block.read() {
+                        Some(ref precious_hash) => {
+                            *precious_hash == header.hash || sidechain_route.contains(precious_hash)
+                        }
+                        None => false,
+                    };
                     let origin = SideChainOrigin {
                         ancestor: number,
                         canonized_route: sidechain_route.into_iter().rev().collect(),
@@ -303,7 +536,9 @@ where
                             .collect(),
                         block_number: block_number,
                     };
-                    if block_number > best_block.number {
+                    if block_number > best_block.number
+                        || (block_number == best_block.number && is_precious)
+                    {
                         return Ok(BlockOrigin::SideChainBecomingCanonChain(origin));
                     } else {
                         return Ok(BlockOrigin::SideChain(origin));
@@ -389,6 +624,11 @@ where
                 }
             }
 
+            // transaction could also be included in the canonical block => do not overwrite
+            if self.transaction_index && self.transaction_block_hash(&tx.hash).is_none() {
+                update.insert(KeyValue::TransactionBlock(tx.hash, block.header.hash));
+            }
+
             update.insert(KeyValue::Transaction(tx.hash, tx.raw));
         }
 
@@ -425,6 +665,9 @@ where
         update.delete(Key::BlockHeader(decanonized_hash.clone()));
         update.delete(Key::BlockTransactions(decanonized_hash.clone()));
         for tx_hash in tx_to_decanonize {
+            if self.transaction_index {
+                update.delete(Key::TransactionBlock(tx_hash));
+            }
             update.delete(Key::Transaction(tx_hash));
         }
 
@@ -478,6 +721,19 @@ where
             new_best_block.hash.clone(),
             new_best_block.number,
         ));
+        // the counter is only maintained if it is known for the parent block (i.e. it is missing
+        // for all blocks, canonized by the database without this column)
+        let parent_tx_count = if block.header.raw.previous_header_hash.is_zero() {
+            Some(0)
+        } else {
+            self.chain_tx_count(block.header.raw.previous_header_hash.clone().into())
+        };
+        if let Some(parent_tx_count) = parent_tx_count {
+            update.insert(KeyValue::ChainTxCount(
+                new_best_block.hash.clone(),
+                parent_tx_count + block.transactions.len() as u64,
+            ));
+        }
         update.insert(KeyValue::Meta(
             KEY_BEST_BLOCK_HASH,
             serialize(&new_best_block.hash),
@@ -557,6 +813,43 @@ where
             update.insert(KeyValue::TransactionMeta(hash, meta));
         }
 
+        if self.transaction_index {
+            for tx in &block.transactions {
+                update.insert(KeyValue::TransactionBlock(tx.hash, *hash));
+            }
+        }
+
+        if self.address_index {
+            let number = new_best_block.number;
+            let bucket = number / ADDRESS_INDEX_BUCKET;
+            for (address, outputs) in block_address_outputs(&block, number) {
+                let mut heights = self.address_heights(Key::AddressOutputHeights(address, bucket));
+                heights.push(number);
+                update.insert(KeyValue::AddressOutputHeights(
+                    address,
+                    bucket,
+                    List::from(heights),
+                ));
+                update.insert(KeyValue::AddressOutputs(
+                    address,
+                    number,
+                    List::from(outputs),
+                ));
+            }
+
+            let store = DuplexTransactionOutputProvider::new(&block, self);
+            for (address, spends) in block_address_spends(&block, number, &store) {
+                let mut heights = self.address_heights(Key::AddressSpendHeights(address, bucket));
+                heights.push(number);
+                update.insert(KeyValue::AddressSpendHeights(
+                    address,
+                    bucket,
+                    List::from(heights),
+                ));
+                update.insert(KeyValue::AddressSpends(address, number, List::from(spends)));
+            }
+        }
+
         self.db.write(update).map_err(Error::DatabaseError)?;
         *best_block = new_best_block;
         Ok(())
@@ -571,6 +864,10 @@ where
                 return Err(Error::CannotDecanonize);
             }
         };
+        if self.is_pruned(best_block.hash.clone().into()) {
+            error!(target: "db", "Block is pruned and can't be decanonized: {}", best_block.hash.reversed());
+            return Err(Error::PrunedBlock);
+        }
         let block_number = best_block.number;
         let block_hash = best_block.hash.clone();
 
@@ -589,6 +886,7 @@ where
         let mut update = DBTransaction::new();
         update.delete(Key::BlockHash(block_number));
         update.delete(Key::BlockNumber(block_hash.clone()));
+        update.delete(Key::ChainTxCount(block_hash.clone()));
         update.insert(KeyValue::Meta(
             KEY_BEST_BLOCK_HASH,
             serialize(&new_best_block.hash),
@@ -658,6 +956,40 @@ where
             update.insert(KeyValue::TransactionMeta(hash, meta));
         }
 
+        if self.address_index {
+            let bucket = block_number / ADDRESS_INDEX_BUCKET;
+            for (address, _) in block_address_outputs(&block, block_number) {
+                let mut heights = self.address_heights(Key::AddressOutputHeights(address, bucket));
+                heights.retain(|height| *height != block_number);
+                if heights.is_empty() {
+                    update.delete(Key::AddressOutputHeights(address, bucket));
+                } else {
+                    update.insert(KeyValue::AddressOutputHeights(
+                        address,
+                        bucket,
+                        List::from(heights),
+                    ));
+                }
+                update.delete(Key::AddressOutputs(address, block_number));
+            }
+
+            let store = DuplexTransactionOutputProvider::new(&block, self);
+            for (address, _) in block_address_spends(&block, block_number, &store) {
+                let mut heights = self.address_heights(Key::AddressSpendHeights(address, bucket));
+                heights.retain(|height| *height != block_number);
+                if heights.is_empty() {
+                    update.delete(Key::AddressSpendHeights(address, bucket));
+                } else {
+                    update.insert(KeyValue::AddressSpendHeights(
+                        address,
+                        bucket,
+                        List::from(heights),
+                    ));
+                }
+                update.delete(Key::AddressSpends(address, block_number));
+            }
+        }
+
         for tx in block.transactions {
             update.delete(Key::TransactionMeta(tx.hash));
         }
@@ -667,6 +999,61 @@ where
         Ok(block_hash)
     }
 
+    /// Prunes transactions of canonical blocks below given height.
+    ///
+    /// Block is only pruned if all its transactions are fully spent. Headers, hashes and numbers
+    /// of pruned blocks are retained, as well as transactions meta and tree states. Last
+    /// `max_fork_route` blocks are never pruned, because they could be decanonized by reorganization.
+    /// Returns number of pruned blocks.
+    pub fn prune_below(&self, height: u32) -> Result<u32, Error> {
+        let best_block = self.best_block.read();
+        let height = ::std::cmp::min(
+            height,
+            (best_block.number + 1).saturating_sub(self.max_fork_route as u32),
+        );
+        if height == 0 {
+            return Ok(0);
+        }
+
+        let mut update = DBTransaction::new();
+        // prune height is never decreased
+        match self.prune_height() {
+            Some(prune_height) if prune_height >= height => (),
+            _ => update.insert(KeyValue::Meta(KEY_PRUNE_HEIGHT, serialize(&height))),
+        }
+        let mut pruned_blocks = 0;
+        for block_number in 0..height {
+            let block_hash = match self.block_hash(block_number) {
+                Some(block_hash) => block_hash,
+                None => continue,
+            };
+
+            if self.is_pruned(block_hash.into()) {
+                continue;
+            }
+
+            let tx_hashes = self.block_transaction_hashes(block_hash.into());
+            let is_fully_spent = tx_hashes.iter().all(|tx_hash| {
+                self.transaction_meta(tx_hash)
+                    .map(|meta| meta.is_fully_spent())
+                    .unwrap_or(false)
+            });
+            if !is_fully_spent {
+                continue;
+            }
+
+            update.delete(Key::BlockTransactions(block_hash));
+            for tx_hash in tx_hashes {
+                update.delete(Key::Transaction(tx_hash));
+            }
+            pruned_blocks += 1;
+        }
+
+        self.db.write(update).map_err(Error::DatabaseError)?;
+        trace!(target: "db", "pruned {} blocks below {}", pruned_blocks, height);
+        Ok(pruned_blocks)
+    }
+
     fn get(&self, key: Key) -> Option<Value> {
         self.db
             .get(&key)
@@ -674,6 +1061,36 @@ where
             .into_option()
     }
 
+    fn address_heights(&self, key: Key) -> Vec<u32> {
+        self.get(key)
+            .and_then(Value::as_address_heights)
+            .map(List::into)
+            .unwrap_or_default()
+    }
+
+    /// Returns heights of blocks in [start; end] range, which have address index entries.
+    fn address_index_heights<F>(&self, start: u32, end: u32, bucket_key: F) -> Vec<u32>
+    where
+        F: Fn(u32) -> Key,
+    {
+        let end = ::std::cmp::min(end, self.best_block().number);
+        if start > end {
+            return Vec::new();
+        }
+
+        let keys = (start / ADDRESS_INDEX_BUCKET..end / ADDRESS_INDEX_BUCKET + 1)
+            .map(bucket_key)
+            .collect::<Vec<_>>();
+        self.db
+            .get_many(&keys)
+            .expect("db values to be fine")
+            .into_iter()
+            .filter_map(|value| value.into_option().and_then(Value::as_address_heights))
+            .flat_map(List::into)
+            .filter(|height| *height >= start && *height <= end)
+            .collect()
+    }
+
     fn resolve_hash(&self, block_ref: BlockRef) -> Option<H256> {
         match block_ref {
             BlockRef::Number(n) => self.block_hash(n),
@@ -723,6 +1140,20 @@ where
         })
     }
 
+    fn block_and_header_bytes(&self, block_ref: BlockRef) -> Option<(Bytes, Bytes)> {
+        if self.is_pruned(block_ref.clone()) {
+            return None;
+        }
+
+        self.block(block_ref).map(|block| {
+            // serialized block starts with the serialized header
+            let header_size = block.header.raw.serialized_size();
+            let block_bytes = serialize(&block.to_raw_block());
+            let header_bytes = block_bytes[..header_size].into();
+            (block_bytes, header_bytes)
+        })
+    }
+
     fn contains_block(&self, block_ref: BlockRef) -> bool {
         self.resolve_hash(block_ref)
             .and_then(|hash| self.get(Key::BlockHeader(hash)))
@@ -738,15 +1169,47 @@ where
     }
 
     fn block_transactions(&self, block_ref: BlockRef) -> Vec<IndexedTransaction> {
-        self.block_transaction_hashes(block_ref)
+        let hashes = self.block_transaction_hashes(block_ref);
+        let keys = hashes
+            .iter()
+            .cloned()
+            .map(Key::Transaction)
+            .collect::<Vec<_>>();
+        self.db
+            .get_many(&keys)
+            .expect("db values to be fine")
             .into_iter()
-            .filter_map(|hash| {
-                self.get(Key::Transaction(hash))
+            .zip(hashes.into_iter())
+            .filter_map(|(value, hash)| {
+                value
+                    .into_option()
                     .and_then(Value::as_transaction)
                     .map(|tx| IndexedTransaction::new(hash, tx))
             })
             .collect()
     }
+
+    fn chain_tx_count(&self, block_ref: BlockRef) -> Option<u64> {
+        self.resolve_hash(block_ref)
+            .and_then(|hash| self.get(Key::ChainTxCount(hash)))
+            .and_then(Value::as_chain_tx_count)
+    }
+
+    fn is_pruned(&self, block_ref: BlockRef) -> bool {
+        // transactions list is inserted together with the header and only removed by pruning
+        self.resolve_hash(block_ref)
+            .map(|hash| {
+                self.get(Key::BlockHeader(hash)).is_some()
+                    && self.get(Key::BlockTransactions(hash)).is_none()
+            })
+            .unwrap_or(false)
+    }
+
+    fn prune_height(&self) -> Option<u32> {
+        self.get(Key::Meta(KEY_PRUNE_HEIGHT))
+            .and_then(Value::as_meta)
+            .map(|height| deserialize(&*height).expect("Inconsistent DB. Invalid prune height."))
+    }
 }
 
 impl<T> TransactionMetaProvider for BlockChainDatabase<T>
@@ -789,6 +1252,50 @@ where
             .and_then(|tx| tx.raw.outputs.into_iter().nth(prevout.index as usize))
     }
 
+    fn transaction_outputs(&self, prevouts: &[OutPoint]) -> Vec<Option<TransactionOutput>> {
+        // read meta and body of every referenced transaction with a single batch
+        let hashes = prevouts
+            .iter()
+            .map(|prevout| prevout.hash.clone())
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect::<Vec<_>>();
+        let keys = hashes
+            .iter()
+            .cloned()
+            .map(Key::TransactionMeta)
+            .chain(hashes.iter().cloned().map(Key::Transaction))
+            .collect::<Vec<_>>();
+        let mut values = self
+            .db
+            .get_many(&keys)
+            .expect("db values to be fine")
+            .into_iter();
+        let metas = values.by_ref().take(hashes.len()).collect::<Vec<_>>();
+
+        // return previous transaction outputs only for canon chain transactions
+        let transactions = hashes
+            .into_iter()
+            .zip(metas.into_iter().zip(values))
+            .filter_map(|(hash, (meta, tx))| match meta.into_option() {
+                Some(_) => tx
+                    .into_option()
+                    .and_then(Value::as_transaction)
+                    .map(|tx| (hash, tx)),
+                None => None,
+            })
+            .collect::<HashMap<_, _>>();
+
+        prevouts
+            .iter()
+            .map(|prevout| {
+                transactions
+                    .get(&prevout.hash)
+                    .and_then(|tx| tx.outputs.get(prevout.index as usize).cloned())
+            })
+            .collect()
+    }
+
     fn is_spent(&self, prevout: &OutPoint) -> bool {
         self.transaction_meta(&prevout.hash)
             .and_then(|meta| meta.is_spent(prevout.index as usize))
@@ -828,6 +1335,86 @@ where
         self.block_header(BlockRef::Hash(*block_hash))
             .map(|header| header.raw.final_sapling_root)
     }
+
+    fn canon_block_hash(&self, height: u32) -> Option<H256> {
+        self.block_hash(height)
+    }
+}
+
+impl<T> AddressIndexProvider for BlockChainDatabase<T>
+where
+    T: KeyValueDatabase,
+{
+    fn is_address_index_enabled(&self) -> bool {
+        self.address_index
+    }
+
+    fn address_outputs(
+        &self,
+        address: &IndexedAddress,
+        start: u32,
+        end: u32,
+    ) -> Vec<AddressOutput> {
+        if !self.address_index {
+            return Vec::new();
+        }
+
+        let address = *address;
+        let keys = self
+            .address_index_heights(start, end, |bucket| {
+                Key::AddressOutputHeights(address, bucket)
+            })
+            .into_iter()
+            .map(|height| Key::AddressOutputs(address, height))
+            .collect::<Vec<_>>();
+        self.db
+            .get_many(&keys)
+            .expect("db values to be fine")
+            .into_iter()
+            .filter_map(|value| value.into_option().and_then(Value::as_address_outputs))
+            .flat_map(List::into)
+            .collect()
+    }
+
+    fn address_spends(&self, address: &IndexedAddress, start: u32, end: u32) -> Vec<AddressSpend> {
+        if !self.address_index {
+            return Vec::new();
+        }
+
+        let address = *address;
+        let keys = self
+            .address_index_heights(start, end, |bucket| {
+                Key::AddressSpendHeights(address, bucket)
+            })
+            .into_iter()
+            .map(|height| Key::AddressSpends(address, height))
+            .collect::<Vec<_>>();
+        self.db
+            .get_many(&keys)
+            .expect("db values to be fine")
+            .into_iter()
+            .filter_map(|value| value.into_option().and_then(Value::as_address_spends))
+            .flat_map(List::into)
+            .collect()
+    }
+}
+
+impl<T> TransactionIndexProvider for BlockChainDatabase<T>
+where
+    T: KeyValueDatabase,
+{
+    fn is_transaction_index_enabled(&self) -> bool {
+        self.transaction_index
+    }
+
+    fn transaction_block_hash(&self, hash: &H256) -> Option<H256> {
+        if !self.transaction_index {
+            return None;
+        }
+
+        self.get(Key::TransactionBlock(*hash))
+            .and_then(Value::as_transaction_block)
+    }
 }
 
 impl<T> BlockChain for BlockChainDatabase<T>
@@ -853,6 +1440,22 @@ where
     fn block_origin(&self, header: &IndexedBlockHeader) -> Result<BlockOrigin, Error> {
         BlockChainDatabase::block_origin(self, header)
     }
+
+    fn precious_block(&self, block_hash: &H256) -> Result<BlockOrigin, Error> {
+        BlockChainDatabase::precious_block(self, block_hash)
+    }
+
+    fn stored_block_origin(&self, block_hash: &H256) -> Result<BlockOrigin, Error> {
+        BlockChainDatabase::stored_block_origin(self, block_hash)
+    }
+
+    fn rollback_origin(&self, block_hash: &H256) -> Result<SideChainOrigin, Error> {
+        BlockChainDatabase::rollback_origin(self, block_hash)
+    }
+
+    fn flush(&self) -> Result<(), Error> {
+        BlockChainDatabase::flush(self)
+    }
 }
 
 impl<T> Forkable for BlockChainDatabase<T>
@@ -895,4 +1498,90 @@ where
         self.block_header(self.best_block().hash.into())
             .expect("best block header should be in db; qed")
     }
+
+    fn column_stats(&self) -> Vec<ColumnStat> {
+        self.db.column_stats()
+    }
+
+    fn snapshot<'a>(&'a self) -> Box<Store + 'a> {
+        Box::new(BlockChainDatabase::snapshot(self))
+    }
+}
+
+/// Returns transparent address, which given output script is paying to.
+fn output_address(script_pubkey: &Bytes) -> Option<IndexedAddress> {
+    let script: Script = script_pubkey.clone().into();
+    match script.script_type() {
+        ScriptType::PubKey | ScriptType::PubKeyHash | ScriptType::ScriptHash => (),
+        _ => return None,
+    }
+
+    let destination = script.extract_destinations().ok()?.into_iter().next()?;
+    match destination.kind {
+        zebra_keys::Type::P2PKH => Some(IndexedAddress::PubKeyHash(destination.hash)),
+        zebra_keys::Type::P2SH => Some(IndexedAddress::ScriptHash(destination.hash)),
+    }
+}
+
+/// Groups outputs of the block transactions by transparent addresses they're paying to.
+fn block_address_outputs(
+    block: &IndexedBlock,
+    height: u32,
+) -> HashMap<IndexedAddress, Vec<AddressOutput>> {
+    let mut result: HashMap<IndexedAddress, Vec<AddressOutput>> = HashMap::new();
+    for tx in &block.transactions {
+        for (index, output) in tx.raw.outputs.iter().enumerate() {
+            let address = match output_address(&output.script_pubkey) {
+                Some(address) => address,
+                None => continue,
+            };
+
+            let address_output = AddressOutput {
+                outpoint: OutPoint {
+                    hash: tx.hash.clone(),
+                    index: index as u32,
+                },
+                height: height,
+                value: output.value,
+            };
+            result
+                .entry(address)
+                .or_insert_with(Vec::new)
+                .push(address_output);
+        }
+    }
+    result
+}
+
+/// Groups inputs of the block transactions by transparent addresses of outputs they're spending.
+fn block_address_spends(
+    block: &IndexedBlock,
+    height: u32,
+    store: &TransactionOutputProvider,
+) -> HashMap<IndexedAddress, Vec<AddressSpend>> {
+    let mut result: HashMap<IndexedAddress, Vec<AddressSpend>> = HashMap::new();
+    for (tx_index, tx) in block.transactions.iter().enumerate().skip(1) {
+        for input in &tx.raw.inputs {
+            let output = match store.transaction_output(&input.previous_output, tx_index) {
+                Some(output) => output,
+                None => continue,
+            };
+            let address = match output_address(&output.script_pubkey) {
+                Some(address) => address,
+                None => continue,
+            };
+
+            let address_spend = AddressSpend {
+                txid: tx.hash.clone(),
+                height: height,
+                prevout: input.previous_output.clone(),
+                value: output.value,
+            };
+            result
+                .entry(address)
+                .or_insert_with(Vec::new)
+                .push(address_spend);
+        }
+    }
+    result
 }
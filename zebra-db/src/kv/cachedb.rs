@@ -3,6 +3,7 @@ use kv::{Key, KeyState, KeyValue, KeyValueDatabase, Operation, Transaction, Valu
 use lru_cache::LruCache;
 use parking_lot::Mutex;
 use zebra_chain::BlockHeader;
+use zebra_storage::ColumnStat;
 
 pub struct CacheDatabase<T>
 where
@@ -55,4 +56,44 @@ where
         }
         self.db.get(key)
     }
+
+    fn get_many(&self, keys: &[Key]) -> Result<Vec<KeyState<Value>>, String> {
+        // read cached headers first, then read everything else with single call
+        let result = {
+            let mut header = self.header.lock();
+            keys.iter()
+                .map(|key| match *key {
+                    Key::BlockHeader(ref hash) => header
+                        .get_mut(hash)
+                        .map(|state| state.clone().map(Value::BlockHeader)),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+        let missing_keys = keys
+            .iter()
+            .zip(result.iter())
+            .filter(|&(_, state)| state.is_none())
+            .map(|(key, _)| key.clone())
+            .collect::<Vec<_>>();
+        let mut missing_values = self.db.get_many(&missing_keys)?.into_iter();
+        Ok(result
+            .into_iter()
+            .map(|state| {
+                state.unwrap_or_else(|| {
+                    missing_values
+                        .next()
+                        .expect("value is read for every missing key; qed")
+                })
+            })
+            .collect())
+    }
+
+    fn column_stats(&self) -> Vec<ColumnStat> {
+        self.db.column_stats()
+    }
+
+    fn persist(&self) -> Result<(), String> {
+        self.db.persist()
+    }
 }
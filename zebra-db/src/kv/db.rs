@@ -1,7 +1,33 @@
 use kv::{Key, KeyState, Transaction, Value};
+use zebra_storage::ColumnStat;
 
 pub trait KeyValueDatabase: Send + Sync {
     fn write(&self, tx: Transaction) -> Result<(), String>;
 
     fn get(&self, key: &Key) -> Result<KeyState<Value>, String>;
+
+    /// Reads values of all given keys. Values are returned in the same order as keys.
+    fn get_many(&self, keys: &[Key]) -> Result<Vec<KeyState<Value>>, String>;
+
+    /// Returns size statistics of all database columns.
+    fn column_stats(&self) -> Vec<ColumnStat>;
+
+    /// Writes changes, buffered in memory, to the persistent storage.
+    fn persist(&self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+/// Sums statistics of the same columns.
+pub fn merge_column_stats(mut stats: Vec<ColumnStat>, other: Vec<ColumnStat>) -> Vec<ColumnStat> {
+    for other_stat in other {
+        match stats.iter_mut().find(|stat| stat.col == other_stat.col) {
+            Some(stat) => {
+                stat.approx_keys += other_stat.approx_keys;
+                stat.approx_bytes += other_stat.approx_bytes;
+            }
+            None => stats.push(other_stat),
+        }
+    }
+    stats
 }
@@ -11,10 +11,18 @@ use rocksdb::{
 };
 use std::collections::HashMap;
 use std::path::Path;
+use zebra_storage::ColumnStat;
 
 const DB_BACKGROUND_FLUSHES: i32 = 2;
 const DB_BACKGROUND_COMPACTIONS: i32 = 2;
 
+/// Estimated number of keys in the column (including unflushed keys).
+const PROPERTY_ESTIMATE_NUM_KEYS: &str = "rocksdb.estimate-num-keys";
+/// Estimated size of the live data in the column SST files.
+const PROPERTY_ESTIMATE_LIVE_DATA_SIZE: &str = "rocksdb.estimate-live-data-size";
+/// Size of the column memtables.
+const PROPERTY_SIZE_ALL_MEM_TABLES: &str = "rocksdb.size-all-mem-tables";
+
 /// Compaction profile for the database settings
 #[derive(Clone, Copy, PartialEq, Debug)]
 pub struct CompactionProfile {
@@ -134,6 +142,22 @@ impl KeyValueDatabase for Database {
             None => Ok(KeyState::Unknown),
         }
     }
+
+    fn get_many(&self, keys: &[Key]) -> Result<Vec<KeyState<Value>>, String> {
+        let raw_keys = keys.iter().map(Into::into).collect::<Vec<RawKey>>();
+        Database::get_many(self, &raw_keys)?
+            .into_iter()
+            .zip(keys.iter())
+            .map(|(value, key)| match value {
+                Some(value) => Ok(KeyState::Insert(Value::for_key(key, &value)?)),
+                None => Ok(KeyState::Unknown),
+            })
+            .collect()
+    }
+
+    fn column_stats(&self) -> Vec<ColumnStat> {
+        Database::column_stats(self)
+    }
 }
 
 impl Database {
@@ -306,6 +330,44 @@ impl Database {
         }
     }
 
+    /// Get values by keys. Values are returned in the same order as keys.
+    pub fn get_many(&self, keys: &[RawKey]) -> Result<Vec<Option<Bytes>>, String> {
+        let DBAndColumns { ref db, ref cfs } = self.db;
+        keys.iter()
+            .map(|key| {
+                let value = match key.location {
+                    Location::DB => db.get_opt(&key.key, &self.read_opts)?,
+                    Location::Column(col) => {
+                        db.get_cf_opt(cfs[col as usize], &key.key, &self.read_opts)?
+                    }
+                };
+                Ok(value.map(|v| (&*v).into()))
+            })
+            .collect()
+    }
+
+    /// Get size statistics of all columns.
+    ///
+    /// Statistics are RocksDB estimates, so they're cheap to compute, but could be inaccurate.
+    pub fn column_stats(&self) -> Vec<ColumnStat> {
+        (0..self.db.cfs.len() as u32)
+            .map(|col| ColumnStat {
+                col: col,
+                approx_keys: self.column_property(col, PROPERTY_ESTIMATE_NUM_KEYS),
+                approx_bytes: self.column_property(col, PROPERTY_ESTIMATE_LIVE_DATA_SIZE)
+                    + self.column_property(col, PROPERTY_SIZE_ALL_MEM_TABLES),
+            })
+            .collect()
+    }
+
+    /// Get integer property of the column. Returns 0 if property is unknown.
+    fn column_property(&self, col: u32, name: &str) -> u64 {
+        let DBAndColumns { ref db, ref cfs } = self.db;
+        db.property_value_cf(cfs[col as usize], name)
+            .and_then(|value| value.parse().ok())
+            .unwrap_or_default()
+    }
+
     /// Close the database
     pub fn close(self) {}
 
@@ -382,6 +444,22 @@ mod tests {
                 .unwrap(),
             b"elephant"
         );
+
+        let values = db
+            .get_many(&[
+                RawKey::new(Location::DB, key1 as &[u8]),
+                RawKey::new(Location::DB, key2 as &[u8]),
+                RawKey::new(Location::DB, key3 as &[u8]),
+            ])
+            .unwrap();
+        assert_eq!(
+            values,
+            vec![
+                None,
+                Some(Bytes::from(&b"dog"[..])),
+                Some(Bytes::from(&b"elephant"[..])),
+            ]
+        );
     }
 
     #[test]
@@ -390,4 +468,23 @@ mod tests {
         let _ = Database::open_default(tempdir.path()).unwrap();
         test_db(DatabaseConfig::default());
     }
+
+    #[test]
+    fn column_stats() {
+        let tempdir = TempDir::new("").unwrap();
+        let db = Database::open(DatabaseConfig::with_columns(Some(2)), tempdir.path()).unwrap();
+
+        let mut batch = RawTransaction::default();
+        batch.insert_raw(Location::Column(1), b"key1", b"cat");
+        batch.insert_raw(Location::Column(1), b"key2", b"elephant");
+        db.write(batch).unwrap();
+
+        let stats = db.column_stats();
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].col, 0);
+        assert_eq!(stats[0].approx_keys, 0);
+        assert_eq!(stats[1].col, 1);
+        assert_eq!(stats[1].approx_keys, 2);
+        assert!(stats[1].approx_bytes >= 19);
+    }
 }
@@ -1,13 +1,23 @@
 use bytes::Bytes;
 use hash::H256;
-use kv::{Key, KeyState, KeyValue, KeyValueDatabase, Operation, Transaction, Value};
+use kv::{
+    Key, KeyState, KeyValue, KeyValueDatabase, Operation, Transaction, Value, COL_ADDRESS_OUTPUTS,
+    COL_ADDRESS_OUTPUT_HEIGHTS, COL_ADDRESS_SPENDS, COL_ADDRESS_SPEND_HEIGHTS, COL_BLOCK_HASHES,
+    COL_BLOCK_HEADERS, COL_BLOCK_NUMBERS, COL_BLOCK_TRANSACTIONS, COL_CHAIN_TX_COUNTS,
+    COL_CONFIGURATION, COL_COUNT, COL_META, COL_SAPLING_NULLIFIERS, COL_SPROUT_BLOCK_ROOTS,
+    COL_SPROUT_NULLIFIERS, COL_TRANSACTIONS, COL_TRANSACTIONS_META, COL_TRANSACTION_BLOCKS,
+    COL_TREE_STATES,
+};
 use parking_lot::RwLock;
 use ser::List;
 use std::collections::HashMap;
 use std::mem::replace;
 use std::sync::Arc;
 use zebra_chain::{BlockHeader, Transaction as ChainTransaction};
-use zebra_storage::{EpochRef, EpochTag, SaplingTreeState, SproutTreeState, TransactionMeta};
+use zebra_storage::{
+    AddressOutput, AddressSpend, ColumnStat, EpochRef, EpochTag, IndexedAddress, SaplingTreeState,
+    SproutTreeState, TransactionMeta,
+};
 
 #[derive(Default, Debug)]
 struct InnerDatabase {
@@ -24,6 +34,12 @@ struct InnerDatabase {
     sapling_nullifiers: HashMap<H256, KeyState<()>>,
     sprout_tree_state: HashMap<H256, KeyState<SproutTreeState>>,
     sapling_tree_state: HashMap<H256, KeyState<SaplingTreeState>>,
+    address_outputs: HashMap<(IndexedAddress, u32), KeyState<List<AddressOutput>>>,
+    address_spends: HashMap<(IndexedAddress, u32), KeyState<List<AddressSpend>>>,
+    address_output_heights: HashMap<(IndexedAddress, u32), KeyState<List<u32>>>,
+    address_spend_heights: HashMap<(IndexedAddress, u32), KeyState<List<u32>>>,
+    transaction_block: HashMap<H256, KeyState<H256>>,
+    chain_tx_count: HashMap<H256, KeyState<u64>>,
 }
 
 #[derive(Default, Debug)]
@@ -122,6 +138,58 @@ impl MemoryDatabase {
                 })
             });
 
+        let address_outputs = replace(&mut db.address_outputs, HashMap::default())
+            .into_iter()
+            .flat_map(|(key, state)| {
+                state.into_operation(
+                    key,
+                    |(a, h), v| KeyValue::AddressOutputs(a, h, v),
+                    |(a, h)| Key::AddressOutputs(a, h),
+                )
+            });
+
+        let address_spends = replace(&mut db.address_spends, HashMap::default())
+            .into_iter()
+            .flat_map(|(key, state)| {
+                state.into_operation(
+                    key,
+                    |(a, h), v| KeyValue::AddressSpends(a, h, v),
+                    |(a, h)| Key::AddressSpends(a, h),
+                )
+            });
+
+        let address_output_heights = replace(&mut db.address_output_heights, HashMap::default())
+            .into_iter()
+            .flat_map(|(key, state)| {
+                state.into_operation(
+                    key,
+                    |(a, b), v| KeyValue::AddressOutputHeights(a, b, v),
+                    |(a, b)| Key::AddressOutputHeights(a, b),
+                )
+            });
+
+        let address_spend_heights = replace(&mut db.address_spend_heights, HashMap::default())
+            .into_iter()
+            .flat_map(|(key, state)| {
+                state.into_operation(
+                    key,
+                    |(a, b), v| KeyValue::AddressSpendHeights(a, b, v),
+                    |(a, b)| Key::AddressSpendHeights(a, b),
+                )
+            });
+
+        let transaction_block = replace(&mut db.transaction_block, HashMap::default())
+            .into_iter()
+            .flat_map(|(key, state)| {
+                state.into_operation(key, KeyValue::TransactionBlock, Key::TransactionBlock)
+            });
+
+        let chain_tx_count = replace(&mut db.chain_tx_count, HashMap::default())
+            .into_iter()
+            .flat_map(|(key, state)| {
+                state.into_operation(key, KeyValue::ChainTxCount, Key::ChainTxCount)
+            });
+
         Transaction {
             operations: meta
                 .chain(block_hash)
@@ -136,6 +204,12 @@ impl MemoryDatabase {
                 .chain(sprout_block_root)
                 .chain(sprout_nullifiers)
                 .chain(sapling_nullifiers)
+                .chain(address_outputs)
+                .chain(address_spends)
+                .chain(address_output_heights)
+                .chain(address_spend_heights)
+                .chain(transaction_block)
+                .chain(chain_tx_count)
                 .collect(),
         }
     }
@@ -190,6 +264,28 @@ impl KeyValueDatabase for MemoryDatabase {
                     KeyValue::SproutBlockRoot(key, value) => {
                         db.sprout_block_root.insert(key, KeyState::Insert(value));
                     }
+                    KeyValue::AddressOutputs(key, height, value) => {
+                        db.address_outputs
+                            .insert((key, height), KeyState::Insert(value));
+                    }
+                    KeyValue::AddressSpends(key, height, value) => {
+                        db.address_spends
+                            .insert((key, height), KeyState::Insert(value));
+                    }
+                    KeyValue::AddressOutputHeights(key, bucket, value) => {
+                        db.address_output_heights
+                            .insert((key, bucket), KeyState::Insert(value));
+                    }
+                    KeyValue::AddressSpendHeights(key, bucket, value) => {
+                        db.address_spend_heights
+                            .insert((key, bucket), KeyState::Insert(value));
+                    }
+                    KeyValue::TransactionBlock(key, value) => {
+                        db.transaction_block.insert(key, KeyState::Insert(value));
+                    }
+                    KeyValue::ChainTxCount(key, value) => {
+                        db.chain_tx_count.insert(key, KeyState::Insert(value));
+                    }
                 },
                 Operation::Delete(delete) => match delete {
                     Key::Meta(key) => {
@@ -235,6 +331,26 @@ impl KeyValueDatabase for MemoryDatabase {
                     Key::SproutBlockRoot(key) => {
                         db.sprout_block_root.insert(key, KeyState::Delete);
                     }
+                    Key::AddressOutputs(key, height) => {
+                        db.address_outputs.insert((key, height), KeyState::Delete);
+                    }
+                    Key::AddressSpends(key, height) => {
+                        db.address_spends.insert((key, height), KeyState::Delete);
+                    }
+                    Key::AddressOutputHeights(key, bucket) => {
+                        db.address_output_heights
+                            .insert((key, bucket), KeyState::Delete);
+                    }
+                    Key::AddressSpendHeights(key, bucket) => {
+                        db.address_spend_heights
+                            .insert((key, bucket), KeyState::Delete);
+                    }
+                    Key::TransactionBlock(key) => {
+                        db.transaction_block.insert(key, KeyState::Delete);
+                    }
+                    Key::ChainTxCount(key) => {
+                        db.chain_tx_count.insert(key, KeyState::Delete);
+                    }
                 },
             }
         }
@@ -326,10 +442,93 @@ impl KeyValueDatabase for MemoryDatabase {
                 .cloned()
                 .unwrap_or_default()
                 .map(Value::SproutTreeRoot),
+            Key::AddressOutputs(key, height) => db
+                .address_outputs
+                .get(&(key, height))
+                .cloned()
+                .unwrap_or_default()
+                .map(Value::AddressOutputs),
+            Key::AddressSpends(key, height) => db
+                .address_spends
+                .get(&(key, height))
+                .cloned()
+                .unwrap_or_default()
+                .map(Value::AddressSpends),
+            Key::AddressOutputHeights(key, bucket) => db
+                .address_output_heights
+                .get(&(key, bucket))
+                .cloned()
+                .unwrap_or_default()
+                .map(Value::AddressHeights),
+            Key::AddressSpendHeights(key, bucket) => db
+                .address_spend_heights
+                .get(&(key, bucket))
+                .cloned()
+                .unwrap_or_default()
+                .map(Value::AddressHeights),
+            Key::TransactionBlock(ref key) => db
+                .transaction_block
+                .get(key)
+                .cloned()
+                .unwrap_or_default()
+                .map(Value::TransactionBlock),
+            Key::ChainTxCount(ref key) => db
+                .chain_tx_count
+                .get(key)
+                .cloned()
+                .unwrap_or_default()
+                .map(Value::ChainTxCount),
         };
 
         Ok(result)
     }
+
+    fn get_many(&self, keys: &[Key]) -> Result<Vec<KeyState<Value>>, String> {
+        keys.iter().map(|key| self.get(key)).collect()
+    }
+
+    /// Only number of keys is known, sizes of keys and values are not tracked.
+    fn column_stats(&self) -> Vec<ColumnStat> {
+        let db = self.db.read();
+        let mut stats: Vec<_> = (0..COL_COUNT)
+            .map(|col| ColumnStat {
+                col: col,
+                ..Default::default()
+            })
+            .collect();
+        stats[COL_META as usize].approx_keys = inserted_keys(&db.meta);
+        stats[COL_BLOCK_HASHES as usize].approx_keys = inserted_keys(&db.block_hash);
+        stats[COL_BLOCK_HEADERS as usize].approx_keys = inserted_keys(&db.block_header);
+        stats[COL_BLOCK_TRANSACTIONS as usize].approx_keys = inserted_keys(&db.block_transactions);
+        stats[COL_TRANSACTIONS as usize].approx_keys = inserted_keys(&db.transaction);
+        stats[COL_TRANSACTIONS_META as usize].approx_keys = inserted_keys(&db.transaction_meta);
+        stats[COL_BLOCK_NUMBERS as usize].approx_keys = inserted_keys(&db.block_number);
+        stats[COL_SPROUT_NULLIFIERS as usize].approx_keys = inserted_keys(&db.sprout_nullifiers);
+        stats[COL_SAPLING_NULLIFIERS as usize].approx_keys = inserted_keys(&db.sapling_nullifiers);
+        stats[COL_SPROUT_BLOCK_ROOTS as usize].approx_keys = inserted_keys(&db.sprout_block_root);
+        stats[COL_TREE_STATES as usize].approx_keys =
+            inserted_keys(&db.sprout_tree_state) + inserted_keys(&db.sapling_tree_state);
+        stats[COL_CONFIGURATION as usize].approx_keys = inserted_keys(&db.configuration);
+        stats[COL_ADDRESS_OUTPUTS as usize].approx_keys = inserted_keys(&db.address_outputs);
+        stats[COL_ADDRESS_SPENDS as usize].approx_keys = inserted_keys(&db.address_spends);
+        stats[COL_ADDRESS_OUTPUT_HEIGHTS as usize].approx_keys =
+            inserted_keys(&db.address_output_heights);
+        stats[COL_ADDRESS_SPEND_HEIGHTS as usize].approx_keys =
+            inserted_keys(&db.address_spend_heights);
+        stats[COL_TRANSACTION_BLOCKS as usize].approx_keys = inserted_keys(&db.transaction_block);
+        stats[COL_CHAIN_TX_COUNTS as usize].approx_keys = inserted_keys(&db.chain_tx_count);
+        stats
+    }
+}
+
+/// Number of keys, inserted into the map.
+fn inserted_keys<K, V>(map: &HashMap<K, KeyState<V>>) -> u64 {
+    map.values()
+        .filter(|state| match **state {
+            KeyState::Insert(_) => true,
+            _ => false,
+        })
+        .count() as u64
 }
 
 #[derive(Debug)]
@@ -359,4 +558,12 @@ impl KeyValueDatabase for SharedMemoryDatabase {
     fn get(&self, key: &Key) -> Result<KeyState<Value>, String> {
         self.db.get(key)
     }
+
+    fn get_many(&self, keys: &[Key]) -> Result<Vec<KeyState<Value>>, String> {
+        self.db.get_many(keys)
+    }
+
+    fn column_stats(&self) -> Vec<ColumnStat> {
+        self.db.column_stats()
+    }
 }
@@ -3,16 +3,22 @@ mod db;
 mod diskdb;
 mod memorydb;
 mod overlaydb;
+mod snapshotdb;
 mod transaction;
 
 pub use self::cachedb::CacheDatabase;
-pub use self::db::KeyValueDatabase;
+pub use self::db::{merge_column_stats, KeyValueDatabase};
 pub use self::diskdb::{CompactionProfile, Database as DiskDatabase, DatabaseConfig};
 pub use self::memorydb::{MemoryDatabase, SharedMemoryDatabase};
-pub use self::overlaydb::{AutoFlushingOverlayDatabase, OverlayDatabase};
+pub use self::overlaydb::{
+    AutoFlushingOverlayDatabase, OverlayDatabase, AUTO_FLUSH_MAX_BYTES, AUTO_FLUSH_MAX_OPERATIONS,
+};
+pub use self::snapshotdb::{DatabaseSnapshot, SnapshotDatabase};
 pub use self::transaction::{
     Key, KeyState, KeyValue, Location, Operation, RawKey, RawKeyValue, RawOperation,
-    RawTransaction, Transaction, Value, COL_BLOCK_HASHES, COL_BLOCK_HEADERS, COL_BLOCK_NUMBERS,
-    COL_BLOCK_TRANSACTIONS, COL_COUNT, COL_META, COL_SAPLING_NULLIFIERS, COL_SPROUT_BLOCK_ROOTS,
-    COL_SPROUT_NULLIFIERS, COL_TRANSACTIONS, COL_TRANSACTIONS_META, COL_TREE_STATES,
+    RawTransaction, Transaction, Value, COL_ADDRESS_OUTPUTS, COL_ADDRESS_OUTPUT_HEIGHTS,
+    COL_ADDRESS_SPENDS, COL_ADDRESS_SPEND_HEIGHTS, COL_BLOCK_HASHES, COL_BLOCK_HEADERS,
+    COL_BLOCK_NUMBERS, COL_BLOCK_TRANSACTIONS, COL_CHAIN_TX_COUNTS, COL_CONFIGURATION, COL_COUNT,
+    COL_META, COL_SAPLING_NULLIFIERS, COL_SPROUT_BLOCK_ROOTS, COL_SPROUT_NULLIFIERS,
+    COL_TRANSACTIONS, COL_TRANSACTIONS_META, COL_TRANSACTION_BLOCKS, COL_TREE_STATES,
 };
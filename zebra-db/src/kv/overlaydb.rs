@@ -1,5 +1,6 @@
-use kv::{Key, KeyState, KeyValueDatabase, MemoryDatabase, Transaction, Value};
+use kv::{merge_column_stats, Key, KeyState, KeyValueDatabase, MemoryDatabase, Transaction, Value};
 use parking_lot::Mutex;
+use zebra_storage::ColumnStat;
 
 pub struct OverlayDatabase<'a, T>
 where
@@ -39,28 +40,55 @@ where
             exists => Ok(exists),
         }
     }
+    fn get_many(&self, keys: &[Key]) -> Result<Vec<KeyState<Value>>, String> {
+        get_many_with_overlay(&self.overlay, self.db, keys)
+    }
+
+    /// Overlay values are counted as new keys, so statistics are approximate.
+    fn column_stats(&self) -> Vec<ColumnStat> {
+        merge_column_stats(self.db.column_stats(), self.overlay.column_stats())
+    }
 }
 
+/// Default number of writes after which `AutoFlushingOverlayDatabase` is flushed
+pub const AUTO_FLUSH_MAX_OPERATIONS: usize = 1024;
+/// Default estimated size of pending writes (in bytes) after which `AutoFlushingOverlayDatabase` is flushed
+pub const AUTO_FLUSH_MAX_BYTES: usize = 32 * 1024 * 1024;
+
+/// Overlay database, which is flushed to the underlying database when either
+/// number of writes or estimated size of pending writes reaches the threshold.
+/// All pending writes are flushed with single database transaction.
 pub struct AutoFlushingOverlayDatabase<T>
 where
     T: KeyValueDatabase,
 {
     db: T,
     overlay: MemoryDatabase,
-    operations: Mutex<usize>,
+    pending: Mutex<PendingWrites>,
     max_operations: usize,
+    max_bytes: usize,
+}
+
+/// Writes, not yet flushed to the underlying database
+#[derive(Default)]
+struct PendingWrites {
+    /// Number of writes
+    operations: usize,
+    /// Estimated size of writes
+    bytes: usize,
 }
 
 impl<T> AutoFlushingOverlayDatabase<T>
 where
     T: KeyValueDatabase,
 {
-    pub fn new(db: T, max_operations: usize) -> Self {
+    pub fn new(db: T, max_operations: usize, max_bytes: usize) -> Self {
         AutoFlushingOverlayDatabase {
             db: db,
             overlay: MemoryDatabase::default(),
-            operations: Mutex::default(),
+            pending: Mutex::default(),
             max_operations: max_operations,
+            max_bytes: max_bytes,
         }
     }
 
@@ -74,12 +102,13 @@ where
     T: KeyValueDatabase,
 {
     fn write(&self, tx: Transaction) -> Result<(), String> {
-        let mut operations = self.operations.lock();
-        *operations += 1;
+        let mut pending = self.pending.lock();
+        pending.operations += 1;
+        pending.bytes += tx.estimated_size();
         self.overlay.write(tx)?;
-        if *operations == self.max_operations {
+        if pending.operations >= self.max_operations || pending.bytes >= self.max_bytes {
             self.flush()?;
-            *operations = 0;
+            *pending = PendingWrites::default();
         }
         Ok(())
     }
@@ -90,6 +119,22 @@ where
             exists => Ok(exists),
         }
     }
+    fn get_many(&self, keys: &[Key]) -> Result<Vec<KeyState<Value>>, String> {
+        get_many_with_overlay(&self.overlay, &self.db, keys)
+    }
+
+    /// Overlay values are counted as new keys, so statistics are approximate.
+    fn column_stats(&self) -> Vec<ColumnStat> {
+        merge_column_stats(self.db.column_stats(), self.overlay.column_stats())
+    }
+
+    fn persist(&self) -> Result<(), String> {
+        // lock is held until the flush is completed, so that concurrent writes aren't lost
+        let mut pending = self.pending.lock();
+        self.flush()?;
+        *pending = PendingWrites::default();
+        self.db.persist()
+    }
 }
 
 impl<T> Drop for AutoFlushingOverlayDatabase<T>
@@ -100,3 +145,152 @@ where
         self.flush().expect("Failed to save database");
     }
 }
+
+/// Reads values from the overlay. Values, unknown to the overlay, are read from the database with single call.
+fn get_many_with_overlay<T>(
+    overlay: &MemoryDatabase,
+    db: &T,
+    keys: &[Key],
+) -> Result<Vec<KeyState<Value>>, String>
+where
+    T: KeyValueDatabase,
+{
+    let overlay_values = overlay.get_many(keys)?;
+    let unknown_keys = keys
+        .iter()
+        .zip(overlay_values.iter())
+        .filter(|&(_, state)| match *state {
+            KeyState::Unknown => true,
+            _ => false,
+        })
+        .map(|(key, _)| key.clone())
+        .collect::<Vec<_>>();
+    if unknown_keys.is_empty() {
+        return Ok(overlay_values);
+    }
+
+    let mut db_values = db.get_many(&unknown_keys)?.into_iter();
+    Ok(overlay_values
+        .into_iter()
+        .map(|state| match state {
+            KeyState::Unknown => db_values
+                .next()
+                .expect("value is read for every unknown key; qed"),
+            exists => exists,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AutoFlushingOverlayDatabase, OverlayDatabase};
+    use kv::{
+        Key, KeyState, KeyValue, KeyValueDatabase, MemoryDatabase, SharedMemoryDatabase,
+        Transaction, Value,
+    };
+
+    fn insert_block_number(db: &KeyValueDatabase, number: u32) {
+        let mut update = Transaction::new();
+        update.insert(KeyValue::BlockNumber(number.into(), number));
+        db.write(update).unwrap();
+    }
+
+    fn is_written(db: &KeyValueDatabase, number: u32) -> bool {
+        match db.get(&Key::BlockNumber(number.into())).unwrap() {
+            KeyState::Insert(_) => true,
+            _ => false,
+        }
+    }
+
+    #[test]
+    fn auto_flushing_overlay_flushes_by_operations() {
+        let db = SharedMemoryDatabase::default();
+        let overlay = AutoFlushingOverlayDatabase::new(db.clone(), 2, usize::max_value());
+
+        insert_block_number(&overlay, 1);
+        assert!(is_written(&overlay, 1));
+        assert!(!is_written(&db, 1));
+
+        insert_block_number(&overlay, 2);
+        assert!(is_written(&db, 1));
+        assert!(is_written(&db, 2));
+    }
+
+    #[test]
+    fn auto_flushing_overlay_flushes_by_bytes() {
+        let db = SharedMemoryDatabase::default();
+        // every block number insertion is estimated as 36 bytes
+        let overlay = AutoFlushingOverlayDatabase::new(db.clone(), usize::max_value(), 100);
+
+        insert_block_number(&overlay, 1);
+        insert_block_number(&overlay, 2);
+        assert!(!is_written(&db, 1));
+
+        insert_block_number(&overlay, 3);
+        assert!(is_written(&db, 1));
+        assert!(is_written(&db, 3));
+
+        insert_block_number(&overlay, 4);
+        assert!(!is_written(&db, 4));
+    }
+
+    #[test]
+    fn auto_flushing_overlay_flushes_on_drop() {
+        let db = SharedMemoryDatabase::default();
+        {
+            let overlay = AutoFlushingOverlayDatabase::new(db.clone(), 100, 100_000);
+            insert_block_number(&overlay, 1);
+            assert!(!is_written(&db, 1));
+        }
+        assert!(is_written(&db, 1));
+    }
+
+    #[test]
+    fn auto_flushing_overlay_persists_pending_writes() {
+        let db = SharedMemoryDatabase::default();
+        let overlay = AutoFlushingOverlayDatabase::new(db.clone(), 2, 100_000);
+        insert_block_number(&overlay, 1);
+        assert!(!is_written(&db, 1));
+        overlay.persist().unwrap();
+        assert!(is_written(&db, 1));
+
+        // pending writes counter is reset by persist
+        insert_block_number(&overlay, 2);
+        assert!(!is_written(&db, 2));
+        insert_block_number(&overlay, 3);
+        assert!(is_written(&db, 3));
+    }
+
+    #[test]
+    fn overlay_get_many() {
+        let db = MemoryDatabase::default();
+        let mut update = Transaction::new();
+        update.insert(KeyValue::BlockNumber(1.into(), 1));
+        update.insert(KeyValue::BlockNumber(2.into(), 2));
+        db.write(update).unwrap();
+
+        let overlay = OverlayDatabase::new(&db);
+        let mut update = Transaction::new();
+        update.delete(Key::BlockNumber(1.into()));
+        update.insert(KeyValue::BlockNumber(3.into(), 3));
+        overlay.write(update).unwrap();
+
+        let values = overlay
+            .get_many(&[
+                Key::BlockNumber(1.into()),
+                Key::BlockNumber(2.into()),
+                Key::BlockNumber(3.into()),
+                Key::BlockNumber(4.into()),
+            ])
+            .unwrap()
+            .into_iter()
+            .map(|state| match state {
+                KeyState::Insert(Value::BlockNumber(number)) => Some(Some(number)),
+                KeyState::Delete => Some(None),
+                KeyState::Unknown => None,
+                state => panic!("unexpected value: {:?}", state),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(values, vec![Some(None), Some(Some(2)), Some(Some(3)), None]);
+    }
+}
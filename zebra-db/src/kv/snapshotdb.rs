@@ -0,0 +1,207 @@
+use kv::{Key, KeyState, KeyValue, KeyValueDatabase, MemoryDatabase, Transaction, Value};
+use parking_lot::Mutex;
+use std::sync::{Arc, Weak};
+use zebra_storage::ColumnStat;
+
+/// Database, which is able to create read-only snapshots of its state.
+///
+/// Snapshots are copy-on-write: while snapshot is alive, every write first copies original
+/// values of all modified keys to the snapshot. So creating a snapshot is cheap and it never
+/// blocks writes, but writes are slowed down by additional reads and original values are kept
+/// in memory until snapshot is dropped. Snapshots should only be held for the duration of
+/// a single request.
+pub struct SnapshotDatabase<T>
+where
+    T: KeyValueDatabase,
+{
+    db: T,
+    /// Original values of all alive snapshots.
+    snapshots: Mutex<Vec<Weak<MemoryDatabase>>>,
+}
+
+/// Read-only view of the database state at the moment of snapshot creation.
+pub struct DatabaseSnapshot<'a, T>
+where
+    T: 'a + KeyValueDatabase,
+{
+    db: &'a T,
+    /// Values of keys, modified after snapshot has been created.
+    original: Arc<MemoryDatabase>,
+}
+
+impl<T> SnapshotDatabase<T>
+where
+    T: KeyValueDatabase,
+{
+    pub fn new(db: T) -> Self {
+        SnapshotDatabase {
+            db: db,
+            snapshots: Mutex::default(),
+        }
+    }
+
+    /// Returns reference to the underlying database.
+    pub fn inner(&self) -> &T {
+        &self.db
+    }
+
+    /// Creates snapshot of the current database state.
+    pub fn snapshot(&self) -> DatabaseSnapshot<T> {
+        let original = Arc::new(MemoryDatabase::default());
+        self.snapshots.lock().push(Arc::downgrade(&original));
+        DatabaseSnapshot {
+            db: &self.db,
+            original: original,
+        }
+    }
+}
+
+impl<T> KeyValueDatabase for SnapshotDatabase<T>
+where
+    T: KeyValueDatabase,
+{
+    fn write(&self, tx: Transaction) -> Result<(), String> {
+        // lock is held until the write is completed, so snapshot is never created mid-write
+        let mut snapshots = self.snapshots.lock();
+        let alive_snapshots = snapshots
+            .iter()
+            .filter_map(Weak::upgrade)
+            .collect::<Vec<_>>();
+        *snapshots = alive_snapshots.iter().map(Arc::downgrade).collect();
+
+        if !alive_snapshots.is_empty() {
+            let keys = tx.operations.iter().map(|op| op.key()).collect::<Vec<_>>();
+            let values = self.db.get_many(&keys)?;
+            for original in &alive_snapshots {
+                let mut update = Transaction::new();
+                let known_values = original.get_many(&keys)?;
+                for ((key, value), known_value) in keys.iter().zip(values.iter()).zip(known_values)
+                {
+                    // only the first modification after snapshot creation is copied
+                    if let KeyState::Unknown = known_value {
+                        match value.clone().into_option() {
+                            Some(value) => update.insert(
+                                KeyValue::from_key_value(key.clone(), value)
+                                    .expect("value is read using the same key; qed"),
+                            ),
+                            None => update.delete(key.clone()),
+                        }
+                    }
+                }
+                original.write(update)?;
+            }
+        }
+
+        self.db.write(tx)
+    }
+
+    fn get(&self, key: &Key) -> Result<KeyState<Value>, String> {
+        self.db.get(key)
+    }
+
+    fn get_many(&self, keys: &[Key]) -> Result<Vec<KeyState<Value>>, String> {
+        self.db.get_many(keys)
+    }
+
+    fn column_stats(&self) -> Vec<ColumnStat> {
+        self.db.column_stats()
+    }
+
+    fn persist(&self) -> Result<(), String> {
+        self.db.persist()
+    }
+}
+
+impl<'a, T> KeyValueDatabase for DatabaseSnapshot<'a, T>
+where
+    T: 'a + KeyValueDatabase,
+{
+    fn write(&self, _tx: Transaction) -> Result<(), String> {
+        Err("Database snapshot is read-only".into())
+    }
+
+    fn get(&self, key: &Key) -> Result<KeyState<Value>, String> {
+        // original value is copied before database is modified => database must be read first
+        let value = self.db.get(key)?;
+        match self.original.get(key)? {
+            KeyState::Unknown => Ok(value),
+            original => Ok(original),
+        }
+    }
+
+    fn get_many(&self, keys: &[Key]) -> Result<Vec<KeyState<Value>>, String> {
+        let values = self.db.get_many(keys)?;
+        let originals = self.original.get_many(keys)?;
+        Ok(values
+            .into_iter()
+            .zip(originals)
+            .map(|(value, original)| match original {
+                KeyState::Unknown => value,
+                original => original,
+            })
+            .collect())
+    }
+
+    /// Statistics of the underlying database are returned.
+    fn column_stats(&self) -> Vec<ColumnStat> {
+        self.db.column_stats()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SnapshotDatabase;
+    use kv::{Key, KeyState, KeyValue, KeyValueDatabase, MemoryDatabase, Transaction, Value};
+
+    fn block_number(db: &KeyValueDatabase, hash: u32) -> Option<u32> {
+        match db.get(&Key::BlockNumber(hash.into())).unwrap() {
+            KeyState::Insert(Value::BlockNumber(number)) => Some(number),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn snapshot_is_not_affected_by_writes() {
+        let db = SnapshotDatabase::new(MemoryDatabase::default());
+        let mut update = Transaction::new();
+        update.insert(KeyValue::BlockNumber(1.into(), 1));
+        update.insert(KeyValue::BlockNumber(2.into(), 2));
+        db.write(update).unwrap();
+
+        let snapshot = db.snapshot();
+        let mut update = Transaction::new();
+        update.delete(Key::BlockNumber(1.into()));
+        update.insert(KeyValue::BlockNumber(2.into(), 20));
+        update.insert(KeyValue::BlockNumber(3.into(), 3));
+        db.write(update).unwrap();
+        let mut update = Transaction::new();
+        update.insert(KeyValue::BlockNumber(2.into(), 200));
+        db.write(update).unwrap();
+
+        assert_eq!(block_number(&db, 1), None);
+        assert_eq!(block_number(&db, 2), Some(200));
+        assert_eq!(block_number(&db, 3), Some(3));
+        assert_eq!(block_number(&snapshot, 1), Some(1));
+        assert_eq!(block_number(&snapshot, 2), Some(2));
+        assert_eq!(block_number(&snapshot, 3), None);
+        assert!(snapshot.write(Transaction::new()).is_err());
+    }
+
+    #[test]
+    fn dropped_snapshots_are_forgotten() {
+        let db = SnapshotDatabase::new(MemoryDatabase::default());
+        drop(db.snapshot());
+        let snapshot = db.snapshot();
+
+        let mut update = Transaction::new();
+        update.insert(KeyValue::BlockNumber(1.into(), 1));
+        db.write(update).unwrap();
+
+        assert_eq!(db.snapshots.lock().len(), 1);
+        assert_eq!(block_number(&snapshot, 1), None);
+        drop(snapshot);
+
+        db.write(Transaction::new()).unwrap();
+        assert!(db.snapshots.lock().is_empty());
+    }
+}
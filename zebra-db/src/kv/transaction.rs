@@ -1,10 +1,13 @@
 use bytes::Bytes;
 use hash::H256;
-use ser::{deserialize, serialize, List};
+use ser::{deserialize, serialize, List, Serializable, Stream};
 use zebra_chain::{BlockHeader, Transaction as ChainTransaction};
-use zebra_storage::{EpochRef, EpochTag, SaplingTreeState, SproutTreeState, TransactionMeta};
+use zebra_storage::{
+    AddressOutput, AddressSpend, EpochRef, EpochTag, IndexedAddress, SaplingTreeState,
+    SproutTreeState, TransactionMeta,
+};
 
-pub const COL_COUNT: u32 = 16;
+pub const COL_COUNT: u32 = 18;
 pub const COL_META: u32 = 0;
 pub const COL_BLOCK_HASHES: u32 = 1;
 pub const COL_BLOCK_HEADERS: u32 = 2;
@@ -17,6 +20,12 @@ pub const COL_SAPLING_NULLIFIERS: u32 = 8;
 pub const COL_SPROUT_BLOCK_ROOTS: u32 = 9;
 pub const COL_TREE_STATES: u32 = 10;
 pub const COL_CONFIGURATION: u32 = 11;
+pub const COL_ADDRESS_OUTPUTS: u32 = 12;
+pub const COL_ADDRESS_SPENDS: u32 = 13;
+pub const COL_TRANSACTION_BLOCKS: u32 = 14;
+pub const COL_CHAIN_TX_COUNTS: u32 = 15;
+pub const COL_ADDRESS_OUTPUT_HEIGHTS: u32 = 16;
+pub const COL_ADDRESS_SPEND_HEIGHTS: u32 = 17;
 
 #[derive(Debug)]
 pub enum Operation {
@@ -24,6 +33,24 @@ pub enum Operation {
     Delete(Key),
 }
 
+impl Operation {
+    /// Estimated number of bytes, written to the database by this operation.
+    pub fn estimated_size(&self) -> usize {
+        match *self {
+            Operation::Insert(ref insert) => insert.estimated_size(),
+            Operation::Delete(ref delete) => RawKey::from(delete).key.len(),
+        }
+    }
+
+    /// Key, modified by this operation.
+    pub fn key(&self) -> Key {
+        match *self {
+            Operation::Insert(ref insert) => insert.key(),
+            Operation::Delete(ref delete) => delete.clone(),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum KeyValue {
     Meta(&'static str, Bytes),
@@ -38,9 +65,127 @@ pub enum KeyValue {
     SproutTreeState(H256, SproutTreeState),
     SaplingTreeState(H256, SaplingTreeState),
     SproutBlockRoot(H256, H256),
+    AddressOutputs(IndexedAddress, u32, List<AddressOutput>),
+    AddressSpends(IndexedAddress, u32, List<AddressSpend>),
+    AddressOutputHeights(IndexedAddress, u32, List<u32>),
+    AddressSpendHeights(IndexedAddress, u32, List<u32>),
+    TransactionBlock(H256, H256),
+    ChainTxCount(H256, u64),
 }
 
-#[derive(Debug)]
+impl KeyValue {
+    /// Estimated number of bytes in serialized key and value.
+    pub fn estimated_size(&self) -> usize {
+        match *self {
+            KeyValue::Meta(ref key, ref value) | KeyValue::Configuration(ref key, ref value) => {
+                key.len() + value.len()
+            }
+            KeyValue::BlockHash(_, _) => 4 + 32,
+            KeyValue::BlockHeader(_, ref value) => 32 + value.serialized_size(),
+            KeyValue::BlockTransactions(_, ref value) => 32 + value.serialized_size(),
+            KeyValue::Transaction(_, ref value) => 32 + value.serialized_size(),
+            KeyValue::TransactionMeta(_, ref value) => 32 + value.serialized_size(),
+            KeyValue::BlockNumber(_, _) => 32 + 4,
+            KeyValue::Nullifier(_) => 32,
+            KeyValue::SproutTreeState(_, ref value) => 32 + value.serialized_size(),
+            KeyValue::SaplingTreeState(_, ref value) => 32 + value.serialized_size(),
+            KeyValue::SproutBlockRoot(_, _) => 32 + 32,
+            KeyValue::AddressOutputs(_, _, ref value) => 21 + 4 + value.serialized_size(),
+            KeyValue::AddressSpends(_, _, ref value) => 21 + 4 + value.serialized_size(),
+            KeyValue::AddressOutputHeights(_, _, ref value) => 21 + 4 + value.serialized_size(),
+            KeyValue::AddressSpendHeights(_, _, ref value) => 21 + 4 + value.serialized_size(),
+            KeyValue::TransactionBlock(_, _) => 32 + 32,
+            KeyValue::ChainTxCount(_, _) => 32 + 8,
+        }
+    }
+
+    /// Key of this key-value pair.
+    pub fn key(&self) -> Key {
+        match *self {
+            KeyValue::Meta(key, _) => Key::Meta(key),
+            KeyValue::BlockHash(key, _) => Key::BlockHash(key),
+            KeyValue::BlockHeader(key, _) => Key::BlockHeader(key),
+            KeyValue::BlockTransactions(key, _) => Key::BlockTransactions(key),
+            KeyValue::Transaction(key, _) => Key::Transaction(key),
+            KeyValue::TransactionMeta(key, _) => Key::TransactionMeta(key),
+            KeyValue::BlockNumber(key, _) => Key::BlockNumber(key),
+            KeyValue::Configuration(key, _) => Key::Configuration(key),
+            KeyValue::Nullifier(key) => Key::Nullifier(key),
+            KeyValue::SproutTreeState(key, _) => {
+                Key::TreeRoot(EpochRef::new(EpochTag::Sprout, key))
+            }
+            KeyValue::SaplingTreeState(key, _) => {
+                Key::TreeRoot(EpochRef::new(EpochTag::Sapling, key))
+            }
+            KeyValue::SproutBlockRoot(key, _) => Key::SproutBlockRoot(key),
+            KeyValue::AddressOutputs(key, height, _) => Key::AddressOutputs(key, height),
+            KeyValue::AddressSpends(key, height, _) => Key::AddressSpends(key, height),
+            KeyValue::AddressOutputHeights(key, bucket, _) => {
+                Key::AddressOutputHeights(key, bucket)
+            }
+            KeyValue::AddressSpendHeights(key, bucket, _) => Key::AddressSpendHeights(key, bucket),
+            KeyValue::TransactionBlock(key, _) => Key::TransactionBlock(key),
+            KeyValue::ChainTxCount(key, _) => Key::ChainTxCount(key),
+        }
+    }
+
+    /// Joins the key and the value, read by this key. Returns None if value doesn't match the key.
+    pub fn from_key_value(key: Key, value: Value) -> Option<KeyValue> {
+        match (key, value) {
+            (Key::Meta(key), Value::Meta(value)) => Some(KeyValue::Meta(key, value)),
+            (Key::BlockHash(key), Value::BlockHash(value)) => Some(KeyValue::BlockHash(key, value)),
+            (Key::BlockHeader(key), Value::BlockHeader(value)) => {
+                Some(KeyValue::BlockHeader(key, value))
+            }
+            (Key::BlockTransactions(key), Value::BlockTransactions(value)) => {
+                Some(KeyValue::BlockTransactions(key, value))
+            }
+            (Key::Transaction(key), Value::Transaction(value)) => {
+                Some(KeyValue::Transaction(key, value))
+            }
+            (Key::TransactionMeta(key), Value::TransactionMeta(value)) => {
+                Some(KeyValue::TransactionMeta(key, value))
+            }
+            (Key::BlockNumber(key), Value::BlockNumber(value)) => {
+                Some(KeyValue::BlockNumber(key, value))
+            }
+            (Key::Configuration(key), Value::Configuration(value)) => {
+                Some(KeyValue::Configuration(key, value))
+            }
+            (Key::Nullifier(key), Value::Empty) => Some(KeyValue::Nullifier(key)),
+            (Key::TreeRoot(key), Value::SproutTreeState(value)) => {
+                Some(KeyValue::SproutTreeState(*key.hash(), value))
+            }
+            (Key::TreeRoot(key), Value::SaplingTreeState(value)) => {
+                Some(KeyValue::SaplingTreeState(*key.hash(), value))
+            }
+            (Key::SproutBlockRoot(key), Value::SproutTreeRoot(value)) => {
+                Some(KeyValue::SproutBlockRoot(key, value))
+            }
+            (Key::AddressOutputs(key, height), Value::AddressOutputs(value)) => {
+                Some(KeyValue::AddressOutputs(key, height, value))
+            }
+            (Key::AddressSpends(key, height), Value::AddressSpends(value)) => {
+                Some(KeyValue::AddressSpends(key, height, value))
+            }
+            (Key::AddressOutputHeights(key, bucket), Value::AddressHeights(value)) => {
+                Some(KeyValue::AddressOutputHeights(key, bucket, value))
+            }
+            (Key::AddressSpendHeights(key, bucket), Value::AddressHeights(value)) => {
+                Some(KeyValue::AddressSpendHeights(key, bucket, value))
+            }
+            (Key::TransactionBlock(key), Value::TransactionBlock(value)) => {
+                Some(KeyValue::TransactionBlock(key, value))
+            }
+            (Key::ChainTxCount(key), Value::ChainTxCount(value)) => {
+                Some(KeyValue::ChainTxCount(key, value))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
 pub enum Key {
     Meta(&'static str),
     BlockHash(u32),
@@ -53,6 +198,12 @@ pub enum Key {
     Nullifier(EpochRef),
     TreeRoot(EpochRef),
     SproutBlockRoot(H256),
+    AddressOutputs(IndexedAddress, u32),
+    AddressSpends(IndexedAddress, u32),
+    AddressOutputHeights(IndexedAddress, u32),
+    AddressSpendHeights(IndexedAddress, u32),
+    TransactionBlock(H256),
+    ChainTxCount(H256),
 }
 
 #[derive(Debug, Clone)]
@@ -69,6 +220,11 @@ pub enum Value {
     SproutTreeState(SproutTreeState),
     SaplingTreeState(SaplingTreeState),
     SproutTreeRoot(H256),
+    AddressOutputs(List<AddressOutput>),
+    AddressSpends(List<AddressSpend>),
+    AddressHeights(List<u32>),
+    TransactionBlock(H256),
+    ChainTxCount(u64),
 }
 
 impl Value {
@@ -88,6 +244,13 @@ impl Value {
                 EpochTag::Sapling => deserialize(bytes).map(Value::SaplingTreeState),
             },
             Key::SproutBlockRoot(_) => deserialize(bytes).map(Value::SproutTreeRoot),
+            Key::AddressOutputs(_, _) => deserialize(bytes).map(Value::AddressOutputs),
+            Key::AddressSpends(_, _) => deserialize(bytes).map(Value::AddressSpends),
+            Key::AddressOutputHeights(_, _) | Key::AddressSpendHeights(_, _) => {
+                deserialize(bytes).map(Value::AddressHeights)
+            }
+            Key::TransactionBlock(_) => deserialize(bytes).map(Value::TransactionBlock),
+            Key::ChainTxCount(_) => deserialize(bytes).map(Value::ChainTxCount),
         }
         .map_err(|e| format!("{:?}", e))
     }
@@ -168,6 +331,41 @@ impl Value {
             _ => None,
         }
     }
+
+    pub fn as_address_outputs(self) -> Option<List<AddressOutput>> {
+        match self {
+            Value::AddressOutputs(list) => Some(list),
+            _ => None,
+        }
+    }
+
+    pub fn as_address_spends(self) -> Option<List<AddressSpend>> {
+        match self {
+            Value::AddressSpends(list) => Some(list),
+            _ => None,
+        }
+    }
+
+    pub fn as_address_heights(self) -> Option<List<u32>> {
+        match self {
+            Value::AddressHeights(list) => Some(list),
+            _ => None,
+        }
+    }
+
+    pub fn as_transaction_block(self) -> Option<H256> {
+        match self {
+            Value::TransactionBlock(block_hash) => Some(block_hash),
+            _ => None,
+        }
+    }
+
+    pub fn as_chain_tx_count(self) -> Option<u64> {
+        match self {
+            Value::ChainTxCount(count) => Some(count),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -241,6 +439,11 @@ impl Transaction {
     pub fn delete(&mut self, delete: Key) {
         self.operations.push(Operation::Delete(delete));
     }
+
+    /// Estimated number of bytes, written to the database by this transaction.
+    pub fn estimated_size(&self) -> usize {
+        self.operations.iter().map(Operation::estimated_size).sum()
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
@@ -304,6 +507,32 @@ impl<'a> From<&'a KeyValue> for RawKeyValue {
             KeyValue::Configuration(ref key, ref value) => {
                 (COL_CONFIGURATION, serialize(key), serialize(value))
             }
+            KeyValue::AddressOutputs(ref key, height, ref value) => (
+                COL_ADDRESS_OUTPUTS,
+                address_key(key, height),
+                serialize(value),
+            ),
+            KeyValue::AddressSpends(ref key, height, ref value) => (
+                COL_ADDRESS_SPENDS,
+                address_key(key, height),
+                serialize(value),
+            ),
+            KeyValue::AddressOutputHeights(ref key, bucket, ref value) => (
+                COL_ADDRESS_OUTPUT_HEIGHTS,
+                address_key(key, bucket),
+                serialize(value),
+            ),
+            KeyValue::AddressSpendHeights(ref key, bucket, ref value) => (
+                COL_ADDRESS_SPEND_HEIGHTS,
+                address_key(key, bucket),
+                serialize(value),
+            ),
+            KeyValue::TransactionBlock(ref key, ref value) => {
+                (COL_TRANSACTION_BLOCKS, serialize(key), serialize(value))
+            }
+            KeyValue::ChainTxCount(ref key, ref value) => {
+                (COL_CHAIN_TX_COUNTS, serialize(key), serialize(value))
+            }
         };
 
         RawKeyValue {
@@ -348,6 +577,16 @@ impl<'a> From<&'a Key> for RawKey {
             Key::BlockNumber(ref key) => (COL_BLOCK_NUMBERS, serialize(key)),
             Key::SproutBlockRoot(ref key) => (COL_SPROUT_BLOCK_ROOTS, serialize(key)),
             Key::Configuration(ref key) => (COL_CONFIGURATION, serialize(key)),
+            Key::AddressOutputs(ref key, height) => (COL_ADDRESS_OUTPUTS, address_key(key, height)),
+            Key::AddressSpends(ref key, height) => (COL_ADDRESS_SPENDS, address_key(key, height)),
+            Key::AddressOutputHeights(ref key, bucket) => {
+                (COL_ADDRESS_OUTPUT_HEIGHTS, address_key(key, bucket))
+            }
+            Key::AddressSpendHeights(ref key, bucket) => {
+                (COL_ADDRESS_SPEND_HEIGHTS, address_key(key, bucket))
+            }
+            Key::TransactionBlock(ref key) => (COL_TRANSACTION_BLOCKS, serialize(key)),
+            Key::ChainTxCount(ref key) => (COL_CHAIN_TX_COUNTS, serialize(key)),
         };
 
         RawKey {
@@ -357,6 +596,13 @@ impl<'a> From<&'a Key> for RawKey {
     }
 }
 
+/// Serialized key of the address index entry.
+fn address_key(address: &IndexedAddress, number: u32) -> Bytes {
+    let mut stream = Stream::new();
+    stream.append(address).append(&number);
+    stream.out()
+}
+
 impl<'a> From<&'a Operation> for RawOperation {
     fn from(o: &'a Operation) -> Self {
         match *o {
@@ -7,12 +7,14 @@ extern crate bit_vec;
 extern crate lru_cache;
 
 extern crate zebra_chain;
+extern crate zebra_keys;
 extern crate zebra_primitives;
+extern crate zebra_script;
 extern crate zebra_serialization as ser;
 extern crate zebra_storage;
 
 mod block_chain_db;
 pub mod kv;
 
-pub use block_chain_db::{BlockChainDatabase, ForkChainDatabase};
+pub use block_chain_db::{BlockChainDatabase, ForkChainDatabase, MAX_FORK_ROUTE_PRESET};
 pub use zebra_primitives::{bytes, hash};
@@ -1,12 +1,20 @@
 extern crate zebra_chain;
 extern crate zebra_db;
+extern crate zebra_serialization as ser;
 extern crate zebra_storage;
 extern crate zebra_test_data;
 
-use zebra_chain::IndexedBlock;
-use zebra_db::kv::{MemoryDatabase, SharedMemoryDatabase};
-use zebra_db::BlockChainDatabase;
-use zebra_storage::{BlockProvider, ForkChain, SideChainOrigin};
+use ser::deserialize;
+use zebra_chain::{Block, BlockHeader, IndexedBlock, OutPoint};
+use zebra_db::hash::H160;
+use zebra_db::kv::{Key, KeyValueDatabase, MemoryDatabase, SharedMemoryDatabase, Transaction};
+use zebra_db::{BlockChainDatabase, MAX_FORK_ROUTE_PRESET};
+use zebra_storage::{
+    AddressIndexProvider, AddressOutput, AddressSpend, BlockChain, BlockOrigin, BlockProvider,
+    BlockRef, Error, ForkChain, IndexedAddress, SaplingTreeState, SideChainOrigin, SproutTreeState,
+    Store, TransactionIndexProvider, TransactionMetaProvider, TransactionOutputProvider,
+    TransactionProvider, TreeStateProvider,
+};
 
 #[test]
 fn insert_block() {
@@ -48,6 +56,36 @@ fn insert_block() {
     assert!(store.block_number(b2.hash()).is_none());
 }
 
+#[test]
+fn tree_state_at_height() {
+    let store = BlockChainDatabase::open(MemoryDatabase::default());
+    let b0: IndexedBlock = zebra_test_data::block_h0().into();
+    let b1: IndexedBlock = zebra_test_data::block_h1().into();
+
+    store.insert(b0.clone()).unwrap();
+    store.insert(b1.clone()).unwrap();
+    store.canonize(b0.hash()).unwrap();
+    store.canonize(b1.hash()).unwrap();
+
+    assert_eq!(store.sprout_tree_at_height(0), Some(SproutTreeState::new()));
+    assert_eq!(
+        store.sapling_tree_at_height(0),
+        Some(SaplingTreeState::new())
+    );
+    assert_eq!(
+        store.sprout_tree_at_height(1),
+        store.sprout_tree_at_block(b1.hash())
+    );
+    assert_eq!(
+        store.sapling_tree_at_height(1),
+        store.sapling_tree_at_block(b1.hash())
+    );
+
+    // past the tip
+    assert_eq!(store.sprout_tree_at_height(2), None);
+    assert_eq!(store.sapling_tree_at_height(2), None);
+}
+
 #[test]
 fn reopen_db() {
     let shared_database = SharedMemoryDatabase::default();
@@ -75,6 +113,32 @@ fn reopen_db() {
     }
 }
 
+#[test]
+fn block_and_header_bytes() {
+    let store = BlockChainDatabase::open(MemoryDatabase::default());
+    let b0: IndexedBlock = zebra_test_data::block_h0().into();
+    let b1: IndexedBlock = zebra_test_data::block_h1().into();
+
+    store.insert(b0.clone()).unwrap();
+    store.canonize(b0.hash()).unwrap();
+
+    let (block_bytes, header_bytes) = store.block_and_header_bytes(BlockRef::Number(0)).unwrap();
+    let block: Block = deserialize(block_bytes.as_ref()).unwrap();
+    let header: BlockHeader = deserialize(header_bytes.as_ref()).unwrap();
+    assert_eq!(block, b0.clone().to_raw_block());
+    assert_eq!(header, b0.header.raw);
+
+    let (block_bytes, _) = store
+        .block_and_header_bytes(BlockRef::Hash(b0.hash().clone()))
+        .unwrap();
+    let block: Block = deserialize(block_bytes.as_ref()).unwrap();
+    assert_eq!(block, b0.to_raw_block());
+
+    assert!(store.block_and_header_bytes(BlockRef::Number(1)).is_none());
+    let unknown_block = BlockRef::Hash(b1.hash().clone());
+    assert!(store.block_and_header_bytes(unknown_block).is_none());
+}
+
 #[test]
 fn switch_to_simple_fork() {
     let store = BlockChainDatabase::open(MemoryDatabase::default());
@@ -127,3 +191,947 @@ fn switch_to_simple_fork() {
     assert_eq!(b2.hash(), &store.best_block().hash);
     assert_eq!(store.best_block().hash, store.block_hash(2).unwrap());
 }
+
+#[test]
+fn fork_point() {
+    let store = BlockChainDatabase::open(MemoryDatabase::default());
+    let b0: IndexedBlock = zebra_test_data::genesis().into();
+    let b1: IndexedBlock = zebra_test_data::block_builder()
+        .header()
+        .parent(b0.hash().clone())
+        .build()
+        .build()
+        .into();
+    let b2: IndexedBlock = zebra_test_data::block_builder()
+        .header()
+        .parent(b1.hash().clone())
+        .build()
+        .build()
+        .into();
+    let b3: IndexedBlock = zebra_test_data::block_builder()
+        .header()
+        .parent(b2.hash().clone())
+        .build()
+        .build()
+        .into();
+    // side chain: b0 -> b1 -> s2
+    let s2: IndexedBlock = zebra_test_data::block_builder()
+        .header()
+        .parent(b1.hash().clone())
+        .nonce(1.into())
+        .build()
+        .build()
+        .into();
+
+    for block in vec![&b0, &b1, &b2, &b3, &s2] {
+        store.insert(block.clone()).unwrap();
+    }
+    store.canonize(b0.hash()).unwrap();
+    store.canonize(b1.hash()).unwrap();
+    store.canonize(b2.hash()).unwrap();
+    store.canonize(b3.hash()).unwrap();
+
+    assert_eq!(
+        store.fork_point(b3.hash(), s2.hash()),
+        Some(b1.hash().clone())
+    );
+    assert_eq!(
+        store.fork_point(s2.hash(), b3.hash()),
+        Some(b1.hash().clone())
+    );
+    assert_eq!(
+        store.fork_point(b3.hash(), b1.hash()),
+        Some(b1.hash().clone())
+    );
+    assert_eq!(
+        store.fork_point(b2.hash(), b2.hash()),
+        Some(b2.hash().clone())
+    );
+    assert_eq!(
+        store.fork_point(s2.hash(), b0.hash()),
+        Some(b0.hash().clone())
+    );
+
+    let unknown_block: IndexedBlock = zebra_test_data::block_h1().into();
+    assert_eq!(store.fork_point(b3.hash(), unknown_block.hash()), None);
+    assert_eq!(store.fork_point(unknown_block.hash(), s2.hash()), None);
+}
+
+#[test]
+fn scan_utxos() {
+    let b0 = zebra_test_data::block_builder()
+        .header()
+        .build()
+        .transaction()
+        .output()
+        .value(1)
+        .script_pubkey("51")
+        .build()
+        .output()
+        .value(2)
+        .script_pubkey("52")
+        .build()
+        .output()
+        .value(3)
+        .script_pubkey("51")
+        .build()
+        .build()
+        .build();
+    let tx0_hash = b0.transactions[0].hash();
+    let b1 = zebra_test_data::block_builder()
+        .header()
+        .parent(b0.hash())
+        .build()
+        .transaction()
+        .input()
+        .hash(tx0_hash.clone())
+        .index(0)
+        .build()
+        .output()
+        .value(4)
+        .script_pubkey("51")
+        .build()
+        .build()
+        .build();
+    let tx1_hash = b1.transactions[0].hash();
+    let store = BlockChainDatabase::init_test_chain(vec![b0.into(), b1.into()]);
+
+    let matcher = |script: &[u8]| script == &[0x51];
+
+    // only unspent outputs with matching script are reported
+    let mut utxos = Vec::new();
+    let result = store.scan_utxos(0, &matcher, &mut |outpoint, output| {
+        utxos.push((outpoint, output.value));
+        true
+    });
+    assert_eq!(result, None);
+    assert_eq!(
+        utxos,
+        vec![
+            (
+                OutPoint {
+                    hash: tx0_hash.clone(),
+                    index: 2,
+                },
+                3
+            ),
+            (
+                OutPoint {
+                    hash: tx1_hash.clone(),
+                    index: 0,
+                },
+                4
+            ),
+        ]
+    );
+
+    // interrupted scan is resumed from the block where it has been interrupted
+    let mut utxos = Vec::new();
+    let result = store.scan_utxos(0, &matcher, &mut |outpoint, _| {
+        utxos.push(outpoint);
+        false
+    });
+    assert_eq!(result, Some(0));
+    let result = store.scan_utxos(0, &matcher, &mut |outpoint, _| {
+        utxos.push(outpoint);
+        true
+    });
+    assert_eq!(result, None);
+    let tx0_out = OutPoint {
+        hash: tx0_hash,
+        index: 2,
+    };
+    let tx1_out = OutPoint {
+        hash: tx1_hash,
+        index: 0,
+    };
+    assert_eq!(utxos, vec![tx0_out.clone(), tx0_out, tx1_out]);
+}
+
+#[test]
+fn prune_below() {
+    let b0 = zebra_test_data::block_builder()
+        .header()
+        .build()
+        .transaction()
+        .output()
+        .value(1)
+        .build()
+        .build()
+        .build();
+    let tx0_hash = b0.transactions[0].hash();
+    let b1 = zebra_test_data::block_builder()
+        .header()
+        .parent(b0.hash())
+        .build()
+        .transaction()
+        .input()
+        .hash(tx0_hash.clone())
+        .index(0)
+        .build()
+        .output()
+        .value(1)
+        .build()
+        .build()
+        .build();
+    let tx1_hash = b1.transactions[0].hash();
+    let b0_header = b0.block_header.clone();
+    let b1_hash = b1.hash();
+    let mut store = BlockChainDatabase::init_test_chain(vec![b0.into(), b1.into()]);
+    store.set_max_fork_route(0);
+
+    // only b0 transactions are fully spent
+    assert_eq!(store.prune_height(), None);
+    assert_eq!(store.prune_below(2), Ok(1));
+    assert!(store.is_pruned(BlockRef::Number(0)));
+    assert!(!store.is_pruned(BlockRef::Number(1)));
+    assert_eq!(store.prune_below(2), Ok(0));
+    assert_eq!(store.prune_below(1), Ok(0));
+    assert_eq!(store.prune_height(), Some(2));
+
+    // header, hash and transactions meta of pruned block are retained
+    let pruned_block = store.block(BlockRef::Number(0)).unwrap();
+    assert_eq!(pruned_block.header.raw, b0_header);
+    assert!(pruned_block.transactions.is_empty());
+    assert!(store.block_and_header_bytes(BlockRef::Number(0)).is_none());
+    assert!(store.transaction(&tx0_hash).is_none());
+    assert!(store.transaction_meta(&tx0_hash).is_some());
+    assert!(store.transaction(&tx1_hash).is_some());
+
+    // pruned block can't be decanonized
+    assert_eq!(store.decanonize(), Ok(b1_hash));
+    assert_eq!(store.decanonize(), Err(Error::PrunedBlock));
+}
+
+#[test]
+fn prune_below_keeps_fork_route() {
+    let b0 = zebra_test_data::block_builder()
+        .header()
+        .build()
+        .transaction()
+        .output()
+        .value(1)
+        .build()
+        .build()
+        .build();
+    let b1 = zebra_test_data::block_builder()
+        .header()
+        .parent(b0.hash())
+        .build()
+        .transaction()
+        .input()
+        .hash(b0.transactions[0].hash())
+        .index(0)
+        .build()
+        .output()
+        .value(1)
+        .build()
+        .build()
+        .build();
+    let mut store = BlockChainDatabase::init_test_chain(vec![b0.into(), b1.into()]);
+
+    // both blocks could be decanonized by reorganization => nothing is pruned
+    assert_eq!(store.prune_below(2), Ok(0));
+    assert_eq!(store.prune_height(), None);
+    assert!(!store.is_pruned(BlockRef::Number(0)));
+
+    // only blocks below the fork route are pruned
+    store.set_max_fork_route(1);
+    assert_eq!(store.prune_below(2), Ok(1));
+    assert_eq!(store.prune_height(), Some(1));
+    assert!(store.is_pruned(BlockRef::Number(0)));
+    assert!(!store.is_pruned(BlockRef::Number(1)));
+}
+
+#[test]
+fn reorganize_with_configurable_max_fork_route() {
+    let genesis = zebra_test_data::genesis();
+    let fork_len = MAX_FORK_ROUTE_PRESET as u32;
+    let canon_blocks =
+        zebra_test_data::build_n_empty_blocks_from(fork_len, 0, &genesis.block_header);
+    let side_blocks =
+        zebra_test_data::build_n_empty_blocks_from(fork_len + 1, 1, &genesis.block_header);
+
+    let mut store = BlockChainDatabase::init_test_chain(vec![genesis.into()]);
+    for block in canon_blocks {
+        let hash = block.hash();
+        store.insert(block.into()).unwrap();
+        store.canonize(&hash).unwrap();
+    }
+
+    let (new_best_block, side_blocks) = side_blocks.split_last().unwrap();
+    for block in side_blocks {
+        store.insert(block.clone().into()).unwrap();
+    }
+
+    // fork is longer than the default limit
+    let new_best_block: IndexedBlock = new_best_block.clone().into();
+    assert_eq!(
+        store.block_origin(&new_best_block.header).unwrap_err(),
+        Error::AncientFork
+    );
+
+    // but it is under the configured limit
+    store.set_max_fork_route(MAX_FORK_ROUTE_PRESET * 2);
+    let origin = match store.block_origin(&new_best_block.header) {
+        Ok(BlockOrigin::SideChainBecomingCanonChain(origin)) => origin,
+        origin => panic!("unexpected block origin: {:?}", origin),
+    };
+    assert_eq!(origin.ancestor, 0);
+    assert_eq!(origin.decanonized_route.len(), fork_len as usize);
+    assert_eq!(origin.canonized_route.len(), fork_len as usize);
+
+    let fork = store.fork(origin).unwrap();
+    fork.store().insert(new_best_block.clone()).unwrap();
+    fork.store().canonize(new_best_block.hash()).unwrap();
+    store.switch_to_fork(fork).unwrap();
+
+    assert_eq!(fork_len + 1, store.best_block().number);
+    assert_eq!(new_best_block.hash(), &store.best_block().hash);
+}
+
+#[test]
+fn address_index() {
+    let p2pkh = "76a914010000000000000000000000000000000000000088ac";
+    let p2sh = "a914020000000000000000000000000000000000000087";
+    let b0: IndexedBlock = zebra_test_data::block_builder()
+        .header()
+        .build()
+        .transaction()
+        .output()
+        .value(10)
+        .script_pubkey(p2pkh)
+        .build()
+        .build()
+        .build()
+        .into();
+    let tx0_hash = b0.transactions[0].hash.clone();
+    let b1: IndexedBlock = zebra_test_data::block_builder()
+        .header()
+        .parent(b0.hash().clone())
+        .build()
+        .transaction()
+        .input()
+        .hash(tx0_hash.clone())
+        .index(0)
+        .build()
+        .output()
+        .value(7)
+        .script_pubkey(p2pkh)
+        .build()
+        .output()
+        .value(2)
+        .script_pubkey(p2sh)
+        .build()
+        .output()
+        .value(1)
+        .script_pubkey("51")
+        .build()
+        .build()
+        .build()
+        .into();
+    let tx1_hash = b1.transactions[0].hash.clone();
+
+    let mut store = BlockChainDatabase::open(MemoryDatabase::default());
+    store.set_address_index(true).unwrap();
+    for block in vec![b0.clone(), b1.clone()] {
+        let hash = block.hash().clone();
+        store.insert(block).unwrap();
+        store.canonize(&hash).unwrap();
+    }
+
+    let pubkey_hash = IndexedAddress::PubKeyHash(H160::from(1));
+    let script_hash = IndexedAddress::ScriptHash(H160::from(2));
+    assert_eq!(
+        store.address_outputs(&pubkey_hash, 0, u32::max_value()),
+        vec![
+            AddressOutput {
+                outpoint: OutPoint {
+                    hash: tx0_hash.clone(),
+                    index: 0,
+                },
+                height: 0,
+                value: 10,
+            },
+            AddressOutput {
+                outpoint: OutPoint {
+                    hash: tx1_hash.clone(),
+                    index: 0,
+                },
+                height: 1,
+                value: 7,
+            },
+        ]
+    );
+    assert_eq!(
+        store
+            .address_outputs(&script_hash, 0, u32::max_value())
+            .len(),
+        1
+    );
+    assert!(store
+        .address_outputs(
+            &IndexedAddress::ScriptHash(H160::from(1)),
+            0,
+            u32::max_value()
+        )
+        .is_empty());
+
+    // only outputs of blocks within the heights range are returned
+    let outputs = store.address_outputs(&pubkey_hash, 1, 1);
+    assert_eq!(outputs.len(), 1);
+    assert_eq!(outputs[0].height, 1);
+    assert!(store
+        .address_outputs(&pubkey_hash, 2, u32::max_value())
+        .is_empty());
+
+    // outputs of decanonized block are removed from the index
+    store.decanonize().unwrap();
+    assert_eq!(
+        store
+            .address_outputs(&pubkey_hash, 0, u32::max_value())
+            .len(),
+        1
+    );
+    assert!(store
+        .address_outputs(&script_hash, 0, u32::max_value())
+        .is_empty());
+
+    // index is not maintained by default
+    let store = BlockChainDatabase::init_test_chain(vec![b0, b1]);
+    assert!(!store.is_address_index_enabled());
+    assert!(store
+        .address_outputs(&pubkey_hash, 0, u32::max_value())
+        .is_empty());
+}
+
+#[test]
+fn transaction_index() {
+    let b0: IndexedBlock = zebra_test_data::block_h0().into();
+    let b1: IndexedBlock = zebra_test_data::block_h1().into();
+    let tx0_hash = b0.transactions[0].hash.clone();
+    let tx1_hash = b1.transactions[0].hash.clone();
+
+    let mut store = BlockChainDatabase::open(MemoryDatabase::default());
+    store.set_transaction_index(true).unwrap();
+    store.insert(b0.clone()).unwrap();
+    store.insert(b1.clone()).unwrap();
+    store.canonize(b0.hash()).unwrap();
+    store.canonize(b1.hash()).unwrap();
+
+    assert!(store.is_transaction_index_enabled());
+    assert_eq!(store.transaction_block_hash(&tx0_hash), Some(*b0.hash()));
+    assert_eq!(store.transaction_block_hash(&tx1_hash), Some(*b1.hash()));
+
+    // transactions of removed block are removed from the index
+    BlockChain::rollback_best(&store).unwrap();
+    assert_eq!(store.transaction_block_hash(&tx0_hash), Some(*b0.hash()));
+    assert_eq!(store.transaction_block_hash(&tx1_hash), None);
+
+    // index is not maintained by default
+    let store = BlockChainDatabase::init_test_chain(vec![b0, b1]);
+    assert!(!store.is_transaction_index_enabled());
+    assert_eq!(store.transaction_block_hash(&tx0_hash), None);
+}
+
+#[test]
+fn index_can_only_be_enabled_on_empty_database() {
+    let b0: IndexedBlock = zebra_test_data::block_h0().into();
+    let b1: IndexedBlock = zebra_test_data::block_h1().into();
+
+    // index of the non-empty database would miss already stored blocks
+    let mut store = BlockChainDatabase::init_test_chain(vec![b0.clone()]);
+    assert_eq!(store.set_address_index(true), Err(Error::IncompleteIndex));
+    assert_eq!(
+        store.set_transaction_index(true),
+        Err(Error::IncompleteIndex)
+    );
+    assert!(!store.is_address_index_enabled());
+    assert!(!store.is_transaction_index_enabled());
+
+    // index, enabled since the beginning, can be enabled again
+    let mut store = BlockChainDatabase::open(MemoryDatabase::default());
+    store.set_address_index(true).unwrap();
+    store.insert(b0.clone()).unwrap();
+    store.canonize(b0.hash()).unwrap();
+    store.set_address_index(true).unwrap();
+
+    // but once it has been disabled, it misses blocks, canonized after that
+    store.set_address_index(false).unwrap();
+    store.insert(b1.clone()).unwrap();
+    store.canonize(b1.hash()).unwrap();
+    assert_eq!(store.set_address_index(true), Err(Error::IncompleteIndex));
+}
+
+#[test]
+fn address_index_spends() {
+    let p2pkh = "76a914010000000000000000000000000000000000000088ac";
+    let p2sh = "a914020000000000000000000000000000000000000087";
+    let b0: IndexedBlock = zebra_test_data::block_builder()
+        .header()
+        .build()
+        .transaction()
+        .output()
+        .value(10)
+        .script_pubkey(p2pkh)
+        .build()
+        .build()
+        .build()
+        .into();
+    let tx0_hash = b0.transactions[0].hash.clone();
+    let b1: IndexedBlock = zebra_test_data::block_builder()
+        .header()
+        .parent(b0.hash().clone())
+        .build()
+        .transaction()
+        .coinbase()
+        .output()
+        .value(1)
+        .script_pubkey("51")
+        .build()
+        .build()
+        .transaction()
+        .input()
+        .hash(tx0_hash.clone())
+        .index(0)
+        .build()
+        .output()
+        .value(10)
+        .script_pubkey(p2sh)
+        .build()
+        .build()
+        .build()
+        .into();
+    let tx1_hash = b1.transactions[1].hash.clone();
+
+    let mut store = BlockChainDatabase::open(MemoryDatabase::default());
+    store.set_address_index(true).unwrap();
+    for block in vec![b0, b1] {
+        let hash = block.hash().clone();
+        store.insert(block).unwrap();
+        store.canonize(&hash).unwrap();
+    }
+
+    let pubkey_hash = IndexedAddress::PubKeyHash(H160::from(1));
+    let script_hash = IndexedAddress::ScriptHash(H160::from(2));
+    assert_eq!(
+        store.address_spends(&pubkey_hash, 0, u32::max_value()),
+        vec![AddressSpend {
+            txid: tx1_hash,
+            height: 1,
+            prevout: OutPoint {
+                hash: tx0_hash,
+                index: 0,
+            },
+            value: 10,
+        }]
+    );
+    assert!(store
+        .address_spends(&script_hash, 0, u32::max_value())
+        .is_empty());
+    assert!(store.address_spends(&pubkey_hash, 0, 0).is_empty());
+
+    // spends of decanonized block are removed from the index
+    store.decanonize().unwrap();
+    assert!(store
+        .address_spends(&pubkey_hash, 0, u32::max_value())
+        .is_empty());
+}
+
+#[test]
+fn address_index_spent_in_same_block() {
+    let p2pkh = "76a914010000000000000000000000000000000000000088ac";
+    let b0: IndexedBlock = zebra_test_data::block_builder()
+        .header()
+        .build()
+        .transaction()
+        .output()
+        .value(10)
+        .script_pubkey(p2pkh)
+        .build()
+        .build()
+        .build()
+        .into();
+    let mut tx1: zebra_chain::Transaction = zebra_test_data::TransactionBuilder::with_output(7)
+        .add_input(&b0.transactions[0].raw, 0)
+        .into();
+    tx1.outputs[0].script_pubkey = p2pkh.into();
+    let tx2: zebra_chain::Transaction = zebra_test_data::TransactionBuilder::with_output(4)
+        .add_input(&tx1, 0)
+        .into();
+    let b1: IndexedBlock = zebra_test_data::block_builder()
+        .header()
+        .parent(b0.hash().clone())
+        .build()
+        .transaction()
+        .coinbase()
+        .build()
+        .with_transaction(tx1.clone())
+        .with_transaction(tx2.clone())
+        .build()
+        .into();
+
+    let mut store = BlockChainDatabase::open(MemoryDatabase::default());
+    store.set_address_index(true).unwrap();
+    for block in vec![b0, b1] {
+        let hash = block.hash().clone();
+        store.insert(block).unwrap();
+        store.canonize(&hash).unwrap();
+    }
+
+    // output of the first transaction is indexed once, both as output and as spend
+    let pubkey_hash = IndexedAddress::PubKeyHash(H160::from(1));
+    let outputs = store.address_outputs(&pubkey_hash, 1, 1);
+    assert_eq!(outputs.len(), 1);
+    assert_eq!(outputs[0].outpoint.hash, tx1.hash());
+    assert_eq!(
+        store
+            .address_spends(&pubkey_hash, 1, 1)
+            .into_iter()
+            .map(|spend| (spend.txid, spend.value))
+            .collect::<Vec<_>>(),
+        vec![(tx1.hash(), 10), (tx2.hash(), 7)]
+    );
+}
+
+#[test]
+fn transaction_outputs_batch() {
+    let store = BlockChainDatabase::open(MemoryDatabase::default());
+    let b0: IndexedBlock = zebra_test_data::block_h0().into();
+    let b1: IndexedBlock = zebra_test_data::block_h1().into();
+    let b2: IndexedBlock = zebra_test_data::block_h2().into();
+    store.insert(b0.clone()).unwrap();
+    store.canonize(b0.hash()).unwrap();
+    store.insert(b1.clone()).unwrap();
+    store.canonize(b1.hash()).unwrap();
+    // non-canon transactions outputs are not returned
+    store.insert(b2.clone()).unwrap();
+
+    let outpoints = vec![
+        OutPoint {
+            hash: b1.transactions[0].hash.clone(),
+            index: 0,
+        },
+        OutPoint {
+            hash: b2.transactions[0].hash.clone(),
+            index: 0,
+        },
+        OutPoint {
+            hash: b1.transactions[0].hash.clone(),
+            index: 100,
+        },
+        OutPoint {
+            hash: b0.transactions[0].hash.clone(),
+            index: 0,
+        },
+    ];
+    let outputs = store.transaction_outputs(&outpoints);
+    assert_eq!(
+        outputs,
+        outpoints
+            .iter()
+            .map(|outpoint| store.transaction_output(outpoint, 0))
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(outputs[0], Some(b1.transactions[0].raw.outputs[0].clone()));
+    assert_eq!(outputs[1], None);
+    assert_eq!(outputs[2], None);
+}
+
+#[test]
+fn chain_tx_count() {
+    let b0: IndexedBlock = zebra_test_data::block_h0().into();
+    let b1: IndexedBlock = zebra_test_data::block_builder()
+        .header()
+        .parent(b0.hash().clone())
+        .build()
+        .transaction()
+        .coinbase()
+        .output()
+        .value(1)
+        .build()
+        .build()
+        .transaction()
+        .output()
+        .value(2)
+        .build()
+        .build()
+        .build()
+        .into();
+    let b2: IndexedBlock = zebra_test_data::block_builder()
+        .header()
+        .parent(b1.hash().clone())
+        .build()
+        .transaction()
+        .coinbase()
+        .output()
+        .value(3)
+        .build()
+        .build()
+        .build()
+        .into();
+
+    let store = BlockChainDatabase::open(MemoryDatabase::default());
+    for block in vec![b0.clone(), b1.clone(), b2.clone()] {
+        store.insert(block).unwrap();
+    }
+    assert_eq!(store.chain_tx_count(b0.hash().clone().into()), None);
+
+    store.canonize(b0.hash()).unwrap();
+    store.canonize(b1.hash()).unwrap();
+    store.canonize(b2.hash()).unwrap();
+    assert_eq!(store.chain_tx_count(b0.hash().clone().into()), Some(1));
+    assert_eq!(store.chain_tx_count(b1.hash().clone().into()), Some(3));
+    assert_eq!(store.chain_tx_count(2.into()), Some(4));
+
+    // counter of decanonized block is removed
+    store.decanonize().unwrap();
+    assert_eq!(store.chain_tx_count(b2.hash().clone().into()), None);
+    assert_eq!(store.chain_tx_count(b1.hash().clone().into()), Some(3));
+}
+
+#[test]
+fn backfill_chain_tx_count() {
+    let shared_database = SharedMemoryDatabase::default();
+    let store = BlockChainDatabase::open(shared_database.clone());
+    let mut blocks: Vec<IndexedBlock> = vec![zebra_test_data::block_h0().into()];
+    for value in 1..4 {
+        let block = zebra_test_data::block_builder()
+            .header()
+            .parent(blocks[blocks.len() - 1].hash().clone())
+            .build()
+            .transaction()
+            .coinbase()
+            .output()
+            .value(value)
+            .build()
+            .build()
+            .build()
+            .into();
+        blocks.push(block);
+    }
+    for block in &blocks {
+        store.insert(block.clone()).unwrap();
+        store.canonize(block.hash()).unwrap();
+    }
+
+    // database without counters of blocks #2 and #3
+    let mut update = Transaction::new();
+    update.delete(Key::ChainTxCount(blocks[2].hash().clone()));
+    update.delete(Key::ChainTxCount(blocks[3].hash().clone()));
+    shared_database.write(update).unwrap();
+
+    let store = BlockChainDatabase::open(shared_database);
+    assert_eq!(store.chain_tx_count(3.into()), None);
+    store.backfill_chain_tx_count().unwrap();
+    assert_eq!(store.chain_tx_count(1.into()), Some(2));
+    assert_eq!(store.chain_tx_count(2.into()), Some(3));
+    assert_eq!(store.chain_tx_count(3.into()), Some(4));
+}
+
+#[test]
+fn precious_block() {
+    let store = BlockChainDatabase::open(MemoryDatabase::default());
+    let b0: IndexedBlock = zebra_test_data::genesis().into();
+    let b1: IndexedBlock = zebra_test_data::block_builder()
+        .header()
+        .parent(b0.hash().clone())
+        .build()
+        .build()
+        .into();
+    let b2: IndexedBlock = zebra_test_data::block_builder()
+        .header()
+        .parent(b1.hash().clone())
+        .build()
+        .build()
+        .into();
+    // side chain: b0 -> b1 -> c2
+    let c2: IndexedBlock = zebra_test_data::block_builder()
+        .header()
+        .parent(b1.hash().clone())
+        .nonce(1.into())
+        .build()
+        .build()
+        .into();
+    // side chain: b0 -> s1 -> s2
+    let s1: IndexedBlock = zebra_test_data::block_builder()
+        .header()
+        .parent(b0.hash().clone())
+        .nonce(1.into())
+        .build()
+        .build()
+        .into();
+    let s2: IndexedBlock = zebra_test_data::block_builder()
+        .header()
+        .parent(s1.hash().clone())
+        .build()
+        .build()
+        .into();
+
+    for block in vec![&b0, &b1, &b2, &c2, &s1] {
+        store.insert(block.clone()).unwrap();
+    }
+    store.canonize(b0.hash()).unwrap();
+    store.canonize(b1.hash()).unwrap();
+    store.canonize(b2.hash()).unwrap();
+
+    // canonical block is already preferred
+    match store.precious_block(b2.hash()) {
+        Ok(BlockOrigin::KnownBlock) => (),
+        origin => panic!("unexpected block origin: {:?}", origin),
+    }
+
+    // side chain of the same length becomes canonical
+    match store.precious_block(c2.hash()) {
+        Ok(BlockOrigin::SideChainBecomingCanonChain(origin)) => {
+            assert!(origin.canonized_route.is_empty());
+            assert_eq!(origin.decanonized_route, vec![b2.hash().clone()]);
+        }
+        origin => panic!("unexpected block origin: {:?}", origin),
+    }
+
+    // shorter side chain stays side chain
+    match store.block_origin(&s2.header) {
+        Ok(BlockOrigin::SideChain(_)) => (),
+        origin => panic!("unexpected block origin: {:?}", origin),
+    }
+    match store.precious_block(s1.hash()) {
+        Ok(BlockOrigin::SideChain(_)) => (),
+        origin => panic!("unexpected block origin: {:?}", origin),
+    }
+
+    // but it wins, once it reaches the same length
+    match store.block_origin(&s2.header) {
+        Ok(BlockOrigin::SideChainBecomingCanonChain(origin)) => {
+            assert_eq!(origin.canonized_route, vec![s1.hash().clone()]);
+            assert_eq!(
+                origin.decanonized_route,
+                vec![b1.hash().clone(), b2.hash().clone()]
+            );
+        }
+        origin => panic!("unexpected block origin: {:?}", origin),
+    }
+
+    assert_eq!(
+        store
+            .precious_block(&zebra_test_data::block_h1().hash())
+            .unwrap_err(),
+        Error::UnknownParent
+    );
+}
+
+#[test]
+fn rollback_origin() {
+    let b0: IndexedBlock = zebra_test_data::genesis().into();
+    let b1: IndexedBlock = zebra_test_data::block_builder()
+        .header()
+        .parent(b0.hash().clone())
+        .build()
+        .build()
+        .into();
+    let b2: IndexedBlock = zebra_test_data::block_builder()
+        .header()
+        .parent(b1.hash().clone())
+        .build()
+        .build()
+        .into();
+    let s1: IndexedBlock = zebra_test_data::block_builder()
+        .header()
+        .parent(b0.hash().clone())
+        .nonce(1.into())
+        .build()
+        .build()
+        .into();
+    let mut store = BlockChainDatabase::init_test_chain(vec![b0.clone(), b1.clone(), b2.clone()]);
+    store.insert(s1.clone()).unwrap();
+
+    let origin = store.rollback_origin(b1.hash()).unwrap();
+    assert_eq!(origin.ancestor, 0);
+    assert_eq!(origin.block_number, 0);
+    assert!(origin.canonized_route.is_empty());
+    assert_eq!(
+        origin.decanonized_route,
+        vec![b1.hash().clone(), b2.hash().clone()]
+    );
+
+    // genesis and side chain blocks can't be rolled back
+    assert_eq!(
+        store.rollback_origin(b0.hash()).unwrap_err(),
+        Error::CannotDecanonize
+    );
+    assert_eq!(
+        store.rollback_origin(s1.hash()).unwrap_err(),
+        Error::CannotDecanonize
+    );
+
+    // side chain block origin is computed against the best block
+    match store.stored_block_origin(s1.hash()) {
+        Ok(BlockOrigin::SideChain(origin)) => {
+            assert!(origin.canonized_route.is_empty());
+            assert_eq!(
+                origin.decanonized_route,
+                vec![b1.hash().clone(), b2.hash().clone()]
+            );
+        }
+        origin => panic!("unexpected block origin: {:?}", origin),
+    }
+    match store.stored_block_origin(b2.hash()) {
+        Ok(BlockOrigin::KnownBlock) => (),
+        origin => panic!("unexpected block origin: {:?}", origin),
+    }
+
+    // rollback is limited by the max fork route
+    store.set_max_fork_route(1);
+    assert_eq!(
+        store
+            .rollback_origin(b2.hash())
+            .unwrap()
+            .decanonized_route
+            .len(),
+        1
+    );
+    assert_eq!(
+        store.rollback_origin(b1.hash()).unwrap_err(),
+        Error::AncientFork
+    );
+}
+
+#[test]
+fn snapshot_is_not_affected_by_reorg() {
+    let store = BlockChainDatabase::open(MemoryDatabase::default());
+    let b0: IndexedBlock = zebra_test_data::block_h0().into();
+    let b1: IndexedBlock = zebra_test_data::block_h1().into();
+    let b2: IndexedBlock = zebra_test_data::block_h2().into();
+
+    store.insert(b0.clone()).unwrap();
+    store.insert(b1.clone()).unwrap();
+    store.canonize(b0.hash()).unwrap();
+    store.canonize(b1.hash()).unwrap();
+
+    let snapshot = store.snapshot();
+
+    store.insert(b2.clone()).unwrap();
+    let side_chain_origin = SideChainOrigin {
+        ancestor: 1,
+        canonized_route: Vec::new(),
+        decanonized_route: Vec::new(),
+        block_number: 2,
+    };
+    let fork = store.fork(side_chain_origin).unwrap();
+    fork.store().canonize(b2.hash()).unwrap();
+    store.switch_to_fork(fork).unwrap();
+    assert_eq!(2, store.best_block().number);
+
+    assert_eq!(1, snapshot.best_block().number);
+    assert_eq!(b1.hash(), &snapshot.best_block().hash);
+    assert_eq!(None, snapshot.block_hash(2));
+    assert!(!snapshot.contains_block(b2.hash().clone().into()));
+    assert!(snapshot.insert(b2.clone()).is_err());
+
+    // snapshot of the trait object is also consistent
+    let snapshot = Store::snapshot(&store);
+    BlockChain::rollback_best(&store).unwrap();
+    assert_eq!(1, store.best_block().number);
+    assert_eq!(2, snapshot.best_block().number);
+    assert!(snapshot.transaction(&b2.transactions[0].hash).is_some());
+}
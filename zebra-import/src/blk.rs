@@ -1,49 +1,127 @@
 use block::Block;
-use fs::read_blk_dir;
-use ser::{deserialize_iterator, Error as ReaderError, ReadIterator};
-use std::collections::BTreeSet;
+use error::Error;
+use fs::{read_blk_dir, BlkEntry};
+use std::collections::{btree_set, BTreeSet};
+use std::io::{Seek, SeekFrom};
 use std::{fs, io, path};
+use stream::{open_blk_stream_at, BlkStream};
 
-pub fn open_blk_file<P>(path: P) -> Result<BlkFile, io::Error>
+/// Position in the bitcoind database.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlkPosition {
+    /// Path of the blk file.
+    pub path: path::PathBuf,
+    /// Offset in the blk file.
+    pub offset: u64,
+}
+
+pub fn open_blk_file<P>(
+    path: P,
+    magic: u32,
+    strict: bool,
+    offset: u64,
+) -> Result<BlkFile, io::Error>
 where
     P: AsRef<path::Path>,
 {
-    trace!("Opening blk file: {:?}", path.as_ref());
-    let file = try!(fs::File::open(path));
+    trace!("Opening blk file: {:?} at offset {}", path.as_ref(), offset);
+    let mut file = try!(fs::File::open(path.as_ref()));
+    try!(file.seek(SeekFrom::Start(offset)));
     let blk_file = BlkFile {
-        reader: deserialize_iterator(file),
+        path: path.as_ref().to_path_buf(),
+        stream: open_blk_stream_at(file, magic, offset),
+        strict: strict,
     };
     Ok(blk_file)
 }
 
 pub struct BlkFile {
-    reader: ReadIterator<fs::File, Block>,
+    path: path::PathBuf,
+    stream: BlkStream<fs::File>,
+    strict: bool,
+}
+
+impl BlkFile {
+    /// Returns position right after the last read block.
+    pub fn position(&self) -> BlkPosition {
+        BlkPosition {
+            path: self.path.clone(),
+            offset: self.stream.offset(),
+        }
+    }
 }
 
 impl Iterator for BlkFile {
-    type Item = Result<Block, ReaderError>;
+    type Item = Result<Block, Error>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.reader.next()
+        match self.stream.next() {
+            // truncated record or garbage at the end of file (partially downloaded database)
+            Some(Err(ref err)) if !self.strict && err.is_corrupted_tail() => {
+                warn!(
+                    "Skipping the rest of blk file {:?} at offset {}: {:?}",
+                    self.path,
+                    self.stream.record_offset(),
+                    err
+                );
+                None
+            }
+            next => next,
+        }
     }
 }
 
-/// Creates iterator over bitcoind database blocks
-pub fn open_blk_dir<P>(path: P) -> Result<BlkDir, io::Error>
+/// Creates iterator over bitcoind database blocks.
+///
+/// Every block must be prefixed with the network `magic`. Unless `strict` is true, the truncated
+/// block record or unexpected magic are treated as the end of current file and reading continues
+/// with the next file.
+pub fn open_blk_dir<P>(path: P, magic: u32, strict: bool) -> Result<BlkDir, io::Error>
 where
     P: AsRef<path::Path>,
 {
     let files = read_blk_dir(path)?.collect::<Result<BTreeSet<_>, _>>()?;
 
-    let iter = files
+    let blk_dir = BlkDir {
+        files: files.into_iter(),
+        current: None,
+        magic: magic,
+        strict: strict,
+    };
+
+    Ok(blk_dir)
+}
+
+/// Creates iterator over bitcoind database blocks, starting at given position.
+/// All files that are ordered before the position file are skipped.
+pub fn resume_blk_dir<P>(
+    path: P,
+    magic: u32,
+    strict: bool,
+    position: &BlkPosition,
+) -> Result<BlkDir, io::Error>
+where
+    P: AsRef<path::Path>,
+{
+    let files = read_blk_dir(path)?
+        .collect::<Result<BTreeSet<_>, _>>()?
         .into_iter()
-        // flatten results...
-        .flat_map(|file| open_blk_file(file.path))
-        // flat iterators over each block in each file
-        .flat_map(|file| file);
+        .filter(|file| file.path >= position.path)
+        .collect::<BTreeSet<_>>();
+    let mut files = files.into_iter();
+    let current = match files.next() {
+        Some(ref file) if file.path == position.path => {
+            Some(open_blk_file(&file.path, magic, strict, position.offset)?)
+        }
+        Some(file) => Some(open_blk_file(file.path, magic, strict, 0)?),
+        None => None,
+    };
 
     let blk_dir = BlkDir {
-        iter: Box::new(iter),
+        files: files,
+        current: current,
+        magic: magic,
+        strict: strict,
     };
 
     Ok(blk_dir)
@@ -51,13 +129,126 @@ where
 
 /// Bitcoind database blocks iterator
 pub struct BlkDir {
-    iter: Box<Iterator<Item = Result<Block, ReaderError>>>,
+    files: btree_set::IntoIter<BlkEntry>,
+    current: Option<BlkFile>,
+    magic: u32,
+    strict: bool,
+}
+
+impl BlkDir {
+    /// Returns position right after the last read block.
+    pub fn position(&self) -> Option<BlkPosition> {
+        self.current.as_ref().map(BlkFile::position)
+    }
 }
 
 impl Iterator for BlkDir {
-    type Item = Result<Block, ReaderError>;
+    type Item = Result<Block, Error>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.iter.next()
+        loop {
+            if let Some(next) = self.current.as_mut().and_then(Iterator::next) {
+                return Some(next);
+            }
+
+            let file = match self.files.next() {
+                Some(file) => file,
+                None => return None,
+            };
+            match open_blk_file(&file.path, self.magic, self.strict, 0) {
+                Ok(blk_file) => self.current = Some(blk_file),
+                Err(err) => warn!("Failed to open blk file {:?}: {}", file.path, err),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate tempdir;
+    extern crate zebra_test_data;
+
+    use self::tempdir::TempDir;
+    use super::{open_blk_dir, resume_blk_dir, BlkPosition};
+    use error::Error;
+    use std::fs::File;
+    use std::io::Write;
+    use stream::tests::{record, MAGIC};
+    use zebra_chain::IndexedBlock;
+
+    fn write_blk_file(dir: &TempDir, name: &str, data: &[u8]) {
+        File::create(dir.path().join(name))
+            .unwrap()
+            .write_all(data)
+            .unwrap();
+    }
+
+    fn read_blk_dir(dir: &TempDir, strict: bool) -> Vec<Result<IndexedBlock, Error>> {
+        open_blk_dir(dir.path(), MAGIC, strict)
+            .unwrap()
+            .map(|block| block.map(|block| block.block))
+            .collect()
+    }
+
+    #[test]
+    fn truncated_blk_file_is_skipped() {
+        let b0 = zebra_test_data::block_h0();
+        let b1 = zebra_test_data::block_h1();
+        let b2 = zebra_test_data::block_h2();
+        let (i0, i1, i2): (IndexedBlock, IndexedBlock, IndexedBlock) =
+            (b0.clone().into(), b1.clone().into(), b2.clone().into());
+
+        let dir = TempDir::new("truncated_blk_file_is_skipped").unwrap();
+        let mut data = record(MAGIC, &b0);
+        let truncated_record = record(MAGIC, &b1);
+        data.extend_from_slice(&truncated_record[..truncated_record.len() / 2]);
+        write_blk_file(&dir, "blk00000.dat", &data);
+        let mut data = record(MAGIC, &b1);
+        data.extend(record(MAGIC + 1, &b2));
+        write_blk_file(&dir, "blk00001.dat", &data);
+        write_blk_file(&dir, "blk00002.dat", &record(MAGIC, &b2));
+
+        assert_eq!(
+            read_blk_dir(&dir, false),
+            vec![Ok(i0.clone()), Ok(i1), Ok(i2)]
+        );
+        assert_eq!(
+            &read_blk_dir(&dir, true)[..2],
+            &[Ok(i0), Err(Error::UnexpectedEnd)]
+        );
+    }
+
+    #[test]
+    fn blk_dir_is_resumed_from_position() {
+        let b0 = zebra_test_data::block_h0();
+        let b1 = zebra_test_data::block_h1();
+        let b2 = zebra_test_data::block_h2();
+        let (i1, i2): (IndexedBlock, IndexedBlock) = (b1.clone().into(), b2.clone().into());
+
+        let dir = TempDir::new("blk_dir_is_resumed_from_position").unwrap();
+        write_blk_file(&dir, "blk00000.dat", &record(MAGIC, &b0));
+        let mut data = record(MAGIC, &b0);
+        data.extend(record(MAGIC, &b1));
+        write_blk_file(&dir, "blk00001.dat", &data);
+        write_blk_file(&dir, "blk00002.dat", &record(MAGIC, &b2));
+
+        let mut blk_dir = open_blk_dir(dir.path(), MAGIC, true).unwrap();
+        assert_eq!(blk_dir.position(), None);
+        blk_dir.next().unwrap().unwrap();
+        blk_dir.next().unwrap().unwrap();
+        let position = blk_dir.position().unwrap();
+        assert_eq!(
+            position,
+            BlkPosition {
+                path: dir.path().join("blk00001.dat"),
+                offset: record(MAGIC, &b0).len() as u64,
+            }
+        );
+
+        let blocks = resume_blk_dir(dir.path(), MAGIC, true, &position)
+            .unwrap()
+            .map(|block| block.map(|block| block.block))
+            .collect::<Vec<_>>();
+        assert_eq!(blocks, vec![Ok(i1), Ok(i2)]);
     }
 }
@@ -0,0 +1,34 @@
+use hash::H32;
+use ser::Error as ReaderError;
+
+/// Blocks import error.
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    /// Failed to read from the underlying file or stream.
+    Io(String),
+    /// Stream has ended in the middle of the block record.
+    UnexpectedEnd,
+    /// Block record is prefixed with magic of another network.
+    InvalidMagic(H32),
+    /// Block record size exceeds the maximal block size.
+    TooLargeBlock(usize),
+    /// Failed to deserialize the block.
+    Block(ReaderError),
+}
+
+impl Error {
+    /// Returns true if the error means that the rest of the file is unusable (truncated
+    /// record or garbage instead of the next record).
+    pub fn is_corrupted_tail(&self) -> bool {
+        match *self {
+            Error::UnexpectedEnd | Error::InvalidMagic(_) => true,
+            _ => false,
+        }
+    }
+}
+
+impl From<ReaderError> for Error {
+    fn from(err: ReaderError) -> Self {
+        Error::Block(err)
+    }
+}
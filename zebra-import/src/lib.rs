@@ -8,8 +8,12 @@ extern crate zebra_serialization as ser;
 
 mod blk;
 mod block;
+mod error;
 mod fs;
+mod stream;
 
 pub use zebra_primitives::{bytes, hash};
 
-pub use blk::{open_blk_dir, BlkDir};
+pub use blk::{open_blk_dir, resume_blk_dir, BlkDir, BlkPosition};
+pub use error::Error;
+pub use stream::{open_blk_stream, BlkStream};
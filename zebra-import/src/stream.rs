@@ -0,0 +1,247 @@
+use block::Block;
+use error::Error;
+use hash::H32;
+use ser::deserialize;
+use std::io::{self, Read};
+
+/// Maximal size of the block, accepted from the stream.
+/// Larger blocks are rejected before reading their body into the memory.
+const MAX_BLOCK_SIZE: usize = 2_000_000;
+/// Size of the block record header: magic + block size.
+const RECORD_HEADER_SIZE: usize = 8;
+
+/// Creates iterator over blocks, read from the stream of magic-delimited, length-prefixed
+/// blocks (the same format is used by bitcoind database files). Blocks with network magic,
+/// other than `magic`, are rejected.
+pub fn open_blk_stream<R>(reader: R, magic: u32) -> BlkStream<R>
+where
+    R: Read,
+{
+    open_blk_stream_at(reader, magic, 0)
+}
+
+/// Creates iterator over blocks stream, which is already read up to given `offset`.
+pub fn open_blk_stream_at<R>(reader: R, magic: u32, offset: u64) -> BlkStream<R>
+where
+    R: Read,
+{
+    BlkStream {
+        reader: io::BufReader::new(reader),
+        magic: magic_bytes(magic),
+        offset: offset,
+        record_offset: offset,
+        finished: false,
+    }
+}
+
+/// Blocks stream iterator.
+pub struct BlkStream<R> {
+    reader: io::BufReader<R>,
+    magic: H32,
+    /// Number of bytes read from the stream.
+    offset: u64,
+    /// Offset of the last read block record.
+    record_offset: u64,
+    finished: bool,
+}
+
+impl<R: Read> BlkStream<R> {
+    /// Returns number of bytes read from the stream.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Returns stream offset of the last read (or failed to read) block record.
+    pub fn record_offset(&self) -> u64 {
+        self.record_offset
+    }
+
+    /// Read next block record (including magic and size).
+    /// Returns None if stream has ended before the next record.
+    fn read_record(&mut self) -> Result<Option<Vec<u8>>, Error> {
+        // blocks could be separated by zero-filled gaps (see Block::deserialize)
+        let mut header = [0u8; RECORD_HEADER_SIZE];
+        loop {
+            self.record_offset = self.offset;
+            if self.read_full(&mut header[..1])? == 0 {
+                return Ok(None);
+            }
+            if header[0] != 0 {
+                break;
+            }
+        }
+
+        if self.read_full(&mut header[1..])? != RECORD_HEADER_SIZE - 1 {
+            return Err(Error::UnexpectedEnd);
+        }
+
+        let magic: H32 = header[..4].into();
+        if magic != self.magic {
+            return Err(Error::InvalidMagic(magic));
+        }
+
+        let block_size = u32::from(header[4])
+            | u32::from(header[5]) << 8
+            | u32::from(header[6]) << 16
+            | u32::from(header[7]) << 24;
+        let block_size = block_size as usize;
+        if block_size > MAX_BLOCK_SIZE {
+            return Err(Error::TooLargeBlock(block_size));
+        }
+
+        let mut record = vec![0u8; RECORD_HEADER_SIZE + block_size];
+        record[..RECORD_HEADER_SIZE].copy_from_slice(&header);
+        if self.read_full(&mut record[RECORD_HEADER_SIZE..])? != block_size {
+            return Err(Error::UnexpectedEnd);
+        }
+
+        Ok(Some(record))
+    }
+
+    /// Read until the buffer is filled, or the end of stream is reached.
+    /// Returns number of bytes read.
+    fn read_full(&mut self, buffer: &mut [u8]) -> Result<usize, Error> {
+        let mut read = 0;
+        while read < buffer.len() {
+            match self.reader.read(&mut buffer[read..]) {
+                Ok(0) => break,
+                Ok(n) => read += n,
+                Err(ref err) if err.kind() == io::ErrorKind::Interrupted => (),
+                Err(err) => return Err(Error::Io(format!("{}", err))),
+            }
+        }
+        self.offset += read as u64;
+        Ok(read)
+    }
+}
+
+impl<R: Read> Iterator for BlkStream<R> {
+    type Item = Result<Block, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        // the stream can't be resynchronized after an error => stop iteration
+        let result = match self.read_record() {
+            Ok(Some(record)) => deserialize(&record[..]).map_err(Error::from),
+            Ok(None) => {
+                self.finished = true;
+                return None;
+            }
+            Err(err) => Err(err),
+        };
+        if result.is_err() {
+            self.finished = true;
+        }
+
+        Some(result)
+    }
+}
+
+/// Network magic, as it is serialized in the stream.
+fn magic_bytes(magic: u32) -> H32 {
+    H32::from([
+        magic as u8,
+        (magic >> 8) as u8,
+        (magic >> 16) as u8,
+        (magic >> 24) as u8,
+    ])
+}
+
+#[cfg(test)]
+pub mod tests {
+    extern crate zebra_test_data;
+
+    use super::{magic_bytes, open_blk_stream};
+    use error::Error;
+    use ser::serialize;
+    use std::io::{self, Read};
+
+    pub const MAGIC: u32 = 0x6427e924;
+
+    /// Reader that returns at most `chunk` bytes on every read, like a pipe does.
+    struct ChunkedReader {
+        data: Vec<u8>,
+        position: usize,
+        chunk: usize,
+    }
+
+    impl Read for ChunkedReader {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let len = ::std::cmp::min(
+                ::std::cmp::min(buf.len(), self.chunk),
+                self.data.len() - self.position,
+            );
+            buf[..len].copy_from_slice(&self.data[self.position..self.position + len]);
+            self.position += len;
+            Ok(len)
+        }
+    }
+
+    /// Serialize block record: magic + block size + block.
+    pub fn record(magic: u32, block: &::zebra_chain::Block) -> Vec<u8> {
+        let block = serialize(block);
+        let mut record = magic_bytes(magic).to_vec();
+        let size = block.len() as u32;
+        record.extend_from_slice(&[
+            size as u8,
+            (size >> 8) as u8,
+            (size >> 16) as u8,
+            (size >> 24) as u8,
+        ]);
+        record.extend_from_slice(&block);
+        record
+    }
+
+    #[test]
+    fn blocks_are_read_from_chunked_stream() {
+        let b0 = zebra_test_data::block_h0();
+        let b1 = zebra_test_data::block_h1();
+        let mut data = record(MAGIC, &b0);
+        data.extend_from_slice(&[0, 0, 0]);
+        data.extend(record(MAGIC, &b1));
+
+        for chunk in &[1, 3, 7, 4096] {
+            let reader = ChunkedReader {
+                data: data.clone(),
+                position: 0,
+                chunk: *chunk,
+            };
+            let blocks = open_blk_stream(reader, MAGIC)
+                .map(|block| block.map(|block| block.block))
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap();
+            assert_eq!(blocks, vec![b0.clone().into(), b1.clone().into()]);
+        }
+    }
+
+    #[test]
+    fn invalid_magic_stops_stream() {
+        let b0 = zebra_test_data::block_h0();
+        let mut data = record(MAGIC + 1, &b0);
+        data.extend(record(MAGIC, &b0));
+
+        let mut stream = open_blk_stream(&data[..], MAGIC);
+        assert_eq!(
+            stream.next(),
+            Some(Err(Error::InvalidMagic(magic_bytes(MAGIC + 1))))
+        );
+        assert_eq!(stream.record_offset(), 0);
+        assert!(stream.next().is_none());
+    }
+
+    #[test]
+    fn truncated_block_is_rejected() {
+        let b0 = zebra_test_data::block_h0();
+        let mut data = vec![0, 0];
+        data.extend(record(MAGIC, &b0));
+        data.pop();
+
+        let mut stream = open_blk_stream(&data[..], MAGIC);
+        assert_eq!(stream.next(), Some(Err(Error::UnexpectedEnd)));
+        assert_eq!(stream.record_offset(), 2);
+        assert!(stream.next().is_none());
+    }
+}
@@ -0,0 +1,162 @@
+//! Bech32 encoding, used by Sapling payment addresses.
+//!
+//! https://github.com/bitcoin/bips/blob/master/bip-0173.mediawiki
+
+use Error;
+
+/// Bech32 alphabet.
+const CHARSET: &'static [u8] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+/// BCH code generator coefficients.
+const GENERATOR: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+/// Number of 5-bit groups in the checksum.
+const CHECKSUM_LEN: usize = 6;
+
+fn polymod(values: &[u8]) -> u32 {
+    let mut checksum = 1u32;
+    for value in values {
+        let top = checksum >> 25;
+        checksum = ((checksum & 0x1ffffff) << 5) ^ (*value as u32);
+        for (i, generator) in GENERATOR.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                checksum ^= *generator;
+            }
+        }
+    }
+    checksum
+}
+
+fn expand_hrp(hrp: &[u8]) -> Vec<u8> {
+    let mut result: Vec<u8> = hrp.iter().map(|c| c >> 5).collect();
+    result.push(0);
+    result.extend(hrp.iter().map(|c| c & 0x1f));
+    result
+}
+
+/// Encode 5-bit groups with given human-readable part.
+pub fn encode(hrp: &str, data: &[u8]) -> String {
+    let mut values = expand_hrp(hrp.as_bytes());
+    values.extend_from_slice(data);
+    values.extend_from_slice(&[0u8; CHECKSUM_LEN]);
+    let checksum = polymod(&values) ^ 1;
+
+    let mut result = String::with_capacity(hrp.len() + 1 + data.len() + CHECKSUM_LEN);
+    result.push_str(hrp);
+    result.push('1');
+    result.extend(data.iter().map(|d| CHARSET[*d as usize] as char));
+    for i in 0..CHECKSUM_LEN {
+        let group = (checksum >> (5 * (CHECKSUM_LEN - 1 - i))) & 0x1f;
+        result.push(CHARSET[group as usize] as char);
+    }
+    result
+}
+
+/// Decode bech32 string into human-readable part and 5-bit groups.
+pub fn decode(s: &str) -> Result<(String, Vec<u8>), Error> {
+    let has_lowercase = s.bytes().any(|c| c >= b'a' && c <= b'z');
+    let has_uppercase = s.bytes().any(|c| c >= b'A' && c <= b'Z');
+    if has_lowercase && has_uppercase {
+        return Err(Error::InvalidAddress);
+    }
+
+    let s = s.to_lowercase();
+    let separator = try!(s.rfind('1').ok_or(Error::InvalidAddress));
+    if separator == 0 || separator + 1 + CHECKSUM_LEN > s.len() {
+        return Err(Error::InvalidAddress);
+    }
+
+    let (hrp, data) = (&s[..separator], &s[separator + 1..]);
+    if hrp.bytes().any(|c| c < 33 || c > 126) {
+        return Err(Error::InvalidAddress);
+    }
+
+    let mut data = try!(data
+        .bytes()
+        .map(|c| {
+            CHARSET
+                .iter()
+                .position(|x| *x == c)
+                .map(|position| position as u8)
+                .ok_or(Error::InvalidAddress)
+        })
+        .collect::<Result<Vec<_>, _>>());
+
+    let mut values = expand_hrp(hrp.as_bytes());
+    values.extend_from_slice(&data);
+    if polymod(&values) != 1 {
+        return Err(Error::InvalidChecksum);
+    }
+
+    let data_len = data.len() - CHECKSUM_LEN;
+    data.truncate(data_len);
+    Ok((hrp.to_owned(), data))
+}
+
+/// Regroup bits of `data` from `from`-bit groups into `to`-bit groups.
+pub fn convert_bits(data: &[u8], from: u32, to: u32, pad: bool) -> Result<Vec<u8>, Error> {
+    let mut accumulator = 0u32;
+    let mut bits = 0u32;
+    let max_value = (1u32 << to) - 1;
+    let mut result = Vec::with_capacity(data.len() * from as usize / to as usize + 1);
+    for value in data {
+        let value = *value as u32;
+        if value >> from != 0 {
+            return Err(Error::InvalidAddress);
+        }
+
+        accumulator = (accumulator << from) | value;
+        bits += from;
+        while bits >= to {
+            bits -= to;
+            result.push(((accumulator >> bits) & max_value) as u8);
+        }
+    }
+
+    if pad {
+        if bits > 0 {
+            result.push(((accumulator << (to - bits)) & max_value) as u8);
+        }
+    } else if bits >= from || ((accumulator << (to - bits)) & max_value) != 0 {
+        return Err(Error::InvalidAddress);
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{convert_bits, decode, encode};
+    use Error;
+
+    #[test]
+    fn test_bech32_valid_checksums() {
+        let valid = [
+            "A12UEL5L",
+            "a12uel5l",
+            "abcdef1qpzry9x8gf2tvdw0s3jn54khce6mua7lmqqqxw",
+            "split1checkupstagehandshakeupstreamerranterredcaperred2y9e3w",
+            "?1ezyfcl",
+        ];
+
+        for s in valid.iter() {
+            let (hrp, data) = decode(s).unwrap();
+            assert_eq!(encode(&hrp, &data), s.to_lowercase());
+        }
+    }
+
+    #[test]
+    fn test_bech32_invalid() {
+        assert_eq!(decode("a12UEL5L"), Err(Error::InvalidAddress));
+        assert_eq!(decode("pzry9x0s0muk"), Err(Error::InvalidAddress));
+        assert_eq!(decode("1pzry9x0s0muk"), Err(Error::InvalidAddress));
+        assert_eq!(decode("x1b4n0q5v"), Err(Error::InvalidAddress));
+        assert_eq!(decode("a12uel5m"), Err(Error::InvalidChecksum));
+    }
+
+    #[test]
+    fn test_convert_bits() {
+        let data = vec![0xff, 0x00, 0x12];
+        let groups = convert_bits(&data, 8, 5, true).unwrap();
+        assert_eq!(groups.len(), 5);
+        assert_eq!(convert_bits(&groups, 5, 8, false).unwrap(), data);
+    }
+}
@@ -4,7 +4,9 @@ use hash::{H264, H520};
 use network::Network;
 use secp256k1::key;
 use std::fmt;
-use {Address, Error, Private, Public, Secret, Type, SECP256K1};
+use {
+    Address, CompactSignature, Error, Message, Private, Public, Secret, Signature, Type, SECP256K1,
+};
 
 pub struct KeyPair {
     private: Private,
@@ -76,6 +78,22 @@ impl KeyPair {
         }
     }
 
+    /// Signs the message, using RFC6979 deterministic nonce. Signing the same message
+    /// with the same key always produces the same (low-S, DER-encoded) signature.
+    pub fn sign_deterministic(&self, message: &Message) -> Signature {
+        self.private
+            .sign(message)
+            .expect("secret is checked when key pair is constructed; qed")
+    }
+
+    /// Signs the message with compact (recoverable) signature. The public key, and
+    /// hence the address of this key pair, could be recovered from the signature.
+    pub fn sign_compact(&self, message: &Message) -> CompactSignature {
+        self.private
+            .sign_compact(message)
+            .expect("secret is checked when key pair is constructed; qed")
+    }
+
     pub fn address(&self) -> Address {
         Address {
             kind: Type::P2PKH,
@@ -88,8 +106,10 @@ impl KeyPair {
 #[cfg(test)]
 mod tests {
     use super::KeyPair;
+    use hash::{H256, H520};
+    use network::Network;
     use zebra_crypto::dhash256;
-    use Public;
+    use {CompactSignature, Error, Private, Public};
 
     /// Tests from:
     /// https://github.com/zcash/zcash/blob/66e39a0dd6ffd77bcbede1943195dd456f859cd6/src/test/key_tests.cpp#L25
@@ -202,4 +222,52 @@ mod tests {
         assert!(check_recover_compact(SECRET_2, message));
         assert!(check_recover_compact(SECRET_2C, message));
     }
+
+    #[test]
+    fn test_sign_deterministic() {
+        let message = dhash256(b"Very deterministic message");
+        let kp = KeyPair::from_private(SECRET_1.into()).unwrap();
+        assert_eq!(
+            kp.sign_deterministic(&message),
+            kp.sign_deterministic(&message)
+        );
+        assert_eq!(kp.sign_deterministic(&message), SIGN_1.into());
+    }
+
+    #[test]
+    fn test_keypair_sign_compact_recover_address() {
+        let message = dhash256(b"Very deterministic message");
+        for secret in &[SECRET_1, SECRET_1C, SECRET_2, SECRET_2C] {
+            let kp = KeyPair::from_private(secret.parse().unwrap()).unwrap();
+            let signature = kp.sign_compact(&message);
+            let recovered = Public::recover_compact(&message, &signature).unwrap();
+            assert_eq!(recovered.address_hash(), kp.address().hash);
+        }
+
+        let kp = KeyPair::from_private(SECRET_1.into()).unwrap();
+        let mut signature = kp.sign_compact(&message).to_vec();
+        signature[0] = 0;
+        let signature: CompactSignature = H520::from(&signature[..]).into();
+        assert_eq!(
+            Public::recover_compact(&message, &signature),
+            Err(Error::InvalidSignature)
+        );
+    }
+
+    /// RFC6979 test vector: private key 1, message sha256("Satoshi Nakamoto")
+    #[test]
+    fn test_sign_deterministic_rfc6979_vector() {
+        let private = Private {
+            network: Network::Mainnet,
+            secret: H256::from("0000000000000000000000000000000000000000000000000000000000000001"),
+            compressed: true,
+        };
+        let kp = KeyPair::from_private(private).unwrap();
+        let message =
+            H256::from("a0dc65ffca799873cbea0ac274015b9526505daaaed385155425f7337704883e");
+        assert_eq!(
+            kp.sign_deterministic(&message),
+            "3045022100934b1ea10a4b3c1757e2b0c017d0b6143ce3c9a7e6a4a49860d7a6ab210ee3d802202442ce9d2b916064108014783e923ec36b49743e2ffa1c4496f01a512aafd9e5".into()
+        );
+    }
 }
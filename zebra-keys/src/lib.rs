@@ -10,13 +10,16 @@ extern crate zebra_crypto;
 extern crate zebra_primitives;
 
 mod address;
+mod bech32;
 mod display;
 mod error;
 pub mod generator;
 mod keypair;
 mod network;
+mod payment_address;
 mod private;
 mod public;
+mod shielded_address;
 mod signature;
 
 pub use zebra_primitives::{bytes, hash};
@@ -26,8 +29,10 @@ pub use display::DisplayLayout;
 pub use error::Error;
 pub use keypair::KeyPair;
 pub use network::Network;
+pub use payment_address::PaymentAddress;
 pub use private::Private;
 pub use public::Public;
+pub use shielded_address::{SaplingAddress, SproutAddress};
 pub use signature::{CompactSignature, Signature};
 
 use hash::{H160, H256};
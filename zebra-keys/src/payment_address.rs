@@ -0,0 +1,105 @@
+//! Transparent or shielded payment address.
+
+use base58::FromBase58;
+use bech32;
+use network::Network;
+use shielded_address::{SaplingAddress, SproutAddress};
+use std::fmt;
+use std::str::FromStr;
+use {Address, DisplayLayout, Error};
+
+/// Length of base58-decoded transparent address.
+const TRANSPARENT_ADDRESS_LEN: usize = 26;
+
+/// Any payment address, supported by Zcash.
+#[derive(Debug, PartialEq, Clone)]
+pub enum PaymentAddress {
+    /// Transparent (P2PKH or P2SH) address.
+    Transparent(Address),
+    /// Sprout shielded address.
+    Sprout(SproutAddress),
+    /// Sapling shielded address.
+    Sapling(SaplingAddress),
+}
+
+impl PaymentAddress {
+    /// The network of the address.
+    pub fn network(&self) -> Network {
+        match *self {
+            PaymentAddress::Transparent(ref address) => address.network,
+            PaymentAddress::Sprout(ref address) => address.network,
+            PaymentAddress::Sapling(ref address) => address.network,
+        }
+    }
+}
+
+impl fmt::Display for PaymentAddress {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            PaymentAddress::Transparent(ref address) => address.fmt(f),
+            PaymentAddress::Sprout(ref address) => address.fmt(f),
+            PaymentAddress::Sapling(ref address) => address.fmt(f),
+        }
+    }
+}
+
+impl FromStr for PaymentAddress {
+    type Err = Error;
+
+    /// Sapling addresses are bech32-encoded, while transparent and Sprout addresses
+    /// are base58-encoded and are distinguished by the length of the payload.
+    fn from_str(s: &str) -> Result<Self, Error>
+    where
+        Self: Sized,
+    {
+        if bech32::decode(s).is_ok() {
+            return s.parse().map(PaymentAddress::Sapling);
+        }
+
+        let data = try!(s.from_base58().map_err(|_| Error::InvalidAddress));
+        if data.len() == TRANSPARENT_ADDRESS_LEN {
+            Address::from_layout(&data).map(PaymentAddress::Transparent)
+        } else {
+            SproutAddress::from_layout(&data).map(PaymentAddress::Sprout)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PaymentAddress;
+    use network::Network;
+    use {Address, Error, Type};
+
+    #[test]
+    fn test_payment_address_from_str() {
+        let transparent = Address {
+            kind: Type::P2PKH,
+            network: Network::Mainnet,
+            hash: "ff197b14e502ab41f3bc8ccb48c4abac9eab35bc".into(),
+        };
+        assert_eq!(
+            "t1h8SqgtM3QM5e2M8EzhhT1yL2PXXtA6oqe".parse::<PaymentAddress>(),
+            Ok(PaymentAddress::Transparent(transparent))
+        );
+
+        match "zc8MfnkbU4wMEyu6ayLkXccy1cWGGSjAyDCeLxsMpWgD3VQzZgt5ojdvkiD1TUuA1xCG1gFmYzHDUR1meP1KX5b9vLYdBYp".parse::<PaymentAddress>() {
+            Ok(PaymentAddress::Sprout(ref address)) => assert_eq!(address.network, Network::Mainnet),
+            result => panic!("expected Sprout address, got {:?}", result),
+        }
+
+        match "ztestsapling1qyqqqqqqqqqqqqqqqqpqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqq7z4d7a".parse::<PaymentAddress>() {
+            Ok(PaymentAddress::Sapling(ref address)) => assert_eq!(address.network, Network::Testnet),
+            result => panic!("expected Sapling address, got {:?}", result),
+        }
+
+        assert_eq!(
+            "t1h8SqgtM3QM5e2M8EzhhT1yL2PXXtA6oqf".parse::<PaymentAddress>(),
+            Err(Error::InvalidChecksum)
+        );
+        assert_eq!(
+            "not an address".parse::<PaymentAddress>(),
+            Err(Error::InvalidAddress)
+        );
+    }
+}
@@ -23,11 +23,33 @@ pub struct Private {
 }
 
 impl Private {
+    /// Parses private key from the base58 WIF string. The network and the compression flag
+    /// are detected from the prefix and the length of the decoded key.
+    pub fn from_wif(wif: &str) -> Result<Self, Error> {
+        let data = try!(wif.from_base58().map_err(|_| Error::InvalidPrivate));
+        Private::from_layout(&data)
+    }
+
+    /// Encodes the secret as base58 WIF string for given network. Public key that is
+    /// derived from compressed WIF is also compressed.
+    pub fn to_wif(&self, network: Network, compressed: bool) -> String {
+        let private = Private {
+            network: network,
+            secret: self.secret.clone(),
+            compressed: compressed,
+        };
+        private.layout().to_base58()
+    }
+
+    /// Signs the message. The nonce is generated deterministically (RFC6979) by the
+    /// secp256k1 backend, so the same message is always signed with the same signature.
+    /// The signature always has low S value.
     pub fn sign(&self, message: &Message) -> Result<Signature, Error> {
         let context = &SECP256K1;
         let secret = try!(key::SecretKey::from_slice(context, &*self.secret));
         let message = try!(SecpMessage::from_slice(&**message));
-        let signature = try!(context.sign(&message, &secret));
+        let mut signature = try!(context.sign(&message, &secret));
+        signature.normalize_s(context);
         let data = signature.serialize_der(context);
         Ok(data.into())
     }
@@ -118,7 +140,7 @@ impl fmt::Debug for Private {
 
 impl fmt::Display for Private {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        self.layout().to_base58().fmt(f)
+        self.to_wif(self.network, self.compressed).fmt(f)
     }
 }
 
@@ -129,8 +151,7 @@ impl FromStr for Private {
     where
         Self: Sized,
     {
-        let hex = try!(s.from_base58().map_err(|_| Error::InvalidPrivate));
-        Private::from_layout(&hex)
+        Private::from_wif(s)
     }
 }
 
@@ -145,6 +166,19 @@ mod tests {
     use super::Private;
     use hash::H256;
     use network::Network;
+    use {Error, KeyPair};
+
+    /// https://en.bitcoin.it/wiki/Wallet_import_format
+    const WIF_SECRET: &'static str =
+        "0c28fca386c7a227600b2fe50b7cae11ec86d3bf1fbe471be89827e19d72aa1d";
+    const WIF_MAINNET: &'static str = "5HueCGU8rMjxEXxiPuD5BDku4MkFqeZyd4dZ1jvhTVqvbTLvyTJ";
+    const WIF_MAINNET_C: &'static str = "KwdMAjGmerYanjeui5SHS7JkmpZvVipYvB2LJGU1ZxJwYvP98617";
+    const WIF_TESTNET: &'static str = "91gGn1HgSap6CbU12F6z3pJri26xzp7Ay1VW6NHCoEayNXwRpu2";
+    const WIF_TESTNET_C: &'static str = "cMzLdeGd5vEqxB8B6VFQoRopQ3sLAAvEzDAoQgvX54xwofSWj1fx";
+    const ADDRESS_MAINNET: &'static str = "t1Z3Fi2XbqcwtW7DYFzRentJiCZTz4o9xDH";
+    const ADDRESS_MAINNET_C: &'static str = "t1dg6GZ6ZqUV4G6RCKLLSae5rsFq1jhvZyQ";
+    const ADDRESS_TESTNET: &'static str = "tmQt12s21EHTPeMQyvijPeYyToYYocf6YmB";
+    const ADDRESS_TESTNET_C: &'static str = "tmVWqbPayE8zZQLcdz4eBSJkcUEuqH1kor1";
 
     #[test]
     fn test_private_to_string() {
@@ -177,4 +211,40 @@ mod tests {
             "5KSCKP8NUyBZPCCQusxRwgmz9sfvJQEgbGukmmHepWw5Bzp95mu".into()
         );
     }
+
+    #[test]
+    fn test_private_wif_round_trip() {
+        let vectors = [
+            (WIF_MAINNET, Network::Mainnet, false, ADDRESS_MAINNET),
+            (WIF_MAINNET_C, Network::Mainnet, true, ADDRESS_MAINNET_C),
+            (WIF_TESTNET, Network::Testnet, false, ADDRESS_TESTNET),
+            (WIF_TESTNET_C, Network::Testnet, true, ADDRESS_TESTNET_C),
+        ];
+
+        for &(wif, network, compressed, address) in &vectors {
+            let private = Private::from_wif(wif).unwrap();
+            assert_eq!(private.secret, H256::from(WIF_SECRET));
+            assert_eq!(private.network, network);
+            assert_eq!(private.compressed, compressed);
+            assert_eq!(private.to_wif(network, compressed), wif);
+            assert_eq!(private.to_string(), wif);
+
+            let keypair = KeyPair::from_private(private).unwrap();
+            assert_eq!(keypair.public().len(), if compressed { 33 } else { 65 });
+            assert_eq!(keypair.address().to_string(), address);
+        }
+
+        let private = Private::from_wif(WIF_MAINNET).unwrap();
+        assert_eq!(private.to_wif(Network::Testnet, true), WIF_TESTNET_C);
+    }
+
+    #[test]
+    fn test_private_from_invalid_wif() {
+        assert_eq!(Private::from_wif("0OIl"), Err(Error::InvalidPrivate));
+        // last character is changed
+        assert_eq!(
+            Private::from_wif("5HueCGU8rMjxEXxiPuD5BDku4MkFqeZyd4dZ1jvhTVqvbTLvyTK"),
+            Err(Error::InvalidChecksum)
+        );
+    }
 }
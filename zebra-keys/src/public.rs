@@ -51,7 +51,13 @@ impl Public {
         }
     }
 
+    /// Recovers public key from the compact signature of the message. The first byte of
+    /// signature is 27 + recovery id (+ 4 if the key is compressed).
     pub fn recover_compact(message: &Message, signature: &CompactSignature) -> Result<Self, Error> {
+        if signature[0] < 27 || signature[0] > 34 {
+            return Err(Error::InvalidSignature);
+        }
+
         let context = &SECP256K1;
         let recovery_id = (signature[0] - 27) & 3;
         let compressed = (signature[0] - 27) & 4 != 0;
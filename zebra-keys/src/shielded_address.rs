@@ -0,0 +1,246 @@
+//! Sprout and Sapling shielded payment addresses.
+//!
+//! https://github.com/zcash/zips/blob/master/protocol/protocol.pdf (5.6.3, 5.6.4)
+
+use base58::{FromBase58, ToBase58};
+use bech32;
+use hash::H256;
+use network::Network;
+use std::fmt;
+use std::ops::Deref;
+use std::str::FromStr;
+use zebra_crypto::checksum;
+use {DisplayLayout, Error};
+
+/// Human-readable part of mainnet Sapling addresses.
+const SAPLING_MAINNET_HRP: &'static str = "zs";
+/// Human-readable part of testnet Sapling addresses.
+const SAPLING_TESTNET_HRP: &'static str = "ztestsapling";
+/// Length of serialized Sapling address: diversifier + diversified transmission key.
+const SAPLING_ADDRESS_LEN: usize = 43;
+/// Length of serialized Sprout address: prefix + paying key + transmission key + checksum.
+const SPROUT_ADDRESS_LEN: usize = 70;
+
+/// Sprout payment address.
+#[derive(Debug, PartialEq, Clone)]
+pub struct SproutAddress {
+    /// The network of the address.
+    pub network: Network,
+    /// Paying key (a_pk).
+    pub paying_key: H256,
+    /// Transmission key (pk_enc).
+    pub transmission_key: H256,
+}
+
+pub struct SproutAddressDisplayLayout([u8; SPROUT_ADDRESS_LEN]);
+
+impl Deref for SproutAddressDisplayLayout {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DisplayLayout for SproutAddress {
+    type Target = SproutAddressDisplayLayout;
+
+    fn layout(&self) -> Self::Target {
+        let mut result = [0u8; SPROUT_ADDRESS_LEN];
+
+        result[..2].copy_from_slice(&match self.network {
+            Network::Mainnet => [0x16, 0x9A],
+            Network::Testnet => [0x16, 0xB6],
+        });
+
+        result[2..34].copy_from_slice(&*self.paying_key);
+        result[34..66].copy_from_slice(&*self.transmission_key);
+        let cs = checksum(&result[0..66]);
+        result[66..].copy_from_slice(&*cs);
+        SproutAddressDisplayLayout(result)
+    }
+
+    fn from_layout(data: &[u8]) -> Result<Self, Error>
+    where
+        Self: Sized,
+    {
+        if data.len() != SPROUT_ADDRESS_LEN {
+            return Err(Error::InvalidAddress);
+        }
+
+        let cs = checksum(&data[..66]);
+        if &data[66..] != &*cs {
+            return Err(Error::InvalidChecksum);
+        }
+
+        let network = match (data[0], data[1]) {
+            (0x16, 0x9A) => Network::Mainnet,
+            (0x16, 0xB6) => Network::Testnet,
+            _ => return Err(Error::InvalidAddress),
+        };
+
+        Ok(SproutAddress {
+            network: network,
+            paying_key: H256::from(&data[2..34]),
+            transmission_key: H256::from(&data[34..66]),
+        })
+    }
+}
+
+impl fmt::Display for SproutAddress {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.layout().to_base58().fmt(f)
+    }
+}
+
+impl FromStr for SproutAddress {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error>
+    where
+        Self: Sized,
+    {
+        let data = try!(s.from_base58().map_err(|_| Error::InvalidAddress));
+        SproutAddress::from_layout(&data)
+    }
+}
+
+/// Sapling payment address.
+#[derive(Debug, PartialEq, Clone)]
+pub struct SaplingAddress {
+    /// The network of the address.
+    pub network: Network,
+    /// Diversifier (d).
+    pub diversifier: [u8; 11],
+    /// Diversified transmission key (pk_d).
+    pub transmission_key: H256,
+}
+
+impl SaplingAddress {
+    fn hrp(network: Network) -> &'static str {
+        match network {
+            Network::Mainnet => SAPLING_MAINNET_HRP,
+            Network::Testnet => SAPLING_TESTNET_HRP,
+        }
+    }
+}
+
+impl fmt::Display for SaplingAddress {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut data = Vec::with_capacity(SAPLING_ADDRESS_LEN);
+        data.extend_from_slice(&self.diversifier);
+        data.extend_from_slice(&*self.transmission_key);
+        let groups = bech32::convert_bits(&data, 8, 5, true)
+            .expect("regrouping of 8-bit groups always succeeds; qed");
+        bech32::encode(SaplingAddress::hrp(self.network), &groups).fmt(f)
+    }
+}
+
+impl FromStr for SaplingAddress {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error>
+    where
+        Self: Sized,
+    {
+        let (hrp, groups) = try!(bech32::decode(s));
+        let network = match hrp.as_ref() {
+            SAPLING_MAINNET_HRP => Network::Mainnet,
+            SAPLING_TESTNET_HRP => Network::Testnet,
+            _ => return Err(Error::InvalidNetwork),
+        };
+
+        let data = try!(bech32::convert_bits(&groups, 5, 8, false));
+        if data.len() != SAPLING_ADDRESS_LEN {
+            return Err(Error::InvalidAddress);
+        }
+
+        let mut diversifier = [0u8; 11];
+        diversifier.copy_from_slice(&data[..11]);
+
+        Ok(SaplingAddress {
+            network: network,
+            diversifier: diversifier,
+            transmission_key: H256::from(&data[11..]),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SaplingAddress, SproutAddress};
+    use network::Network;
+    use Error;
+
+    const SAPLING_MAINNET: &'static str =
+        "zs1qyqqqqqqqqqqqqqqqqpqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqk48wef";
+    const SAPLING_TESTNET: &'static str =
+        "ztestsapling1qyqqqqqqqqqqqqqqqqpqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqq7z4d7a";
+    const SPROUT_MAINNET: &'static str = "zc8MfnkbU4wMEyu6ayLkXccy1cWGGSjAyDCeLxsMpWgD3VQzZgt5ojdvkiD1TUuA1xCG1gFmYzHDUR1meP1KX5b9vLYdBYp";
+
+    fn sapling_address(network: Network) -> SaplingAddress {
+        let mut diversifier = [0u8; 11];
+        diversifier[0] = 1;
+        SaplingAddress {
+            network: network,
+            diversifier: diversifier,
+            transmission_key: 2.into(),
+        }
+    }
+
+    #[test]
+    fn test_sapling_address_to_string() {
+        assert_eq!(
+            sapling_address(Network::Mainnet).to_string(),
+            SAPLING_MAINNET
+        );
+        assert_eq!(
+            sapling_address(Network::Testnet).to_string(),
+            SAPLING_TESTNET
+        );
+    }
+
+    #[test]
+    fn test_sapling_address_from_str() {
+        assert_eq!(
+            SAPLING_MAINNET.parse::<SaplingAddress>(),
+            Ok(sapling_address(Network::Mainnet))
+        );
+        assert_eq!(
+            SAPLING_TESTNET.parse::<SaplingAddress>(),
+            Ok(sapling_address(Network::Testnet))
+        );
+        assert_eq!(
+            SAPLING_MAINNET.to_uppercase().parse::<SaplingAddress>(),
+            Ok(sapling_address(Network::Mainnet))
+        );
+        assert_eq!(
+            SAPLING_MAINNET
+                .replace("zs1", "zt1")
+                .parse::<SaplingAddress>(),
+            Err(Error::InvalidChecksum)
+        );
+        assert_eq!(
+            SAPLING_MAINNET
+                .replace("k48wef", "k48wee")
+                .parse::<SaplingAddress>(),
+            Err(Error::InvalidChecksum)
+        );
+    }
+
+    #[test]
+    fn test_sprout_address_to_and_from_string() {
+        let address = SproutAddress {
+            network: Network::Mainnet,
+            paying_key: 1.into(),
+            transmission_key: 2.into(),
+        };
+
+        assert_eq!(address.to_string(), SPROUT_MAINNET);
+        assert_eq!(SPROUT_MAINNET.parse::<SproutAddress>(), Ok(address));
+        assert_eq!(
+            "t1h8SqgtM3QM5e2M8EzhhT1yL2PXXtA6oqe".parse::<SproutAddress>(),
+            Err(Error::InvalidAddress)
+        );
+    }
+}
@@ -4,8 +4,9 @@
 
 use hash::H520;
 use hex::{FromHex, ToHex};
+use secp256k1::Signature as SecpSignature;
 use std::{fmt, ops, str};
-use Error;
+use {Error, SECP256K1};
 
 #[derive(PartialEq)]
 pub struct Signature(Vec<u8>);
@@ -58,8 +59,32 @@ impl From<Signature> for Vec<u8> {
 }
 
 impl Signature {
-    pub fn check_low_s(&self) -> bool {
-        unimplemented!();
+    /// Returns true if S value of DER-encoded signature is not greater than half of the curve
+    /// order (BIP62 rule 5). Signatures that can't be parsed are never low-S.
+    pub fn is_low_s(&self) -> bool {
+        let context = &SECP256K1;
+        let signature = match SecpSignature::from_der_lax(context, &self.0) {
+            Ok(signature) => signature,
+            Err(_) => return false,
+        };
+
+        let mut normalized = signature.clone();
+        normalized.normalize_s(context);
+        normalized.serialize_der(context) == signature.serialize_der(context)
+    }
+
+    /// Returns signature with S value replaced with (order - S) if it is greater than
+    /// half of the curve order. Both signatures are valid for the same message and key.
+    /// Signatures that can't be parsed are returned unchanged.
+    pub fn normalize_s(&self) -> Signature {
+        let context = &SECP256K1;
+        match SecpSignature::from_der_lax(context, &self.0) {
+            Ok(mut signature) => {
+                signature.normalize_s(context);
+                Signature(signature.serialize_der(context))
+            }
+            Err(_) => Signature(self.0.clone()),
+        }
     }
 }
 
@@ -114,3 +139,26 @@ impl From<H520> for CompactSignature {
         CompactSignature(h)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Signature;
+
+    const HIGH_S: &'static str = "3045022052ffc1929a2d8bd365c6a2a4e3421711b4b1e1b8781698ca9075807b4227abcb0221009984107ddb9e3813782b095d0d84361ed4c76e5edaf6561d252ae162c2341cfb";
+    const LOW_S: &'static str = "3044022052ffc1929a2d8bd365c6a2a4e3421711b4b1e1b8781698ca9075807b4227abcb0220667bef822461c7ec87d4f6a2f27bc9dfe5e76e87d4524a1e9aa77d2a0e022446";
+
+    #[test]
+    fn test_signature_is_low_s() {
+        assert!(!Signature::from(HIGH_S).is_low_s());
+        assert!(Signature::from(LOW_S).is_low_s());
+        assert!(!Signature::from("deadbeef").is_low_s());
+    }
+
+    #[test]
+    fn test_signature_normalize_s() {
+        let normalized = Signature::from(HIGH_S).normalize_s();
+        assert_eq!(normalized, LOW_S.into());
+        assert_eq!(normalized.normalize_s(), LOW_S.into());
+        assert_eq!(Signature::from("deadbeef").normalize_s(), "deadbeef".into());
+    }
+}
@@ -2,9 +2,25 @@ use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
 use ser::{Deserializable, Error as ReaderError, Reader, Serializable, Stream};
 use std::{io, net, str};
 
+/// Prefix of IPv4-mapped IPv6 address (::ffff:0:0/96).
+const IPV4_MAPPED_PREFIX: [u8; 12] = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0xff, 0xff];
+
+/// IP address. IPv4-mapped IPv6 addresses are always converted to IPv4 addresses.
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub struct IpAddress(net::IpAddr);
 
+/// Returns IPv4 address if given address is IPv4-mapped IPv6 address.
+fn ipv4_mapped(address: &net::Ipv6Addr) -> Option<net::Ipv4Addr> {
+    let octets = address.octets();
+    if octets[0..12] == IPV4_MAPPED_PREFIX {
+        Some(net::Ipv4Addr::new(
+            octets[12], octets[13], octets[14], octets[15],
+        ))
+    } else {
+        None
+    }
+}
+
 impl Default for IpAddress {
     fn default() -> Self {
         IpAddress(net::IpAddr::V4(net::Ipv4Addr::new(0, 0, 0, 0)))
@@ -13,7 +29,13 @@ impl Default for IpAddress {
 
 impl From<net::IpAddr> for IpAddress {
     fn from(ip: net::IpAddr) -> Self {
-        IpAddress(ip)
+        match ip {
+            net::IpAddr::V6(address) => match ipv4_mapped(&address) {
+                Some(address) => IpAddress(net::IpAddr::V4(address)),
+                None => IpAddress(ip),
+            },
+            net::IpAddr::V4(_) => IpAddress(ip),
+        }
     }
 }
 
@@ -33,7 +55,7 @@ impl str::FromStr for IpAddress {
     type Err = <net::IpAddr as str::FromStr>::Err;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        s.parse().map(IpAddress)
+        s.parse::<net::IpAddr>().map(Into::into)
     }
 }
 
@@ -42,7 +64,7 @@ impl Serializable for IpAddress {
         match self.0 {
             net::IpAddr::V4(address) => {
                 stream
-                    .append_slice(&[0u8; 12])
+                    .append_slice(&IPV4_MAPPED_PREFIX)
                     .append_slice(&address.octets());
             }
             net::IpAddr::V6(address) => {
@@ -61,7 +83,8 @@ impl Deserializable for IpAddress {
     {
         let bytes: &mut [u8] = &mut [0u8; 12];
         try!(reader.read_slice(bytes));
-        if bytes == &[0u8; 12] {
+        // IPv4-compatible addresses are still accepted for compatibility
+        if bytes == &IPV4_MAPPED_PREFIX || bytes == &[0u8; 12] {
             let address: &mut [u8] = &mut [0u8; 4];
             try!(reader.read_slice(address));
             let address = net::Ipv4Addr::new(address[0], address[1], address[2], address[3]);
@@ -105,7 +128,16 @@ mod test {
         assert_eq!(
             serialize(&ip),
             vec![
-                0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x0a, 0x00,
+                0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xff, 0xff, 0x0a, 0x00,
+                0x00, 0x01
+            ]
+            .into()
+        );
+        let ip: IpAddress = "2001:db8::1".into();
+        assert_eq!(
+            serialize(&ip),
+            vec![
+                0x20, 0x01, 0x0d, 0xb8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
                 0x00, 0x01
             ]
             .into()
@@ -119,15 +151,31 @@ mod test {
             0x00, 0x01,
         ] as &[u8])
         .unwrap();
-        assert_eq!(
-            ip,
-            IpAddress(net::IpAddr::V6("::ffff:a00:1".parse().unwrap()))
-        );
+        assert_eq!(ip, IpAddress(net::IpAddr::V4("10.0.0.1".parse().unwrap())));
         let ip: IpAddress = deserialize(&[
             0x00u8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x0a, 0x00,
             0x00, 0x01,
         ] as &[u8])
         .unwrap();
         assert_eq!(ip, IpAddress(net::IpAddr::V4("10.0.0.1".parse().unwrap())));
+        let ip: IpAddress = deserialize(&[
+            0x20u8, 0x01, 0x0d, 0xb8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x01,
+        ] as &[u8])
+        .unwrap();
+        assert_eq!(
+            ip,
+            IpAddress(net::IpAddr::V6("2001:db8::1".parse().unwrap()))
+        );
+    }
+
+    #[test]
+    fn test_ipv4_mapped_is_converted() {
+        let ip: IpAddress = "::ffff:a00:1".into();
+        assert_eq!(ip, IpAddress(net::IpAddr::V4("10.0.0.1".parse().unwrap())));
+        let ip: IpAddress = net::IpAddr::V6("::ffff:a00:1".parse().unwrap()).into();
+        assert_eq!(ip, IpAddress(net::IpAddr::V4("10.0.0.1".parse().unwrap())));
+        let ip: IpAddress = "::1".into();
+        assert_eq!(ip, IpAddress(net::IpAddr::V6("::1".parse().unwrap())));
     }
 }
@@ -1,6 +1,9 @@
-use ser::Reader;
+use ser::{Reader, ReaderLimits};
 use {Error, Payload};
 
+/// Maximal number of bytes that could be allocated for a single list, read from the payload.
+const MAX_PAYLOAD_LIST_BYTES: usize = 128 * 1024 * 1024;
+
 pub fn deserialize_payload<T>(buffer: &[u8], version: u32) -> Result<T, Error>
 where
     T: Payload,
@@ -22,7 +25,14 @@ pub struct PayloadReader<T> {
 impl<'a> PayloadReader<&'a [u8]> {
     pub fn new(buffer: &'a [u8], version: u32) -> Self {
         PayloadReader {
-            reader: Reader::new(buffer),
+            // every list element occupies at least one byte of the payload
+            reader: Reader::with_limits(
+                buffer,
+                ReaderLimits {
+                    max_elements: buffer.len(),
+                    max_bytes: MAX_PAYLOAD_LIST_BYTES,
+                },
+            ),
             version: version,
         }
     }
@@ -42,3 +52,21 @@ impl<'a> PayloadReader<&'a [u8]> {
         self.reader.is_finished()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::deserialize_payload;
+    use bytes::Bytes;
+    use types::Headers;
+    use Error;
+
+    #[test]
+    fn payload_with_huge_declared_list_fails_fast() {
+        // headers message, claiming 10_000_000 headers
+        let payload: Bytes = "fe80969800".into();
+        assert_eq!(
+            deserialize_payload::<Headers>(&payload, 0),
+            Err(Error::Deserialize)
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use hash::H256;
 use ser::{Deserializable, Error as ReaderError, Reader, Serializable, Stream};
 use std::io;
 use {MessageResult, Payload};
@@ -55,17 +56,34 @@ impl Deserializable for RejectCode {
     }
 }
 
+/// Reject message (BIP61), sent in response to the rejected message.
 #[derive(Debug, PartialEq)]
 pub struct Reject {
+    /// Command of the rejected message.
     pub message: String,
-    pub code: RejectCode,
+    /// Reject code.
+    pub ccode: RejectCode,
+    /// Human-readable reject reason.
     pub reason: String,
-    // TODO: data
+    /// Hash of the rejected block or transaction.
+    pub data: Option<H256>,
+}
+
+impl Reject {
+    /// Creates reject message for the rejected block or transaction.
+    pub fn with_hash(message: &str, ccode: RejectCode, reason: String, hash: H256) -> Self {
+        Reject {
+            message: message.into(),
+            ccode: ccode,
+            reason: reason,
+            data: Some(hash),
+        }
+    }
 }
 
 impl Payload for Reject {
     fn version() -> u32 {
-        0
+        70002
     }
 
     fn command() -> &'static str {
@@ -76,10 +94,20 @@ impl Payload for Reject {
     where
         T: io::Read,
     {
+        let message = try!(reader.read());
+        let ccode = try!(reader.read());
+        let reason = try!(reader.read());
+        let data = if reader.is_finished() {
+            None
+        } else {
+            Some(try!(reader.read()))
+        };
+
         let reject = Reject {
-            message: try!(reader.read()),
-            code: try!(reader.read()),
-            reason: try!(reader.read()),
+            message: message,
+            ccode: ccode,
+            reason: reason,
+            data: data,
         };
 
         Ok(reject)
@@ -88,8 +116,46 @@ impl Payload for Reject {
     fn serialize_payload(&self, stream: &mut Stream, _version: u32) -> MessageResult<()> {
         stream
             .append(&self.message)
-            .append(&self.code)
+            .append(&self.ccode)
             .append(&self.reason);
+        if let Some(ref data) = self.data {
+            stream.append(data);
+        }
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Reject, RejectCode};
+    use hash::H256;
+    use {deserialize_payload, serialize_payload, Payload};
+
+    #[test]
+    fn test_reject_roundtrip() {
+        let reject = Reject::with_hash(
+            "tx",
+            RejectCode::InsuficientFee,
+            "insufficient fee".into(),
+            H256::from(1),
+        );
+        let serialized = serialize_payload(&reject, Reject::version()).unwrap();
+        assert_eq!(serialized.len(), 3 + 1 + 17 + 32);
+        assert_eq!(
+            deserialize_payload::<Reject>(&serialized, Reject::version()).unwrap(),
+            reject
+        );
+
+        let reject = Reject {
+            message: "version".into(),
+            ccode: RejectCode::Obsolate,
+            reason: "obsolete".into(),
+            data: None,
+        };
+        let serialized = serialize_payload(&reject, Reject::version()).unwrap();
+        assert_eq!(
+            deserialize_payload::<Reject>(&serialized, Reject::version()).unwrap(),
+            reject
+        );
+    }
+}
@@ -79,6 +79,15 @@ impl Version {
         }
     }
 
+    /// Address of the receiving node, as it is seen by the sender.
+    pub fn receiver(&self) -> &NetAddress {
+        match *self {
+            Version::V0(ref s) | Version::V106(ref s, _) | Version::V70001(ref s, _, _) => {
+                &s.receiver
+            }
+        }
+    }
+
     pub fn services(&self) -> Services {
         match *self {
             Version::V0(ref s) | Version::V106(ref s, _) | Version::V70001(ref s, _, _) => {
@@ -101,6 +110,13 @@ impl Version {
             Version::V106(_, ref v) | Version::V70001(_, ref v, _) => Some(v.user_agent.clone()),
         }
     }
+
+    pub fn start_height(&self) -> Option<i32> {
+        match *self {
+            Version::V0(_) => None,
+            Version::V106(_, ref v) | Version::V70001(_, ref v, _) => Some(v.start_height),
+        }
+    }
 }
 
 #[derive(Debug, Default, PartialEq, Clone)]
@@ -1,4 +1,5 @@
 use memory_pool::{Entry, MemoryPool, OrderingStrategy};
+use std::cmp;
 use std::collections::HashSet;
 use zebra_chain::{
     IndexedTransaction, OutPoint, Transaction, TransactionInput, TransactionOutput,
@@ -10,7 +11,8 @@ use zebra_primitives::compact::Compact;
 use zebra_primitives::hash::H256;
 use zebra_script::Builder;
 use zebra_storage::{SaplingTreeState, SharedStore, TransactionOutputProvider};
-use zebra_verification::{transaction_sigops, work_required};
+use zebra_verification::constants::BLOCK_MAX_FUTURE;
+use zebra_verification::{median_timestamp_inclusive, transaction_sigops, work_required};
 
 const BLOCK_VERSION: u32 = 4;
 const BLOCK_HEADER_SIZE: u32 = 4 + 32 + 32 + 32 + 4 + 4 + 32 + 1344;
@@ -25,6 +27,10 @@ pub struct BlockTemplate {
     pub final_sapling_root_hash: H256,
     /// The current time as seen by the server
     pub time: u32,
+    /// The minimal timestamp of the block (median time of last blocks + 1)
+    pub min_time: u32,
+    /// The maximal timestamp of the block
+    pub max_time: u32,
     /// The compressed difficulty
     pub bits: Compact,
     /// Block height
@@ -286,6 +292,12 @@ impl<'a> BlockAssembler<'a> {
         let best_block = store.best_block();
         let previous_header_hash = best_block.hash;
         let height = best_block.number + 1;
+        let min_time = median_timestamp_inclusive(
+            previous_header_hash.clone(),
+            store.as_block_header_provider(),
+        ) + 1;
+        let time = cmp::max(time, min_time);
+        let max_time = time.saturating_add(BLOCK_MAX_FUTURE as u32);
         let bits = work_required(
             previous_header_hash.clone(),
             time,
@@ -298,7 +310,7 @@ impl<'a> BlockAssembler<'a> {
         let mut miner_reward = consensus.miner_reward(height);
         let mut transactions = Vec::new();
 
-        let mempool_iter = mempool.iter(OrderingStrategy::ByTransactionScore);
+        let mempool_iter = mempool.iter(OrderingStrategy::ByAncestorFeeRate);
         let mut sapling_tree = if previous_header_hash.is_zero() {
             SaplingTreeState::new()
         } else {
@@ -373,6 +385,8 @@ impl<'a> BlockAssembler<'a> {
             previous_header_hash: previous_header_hash,
             final_sapling_root_hash: sapling_tree.root(),
             time: time,
+            min_time: min_time,
+            max_time: max_time,
             bits: bits,
             height: height,
             transactions: transactions,
@@ -480,8 +494,10 @@ mod tests {
             let storage: SharedStore = Arc::new(BlockChainDatabase::init_test_chain(vec![
                 zebra_test_data::genesis().into(),
             ]));
-            pool.insert_verified(chain.at(0).into(), &NonZeroFeeCalculator);
-            pool.insert_verified(chain.at(1).into(), &NonZeroFeeCalculator);
+            pool.insert_verified(chain.at(0).into(), &NonZeroFeeCalculator)
+                .unwrap();
+            pool.insert_verified(chain.at(1).into(), &NonZeroFeeCalculator)
+                .unwrap();
 
             (
                 BlockAssembler {
@@ -504,6 +520,47 @@ mod tests {
         assert_eq!(block.transactions[1].hash, hash1);
     }
 
+    #[test]
+    fn block_assembler_child_pays_for_parent() {
+        let chain = &mut ChainBuilder::new();
+        TransactionBuilder::with_default_input(0)
+            .set_output(10)
+            .store(chain) // transaction0
+            .into_input(0)
+            .set_output(1_000_000_000)
+            .store(chain) // transaction0 -> transaction1
+            .set_default_input(1)
+            .set_output(100_000_000)
+            .store(chain); // transaction2
+
+        let mut pool = MemoryPool::new();
+        for transaction in chain.transactions.iter().cloned() {
+            pool.insert_verified(transaction.into(), &NonZeroFeeCalculator)
+                .unwrap();
+        }
+        // zero fee transactions are not accepted to the pool => make transaction0 free with virtual fee
+        pool.set_virtual_fee(&chain.hash(0), -100_000_010);
+
+        let storage: SharedStore = Arc::new(BlockChainDatabase::init_test_chain(vec![
+            zebra_test_data::genesis().into(),
+        ]));
+        let block = BlockAssembler {
+            miner_address: &"t1h8SqgtM3QM5e2M8EzhhT1yL2PXXtA6oqe".into(),
+            max_block_size: 0xffffffff,
+            max_block_sigops: 0xffffffff,
+        }
+        .create_new_block(&storage, &pool, 0, &ConsensusParams::new(Network::Mainnet))
+        .unwrap();
+
+        // transaction0 is pulled in by transaction1 before transaction2 with better individual fee rate
+        let hashes: Vec<_> = block
+            .transactions
+            .iter()
+            .map(|tx| tx.hash.clone())
+            .collect();
+        assert_eq!(hashes, vec![chain.hash(0), chain.hash(1), chain.hash(2)]);
+    }
+
     #[test]
     fn block_assembler_miner_fee() {
         let input_tx = zebra_test_data::block_h1().transactions[0].clone();
@@ -520,7 +577,8 @@ mod tests {
         pool.insert_verified(
             tx0,
             &FeeCalculator(storage.as_transaction_output_provider()),
-        );
+        )
+        .unwrap();
 
         let consensus = ConsensusParams::new(Network::Mainnet);
         let block = BlockAssembler {
@@ -537,4 +595,34 @@ mod tests {
             expected_coinbase_value
         );
     }
+
+    #[test]
+    fn block_assembler_time_bounds() {
+        let genesis = zebra_test_data::genesis();
+        let genesis_time = genesis.block_header.time;
+        let storage: SharedStore =
+            Arc::new(BlockChainDatabase::init_test_chain(vec![genesis.into()]));
+        let pool = MemoryPool::new();
+        let consensus = ConsensusParams::new(Network::Mainnet);
+        let block_assembler = BlockAssembler {
+            max_block_size: 0xffffffff,
+            max_block_sigops: 0xffffffff,
+            miner_address: &"t1h8SqgtM3QM5e2M8EzhhT1yL2PXXtA6oqe".into(),
+        };
+
+        // time is later than median time => it is used as is
+        let time = genesis_time + 100;
+        let block = block_assembler
+            .create_new_block(&storage, &pool, time, &consensus)
+            .unwrap();
+        assert_eq!(block.min_time, genesis_time + 1);
+        assert_eq!(block.time, time);
+        assert_eq!(block.max_time, time + 2 * 60 * 60);
+
+        // time is earlier than median time => min time is used
+        let block = block_assembler
+            .create_new_block(&storage, &pool, 0, &consensus)
+            .unwrap();
+        assert_eq!(block.time, block.min_time);
+    }
 }
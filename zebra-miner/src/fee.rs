@@ -1,4 +1,3 @@
-use ser::Serializable;
 use zebra_chain::Transaction;
 use zebra_storage::{DuplexTransactionOutputProvider, TransactionOutputProvider};
 use zebra_verification::checked_transaction_fee;
@@ -41,14 +40,19 @@ pub fn transaction_fee(store: &TransactionOutputProvider, tx: &Transaction) -> u
     checked_transaction_fee(store, ::std::usize::MAX, tx).unwrap_or(0)
 }
 
+/// Compute miner fee rate for given (memory pool) transaction.
+///
+/// Fee rate is computed using full transaction size, including shielded data.
 pub fn transaction_fee_rate(store: &TransactionOutputProvider, tx: &Transaction) -> u64 {
-    transaction_fee(store, tx) / tx.serialized_size() as u64
+    transaction_fee(store, tx) / tx.total_size() as u64
 }
 
 #[cfg(test)]
 mod tests {
     use super::transaction_fee_rate;
+    use ser::serialize;
     use std::sync::Arc;
+    use zebra_chain::{JoinSplit, JoinSplitDescription, Transaction};
     use zebra_db::BlockChainDatabase;
     use zebra_storage::AsSubstore;
 
@@ -99,4 +103,32 @@ mod tests {
         assert_eq!(transaction_fee_rate(store, &tx0), 0);
         assert_eq!(transaction_fee_rate(store, &tx2), 4_901);
     }
+
+    #[test]
+    fn shielded_transaction_fee_rate_uses_total_size() {
+        let tx: Transaction = zebra_test_data::TransactionBuilder::with_version(2)
+            .add_output(100_000)
+            .set_join_split(JoinSplit {
+                descriptions: vec![JoinSplitDescription {
+                    value_pub_new: 1_100_000,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            })
+            .into();
+
+        // total size includes join split descriptions
+        let total_size = tx.total_size();
+        assert_eq!(total_size, serialize(&tx).len());
+        assert!(total_size > 1_000);
+
+        let db = Arc::new(BlockChainDatabase::init_test_chain(vec![
+            zebra_test_data::genesis().into(),
+        ]));
+        let store = db.as_transaction_output_provider();
+        assert_eq!(
+            transaction_fee_rate(store, &tx),
+            1_000_000 / total_size as u64
+        );
+    }
 }
@@ -0,0 +1,211 @@
+//! Transactions fee estimator
+//!
+//! `FeeEstimator` remembers fee rates of transactions, confirmed in last blocks, together with
+//! number of blocks these transactions have spent in the memory pool. This history is then used
+//! to estimate fee rate, which is required for transaction to be mined within given number of blocks.
+use memory_pool::MemoryPool;
+use std::collections::{HashMap, VecDeque};
+use zebra_primitives::hash::H256;
+
+/// Maximal confirmation target that could be estimated
+pub const MAX_CONFIRMATION_TARGET: u32 = 25;
+/// Default number of last blocks, used for estimation
+pub const DEFAULT_HISTORY_BLOCKS: usize = 100;
+/// Share of transactions that must be confirmed within target for fee rate to be estimated (in percents)
+const SUCCESS_THRESHOLD_PERCENT: usize = 85;
+
+/// Fee rates of transactions, confirmed in single block
+#[derive(Debug, Default)]
+struct BlockFeeRates {
+    /// Fee rates of confirmed transactions, grouped by confirmation depth.
+    /// Transaction, confirmed in the first block after it has entered the memory pool, goes
+    /// to the first bucket. All transactions with depth >= MAX_CONFIRMATION_TARGET go to the last bucket.
+    by_depth: Vec<Vec<u64>>,
+}
+
+/// Fee estimator, based on historical fee rates of confirmed transactions
+#[derive(Debug)]
+pub struct FeeEstimator {
+    /// Maximal number of blocks to remember
+    history_blocks: usize,
+    /// Fee rates of transactions, confirmed in last blocks
+    blocks: VecDeque<BlockFeeRates>,
+    /// Memory pool transactions: hash => (height of best block when entered pool, fee rate)
+    tracked: HashMap<H256, (u32, u64)>,
+}
+
+impl Default for FeeEstimator {
+    fn default() -> Self {
+        FeeEstimator::new(DEFAULT_HISTORY_BLOCKS)
+    }
+}
+
+impl FeeEstimator {
+    /// Create new estimator, which uses history of last `history_blocks` blocks
+    pub fn new(history_blocks: usize) -> Self {
+        FeeEstimator {
+            history_blocks: history_blocks,
+            blocks: VecDeque::with_capacity(history_blocks),
+            tracked: HashMap::new(),
+        }
+    }
+
+    /// Process new best block at given height.
+    ///
+    /// Fee rates of all tracked memory pool transactions, included in the block, are remembered.
+    /// Then current memory pool transactions are tracked until next block is processed.
+    pub fn on_block(&mut self, height: u32, transactions: &[H256], memory_pool: &MemoryPool) {
+        let mut block_fee_rates = BlockFeeRates {
+            by_depth: vec![Vec::new(); MAX_CONFIRMATION_TARGET as usize],
+        };
+        for hash in transactions {
+            if let Some((entry_height, fee_rate)) = self.tracked.remove(hash) {
+                let depth = height.saturating_sub(entry_height).max(1);
+                let bucket = depth.min(MAX_CONFIRMATION_TARGET) as usize - 1;
+                block_fee_rates.by_depth[bucket].push(fee_rate);
+            }
+        }
+
+        if self.blocks.len() == self.history_blocks {
+            self.blocks.pop_front();
+        }
+        if self.history_blocks != 0 {
+            self.blocks.push_back(block_fee_rates);
+        }
+
+        self.tracked = memory_pool
+            .entries_information()
+            .into_iter()
+            .map(|entry| {
+                let fee_rate = entry.fee / ::std::cmp::max(entry.size, 1) as u64;
+                (entry.hash, (entry.height, fee_rate))
+            })
+            .collect();
+    }
+
+    /// Estimate fee rate (in satoshis per byte), required for transaction to be mined within `target` blocks.
+    ///
+    /// Returns the lowest fee rate such that at least SUCCESS_THRESHOLD_PERCENT of transactions
+    /// paying this (or greater) fee rate were confirmed within `target` blocks.
+    /// Returns None if there's not enough data to make an estimation.
+    pub fn estimate_fee_rate(&self, target: u32) -> Option<u64> {
+        if target == 0 || target > MAX_CONFIRMATION_TARGET {
+            return None;
+        }
+
+        // (fee rate, confirmed within target)
+        let mut fee_rates: Vec<(u64, bool)> = self
+            .blocks
+            .iter()
+            .flat_map(|block| {
+                block
+                    .by_depth
+                    .iter()
+                    .enumerate()
+                    .flat_map(move |(bucket, fee_rates)| {
+                        fee_rates
+                            .iter()
+                            .map(move |fee_rate| (*fee_rate, bucket < target as usize))
+                    })
+            })
+            .collect();
+        fee_rates.sort_by(|a, b| b.0.cmp(&a.0));
+
+        let mut estimation = None;
+        let mut confirmed_within_target = 0;
+        for (index, &(fee_rate, is_within_target)) in fee_rates.iter().enumerate() {
+            if is_within_target {
+                confirmed_within_target += 1;
+            }
+
+            if confirmed_within_target * 100 < (index + 1) * SUCCESS_THRESHOLD_PERCENT {
+                break;
+            }
+
+            estimation = Some(fee_rate);
+        }
+
+        estimation
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate zebra_test_data;
+
+    use self::zebra_test_data::{ChainBuilder, TransactionBuilder};
+    use super::*;
+    use fee::NonZeroFeeCalculator;
+
+    #[test]
+    fn fee_estimator_empty() {
+        let estimator = FeeEstimator::default();
+        assert_eq!(estimator.estimate_fee_rate(0), None);
+        assert_eq!(estimator.estimate_fee_rate(1), None);
+        assert_eq!(
+            estimator.estimate_fee_rate(MAX_CONFIRMATION_TARGET + 1),
+            None
+        );
+    }
+
+    #[test]
+    fn fee_estimator_estimates_by_confirmation_depth() {
+        let chain = &mut ChainBuilder::new();
+        TransactionBuilder::with_output(10)
+            .store(chain) // t0
+            .set_output(50_000_000)
+            .store(chain) // t1
+            .set_output(100_000_000)
+            .store(chain); // t2
+
+        let mut estimator = FeeEstimator::default();
+        let mut pool = MemoryPool::new();
+        pool.set_best_block_height(10);
+        pool.insert_verified(chain.at(0).into(), &NonZeroFeeCalculator)
+            .unwrap();
+        pool.insert_verified(chain.at(1).into(), &NonZeroFeeCalculator)
+            .unwrap();
+        pool.insert_verified(chain.at(2).into(), &NonZeroFeeCalculator)
+            .unwrap();
+        estimator.on_block(10, &[], &pool);
+
+        let fee_rate = |index: usize| {
+            let transaction = chain.at(index);
+            (100_000_000 + transaction.outputs[0].value) / transaction.total_size() as u64
+        };
+
+        // t2 (with highest fee) is mined in the first block
+        pool.remove_by_hash(&chain.hash(2));
+        estimator.on_block(11, &[chain.hash(2)], &pool);
+        // t1 is mined in the third block
+        pool.remove_by_hash(&chain.hash(1));
+        estimator.on_block(13, &[chain.hash(1)], &pool);
+
+        assert_eq!(estimator.estimate_fee_rate(1), Some(fee_rate(2)));
+        assert_eq!(estimator.estimate_fee_rate(3), Some(fee_rate(1)));
+        assert_eq!(
+            estimator.estimate_fee_rate(MAX_CONFIRMATION_TARGET),
+            Some(fee_rate(1))
+        );
+    }
+
+    #[test]
+    fn fee_estimator_forgets_old_blocks() {
+        let chain = &mut ChainBuilder::new();
+        TransactionBuilder::with_output(10).store(chain);
+
+        let mut estimator = FeeEstimator::new(2);
+        let mut pool = MemoryPool::new();
+        pool.insert_verified(chain.at(0).into(), &NonZeroFeeCalculator)
+            .unwrap();
+        estimator.on_block(1, &[], &pool);
+        pool.remove_by_hash(&chain.hash(0));
+        estimator.on_block(2, &[chain.hash(0)], &pool);
+        assert!(estimator.estimate_fee_rate(2).is_some());
+
+        estimator.on_block(3, &[], &pool);
+        assert!(estimator.estimate_fee_rate(2).is_some());
+        estimator.on_block(4, &[], &pool);
+        assert_eq!(estimator.estimate_fee_rate(2), None);
+    }
+}
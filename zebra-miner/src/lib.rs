@@ -1,5 +1,6 @@
 extern crate byteorder;
 extern crate heapsize;
+extern crate time;
 
 extern crate zebra_chain;
 extern crate zebra_crypto;
@@ -14,13 +15,18 @@ extern crate zebra_verification;
 
 mod block_assembler;
 mod fee;
+mod fee_estimator;
 mod memory_pool;
 
 pub use block_assembler::{BlockAssembler, BlockTemplate};
-pub use fee::{transaction_fee, transaction_fee_rate, FeeCalculator};
+pub use fee::{transaction_fee, transaction_fee_rate, FeeCalculator, MemoryPoolFeeCalculator};
+pub use fee_estimator::{FeeEstimator, DEFAULT_HISTORY_BLOCKS, MAX_CONFIRMATION_TARGET};
 pub use memory_pool::{
-    DoubleSpendCheckResult, HashedOutPoint, Information as MemoryPoolInformation, MemoryPool,
-    NonFinalDoubleSpendSet, OrderingStrategy as MemoryPoolOrderingStrategy,
+    DoubleSpendCheckResult, DumpedTransaction as MemoryPoolDumpedTransaction,
+    EntryInformation as MemoryPoolEntryInformation, HashedOutPoint,
+    Information as MemoryPoolInformation, InsertError as MemoryPoolInsertError, MemoryPool,
+    NonFinalDoubleSpendSet, OrderingStrategy as MemoryPoolOrderingStrategy, ReplaceResult,
+    DEFAULT_MIN_RELAY_FEE_RATE,
 };
 
 #[cfg(feature = "test-helpers")]
@@ -7,18 +7,31 @@
 //! before descendants). Removal using `remove_by_hash` can break this rule.
 use fee::MemoryPoolFeeCalculator;
 use heapsize::HeapSizeOf;
-use ser::{serialize, Serializable};
+use ser::{serialize, Deserializable, Error as ReaderError, Reader, Serializable, Stream};
 use std::cmp::Ordering;
 use std::collections::BTreeSet;
+use std::collections::Bound;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::collections::VecDeque;
+use std::fs::{self, File};
 use std::hash::{Hash, Hasher};
+use std::io::{self, Write};
+use std::path::Path;
 use zebra_chain::{IndexedTransaction, OutPoint, Transaction, TransactionOutput};
 use zebra_primitives::bytes::Bytes;
 use zebra_primitives::hash::H256;
 use zebra_storage::{TransactionOutputProvider, TransactionProvider};
 
+/// Maximal input sequence number, which signals that transaction may be replaced (BIP125)
+pub const MAX_BIP125_RBF_SEQUENCE: u32 = 0xfffffffd;
+/// Default maximal total size of transactions in the memory pool (in bytes)
+pub const DEFAULT_MAX_MEMORY_POOL_SIZE: usize = 300 * 1024 * 1024;
+/// Default minimal fee rate (in satoshis per 1000 bytes) of transactions, accepted to the memory pool
+pub const DEFAULT_MIN_RELAY_FEE_RATE: u64 = 100;
+/// Version of the memory pool dump file format
+const MEMORY_POOL_DUMP_VERSION: u32 = 1;
+
 /// Transactions ordering strategy
 #[cfg_attr(feature = "cargo-clippy", allow(enum_variant_names))]
 #[derive(Debug, Clone, Copy)]
@@ -29,6 +42,9 @@ pub enum OrderingStrategy {
     ByTransactionScore,
     /// Order transactions by their in-pool package mining score (score for mining this transaction + all descendants transactions)
     ByPackageScore,
+    /// Order transactions by fee rate of transaction together with all its in-pool ancestors (child pays for parent).
+    /// Ancestors are returned right before the transaction
+    ByAncestorFeeRate,
 }
 
 /// Information on current `MemoryPool` state
@@ -38,6 +54,31 @@ pub struct Information {
     pub transactions_count: usize,
     /// Total number of bytes occupied by transactions from the `MemoryPool`
     pub transactions_size_in_bytes: usize,
+    /// Total (estimated) memory usage of the `MemoryPool`
+    pub memory_usage: usize,
+    /// Minimal fee rate (in satoshis per 1000 bytes) of transaction to be accepted to the `MemoryPool`
+    pub minimum_fee_rate: u64,
+    /// Minimal relay fee rate (in satoshis per 1000 bytes), configured at startup
+    pub min_relay_fee_rate: u64,
+}
+
+/// Information on single `MemoryPool` entry (as in verbose GetRawMemPool RPC)
+#[derive(Debug, Clone, PartialEq)]
+pub struct EntryInformation {
+    /// Transaction hash
+    pub hash: H256,
+    /// Transaction size in bytes
+    pub size: usize,
+    /// Transaction fee
+    pub fee: u64,
+    /// Virtual fee delta, set by `prioritise_transaction`
+    pub fee_delta: i64,
+    /// Time (in seconds since epoch) when transaction has entered the pool
+    pub time: u32,
+    /// Height of the best block when transaction has entered the pool
+    pub height: u32,
+    /// Hashes of in-pool transactions, which outputs are spent by this transaction
+    pub depends: Vec<H256>,
 }
 
 /// Transactions memory pool
@@ -45,6 +86,16 @@ pub struct Information {
 pub struct MemoryPool {
     /// Transactions storage
     storage: Storage,
+    /// Height of the best block, recorded for transactions entering the pool
+    best_block_height: u32,
+    /// Maximal total size of transactions in the pool (in bytes)
+    max_size_in_bytes: usize,
+    /// Minimal fee rate (in satoshis per 1000 bytes) of transactions entering the pool.
+    /// Raised when transactions are evicted from the full pool
+    minimum_fee_rate: u64,
+    /// Minimal fee rate (in satoshis per 1000 bytes) of transactions entering the pool.
+    /// Unlike `minimum_fee_rate`, it never changes
+    min_relay_fee_rate: u64,
 }
 
 /// Single entry
@@ -60,6 +111,10 @@ pub struct Entry {
     pub size: usize,
     /// Throughout index of this transaction in memory pool (non persistent)
     pub storage_index: u64,
+    /// Time (in seconds since epoch) when this transaction has entered memory pool
+    pub time: u32,
+    /// Height of the best block when this transaction has entered memory pool
+    pub height: u32,
     /// Transaction fee (stored for efficiency)
     pub miner_fee: u64,
     /// Virtual transaction fee (a way to prioritize/penalize transaction)
@@ -70,6 +125,23 @@ pub struct Entry {
     pub package_miner_fee: u64,
     /// miner_virtual_fee + Sum(miner_virtual_fee) for all in-pool descendants
     pub package_miner_virtual_fee: i64,
+    /// size + Sum(size) for all in-pool ancestors
+    pub ancestor_size: usize,
+    /// miner_fee + Sum(miner_fee) for all in-pool ancestors
+    pub ancestor_miner_fee: u64,
+    /// miner_virtual_fee + Sum(miner_virtual_fee) for all in-pool ancestors
+    pub ancestor_miner_virtual_fee: i64,
+}
+
+/// Memory pool transaction, written to the memory pool dump file
+#[derive(Debug, Clone, PartialEq)]
+pub struct DumpedTransaction {
+    /// Transaction
+    pub transaction: Transaction,
+    /// Time (in seconds since epoch) when this transaction has entered memory pool
+    pub time: u32,
+    /// Virtual transaction fee (a way to prioritize/penalize transaction)
+    pub fee_delta: i64,
 }
 
 /// Multi-index transactions storage
@@ -83,6 +155,10 @@ struct Storage {
     by_hash: HashMap<H256, Entry>,
     /// Transactions by previous output
     by_previous_output: HashMap<HashedOutPoint, H256>,
+    /// All transactions (including pending) by their own score. Used to select eviction candidates
+    by_eviction_score: BTreeSet<ByTransactionScoreOrderedEntry>,
+    /// All transactions (including pending) by fee rate of transaction together with all its in-pool ancestors
+    by_ancestor_score: BTreeSet<ByAncestorScoreOrderedEntry>,
     /// References storage
     references: ReferenceStorage,
 }
@@ -141,6 +217,18 @@ struct ByPackageScoreOrderedEntry {
     package_miner_virtual_fee: i64,
 }
 
+#[derive(Debug, Eq, PartialEq, Clone)]
+struct ByAncestorScoreOrderedEntry {
+    /// Transaction hash
+    hash: H256,
+    /// size + Sum(size) for all in-pool ancestors
+    ancestor_size: usize,
+    /// miner_fee + Sum(miner_fee) for all in-pool ancestors
+    ancestor_miner_fee: u64,
+    /// miner_virtual_fee + Sum(miner_virtual_fee) for all in-pool ancestors
+    ancestor_miner_virtual_fee: i64,
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct HashedOutPoint {
     /// Transaction output point
@@ -169,6 +257,49 @@ pub struct NonFinalDoubleSpendSet {
     pub dependent_spends: HashSet<HashedOutPoint>,
 }
 
+/// Result of checking if transaction is allowed to replace conflicting memory-pool transactions
+#[derive(Debug, PartialEq)]
+pub enum ReplaceResult {
+    /// Transaction doesn't spend outputs that are already spent by memory-pool transactions
+    NoConflicts,
+    /// Transaction replaces given memory-pool transactions (conflicting transactions && all their descendants)
+    Replace(Vec<H256>),
+    /// Output is already spent by final memory-pool transaction {self.0}, which doesn't signal replaceability
+    NotReplaceable(H256),
+    /// Transaction spends output of memory-pool transaction {self.0}, which it is going to replace
+    SpendsConflictingTransaction(H256),
+    /// Transaction fee doesn't exceed the sum of fees of all replaced transactions
+    InsufficientFee { required: u64, actual: u64 },
+    /// Transaction fee rate doesn't exceed the fee rate of all replaced transactions
+    InsufficientFeeRate { required: u64, actual: u64 },
+}
+
+/// Reason of not accepting verified transaction to the memory pool
+#[derive(Debug, PartialEq)]
+pub enum InsertError {
+    /// Transaction conflicts with memory-pool transactions, which it isn't allowed to replace
+    Replacement(ReplaceResult),
+    /// Transaction fee is below the minimal relay fee (zero-fee transactions are never accepted)
+    InsufficientFee { required: u64, actual: u64 },
+    /// Memory pool is full and transaction fee rate isn't greater than the minimal fee rate
+    /// (both in satoshis per 1000 bytes)
+    MemoryPoolFull {
+        minimum_fee_rate: u64,
+        fee_rate: u64,
+    },
+    /// Transaction has been (or would be) evicted from the full memory pool right after insertion
+    Evicted,
+}
+
+/// Returns true if transaction signals that it could be replaced by the transaction with
+/// greater fee (BIP125)
+pub fn signals_replaceability(transaction: &Transaction) -> bool {
+    transaction
+        .inputs
+        .iter()
+        .any(|input| input.sequence <= MAX_BIP125_RBF_SEQUENCE)
+}
+
 impl From<OutPoint> for HashedOutPoint {
     fn from(out_point: OutPoint) -> Self {
         HashedOutPoint {
@@ -177,6 +308,12 @@ impl From<OutPoint> for HashedOutPoint {
     }
 }
 
+impl HeapSizeOf for HashedOutPoint {
+    fn heap_size_of_children(&self) -> usize {
+        0
+    }
+}
+
 impl Hash for HashedOutPoint {
     fn hash<H>(&self, state: &mut H)
     where
@@ -218,6 +355,17 @@ impl<'a> From<&'a Entry> for ByPackageScoreOrderedEntry {
     }
 }
 
+impl<'a> From<&'a Entry> for ByAncestorScoreOrderedEntry {
+    fn from(entry: &'a Entry) -> Self {
+        ByAncestorScoreOrderedEntry {
+            hash: entry.hash.clone(),
+            ancestor_size: entry.ancestor_size,
+            ancestor_miner_fee: entry.ancestor_miner_fee,
+            ancestor_miner_virtual_fee: entry.ancestor_miner_virtual_fee,
+        }
+    }
+}
+
 impl PartialOrd for ByTimestampOrderedEntry {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
@@ -277,6 +425,50 @@ impl Ord for ByPackageScoreOrderedEntry {
     }
 }
 
+impl PartialOrd for ByAncestorScoreOrderedEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ByAncestorScoreOrderedEntry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // greater ancestor fee rate means earlier mining
+        let left = (self.ancestor_miner_fee as i64 + self.ancestor_miner_virtual_fee)
+            * (other.ancestor_size as i64);
+        let right = (other.ancestor_miner_fee as i64 + other.ancestor_miner_virtual_fee)
+            * (self.ancestor_size as i64);
+        let order = right.cmp(&left);
+        if order != Ordering::Equal {
+            return order;
+        }
+
+        self.hash.cmp(&other.hash)
+    }
+}
+
+impl Serializable for DumpedTransaction {
+    fn serialize(&self, stream: &mut Stream) {
+        stream
+            .append(&self.transaction)
+            .append(&self.time)
+            .append(&self.fee_delta);
+    }
+}
+
+impl Deserializable for DumpedTransaction {
+    fn deserialize<T>(reader: &mut Reader<T>) -> Result<Self, ReaderError>
+    where
+        T: io::Read,
+    {
+        Ok(DumpedTransaction {
+            transaction: reader.read()?,
+            time: reader.read()?,
+            fee_delta: reader.read()?,
+        })
+    }
+}
+
 impl HeapSizeOf for Entry {
     fn heap_size_of_children(&self) -> usize {
         self.transaction.heap_size_of_children() + self.ancestors.heap_size_of_children()
@@ -290,6 +482,8 @@ impl Storage {
             transactions_size_in_bytes: 0,
             by_hash: HashMap::new(),
             by_previous_output: HashMap::new(),
+            by_eviction_score: BTreeSet::new(),
+            by_ancestor_score: BTreeSet::new(),
             references: ReferenceStorage {
                 by_input: HashMap::new(),
                 pending: HashSet::new(),
@@ -302,10 +496,19 @@ impl Storage {
         }
     }
 
-    pub fn insert(&mut self, entry: Entry) {
+    pub fn insert(&mut self, mut entry: Entry) {
         // update pool information
         self.transactions_size_in_bytes += entry.size;
 
+        // remember score of the package, this transaction is mined with
+        for ancestor_hash in &entry.ancestors {
+            if let Some(ancestor_entry) = self.by_hash.get(ancestor_hash) {
+                entry.ancestor_size += ancestor_entry.size;
+                entry.ancestor_miner_fee += ancestor_entry.miner_fee;
+                entry.ancestor_miner_virtual_fee += ancestor_entry.miner_virtual_fee;
+            }
+        }
+
         // remember that this transaction depends on its inputs
         for input_hash in entry
             .transaction
@@ -361,6 +564,8 @@ impl Storage {
         }
 
         // add to by_hash storage
+        self.by_eviction_score.insert((&entry).into());
+        self.by_ancestor_score.insert((&entry).into());
         self.by_hash.insert(entry.hash.clone(), entry);
     }
 
@@ -395,12 +600,19 @@ impl Storage {
                 .by_transaction_score
                 .remove(&(entry as &Entry).into());
 
+            self.by_eviction_score.remove(&(entry as &Entry).into());
+            self.by_ancestor_score.remove(&(entry as &Entry).into());
+
             miner_virtual_fee_change = virtual_fee - entry.miner_virtual_fee;
             if !entry.ancestors.is_empty() {
                 ancestors = Some(entry.ancestors.iter().cloned().collect());
             }
 
             entry.miner_virtual_fee = virtual_fee;
+            entry.ancestor_miner_virtual_fee += miner_virtual_fee_change;
+
+            self.by_eviction_score.insert((entry as &Entry).into());
+            self.by_ancestor_score.insert((entry as &Entry).into());
 
             if insert_to_transaction_score {
                 self.references
@@ -436,6 +648,21 @@ impl Storage {
                     }
                 }
             });
+
+            // and all descendant entries
+            for descendant_hash in self.descendants(h) {
+                if let Some(descendant_entry) = self.by_hash.get_mut(&descendant_hash) {
+                    if !descendant_entry.ancestors.contains(h) {
+                        continue;
+                    }
+
+                    self.by_ancestor_score
+                        .remove(&(descendant_entry as &Entry).into());
+                    descendant_entry.ancestor_miner_virtual_fee += miner_virtual_fee_change;
+                    self.by_ancestor_score
+                        .insert((descendant_entry as &Entry).into());
+                }
+            }
         }
     }
 
@@ -466,12 +693,29 @@ impl Storage {
                 .iter()
                 .map(|entry| entry.hash.clone())
                 .nth(0),
+            OrderingStrategy::ByAncestorFeeRate => self.best_ancestor_package().into_iter().nth(0),
         }
     }
 
     pub fn remove_by_hash(&mut self, h: &H256) -> Option<Entry> {
+        let descendants = self.descendants(h);
         self.by_hash.remove(h)
 			.map(|entry| {
+				// removed transaction isn't mined together with its descendants anymore
+				for descendant_hash in descendants {
+					if let Some(descendant_entry) = self.by_hash.get_mut(&descendant_hash) {
+						if !descendant_entry.ancestors.remove(h) {
+							continue;
+						}
+
+						self.by_ancestor_score.remove(&(descendant_entry as &Entry).into());
+						descendant_entry.ancestor_size -= entry.size;
+						descendant_entry.ancestor_miner_fee -= entry.miner_fee;
+						descendant_entry.ancestor_miner_virtual_fee -= entry.miner_virtual_fee;
+						self.by_ancestor_score.insert((descendant_entry as &Entry).into());
+					}
+				}
+
 				// update pool information
 				self.transactions_size_in_bytes -= entry.size;
 
@@ -483,6 +727,8 @@ impl Storage {
 				}
 
 				// remove from storage
+				self.by_eviction_score.remove(&(&entry).into());
+				self.by_ancestor_score.remove(&(&entry).into());
 				self.references.remove(None, &self.by_hash, &entry);
 
 				entry
@@ -497,12 +743,12 @@ impl Storage {
             // find transaction that spends the same output
             let prevout: HashedOutPoint = input.previous_output.clone().into();
             if let Some(entry_hash) = self.by_previous_output.get(&prevout).cloned() {
-                // check if this is final transaction. If so, that's a potential double-spend error
+                // check if this is final transaction, which isn't replaceable. If so, that's a potential double-spend error
                 let entry = self
                     .by_hash
                     .get(&entry_hash)
                     .expect("checked that it exists line above; qed");
-                if entry.transaction.is_final() {
+                if entry.transaction.is_final() && !signals_replaceability(&entry.transaction) {
                     return DoubleSpendCheckResult::DoubleSpend(
                         entry_hash,
                         prevout.out_point.hash,
@@ -553,6 +799,90 @@ impl Storage {
         }
     }
 
+    pub fn check_replacement(
+        &self,
+        transaction: &Transaction,
+        fee: u64,
+        size: usize,
+    ) -> ReplaceResult {
+        // find all in-pool transactions that are spending the same outputs
+        let mut conflicts: Vec<H256> = Vec::new();
+        let mut is_bip125_replacement = false;
+        for input in &transaction.inputs {
+            let prevout: HashedOutPoint = input.previous_output.clone().into();
+            if let Some(entry_hash) = self.by_previous_output.get(&prevout) {
+                let entry = self
+                    .by_hash
+                    .get(entry_hash)
+                    .expect("by_previous_output only references in-pool transactions; qed");
+                // non-final transactions could always be replaced
+                // final transactions could only be replaced if they're signaling replaceability
+                if entry.transaction.is_final() {
+                    if !signals_replaceability(&entry.transaction) {
+                        return ReplaceResult::NotReplaceable(entry_hash.clone());
+                    }
+                    is_bip125_replacement = true;
+                }
+                if !conflicts.contains(entry_hash) {
+                    conflicts.push(entry_hash.clone());
+                }
+            }
+        }
+
+        if conflicts.is_empty() {
+            return ReplaceResult::NoConflicts;
+        }
+
+        // all descendants of conflicting transactions are also replaced
+        let mut replaced: Vec<H256> = Vec::new();
+        let mut queue: VecDeque<H256> = conflicts.into_iter().collect();
+        while let Some(hash) = queue.pop_front() {
+            if replaced.contains(&hash) {
+                continue;
+            }
+            if let Some(descendants) = self.references.by_input.get(&hash) {
+                queue.extend(descendants.iter().cloned());
+            }
+            replaced.push(hash);
+        }
+
+        // transaction can't depend on transactions it is replacing
+        if let Some(input) = transaction
+            .inputs
+            .iter()
+            .find(|input| replaced.contains(&input.previous_output.hash))
+        {
+            return ReplaceResult::SpendsConflictingTransaction(input.previous_output.hash.clone());
+        }
+
+        if is_bip125_replacement {
+            let (replaced_fee, replaced_size) = replaced
+                .iter()
+                .filter_map(|hash| self.by_hash.get(hash))
+                .fold((0u64, 0usize), |(fee, size), entry| {
+                    (fee + entry.miner_fee, size + entry.size)
+                });
+
+            // replacement must pay more than all replaced transactions together
+            if fee <= replaced_fee {
+                return ReplaceResult::InsufficientFee {
+                    required: replaced_fee + 1,
+                    actual: fee,
+                };
+            }
+
+            // replacement fee rate must be greater than fee rate of replaced transactions
+            if fee as u128 * replaced_size as u128 <= replaced_fee as u128 * size as u128 {
+                return ReplaceResult::InsufficientFeeRate {
+                    required: replaced_fee / replaced_size as u64,
+                    actual: fee / size as u64,
+                };
+            }
+        }
+
+        ReplaceResult::Replace(replaced)
+    }
+
     pub fn remove_by_prevout(&mut self, prevout: &OutPoint) -> Option<Vec<IndexedTransaction>> {
         let mut queue: VecDeque<OutPoint> = VecDeque::new();
         let mut removed: Vec<IndexedTransaction> = Vec::new();
@@ -664,6 +994,7 @@ impl Storage {
                 .iter()
                 .map(|entry| entry.hash.clone())
                 .nth(0),
+            OrderingStrategy::ByAncestorFeeRate => self.best_ancestor_package().into_iter().nth(0),
         };
         top_hash.map(|hash| {
 			let entry = self.remove_by_hash(&hash)
@@ -695,6 +1026,168 @@ impl Storage {
     pub fn get_transactions_ids(&self) -> Vec<H256> {
         self.by_hash.keys().cloned().collect()
     }
+
+    /// Returns hashes of transaction with the best ancestor fee rate and all its in-pool ancestors.
+    /// Ancestors are returned before descendants.
+    pub fn best_ancestor_package(&self) -> Vec<H256> {
+        self.by_ancestor_score
+            .iter()
+            .next()
+            .and_then(|entry| self.by_hash.get(&entry.hash))
+            .map(|entry| self.ancestor_package(entry, &HashSet::new()))
+            .unwrap_or_default()
+    }
+
+    /// Returns hashes of transaction and all its in-pool ancestors, except transactions from the
+    /// `removed` set. Ancestors are returned before descendants.
+    fn ancestor_package(&self, entry: &Entry, removed: &HashSet<H256>) -> Vec<H256> {
+        let mut package: Vec<_> = entry
+            .ancestors
+            .iter()
+            .filter(|hash| !removed.contains(hash))
+            .filter_map(|hash| self.by_hash.get(hash))
+            .collect();
+        // ancestors always have less in-pool ancestors than their descendants
+        package.sort_by_key(|entry| entry.ancestors.len());
+        package.push(entry);
+        package
+            .into_iter()
+            .map(|entry| entry.hash.clone())
+            .collect()
+    }
+
+    /// Returns hash of the transaction with lowest mining score
+    pub fn lowest_score_transaction(&self) -> Option<H256> {
+        self.by_eviction_score
+            .iter()
+            .next_back()
+            .map(|entry| entry.hash.clone())
+    }
+
+    /// Returns true if transaction isn't evicted from the pool of given maximal size, when it is
+    /// inserted after `replaced` transactions are removed.
+    pub fn survives_eviction(
+        &self,
+        entry: &Entry,
+        replaced: &[H256],
+        max_size_in_bytes: usize,
+    ) -> bool {
+        let replaced_size: usize = replaced
+            .iter()
+            .filter_map(|hash| self.by_hash.get(hash))
+            .map(|entry| entry.size)
+            .sum();
+        let mut size = self.transactions_size_in_bytes + entry.size - replaced_size;
+        let mut removed: HashSet<H256> = replaced.iter().cloned().collect();
+        let entry_score: ByTransactionScoreOrderedEntry = entry.into();
+        for evicted in self.by_eviction_score.iter().rev() {
+            if size <= max_size_in_bytes {
+                return true;
+            }
+            if removed.contains(&evicted.hash) {
+                continue;
+            }
+
+            // transaction itself has the lowest score => it is evicted
+            if entry_score > *evicted {
+                return false;
+            }
+            // transaction is evicted together with its ancestor
+            if entry.ancestors.contains(&evicted.hash) {
+                return false;
+            }
+
+            let mut evicted_hashes = self.descendants(&evicted.hash);
+            evicted_hashes.insert(evicted.hash.clone());
+            for hash in evicted_hashes {
+                if let Some(evicted_entry) = self.by_hash.get(&hash) {
+                    if removed.insert(hash) {
+                        size -= evicted_entry.size;
+                    }
+                }
+            }
+        }
+
+        size <= max_size_in_bytes
+    }
+
+    /// Returns hashes of all in-pool descendants of the transaction.
+    fn descendants(&self, h: &H256) -> HashSet<H256> {
+        // walk the spending graph instead of scanning all entries
+        let mut visited: HashSet<H256> = HashSet::new();
+        let mut queue: VecDeque<H256> = VecDeque::new();
+        queue.push_back(h.clone());
+        while let Some(hash) = queue.pop_front() {
+            if let Some(children) = self.references.by_input.get(&hash) {
+                for child in children {
+                    if visited.insert(child.clone()) {
+                        queue.push_back(child.clone());
+                    }
+                }
+            }
+        }
+        visited
+    }
+
+    /// Removes transaction and all its in-pool descendants. Removed transaction is the last returned entry.
+    pub fn remove_with_descendants(&mut self, h: &H256) -> Vec<Entry> {
+        let mut descendants: Vec<_> = self
+            .descendants(h)
+            .into_iter()
+            .filter_map(|hash| self.by_hash.get(&hash))
+            .map(|entry| (entry.ancestors.len(), entry.hash.clone()))
+            .collect();
+        // descendants always have more in-pool ancestors than their ancestors => remove them first
+        descendants.sort_by(|a, b| b.0.cmp(&a.0));
+        descendants.push((0, h.clone()));
+
+        let mut removed = Vec::with_capacity(descendants.len());
+        for (_, hash) in descendants {
+            if let Some(entry) = self.remove_by_hash(&hash) {
+                self.remove_from_packages(&entry);
+                removed.push(entry);
+            }
+        }
+        removed
+    }
+
+    /// Updates score of all packages, removed entry has been in
+    fn remove_from_packages(&mut self, entry: &Entry) {
+        for ancestor_hash in &entry.ancestors {
+            if let Some(ancestor_entry) = self.by_hash.get_mut(ancestor_hash) {
+                let removed = self
+                    .references
+                    .ordered
+                    .by_package_score
+                    .remove(&(ancestor_entry as &Entry).into());
+
+                ancestor_entry.package_size -= entry.size;
+                ancestor_entry.package_miner_fee -= entry.miner_fee;
+                ancestor_entry.package_miner_virtual_fee -= entry.miner_virtual_fee;
+
+                if removed {
+                    self.references
+                        .ordered
+                        .by_package_score
+                        .insert((ancestor_entry as &Entry).into());
+                }
+            }
+        }
+    }
+
+    pub fn get_in_pool_parents(&self, entry: &Entry) -> Vec<H256> {
+        let mut parents: Vec<H256> = entry
+            .transaction
+            .inputs
+            .iter()
+            .map(|input| &input.previous_output.hash)
+            .filter(|hash| self.by_hash.contains_key(hash))
+            .cloned()
+            .collect();
+        parents.sort();
+        parents.dedup();
+        parents
+    }
 }
 
 impl ReferenceStorage {
@@ -757,7 +1250,12 @@ impl OrderedReferenceStorage {
 
 impl HeapSizeOf for Storage {
     fn heap_size_of_children(&self) -> usize {
-        self.by_hash.heap_size_of_children() + self.references.heap_size_of_children()
+        // HeapSizeOf is not implemented for BTreeSet => rough estimation here
+        use std::mem::size_of;
+        self.by_hash.heap_size_of_children()
+            + self.by_previous_output.heap_size_of_children()
+            + self.by_eviction_score.len() * size_of::<ByTransactionScoreOrderedEntry>()
+            + self.references.heap_size_of_children()
     }
 }
 
@@ -782,9 +1280,7 @@ impl HeapSizeOf for OrderedReferenceStorage {
 
 impl Default for MemoryPool {
     fn default() -> Self {
-        MemoryPool {
-            storage: Storage::new(),
-        }
+        MemoryPool::with_max_size(DEFAULT_MAX_MEMORY_POOL_SIZE)
     }
 }
 
@@ -794,18 +1290,143 @@ impl MemoryPool {
         MemoryPool::default()
     }
 
-    /// Insert verified transaction to the `MemoryPool`
-    pub fn insert_verified<FC: MemoryPoolFeeCalculator>(&mut self, t: IndexedTransaction, fc: &FC) {
-        if let Some(entry) = self.make_entry(t, fc) {
-            let descendants = self.storage.remove_by_parent_hash(&entry.hash);
-            self.storage.insert(entry);
-            if let Some(descendants_iter) = descendants.map(|d| d.into_iter()) {
-                for descendant in descendants_iter {
-                    if let Some(descendant_entry) = self.make_entry(descendant, fc) {
-                        self.storage.insert(descendant_entry);
-                    }
+    /// Creates new memory pool, which holds up to `max_size_in_bytes` bytes of transactions
+    pub fn with_max_size(max_size_in_bytes: usize) -> Self {
+        MemoryPool {
+            storage: Storage::new(),
+            best_block_height: 0,
+            max_size_in_bytes: max_size_in_bytes,
+            minimum_fee_rate: 0,
+            min_relay_fee_rate: DEFAULT_MIN_RELAY_FEE_RATE,
+        }
+    }
+
+    /// Sets minimal fee rate (in satoshis per 1000 bytes) of transactions entering the pool.
+    /// Transactions already in the pool are not affected
+    pub fn set_min_relay_fee_rate(&mut self, min_relay_fee_rate: u64) {
+        self.min_relay_fee_rate = min_relay_fee_rate;
+    }
+
+    /// Minimal fee rate (in satoshis per 1000 bytes) of transactions entering the pool
+    pub fn min_relay_fee_rate(&self) -> u64 {
+        self.min_relay_fee_rate
+    }
+
+    /// Minimal fee of transaction of given size, required to enter the pool
+    pub fn min_relay_fee(&self, size: usize) -> u64 {
+        self.min_relay_fee_rate * size as u64 / 1000
+    }
+
+    /// Insert verified transaction to the `MemoryPool`.
+    ///
+    /// Transactions paying fee below the minimal relay fee, or fee rate not greater than the current
+    /// minimal fee rate are rejected. If the pool size exceeds the limit after insertion, transactions
+    /// with lowest fee rate are evicted (together with their in-pool descendants). Error is returned
+    /// if the inserted transaction itself is evicted.
+    pub fn insert_verified(
+        &mut self,
+        t: IndexedTransaction,
+        fc: &MemoryPoolFeeCalculator,
+    ) -> Result<(), InsertError> {
+        let entry = self.make_entry(t, fc)?;
+        self.insert_entry(entry, fc)
+    }
+
+    fn insert_entry(
+        &mut self,
+        entry: Entry,
+        fc: &MemoryPoolFeeCalculator,
+    ) -> Result<(), InsertError> {
+        self.check_minimum_fee_rate(&entry)?;
+
+        let hash = entry.hash.clone();
+        let descendants = self.storage.remove_by_parent_hash(&entry.hash);
+        self.storage.insert(entry);
+        if let Some(descendants_iter) = descendants.map(|d| d.into_iter()) {
+            for descendant in descendants_iter {
+                if let Ok(descendant_entry) = self.make_entry(descendant, fc) {
+                    self.storage.insert(descendant_entry);
+                }
+            }
+        }
+
+        self.trim_to_size();
+        if !self.storage.contains(&hash) {
+            return Err(InsertError::Evicted);
+        }
+
+        Ok(())
+    }
+
+    fn check_minimum_fee_rate(&self, entry: &Entry) -> Result<(), InsertError> {
+        // compare fee rates without rounding the entry fee rate
+        if self.minimum_fee_rate != 0
+            && entry.miner_fee * 1000 <= self.minimum_fee_rate * entry.size as u64
+        {
+            return Err(InsertError::MemoryPoolFull {
+                minimum_fee_rate: self.minimum_fee_rate,
+                fee_rate: fee_rate(entry),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Evicts transactions with lowest fee rate (and all their descendants) until the pool
+    /// size is below the limit. Minimal fee rate is raised to the fee rate of the last evicted transaction.
+    fn trim_to_size(&mut self) {
+        while self.storage.transactions_size_in_bytes > self.max_size_in_bytes {
+            let hash = match self.storage.lowest_score_transaction() {
+                Some(hash) => hash,
+                None => break,
+            };
+
+            let removed = self.storage.remove_with_descendants(&hash);
+            if let Some(evicted) = removed.last() {
+                self.minimum_fee_rate = ::std::cmp::max(self.minimum_fee_rate, fee_rate(evicted));
+            }
+        }
+    }
+
+    /// Insert verified transaction to the `MemoryPool`, replacing in-pool transactions that are
+    /// spending the same outputs (and all their descendants).
+    ///
+    /// Non-final transactions are always replaced. Final transactions are only replaced if they
+    /// are signaling replaceability (BIP125) and the new transaction pays both greater absolute fee
+    /// and greater fee rate than all replaced transactions. Either all conflicting transactions are
+    /// replaced, or the memory pool is left untouched.
+    ///
+    /// Returns hashes of all replaced transactions.
+    pub fn replace_verified(
+        &mut self,
+        t: IndexedTransaction,
+        fc: &MemoryPoolFeeCalculator,
+    ) -> Result<Vec<H256>, InsertError> {
+        let fee = fc.calculate(self, &t.raw);
+        let size = self.get_transaction_size(&t.raw);
+        match self.storage.check_replacement(&t.raw, fee, size) {
+            ReplaceResult::NoConflicts => {
+                self.insert_verified(t, fc)?;
+                Ok(Vec::new())
+            }
+            ReplaceResult::Replace(replaced) => {
+                // check the fee before touching replaced transactions
+                let entry = self.make_entry(t, fc)?;
+                self.check_minimum_fee_rate(&entry)?;
+                // replaced transactions are kept if the replacement would be evicted anyway
+                if !self
+                    .storage
+                    .survives_eviction(&entry, &replaced, self.max_size_in_bytes)
+                {
+                    return Err(InsertError::Evicted);
                 }
+                for hash in &replaced {
+                    self.storage.remove_by_hash(hash);
+                }
+                self.insert_entry(entry, fc)?;
+                Ok(replaced)
             }
+            result => Err(InsertError::Replacement(result)),
         }
     }
 
@@ -877,6 +1498,73 @@ impl MemoryPool {
         self.storage.set_virtual_fee(h, virtual_fee)
     }
 
+    /// Adds virtual fee delta to the memory pool transaction, so that it is selected (or evicted) as if it
+    /// has paid `fee_delta` more fee. Real transaction fee is still used to check if it's paying minimal fee.
+    /// Delta is kept until transaction is removed from the pool.
+    /// Returns false if transaction is not in the pool and error if accumulated delta overflows.
+    pub fn prioritise_transaction(&mut self, h: &H256, fee_delta: i64) -> Result<bool, String> {
+        let virtual_fee = match self.storage.get_by_hash(h) {
+            Some(entry) => entry
+                .miner_virtual_fee
+                .checked_add(fee_delta)
+                .ok_or_else(|| "Accumulated fee delta is out of range".to_owned())?,
+            None => return Ok(false),
+        };
+
+        self.storage.set_virtual_fee(h, virtual_fee);
+        Ok(true)
+    }
+
+    /// Restores entry time and virtual fee of the transaction, loaded from the memory pool dump
+    pub fn restore_dumped_metadata(&mut self, h: &H256, time: u32, fee_delta: i64) {
+        if let Some(entry) = self.storage.by_hash.get_mut(h) {
+            entry.time = time;
+        }
+        if fee_delta != 0 {
+            self.storage.set_virtual_fee(h, fee_delta);
+        }
+    }
+
+    /// Writes all memory pool transactions (ancestors before descendants) to the file at given path.
+    /// The file is replaced atomically, so previous dump survives a crash during the write.
+    pub fn dump(&self, path: &Path) -> Result<(), io::Error> {
+        let transactions: Vec<_> = self
+            .iter(OrderingStrategy::ByTimestamp)
+            .map(|entry| DumpedTransaction {
+                transaction: entry.transaction.clone(),
+                time: entry.time,
+                fee_delta: entry.miner_virtual_fee,
+            })
+            .collect();
+
+        let mut stream = Stream::new();
+        stream
+            .append(&MEMORY_POOL_DUMP_VERSION)
+            .append_list(&transactions);
+
+        let tmp_path = path.with_extension("new");
+        File::create(&tmp_path)?.write_all(&stream.out())?;
+        fs::rename(&tmp_path, path)
+    }
+
+    /// Reads transactions from the memory pool dump file, written by `dump`.
+    /// Transactions must be verified again before they're inserted to the pool.
+    pub fn load(path: &Path) -> Result<Vec<DumpedTransaction>, io::Error> {
+        let invalid_data =
+            |err: ReaderError| io::Error::new(io::ErrorKind::InvalidData, format!("{:?}", err));
+
+        let mut reader = Reader::from_read(File::open(path)?);
+        let version: u32 = reader.read().map_err(invalid_data)?;
+        if version != MEMORY_POOL_DUMP_VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Unsupported memory pool dump version: {}", version),
+            ));
+        }
+
+        reader.read_list().map_err(invalid_data)
+    }
+
     /// Get transaction by hash
     pub fn get(&self, hash: &H256) -> Option<&Transaction> {
         self.storage
@@ -895,6 +1583,64 @@ impl MemoryPool {
         Information {
             transactions_count: self.storage.by_hash.len(),
             transactions_size_in_bytes: self.storage.transactions_size_in_bytes,
+            memory_usage: self.heap_size_of_children(),
+            minimum_fee_rate: self.minimum_fee_rate,
+            min_relay_fee_rate: self.min_relay_fee_rate,
+        }
+    }
+
+    /// Returns information on all `MemoryPool` entries (as in verbose GetRawMemPool RPC).
+    /// Ancestors are always returned before descendant transactions.
+    pub fn entries_information(&self) -> Vec<EntryInformation> {
+        self.iter(OrderingStrategy::ByTimestamp)
+            .map(|entry| EntryInformation {
+                hash: entry.hash.clone(),
+                size: entry.size,
+                fee: entry.miner_fee,
+                fee_delta: entry.miner_virtual_fee,
+                time: entry.time,
+                height: entry.height,
+                depends: self.storage.get_in_pool_parents(entry),
+            })
+            .collect()
+    }
+
+    /// Process new best block at given height.
+    ///
+    /// Transactions that can't be mined in the next block because of their expiry height are removed
+    /// from the pool (together with all their in-pool descendants).
+    pub fn on_block(&mut self, height: u32) -> Vec<IndexedTransaction> {
+        let expired: Vec<_> = self
+            .storage
+            .by_hash
+            .values()
+            .filter(|entry| {
+                entry.transaction.expiry_height != 0 && entry.transaction.expiry_height <= height
+            })
+            .map(|entry| entry.hash.clone())
+            .collect();
+
+        let mut removed = Vec::new();
+        for hash in expired {
+            removed.extend(
+                self.storage
+                    .remove_with_descendants(&hash)
+                    .into_iter()
+                    .map(|entry| IndexedTransaction::new(entry.hash, entry.transaction)),
+            );
+        }
+
+        self.set_best_block_height(height);
+        removed
+    }
+
+    /// Set height of the best block. This height is remembered for all transactions entering the pool.
+    ///
+    /// Minimal fee rate is reset if the pool is less than half full after the block.
+    pub fn set_best_block_height(&mut self, height: u32) {
+        self.best_block_height = height;
+        if self.storage.transactions_size_in_bytes < self.max_size_in_bytes / 2 {
+            self.minimum_fee_rate = 0;
         }
     }
 
@@ -909,33 +1655,42 @@ impl MemoryPool {
         self.storage.is_output_spent(prevout)
     }
 
-    fn make_entry<FC: MemoryPoolFeeCalculator>(
+    fn make_entry(
         &mut self,
         t: IndexedTransaction,
-        fc: &FC,
-    ) -> Option<Entry> {
-        let ancestors = self.get_ancestors(&t.raw);
+        fc: &MemoryPoolFeeCalculator,
+    ) -> Result<Entry, InsertError> {
         let size = self.get_transaction_size(&t.raw);
-        let storage_index = self.get_storage_index();
         let miner_fee = fc.calculate(self, &t.raw);
 
         // do not accept any transactions that have negative OR zero fee
-        if miner_fee == 0 {
-            return None;
+        let required_fee = ::std::cmp::max(1, self.min_relay_fee(size));
+        if miner_fee < required_fee {
+            return Err(InsertError::InsufficientFee {
+                required: required_fee,
+                actual: miner_fee,
+            });
         }
 
-        Some(Entry {
+        let ancestors = self.get_ancestors(&t.raw);
+        let storage_index = self.get_storage_index();
+        Ok(Entry {
             transaction: t.raw,
             hash: t.hash,
             ancestors: ancestors,
             storage_index: storage_index,
             size: size,
+            time: ::time::get_time().sec as u32,
+            height: self.best_block_height,
             miner_fee: miner_fee,
             miner_virtual_fee: 0,
             // following fields are also updated when inserted to storage
             package_size: size,
             package_miner_fee: miner_fee,
             package_miner_virtual_fee: 0,
+            ancestor_size: size,
+            ancestor_miner_fee: miner_fee,
+            ancestor_miner_virtual_fee: 0,
         })
     }
 
@@ -955,7 +1710,7 @@ impl MemoryPool {
     }
 
     fn get_transaction_size(&self, t: &Transaction) -> usize {
-        t.serialized_size()
+        t.total_size()
     }
 
     #[cfg(not(test))]
@@ -970,6 +1725,11 @@ impl MemoryPool {
     }
 }
 
+/// Fee rate (in satoshis per 1000 bytes) of memory pool entry
+fn fee_rate(entry: &Entry) -> u64 {
+    entry.miner_fee * 1000 / ::std::cmp::max(entry.size, 1) as u64
+}
+
 impl TransactionProvider for MemoryPool {
     fn transaction_bytes(&self, hash: &H256) -> Option<Bytes> {
         self.get(hash).map(|t| serialize(t))
@@ -1009,6 +1769,14 @@ pub struct MemoryPoolIterator<'a> {
     references: ReferenceStorage,
     removed: HashSet<H256>,
     strategy: OrderingStrategy,
+    /// Not yet returned transactions of the selected ancestor package
+    package: VecDeque<H256>,
+    /// Ancestor scores of transactions, which ancestors have been already returned
+    modified: HashMap<H256, ByAncestorScoreOrderedEntry>,
+    /// Transactions with modified ancestor score, ordered by this score
+    by_modified_ancestor_score: BTreeSet<ByAncestorScoreOrderedEntry>,
+    /// Lower bound of not yet visited transactions with unmodified ancestor score
+    ancestor_score_cursor: Bound<ByAncestorScoreOrderedEntry>,
 }
 
 impl<'a> MemoryPoolIterator<'a> {
@@ -1018,12 +1786,86 @@ impl<'a> MemoryPoolIterator<'a> {
             references: memory_pool.storage.references.clone(),
             removed: HashSet::new(),
             strategy: strategy,
+            package: VecDeque::new(),
+            modified: HashMap::new(),
+            by_modified_ancestor_score: BTreeSet::new(),
+            ancestor_score_cursor: Bound::Unbounded,
         }
     }
-}
-
-impl<'a> Iterator for MemoryPoolIterator<'a> {
-    type Item = &'a Entry;
+
+    /// Returns not yet returned transaction with the best ancestor fee rate.
+    fn best_ancestor_score_entry(&mut self) -> Option<&'a Entry> {
+        let storage = &self.memory_pool.storage;
+        // score of transaction is only modified when its ancestor is returned => transactions,
+        // skipped in the storage ordering, are never visited again
+        let unmodified = {
+            let removed = &self.removed;
+            let modified = &self.modified;
+            storage
+                .by_ancestor_score
+                .range((self.ancestor_score_cursor.clone(), Bound::Unbounded))
+                .find(|entry| !removed.contains(&entry.hash) && !modified.contains_key(&entry.hash))
+                .cloned()
+        };
+        self.ancestor_score_cursor = match unmodified {
+            Some(ref entry) => Bound::Included(entry.clone()),
+            None => match storage.by_ancestor_score.iter().next_back() {
+                Some(entry) => Bound::Excluded(entry.clone()),
+                None => Bound::Unbounded,
+            },
+        };
+
+        let best = match (unmodified, self.by_modified_ancestor_score.iter().next()) {
+            (Some(unmodified), Some(modified)) => {
+                if modified < &unmodified {
+                    modified.hash.clone()
+                } else {
+                    unmodified.hash
+                }
+            }
+            (Some(unmodified), None) => unmodified.hash,
+            (None, Some(modified)) => modified.hash.clone(),
+            (None, None) => return None,
+        };
+        storage.by_hash.get(&best)
+    }
+
+    /// Excludes returned transaction from the ancestor score of its descendants.
+    fn update_ancestor_scores(&mut self, entry: &Entry) {
+        if let Some(score) = self.modified.remove(&entry.hash) {
+            self.by_modified_ancestor_score.remove(&score);
+        }
+
+        let storage = &self.memory_pool.storage;
+        for descendant_hash in storage.descendants(&entry.hash) {
+            let descendant = match storage.by_hash.get(&descendant_hash) {
+                Some(descendant) => descendant,
+                None => continue,
+            };
+            if self.removed.contains(&descendant_hash)
+                || !descendant.ancestors.contains(&entry.hash)
+            {
+                continue;
+            }
+
+            let mut score = match self.modified.remove(&descendant_hash) {
+                Some(score) => {
+                    self.by_modified_ancestor_score.remove(&score);
+                    score
+                }
+                None => descendant.into(),
+            };
+            score.ancestor_size -= entry.size;
+            score.ancestor_miner_fee -= entry.miner_fee;
+            score.ancestor_miner_virtual_fee -= entry.miner_virtual_fee;
+            self.by_modified_ancestor_score.insert(score.clone());
+            self.modified.insert(descendant_hash, score);
+        }
+    }
+}
+
+impl<'a> Iterator for MemoryPoolIterator<'a> {
+    type Item = &'a Entry;
 
     fn next(&mut self) -> Option<Self::Item> {
         let top_hash = match self.strategy {
@@ -1048,6 +1890,18 @@ impl<'a> Iterator for MemoryPoolIterator<'a> {
                 .iter()
                 .map(|entry| entry.hash.clone())
                 .nth(0),
+            OrderingStrategy::ByAncestorFeeRate => {
+                if self.package.is_empty() {
+                    if let Some(entry) = self.best_ancestor_score_entry() {
+                        self.package = self
+                            .memory_pool
+                            .storage
+                            .ancestor_package(entry, &self.removed)
+                            .into();
+                    }
+                }
+                self.package.pop_front()
+            }
         };
 
         top_hash.map(|top_hash| {
@@ -1063,6 +1917,9 @@ impl<'a> Iterator for MemoryPoolIterator<'a> {
                 &self.memory_pool.storage.by_hash,
                 entry,
             );
+            if let OrderingStrategy::ByAncestorFeeRate = self.strategy {
+                self.update_ancestor_scores(entry);
+            }
             entry
         })
     }
@@ -1073,15 +1930,28 @@ pub mod tests {
     extern crate zebra_test_data;
 
     use self::zebra_test_data::{ChainBuilder, TransactionBuilder};
-    use super::{DoubleSpendCheckResult, MemoryPool, OrderingStrategy};
-    use fee::NonZeroFeeCalculator;
+    use super::{
+        DoubleSpendCheckResult, DumpedTransaction, InsertError, MemoryPool, OrderingStrategy,
+        ReplaceResult,
+    };
+    use fee::{MemoryPoolFeeCalculator, NonZeroFeeCalculator};
     use heapsize::HeapSizeOf;
+    use std::collections::HashSet;
     use zebra_chain::{OutPoint, Transaction};
 
+    struct FixedFeeCalculator(u64);
+
+    impl MemoryPoolFeeCalculator for FixedFeeCalculator {
+        fn calculate(&self, _: &MemoryPool, _: &Transaction) -> u64 {
+            self.0
+        }
+    }
+
     fn to_memory_pool(chain: &mut ChainBuilder) -> MemoryPool {
         let mut pool = MemoryPool::new();
         for transaction in chain.transactions.iter().cloned() {
-            pool.insert_verified(transaction.into(), &NonZeroFeeCalculator);
+            pool.insert_verified(transaction.into(), &NonZeroFeeCalculator)
+                .unwrap();
         }
         pool
     }
@@ -1096,23 +1966,55 @@ pub mod tests {
 
         let size1 = pool.heap_size_of_children();
 
-        pool.insert_verified(default_tx().into(), &NonZeroFeeCalculator);
+        pool.insert_verified(default_tx().into(), &NonZeroFeeCalculator)
+            .unwrap();
         let size2 = pool.heap_size_of_children();
         assert!(size2 > size1);
 
-        pool.insert_verified(default_tx().into(), &NonZeroFeeCalculator);
+        pool.insert_verified(default_tx().into(), &NonZeroFeeCalculator)
+            .unwrap();
         let size3 = pool.heap_size_of_children();
         assert!(size3 > size2);
     }
 
+    #[test]
+    fn test_memory_pool_usage_follows_inserts_and_removals() {
+        let chain = &mut ChainBuilder::new();
+        TransactionBuilder::with_output(10)
+            .store(chain)
+            .into_input(0)
+            .add_output(20)
+            .store(chain);
+        let mut pool = MemoryPool::new();
+
+        let usage0 = pool.information().memory_usage;
+        pool.insert_verified(chain.at(0).into(), &NonZeroFeeCalculator)
+            .unwrap();
+        let usage1 = pool.information().memory_usage;
+        assert!(usage1 > usage0);
+        pool.insert_verified(chain.at(1).into(), &NonZeroFeeCalculator)
+            .unwrap();
+        let usage2 = pool.information().memory_usage;
+        assert!(usage2 > usage1);
+        assert_eq!(usage2, pool.heap_size_of_children());
+
+        pool.remove_by_hash(&chain.hash(1));
+        let usage3 = pool.information().memory_usage;
+        assert!(usage3 < usage2);
+        pool.remove_by_hash(&chain.hash(0));
+        assert!(pool.information().memory_usage < usage3);
+    }
+
     #[test]
     fn test_memory_pool_insert_same_transaction() {
         let mut pool = MemoryPool::new();
-        pool.insert_verified(default_tx().into(), &NonZeroFeeCalculator);
+        pool.insert_verified(default_tx().into(), &NonZeroFeeCalculator)
+            .unwrap();
         assert_eq!(pool.get_transactions_ids().len(), 1);
 
         // insert the same transaction again
-        pool.insert_verified(default_tx().into(), &NonZeroFeeCalculator);
+        pool.insert_verified(default_tx().into(), &NonZeroFeeCalculator)
+            .unwrap();
         assert_eq!(pool.get_transactions_ids().len(), 1);
     }
 
@@ -1125,7 +2027,8 @@ pub mod tests {
             vec![]
         );
 
-        pool.insert_verified(default_tx().into(), &NonZeroFeeCalculator);
+        pool.insert_verified(default_tx().into(), &NonZeroFeeCalculator)
+            .unwrap();
         assert_eq!(
             pool.read_with_strategy(OrderingStrategy::ByTimestamp),
             Some(default_tx().hash())
@@ -1156,12 +2059,14 @@ pub mod tests {
             vec![]
         );
 
-        pool.insert_verified(default_tx().into(), &NonZeroFeeCalculator);
+        pool.insert_verified(default_tx().into(), &NonZeroFeeCalculator)
+            .unwrap();
         let removed = pool.remove_with_strategy(OrderingStrategy::ByTimestamp);
         assert!(removed.is_some());
         assert_eq!(removed.unwrap(), default_tx().into());
 
-        pool.insert_verified(default_tx().into(), &NonZeroFeeCalculator);
+        pool.insert_verified(default_tx().into(), &NonZeroFeeCalculator)
+            .unwrap();
         let removed = pool.remove_n_with_strategy(100, OrderingStrategy::ByTimestamp);
         assert_eq!(removed.len(), 1);
         assert_eq!(removed[0], default_tx().into());
@@ -1180,7 +2085,8 @@ pub mod tests {
     fn test_memory_pool_remove_by_hash() {
         let mut pool = MemoryPool::new();
 
-        pool.insert_verified(default_tx().into(), &NonZeroFeeCalculator);
+        pool.insert_verified(default_tx().into(), &NonZeroFeeCalculator)
+            .unwrap();
         assert_eq!(pool.get_transactions_ids().len(), 1);
 
         // remove and check remaining transactions
@@ -1211,9 +2117,12 @@ pub mod tests {
 
         // insert child, then parent
         let mut pool = MemoryPool::new();
-        pool.insert_verified(chain.at(2).into(), &NonZeroFeeCalculator); // timestamp 0
-        pool.insert_verified(chain.at(1).into(), &NonZeroFeeCalculator); // timestamp 1
-        pool.insert_verified(chain.at(0).into(), &NonZeroFeeCalculator); // timestamp 2
+        pool.insert_verified(chain.at(2).into(), &NonZeroFeeCalculator)
+            .unwrap(); // timestamp 0
+        pool.insert_verified(chain.at(1).into(), &NonZeroFeeCalculator)
+            .unwrap(); // timestamp 1
+        pool.insert_verified(chain.at(0).into(), &NonZeroFeeCalculator)
+            .unwrap(); // timestamp 2
 
         // check that parent transaction was removed before child transaction
         let transactions = pool.remove_n_with_strategy(3, OrderingStrategy::ByTimestamp);
@@ -1266,7 +2175,8 @@ pub mod tests {
         assert_eq!(pool.get_transactions_ids().len(), 2);
 
         // insert child transaction back to the pool & assert transactions are removed in correct order
-        pool.insert_verified(chain.at(1).into(), &NonZeroFeeCalculator);
+        pool.insert_verified(chain.at(1).into(), &NonZeroFeeCalculator)
+            .unwrap();
         let transactions = pool.remove_n_with_strategy(3, OrderingStrategy::ByTransactionScore);
         assert_eq!(transactions.len(), 3);
         assert_eq!(transactions[0], chain.at(0).into());
@@ -1292,7 +2202,8 @@ pub mod tests {
 
         let mut transactions_size = 0;
         for transaction_index in 0..4 {
-            pool.insert_verified(chain.at(transaction_index).into(), &NonZeroFeeCalculator);
+            pool.insert_verified(chain.at(transaction_index).into(), &NonZeroFeeCalculator)
+                .unwrap();
             transactions_size += chain.size(transaction_index);
 
             let info = pool.information();
@@ -1402,8 +2313,10 @@ pub mod tests {
         // <
         // score({ transaction2 }) = 35/60
         let expected = vec![chain.hash(2), chain.hash(0)];
-        pool.insert_verified(chain.at(0).into(), &NonZeroFeeCalculator);
-        pool.insert_verified(chain.at(2).into(), &NonZeroFeeCalculator);
+        pool.insert_verified(chain.at(0).into(), &NonZeroFeeCalculator)
+            .unwrap();
+        pool.insert_verified(chain.at(2).into(), &NonZeroFeeCalculator)
+            .unwrap();
         assert_eq!(
             pool.read_n_with_strategy(2, OrderingStrategy::ByPackageScore),
             expected
@@ -1415,7 +2328,8 @@ pub mod tests {
         // score({ transaction2 }) = 35/60 ~ 0.583
         // => chain1 is boosted
         // => so transaction with lesser individual score (but with bigger package score) is mined first
-        pool.insert_verified(chain.at(1).into(), &NonZeroFeeCalculator);
+        pool.insert_verified(chain.at(1).into(), &NonZeroFeeCalculator)
+            .unwrap();
         let expected = vec![chain.hash(0), chain.hash(1), chain.hash(2)];
         assert_eq!(
             pool.read_n_with_strategy(3, OrderingStrategy::ByPackageScore),
@@ -1427,7 +2341,8 @@ pub mod tests {
         // >
         // score({ transaction2, transaction3 }) = (35 + 10) / 120 ~ 0.375
         // => chain2 is not boosted
-        pool.insert_verified(chain.at(3).into(), &NonZeroFeeCalculator);
+        pool.insert_verified(chain.at(3).into(), &NonZeroFeeCalculator)
+            .unwrap();
         let expected = vec![chain.hash(0), chain.hash(1), chain.hash(2), chain.hash(3)];
         assert_eq!(
             pool.read_n_with_strategy(4, OrderingStrategy::ByPackageScore),
@@ -1439,7 +2354,8 @@ pub mod tests {
         // <
         // score({ transaction2, transaction3, transaction4 }) = (35 + 10 + 100) / 180 ~ 0.806
         // => chain2 is boosted
-        pool.insert_verified(chain.at(4).into(), &NonZeroFeeCalculator);
+        pool.insert_verified(chain.at(4).into(), &NonZeroFeeCalculator)
+            .unwrap();
         let expected = vec![
             chain.hash(2),
             chain.hash(3),
@@ -1489,9 +2405,12 @@ pub mod tests {
         // transaction0 is not linked to the transaction2
         // => they are in separate chains now
         // => transaction3 has greater score than both of these chains
-        pool.insert_verified(chain.at(3).into(), &NonZeroFeeCalculator);
-        pool.insert_verified(chain.at(0).into(), &NonZeroFeeCalculator);
-        pool.insert_verified(chain.at(2).into(), &NonZeroFeeCalculator);
+        pool.insert_verified(chain.at(3).into(), &NonZeroFeeCalculator)
+            .unwrap();
+        pool.insert_verified(chain.at(0).into(), &NonZeroFeeCalculator)
+            .unwrap();
+        pool.insert_verified(chain.at(2).into(), &NonZeroFeeCalculator)
+            .unwrap();
         let expected = vec![chain.hash(3), chain.hash(0), chain.hash(2)];
         assert_eq!(
             pool.read_n_with_strategy(3, OrderingStrategy::ByPackageScore),
@@ -1500,7 +2419,8 @@ pub mod tests {
 
         // insert the missing transaction to link together chain1
         // => it now will have better score than chain2
-        pool.insert_verified(chain.at(1).into(), &NonZeroFeeCalculator);
+        pool.insert_verified(chain.at(1).into(), &NonZeroFeeCalculator)
+            .unwrap();
         let expected = vec![chain.hash(0), chain.hash(1), chain.hash(3), chain.hash(2)];
         assert_eq!(
             pool.read_n_with_strategy(4, OrderingStrategy::ByPackageScore),
@@ -1557,9 +2477,12 @@ pub mod tests {
         // insert level1 + level2. There are two chains:
         // score({ transaction3, transaction5 }) = 40 + 60
         // score({ transaction4, transaction5 }) = 50 + 60
-        pool.insert_verified(chain.at(5).into(), &NonZeroFeeCalculator);
-        pool.insert_verified(chain.at(3).into(), &NonZeroFeeCalculator);
-        pool.insert_verified(chain.at(4).into(), &NonZeroFeeCalculator);
+        pool.insert_verified(chain.at(5).into(), &NonZeroFeeCalculator)
+            .unwrap();
+        pool.insert_verified(chain.at(3).into(), &NonZeroFeeCalculator)
+            .unwrap();
+        pool.insert_verified(chain.at(4).into(), &NonZeroFeeCalculator)
+            .unwrap();
         let expected = vec![chain.hash(4), chain.hash(3), chain.hash(5)];
         assert_eq!(
             pool.read_n_with_strategy(3, OrderingStrategy::ByTransactionScore),
@@ -1574,7 +2497,8 @@ pub mod tests {
         // score({ transaction3, transaction5 }) = 40 + 60
         // score({ transaction4, transaction5 }) = 50 + 60
         // score({ transaction2, transaction5 }) = 30 + 60
-        pool.insert_verified(chain.at(2).into(), &NonZeroFeeCalculator);
+        pool.insert_verified(chain.at(2).into(), &NonZeroFeeCalculator)
+            .unwrap();
         let expected = vec![chain.hash(4), chain.hash(3), chain.hash(2), chain.hash(5)];
         assert_eq!(
             pool.read_n_with_strategy(4, OrderingStrategy::ByTransactionScore),
@@ -1590,7 +2514,8 @@ pub mod tests {
         // score({ transaction1, transaction4, transaction5 }) = 20 + 50 + 60 / 3 ~ 0.333
         // score({ transaction2, transaction5 }) = 30 + 60 / 2 = 0.45
         // but second chain will be removed first anyway because previous #1 ({ transaction4, transaction5}) now depends on level 01
-        pool.insert_verified(chain.at(1).into(), &NonZeroFeeCalculator);
+        pool.insert_verified(chain.at(1).into(), &NonZeroFeeCalculator)
+            .unwrap();
         let expected = vec![
             chain.hash(3),
             chain.hash(2),
@@ -1612,7 +2537,8 @@ pub mod tests {
         // score({ transaction0, transaction4, transaction5 }) = (10 + 50 + 60) / (60 + 60 + 142) ~ 0.458
         // score({ transaction1, transaction3, transaction5 }) = (20 + 50 + 60) / (60 + 60 + 142) ~ 0.496
         // score({ transaction2, transaction5 }) = (30 + 60) / (60 + 142) ~ 0.445
-        pool.insert_verified(chain.at(0).into(), &NonZeroFeeCalculator);
+        pool.insert_verified(chain.at(0).into(), &NonZeroFeeCalculator)
+            .unwrap();
         let expected = vec![
             chain.hash(2),
             chain.hash(1),
@@ -1659,7 +2585,8 @@ pub mod tests {
             index: 0,
         }));
 
-        pool.insert_verified(chain.at(0).into(), &NonZeroFeeCalculator);
+        pool.insert_verified(chain.at(0).into(), &NonZeroFeeCalculator)
+            .unwrap();
         assert!(!pool.is_spent(&OutPoint {
             hash: chain.hash(0),
             index: 0,
@@ -1673,7 +2600,8 @@ pub mod tests {
             index: 0,
         }));
 
-        pool.insert_verified(chain.at(1).into(), &NonZeroFeeCalculator);
+        pool.insert_verified(chain.at(1).into(), &NonZeroFeeCalculator)
+            .unwrap();
         assert!(!pool.is_spent(&OutPoint {
             hash: chain.hash(0),
             index: 0,
@@ -1687,7 +2615,8 @@ pub mod tests {
             index: 0,
         }));
 
-        pool.insert_verified(chain.at(2).into(), &NonZeroFeeCalculator);
+        pool.insert_verified(chain.at(2).into(), &NonZeroFeeCalculator)
+            .unwrap();
         assert!(pool.is_spent(&OutPoint {
             hash: chain.hash(0),
             index: 0,
@@ -1735,10 +2664,14 @@ pub mod tests {
             .store(chain); // transaction3
         let mut pool = MemoryPool::new();
 
-        pool.insert_verified(chain.at(0).into(), &NonZeroFeeCalculator);
-        pool.insert_verified(chain.at(1).into(), &NonZeroFeeCalculator);
-        pool.insert_verified(chain.at(2).into(), &NonZeroFeeCalculator);
-        pool.insert_verified(chain.at(3).into(), &NonZeroFeeCalculator);
+        pool.insert_verified(chain.at(0).into(), &NonZeroFeeCalculator)
+            .unwrap();
+        pool.insert_verified(chain.at(1).into(), &NonZeroFeeCalculator)
+            .unwrap();
+        pool.insert_verified(chain.at(2).into(), &NonZeroFeeCalculator)
+            .unwrap();
+        pool.insert_verified(chain.at(3).into(), &NonZeroFeeCalculator)
+            .unwrap();
         assert_eq!(pool.information().transactions_count, 4);
 
         assert_eq!(
@@ -1751,6 +2684,52 @@ pub mod tests {
         assert_eq!(pool.information().transactions_count, 2);
     }
 
+    #[test]
+    fn test_memory_pool_entries_information() {
+        let chain = &mut ChainBuilder::new();
+
+        TransactionBuilder::with_output(10)
+            .add_output(20)
+            .store(chain) // t0
+            .reset()
+            .set_input(&chain.at(0), 0)
+            .add_output(30)
+            .store(chain) // t0[0] -> t1
+            .reset()
+            .set_input(&chain.at(0), 1)
+            .add_input(&chain.at(1), 0)
+            .add_output(40)
+            .store(chain); // t0[1], t1[0] -> t2
+        let mut pool = MemoryPool::new();
+        pool.set_best_block_height(100);
+        pool.insert_verified(chain.at(0).into(), &NonZeroFeeCalculator)
+            .unwrap();
+        pool.set_best_block_height(101);
+        pool.insert_verified(chain.at(1).into(), &NonZeroFeeCalculator)
+            .unwrap();
+        pool.insert_verified(chain.at(2).into(), &NonZeroFeeCalculator)
+            .unwrap();
+
+        let information = pool.information();
+        assert_eq!(information.transactions_count, 3);
+        assert!(information.memory_usage > 0);
+
+        let entries = pool.entries_information();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].hash, chain.hash(0));
+        assert_eq!(entries[0].height, 100);
+        assert_eq!(entries[0].fee, 100_000_030);
+        assert_eq!(entries[0].size, chain.at(0).total_size());
+        assert!(entries[0].depends.is_empty());
+        assert_eq!(entries[1].hash, chain.hash(1));
+        assert_eq!(entries[1].height, 101);
+        assert_eq!(entries[1].depends, vec![chain.hash(0)]);
+        assert_eq!(entries[2].hash, chain.hash(2));
+        let mut depends = vec![chain.hash(0), chain.hash(1)];
+        depends.sort();
+        assert_eq!(entries[2].depends, depends);
+    }
+
     #[test]
     fn test_memory_pool_check_double_spend() {
         let chain = &mut ChainBuilder::new();
@@ -1786,9 +2765,12 @@ pub mod tests {
             .store(chain); // no double spend: t0[2] -> t6
 
         let mut pool = MemoryPool::new();
-        pool.insert_verified(chain.at(1).into(), &NonZeroFeeCalculator);
-        pool.insert_verified(chain.at(2).into(), &NonZeroFeeCalculator);
-        pool.insert_verified(chain.at(4).into(), &NonZeroFeeCalculator);
+        pool.insert_verified(chain.at(1).into(), &NonZeroFeeCalculator)
+            .unwrap();
+        pool.insert_verified(chain.at(2).into(), &NonZeroFeeCalculator)
+            .unwrap();
+        pool.insert_verified(chain.at(4).into(), &NonZeroFeeCalculator)
+            .unwrap();
         // when output is spent by nonfinal transaction
         match pool.check_double_spend(&chain.at(3)) {
             DoubleSpendCheckResult::NonFinalDoubleSpend(set) => {
@@ -1849,7 +2831,8 @@ pub mod tests {
             .store(chain); // good replacement: t0[0] -> t2
 
         let mut pool = MemoryPool::new();
-        pool.insert_verified(chain.at(1).into(), &NonZeroFeeCalculator);
+        pool.insert_verified(chain.at(1).into(), &NonZeroFeeCalculator)
+            .unwrap();
 
         // when output is spent by nonfinal transaction
         match pool.check_double_spend(&chain.at(2)) {
@@ -1896,8 +2879,556 @@ pub mod tests {
         let out1 = tx1.inputs[0].previous_output.clone();
         let out2 = tx2.inputs[0].previous_output.clone();
         let mut memory_pool = MemoryPool::new();
-        memory_pool.insert_verified(tx1.into(), &NonZeroFeeCalculator);
+        memory_pool
+            .insert_verified(tx1.into(), &NonZeroFeeCalculator)
+            .unwrap();
         assert!(memory_pool.is_spent(&out1));
         assert!(!memory_pool.is_spent(&out2));
     }
+
+    #[test]
+    fn test_memory_pool_replace_by_fee() {
+        let chain = &mut ChainBuilder::new();
+
+        TransactionBuilder::with_output(100)
+            .store(chain) // t0
+            .reset()
+            .set_input(&chain.at(0), 0)
+            .add_output(50)
+            .set_sequence(0xfffffffd)
+            .store(chain) // replaceable: t0[0] -> t1
+            .reset()
+            .set_input(&chain.at(1), 0)
+            .add_output(40)
+            .store(chain) // dependent: t0[0] -> t1[0] -> t2
+            .reset()
+            .set_input(&chain.at(0), 0)
+            .add_output(200_000_000)
+            .store(chain); // replacement: t0[0] -> t3
+
+        let mut pool = MemoryPool::new();
+        pool.insert_verified(chain.at(1).into(), &NonZeroFeeCalculator)
+            .unwrap();
+        pool.insert_verified(chain.at(2).into(), &NonZeroFeeCalculator)
+            .unwrap();
+
+        // t3 pays more than t1 + t2 together => both are replaced
+        assert_eq!(
+            pool.replace_verified(chain.at(3).into(), &NonZeroFeeCalculator),
+            Ok(vec![chain.hash(1), chain.hash(2)])
+        );
+        assert_eq!(pool.get_transactions_ids(), vec![chain.hash(3)]);
+    }
+
+    #[test]
+    fn test_memory_pool_replace_by_fee_rejects_low_fee() {
+        let chain = &mut ChainBuilder::new();
+
+        TransactionBuilder::with_output(100)
+            .store(chain) // t0
+            .reset()
+            .set_input(&chain.at(0), 0)
+            .add_output(50)
+            .set_sequence(0xfffffffd)
+            .store(chain) // replaceable: t0[0] -> t1
+            .reset()
+            .set_input(&chain.at(0), 0)
+            .add_output(40)
+            .store(chain) // low-fee replacement: t0[0] -> t2
+            .reset()
+            .set_input(&chain.at(0), 0)
+            .add_output(60)
+            .store(chain) // replacement of non-replaceable t2: t0[0] -> t3
+            .reset()
+            .set_input(&chain.at(0), 1)
+            .add_output(60)
+            .store(chain); // no conflicts: t0[1] -> t4
+
+        let mut pool = MemoryPool::new();
+        pool.insert_verified(chain.at(1).into(), &NonZeroFeeCalculator)
+            .unwrap();
+
+        // t2 pays less than t1 => rejected, pool is untouched
+        assert_eq!(
+            pool.replace_verified(chain.at(2).into(), &NonZeroFeeCalculator),
+            Err(InsertError::Replacement(ReplaceResult::InsufficientFee {
+                required: 100_000_051,
+                actual: 100_000_040,
+            }))
+        );
+        assert_eq!(pool.get_transactions_ids(), vec![chain.hash(1)]);
+
+        // now replace t1 with t2, which doesn't signal replaceability
+        pool.remove_by_hash(&chain.hash(1));
+        pool.insert_verified(chain.at(2).into(), &NonZeroFeeCalculator)
+            .unwrap();
+
+        // t2 doesn't signal replaceability => can't be replaced
+        assert_eq!(
+            pool.replace_verified(chain.at(3).into(), &NonZeroFeeCalculator),
+            Err(InsertError::Replacement(ReplaceResult::NotReplaceable(
+                chain.hash(2)
+            )))
+        );
+        assert_eq!(pool.get_transactions_ids(), vec![chain.hash(2)]);
+
+        // transaction without conflicts is simply inserted
+        assert_eq!(
+            pool.replace_verified(chain.at(4).into(), &NonZeroFeeCalculator),
+            Ok(Vec::new())
+        );
+        assert_eq!(pool.get_transactions_ids().len(), 2);
+    }
+
+    #[test]
+    fn test_memory_pool_replace_by_fee_keeps_replaced_if_replacement_is_evicted() {
+        let chain = &mut ChainBuilder::new();
+
+        TransactionBuilder::with_output(100)
+            .add_output(100)
+            .store(chain) // t0
+            .reset()
+            .set_input(&chain.at(0), 0)
+            .add_output(50)
+            .set_sequence(0xfffffffd)
+            .store(chain) // replaceable: t0[0] -> t1
+            .reset()
+            .set_input(&chain.at(0), 1)
+            .add_output(50)
+            .store(chain) // high fee rate: t0[1] -> t2
+            .reset()
+            .set_input(&chain.at(0), 0)
+            .add_output(10)
+            .add_output(20)
+            .add_output(30)
+            .store(chain); // large replacement with lowest fee rate: t0[0] -> t3
+
+        let mut pool = MemoryPool::with_max_size(chain.size(1) + chain.size(2));
+        pool.insert_verified(chain.at(1).into(), &FixedFeeCalculator(1_000))
+            .unwrap();
+        pool.insert_verified(chain.at(2).into(), &FixedFeeCalculator(100_000))
+            .unwrap();
+
+        // t3 pays more than t1, but it is larger => it would be evicted right after t1 is replaced
+        assert_eq!(
+            pool.replace_verified(chain.at(3).into(), &FixedFeeCalculator(5_000)),
+            Err(InsertError::Evicted)
+        );
+        assert!(pool.contains(&chain.hash(1)));
+        assert!(pool.contains(&chain.hash(2)));
+        assert!(!pool.contains(&chain.hash(3)));
+        assert_eq!(pool.information().minimum_fee_rate, 0);
+    }
+
+    #[test]
+    fn test_memory_pool_evicts_lowest_fee_rate_transactions() {
+        let chain = &mut ChainBuilder::new();
+        TransactionBuilder::with_output(10)
+            .store(chain) // t0
+            .set_output(20)
+            .store(chain) // t1
+            .set_output(30)
+            .store(chain) // t2
+            .set_output(5)
+            .store(chain); // t3
+
+        let mut pool = MemoryPool::with_max_size(chain.size(0) + chain.size(1));
+        pool.insert_verified(chain.at(0).into(), &NonZeroFeeCalculator)
+            .unwrap();
+        pool.insert_verified(chain.at(1).into(), &NonZeroFeeCalculator)
+            .unwrap();
+        assert_eq!(pool.information().minimum_fee_rate, 0);
+
+        // t0 has the lowest fee rate => it is evicted
+        pool.insert_verified(chain.at(2).into(), &NonZeroFeeCalculator)
+            .unwrap();
+        assert!(!pool.contains(&chain.hash(0)));
+        assert!(pool.contains(&chain.hash(1)));
+        assert!(pool.contains(&chain.hash(2)));
+        let minimum_fee_rate = 100_000_010 * 1000 / chain.size(0) as u64;
+        assert_eq!(pool.information().minimum_fee_rate, minimum_fee_rate);
+
+        // t3 pays less than the minimal fee rate => it is not accepted
+        assert_eq!(
+            pool.insert_verified(chain.at(3).into(), &NonZeroFeeCalculator),
+            Err(InsertError::MemoryPoolFull {
+                minimum_fee_rate: minimum_fee_rate,
+                fee_rate: 100_000_005 * 1000 / chain.size(3) as u64,
+            })
+        );
+        assert!(!pool.contains(&chain.hash(3)));
+        assert_eq!(pool.information().transactions_count, 2);
+
+        // minimal fee rate is reset when the pool is less than half full
+        pool.set_best_block_height(1);
+        assert_eq!(pool.information().minimum_fee_rate, minimum_fee_rate);
+        pool.remove_by_hash(&chain.hash(1));
+        pool.remove_by_hash(&chain.hash(2));
+        pool.set_best_block_height(2);
+        assert_eq!(pool.information().minimum_fee_rate, 0);
+    }
+
+    #[test]
+    fn test_memory_pool_reports_evicted_transaction() {
+        let chain = &mut ChainBuilder::new();
+        TransactionBuilder::with_output(10)
+            .store(chain) // t0
+            .set_output(20)
+            .store(chain) // t1
+            .set_output(5)
+            .store(chain); // t2
+
+        let mut pool = MemoryPool::with_max_size(chain.size(0) + chain.size(1));
+        pool.insert_verified(chain.at(0).into(), &NonZeroFeeCalculator)
+            .unwrap();
+        pool.insert_verified(chain.at(1).into(), &NonZeroFeeCalculator)
+            .unwrap();
+
+        // t2 has the lowest fee rate => it is evicted right after insertion
+        assert_eq!(
+            pool.insert_verified(chain.at(2).into(), &NonZeroFeeCalculator),
+            Err(InsertError::Evicted)
+        );
+        assert!(pool.contains(&chain.hash(0)));
+        assert!(pool.contains(&chain.hash(1)));
+        assert!(!pool.contains(&chain.hash(2)));
+        assert_eq!(
+            pool.information().minimum_fee_rate,
+            100_000_005 * 1000 / chain.size(2) as u64
+        );
+    }
+
+    #[test]
+    fn test_memory_pool_minimum_fee_rate_is_tracked_per_1000_bytes() {
+        let chain = &mut ChainBuilder::new();
+        TransactionBuilder::with_output(10)
+            .store(chain) // t0
+            .set_output(20)
+            .store(chain) // t1
+            .set_output(30)
+            .store(chain) // t2
+            .set_output(40)
+            .store(chain) // t3
+            .set_output(50)
+            .store(chain); // t4
+
+        // all fee rates except t2 fee rate are 4 satoshis per byte, when rounded down
+        let fee = |index: usize, tenths: usize| (chain.size(index) * (40 + tenths) / 10) as u64;
+        let (fee0, fee1, fee2, fee3, fee4) =
+            (fee(0, 0), fee(1, 2), fee(2, 1000), fee(3, 5), fee(4, 1));
+
+        let mut pool = MemoryPool::with_max_size(chain.size(0) + chain.size(1));
+        pool.insert_verified(chain.at(0).into(), &FixedFeeCalculator(fee0))
+            .unwrap();
+        pool.insert_verified(chain.at(1).into(), &FixedFeeCalculator(fee1))
+            .unwrap();
+
+        // t0 is evicted
+        pool.insert_verified(chain.at(2).into(), &FixedFeeCalculator(fee2))
+            .unwrap();
+        assert!(!pool.contains(&chain.hash(0)));
+        assert_eq!(
+            pool.information().minimum_fee_rate,
+            fee0 * 1000 / chain.size(0) as u64
+        );
+
+        // t3 pays less than 1 satoshi per byte more than t0 => it is accepted and t1 is evicted
+        pool.insert_verified(chain.at(3).into(), &FixedFeeCalculator(fee3))
+            .unwrap();
+        assert!(!pool.contains(&chain.hash(1)));
+        assert!(pool.contains(&chain.hash(3)));
+        let minimum_fee_rate = fee1 * 1000 / chain.size(1) as u64;
+        assert_eq!(pool.information().minimum_fee_rate, minimum_fee_rate);
+
+        // t4 pays more than t0, but less than t1 => it is rejected
+        assert_eq!(
+            pool.insert_verified(chain.at(4).into(), &FixedFeeCalculator(fee4)),
+            Err(InsertError::MemoryPoolFull {
+                minimum_fee_rate: minimum_fee_rate,
+                fee_rate: fee4 * 1000 / chain.size(4) as u64,
+            })
+        );
+    }
+
+    #[test]
+    fn test_memory_pool_rejects_transactions_below_min_relay_fee() {
+        let chain = &mut ChainBuilder::new();
+        TransactionBuilder::with_output(10)
+            .store(chain) // t0
+            .set_output(20)
+            .store(chain); // t1
+
+        let mut pool = MemoryPool::new();
+        pool.set_min_relay_fee_rate(10_000);
+        assert_eq!(pool.information().min_relay_fee_rate, 10_000);
+        let min_relay_fee = pool.min_relay_fee(chain.size(0));
+        assert_eq!(min_relay_fee, 10 * chain.size(0) as u64);
+
+        assert_eq!(
+            pool.insert_verified(chain.at(0).into(), &FixedFeeCalculator(min_relay_fee - 1)),
+            Err(InsertError::InsufficientFee {
+                required: min_relay_fee,
+                actual: min_relay_fee - 1,
+            })
+        );
+        assert!(!pool.contains(&chain.hash(0)));
+        pool.insert_verified(chain.at(0).into(), &FixedFeeCalculator(min_relay_fee))
+            .unwrap();
+        assert!(pool.contains(&chain.hash(0)));
+
+        // zero fee transactions are rejected even if the minimal relay fee is zero
+        pool.set_min_relay_fee_rate(0);
+        assert_eq!(
+            pool.insert_verified(chain.at(1).into(), &FixedFeeCalculator(0)),
+            Err(InsertError::InsufficientFee {
+                required: 1,
+                actual: 0,
+            })
+        );
+        assert!(!pool.contains(&chain.hash(1)));
+    }
+
+    #[test]
+    fn test_memory_pool_evicts_descendants() {
+        let chain = &mut ChainBuilder::new();
+        TransactionBuilder::with_output(10)
+            .store(chain) // t0
+            .into_input(0)
+            .add_output(1_000_000_000)
+            .store(chain) // t1 (child of t0, with large fee rate)
+            .reset()
+            .add_output(50)
+            .store(chain); // t2
+
+        let mut pool = MemoryPool::with_max_size(chain.size(0) + chain.size(1) + chain.size(2) - 1);
+        pool.insert_verified(chain.at(0).into(), &NonZeroFeeCalculator)
+            .unwrap();
+        pool.insert_verified(chain.at(1).into(), &NonZeroFeeCalculator)
+            .unwrap();
+        pool.insert_verified(chain.at(2).into(), &NonZeroFeeCalculator)
+            .unwrap();
+
+        let transactions: HashSet<_> = pool.get_transactions_ids().into_iter().collect();
+        assert_eq!(transactions, vec![chain.hash(2)].into_iter().collect());
+        assert_eq!(pool.information().transactions_size_in_bytes, chain.size(2));
+        assert_eq!(
+            pool.read_with_strategy(OrderingStrategy::ByPackageScore),
+            Some(chain.hash(2))
+        );
+    }
+
+    #[test]
+    fn test_memory_pool_removes_expired_transactions() {
+        let chain = &mut ChainBuilder::new();
+        TransactionBuilder::with_output(10)
+            .store(chain) // t0
+            .into_input(0)
+            .add_output(20)
+            .store(chain) // t0 -> t1
+            .reset()
+            .add_output(30)
+            .store(chain); // t2
+        let mut t0 = chain.at(0);
+        t0.expiry_height = 10;
+        let t1 = chain.at(1);
+        let mut t2 = chain.at(2);
+        t2.expiry_height = 11;
+
+        let mut pool = MemoryPool::new();
+        pool.insert_verified(t0.into(), &NonZeroFeeCalculator)
+            .unwrap();
+        pool.insert_verified(t1.into(), &NonZeroFeeCalculator)
+            .unwrap();
+        pool.insert_verified(t2.into(), &NonZeroFeeCalculator)
+            .unwrap();
+
+        assert!(pool.on_block(9).is_empty());
+        assert_eq!(pool.information().transactions_count, 3);
+
+        // t0 can't be mined in block 11 => it is removed with its descendant
+        let removed = pool.on_block(10);
+        assert_eq!(removed.len(), 2);
+        assert_eq!(pool.information().transactions_count, 1);
+        assert_eq!(pool.information().transactions_size_in_bytes, chain.size(2));
+
+        assert_eq!(pool.on_block(11).len(), 1);
+        assert_eq!(pool.information().transactions_count, 0);
+    }
+
+    #[test]
+    fn test_memory_pool_ancestor_fee_rate_ordering_strategy() {
+        let chain = &mut ChainBuilder::new();
+        // all transactions of same size
+        TransactionBuilder::with_default_input(0)
+            .set_output(10)
+            .store(chain) // transaction0
+            .into_input(0)
+            .set_output(1_000_000_000)
+            .store(chain) // transaction0 -> transaction1
+            .set_default_input(1)
+            .set_output(100_000_000)
+            .store(chain); // transaction2
+        let mut pool = to_memory_pool(chain);
+
+        // transaction0 pays no fee at all
+        pool.set_virtual_fee(&chain.hash(0), -100_000_010);
+
+        // transaction2 has better individual score than transaction0
+        assert_eq!(
+            pool.read_n_with_strategy(3, OrderingStrategy::ByTransactionScore),
+            vec![chain.hash(2), chain.hash(0), chain.hash(1)]
+        );
+        // but transaction1 pays for transaction0
+        assert_eq!(
+            pool.read_n_with_strategy(3, OrderingStrategy::ByAncestorFeeRate),
+            vec![chain.hash(0), chain.hash(1), chain.hash(2)]
+        );
+        assert_eq!(
+            pool.read_with_strategy(OrderingStrategy::ByAncestorFeeRate),
+            Some(chain.hash(0))
+        );
+        assert_eq!(
+            pool.remove_with_strategy(OrderingStrategy::ByAncestorFeeRate),
+            Some(chain.at(0).into())
+        );
+        assert_eq!(
+            pool.read_with_strategy(OrderingStrategy::ByAncestorFeeRate),
+            Some(chain.hash(1))
+        );
+    }
+
+    #[test]
+    fn test_memory_pool_ancestor_score_is_maintained() {
+        let chain = &mut ChainBuilder::new();
+        TransactionBuilder::with_output(10)
+            .store(chain) // transaction0
+            .into_input(0)
+            .set_output(20)
+            .store(chain) // transaction0 -> transaction1
+            .into_input(0)
+            .set_output(30)
+            .store(chain); // transaction0 -> transaction1 -> transaction2
+        let mut pool = to_memory_pool(chain);
+        let size = |pool: &MemoryPool, index: usize| {
+            pool.storage.get_by_hash(&chain.hash(index)).unwrap().size
+        };
+        let (size0, size1, size2) = (size(&pool, 0), size(&pool, 1), size(&pool, 2));
+        let ancestor_score = |pool: &MemoryPool, index: usize| {
+            let entry = pool.storage.get_by_hash(&chain.hash(index)).unwrap();
+            (
+                entry.ancestor_size,
+                entry.ancestor_miner_fee,
+                entry.ancestor_miner_virtual_fee,
+            )
+        };
+
+        assert_eq!(
+            ancestor_score(&pool, 2),
+            (size0 + size1 + size2, 300_000_060, 0)
+        );
+
+        // virtual fee of ancestor is included in the score of all descendants
+        pool.set_virtual_fee(&chain.hash(0), 100);
+        assert_eq!(ancestor_score(&pool, 1), (size0 + size1, 200_000_030, 100));
+        assert_eq!(
+            ancestor_score(&pool, 2),
+            (size0 + size1 + size2, 300_000_060, 100)
+        );
+
+        // mined ancestor is excluded from the score of all descendants
+        pool.remove_by_hash(&chain.hash(0));
+        assert_eq!(ancestor_score(&pool, 1), (size1, 100_000_020, 0));
+        assert_eq!(ancestor_score(&pool, 2), (size1 + size2, 200_000_050, 0));
+        assert_eq!(
+            pool.read_n_with_strategy(2, OrderingStrategy::ByAncestorFeeRate),
+            vec![chain.hash(1), chain.hash(2)]
+        );
+    }
+
+    #[test]
+    fn test_memory_pool_dump_and_load() {
+        let chain = &mut ChainBuilder::new();
+        TransactionBuilder::with_output(10)
+            .store(chain)
+            .into_input(0)
+            .set_output(20)
+            .store(chain);
+        let mut pool = to_memory_pool(chain);
+        pool.set_virtual_fee(&chain.hash(1), 100);
+
+        let path = ::std::env::temp_dir().join(format!(
+            "zebra-memory-pool-dump-{}.dat",
+            ::std::process::id()
+        ));
+        pool.dump(&path).unwrap();
+        let loaded = MemoryPool::load(&path);
+        ::std::fs::remove_file(&path).unwrap();
+
+        let time0 = pool.storage.get_by_hash(&chain.hash(0)).unwrap().time;
+        let time1 = pool.storage.get_by_hash(&chain.hash(1)).unwrap().time;
+        // parent transaction goes first
+        assert_eq!(
+            loaded.unwrap(),
+            vec![
+                DumpedTransaction {
+                    transaction: chain.at(0),
+                    time: time0,
+                    fee_delta: 0,
+                },
+                DumpedTransaction {
+                    transaction: chain.at(1),
+                    time: time1,
+                    fee_delta: 100,
+                },
+            ]
+        );
+
+        // metadata is restored after transaction is inserted back to the pool
+        let mut pool = MemoryPool::new();
+        pool.insert_verified(chain.at(0).into(), &NonZeroFeeCalculator)
+            .unwrap();
+        pool.restore_dumped_metadata(&chain.hash(0), 42, -10);
+        let entry = pool.storage.get_by_hash(&chain.hash(0)).unwrap();
+        assert_eq!(entry.time, 42);
+        assert_eq!(entry.miner_virtual_fee, -10);
+    }
+
+    #[test]
+    fn test_memory_pool_prioritise_transaction() {
+        let chain = &mut ChainBuilder::new();
+        TransactionBuilder::with_output(10)
+            .store(chain)
+            .set_output(20)
+            .store(chain)
+            .set_output(30)
+            .store(chain);
+        let mut pool = to_memory_pool(chain);
+        assert_eq!(
+            pool.prioritise_transaction(&default_tx().hash(), 100),
+            Ok(false)
+        );
+
+        // deltas are accumulated
+        assert_eq!(pool.prioritise_transaction(&chain.hash(0), 100), Ok(true));
+        assert_eq!(pool.prioritise_transaction(&chain.hash(0), 50), Ok(true));
+        assert_eq!(pool.prioritise_transaction(&chain.hash(2), -100), Ok(true));
+
+        // overflowing delta is rejected and previous delta is kept
+        assert!(pool
+            .prioritise_transaction(&chain.hash(0), i64::max_value())
+            .is_err());
+        assert_eq!(
+            pool.entries_information()
+                .into_iter()
+                .map(|entry| (entry.fee, entry.fee_delta))
+                .collect::<Vec<_>>(),
+            vec![(100_000_010, 150), (100_000_020, 0), (100_000_030, -100)]
+        );
+
+        // without deltas, transactions would be ordered in reverse
+        assert_eq!(
+            pool.read_n_with_strategy(3, OrderingStrategy::ByAncestorFeeRate),
+            vec![chain.hash(0), chain.hash(1), chain.hash(2)]
+        );
+    }
 }
@@ -0,0 +1,151 @@
+use hash::H256;
+use std::collections::BTreeMap;
+use Network;
+
+/// Mainnet checkpoints of zcashd (block height, reversed block hash).
+const MAINNET_CHECKPOINTS: &'static [(u32, &'static str)] = &[
+    (
+        2500,
+        "00000006dc968f600be11a86cbfbf7feb61c7577f45caced2e82b6d261d19744",
+    ),
+    (
+        15000,
+        "00000000b6bc56656812a5b8dcad69d6ad4446dec23b5ec456c18641fb5381ba",
+    ),
+    (
+        67500,
+        "000000006b366d2c1649a6ebb4787ac2b39c422f451880bc922e3a6fbd723616",
+    ),
+    (
+        100000,
+        "000000001c5c82cd6baccfc0879e3830fd50d5ede17fa2c37a9a253c610eb285",
+    ),
+    (
+        133337,
+        "0000000002776ccfaf06cc19857accf3e20c01965282f916b8a886e3e4a05be9",
+    ),
+    (
+        180000,
+        "000000001205b742eac4a1b3959635bdf8aeada078d6a996df89740f7b54351d",
+    ),
+    (
+        222222,
+        "000000000cafb9e56445a6cabc8057b57ee6fcc709e7adbfa195e5c7fac61343",
+    ),
+    (
+        270000,
+        "00000000025c1cfa0258e33ab050aaa9338a3d4aaa3eb41defefc887779a9729",
+    ),
+    (
+        304600,
+        "00000000028324e022a45014c4a4dc51e95d41e6bceb6ad554c5b65d5cea3ea5",
+    ),
+    (
+        410100,
+        "0000000002c565958f783a24a4ac17cde898ff525e75ed9baf66861b0b9fcada",
+    ),
+    (
+        497000,
+        "0000000000abd333f0acca6ffdf78a167699686d6a7d25c33fca5f295061ffff",
+    ),
+    (
+        525000,
+        "0000000001a36c500378be8862d9bf1bea8f1616da6e155971b608139cc7e39b",
+    ),
+];
+
+/// Set of known (height => block hash) pairs of the canonical chain.
+///
+/// Blocks at checkpointed heights must have the same hash as the checkpoint. Blocks at or
+/// below the last checkpoint, built on top of the checkpointed chain, could be verified
+/// without checking transaction scripts.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Checkpoints {
+    checkpoints: BTreeMap<u32, H256>,
+}
+
+impl Checkpoints {
+    /// Create checkpoints from given (height, block hash) pairs.
+    pub fn new<I: IntoIterator<Item = (u32, H256)>>(checkpoints: I) -> Self {
+        Checkpoints {
+            checkpoints: checkpoints.into_iter().collect(),
+        }
+    }
+
+    /// Default checkpoints of given network.
+    pub fn for_network(network: Network) -> Self {
+        let genesis_checkpoint = (0, network.genesis_block().hash().clone());
+        match network {
+            Network::Mainnet => Checkpoints::new(
+                ::std::iter::once(genesis_checkpoint).chain(
+                    MAINNET_CHECKPOINTS
+                        .iter()
+                        .map(|&(height, hash)| (height, H256::from_reversed_str(hash))),
+                ),
+            ),
+            Network::Testnet => Checkpoints::new(::std::iter::once(genesis_checkpoint)),
+            Network::Regtest | Network::Unitest | Network::Other(_) => Checkpoints::default(),
+        }
+    }
+
+    /// Returns true if there are no checkpoints.
+    pub fn is_empty(&self) -> bool {
+        self.checkpoints.is_empty()
+    }
+
+    /// Returns checkpointed hash of the block at given height.
+    pub fn hash_at(&self, height: u32) -> Option<&H256> {
+        self.checkpoints.get(&height)
+    }
+
+    /// Returns height of the last checkpoint.
+    pub fn last_height(&self) -> Option<u32> {
+        self.checkpoints.keys().next_back().cloned()
+    }
+
+    /// Returns the last checkpoint at or below given height.
+    pub fn last_at_or_below(&self, height: u32) -> Option<(u32, &H256)> {
+        self.checkpoints
+            .range(..height.saturating_add(1))
+            .next_back()
+            .map(|(height, hash)| (*height, hash))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Checkpoints;
+    use hash::H256;
+    use Network;
+
+    #[test]
+    fn checkpoints_lookup() {
+        let checkpoints = Checkpoints::new(vec![(10, H256::from(1)), (20, H256::from(2))]);
+        assert_eq!(checkpoints.hash_at(10), Some(&H256::from(1)));
+        assert_eq!(checkpoints.hash_at(15), None);
+        assert_eq!(checkpoints.last_height(), Some(20));
+        assert_eq!(checkpoints.last_at_or_below(9), None);
+        assert_eq!(checkpoints.last_at_or_below(10), Some((10, &H256::from(1))));
+        assert_eq!(checkpoints.last_at_or_below(19), Some((10, &H256::from(1))));
+        assert_eq!(
+            checkpoints.last_at_or_below(100),
+            Some((20, &H256::from(2)))
+        );
+        assert_eq!(Checkpoints::default().last_height(), None);
+    }
+
+    #[test]
+    fn mainnet_checkpoints() {
+        let checkpoints = Checkpoints::for_network(Network::Mainnet);
+        assert_eq!(
+            checkpoints.hash_at(0),
+            Some(Network::Mainnet.genesis_block().hash())
+        );
+        assert_eq!(
+            checkpoints.hash_at(410100),
+            Some(&Network::Mainnet.default_verification_edge())
+        );
+        assert_eq!(checkpoints.last_height(), Some(525000));
+        assert!(Checkpoints::for_network(Network::Unitest).is_empty());
+    }
+}
@@ -1,6 +1,14 @@
 use zebra_crypto;
 use zebra_keys::Address;
-use {Deployment, Magic, Network};
+use {Checkpoints, Deployment, Magic, Network, NetworkUpgrade};
+
+/// Ratio of pre-Blossom to post-Blossom target block spacing.
+/// Details: https://zips.z.cash/zip-0208
+const BLOSSOM_POW_TARGET_SPACING_RATIO: u32 = 2;
+
+/// Number of funding stream address periods in the post-Blossom halving interval.
+/// Details: https://zips.z.cash/zip-0207
+const FUNDING_STREAM_ADDRESS_PERIODS: u32 = 48;
 
 lazy_static! {
     static ref SAPLING_SPEND_VK: zebra_crypto::Groth16VerifyingKey =
@@ -14,6 +22,42 @@ lazy_static! {
             .expect("hardcoded value should load without errors");
 }
 
+/// Distribution of the block subsidy between miner and other recipients.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockRewardDistribution {
+    /// Amount that goes to the miner.
+    pub miner: u64,
+    /// Amounts that go to other (transparent) recipients.
+    pub recipients: Vec<BlockRewardRecipient>,
+}
+
+/// Part of the block subsidy that goes to given address.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockRewardRecipient {
+    /// Recipient address.
+    pub address: Address,
+    /// Amount that goes to the recipient.
+    pub amount: u64,
+}
+
+/// Part of the block subsidy that goes to the funding stream recipient.
+/// Details: https://zips.z.cash/zip-0207
+#[derive(Debug, Clone, PartialEq)]
+pub struct FundingStream {
+    /// Numerator of the block subsidy fraction.
+    pub numerator: u64,
+    /// Denominator of the block subsidy fraction.
+    pub denominator: u64,
+    /// Height of the first block that pays to the stream.
+    pub start_height: u32,
+    /// Height of the first block that doesn't pay to the stream.
+    pub end_height: u32,
+    /// Recipient addresses, one per address period, starting from the `start_height`.
+    ///
+    /// The list may be shorter than the stream, if further addresses are not known.
+    pub addresses: Vec<Address>,
+}
+
 #[derive(Debug, Clone)]
 /// Parameters that influence chain consensus.
 pub struct ConsensusParams {
@@ -44,6 +88,15 @@ pub struct ConsensusParams {
     /// Height of Sapling activation.
     /// Details: https://zcash.readthedocs.io/en/latest/rtd_pages/nu_dev_guide.html#sapling
     pub sapling_height: u32,
+    /// Height of Blossom activation.
+    /// Details: https://zips.z.cash/zip-0206
+    pub blossom_height: u32,
+    /// Height of Heartwood activation.
+    /// Details: https://zips.z.cash/zip-0250
+    pub heartwood_height: u32,
+    /// Height of Canopy activation.
+    /// Details: https://zips.z.cash/zip-0251
+    pub canopy_height: u32,
 
     /// Interval (in blocks) to calculate average work.
     pub pow_averaging_window: u32,
@@ -51,7 +104,8 @@ pub struct ConsensusParams {
     pub pow_max_adjust_down: u32,
     /// % of possible up adjustment of work.
     pub pow_max_adjust_up: u32,
-    /// Optimal blocks interval (in seconds).
+    /// Optimal blocks interval (in seconds) before Blossom activation.
+    /// Use `pow_target_spacing_at` to get the interval at given height.
     pub pow_target_spacing: u32,
     /// Allow minimal difficulty after block at given height.
     pub pow_allow_min_difficulty_after_height: Option<u32>,
@@ -64,7 +118,8 @@ pub struct ConsensusParams {
     pub subsidy_slow_start_interval: u32,
     /// Block subsidy halving interval.
     ///
-    /// Block subsidy is halved every `subsidy_halving_interval` blocks.
+    /// Block subsidy is halved every `subsidy_halving_interval` blocks before Blossom activation.
+    /// After Blossom activation, the interval is `BLOSSOM_POW_TARGET_SPACING_RATIO` times longer.
     /// There are 64 halving intervals in total.
     pub subsidy_halving_interval: u32,
     /// The vector of addresses where founders reward goes.
@@ -72,9 +127,16 @@ pub struct ConsensusParams {
     /// For details on what's founders' reward, refer to:
     /// https://z.cash/support/faq/#founders-reward
     pub founders_addresses: Vec<Address>,
+    /// Funding streams that are paid from the block subsidy since Canopy activation.
+    ///
+    /// For details on funding streams, refer to:
+    /// https://zips.z.cash/zip-1014
+    pub funding_streams: Vec<FundingStream>,
 
     /// Equihash (N, K) parameters.
     pub equihash_params: Option<(u32, u32)>,
+    /// Known blocks of the canonical chain.
+    pub checkpoints: Checkpoints,
 
     /// Active key for pghr13 joinsplit verification
     pub joinsplit_verification_key: zebra_crypto::Pghr13VerifyingKey,
@@ -88,6 +150,100 @@ pub struct ConsensusParams {
     pub sapling_output_verifying_key: &'static zebra_crypto::Groth16VerifyingKey,
 }
 
+fn mainnet_funding_streams() -> Vec<FundingStream> {
+    vec![
+        // Electric Coin Company
+        FundingStream {
+            numerator: 7,
+            denominator: 100,
+            start_height: 1_046_400,
+            end_height: 2_726_400,
+            addresses: vec![
+                "t3LmX1cxWPPPqL4TZHx42HU3U5ghbFjRiif".into(),
+                "t3Toxk1vJQ6UjWQ42tUJz2rV2feUWkpbTDs".into(),
+                "t3ZBdBe4iokmsjdhMuwkxEdqMCFN16YxKe6".into(),
+                "t3ZuaJziLM8xZ32rjDUzVjVtyYdDSz8GLWB".into(),
+                "t3bAtYWa4bi8VrtvqySxnbr5uqcG9czQGTZ".into(),
+                "t3dktADfb5Rmxncpe1HS5BRS5Gcj7MZWYBi".into(),
+                "t3hgskquvKKoCtvxw86yN7q8bzwRxNgUZmc".into(),
+                "t3R1VrLzwcxAZzkX4mX3KGbWpNsgtYtMntj".into(),
+                "t3ff6fhemqPMVujD3AQurxRxTdvS1pPSaa2".into(),
+                "t3cEUQFG3KYnFG6qYhPxSNgGi3HDjUPwC3J".into(),
+                "t3WR9F5U4QvUFqqx9zFmwT6xFqduqRRXnaa".into(),
+                "t3PYc1LWngrdUrJJbHkYPCKvJuvJjcm85Ch".into(),
+                "t3bgkjiUeatWNkhxY3cWyLbTxKksAfk561R".into(),
+                "t3Z5rrR8zahxUpZ8itmCKhMSfxiKjUp5Dk5".into(),
+                "t3PU1j7YW3fJ67jUbkGhSRto8qK2qXCUiW3".into(),
+                "t3S3yaT7EwNLaFZCamfsxxKwamQW2aRGEkh".into(),
+                "t3eutXKJ9tEaPSxZpmowhzKhPfJvmtwTEZK".into(),
+                "t3gbTb7brxLdVVghSPSd3ycGxzHbUpukeDm".into(),
+                "t3UCKW2LrHFqPMQFEbZn6FpjqnhAAbfpMYR".into(),
+                "t3NyHsrnYbqaySoQqEQRyTWkjvM2PLkU7Uu".into(),
+                "t3QEFL6acxuZwiXtW3YvV6njDVGjJ1qeaRo".into(),
+                "t3PdBRr2S1XTDzrV8bnZkXF3SJcrzHWe1wj".into(),
+                "t3ZWyRPpWRo23pKxTLtWsnfEKeq9T4XPxKM".into(),
+                "t3he6QytKCTydhpztykFsSsb9PmBT5JBZLi".into(),
+                "t3VWxWDsLb2TURNEP6tA1ZSeQzUmPKFNxRY".into(),
+                "t3NmWLvZkbciNAipauzsFRMxoZGqmtJksbz".into(),
+                "t3cKr4YxVPvPBG1mCvzaoTTdBNokohsRJ8n".into(),
+                "t3T3smGZn6BoSFXWWXa1RaoQdcyaFjMfuYK".into(),
+                "t3gkDUe9Gm4GGpjMk86TiJZqhztBVMiUSSA".into(),
+                "t3eretuBeBXFHe5jAqeSpUS1cpxVh51fAeb".into(),
+                "t3dN8g9zi2UGJdixGe9txeSxeofLS9t3yFQ".into(),
+                "t3S799pq9sYBFwccRecoTJ3SvQXRHPrHqvx".into(),
+                "t3fhYnv1S5dXwau7GED3c1XErzt4n4vDxmf".into(),
+                "t3cmE3vsBc5xfDJKXXZdpydCPSdZqt6AcNi".into(),
+                "t3h5fPdjJVHaH4HwynYDM5BB3J7uQaoUwKi".into(),
+                "t3Ma35c68BgRX8sdLDJ6WR1PCrKiWHG4Da9".into(),
+                "t3LokMKPL1J8rkJZvVpfuH7dLu6oUWqZKQK".into(),
+                "t3WFFGbEbhJWnASZxVLw2iTJBZfJGGX73mM".into(),
+                "t3L8GLEsUn4QHNaRYcX3EGyXmQ8kjpT1zTa".into(),
+                "t3PgfByBhaBSkH8uq4nYJ9ZBX4NhGCJBVYm".into(),
+                "t3WecsqKDhWXD4JAgBVcnaCC2itzyNZhJrv".into(),
+                "t3ZG9cSfopnsMQupKW5v9sTotjcP5P6RTbn".into(),
+                "t3hC1Ywb5zDwUYYV8LwhvF5rZ6m49jxXSG5".into(),
+                "t3VgMqDL15ZcyQDeqBsBW3W6rzfftrWP2yB".into(),
+                "t3LC94Y6BwLoDtBoK2NuewaEbnko1zvR9rm".into(),
+                "t3cWCUZJR3GtALaTcatrrpNJ3MGbMFVLRwQ".into(),
+                "t3YYF4rPLVxDcF9hHFsXyc5Yq1TFfbojCY6".into(),
+                "t3XHAGxRP2FNfhAjxGjxbrQPYtQQjc3RCQD".into(),
+            ],
+        },
+        // Zcash Foundation
+        FundingStream {
+            numerator: 5,
+            denominator: 100,
+            start_height: 1_046_400,
+            end_height: 2_726_400,
+            addresses: vec!["t3dvVE3SQEi7kqNzwrfNePxZ1d4hUyztBA1".into(); 48],
+        },
+        // Major Grants
+        //
+        // only first 13 addresses are transparent, the rest are Sapling addresses
+        FundingStream {
+            numerator: 8,
+            denominator: 100,
+            start_height: 1_046_400,
+            end_height: 2_726_400,
+            addresses: vec!["t3XyYW8yBFRuMnfvm5KLGFbEVz25kckZXym".into(); 13],
+        },
+    ]
+}
+
+fn testnet_funding_streams() -> Vec<FundingStream> {
+    // recipient addresses are not known => distribution is unknown while streams are active
+    [7, 5, 8]
+        .iter()
+        .map(|numerator| FundingStream {
+            numerator: *numerator,
+            denominator: 100,
+            start_height: 1_028_500,
+            end_height: 2_796_000,
+            addresses: Vec::new(),
+        })
+        .collect()
+}
+
 fn mainnet_pghr_verification_key() -> zebra_crypto::Pghr13VerifyingKey {
     zebra_crypto::json::pghr13::decode(include_bytes!("../../res/sprout-verifying-key.json"))
         .expect("verifying key json invalid")
@@ -109,6 +265,9 @@ impl ConsensusParams {
 
                 overwinter_height: 347500,
                 sapling_height: 419200,
+                blossom_height: 653600,
+                heartwood_height: 903000,
+                canopy_height: 1046400,
 
                 pow_averaging_window: 17,
                 pow_max_adjust_down: 32,
@@ -168,8 +327,10 @@ impl ConsensusParams {
                     "t3R3Y5vnBLrEn8L6wFjPjBLnxSUQsKnmFpv".into(),
                     "t3Pcm737EsVkGTbhsu2NekKtJeG92mvYyoN".into(),
                 ],
+                funding_streams: mainnet_funding_streams(),
 
                 equihash_params: Some((200, 9)),
+                checkpoints: Checkpoints::for_network(network),
 
                 joinsplit_verification_key: mainnet_pghr_verification_key(),
                 joinsplit_groth16_verification_key: &JOINSPLIT_GROTH16_VK,
@@ -189,6 +350,9 @@ impl ConsensusParams {
 
                 overwinter_height: 207500,
                 sapling_height: 280000,
+                blossom_height: 584000,
+                heartwood_height: 903800,
+                canopy_height: 1028500,
 
                 pow_averaging_window: 17,
                 pow_max_adjust_down: 32,
@@ -248,8 +412,10 @@ impl ConsensusParams {
                     "t2D5y7J5fpXajLbGrMBQkFg2mFN8fo3n8cX".into(),
                     "t2UV2wr1PTaUiybpkV3FdSdGxUJeZdZztyt".into(),
                 ],
+                funding_streams: testnet_funding_streams(),
 
                 equihash_params: Some((200, 9)),
+                checkpoints: Checkpoints::for_network(network),
 
                 joinsplit_verification_key: mainnet_pghr_verification_key(),
                 joinsplit_groth16_verification_key: &JOINSPLIT_GROTH16_VK,
@@ -269,6 +435,9 @@ impl ConsensusParams {
 
                 overwinter_height: ::std::u32::MAX,
                 sapling_height: ::std::u32::MAX,
+                blossom_height: ::std::u32::MAX,
+                heartwood_height: ::std::u32::MAX,
+                canopy_height: ::std::u32::MAX,
 
                 pow_averaging_window: 17,
                 pow_max_adjust_down: 0,
@@ -279,8 +448,10 @@ impl ConsensusParams {
                 subsidy_slow_start_interval: 0,
                 subsidy_halving_interval: 150,
                 founders_addresses: vec!["t2FwcEhFdNXuFMv1tcYwaBJtYVtMj8b1uTg".into()],
+                funding_streams: Vec::new(),
 
                 equihash_params: Some((200, 9)),
+                checkpoints: Checkpoints::for_network(network),
 
                 joinsplit_verification_key: mainnet_pghr_verification_key(),
                 joinsplit_groth16_verification_key: &JOINSPLIT_GROTH16_VK,
@@ -300,6 +471,9 @@ impl ConsensusParams {
 
                 overwinter_height: ::std::u32::MAX,
                 sapling_height: ::std::u32::MAX,
+                blossom_height: ::std::u32::MAX,
+                heartwood_height: ::std::u32::MAX,
+                canopy_height: ::std::u32::MAX,
 
                 pow_averaging_window: 17,
                 pow_max_adjust_down: 0,
@@ -310,8 +484,10 @@ impl ConsensusParams {
                 subsidy_slow_start_interval: 0,
                 subsidy_halving_interval: 150,
                 founders_addresses: vec!["t2FwcEhFdNXuFMv1tcYwaBJtYVtMj8b1uTg".into()],
+                funding_streams: Vec::new(),
 
                 equihash_params: None,
+                checkpoints: Checkpoints::for_network(network),
 
                 joinsplit_verification_key: mainnet_pghr_verification_key(),
                 joinsplit_groth16_verification_key: &JOINSPLIT_GROTH16_VK,
@@ -326,16 +502,25 @@ impl ConsensusParams {
         self.network.magic()
     }
 
-    pub fn averaging_window_timespan(&self) -> u32 {
-        self.pow_averaging_window * self.pow_target_spacing
+    /// Optimal blocks interval (in seconds) at given height.
+    pub fn pow_target_spacing_at(&self, height: u32) -> u32 {
+        if self.is_blossom_active(height) {
+            self.pow_target_spacing / BLOSSOM_POW_TARGET_SPACING_RATIO
+        } else {
+            self.pow_target_spacing
+        }
+    }
+
+    pub fn averaging_window_timespan(&self, height: u32) -> u32 {
+        self.pow_averaging_window * self.pow_target_spacing_at(height)
     }
 
-    pub fn min_actual_timespan(&self) -> u32 {
-        (self.averaging_window_timespan() * (100 - self.pow_max_adjust_up)) / 100
+    pub fn min_actual_timespan(&self, height: u32) -> u32 {
+        (self.averaging_window_timespan(height) * (100 - self.pow_max_adjust_up)) / 100
     }
 
-    pub fn max_actual_timespan(&self) -> u32 {
-        (self.averaging_window_timespan() * (100 + self.pow_max_adjust_down)) / 100
+    pub fn max_actual_timespan(&self, height: u32) -> u32 {
+        (self.averaging_window_timespan(height) * (100 + self.pow_max_adjust_down)) / 100
     }
 
     pub fn min_block_version(&self) -> u32 {
@@ -370,6 +555,10 @@ impl ConsensusParams {
         500_000_000
     }
 
+    pub fn max_transaction_expiry_delta(&self) -> u32 {
+        40_000
+    }
+
     pub fn is_overwinter_active(&self, height: u32) -> bool {
         height >= self.overwinter_height
     }
@@ -378,6 +567,44 @@ impl ConsensusParams {
         height >= self.sapling_height
     }
 
+    pub fn is_blossom_active(&self, height: u32) -> bool {
+        height >= self.blossom_height
+    }
+
+    /// Number of block subsidy halvings that have happened before given height.
+    ///
+    /// Must only be called for heights after the 'slow start' interval.
+    fn halvings(&self, height: u32) -> u32 {
+        let slow_start_shift = self.subsidy_slow_start_interval / 2;
+        if !self.is_blossom_active(height) {
+            return (height - slow_start_shift) / self.subsidy_halving_interval;
+        }
+
+        // pre-Blossom part of the halving interval is scaled to the post-Blossom interval,
+        // so that the number of halvings is never fractional
+        let scaled_halvings = (self.blossom_height - slow_start_shift) as u64
+            * BLOSSOM_POW_TARGET_SPACING_RATIO as u64
+            + (height - self.blossom_height) as u64;
+        let post_blossom_halving_interval =
+            self.subsidy_halving_interval as u64 * BLOSSOM_POW_TARGET_SPACING_RATIO as u64;
+        (scaled_halvings / post_blossom_halving_interval) as u32
+    }
+
+    /// Height of the last block, paying founders reward.
+    fn last_founder_reward_block_height(&self, height: u32) -> u32 {
+        let slow_start_shift = self.subsidy_slow_start_interval / 2;
+        if !self.is_blossom_active(height) {
+            return self.subsidy_halving_interval + slow_start_shift - 1;
+        }
+
+        // founders reward is paid until the first halving
+        let post_blossom_halving_interval =
+            self.subsidy_halving_interval * BLOSSOM_POW_TARGET_SPACING_RATIO;
+        post_blossom_halving_interval + self.blossom_height
+            - (self.blossom_height - slow_start_shift) * BLOSSOM_POW_TARGET_SPACING_RATIO
+            - 1
+    }
+
     /// Block subsidy (total block reward).
     pub fn block_reward(&self, height: u32) -> u64 {
         let mut reward = 1_250_000_000u64;
@@ -388,12 +615,16 @@ impl ConsensusParams {
             reward /= self.subsidy_slow_start_interval as u64;
             reward *= height as u64 + 1;
         } else {
-            let halvings =
-                (height - self.subsidy_slow_start_interval / 2) / self.subsidy_halving_interval;
+            let halvings = self.halvings(height);
             if halvings >= 64 {
                 return 0;
             }
 
+            // blocks are mined twice as often after Blossom => subsidy is halved
+            if self.is_blossom_active(height) {
+                reward /= BLOSSOM_POW_TARGET_SPACING_RATIO as u64;
+            }
+
             reward >>= halvings as u64;
         }
 
@@ -416,32 +647,101 @@ impl ConsensusParams {
 
     /// Address (transparent) where founders reward goes at given height.
     pub fn founder_address(&self, height: u32) -> Option<Address> {
-        let last_founder_reward_block_height =
-            self.subsidy_halving_interval + self.subsidy_slow_start_interval / 2 - 1;
-        if height == 0 || height > last_founder_reward_block_height {
+        if height == 0 || height > self.last_founder_reward_block_height(height) {
             return None;
         }
 
+        // addresses are changed at the pre-Blossom rate
+        let adjusted_height = if self.is_blossom_active(height) {
+            self.blossom_height + (height - self.blossom_height) / BLOSSOM_POW_TARGET_SPACING_RATIO
+        } else {
+            height
+        };
+
+        let pre_blossom_last_height =
+            self.subsidy_halving_interval + self.subsidy_slow_start_interval / 2 - 1;
         let founders_len = self.founders_addresses.len() as u32;
-        let address_change_interval =
-            (last_founder_reward_block_height + founders_len) / founders_len;
-        let address_index = height / address_change_interval;
+        let address_change_interval = (pre_blossom_last_height + founders_len) / founders_len;
+        let address_index = adjusted_height / address_change_interval;
         Some(self.founders_addresses[address_index as usize].clone())
     }
 
-    pub fn consensus_branch_id(&self, height: u32) -> u32 {
-        // sapling upgrade
-        if height >= self.sapling_height {
-            return 0x76b809bb;
+    /// Distribution of the block subsidy at given height.
+    ///
+    /// Amounts of the miner and all recipients sum up to the `block_reward(height)`.
+    /// Returns None if recipient of the active funding stream is not known.
+    pub fn block_reward_distribution(&self, height: u32) -> Option<BlockRewardDistribution> {
+        let block_reward = self.block_reward(height);
+        let mut recipients = Vec::new();
+
+        // founders reward is not paid since Canopy activation
+        if height < self.canopy_height {
+            if let Some(address) = self.founder_address(height) {
+                recipients.push(BlockRewardRecipient {
+                    address: address,
+                    amount: self.founder_reward(height),
+                });
+            }
+        }
+
+        for stream in &self.funding_streams {
+            if height < stream.start_height || height >= stream.end_height {
+                continue;
+            }
+
+            let address_index = self.funding_stream_address_period(height)
+                - self.funding_stream_address_period(stream.start_height);
+            recipients.push(BlockRewardRecipient {
+                address: stream.addresses.get(address_index as usize)?.clone(),
+                amount: block_reward * stream.numerator / stream.denominator,
+            });
         }
 
-        // overwinter upgrade
-        if height >= self.overwinter_height {
-            return 0x5ba81b19;
+        let recipients_amount = recipients
+            .iter()
+            .map(|recipient| recipient.amount)
+            .sum::<u64>();
+        Some(BlockRewardDistribution {
+            miner: block_reward - recipients_amount,
+            recipients: recipients,
+        })
+    }
+
+    /// Funding stream address period of given (post-Blossom) height.
+    fn funding_stream_address_period(&self, height: u32) -> u32 {
+        let post_blossom_halving_interval =
+            self.subsidy_halving_interval * BLOSSOM_POW_TARGET_SPACING_RATIO;
+        let address_change_interval =
+            post_blossom_halving_interval / FUNDING_STREAM_ADDRESS_PERIODS;
+        let first_halving_height = self.last_founder_reward_block_height(self.blossom_height) + 1;
+        (height + post_blossom_halving_interval - first_halving_height) / address_change_interval
+    }
+
+    /// Height at which given network upgrade activates.
+    pub fn activation_height(&self, upgrade: NetworkUpgrade) -> u32 {
+        match upgrade {
+            NetworkUpgrade::Sprout => 0,
+            NetworkUpgrade::Overwinter => self.overwinter_height,
+            NetworkUpgrade::Sapling => self.sapling_height,
+            NetworkUpgrade::Blossom => self.blossom_height,
+            NetworkUpgrade::Heartwood => self.heartwood_height,
+            NetworkUpgrade::Canopy => self.canopy_height,
         }
+    }
+
+    /// Latest network upgrade that is active at given height.
+    pub fn upgrade_active_at(&self, height: u32) -> NetworkUpgrade {
+        NetworkUpgrade::all()
+            .iter()
+            .rev()
+            .cloned()
+            .find(|upgrade| height >= self.activation_height(*upgrade))
+            .unwrap_or(NetworkUpgrade::Sprout)
+    }
 
-        // sprout
-        0
+    /// Consensus branch id of the network upgrade that is active at given height.
+    pub fn consensus_branch_id_at(&self, height: u32) -> u32 {
+        self.upgrade_active_at(height).branch_id()
     }
 }
 
@@ -458,8 +758,178 @@ mod tests {
         assert_eq!(consensus.block_reward(1_000_000), 625_000_000);
         assert_eq!(consensus.block_reward(2_000_000), 312_500_000);
         assert_eq!(consensus.block_reward(3_000_000), 156_250_000);
-        assert_eq!(consensus.block_reward(4_000_000), 78_125_000);
-        assert_eq!(consensus.block_reward(20_000_000), 149);
-        assert_eq!(consensus.block_reward(30_000_000), 0);
+        assert_eq!(consensus.block_reward(4_000_000), 156_250_000);
+        assert_eq!(consensus.block_reward(20_000_000), 152_587);
+        assert_eq!(consensus.block_reward(30_000_000), 2_384);
+        assert_eq!(consensus.block_reward(200_000_000), 0);
+    }
+
+    #[test]
+    fn block_reward_at_blossom_activation() {
+        let consensus = ConsensusParams::new(Network::Mainnet);
+        let blossom_height = consensus.blossom_height;
+        assert_eq!(consensus.block_reward(blossom_height - 1), 1_250_000_000);
+        assert_eq!(consensus.block_reward(blossom_height), 625_000_000);
+
+        // the first halving happens at the same time as it would without Blossom
+        // if blocks were mined twice as often since Blossom activation
+        assert_eq!(consensus.block_reward(1_046_399), 625_000_000);
+        assert_eq!(consensus.block_reward(1_046_400), 312_500_000);
+
+        let consensus = ConsensusParams::new(Network::Testnet);
+        assert_eq!(consensus.block_reward(1_115_999), 625_000_000);
+        assert_eq!(consensus.block_reward(1_116_000), 312_500_000);
+    }
+
+    #[test]
+    fn founder_reward_at_blossom_activation() {
+        let consensus = ConsensusParams::new(Network::Mainnet);
+        let blossom_height = consensus.blossom_height;
+        assert_eq!(consensus.founder_reward(blossom_height - 1), 250_000_000);
+        assert_eq!(consensus.founder_reward(blossom_height), 125_000_000);
+
+        // founders reward is paid until the first halving
+        assert!(consensus.founder_address(849_999).is_some());
+        assert!(consensus.founder_address(1_046_399).is_some());
+        assert_eq!(consensus.founder_address(1_046_400), None);
+
+        // address change interval is not affected by Blossom
+        assert_eq!(
+            consensus.founder_address(blossom_height - 1),
+            consensus.founder_address(blossom_height)
+        );
+        assert_eq!(
+            consensus.founder_address(1_046_399),
+            Some(consensus.founders_addresses[47].clone())
+        );
+    }
+
+    #[test]
+    fn pow_target_spacing_at_blossom_activation() {
+        let consensus = ConsensusParams::new(Network::Mainnet);
+        let blossom_height = consensus.blossom_height;
+        assert_eq!(consensus.pow_target_spacing_at(blossom_height - 1), 150);
+        assert_eq!(consensus.pow_target_spacing_at(blossom_height), 75);
+        assert_eq!(
+            consensus.averaging_window_timespan(blossom_height - 1),
+            17 * 150
+        );
+        assert_eq!(consensus.averaging_window_timespan(blossom_height), 17 * 75);
+    }
+
+    #[test]
+    fn block_reward_distribution() {
+        let consensus = ConsensusParams::new(Network::Mainnet);
+        let distribution = consensus.block_reward_distribution(10_000).unwrap();
+        assert_eq!(distribution.miner, 500_050_000);
+        assert_eq!(
+            distribution.recipients,
+            vec![BlockRewardRecipient {
+                address: "t3Vz22vK5z2LcKEdg16Yv4FFneEL1zg9ojd".into(),
+                amount: 125_012_500,
+            }]
+        );
+        assert_eq!(
+            distribution.miner
+                + distribution
+                    .recipients
+                    .iter()
+                    .map(|recipient| recipient.amount)
+                    .sum::<u64>(),
+            consensus.block_reward(10_000)
+        );
+    }
+
+    #[test]
+    fn block_reward_distribution_with_funding_streams() {
+        let consensus = ConsensusParams::new(Network::Mainnet);
+        let distribution = consensus.block_reward_distribution(1_046_400).unwrap();
+        assert_eq!(distribution.miner, 250_000_000);
+        assert_eq!(
+            distribution.recipients,
+            vec![
+                BlockRewardRecipient {
+                    address: "t3LmX1cxWPPPqL4TZHx42HU3U5ghbFjRiif".into(),
+                    amount: 21_875_000,
+                },
+                BlockRewardRecipient {
+                    address: "t3dvVE3SQEi7kqNzwrfNePxZ1d4hUyztBA1".into(),
+                    amount: 15_625_000,
+                },
+                BlockRewardRecipient {
+                    address: "t3XyYW8yBFRuMnfvm5KLGFbEVz25kckZXym".into(),
+                    amount: 25_000_000,
+                },
+            ]
+        );
+        assert_eq!(
+            distribution.miner
+                + distribution
+                    .recipients
+                    .iter()
+                    .map(|recipient| recipient.amount)
+                    .sum::<u64>(),
+            consensus.block_reward(1_046_400)
+        );
+
+        // addresses are changed every 35_000 blocks
+        let distribution = consensus.block_reward_distribution(1_081_399).unwrap();
+        assert_eq!(
+            distribution.recipients[0].address,
+            Address::from("t3LmX1cxWPPPqL4TZHx42HU3U5ghbFjRiif")
+        );
+        let distribution = consensus.block_reward_distribution(1_081_400).unwrap();
+        assert_eq!(
+            distribution.recipients[0].address,
+            Address::from("t3Toxk1vJQ6UjWQ42tUJz2rV2feUWkpbTDs")
+        );
+
+        // Major Grants addresses are not known after first 13 address periods
+        assert!(consensus.block_reward_distribution(1_501_399).is_some());
+        assert_eq!(consensus.block_reward_distribution(1_501_400), None);
+
+        // testnet funding stream addresses are not known
+        let consensus = ConsensusParams::new(Network::Testnet);
+        assert!(consensus.block_reward_distribution(1_028_499).is_some());
+        assert_eq!(consensus.block_reward_distribution(1_028_500), None);
+    }
+
+    #[test]
+    fn upgrade_active_at() {
+        let consensus = ConsensusParams::new(Network::Mainnet);
+        assert_eq!(consensus.upgrade_active_at(0), NetworkUpgrade::Sprout);
+        assert_eq!(consensus.upgrade_active_at(347499), NetworkUpgrade::Sprout);
+        assert_eq!(
+            consensus.upgrade_active_at(347500),
+            NetworkUpgrade::Overwinter
+        );
+        assert_eq!(consensus.upgrade_active_at(419200), NetworkUpgrade::Sapling);
+        assert_eq!(consensus.upgrade_active_at(653600), NetworkUpgrade::Blossom);
+        assert_eq!(
+            consensus.upgrade_active_at(903000),
+            NetworkUpgrade::Heartwood
+        );
+        assert_eq!(consensus.upgrade_active_at(1046400), NetworkUpgrade::Canopy);
+        assert_eq!(
+            consensus.upgrade_active_at(::std::u32::MAX),
+            NetworkUpgrade::Canopy
+        );
+
+        let consensus = ConsensusParams::new(Network::Unitest);
+        assert_eq!(
+            consensus.upgrade_active_at(1_000_000),
+            NetworkUpgrade::Sprout
+        );
+    }
+
+    #[test]
+    fn consensus_branch_id_at() {
+        let consensus = ConsensusParams::new(Network::Testnet);
+        assert_eq!(consensus.consensus_branch_id_at(207499), 0);
+        assert_eq!(consensus.consensus_branch_id_at(207500), 0x5ba81b19);
+        assert_eq!(consensus.consensus_branch_id_at(280000), 0x76b809bb);
+        assert_eq!(consensus.consensus_branch_id_at(584000), 0x2bb40e60);
+        assert_eq!(consensus.consensus_branch_id_at(903800), 0xf5b9230b);
+        assert_eq!(consensus.consensus_branch_id_at(1028500), 0xe9ff75a6);
     }
 }
@@ -8,12 +8,16 @@ extern crate zebra_keys;
 extern crate zebra_primitives;
 extern crate zebra_serialization;
 
+mod checkpoints;
 mod consensus;
 mod deployments;
 mod network;
+mod network_upgrade;
 
 pub use zebra_primitives::{compact, hash};
 
-pub use consensus::ConsensusParams;
+pub use checkpoints::Checkpoints;
+pub use consensus::{BlockRewardDistribution, BlockRewardRecipient, ConsensusParams, FundingStream};
 pub use deployments::Deployment;
 pub use network::{Magic, Network};
+pub use network_upgrade::NetworkUpgrade;
@@ -0,0 +1,45 @@
+/// Zcash network upgrade.
+///
+/// Upgrades are ordered by their activation heights.
+/// Details: https://zips.z.cash/zip-0200
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum NetworkUpgrade {
+    /// Pre-Overwinter consensus rules.
+    Sprout,
+    /// Overwinter upgrade (ZIP-201, ZIP-202, ZIP-203, ZIP-143).
+    Overwinter,
+    /// Sapling upgrade (ZIP-205, ZIP-243).
+    Sapling,
+    /// Blossom upgrade (ZIP-206, ZIP-208).
+    Blossom,
+    /// Heartwood upgrade (ZIP-213, ZIP-221).
+    Heartwood,
+    /// Canopy upgrade (ZIP-207, ZIP-211, ZIP-212, ZIP-214, ZIP-215).
+    Canopy,
+}
+
+impl NetworkUpgrade {
+    /// All network upgrades, ordered by activation height.
+    pub fn all() -> &'static [NetworkUpgrade] {
+        &[
+            NetworkUpgrade::Sprout,
+            NetworkUpgrade::Overwinter,
+            NetworkUpgrade::Sapling,
+            NetworkUpgrade::Blossom,
+            NetworkUpgrade::Heartwood,
+            NetworkUpgrade::Canopy,
+        ]
+    }
+
+    /// Consensus branch id of the upgrade, used to compute signature hashes.
+    pub fn branch_id(&self) -> u32 {
+        match *self {
+            NetworkUpgrade::Sprout => 0,
+            NetworkUpgrade::Overwinter => 0x5ba81b19,
+            NetworkUpgrade::Sapling => 0x76b809bb,
+            NetworkUpgrade::Blossom => 0x2bb40e60,
+            NetworkUpgrade::Heartwood => 0xf5b9230b,
+            NetworkUpgrade::Canopy => 0xe9ff75a6,
+        }
+    }
+}
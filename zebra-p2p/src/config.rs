@@ -7,18 +7,38 @@ use zebra_message::common::Services;
 pub struct Config {
     /// Number of threads used by p2p thread pool.
     pub threads: usize,
-    /// Number of inbound connections.
-    pub inbound_connections: u32,
-    /// Number of outbound connections.
-    pub outbound_connections: u32,
+    /// Maximal number of inbound connections.
+    pub max_inbound: u32,
+    /// Number of inbound connections from single IP.
+    pub inbound_connections_per_ip: u32,
+    /// Maximal number of outbound connections. Nodes, added with `Context::connect_added_node`,
+    /// could use `ADDED_NODE_CONNECTIONS` more connections.
+    pub max_outbound: u32,
     /// Configuration for every connection.
     pub connection: NetConfig,
     /// Connect only to these nodes.
     pub peers: Vec<net::SocketAddr>,
     /// Connect to these nodes to retrieve peer addresses, and disconnect.
     pub seeds: Vec<String>,
-    /// p2p/nodes.csv file path.
+    /// Resolve these DNS seeds (`host:port`) to retrieve peer addresses.
+    pub dns_seeds: Vec<String>,
+    /// Peer addresses, used when none of DNS seeds could be resolved.
+    pub fixed_seeds: Vec<net::SocketAddr>,
+    /// Ban score at which misbehaving peer gets banned. Ban score decreases by one point every minute.
+    pub ban_score_threshold: u32,
+    /// Duration of the ban of misbehaving peer (in seconds).
+    pub ban_time: u32,
+    /// Inbound connections from these IPs are not limited by `inbound_connections_per_ip`.
+    /// Peers with these IPs are never banned for misbehavior.
+    pub whitelist: Vec<net::IpAddr>,
+    /// p2p/nodes.dat file path.
     pub node_table_path: path::PathBuf,
+    /// Maximal number of nodes, saved to the node table file.
+    pub node_table_max_size: usize,
+    /// Interval between node table saves (in seconds). If 0, node table is saved on shutdown only.
+    pub node_table_save_interval: u64,
+    /// Interval between feeler connections (in seconds). If 0, feeler connections are not made.
+    pub feeler_interval: u64,
     /// Peers with these services will get a boost in node_table.
     pub preferable_services: Services,
     /// Internet protocol.
@@ -1,4 +1,7 @@
+use futures::sync::oneshot;
 use futures::{empty, Empty};
+use parking_lot::Mutex;
+use std::sync::Arc;
 use tokio_core::reactor::Core;
 
 pub fn event_loop() -> Core {
@@ -8,3 +11,44 @@ pub fn event_loop() -> Core {
 pub fn forever() -> Empty<(), ()> {
     empty()
 }
+
+/// Handle, requesting the event loop to stop.
+#[derive(Clone)]
+pub struct Shutdown {
+    sender: Arc<Mutex<Option<oneshot::Sender<()>>>>,
+}
+
+impl Shutdown {
+    /// Requests the shutdown. Repeated requests are ignored.
+    pub fn request(&self) {
+        if let Some(sender) = self.sender.lock().take() {
+            // receiver is only dropped after the event loop has stopped
+            let _ = sender.send(());
+        }
+    }
+}
+
+/// Returns shutdown handle and the future, which is resolved once the shutdown is requested
+/// (or fails if all handles are dropped). Event loop should run this future instead of
+/// `forever()` to be stoppable.
+pub fn shutdown_signal() -> (Shutdown, oneshot::Receiver<()>) {
+    let (sender, receiver) = oneshot::channel();
+    let shutdown = Shutdown {
+        sender: Arc::new(Mutex::new(Some(sender))),
+    };
+    (shutdown, receiver)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::shutdown_signal;
+    use futures::Future;
+
+    #[test]
+    fn shutdown_signal_is_received_once() {
+        let (shutdown, receiver) = shutdown_signal();
+        shutdown.clone().request();
+        shutdown.request();
+        assert_eq!(receiver.wait(), Ok(()));
+    }
+}
@@ -15,5 +15,5 @@ pub use self::read_any_message::{read_any_message, ReadAnyMessage};
 pub use self::read_header::{read_header, ReadHeader};
 pub use self::read_message::{read_message, ReadMessage};
 pub use self::read_payload::{read_payload, ReadPayload};
-pub use self::sharedtcpstream::SharedTcpStream;
+pub use self::sharedtcpstream::{net_totals, SharedTcpStream};
 pub use self::write_message::{write_message, WriteMessage};
@@ -1,9 +1,24 @@
 use futures::Poll;
 use std::io::{Error, Read, Write};
 use std::net::Shutdown;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use tokio_core::net::TcpStream;
 use tokio_io::{AsyncRead, AsyncWrite};
+use util::NetTotals;
+
+/// Total number of bytes, received from all peers.
+static TOTAL_BYTES_RECEIVED: AtomicU64 = AtomicU64::new(0);
+/// Total number of bytes, sent to all peers.
+static TOTAL_BYTES_SENT: AtomicU64 = AtomicU64::new(0);
+
+/// Returns traffic counters of all connections, made since the process start.
+pub fn net_totals() -> NetTotals {
+    NetTotals {
+        bytes_received: TOTAL_BYTES_RECEIVED.load(Ordering::Relaxed),
+        bytes_sent: TOTAL_BYTES_SENT.load(Ordering::Relaxed),
+    }
+}
 
 pub struct SharedTcpStream {
     io: Arc<TcpStream>,
@@ -28,7 +43,9 @@ impl From<TcpStream> for SharedTcpStream {
 
 impl Read for SharedTcpStream {
     fn read(&mut self, buf: &mut [u8]) -> Result<usize, Error> {
-        Read::read(&mut (&*self.io as &TcpStream), buf)
+        let read = Read::read(&mut (&*self.io as &TcpStream), buf)?;
+        TOTAL_BYTES_RECEIVED.fetch_add(read as u64, Ordering::Relaxed);
+        Ok(read)
     }
 }
 
@@ -42,7 +59,9 @@ impl AsyncWrite for SharedTcpStream {
 
 impl Write for SharedTcpStream {
     fn write(&mut self, buf: &[u8]) -> Result<usize, Error> {
-        Write::write(&mut (&*self.io as &TcpStream), buf)
+        let written = Write::write(&mut (&*self.io as &TcpStream), buf)?;
+        TOTAL_BYTES_SENT.fetch_add(written as u64, Ordering::Relaxed);
+        Ok(written)
     }
 
     fn flush(&mut self) -> Result<(), Error> {
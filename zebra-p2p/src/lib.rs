@@ -30,12 +30,15 @@ mod util;
 pub use zebra_primitives::{bytes, hash};
 
 pub use config::Config;
-pub use event_loop::{event_loop, forever};
+pub use event_loop::{event_loop, forever, shutdown_signal, Shutdown};
 pub use net::Config as NetConfig;
-pub use p2p::{Context, P2P};
+pub use p2p::{Context, ADDED_NODE_CONNECTIONS, P2P};
 pub use protocol::{
     InboundSyncConnection, InboundSyncConnectionRef, InboundSyncConnectionState,
     InboundSyncConnectionStateRef, LocalSyncNode, LocalSyncNodeRef, OutboundSyncConnection,
     OutboundSyncConnectionRef,
 };
-pub use util::{Direction, InternetProtocol, NodeTableError, PeerId, PeerInfo};
+pub use util::{
+    BanEntry, BanListError, Direction, InternetProtocol, InvalidIpSubnet, IpSubnet, NetTotals,
+    NodeTableError, PeerId, PeerInfo, PeerStatistics,
+};
@@ -1,39 +1,69 @@
+use parking_lot::Mutex;
 use std::cmp;
+use std::collections::HashMap;
+use std::net::IpAddr;
 use std::sync::atomic::{AtomicUsize, Ordering};
 
 /// Counts number of open inbound and outbound connections.
 pub struct ConnectionCounter {
     /// Current number of inbound connections.
     current_inbound_connections: AtomicUsize,
+    /// Current number of inbound connections from every IP.
+    current_inbound_connections_per_ip: Mutex<HashMap<IpAddr, u32>>,
     /// Current number of outbound connections.
     current_outbound_connections: AtomicUsize,
     /// Maximum number of inbound connections.
     max_inbound_connections: u32,
+    /// Maximum number of inbound connections from single IP.
+    max_inbound_connections_per_ip: u32,
     /// Maximum number of outbound connections.
     max_outbound_connections: u32,
 }
 
 impl ConnectionCounter {
-    pub fn new(max_inbound_connections: u32, max_outbound_connections: u32) -> Self {
+    pub fn new(
+        max_inbound_connections: u32,
+        max_inbound_connections_per_ip: u32,
+        max_outbound_connections: u32,
+    ) -> Self {
         ConnectionCounter {
             current_inbound_connections: AtomicUsize::new(0),
+            current_inbound_connections_per_ip: Mutex::new(HashMap::new()),
             current_outbound_connections: AtomicUsize::new(0),
             max_inbound_connections: max_inbound_connections,
+            max_inbound_connections_per_ip: max_inbound_connections_per_ip,
             max_outbound_connections: max_outbound_connections,
         }
     }
 
-    /// Increases inbound connections counter by 1.
-    pub fn note_new_inbound_connection(&self) {
+    /// Increases inbound connections counter (and counter of connections from given IP) by 1.
+    pub fn note_new_inbound_connection(&self, ip: IpAddr) {
         self.current_inbound_connections
             .fetch_add(1, Ordering::AcqRel);
+        *self
+            .current_inbound_connections_per_ip
+            .lock()
+            .entry(ip)
+            .or_insert(0) += 1;
     }
 
-    /// Decreases inbound connections counter by 1.
+    /// Decreases inbound connections counter (and counter of connections from given IP) by 1.
     /// If it underflows, it means, that there is a logic error.
-    pub fn note_close_inbound_connection(&self) {
+    pub fn note_close_inbound_connection(&self, ip: IpAddr) {
         self.current_inbound_connections
             .fetch_sub(1, Ordering::AcqRel);
+
+        let mut inbound_connections_per_ip = self.current_inbound_connections_per_ip.lock();
+        let is_last_connection = match inbound_connections_per_ip.get_mut(&ip) {
+            Some(connections) => {
+                *connections -= 1;
+                *connections == 0
+            }
+            None => false,
+        };
+        if is_last_connection {
+            inbound_connections_per_ip.remove(&ip);
+        }
     }
 
     /// Increases outbound connections counter by 1.
@@ -61,6 +91,20 @@ impl ConnectionCounter {
         oc.1 - cmp::min(oc.0, oc.1)
     }
 
+    /// Returns true if maximal number of inbound connections from given IP is reached.
+    pub fn is_inbound_connections_per_ip_limit_reached(&self, ip: &IpAddr) -> bool {
+        self.inbound_connections_from(ip) >= self.max_inbound_connections_per_ip
+    }
+
+    /// Returns current number of inbound connections from given IP.
+    pub fn inbound_connections_from(&self, ip: &IpAddr) -> u32 {
+        self.current_inbound_connections_per_ip
+            .lock()
+            .get(ip)
+            .cloned()
+            .unwrap_or_default()
+    }
+
     /// Returns a pair of unsigned integers where first element is current number of connections and the second is max.
     pub fn inbound_connections(&self) -> (u32, u32) {
         let current = self.current_inbound_connections.load(Ordering::Acquire) as u32;
@@ -77,32 +121,34 @@ impl ConnectionCounter {
 #[cfg(test)]
 mod tests {
     use super::ConnectionCounter;
+    use std::net::IpAddr;
 
     #[test]
     fn test_inbound_connection_counter() {
-        let cc = ConnectionCounter::new(5, 10);
+        let ip: IpAddr = "127.0.0.1".parse().unwrap();
+        let cc = ConnectionCounter::new(5, 10, 10);
         assert_eq!(cc.inbound_connections_needed(), 5);
         assert_eq!(cc.inbound_connections(), (0, 5));
-        cc.note_new_inbound_connection();
+        cc.note_new_inbound_connection(ip);
         assert_eq!(cc.inbound_connections_needed(), 4);
         assert_eq!(cc.inbound_connections(), (1, 5));
-        cc.note_new_inbound_connection();
-        cc.note_new_inbound_connection();
-        cc.note_new_inbound_connection();
-        cc.note_new_inbound_connection();
+        cc.note_new_inbound_connection(ip);
+        cc.note_new_inbound_connection(ip);
+        cc.note_new_inbound_connection(ip);
+        cc.note_new_inbound_connection(ip);
         assert_eq!(cc.inbound_connections_needed(), 0);
         // it may exceed max
-        cc.note_new_inbound_connection();
+        cc.note_new_inbound_connection(ip);
         assert_eq!(cc.inbound_connections_needed(), 0);
         assert_eq!(cc.inbound_connections(), (6, 5));
-        cc.note_close_inbound_connection();
+        cc.note_close_inbound_connection(ip);
         assert_eq!(cc.inbound_connections_needed(), 0);
         assert_eq!(cc.inbound_connections(), (5, 5));
     }
 
     #[test]
     fn test_outbound_connection_counter() {
-        let cc = ConnectionCounter::new(0, 4);
+        let cc = ConnectionCounter::new(0, 0, 4);
         assert_eq!(cc.outbound_connections_needed(), 4);
         assert_eq!(cc.outbound_connections(), (0, 4));
         cc.note_new_outbound_connection();
@@ -113,4 +159,28 @@ mod tests {
         assert_eq!(cc.outbound_connections_needed(), 3);
         assert_eq!(cc.outbound_connections(), (1, 4));
     }
+
+    #[test]
+    fn test_inbound_connections_per_ip_limit() {
+        let ip1: IpAddr = "127.0.0.1".parse().unwrap();
+        let ip2: IpAddr = "127.0.0.2".parse().unwrap();
+        let cc = ConnectionCounter::new(10, 2, 0);
+        assert!(!cc.is_inbound_connections_per_ip_limit_reached(&ip1));
+        cc.note_new_inbound_connection(ip1);
+        assert!(!cc.is_inbound_connections_per_ip_limit_reached(&ip1));
+        cc.note_new_inbound_connection(ip1);
+        assert_eq!(cc.inbound_connections_from(&ip1), 2);
+        // third connection from the same IP is rejected
+        assert!(cc.is_inbound_connections_per_ip_limit_reached(&ip1));
+        // while connection from other IP is accepted
+        assert!(!cc.is_inbound_connections_per_ip_limit_reached(&ip2));
+        cc.note_new_inbound_connection(ip2);
+        assert_eq!(cc.inbound_connections(), (3, 10));
+        // when connection is closed, new connection from the same IP could be accepted
+        cc.note_close_inbound_connection(ip1);
+        assert!(!cc.is_inbound_connections_per_ip_limit_reached(&ip1));
+        cc.note_close_inbound_connection(ip1);
+        assert_eq!(cc.inbound_connections_from(&ip1), 0);
+        assert_eq!(cc.inbound_connections(), (1, 10));
+    }
 }
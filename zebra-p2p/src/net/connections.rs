@@ -6,7 +6,8 @@ use std::collections::{HashMap, HashSet};
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::{mem, net};
-use util::{Direction, PeerInfo};
+use util::time::{RealTime, Time};
+use util::{Direction, PeerInfo, PeerStatistics};
 use PeerId;
 
 const SYNCHRONOUS_RESPONSES: bool = true;
@@ -46,6 +47,24 @@ impl Connections {
             .collect()
     }
 
+    /// Returns info && traffic statistics of every peer, ordered by peer id
+    pub fn statistics(&self) -> Vec<PeerStatistics> {
+        let mut statistics: Vec<_> = self
+            .channels()
+            .values()
+            .map(|channel| {
+                let stats = channel.session().stats().lock();
+                PeerStatistics {
+                    info: channel.peer_info(),
+                    bytes_sent: stats.total_send,
+                    bytes_received: stats.total_recv,
+                }
+            })
+            .collect();
+        statistics.sort_by_key(|peer| peer.info.id);
+        statistics
+    }
+
     /// Returns number of connections.
     pub fn count(&self) -> usize {
         self.channels.read().len()
@@ -75,6 +94,7 @@ impl Connections {
             version: connection.version,
             version_message: connection.version_message,
             magic: connection.magic,
+            connection_time: RealTime.get().sec as u32,
         };
 
         let session = T::new_session(context, peer_info.clone(), SYNCHRONOUS_RESPONSES);
@@ -1,8 +1,9 @@
 use abstract_ns::Resolver;
+use futures::future::join_all;
 use futures::stream::Stream;
 use futures::{failed, finished, Future};
 use futures_cpupool::{Builder as CpuPoolBuilder, CpuPool};
-use io::DeadlineStatus;
+use io::{net_totals, DeadlineStatus};
 use net::{
     accept_connection, connect, Channel, Config as NetConfig, ConnectionCounter, Connections,
 };
@@ -10,20 +11,36 @@ use ns_dns_tokio::DnsResolver;
 use parking_lot::RwLock;
 use protocol::{InboundSyncConnectionRef, LocalSyncNodeRef, OutboundSyncConnectionRef};
 use session::{NormalSessionFactory, SeednodeSessionFactory, SessionFactory};
-use std::net::SocketAddr;
+use std::collections::HashSet;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::{error, io, net, time};
 use tokio_core::net::{TcpListener, TcpStream};
 use tokio_core::reactor::{Handle, Interval, Remote, Timeout};
 use tokio_io::IoFuture;
-use util::{Direction, Node, NodeTable, NodeTableError};
-use zebra_message::common::Services;
+use util::time::{RealTime, Time};
+use util::{
+    is_routable, BanEntry, BanList, BanListError, Direction, FeelerAttempts, IpSubnet, NetTotals,
+    Node, NodeTable, NodeTableError,
+};
+use zebra_message::common::{NetAddress, Services};
 use zebra_message::types::addr::AddressEntry;
 use zebra_message::{Message, MessageResult, Payload};
 use {Config, PeerId};
 
 pub type BoxedEmptyFuture = Box<Future<Item = (), Error = ()> + Send>;
 
+/// Number of outbound connections (in addition to `Config::max_outbound`), reserved for
+/// manually added nodes.
+pub const ADDED_NODE_CONNECTIONS: u32 = 8;
+
+/// Maximal number of new addresses, added to the node table from a single DNS seed lookup.
+const MAX_DNS_SEED_ADDRESSES: usize = 64;
+
+/// Minimal interval between feeler connections to the same address (in seconds).
+const FEELER_RETRY_INTERVAL: u64 = 60 * 60;
+
 /// Network context.
 pub struct Context {
     /// Connections.
@@ -32,6 +49,14 @@ pub struct Context {
     connection_counter: ConnectionCounter,
     /// Node Table.
     node_table: RwLock<NodeTable>,
+    /// Ban list.
+    ban_list: RwLock<BanList>,
+    /// Our own public addresses, as they are seen by outbound peers.
+    local_addresses: RwLock<HashSet<IpAddr>>,
+    /// False if networking is paused: all peers are disconnected and no new connections are made.
+    network_active: AtomicBool,
+    /// Addresses, recently tested with feeler connections.
+    feeler_attempts: RwLock<FeelerAttempts>,
     /// Thread pool handle.
     pool: CpuPool,
     /// Remote event loop handle.
@@ -53,12 +78,21 @@ impl Context {
         let context = Context {
             connections: Default::default(),
             connection_counter: ConnectionCounter::new(
-                config.inbound_connections,
-                config.outbound_connections,
+                config.max_inbound,
+                config.inbound_connections_per_ip,
+                config.max_outbound,
             ),
             node_table: RwLock::new(try!(NodeTable::from_file(
                 config.preferable_services,
-                &config.node_table_path
+                &config.node_table_path,
+                config.node_table_max_size
+            ))),
+            ban_list: RwLock::new(BanList::new(config.ban_score_threshold, config.ban_time)),
+            local_addresses: Default::default(),
+            network_active: AtomicBool::new(true),
+            feeler_attempts: RwLock::new(FeelerAttempts::new(time::Duration::new(
+                FEELER_RETRY_INTERVAL,
+                0,
             ))),
             pool: pool_handle,
             remote: remote,
@@ -106,12 +140,44 @@ impl Context {
             .recently_active_nodes(self.config.internet_protocol)
     }
 
-    /// Updates node table.
+    /// Updates node table. Our own addresses are never inserted.
     pub fn update_node_table(&self, nodes: Vec<AddressEntry>) {
         trace!("Updating node table with {} entries", nodes.len());
+        let nodes = {
+            let local_addresses = self.local_addresses.read();
+            let local_port = self.config.connection.local_address.port();
+            nodes
+                .into_iter()
+                .filter(|entry| {
+                    let ip: IpAddr = entry.address.address.into();
+                    let port: u16 = entry.address.port.into();
+                    port != local_port
+                        || !(local_addresses.contains(&ip)
+                            || ip == self.config.connection.local_address.ip())
+                })
+                .collect()
+        };
         self.node_table.write().insert_many(nodes);
     }
 
+    /// Returns address entry to advertise ourselves with, when peer sees us at given address.
+    /// Returns None if we're not accepting inbound connections or address is not routable.
+    pub fn local_address_entry(&self, ip: IpAddr) -> Option<AddressEntry> {
+        if self.config.max_inbound == 0 || !is_routable(&ip) {
+            return None;
+        }
+
+        self.local_addresses.write().insert(ip);
+        Some(AddressEntry {
+            timestamp: RealTime.get().sec as u32,
+            address: NetAddress {
+                services: self.config.connection.services,
+                address: ip.into(),
+                port: self.config.connection.local_address.port().into(),
+            },
+        })
+    }
+
     /// Penalize node.
     pub fn penalize_node(&self, addr: &SocketAddr) {
         trace!("Penalizing node {}", addr);
@@ -132,6 +198,117 @@ impl Context {
         self.node_table.write().remove(&addr)
     }
 
+    /// Returns a pair of current and maximal number of inbound connections.
+    pub fn inbound_connections(&self) -> (u32, u32) {
+        self.connection_counter.inbound_connections()
+    }
+
+    /// Returns a pair of current and maximal number of outbound connections.
+    pub fn outbound_connections(&self) -> (u32, u32) {
+        self.connection_counter.outbound_connections()
+    }
+
+    /// Returns true if networking is active.
+    pub fn is_network_active(&self) -> bool {
+        self.network_active.load(Ordering::SeqCst)
+    }
+
+    /// Pauses (and disconnects from all peers) or resumes networking.
+    pub fn set_network_active(&self, active: bool) {
+        if self.network_active.swap(active, Ordering::SeqCst) == active {
+            return;
+        }
+
+        if active {
+            info!("Resuming networking");
+        } else {
+            info!("Pausing networking");
+            for info in self.connections.info() {
+                self.close_channel(info.id);
+            }
+        }
+    }
+
+    /// Closes connection to the peer with given address.
+    /// Returns false if there's no connection to this address.
+    pub fn disconnect_address(&self, addr: &SocketAddr) -> bool {
+        match self
+            .connections
+            .info()
+            .into_iter()
+            .find(|info| info.address == *addr)
+        {
+            Some(info) => {
+                self.close_channel(info.id);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Closes connection to the peer with given id.
+    /// Returns false if there's no such peer.
+    pub fn disconnect_peer(&self, id: PeerId) -> bool {
+        if self.connections.channel(id).is_none() {
+            return false;
+        }
+
+        self.close_channel(id);
+        true
+    }
+
+    /// Bans subnet for `ban_time` seconds (or for default ban time) and disconnects from peers in it.
+    pub fn ban(&self, subnet: IpSubnet, ban_time: Option<u32>) -> Result<(), BanListError> {
+        trace!("Banning subnet {}", subnet);
+        try!(self.ban_list.write().ban(subnet, ban_time));
+        self.disconnect_subnet(&subnet);
+        Ok(())
+    }
+
+    /// Removes subnet from the ban list.
+    pub fn unban(&self, subnet: &IpSubnet) -> Result<(), BanListError> {
+        trace!("Unbanning subnet {}", subnet);
+        self.ban_list.write().unban(subnet)
+    }
+
+    /// Returns all banned subnets.
+    pub fn banned(&self) -> Vec<BanEntry> {
+        self.ban_list.read().entries()
+    }
+
+    /// Removes all subnets from the ban list.
+    pub fn clear_banned(&self) {
+        trace!("Clearing ban list");
+        self.ban_list.write().clear()
+    }
+
+    /// Returns ban score of the address.
+    pub fn ban_score(&self, addr: &IpAddr) -> u32 {
+        self.ban_list.read().ban_score(addr)
+    }
+
+    /// Increases ban score of misbehaving node. Bans node when ban score threshold is reached.
+    pub fn note_misbehavior(&self, addr: &SocketAddr, score: u32) {
+        if self.config.whitelist.contains(&addr.ip()) {
+            return;
+        }
+
+        trace!("Increasing ban score of {} by {}", addr, score);
+        if self.ban_list.write().note_misbehavior(addr.ip(), score) {
+            warn!("Banning {} due to misbehavior", addr.ip());
+            self.disconnect_subnet(&IpSubnet::single(addr.ip()));
+        }
+    }
+
+    /// Closes all connections to peers from given subnet.
+    fn disconnect_subnet(&self, subnet: &IpSubnet) {
+        for info in self.connections.info() {
+            if subnet.contains(&info.address.ip()) {
+                self.close_channel(info.id);
+            }
+        }
+    }
+
     /// Every 10 seconds check if we have reached maximum number of outbound connections.
     /// If not, connect to best peers.
     pub fn autoconnect(context: Arc<Context>, handle: &Handle) {
@@ -152,7 +329,7 @@ impl Context {
                     }
 
                     let needed = context.connection_counter.outbound_connections_needed() as usize;
-                    if needed != 0 {
+                    if needed != 0 && context.is_network_active() {
                         let used_addresses = context.connections.addresses();
                         let peers = context.node_table.read().nodes_with_services(
                             &Services::default(),
@@ -160,10 +337,14 @@ impl Context {
                             &used_addresses,
                             needed,
                         );
-                        let addresses = peers
-                            .into_iter()
-                            .map(|peer| peer.address())
-                            .collect::<Vec<_>>();
+                        let addresses = {
+                            let ban_list = context.ban_list.read();
+                            peers
+                                .into_iter()
+                                .map(|peer| peer.address())
+                                .filter(|address| !ban_list.is_banned(&address.ip()))
+                                .collect::<Vec<_>>()
+                        };
 
                         trace!("Creating {} more outbound connections", addresses.len());
                         for address in addresses {
@@ -171,12 +352,60 @@ impl Context {
                         }
                     }
 
-                    if let Err(_err) = context
-                        .node_table
-                        .read()
-                        .save_to_file(&context.config.node_table_path)
-                    {
-                        error!("Saving node table to disk failed");
+                    Ok(())
+                })
+                .for_each(|_| Ok(()))
+                .then(|_| finished(())),
+        );
+        c.spawn(interval);
+    }
+
+    /// Periodically makes feeler connection: short-lived connection to the least recently active
+    /// node from the node table, which checks if the node is still reachable. Feeler connections
+    /// are not counted against outbound connections limit.
+    pub fn feeler(context: Arc<Context>, handle: &Handle) {
+        let interval = context.config.feeler_interval;
+        if interval == 0 {
+            return;
+        }
+
+        let c = context.clone();
+        let feeler: BoxedEmptyFuture = Box::new(
+            Interval::new(time::Duration::new(interval, 0), handle)
+                .expect("Failed to create interval")
+                .and_then(move |_| {
+                    if !context.is_network_active() {
+                        return Ok(());
+                    }
+
+                    context.feeler_attempts.write().prune();
+                    let candidate = {
+                        let used_addresses = context.connections.addresses();
+                        let ban_list = context.ban_list.read();
+                        let feeler_attempts = context.feeler_attempts.read();
+                        context.node_table.read().feeler_candidate(
+                            context.config.internet_protocol,
+                            |address| {
+                                !used_addresses.contains(address)
+                                    && !ban_list.is_banned(&address.ip())
+                                    && !feeler_attempts.is_recently_tried(address)
+                            },
+                        )
+                    };
+
+                    if let Some(node) = candidate {
+                        let address = node.address();
+                        context.feeler_attempts.write().note_attempt(address);
+                        let feeler_context = context.clone();
+                        context.remote.spawn(move |handle| {
+                            let config = feeler_context.config.clone();
+                            feeler_context.pool.clone().spawn(Context::feeler_future(
+                                feeler_context,
+                                address,
+                                handle,
+                                &config.connection,
+                            ))
+                        });
                     }
 
                     Ok(())
@@ -184,7 +413,74 @@ impl Context {
                 .for_each(|_| Ok(()))
                 .then(|_| finished(())),
         );
-        c.spawn(interval);
+        c.spawn(feeler);
+    }
+
+    /// Connect to socket, complete the handshake, record the result in the node table
+    /// and disconnect.
+    fn feeler_future(
+        context: Arc<Context>,
+        socket: net::SocketAddr,
+        handle: &Handle,
+        config: &NetConfig,
+    ) -> BoxedEmptyFuture {
+        trace!("Making feeler connection to: {}", socket);
+        let connection = connect(&socket, handle, config);
+        Box::new(connection.then(move |result| {
+            match result {
+                Ok(DeadlineStatus::Meet(Ok(connection))) => {
+                    trace!("Feeler connection to {} succeeded", connection.address);
+                    context
+                        .node_table
+                        .write()
+                        .insert(connection.address, connection.services);
+                    connection.stream.shutdown();
+                }
+                Ok(DeadlineStatus::Meet(Err(err))) => {
+                    trace!("Feeler handshake with {} failed with: {}", socket, err);
+                    context.node_table.write().note_failure(&socket);
+                }
+                Ok(DeadlineStatus::Timeout) => {
+                    trace!("Feeler handshake with {} timed out", socket);
+                    context.node_table.write().note_failure(&socket);
+                }
+                Err(_) => {
+                    trace!("Unable to make feeler connection to {}", socket);
+                    context.node_table.write().note_failure(&socket);
+                }
+            }
+
+            finished(())
+        }))
+    }
+
+    /// Periodically saves node table to disk.
+    pub fn persist_node_table(context: Arc<Context>, handle: &Handle) {
+        let interval = context.config.node_table_save_interval;
+        if interval == 0 {
+            return;
+        }
+
+        let c = context.clone();
+        let persist: BoxedEmptyFuture = Box::new(
+            Interval::new(time::Duration::new(interval, 0), handle)
+                .expect("Failed to create interval")
+                .and_then(move |_| {
+                    context.save_node_table();
+                    Ok(())
+                })
+                .for_each(|_| Ok(()))
+                .then(|_| finished(())),
+        );
+        c.spawn(persist);
+    }
+
+    /// Saves node table to disk.
+    pub fn save_node_table(&self) {
+        trace!("Saving node table");
+        if let Err(_err) = self.node_table.read().save() {
+            error!("Saving node table to disk failed");
+        }
     }
 
     /// Connect to socket using given context and handle.
@@ -254,6 +550,11 @@ impl Context {
     where
         T: SessionFactory,
     {
+        if !context.is_network_active() {
+            trace!("Not connecting to {}: networking is paused", socket);
+            return;
+        }
+
         context.connection_counter.note_new_outbound_connection();
         context.remote.clone().spawn(move |handle| {
             let config = context.config.clone();
@@ -266,7 +567,19 @@ impl Context {
         })
     }
 
-    pub fn connect_normal(context: Arc<Context>, socket: net::SocketAddr) {
+    /// Connect to manually added node. Unlike automatic connections, these could use
+    /// `ADDED_NODE_CONNECTIONS` outbound connections above the `max_outbound` limit.
+    pub fn connect_added_node(context: Arc<Context>, socket: net::SocketAddr) {
+        let (current, max) = context.connection_counter.outbound_connections();
+        if current >= max + ADDED_NODE_CONNECTIONS {
+            info!(
+                "Not connecting to {}: all {} outbound connections are used",
+                socket,
+                max + ADDED_NODE_CONNECTIONS
+            );
+            return;
+        }
+
         Self::connect::<NormalSessionFactory>(context, socket)
     }
 
@@ -307,7 +620,9 @@ impl Context {
                             );
                             // TODO: close socket
                             context.node_table.write().note_failure(&socket);
-                            context.connection_counter.note_close_inbound_connection();
+                            context
+                                .connection_counter
+                                .note_close_inbound_connection(socket.ip());
                             Box::new(finished(Ok(())))
                         }
                         Ok(DeadlineStatus::Timeout) => {
@@ -315,7 +630,9 @@ impl Context {
                             trace!("Accepting handshake from {} timed out", socket);
                             // TODO: close socket
                             context.node_table.write().note_failure(&socket);
-                            context.connection_counter.note_close_inbound_connection();
+                            context
+                                .connection_counter
+                                .note_close_inbound_connection(socket.ip());
                             Box::new(finished(Ok(())))
                         }
                         Err(_) => {
@@ -325,7 +642,9 @@ impl Context {
                                 socket
                             );
                             context.node_table.write().note_failure(&socket);
-                            context.connection_counter.note_close_inbound_connection();
+                            context
+                                .connection_counter
+                                .note_close_inbound_connection(socket.ip());
                             Box::new(finished(Ok(())))
                         }
                     }
@@ -340,7 +659,9 @@ impl Context {
         socket: net::SocketAddr,
         config: NetConfig,
     ) {
-        context.connection_counter.note_new_inbound_connection();
+        context
+            .connection_counter
+            .note_new_inbound_connection(socket.ip());
         context.remote.clone().spawn(move |handle| {
             context
                 .pool
@@ -366,11 +687,34 @@ impl Context {
                     // because we acquire atomic value twice,
                     // it may happen that accept slightly more connections than we need
                     // we don't mind
-                    if context.connection_counter.inbound_connections_needed() > 0 {
-                        Context::accept_connection(context.clone(), stream, socket, config.clone());
-                    } else {
+                    if context.connection_counter.inbound_connections_needed() == 0 {
+                        trace!(
+                            "Rejecting inbound connection from {}: maximal number of inbound connections is reached",
+                            socket
+                        );
+                        // ignore result
+                        let _ = stream.shutdown(net::Shutdown::Both);
+                    } else if !context.is_network_active() {
+                        trace!("Rejecting inbound connection from {}: networking is paused", socket);
                         // ignore result
                         let _ = stream.shutdown(net::Shutdown::Both);
+                    } else if context.ban_list.read().is_banned(&socket.ip()) {
+                        trace!("Rejecting inbound connection from banned {}", socket);
+                        // ignore result
+                        let _ = stream.shutdown(net::Shutdown::Both);
+                    } else if !context.config.whitelist.contains(&socket.ip())
+                        && context
+                            .connection_counter
+                            .is_inbound_connections_per_ip_limit_reached(&socket.ip())
+                    {
+                        trace!(
+                            "Rejecting inbound connection from {}: too many connections from this IP",
+                            socket
+                        );
+                        // ignore result
+                        let _ = stream.shutdown(net::Shutdown::Both);
+                    } else {
+                        Context::accept_connection(context.clone(), stream, socket, config.clone());
                     }
                     Ok(())
                 })
@@ -493,7 +837,9 @@ impl Context {
             trace!("Disconnecting from {}", info.address);
             channel.shutdown();
             match info.direction {
-                Direction::Inbound => self.connection_counter.note_close_inbound_connection(),
+                Direction::Inbound => self
+                    .connection_counter
+                    .note_close_inbound_connection(info.address.ip()),
                 Direction::Outbound => self.connection_counter.note_close_outbound_connection(),
             }
         }
@@ -512,7 +858,9 @@ impl Context {
             channel.shutdown();
             self.node_table.write().note_failure(&info.address);
             match info.direction {
-                Direction::Inbound => self.connection_counter.note_close_inbound_connection(),
+                Direction::Inbound => self
+                    .connection_counter
+                    .note_close_inbound_connection(info.address.ip()),
                 Direction::Outbound => self.connection_counter.note_close_outbound_connection(),
             }
         }
@@ -535,6 +883,22 @@ impl Context {
     pub fn nodes(&self) -> Vec<Node> {
         self.node_table.read().nodes()
     }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Sends ping request to every connected peer.
+    pub fn ping_all(&self) {
+        for channel in self.connections.channels().values() {
+            channel.session().on_ping_request();
+        }
+    }
+
+    /// Returns traffic counters of all connections.
+    pub fn net_totals(&self) -> NetTotals {
+        net_totals()
+    }
 }
 
 pub struct P2P {
@@ -550,6 +914,9 @@ pub struct P2P {
 
 impl Drop for P2P {
     fn drop(&mut self) {
+        // flush node table, so that recently learned nodes are not lost
+        self.context.save_node_table();
+
         // there are retain cycles
         // context->connections->channel->session->protocol->context
         // context->connections->channel->on_message closure->context
@@ -598,8 +965,11 @@ impl P2P {
         for seed in &self.config.seeds {
             self.connect_to_seednode(&resolver, seed);
         }
+        self.query_dns_seeds(&resolver);
 
         Context::autoconnect(self.context.clone(), &self.event_loop_handle);
+        Context::persist_node_table(self.context.clone(), &self.event_loop_handle);
+        Context::feeler(self.context.clone(), &self.event_loop_handle);
         try!(self.listen());
         Ok(())
     }
@@ -643,6 +1013,58 @@ impl P2P {
         self.event_loop_handle.spawn(pool_work);
     }
 
+    /// Resolves DNS seeds and adds resolved addresses to the node table. If none of DNS seeds
+    /// could be resolved, fixed seed addresses are added instead.
+    pub fn query_dns_seeds(&self, resolver: &Resolver) {
+        if self.config.dns_seeds.is_empty() {
+            return;
+        }
+
+        let lookups = self
+            .config
+            .dns_seeds
+            .iter()
+            .map(|seed| {
+                let owned_seed = seed.clone();
+                resolver.resolve(seed).then(move |result| {
+                    let addresses = match result {
+                        Ok(address) => address.at(0).addresses().collect::<Vec<_>>(),
+                        Err(_err) => {
+                            trace!("Dns lookup of seed {} failed", owned_seed);
+                            Vec::new()
+                        }
+                    };
+                    trace!(
+                        "Dns lookup of seed {} resolved {} addresses",
+                        owned_seed,
+                        addresses.len()
+                    );
+                    Ok::<_, ()>(addresses)
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let context = self.context.clone();
+        let fixed_seeds = self.config.fixed_seeds.clone();
+        let seeding = join_all(lookups).then(move |result| {
+            let resolved = result.unwrap_or_default();
+            let added = context.node_table.write().insert_seeds(
+                &resolved,
+                &fixed_seeds,
+                MAX_DNS_SEED_ADDRESSES,
+            );
+            if resolved.iter().all(Vec::is_empty) {
+                info!(
+                    "None of DNS seeds could be resolved, using {} fixed seed addresses",
+                    added
+                );
+            }
+            finished(())
+        });
+        let pool_work = self.pool.spawn(seeding);
+        self.event_loop_handle.spawn(pool_work);
+    }
+
     fn listen(&self) -> Result<(), Box<error::Error>> {
         let server = try!(Context::listen(
             self.context.clone(),
@@ -27,6 +27,18 @@ impl Protocol for AddrProtocol {
     fn initialize(&mut self) {
         if let Direction::Outbound = self.context.info().direction {
             self.context.send_request(&GetAddr);
+
+            // advertise ourselves using address, at which the peer sees us
+            let ip = self
+                .context
+                .info()
+                .version_message
+                .receiver()
+                .address
+                .into();
+            if let Some(entry) = self.context.global().local_address_entry(ip) {
+                self.context.send_request(&Addr::new(vec![entry]));
+            }
         }
     }
 
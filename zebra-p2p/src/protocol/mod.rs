@@ -27,6 +27,9 @@ pub trait Protocol: Send {
     /// On disconnect.
     fn on_close(&mut self) {}
 
+    /// On ping request from the node operator.
+    fn on_ping_request(&mut self) {}
+
     /// Boxes the protocol.
     fn boxed(self) -> Box<Protocol>
     where
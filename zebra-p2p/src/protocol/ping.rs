@@ -44,6 +44,17 @@ impl PingProtocol {
     }
 }
 
+impl PingProtocol {
+    /// Send ping request with fresh nonce and start waiting for pong.
+    fn send_ping(&mut self, now: f64) {
+        let nonce = self.nonce_generator.get();
+        self.state = State::WaitingPong(now);
+        self.last_ping_nonce = Some(nonce);
+        let ping = Ping::new(nonce);
+        self.context.send_request(&ping);
+    }
+}
+
 impl Protocol for PingProtocol {
     fn initialize(&mut self) {
         // bitcoind always sends ping, let's do the same
@@ -56,11 +67,7 @@ impl Protocol for PingProtocol {
             State::WaitingTimeout(time) => {
                 // send ping request if enough time has passed since last message
                 if now - time > PING_INTERVAL_S {
-                    let nonce = self.nonce_generator.get();
-                    self.state = State::WaitingPong(now);
-                    self.last_ping_nonce = Some(nonce);
-                    let ping = Ping::new(nonce);
-                    self.context.send_request(&ping);
+                    self.send_ping(now);
                 }
             }
             State::WaitingPong(time) => {
@@ -77,6 +84,13 @@ impl Protocol for PingProtocol {
         }
     }
 
+    fn on_ping_request(&mut self) {
+        // if we are already waiting for pong, the peer is being pinged
+        if let State::WaitingTimeout(_) = self.state {
+            self.send_ping(time::precise_time_s());
+        }
+    }
+
     fn on_message(&mut self, command: &Command, payload: &Bytes) -> Result<(), Error> {
         // we have received new message => do not close connection because of timeout
         self.state = State::WaitingTimeout(time::precise_time_s());
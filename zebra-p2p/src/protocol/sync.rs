@@ -41,6 +41,7 @@ pub trait InboundSyncConnection: Send + Sync {
     fn on_sendheaders(&self, message: types::SendHeaders);
     fn on_feefilter(&self, message: types::FeeFilter);
     fn on_notfound(&self, message: types::NotFound);
+    fn on_reject(&self, message: types::Reject);
 }
 
 pub trait OutboundSyncConnection: Send + Sync {
@@ -60,7 +61,9 @@ pub trait OutboundSyncConnection: Send + Sync {
     fn send_sendheaders(&self, message: &types::SendHeaders);
     fn send_feefilter(&self, message: &types::FeeFilter);
     fn send_notfound(&self, message: &types::NotFound);
+    fn send_reject(&self, message: &types::Reject);
     fn ignored(&self, id: u32);
+    fn note_misbehavior(&self, score: u32);
     fn close(&self);
 }
 
@@ -139,10 +142,23 @@ impl OutboundSyncConnection for OutboundSync {
         self.context.send_request(message);
     }
 
+    fn send_reject(&self, message: &types::Reject) {
+        // peers with older protocol versions do not understand reject messages
+        if self.context.info().version >= types::Reject::version() {
+            self.context.send_request(message);
+        }
+    }
+
     fn ignored(&self, id: u32) {
         self.context.ignore_response(id);
     }
 
+    fn note_misbehavior(&self, score: u32) {
+        self.context
+            .global()
+            .note_misbehavior(&self.context.info().address, score)
+    }
+
     fn close(&self) {
         self.context
             .global()
@@ -265,6 +281,9 @@ impl Protocol for SyncProtocol {
         } else if command == &types::NotFound::command() {
             let message: types::NotFound = try!(deserialize_payload(payload, version));
             self.inbound_connection.on_notfound(message);
+        } else if command == &types::Reject::command() {
+            let message: types::Reject = try!(deserialize_payload(payload, version));
+            self.inbound_connection.on_reject(message);
         }
         Ok(())
     }
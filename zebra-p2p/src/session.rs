@@ -79,6 +79,12 @@ impl Session {
         }
     }
 
+    pub fn on_ping_request(&self) {
+        for protocol in self.protocols.lock().iter_mut() {
+            protocol.on_ping_request();
+        }
+    }
+
     pub fn stats(&self) -> &Mutex<PeerStats> {
         self.peer_context.stats()
     }
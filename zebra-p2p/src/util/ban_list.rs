@@ -0,0 +1,409 @@
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::{fmt, str};
+use util::time::{RealTime, Time};
+
+/// Ban score of misbehaving peer decreases by one point every `BAN_SCORE_DECAY_INTERVAL` seconds.
+const BAN_SCORE_DECAY_INTERVAL: i64 = 60;
+
+/// IP subnet: network address + prefix length.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct IpSubnet {
+    /// Network address (all host bits are cleared).
+    address: IpAddr,
+    /// Prefix length.
+    prefix: u8,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct InvalidIpSubnet;
+
+impl IpSubnet {
+    /// Creates subnet which contains given address only.
+    pub fn single(address: IpAddr) -> Self {
+        IpSubnet {
+            address: address,
+            prefix: max_prefix(&address),
+        }
+    }
+
+    /// Creates subnet from the address and the prefix length. Host bits of the address are ignored.
+    pub fn new(address: IpAddr, prefix: u8) -> Result<Self, InvalidIpSubnet> {
+        if prefix > max_prefix(&address) {
+            return Err(InvalidIpSubnet);
+        }
+
+        Ok(IpSubnet {
+            address: network_address(&address, prefix),
+            prefix: prefix,
+        })
+    }
+
+    /// Network address of this subnet.
+    pub fn address(&self) -> IpAddr {
+        self.address
+    }
+
+    /// Prefix length of this subnet.
+    pub fn prefix(&self) -> u8 {
+        self.prefix
+    }
+
+    /// Returns true if address belongs to this subnet.
+    pub fn contains(&self, address: &IpAddr) -> bool {
+        match (self.address, *address) {
+            (IpAddr::V4(_), IpAddr::V4(_)) | (IpAddr::V6(_), IpAddr::V6(_)) => {
+                network_address(address, self.prefix) == self.address
+            }
+            _ => false,
+        }
+    }
+}
+
+impl str::FromStr for IpSubnet {
+    type Err = InvalidIpSubnet;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.splitn(2, '/');
+        let address: IpAddr = try!(parts
+            .next()
+            .and_then(|address| address.parse().ok())
+            .ok_or(InvalidIpSubnet));
+        match parts.next() {
+            Some(prefix) => {
+                let prefix = try!(prefix.parse().map_err(|_| InvalidIpSubnet));
+                IpSubnet::new(address, prefix)
+            }
+            None => Ok(IpSubnet::single(address)),
+        }
+    }
+}
+
+impl fmt::Display for IpSubnet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.prefix == max_prefix(&self.address) {
+            write!(f, "{}", self.address)
+        } else {
+            write!(f, "{}/{}", self.address, self.prefix)
+        }
+    }
+}
+
+fn max_prefix(address: &IpAddr) -> u8 {
+    match *address {
+        IpAddr::V4(_) => 32,
+        IpAddr::V6(_) => 128,
+    }
+}
+
+fn network_address(address: &IpAddr, prefix: u8) -> IpAddr {
+    match *address {
+        IpAddr::V4(address) => {
+            let mask = (!0u32).checked_shl(32 - prefix as u32).unwrap_or(0);
+            IpAddr::V4(Ipv4Addr::from(u32::from(address) & mask))
+        }
+        IpAddr::V6(address) => {
+            let mask = (!0u128).checked_shl(128 - prefix as u32).unwrap_or(0);
+            IpAddr::V6(Ipv6Addr::from(u128::from(address) & mask))
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum BanListError {
+    AlreadyBanned,
+    NotBanned,
+}
+
+/// Banned subnet.
+#[derive(Debug, PartialEq, Clone)]
+pub struct BanEntry {
+    /// Banned subnet.
+    pub subnet: IpSubnet,
+    /// Ban expiration timestamp.
+    pub banned_until: i64,
+}
+
+/// List of banned subnets + ban scores of misbehaving peers.
+#[derive(Default, Debug)]
+pub struct BanList<T = RealTime>
+where
+    T: Time,
+{
+    /// Time source.
+    time: T,
+    /// Ban score at which peer gets banned.
+    ban_score_threshold: u32,
+    /// Duration of the automatic ban (in seconds).
+    ban_time: u32,
+    /// Banned subnets => ban expiration timestamps.
+    banned: HashMap<IpSubnet, i64>,
+    /// Ban scores of misbehaving peers => timestamps of the last score decay.
+    ban_scores: HashMap<IpAddr, (u32, i64)>,
+}
+
+impl BanList {
+    /// Creates empty ban list.
+    pub fn new(ban_score_threshold: u32, ban_time: u32) -> Self {
+        BanList {
+            time: RealTime,
+            ban_score_threshold: ban_score_threshold,
+            ban_time: ban_time,
+            banned: HashMap::new(),
+            ban_scores: HashMap::new(),
+        }
+    }
+}
+
+impl<T> BanList<T>
+where
+    T: Time,
+{
+    /// Bans subnet for `ban_time` seconds (or for default ban time if None).
+    pub fn ban(&mut self, subnet: IpSubnet, ban_time: Option<u32>) -> Result<(), BanListError> {
+        let now = self.time.get().sec;
+        self.remove_expired(now);
+        if self.banned.contains_key(&subnet) {
+            return Err(BanListError::AlreadyBanned);
+        }
+
+        let ban_time = ban_time.unwrap_or(self.ban_time);
+        self.banned.insert(subnet, now + ban_time as i64);
+        Ok(())
+    }
+
+    /// Removes subnet from the ban list.
+    pub fn unban(&mut self, subnet: &IpSubnet) -> Result<(), BanListError> {
+        let now = self.time.get().sec;
+        self.remove_expired(now);
+        self.banned
+            .remove(subnet)
+            .map(|_| ())
+            .ok_or(BanListError::NotBanned)
+    }
+
+    /// Removes all subnets from the ban list.
+    pub fn clear(&mut self) {
+        self.banned.clear();
+    }
+
+    /// Returns true if address belongs to any of banned subnets.
+    pub fn is_banned(&self, address: &IpAddr) -> bool {
+        let now = self.time.get().sec;
+        self.banned
+            .iter()
+            .any(|(subnet, banned_until)| *banned_until > now && subnet.contains(address))
+    }
+
+    /// Returns all active bans, ordered by expiration time.
+    pub fn entries(&self) -> Vec<BanEntry> {
+        let now = self.time.get().sec;
+        let mut entries: Vec<_> = self
+            .banned
+            .iter()
+            .filter(|&(_, banned_until)| *banned_until > now)
+            .map(|(subnet, banned_until)| BanEntry {
+                subnet: *subnet,
+                banned_until: *banned_until,
+            })
+            .collect();
+        entries.sort_by_key(|entry| entry.banned_until);
+        entries
+    }
+
+    /// Returns current ban score of the address.
+    pub fn ban_score(&self, address: &IpAddr) -> u32 {
+        let now = self.time.get().sec;
+        self.ban_scores
+            .get(address)
+            .map(|&(ban_score, decayed_at)| decay_ban_score(ban_score, decayed_at, now).0)
+            .unwrap_or_default()
+    }
+
+    /// Increases ban score of the address. When score reaches the threshold, address is banned.
+    /// Returns true if address has been banned.
+    pub fn note_misbehavior(&mut self, address: IpAddr, score: u32) -> bool {
+        let now = self.time.get().sec;
+        self.remove_decayed(now);
+
+        let ban_score = {
+            let entry = self.ban_scores.entry(address).or_insert((0, now));
+            let (ban_score, decayed_at) = decay_ban_score(entry.0, entry.1, now);
+            *entry = (ban_score.saturating_add(score), decayed_at);
+            entry.0
+        };
+
+        if ban_score < self.ban_score_threshold {
+            return false;
+        }
+
+        self.ban_scores.remove(&address);
+        self.banned
+            .insert(IpSubnet::single(address), now + self.ban_time as i64);
+        true
+    }
+
+    fn remove_expired(&mut self, now: i64) {
+        self.banned.retain(|_, banned_until| *banned_until > now);
+    }
+
+    fn remove_decayed(&mut self, now: i64) {
+        self.ban_scores.retain(|_, &mut (ban_score, decayed_at)| {
+            decay_ban_score(ban_score, decayed_at, now).0 != 0
+        });
+    }
+}
+
+/// Returns ban score, decayed by `now` + timestamp of the last decay.
+fn decay_ban_score(ban_score: u32, decayed_at: i64, now: i64) -> (u32, i64) {
+    let intervals = ::std::cmp::max(now - decayed_at, 0) / BAN_SCORE_DECAY_INTERVAL;
+    if intervals >= ban_score as i64 {
+        return (0, now);
+    }
+
+    (
+        ban_score - intervals as u32,
+        decayed_at + intervals * BAN_SCORE_DECAY_INTERVAL,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BanList, BanListError, InvalidIpSubnet, IpSubnet, BAN_SCORE_DECAY_INTERVAL};
+    use std::net::IpAddr;
+    use util::time::IncrementalTime;
+
+    fn ip(s: &str) -> IpAddr {
+        s.parse().unwrap()
+    }
+
+    fn ban_list(ban_score_threshold: u32, ban_time: u32) -> BanList<IncrementalTime> {
+        BanList {
+            ban_score_threshold: ban_score_threshold,
+            ban_time: ban_time,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_ip_subnet_parse() {
+        let subnet: IpSubnet = "192.168.1.17/24".parse().unwrap();
+        assert_eq!(subnet.address(), ip("192.168.1.0"));
+        assert_eq!(subnet.prefix(), 24);
+        assert_eq!(subnet.to_string(), "192.168.1.0/24");
+
+        let subnet: IpSubnet = "10.0.0.1".parse().unwrap();
+        assert_eq!(subnet, IpSubnet::single(ip("10.0.0.1")));
+        assert_eq!(subnet.to_string(), "10.0.0.1");
+
+        let subnet: IpSubnet = "2001:db8::1/32".parse().unwrap();
+        assert_eq!(subnet.address(), ip("2001:db8::"));
+        assert_eq!(subnet.to_string(), "2001:db8::/32");
+
+        assert_eq!("10.0.0.1/33".parse::<IpSubnet>(), Err(InvalidIpSubnet));
+        assert_eq!("10.0.0/8".parse::<IpSubnet>(), Err(InvalidIpSubnet));
+        assert_eq!("10.0.0.1/x".parse::<IpSubnet>(), Err(InvalidIpSubnet));
+    }
+
+    #[test]
+    fn test_ip_subnet_contains() {
+        let subnet: IpSubnet = "192.168.1.0/24".parse().unwrap();
+        assert!(subnet.contains(&ip("192.168.1.0")));
+        assert!(subnet.contains(&ip("192.168.1.255")));
+        assert!(!subnet.contains(&ip("192.168.2.1")));
+        assert!(!subnet.contains(&ip("::ffff:192.168.1.1")));
+
+        let subnet: IpSubnet = "0.0.0.0/0".parse().unwrap();
+        assert!(subnet.contains(&ip("8.8.8.8")));
+        assert!(!subnet.contains(&ip("::1")));
+
+        let subnet = IpSubnet::single(ip("::1"));
+        assert!(subnet.contains(&ip("::1")));
+        assert!(!subnet.contains(&ip("::2")));
+    }
+
+    #[test]
+    fn test_ban_list_ban_unban() {
+        let subnet: IpSubnet = "192.168.1.0/24".parse().unwrap();
+        let mut list = ban_list(100, 100);
+        assert!(!list.is_banned(&ip("192.168.1.1")));
+
+        assert_eq!(list.ban(subnet, None), Ok(()));
+        assert_eq!(list.ban(subnet, None), Err(BanListError::AlreadyBanned));
+        assert!(list.is_banned(&ip("192.168.1.1")));
+        assert!(!list.is_banned(&ip("192.168.2.1")));
+        assert_eq!(list.entries().len(), 1);
+
+        assert_eq!(list.unban(&subnet), Ok(()));
+        assert_eq!(list.unban(&subnet), Err(BanListError::NotBanned));
+        assert!(!list.is_banned(&ip("192.168.1.1")));
+        assert!(list.entries().is_empty());
+    }
+
+    #[test]
+    fn test_ban_list_expiration() {
+        let subnet = IpSubnet::single(ip("10.0.0.1"));
+        let mut list = ban_list(100, 100);
+        // banned at time 0 until time 3
+        list.ban(subnet, Some(3)).unwrap();
+        // time 1
+        assert!(list.is_banned(&ip("10.0.0.1")));
+        // time 2
+        assert!(list.is_banned(&ip("10.0.0.1")));
+        // time 3
+        assert!(!list.is_banned(&ip("10.0.0.1")));
+        // expired ban is replaced
+        assert_eq!(list.ban(subnet, Some(3)), Ok(()));
+    }
+
+    #[test]
+    fn test_ban_list_clear() {
+        let mut list = ban_list(100, 100);
+        list.ban("10.0.0.0/8".parse().unwrap(), None).unwrap();
+        list.ban("192.168.0.0/16".parse().unwrap(), None).unwrap();
+        assert_eq!(list.entries().len(), 2);
+        list.clear();
+        assert!(list.entries().is_empty());
+    }
+
+    #[test]
+    fn test_ban_list_note_misbehavior() {
+        let mut list = ban_list(100, 100);
+        assert!(!list.note_misbehavior(ip("10.0.0.1"), 50));
+        assert_eq!(list.ban_score(&ip("10.0.0.1")), 50);
+        assert!(!list.is_banned(&ip("10.0.0.1")));
+
+        assert!(list.note_misbehavior(ip("10.0.0.1"), 50));
+        assert_eq!(list.ban_score(&ip("10.0.0.1")), 0);
+        assert!(list.is_banned(&ip("10.0.0.1")));
+        assert!(!list.is_banned(&ip("10.0.0.2")));
+    }
+
+    #[test]
+    fn test_ban_list_ban_score_decay() {
+        let mut list = ban_list(100, 100);
+        // misbehavior at time 0
+        assert!(!list.note_misbehavior(ip("10.0.0.1"), 2));
+        // time 1
+        assert_eq!(list.ban_score(&ip("10.0.0.1")), 2);
+
+        // every call advances time by one second
+        for _ in 2..BAN_SCORE_DECAY_INTERVAL {
+            list.ban_score(&ip("10.0.0.1"));
+        }
+        // time BAN_SCORE_DECAY_INTERVAL
+        assert_eq!(list.ban_score(&ip("10.0.0.1")), 1);
+        // time BAN_SCORE_DECAY_INTERVAL + 1 => decay is applied before score is increased
+        assert!(!list.note_misbehavior(ip("10.0.0.1"), 98));
+        assert_eq!(list.ban_score(&ip("10.0.0.1")), 99);
+
+        for _ in BAN_SCORE_DECAY_INTERVAL + 3..2 * BAN_SCORE_DECAY_INTERVAL {
+            list.ban_score(&ip("10.0.0.1"));
+        }
+        // time 2 * BAN_SCORE_DECAY_INTERVAL
+        assert_eq!(list.ban_score(&ip("10.0.0.1")), 98);
+        assert!(!list.note_misbehavior(ip("10.0.0.1"), 1));
+        assert!(list.note_misbehavior(ip("10.0.0.1"), 1));
+        assert!(list.is_banned(&ip("10.0.0.1")));
+    }
+}
@@ -0,0 +1,79 @@
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+use util::interval::{Interval, RealInterval};
+
+/// Addresses, recently tested with feeler connections.
+pub struct FeelerAttempts<I: Interval = RealInterval> {
+    /// Minimal duration between two feeler connections to the same address.
+    retry_interval: Duration,
+    /// Time of the last feeler connection to the address.
+    attempts: HashMap<SocketAddr, Instant>,
+    interval: I,
+}
+
+impl FeelerAttempts<RealInterval> {
+    pub fn new(retry_interval: Duration) -> Self {
+        FeelerAttempts::with_interval(retry_interval, RealInterval)
+    }
+}
+
+impl<I: Interval> FeelerAttempts<I> {
+    pub fn with_interval(retry_interval: Duration, interval: I) -> Self {
+        FeelerAttempts {
+            retry_interval: retry_interval,
+            attempts: HashMap::new(),
+            interval: interval,
+        }
+    }
+
+    /// Notes feeler connection to the address.
+    pub fn note_attempt(&mut self, addr: SocketAddr) {
+        let now = self.interval.now();
+        self.attempts.insert(addr, now);
+    }
+
+    /// Returns true if feeler connection to the address has been made less than
+    /// `retry_interval` ago.
+    pub fn is_recently_tried(&self, addr: &SocketAddr) -> bool {
+        self.attempts
+            .get(addr)
+            .map(|instant| self.interval.elapsed(*instant) < self.retry_interval)
+            .unwrap_or(false)
+    }
+
+    /// Forgets attempts, made more than `retry_interval` ago.
+    pub fn prune(&mut self) {
+        let retry_interval = self.retry_interval;
+        let interval = &self.interval;
+        self.attempts
+            .retain(|_, instant| interval.elapsed(*instant) < retry_interval);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FeelerAttempts;
+    use std::time::Duration;
+    use util::interval::FixedIntervalSpawner;
+
+    #[test]
+    fn feeler_attempts_expire() {
+        let addr = "127.0.0.1:8233".parse().unwrap();
+        let mut attempts = FeelerAttempts::new(Duration::from_secs(60));
+        assert!(!attempts.is_recently_tried(&addr));
+        attempts.note_attempt(addr);
+        assert!(attempts.is_recently_tried(&addr));
+        attempts.prune();
+        assert!(attempts.is_recently_tried(&addr));
+
+        let mut attempts = FeelerAttempts::with_interval(
+            Duration::from_millis(50),
+            FixedIntervalSpawner::new(100),
+        );
+        attempts.note_attempt(addr);
+        assert!(!attempts.is_recently_tried(&addr));
+        attempts.prune();
+        assert!(attempts.attempts.is_empty());
+    }
+}
@@ -26,24 +26,62 @@ impl str::FromStr for InternetProtocol {
 }
 
 impl InternetProtocol {
+    /// Returns true if address could be used with this protocol.
+    /// IPv4-mapped IPv6 addresses are treated as IPv4 addresses.
     pub fn is_allowed(&self, addr: &net::SocketAddr) -> bool {
         match *self {
             InternetProtocol::Any => true,
-            InternetProtocol::IpV4 => match *addr {
-                net::SocketAddr::V4(_) => true,
-                _ => false,
-            },
-            InternetProtocol::IpV6 => match *addr {
-                net::SocketAddr::V6(_) => true,
-                _ => false,
-            },
+            InternetProtocol::IpV4 => is_ipv4(&addr.ip()),
+            InternetProtocol::IpV6 => !is_ipv4(&addr.ip()),
+        }
+    }
+}
+
+/// Returns true if address is IPv4 or IPv4-mapped IPv6 address.
+fn is_ipv4(ip: &net::IpAddr) -> bool {
+    match *ip {
+        net::IpAddr::V4(_) => true,
+        net::IpAddr::V6(ref ip) => ip.segments()[0..6] == [0, 0, 0, 0, 0, 0xffff],
+    }
+}
+
+/// Returns true if address could be reached from the public internet.
+pub fn is_routable(ip: &net::IpAddr) -> bool {
+    match *ip {
+        net::IpAddr::V4(ref ip) => {
+            !(ip.is_unspecified()
+                || ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_broadcast()
+                || ip.is_documentation()
+                || ip.is_multicast())
+        }
+        net::IpAddr::V6(ref ip) => {
+            let segments = ip.segments();
+            if segments[0..6] == [0, 0, 0, 0, 0, 0xffff] {
+                let octets = ip.octets();
+                return is_routable(&net::IpAddr::V4(net::Ipv4Addr::new(
+                    octets[12], octets[13], octets[14], octets[15],
+                )));
+            }
+
+            !(ip.is_unspecified()
+                || ip.is_loopback()
+                || ip.is_multicast()
+                // unique local addresses (fc00::/7)
+                || (segments[0] & 0xfe00) == 0xfc00
+                // unicast link-local addresses (fe80::/10)
+                || (segments[0] & 0xffc0) == 0xfe80
+                // documentation addresses (2001:db8::/32)
+                || (segments[0] == 0x2001 && segments[1] == 0x0db8))
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::InternetProtocol;
+    use super::{is_routable, InternetProtocol};
 
     #[test]
     fn test_default_internet_protocol() {
@@ -56,4 +94,34 @@ mod tests {
         assert_eq!(InternetProtocol::IpV6, "ipv6".parse().unwrap());
         assert!("sa".parse::<InternetProtocol>().is_err());
     }
+
+    #[test]
+    fn test_internet_protocol_is_allowed() {
+        let v4 = "10.0.0.1:8233".parse().unwrap();
+        let v6 = "[2001:470::1]:8233".parse().unwrap();
+        let mapped = "[::ffff:10.0.0.1]:8233".parse().unwrap();
+        assert!(InternetProtocol::Any.is_allowed(&v4));
+        assert!(InternetProtocol::Any.is_allowed(&v6));
+        assert!(InternetProtocol::IpV4.is_allowed(&v4));
+        assert!(!InternetProtocol::IpV4.is_allowed(&v6));
+        assert!(InternetProtocol::IpV4.is_allowed(&mapped));
+        assert!(!InternetProtocol::IpV6.is_allowed(&v4));
+        assert!(InternetProtocol::IpV6.is_allowed(&v6));
+        assert!(!InternetProtocol::IpV6.is_allowed(&mapped));
+    }
+
+    #[test]
+    fn test_is_routable() {
+        assert!(is_routable(&"8.8.8.8".parse().unwrap()));
+        assert!(is_routable(&"2001:470::1".parse().unwrap()));
+        assert!(is_routable(&"::ffff:8.8.8.8".parse().unwrap()));
+        assert!(!is_routable(&"0.0.0.0".parse().unwrap()));
+        assert!(!is_routable(&"127.0.0.1".parse().unwrap()));
+        assert!(!is_routable(&"192.168.1.1".parse().unwrap()));
+        assert!(!is_routable(&"::".parse().unwrap()));
+        assert!(!is_routable(&"::1".parse().unwrap()));
+        assert!(!is_routable(&"fd00::1".parse().unwrap()));
+        assert!(!is_routable(&"fe80::1".parse().unwrap()));
+        assert!(!is_routable(&"::ffff:10.0.0.1".parse().unwrap()));
+    }
 }
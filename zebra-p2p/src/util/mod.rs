@@ -1,3 +1,5 @@
+mod ban_list;
+mod feeler;
 mod internet_protocol;
 pub mod interval;
 mod node_table;
@@ -7,8 +9,10 @@ mod response_queue;
 mod synchronizer;
 pub mod time;
 
-pub use self::internet_protocol::InternetProtocol;
+pub use self::ban_list::{BanEntry, BanList, BanListError, InvalidIpSubnet, IpSubnet};
+pub use self::feeler::FeelerAttempts;
+pub use self::internet_protocol::{is_routable, InternetProtocol};
 pub use self::node_table::{Node, NodeTable, NodeTableError};
-pub use self::peer::{Direction, PeerId, PeerInfo};
+pub use self::peer::{Direction, NetTotals, PeerId, PeerInfo, PeerStatistics};
 pub use self::response_queue::{ResponseQueue, Responses};
 pub use self::synchronizer::{ConfigurableSynchronizer, Synchronizer};
@@ -1,14 +1,24 @@
 use csv;
+use ser::{Error as ReaderError, Reader, Stream};
 use std::cmp::{Ord, Ordering, PartialOrd};
 use std::collections::hash_map::Entry;
 use std::collections::{BTreeSet, HashMap, HashSet};
+use std::io::Read;
 use std::net::SocketAddr;
 use std::{fs, io, net, path};
 use util::time::{RealTime, Time};
 use util::InternetProtocol;
+use zebra_crypto::checksum;
 use zebra_message::common::{NetAddress, Services};
 use zebra_message::types::addr::AddressEntry;
 
+/// Magic bytes at the beginning of the binary node table file.
+const NODE_TABLE_MAGIC: &'static [u8] = b"ZNT1";
+/// Size of the node record in the binary node table file: address + time + failures + checksum.
+const NODE_RECORD_SIZE: usize = 26 + 8 + 4 + 4;
+/// Extension of the legacy (csv) node table file.
+const LEGACY_NODE_TABLE_EXTENSION: &'static str = "csv";
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct Node {
     /// Node address.
@@ -179,6 +189,10 @@ where
     by_score: BTreeSet<NodeByScore>,
     /// Nodes sorted by time.
     by_time: BTreeSet<NodeByTime>,
+    /// File, the node table is persisted to.
+    path: Option<path::PathBuf>,
+    /// Maximal number of nodes, persisted to the file.
+    max_persisted_nodes: usize,
 }
 
 impl NodeTable {
@@ -191,26 +205,70 @@ impl NodeTable {
         }
     }
 
-    /// Opens a file loads node_table from it.
-    pub fn from_file<P>(preferable_services: Services, path: P) -> Result<Self, io::Error>
+    /// Opens a file loads node_table from it. At most `max_persisted_nodes` best nodes
+    /// are written back to this file on `save`.
+    /// Falls back to the legacy csv format if the file (or legacy csv file) is not in binary format.
+    pub fn from_file<P>(
+        preferable_services: Services,
+        path: P,
+        max_persisted_nodes: usize,
+    ) -> Result<Self, io::Error>
     where
         P: AsRef<path::Path>,
     {
-        fs::OpenOptions::new()
+        let path = path.as_ref();
+        let mut table = try!(Self::read_file(preferable_services, path));
+        table.path = Some(path.to_owned());
+        table.max_persisted_nodes = max_persisted_nodes;
+        Ok(table)
+    }
+
+    /// Saves node table to the file, it has been opened from.
+    pub fn save(&self) -> Result<(), io::Error> {
+        match self.path {
+            Some(ref path) => self.save_to_file(path, self.max_persisted_nodes),
+            None => Ok(()),
+        }
+    }
+
+    fn read_file(preferable_services: Services, path: &path::Path) -> Result<Self, io::Error> {
+        let legacy_path = path.with_extension(LEGACY_NODE_TABLE_EXTENSION);
+        if !path.exists() && legacy_path.exists() {
+            return fs::File::open(legacy_path)
+                .and_then(|f| Self::load_csv(preferable_services, f));
+        }
+
+        let mut data = Vec::new();
+        try!(fs::OpenOptions::new()
             .create(true)
             .read(true)
             // without opening for write, macOS returns os error 22
             .write(true)
             .open(path)
-            .and_then(|f| Self::load(preferable_services, f))
+            .and_then(|mut f| f.read_to_end(&mut data)));
+
+        if data.is_empty() || data.starts_with(NODE_TABLE_MAGIC) {
+            Self::load(preferable_services, &data as &[u8])
+        } else {
+            Self::load_csv(preferable_services, &data as &[u8])
+        }
     }
 
-    /// Saves node table to file
-    pub fn save_to_file<P>(&self, path: P) -> Result<(), io::Error>
+    /// Saves at most `max_nodes` best nodes to file.
+    /// Nodes are written to temporary file first, which then replaces the original file.
+    pub fn save_to_file<P>(&self, path: P, max_nodes: usize) -> Result<(), io::Error>
     where
         P: AsRef<path::Path>,
     {
-        fs::File::create(path).and_then(|file| self.save(file))
+        let path = path.as_ref();
+        let mut temp_path = path.as_os_str().to_owned();
+        temp_path.push(".tmp");
+
+        try!(fs::File::create(&temp_path).and_then(|mut file| {
+            try!(self.write_to(&mut file, max_nodes));
+            file.sync_all()
+        }));
+        fs::rename(temp_path, path)
     }
 }
 
@@ -274,15 +332,53 @@ where
         }
     }
 
+    /// Inserts addresses, resolved from DNS seeds (at most `max_per_seed` new addresses of every
+    /// seed). If none of DNS seeds has been resolved, fixed seed addresses are inserted instead.
+    /// Returns number of inserted addresses.
+    pub fn insert_seeds(
+        &mut self,
+        resolved: &[Vec<SocketAddr>],
+        fixed_seeds: &[SocketAddr],
+        max_per_seed: usize,
+    ) -> usize {
+        if resolved.iter().all(Vec::is_empty) {
+            return self.insert_new_addresses(fixed_seeds, fixed_seeds.len());
+        }
+
+        resolved
+            .iter()
+            .map(|addresses| self.insert_new_addresses(addresses, max_per_seed))
+            .sum()
+    }
+
+    /// Inserts at most `max` addresses, which are not yet in the table.
+    fn insert_new_addresses(&mut self, addresses: &[SocketAddr], max: usize) -> usize {
+        let mut inserted = 0;
+        for address in addresses {
+            if inserted == max {
+                break;
+            }
+
+            if !self.exists(*address) {
+                self.insert(*address, Services::default());
+                inserted += 1;
+            }
+        }
+        inserted
+    }
+
     /// Inserts many new addresses into node table.
     /// Used in `addr` request handler.
     /// Discards all nodes with timestamp newer than current time.
     pub fn insert_many(&mut self, addresses: Vec<AddressEntry>) {
-        // discard all nodes with timestamp newer than current time.
+        // discard all nodes with timestamp newer than current time
+        // and nodes which can't be connected to (unspecified address or port)
         let now = self.time.get().sec;
-        let iter = addresses
-            .into_iter()
-            .filter(|addr| addr.timestamp as i64 <= now);
+        let iter = addresses.into_iter().filter(|addr| {
+            let ip: net::IpAddr = addr.address.address.into();
+            let port: u16 = addr.address.port.into();
+            addr.timestamp as i64 <= now && !ip.is_unspecified() && port != 0
+        });
 
         // iterate over the rest
         for addr in iter {
@@ -376,6 +472,19 @@ where
             .collect()
     }
 
+    /// Returns the least recently active node, accepted by the `filter`.
+    pub fn feeler_candidate<F>(&self, protocol: InternetProtocol, filter: F) -> Option<Node>
+    where
+        F: Fn(&SocketAddr) -> bool,
+    {
+        self.by_time
+            .iter()
+            .rev()
+            .filter(|node| protocol.is_allowed(&node.0.addr))
+            .find(|node| filter(&node.0.addr))
+            .map(|node| node.0.clone())
+    }
+
     /// Marks address as recently used.
     pub fn note_used(&mut self, addr: &SocketAddr) {
         if let Some(ref mut node) = self.by_addr.get_mut(addr) {
@@ -398,31 +507,73 @@ where
         }
     }
 
-    /// Save node table in csv format.
-    pub fn save<W>(&self, write: W) -> Result<(), io::Error>
+    /// Write at most `max_nodes` best nodes in binary format.
+    pub fn write_to<W>(&self, mut write: W, max_nodes: usize) -> Result<(), io::Error>
     where
         W: io::Write,
     {
-        let mut writer = csv::WriterBuilder::new().delimiter(b' ').from_writer(write);
-        let iter = self.by_score.iter().map(|node| &node.0).take(1000);
-
-        let err = || io::Error::new(io::ErrorKind::Other, "Write csv error");
-
-        for n in iter {
-            let record = (
-                n.addr.to_string(),
-                n.time,
-                u64::from(n.services),
-                n.failures,
-            );
-            try!(writer.serialize(record).map_err(|_| err()));
+        try!(write.write_all(NODE_TABLE_MAGIC));
+
+        for n in self.by_score.iter().map(|node| &node.0).take(max_nodes) {
+            let mut stream = Stream::new();
+            stream
+                .append(&NetAddress {
+                    services: n.services,
+                    address: n.addr.ip().into(),
+                    port: n.addr.port().into(),
+                })
+                .append(&n.time)
+                .append(&n.failures);
+            let record = stream.out();
+            try!(write.write_all(&record));
+            try!(write.write_all(&*checksum(&record)));
         }
 
         Ok(())
     }
 
-    /// Loads table in from a csv source.
-    pub fn load<R>(preferable_services: Services, read: R) -> Result<Self, io::Error>
+    /// Loads table from a binary source. Corrupted and truncated node records are skipped.
+    pub fn load<R>(preferable_services: Services, mut read: R) -> Result<Self, io::Error>
+    where
+        R: io::Read,
+        T: Default,
+    {
+        let mut data = Vec::new();
+        try!(read.read_to_end(&mut data));
+
+        let mut node_table = NodeTable::default();
+        node_table.preferable_services = preferable_services;
+
+        if data.is_empty() {
+            return Ok(node_table);
+        }
+
+        if !data.starts_with(NODE_TABLE_MAGIC) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Invalid node table format",
+            ));
+        }
+
+        for record in data[NODE_TABLE_MAGIC.len()..].chunks(NODE_RECORD_SIZE) {
+            let node = match read_node(&preferable_services, record) {
+                Ok(node) => node,
+                Err(_) => {
+                    warn!("Skipping corrupted node table record");
+                    continue;
+                }
+            };
+
+            node_table.by_score.insert(node.clone().into());
+            node_table.by_time.insert(node.clone().into());
+            node_table.by_addr.insert(node.addr, node);
+        }
+
+        Ok(node_table)
+    }
+
+    /// Loads table in from a legacy csv source.
+    pub fn load_csv<R>(preferable_services: Services, read: R) -> Result<Self, io::Error>
     where
         R: io::Read,
         T: Default,
@@ -459,14 +610,42 @@ where
     }
 }
 
+/// Reads single node record from the binary node table.
+fn read_node(preferable_services: &Services, record: &[u8]) -> Result<Node, ReaderError> {
+    if record.len() != NODE_RECORD_SIZE {
+        return Err(ReaderError::UnexpectedEnd);
+    }
+
+    let (data, record_checksum) = record.split_at(NODE_RECORD_SIZE - 4);
+    if &*checksum(data) != record_checksum {
+        return Err(ReaderError::MalformedData);
+    }
+
+    let mut reader = Reader::new(data);
+    let address: NetAddress = try!(reader.read());
+    let time = try!(reader.read());
+    let failures = try!(reader.read());
+    Ok(Node {
+        addr: SocketAddr::new(address.address.into(), address.port.into()),
+        time: time,
+        services: address.services,
+        is_preferable: address.services.includes(preferable_services),
+        failures: failures,
+    })
+}
+
 #[cfg(test)]
 mod tests {
-    use super::NodeTable;
+    extern crate tempdir;
+
+    use self::tempdir::TempDir;
+    use super::{NodeTable, NODE_RECORD_SIZE, NODE_TABLE_MAGIC};
     use std::collections::HashSet;
     use std::net::SocketAddr;
     use util::time::{IncrementalTime, ZeroTime};
     use util::InternetProtocol;
-    use zebra_message::common::Services;
+    use zebra_message::common::{NetAddress, Services};
+    use zebra_message::types::addr::AddressEntry;
 
     #[test]
     fn test_node_table_insert() {
@@ -492,6 +671,55 @@ mod tests {
         assert_eq!(nodes[1].failures, 0);
     }
 
+    #[test]
+    fn test_node_table_insert_seeds() {
+        let s0: SocketAddr = "127.0.0.1:8000".parse().unwrap();
+        let s1: SocketAddr = "127.0.0.1:8001".parse().unwrap();
+        let s2: SocketAddr = "127.0.0.1:8002".parse().unwrap();
+        let fixed_seeds = vec![s0];
+
+        // resolved addresses are inserted instead of fixed seeds
+        let mut table = NodeTable::<ZeroTime>::default();
+        assert_eq!(
+            table.insert_seeds(&[vec![s1, s2], vec![]], &fixed_seeds, 1),
+            1
+        );
+        assert!(!table.exists(s0));
+        assert!(table.exists(s1));
+        assert!(!table.exists(s2));
+
+        // DNS seeds have resolved nothing => fixed seeds are inserted
+        let mut table = NodeTable::<ZeroTime>::default();
+        assert_eq!(table.insert_seeds(&[vec![], vec![]], &fixed_seeds, 1), 1);
+        assert!(table.exists(s0));
+        assert_eq!(table.insert_seeds(&[], &fixed_seeds, 1), 0);
+    }
+
+    #[test]
+    fn test_node_table_insert_many_skips_unconnectable_nodes() {
+        let entry = |address: &str, port: u16| AddressEntry {
+            timestamp: 0,
+            address: NetAddress {
+                services: Services::default(),
+                address: address.into(),
+                port: port.into(),
+            },
+        };
+        let mut table = NodeTable::<ZeroTime>::default();
+        table.insert_many(vec![
+            entry("0.0.0.0", 8233),
+            entry("::", 8233),
+            entry("10.0.0.1", 0),
+            entry("10.0.0.1", 8233),
+            entry("2001:470::1", 8233),
+        ]);
+        let nodes = table.recently_active_nodes(InternetProtocol::default());
+        let addrs: HashSet<SocketAddr> = nodes.into_iter().map(|node| node.addr).collect();
+        assert_eq!(addrs.len(), 2);
+        assert!(addrs.contains(&"10.0.0.1:8233".parse().unwrap()));
+        assert!(addrs.contains(&"[2001:470::1]:8233".parse().unwrap()));
+    }
+
     #[test]
     fn test_node_table_note() {
         let s0: SocketAddr = "127.0.0.1:8000".parse().unwrap();
@@ -573,6 +801,29 @@ mod tests {
         table.note_failure(&s1);
     }
 
+    #[test]
+    fn test_node_table_feeler_candidate() {
+        let s0: SocketAddr = "127.0.0.1:8000".parse().unwrap();
+        let s1: SocketAddr = "127.0.0.1:8001".parse().unwrap();
+        let s2: SocketAddr = "127.0.0.1:8002".parse().unwrap();
+        let mut table = NodeTable::<IncrementalTime>::default();
+        assert_eq!(
+            table.feeler_candidate(InternetProtocol::default(), |_| true),
+            None
+        );
+        table.insert(s0, Services::default());
+        table.insert(s1, Services::default());
+        table.insert(s2, Services::default());
+        table.note_used(&s0);
+
+        let candidate = table.feeler_candidate(InternetProtocol::default(), |_| true);
+        assert_eq!(candidate.map(|node| node.addr), Some(s1));
+        let candidate = table.feeler_candidate(InternetProtocol::default(), |addr| *addr != s1);
+        assert_eq!(candidate.map(|node| node.addr), Some(s2));
+        let candidate = table.feeler_candidate(InternetProtocol::IpV6, |_| true);
+        assert_eq!(candidate, None);
+    }
+
     #[test]
     fn add_node() {
         let mut table = NodeTable::<ZeroTime>::default();
@@ -620,12 +871,12 @@ mod tests {
         let s1: SocketAddr = "127.0.0.1:8001".parse().unwrap();
         let s2: SocketAddr = "127.0.0.1:8002".parse().unwrap();
         let s3: SocketAddr = "127.0.0.1:8003".parse().unwrap();
-        let s4: SocketAddr = "127.0.0.1:8004".parse().unwrap();
+        let s4: SocketAddr = "[::1]:8004".parse().unwrap();
         let mut table = NodeTable::<IncrementalTime>::default();
         table.insert(s0, Services::default());
         table.insert(s1, Services::default());
         table.insert(s2, Services::default());
-        table.insert(s3, Services::default());
+        table.insert(s3, Services::default().with_network(true));
         table.insert(s4, Services::default());
         table.note_used(&s2);
         table.note_used(&s4);
@@ -634,24 +885,111 @@ mod tests {
         table.note_failure(&s3);
 
         let mut db = Vec::new();
-        assert_eq!(table.save(&mut db).unwrap(), ());
+        assert_eq!(table.write_to(&mut db, 1000).unwrap(), ());
+        assert_eq!(db.len(), NODE_TABLE_MAGIC.len() + 5 * NODE_RECORD_SIZE);
         let loaded_table =
             NodeTable::<IncrementalTime>::load(Services::default(), &db as &[u8]).unwrap();
         assert_eq!(table.by_addr, loaded_table.by_addr);
         assert_eq!(table.by_score, loaded_table.by_score);
         assert_eq!(table.by_time, loaded_table.by_time);
+    }
 
-        let s = String::from_utf8(db).unwrap();
-        assert_eq!(
-            "127.0.0.1:8001 7 0 0
-127.0.0.1:8004 6 0 0
-127.0.0.1:8000 0 0 0
+    #[test]
+    fn test_save_to_opened_file() {
+        let s0: SocketAddr = "127.0.0.1:8000".parse().unwrap();
+        let s1: SocketAddr = "127.0.0.1:8001".parse().unwrap();
+        let tempdir = TempDir::new("").unwrap();
+        let path = tempdir.path().join("nodes.dat");
+
+        let mut table = NodeTable::from_file(Services::default(), &path, 1000).unwrap();
+        assert!(table.nodes().is_empty());
+        table.insert(s0, Services::default());
+        table.insert(s1, Services::default());
+        table.save().unwrap();
+
+        let loaded_table = NodeTable::from_file(Services::default(), &path, 1000).unwrap();
+        let mut addresses: Vec<_> = loaded_table.nodes().into_iter().map(|n| n.addr).collect();
+        addresses.sort();
+        assert_eq!(addresses, vec![s0, s1]);
+
+        table.remove(&s0).unwrap();
+        table.save().unwrap();
+
+        let loaded_table = NodeTable::from_file(Services::default(), &path, 1000).unwrap();
+        let addresses: Vec<_> = loaded_table.nodes().into_iter().map(|n| n.addr).collect();
+        assert_eq!(addresses, vec![s1]);
+    }
+
+    #[test]
+    fn test_save_best_nodes_only() {
+        let s0: SocketAddr = "127.0.0.1:8000".parse().unwrap();
+        let s1: SocketAddr = "127.0.0.1:8001".parse().unwrap();
+        let s2: SocketAddr = "127.0.0.1:8002".parse().unwrap();
+        let mut table = NodeTable::<IncrementalTime>::default();
+        table.insert(s0, Services::default());
+        table.insert(s1, Services::default());
+        table.insert(s2, Services::default());
+        table.note_failure(&s1);
+
+        let mut db = Vec::new();
+        table.write_to(&mut db, 2).unwrap();
+        let loaded_table =
+            NodeTable::<IncrementalTime>::load(Services::default(), &db as &[u8]).unwrap();
+        let mut addresses: Vec<_> = loaded_table.nodes().into_iter().map(|n| n.addr).collect();
+        addresses.sort();
+        assert_eq!(addresses, vec![s0, s2]);
+    }
+
+    #[test]
+    fn test_load_skips_corrupted_records() {
+        let s0: SocketAddr = "127.0.0.1:8000".parse().unwrap();
+        let s1: SocketAddr = "127.0.0.1:8001".parse().unwrap();
+        let s2: SocketAddr = "127.0.0.1:8002".parse().unwrap();
+        let mut table = NodeTable::<IncrementalTime>::default();
+        table.insert(s0, Services::default());
+        table.insert(s1, Services::default());
+        table.insert(s2, Services::default());
+
+        let mut db = Vec::new();
+        table.write_to(&mut db, 1000).unwrap();
+        // corrupt the first record
+        db[NODE_TABLE_MAGIC.len() + 10] ^= 0xff;
+        // truncate the last record
+        db.pop();
+
+        let loaded_table =
+            NodeTable::<IncrementalTime>::load(Services::default(), &db as &[u8]).unwrap();
+        assert_eq!(loaded_table.nodes().len(), 1);
+        assert_eq!(loaded_table.by_score.len(), 1);
+        assert_eq!(loaded_table.by_time.len(), 1);
+
+        // empty source is an empty table
+        let empty_table =
+            NodeTable::<IncrementalTime>::load(Services::default(), &[] as &[u8]).unwrap();
+        assert!(empty_table.nodes().is_empty());
+
+        // unknown format is an error
+        assert!(NodeTable::<IncrementalTime>::load(Services::default(), &b"garbage"[..]).is_err());
+    }
+
+    #[test]
+    fn test_load_csv() {
+        let csv = "127.0.0.1:8001 7 0 0
+127.0.0.1:8004 6 1 0
 127.0.0.1:8002 5 0 1
-127.0.0.1:8003 3 0 1
-"
-            .to_string(),
-            s
-        );
+";
+        let table =
+            NodeTable::<IncrementalTime>::load_csv(Services::default(), csv.as_bytes()).unwrap();
+        assert_eq!(table.by_addr.len(), 3);
+        assert_eq!(table.by_score.len(), 3);
+        assert_eq!(table.by_time.len(), 3);
+
+        let node = &table.by_addr[&"127.0.0.1:8004".parse().unwrap()];
+        assert_eq!(node.time, 6);
+        assert_eq!(node.services, Services::default().with_network(true));
+        assert_eq!(node.failures, 0);
+        let node = &table.by_addr[&"127.0.0.1:8002".parse().unwrap()];
+        assert_eq!(node.failures, 1);
     }
 
     #[test]
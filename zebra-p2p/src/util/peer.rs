@@ -19,4 +19,21 @@ pub struct PeerInfo {
     pub version: u32,
     pub version_message: types::Version,
     pub magic: Magic,
+    /// Time when connection has been established.
+    pub connection_time: u32,
+}
+
+/// Information on connected peer together with traffic counters.
+#[derive(Debug, PartialEq, Clone)]
+pub struct PeerStatistics {
+    pub info: PeerInfo,
+    pub bytes_sent: u64,
+    pub bytes_received: u64,
+}
+
+/// Traffic counters of all connections, made since the process start.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct NetTotals {
+    pub bytes_sent: u64,
+    pub bytes_received: u64,
 }
@@ -1,3 +1,5 @@
+extern crate base64;
+extern crate bit_vec;
 extern crate log;
 extern crate rustc_hex as hex;
 extern crate serde;
@@ -11,8 +13,10 @@ extern crate jsonrpc_http_server;
 extern crate time;
 extern crate tokio_core;
 extern crate zebra_chain;
+extern crate zebra_crypto;
 extern crate zebra_db;
 extern crate zebra_keys;
+extern crate zebra_message;
 extern crate zebra_miner;
 extern crate zebra_network;
 extern crate zebra_p2p;
@@ -29,4 +33,7 @@ pub mod v1;
 pub use jsonrpc_core::{Compatibility, Error, MetaIoHandler};
 
 pub use jsonrpc_http_server::Server;
-pub use rpc_server::start_http;
+pub use rpc_server::{
+    start_http, Credentials, RequestFilter, RpcAuth, RpcMeta, DEFAULT_MAX_BATCH_SIZE,
+    DEFAULT_MAX_REQUEST_SIZE,
+};
@@ -1,15 +1,171 @@
 // TODO: panic handler
-use jsonrpc_core;
-use jsonrpc_http_server::{self, Host, Server, ServerBuilder};
+use base64;
+use jsonrpc_core::futures::future::{self, Either};
+use jsonrpc_core::futures::Future;
+use jsonrpc_core::{self, Call, Middleware, Output, Request, Response, Version};
+use jsonrpc_http_server::hyper::{self, header, StatusCode};
+use jsonrpc_http_server::{self, Host, RequestMiddlewareAction, Server, ServerBuilder};
+use serde_json;
+use std::collections::HashSet;
 use std::io;
 use std::net::SocketAddr;
+use std::sync::Arc;
+use v1::helpers::errors::{batch_too_large, method_not_allowed, request_too_large};
+
+/// Default limit of the request body size (in bytes).
+pub const DEFAULT_MAX_REQUEST_SIZE: usize = 5 * 1024 * 1024;
+/// Default limit of the number of calls in the single batch request.
+pub const DEFAULT_MAX_BATCH_SIZE: usize = 100;
+
+/// Metadata of the single HTTP request.
+#[derive(Debug, Clone, Default)]
+pub struct RpcMeta {
+    /// Methods that the caller is allowed to call. All methods are allowed if None.
+    pub allowed_methods: Option<Arc<HashSet<String>>>,
+}
+
+impl jsonrpc_core::Metadata for RpcMeta {}
+
+/// User name and password of the HTTP basic authentication.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Credentials {
+    pub user: String,
+    pub password: String,
+}
+
+impl Credentials {
+    /// Returns true if value of the `Authorization` header holds these credentials.
+    fn is_authorization(&self, authorization: &[u8]) -> bool {
+        let expected = format!(
+            "Basic {}",
+            base64::encode(&format!("{}:{}", self.user, self.password))
+        );
+        // the whole header is compared, so that the time doesn't depend on the matched prefix
+        expected.len() == authorization.len()
+            && expected
+                .bytes()
+                .zip(authorization.iter())
+                .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+                == 0
+    }
+}
+
+/// Access control of the RPC server. Everyone has access to all methods by default.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RpcAuth {
+    /// Credentials, required to call all methods. Requests with invalid credentials are
+    /// rejected with 401. Requests without credentials are only accepted if `public_methods`
+    /// are set.
+    pub credentials: Option<Credentials>,
+    /// Methods that could be called without credentials.
+    pub public_methods: Option<HashSet<String>>,
+}
+
+/// Result of checking request credentials.
+enum Authorization {
+    /// Caller has access to all methods.
+    Full,
+    /// Caller has access to public methods only.
+    Public,
+    /// Request must be rejected.
+    Denied,
+}
+
+impl RpcAuth {
+    fn authorize(&self, request: &hyper::Request<hyper::Body>) -> Authorization {
+        let credentials = match self.credentials {
+            Some(ref credentials) => credentials,
+            None if self.public_methods.is_some() => return Authorization::Public,
+            None => return Authorization::Full,
+        };
+
+        match request.headers().get(header::AUTHORIZATION) {
+            Some(authorization) if credentials.is_authorization(authorization.as_bytes()) => {
+                Authorization::Full
+            }
+            None if self.public_methods.is_some() => Authorization::Public,
+            _ => Authorization::Denied,
+        }
+    }
+}
+
+/// Middleware, rejecting requests before any call is dispatched if:
+/// - batch request has too many calls;
+/// - the caller is not allowed to call the method.
+#[derive(Debug, Clone, Copy)]
+pub struct RequestFilter {
+    max_batch_size: usize,
+}
+
+impl RequestFilter {
+    pub fn new(max_batch_size: usize) -> Self {
+        RequestFilter {
+            max_batch_size: max_batch_size,
+        }
+    }
+}
+
+impl Default for RequestFilter {
+    fn default() -> Self {
+        RequestFilter::new(DEFAULT_MAX_BATCH_SIZE)
+    }
+}
+
+impl Middleware<RpcMeta> for RequestFilter {
+    type Future = future::FutureResult<Option<Response>, ()>;
+    type CallFuture = future::FutureResult<Option<Output>, ()>;
+
+    fn on_request<F, X>(&self, request: Request, meta: RpcMeta, next: F) -> Either<Self::Future, X>
+    where
+        F: FnOnce(Request, RpcMeta) -> X + Send,
+        X: Future<Item = Option<Response>, Error = ()> + Send + 'static,
+    {
+        match request {
+            Request::Batch(ref calls) if calls.len() > self.max_batch_size => {
+                Either::A(future::ok(Some(Response::from(
+                    batch_too_large(self.max_batch_size),
+                    Some(Version::V2),
+                ))))
+            }
+            request => Either::B(next(request, meta)),
+        }
+    }
+
+    fn on_call<F, X>(&self, call: Call, meta: RpcMeta, next: F) -> Either<Self::CallFuture, X>
+    where
+        F: FnOnce(Call, RpcMeta) -> X + Send,
+        X: Future<Item = Option<Output>, Error = ()> + Send + 'static,
+    {
+        let is_allowed = match (&call, &meta.allowed_methods) {
+            (&Call::MethodCall(ref call), &Some(ref allowed)) => allowed.contains(&call.method),
+            (&Call::Notification(ref call), &Some(ref allowed)) => allowed.contains(&call.method),
+            _ => true,
+        };
+
+        match call {
+            Call::MethodCall(call) if !is_allowed => Either::A(future::ok(Some(Output::from(
+                method_not_allowed(&call.method),
+                call.id,
+                Some(Version::V2),
+            )))),
+            // notifications are never answered
+            Call::Notification(_) if !is_allowed => Either::A(future::ok(None)),
+            call => Either::B(next(call, meta)),
+        }
+    }
+}
 
 /// Start http server asynchronously and returns result with `Server` handle on success or an error.
-pub fn start_http<M: Default + jsonrpc_core::Metadata>(
+///
+/// Requests with body larger than `max_request_size` are rejected without reading the body.
+/// Batch size and methods, available to the caller, are limited by the `RequestFilter`.
+pub fn start_http(
     addr: &SocketAddr,
     cors_domains: Option<Vec<String>>,
     allowed_hosts: Option<Vec<String>>,
-    handler: jsonrpc_core::MetaIoHandler<M>,
+    max_request_size: usize,
+    auth: RpcAuth,
+    handler: jsonrpc_core::MetaIoHandler<RpcMeta, RequestFilter>,
 ) -> Result<Server, io::Error> {
     let cors_domains = cors_domains.map(|domains| {
         domains
@@ -22,6 +178,8 @@ pub fn start_http<M: Default + jsonrpc_core::Metadata>(
             .collect()
     });
 
+    let public_methods = auth.public_methods.clone().map(Arc::new);
+    let request_auth = auth.clone();
     ServerBuilder::new(handler)
         .cors(cors_domains.into())
         .allowed_hosts(
@@ -29,5 +187,64 @@ pub fn start_http<M: Default + jsonrpc_core::Metadata>(
                 .map(|hosts| hosts.into_iter().map(Host::from).collect())
                 .into(),
         )
+        // credentials and declared size are checked before the body is read; the server
+        // itself also stops reading bodies of unknown length once the limit is reached
+        .request_middleware(move |request: hyper::Request<hyper::Body>| {
+            if let Authorization::Denied = request_auth.authorize(&request) {
+                return unauthorized_response();
+            }
+
+            match declared_content_length(&request) {
+                Some(length) if length > max_request_size => {
+                    RequestMiddlewareAction::from(request_too_large_response(max_request_size))
+                }
+                _ => request.into(),
+            }
+        })
+        .meta_extractor(move |request: &hyper::Request<hyper::Body>| {
+            match auth.authorize(request) {
+                Authorization::Full => RpcMeta::default(),
+                // denied requests never get here, but they're restricted just in case
+                Authorization::Public | Authorization::Denied => RpcMeta {
+                    allowed_methods: Some(
+                        public_methods
+                            .clone()
+                            .unwrap_or_else(|| Arc::new(HashSet::new())),
+                    ),
+                },
+            }
+        })
+        .max_request_body_size(max_request_size)
         .start_http(addr)
 }
+
+fn declared_content_length(request: &hyper::Request<hyper::Body>) -> Option<usize> {
+    request
+        .headers()
+        .get(header::CONTENT_LENGTH)
+        .and_then(|length| length.to_str().ok())
+        .and_then(|length| length.parse().ok())
+}
+
+fn request_too_large_response(max_request_size: usize) -> jsonrpc_http_server::Response {
+    let response = Response::from(request_too_large(max_request_size), Some(Version::V2));
+    jsonrpc_http_server::Response {
+        code: StatusCode::PAYLOAD_TOO_LARGE,
+        content_type: header::HeaderValue::from_static("application/json; charset=utf-8"),
+        content: serde_json::to_string(&response)
+            .expect("serialization of JSON-RPC response never fails; qed")
+            + "\n",
+    }
+}
+
+fn unauthorized_response() -> RequestMiddlewareAction {
+    let response = hyper::Response::builder()
+        .status(StatusCode::UNAUTHORIZED)
+        .header(header::WWW_AUTHENTICATE, "Basic realm=\"jsonrpc\"")
+        .body(hyper::Body::empty())
+        .expect("status and header are valid; qed");
+    RequestMiddlewareAction::Respond {
+        should_validate_hosts: true,
+        response: Box::new(future::ok(response)),
+    }
+}
@@ -4,12 +4,23 @@ mod codes {
     // NOTE [ToDr] Codes from [-32099, -32000]
     pub const UNKNOWN: i64 = -32000;
     pub const EXECUTION_ERROR: i64 = -32015;
+    pub const METHOD_NOT_ALLOWED: i64 = -32088;
+    pub const BATCH_TOO_LARGE: i64 = -32089;
+    pub const REQUEST_TOO_LARGE: i64 = -32090;
+    pub const BLOCK_DELTAS_UNAVAILABLE: i64 = -32091;
+    pub const PRIVATE_KEY_NOT_AVAILABLE: i64 = -32092;
+    pub const ADDRESS_NOT_TRANSPARENT_KEY: i64 = -32093;
+    pub const ADDRESS_INDEX_DISABLED: i64 = -32094;
+    pub const BLOCK_OF_SIDE_BRANCH: i64 = -32095;
     pub const TRANSACTION_NOT_FOUND: i64 = -32096;
     pub const TRANSACTION_OUTPUT_NOT_FOUND: i64 = -32097;
     pub const TRANSACTION_OF_SIDE_BRANCH: i64 = -32098;
     pub const BLOCK_NOT_FOUND: i64 = -32099;
     pub const NODE_ALREADY_ADDED: i64 = -32150;
     pub const NODE_NOT_ADDED: i64 = -32151;
+    pub const SUBNET_ALREADY_BANNED: i64 = -32152;
+    pub const SUBNET_NOT_BANNED: i64 = -32153;
+    pub const NODE_NOT_CONNECTED: i64 = -32154;
 }
 
 use jsonrpc_core::{Error, ErrorCode, Value};
@@ -62,6 +73,14 @@ pub fn block_at_height_not_found<T: fmt::Debug>(data: T) -> Error {
     }
 }
 
+pub fn block_of_side_branch<T: fmt::Debug>(data: T) -> Error {
+    Error {
+        code: ErrorCode::ServerError(codes::BLOCK_OF_SIDE_BRANCH),
+        message: "Block is of side branch".into(),
+        data: Some(Value::String(format!("{:?}", data))),
+    }
+}
+
 pub fn transaction_not_found<T: fmt::Debug>(data: T) -> Error {
     Error {
         code: ErrorCode::ServerError(codes::TRANSACTION_NOT_FOUND),
@@ -86,6 +105,62 @@ pub fn transaction_of_side_branch<T: fmt::Debug>(data: T) -> Error {
     }
 }
 
+pub fn address_not_transparent_key<T: fmt::Debug>(data: T) -> Error {
+    Error {
+        code: ErrorCode::ServerError(codes::ADDRESS_NOT_TRANSPARENT_KEY),
+        message: "Address does not refer to transparent key".into(),
+        data: Some(Value::String(format!("{:?}", data))),
+    }
+}
+
+pub fn private_key_not_available<T: fmt::Debug>(data: T) -> Error {
+    Error {
+        code: ErrorCode::ServerError(codes::PRIVATE_KEY_NOT_AVAILABLE),
+        message: "Private key for the address is not available".into(),
+        data: Some(Value::String(format!("{:?}", data))),
+    }
+}
+
+pub fn address_index_disabled() -> Error {
+    Error {
+        code: ErrorCode::ServerError(codes::ADDRESS_INDEX_DISABLED),
+        message: "Address index is disabled".into(),
+        data: None,
+    }
+}
+
+pub fn block_deltas_unavailable() -> Error {
+    Error {
+        code: ErrorCode::ServerError(codes::BLOCK_DELTAS_UNAVAILABLE),
+        message: "Block or outputs it spends are below the prune height".into(),
+        data: None,
+    }
+}
+
+pub fn request_too_large(max_request_size: usize) -> Error {
+    Error {
+        code: ErrorCode::ServerError(codes::REQUEST_TOO_LARGE),
+        message: "Request body exceeds allowed maximum size".into(),
+        data: Some(Value::Number(max_request_size.into())),
+    }
+}
+
+pub fn batch_too_large(max_batch_size: usize) -> Error {
+    Error {
+        code: ErrorCode::ServerError(codes::BATCH_TOO_LARGE),
+        message: "Batch request exceeds allowed maximum number of calls".into(),
+        data: Some(Value::Number(max_batch_size.into())),
+    }
+}
+
+pub fn method_not_allowed<T: fmt::Debug>(data: T) -> Error {
+    Error {
+        code: ErrorCode::ServerError(codes::METHOD_NOT_ALLOWED),
+        message: "Method is not allowed without credentials".into(),
+        data: Some(Value::String(format!("{:?}", data))),
+    }
+}
+
 pub fn node_already_added() -> Error {
     Error {
         code: ErrorCode::ServerError(codes::NODE_ALREADY_ADDED),
@@ -102,6 +177,30 @@ pub fn node_not_added() -> Error {
     }
 }
 
+pub fn node_not_connected() -> Error {
+    Error {
+        code: ErrorCode::ServerError(codes::NODE_NOT_CONNECTED),
+        message: "Node is not connected".into(),
+        data: None,
+    }
+}
+
+pub fn subnet_already_banned() -> Error {
+    Error {
+        code: ErrorCode::ServerError(codes::SUBNET_ALREADY_BANNED),
+        message: "Subnet is already banned".into(),
+        data: None,
+    }
+}
+
+pub fn subnet_not_banned() -> Error {
+    Error {
+        code: ErrorCode::ServerError(codes::SUBNET_NOT_BANNED),
+        message: "Subnet is not banned".into(),
+        data: None,
+    }
+}
+
 pub fn unknown() -> Error {
     Error {
         code: ErrorCode::ServerError(codes::UNKNOWN),
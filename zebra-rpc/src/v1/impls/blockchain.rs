@@ -1,22 +1,69 @@
+use bit_vec::BitVec;
 use global_script::Script;
 use jsonrpc_core::Error;
 use ser::serialize;
+use std::collections::HashSet;
 use v1::helpers::errors::{
-    block_at_height_not_found, block_not_found, invalid_params, transaction_not_found,
+    address_index_disabled, block_at_height_not_found, block_deltas_unavailable, block_not_found,
+    block_of_side_branch, execution, invalid_params, transaction_not_found,
     transaction_of_side_branch, transaction_output_not_found,
 };
+use v1::impls::raw::{reverse_transaction_hashes, RawClientCore};
 use v1::traits::BlockChain;
+use v1::types::AddressUtxo;
+use v1::types::GetAddressBalanceResponse;
+use v1::types::GetBlockStatsResponse;
+use v1::types::GetChainTxStatsResponse;
 use v1::types::GetTxOutSetInfoResponse;
-use v1::types::H256;
 use v1::types::{BlockRef, GetBlockResponse, RawBlock, VerboseBlock};
-use v1::types::{GetTxOutResponse, TransactionOutputScript};
-use zebra_chain::OutPoint;
+use v1::types::{Bytes, H256};
+use v1::types::{CommitmentTreeState, GetTreeStateResponse};
+use v1::types::{DbColumnInfo, GetDbInfoResponse};
+use v1::types::{GetBlockChainInfoResponse, NetworkUpgradeInfo, NetworkUpgradeStatus};
+use v1::types::{GetBlockDeltasResponse, InputDelta, OutputDelta, TransactionDeltas};
+use v1::types::{GetTxOutResponse, Transaction as VerboseTransaction, TransactionOutputScript};
+use zebra_chain::{IndexedBlock, OutPoint, Transaction};
 use zebra_keys::{self, Address};
-use zebra_network::{ConsensusParams, Network};
+use zebra_message::{deserialize_payload, serialize_payload, types, Payload};
+use zebra_miner;
+use zebra_network::{ConsensusParams, Network, NetworkUpgrade};
+use zebra_primitives::bytes::Bytes as GlobalBytes;
 use zebra_primitives::hash::H256 as GlobalH256;
-use zebra_storage;
+use zebra_storage::{
+    self, DuplexTransactionOutputProvider, EpochRef, EpochTag, IndexedAddress,
+    TransactionOutputProvider,
+};
+use zebra_sync;
 use zebra_verification;
 
+/// Default `verifychain` check level.
+const DEFAULT_CHECK_LEVEL: u32 = 3;
+/// Maximal `verifychain` check level.
+const MAX_CHECK_LEVEL: u32 = 4;
+/// Default number of best blocks, checked by `verifychain`.
+const DEFAULT_CHECK_BLOCKS: u32 = 288;
+/// Number of blocks, scanned around `getblockhashes` search boundaries.
+/// Block timestamp must only be greater than median time of last 11 blocks => timestamps are not monotonic.
+const BLOCK_TIME_SEARCH_WINDOW: u32 = 11;
+/// Statistics, which could be requested by `getblockstats`.
+const BLOCK_STATS: &'static [&'static str] = &[
+    "total_size",
+    "txs",
+    "total_out",
+    "totalfee",
+    "avgfee",
+    "minfee",
+    "maxfee",
+    "avgfeerate",
+    "ins",
+    "outs",
+    "subsidy",
+    "total_sigops",
+];
+/// Statistics, which require previous outputs to be read from the database.
+const BLOCK_FEE_STATS: &'static [&'static str] =
+    &["totalfee", "avgfee", "minfee", "maxfee", "avgfeerate"];
+
 pub struct BlockChainClient<T: BlockChainClientCoreApi> {
     core: T,
 }
@@ -25,61 +72,116 @@ pub trait BlockChainClientCoreApi: Send + Sync + 'static {
     fn best_block_hash(&self) -> GlobalH256;
     fn block_count(&self) -> u32;
     fn block_hash(&self, height: u32) -> Option<GlobalH256>;
+    fn block_height(&self, hash: GlobalH256) -> Result<u32, Error>;
+    fn block_hashes(&self, high_time: u32, low_time: u32) -> Vec<GlobalH256>;
     fn difficulty(&self) -> f64;
+    fn next_work_required(&self) -> u32;
     fn raw_block(&self, hash: GlobalH256) -> Option<RawBlock>;
     fn verbose_block(&self, hash: GlobalH256) -> Option<VerboseBlock>;
-    fn verbose_transaction_out(&self, prev_out: OutPoint) -> Result<GetTxOutResponse, Error>;
+    fn verbose_block_with_transactions(
+        &self,
+        hash: GlobalH256,
+    ) -> Option<VerboseBlock<VerboseTransaction>>;
+    fn verbose_transaction_out(
+        &self,
+        prev_out: OutPoint,
+        include_mempool: bool,
+    ) -> Result<GetTxOutResponse, Error>;
+    fn verify_chain(&self, check_level: u32, blocks: u32) -> bool;
+    fn transaction_out_proof(
+        &self,
+        txids: &[GlobalH256],
+        block_hash: Option<GlobalH256>,
+    ) -> Result<GlobalBytes, Error>;
+    fn verify_transaction_out_proof(&self, proof: &[u8]) -> Result<Vec<GlobalH256>, Error>;
+
+    fn db_info(&self) -> GetDbInfoResponse;
+    fn block_stats(&self, hash: GlobalH256, stats: &[&str])
+        -> Result<GetBlockStatsResponse, Error>;
+    fn chain_tx_stats(
+        &self,
+        hash: GlobalH256,
+        block_count: u32,
+    ) -> Result<GetChainTxStatsResponse, Error>;
+    fn address_utxos(&self, addresses: &[Address]) -> Result<Vec<AddressUtxo>, Error>;
+    fn address_balance(&self, addresses: &[Address]) -> Result<GetAddressBalanceResponse, Error>;
+    fn address_txids(
+        &self,
+        addresses: &[Address],
+        start: u32,
+        end: u32,
+    ) -> Result<Vec<GlobalH256>, Error>;
+    fn contains_nullifier(&self, nullifier: EpochRef) -> bool;
+    fn tree_state(&self, hash: GlobalH256) -> Option<GetTreeStateResponse>;
+    fn blockchain_info(&self) -> GetBlockChainInfoResponse;
+    fn block_deltas(&self, hash: GlobalH256) -> Result<GetBlockDeltasResponse, Error>;
 }
 
 pub struct BlockChainClientCore {
     consensus: ConsensusParams,
     storage: zebra_storage::SharedStore,
+    memory_pool: zebra_sync::MemoryPoolRef,
+    sync_state: zebra_sync::SynchronizationStateRef,
 }
 
 impl BlockChainClientCore {
-    pub fn new(consensus: ConsensusParams, storage: zebra_storage::SharedStore) -> Self {
+    pub fn new(
+        consensus: ConsensusParams,
+        storage: zebra_storage::SharedStore,
+        memory_pool: zebra_sync::MemoryPoolRef,
+        sync_state: zebra_sync::SynchronizationStateRef,
+    ) -> Self {
         BlockChainClientCore {
             consensus: consensus,
             storage: storage,
+            memory_pool: memory_pool,
+            sync_state: sync_state,
         }
     }
-}
-
-impl BlockChainClientCoreApi for BlockChainClientCore {
-    fn best_block_hash(&self) -> GlobalH256 {
-        self.storage.best_block().hash
-    }
-
-    fn block_count(&self) -> u32 {
-        self.storage.best_block().number
-    }
 
-    fn block_hash(&self, height: u32) -> Option<GlobalH256> {
-        self.storage.block_hash(height)
+    /// Binary search for the first canonical block with timestamp >= `time`.
+    /// Block timestamps are assumed to be increasing here, so the result is only approximate.
+    fn first_block_not_older_than(&self, time: u32, best_height: u32) -> u32 {
+        let (mut low, mut high) = (0, best_height.saturating_add(1));
+        while low < high {
+            let middle = low + (high - low) / 2;
+            match self.storage.block_header(middle.into()) {
+                Some(ref header) if header.raw.time < time => low = middle + 1,
+                _ => high = middle,
+            }
+        }
+        low
     }
 
-    fn difficulty(&self) -> f64 {
-        let best_block = self.storage.best_block();
-        let now = ::time::get_time().sec as u32;
-
-        let next_work_required = zebra_verification::work_required(
-            best_block.hash,
-            now,
-            best_block.number + 1,
-            self.storage.as_block_header_provider(),
-            &self.consensus,
-        );
+    /// Difficulty of the best block of the `storage`, relative to the genesis difficulty.
+    fn best_block_difficulty(&self, storage: &zebra_storage::Store) -> f64 {
+        let genesis_bits = storage
+            .block_header(0.into())
+            .map(|header| header.raw.bits)
+            .unwrap_or_else(|| self.consensus.network.max_bits().into());
+        let best_bits = storage
+            .block_header(storage.best_block().hash.into())
+            .map(|header| header.raw.bits)
+            .unwrap_or(genesis_bits);
 
-        next_work_required.to_f64(self.consensus.network.max_bits().into())
+        best_bits.to_f64(genesis_bits)
     }
 
-    fn raw_block(&self, hash: GlobalH256) -> Option<RawBlock> {
-        self.storage
-            .block(hash.into())
-            .map(|block| serialize(&block.to_raw_block()).into())
+    /// Network of addresses, displayed to the user.
+    fn keys_network(&self) -> zebra_keys::Network {
+        match self.consensus.network {
+            Network::Mainnet => zebra_keys::Network::Mainnet,
+            // there's no correct choices for Regtests && Other networks
+            // => let's just make Testnet key
+            _ => zebra_keys::Network::Testnet,
+        }
     }
 
-    fn verbose_block(&self, hash: GlobalH256) -> Option<VerboseBlock> {
+    /// Returns verbose information on block, with transactions converted by `transactions`.
+    fn do_verbose_block<T, F>(&self, hash: GlobalH256, transactions: F) -> Option<VerboseBlock<T>>
+    where
+        F: FnOnce(&IndexedBlock, Option<u32>) -> Vec<T>,
+    {
         self.storage.block(hash.into()).map(|block| {
             let height = self.storage.block_number(block.hash());
             let confirmations = match height {
@@ -106,17 +208,220 @@ impl BlockChainClientCoreApi for BlockChainClientCore {
                 finalsaplingroot: block.header.raw.final_sapling_root.into(),
                 nonce: block.header.raw.nonce.clone().into(),
                 time: block.header.raw.time,
-                tx: block
-                    .transactions
+                tx: transactions(&block, height),
+                version: block.header.raw.version,
+            }
+        })
+    }
+
+    fn transaction_out_response(
+        &self,
+        bestblock: GlobalH256,
+        confirmations: u32,
+        transaction: &Transaction,
+        index: usize,
+    ) -> GetTxOutResponse {
+        let ref script_bytes = transaction.outputs[index].script_pubkey;
+        let script: Script = script_bytes.clone().into();
+        let script_asm = script.to_asm_string();
+        let script_addresses = script.extract_destinations().unwrap_or(vec![]);
+
+        GetTxOutResponse {
+            bestblock: bestblock.into(),
+            confirmations: confirmations,
+            value: 0.00000001f64 * (transaction.outputs[index].value as f64),
+            script: TransactionOutputScript {
+                asm: script_asm,
+                hex: script_bytes.clone().into(),
+                req_sigs: script.num_signatures_required() as u32,
+                script_type: script.script_type().into(),
+                addresses: script_addresses
                     .into_iter()
-                    .map(|t| t.hash.into())
+                    .map(|a| Address {
+                        network: self.keys_network(),
+                        hash: a.hash,
+                        kind: a.kind,
+                    })
                     .collect(),
-                version: block.header.raw.version,
+            },
+            version: transaction.version,
+            coinbase: transaction.is_coinbase(),
+        }
+    }
+}
+
+impl BlockChainClientCoreApi for BlockChainClientCore {
+    fn best_block_hash(&self) -> GlobalH256 {
+        self.storage.best_block().hash
+    }
+
+    fn block_count(&self) -> u32 {
+        self.storage.best_block().number
+    }
+
+    fn block_hash(&self, height: u32) -> Option<GlobalH256> {
+        self.storage.block_hash(height)
+    }
+
+    fn block_height(&self, hash: GlobalH256) -> Result<u32, Error> {
+        match self.storage.block_number(&hash) {
+            Some(height) => Ok(height),
+            None if self.storage.contains_block(hash.clone().into()) => {
+                Err(block_of_side_branch(hash.reversed()))
             }
+            None => Err(block_not_found(hash.reversed())),
+        }
+    }
+
+    fn block_hashes(&self, high_time: u32, low_time: u32) -> Vec<GlobalH256> {
+        let best_height = self.storage.best_block().number;
+        let first = self
+            .first_block_not_older_than(low_time, best_height)
+            .saturating_sub(BLOCK_TIME_SEARCH_WINDOW);
+        let last = ::std::cmp::min(
+            best_height,
+            self.first_block_not_older_than(high_time.saturating_add(1), best_height)
+                .saturating_add(BLOCK_TIME_SEARCH_WINDOW),
+        );
+
+        (first..last.saturating_add(1))
+            .filter_map(|height| self.storage.block_header(height.into()))
+            .filter(|header| header.raw.time >= low_time && header.raw.time <= high_time)
+            .map(|header| header.hash)
+            .collect()
+    }
+
+    fn difficulty(&self) -> f64 {
+        self.best_block_difficulty(&*self.storage.snapshot())
+    }
+
+    fn next_work_required(&self) -> u32 {
+        let best_block = self.storage.best_block();
+        let now = ::time::get_time().sec as u32;
+
+        zebra_verification::work_required(
+            best_block.hash,
+            now,
+            best_block.number + 1,
+            self.storage.as_block_header_provider(),
+            &self.consensus,
+        )
+        .into()
+    }
+
+    fn raw_block(&self, hash: GlobalH256) -> Option<RawBlock> {
+        self.storage
+            .block(hash.into())
+            .map(|block| serialize(&block.to_raw_block()).into())
+    }
+
+    fn verbose_block(&self, hash: GlobalH256) -> Option<VerboseBlock> {
+        self.do_verbose_block(hash, |block, _| {
+            block
+                .transactions
+                .iter()
+                .map(|t| t.hash.clone().into())
+                .collect()
+        })
+    }
+
+    fn verbose_block_with_transactions(
+        &self,
+        hash: GlobalH256,
+    ) -> Option<VerboseBlock<VerboseTransaction>> {
+        let network = self.keys_network();
+        self.do_verbose_block(hash, |block, height| {
+            // spent outputs are only resolved for canonical blocks, where meta of
+            // previous transactions is available
+            let spent_outputs = match height {
+                Some(_) => {
+                    let prevouts = block
+                        .transactions
+                        .iter()
+                        .filter(|tx| !tx.raw.is_coinbase())
+                        .flat_map(|tx| tx.raw.inputs.iter())
+                        .map(|input| input.previous_output.clone())
+                        .collect::<Vec<_>>();
+                    self.storage
+                        .as_transaction_output_provider()
+                        .transaction_outputs(&prevouts)
+                }
+                None => Vec::new(),
+            };
+            let mut spent_outputs = spent_outputs.into_iter();
+            let confirmations = match height {
+                Some(height) => (self.storage.best_block().number - height + 1) as i64,
+                // block is in the side chain
+                None => -1,
+            };
+
+            block
+                .transactions
+                .iter()
+                .map(|tx| {
+                    let mut transaction = RawClientCore::do_decode_transaction(tx, network);
+                    if height.is_some() && !tx.raw.is_coinbase() {
+                        for input in &mut transaction.vin {
+                            let spent_output = match spent_outputs.next() {
+                                Some(Some(spent_output)) => spent_output,
+                                _ => continue,
+                            };
+                            let script: Script = spent_output.script_pubkey.into();
+                            input.value = Some(0.00000001f64 * (spent_output.value as f64));
+                            input.addresses = script
+                                .extract_destinations()
+                                .unwrap_or(vec![])
+                                .into_iter()
+                                .map(|a| Address {
+                                    network: network,
+                                    hash: a.hash,
+                                    kind: a.kind,
+                                })
+                                .collect();
+                        }
+                    }
+                    transaction.blockhash = Some(block.hash().clone().into());
+                    transaction.height = height;
+                    transaction.confirmations = Some(confirmations);
+                    transaction.time = Some(block.header.raw.time);
+                    transaction.blocktime = Some(block.header.raw.time);
+                    transaction
+                })
+                .collect()
         })
     }
 
-    fn verbose_transaction_out(&self, prev_out: OutPoint) -> Result<GetTxOutResponse, Error> {
+    fn verbose_transaction_out(
+        &self,
+        prev_out: OutPoint,
+        include_mempool: bool,
+    ) -> Result<GetTxOutResponse, Error> {
+        if include_mempool {
+            let memory_pool = self.memory_pool.read();
+            let provider = DuplexTransactionOutputProvider::new(
+                &*memory_pool,
+                self.storage.as_transaction_output_provider(),
+            );
+            // output is spent by memory pool transaction
+            if provider.is_spent(&prev_out) {
+                return Err(transaction_output_not_found(prev_out));
+            }
+
+            // output of memory pool transaction
+            if let Some(transaction) = memory_pool.read_by_hash(&prev_out.hash) {
+                if prev_out.index >= transaction.outputs.len() as u32 {
+                    return Err(transaction_output_not_found(prev_out));
+                }
+
+                return Ok(self.transaction_out_response(
+                    self.storage.best_block().hash,
+                    0,
+                    transaction,
+                    prev_out.index as usize,
+                ));
+            }
+        }
+
         let transaction = match self.storage.transaction(&prev_out.hash) {
             Some(transaction) => transaction,
             // no transaction => no response
@@ -145,36 +450,561 @@ impl BlockChainClientCoreApi for BlockChainClientCore {
             return Err(transaction_not_found(prev_out.hash));
         }
 
-        let ref script_bytes = transaction.raw.outputs[prev_out.index as usize].script_pubkey;
-        let script: Script = script_bytes.clone().into();
-        let script_asm = format!("{}", script);
-        let script_addresses = script.extract_destinations().unwrap_or(vec![]);
+        Ok(self.transaction_out_response(
+            block_header.hash,
+            best_block.number - meta.height() + 1,
+            &transaction.raw,
+            prev_out.index as usize,
+        ))
+    }
+    fn verify_chain(&self, check_level: u32, blocks: u32) -> bool {
+        zebra_verification::BackwardsCompatibleChainVerifier::new(
+            self.storage.clone(),
+            self.consensus.clone(),
+        )
+        .verify_stored_chain(check_level, blocks)
+        .is_ok()
+    }
 
-        Ok(GetTxOutResponse {
-            bestblock: block_header.hash.into(),
-            confirmations: best_block.number - meta.height() + 1,
-            value: 0.00000001f64 * (transaction.raw.outputs[prev_out.index as usize].value as f64),
-            script: TransactionOutputScript {
-                asm: script_asm,
-                hex: script_bytes.clone().into(),
-                req_sigs: script.num_signatures_required() as u32,
-                script_type: script.script_type().into(),
-                addresses: script_addresses
-                    .into_iter()
-                    .map(|a| Address {
-                        network: match self.consensus.network {
-                            Network::Mainnet => zebra_keys::Network::Mainnet,
-                            // there's no correct choices for Regtests && Other networks
-                            // => let's just make Testnet key
-                            _ => zebra_keys::Network::Testnet,
-                        },
-                        hash: a.hash,
-                        kind: a.kind,
+    fn transaction_out_proof(
+        &self,
+        txids: &[GlobalH256],
+        block_hash: Option<GlobalH256>,
+    ) -> Result<GlobalBytes, Error> {
+        let block = match block_hash {
+            Some(block_hash) => match self.storage.block(block_hash.clone().into()) {
+                Some(block) => block,
+                None => return Err(block_not_found(block_hash.reversed())),
+            },
+            None => {
+                // locate block using the transactions index
+                let mut block_height = None;
+                for txid in txids {
+                    let height = match self.storage.transaction_meta(txid) {
+                        Some(meta) => meta.height(),
+                        None => return Err(transaction_not_found(txid.reversed())),
+                    };
+                    match block_height {
+                        Some(block_height) if block_height != height => {
+                            return Err(invalid_params(
+                                "txids",
+                                "transactions are included in different blocks",
+                            ))
+                        }
+                        _ => block_height = Some(height),
+                    }
+                }
+
+                let block_height = block_height.unwrap_or_default();
+                match self.storage.block(block_height.into()) {
+                    Some(block) => block,
+                    None => return Err(block_at_height_not_found(block_height)),
+                }
+            }
+        };
+
+        let block_txids: HashSet<_> = block.transactions.iter().map(|tx| &tx.hash).collect();
+        if let Some(txid) = txids.iter().find(|txid| !block_txids.contains(txid)) {
+            return Err(transaction_not_found(txid.reversed()));
+        }
+
+        let matches: BitVec = block
+            .transactions
+            .iter()
+            .map(|tx| txids.contains(&tx.hash))
+            .collect();
+        let merkle_block = zebra_sync::build_merkle_block(&block, matches);
+        serialize_payload(&merkle_block, types::MerkleBlock::version()).map_err(execution)
+    }
+
+    fn verify_transaction_out_proof(&self, proof: &[u8]) -> Result<Vec<GlobalH256>, Error> {
+        let merkle_block: types::MerkleBlock =
+            deserialize_payload(proof, types::MerkleBlock::version())
+                .map_err(|err| invalid_params("proof", err))?;
+        let parsed_tree = zebra_sync::parse_merkle_block(&merkle_block)
+            .map_err(|err| invalid_params("proof", err))?;
+        if parsed_tree.root != merkle_block.block_header.merkle_root_hash {
+            return Ok(Vec::new());
+        }
+
+        // proven block must be the part of the canonical chain
+        self.block_height(merkle_block.block_header.hash())?;
+        Ok(parsed_tree.hashes)
+    }
+
+    fn db_info(&self) -> GetDbInfoResponse {
+        GetDbInfoResponse {
+            columns: self
+                .storage
+                .column_stats()
+                .into_iter()
+                .map(|stat| DbColumnInfo {
+                    column: stat.col,
+                    keys: stat.approx_keys,
+                    bytes: stat.approx_bytes,
+                })
+                .collect(),
+        }
+    }
+
+    fn block_stats(
+        &self,
+        hash: GlobalH256,
+        stats: &[&str],
+    ) -> Result<GetBlockStatsResponse, Error> {
+        let height = self.block_height(hash.clone())?;
+        let block = match self.storage.block(hash.clone().into()) {
+            Some(block) => block,
+            // this is possible during reorgs
+            None => return Err(block_not_found(hash.reversed())),
+        };
+
+        let wants = |stat: &str| stats.iter().any(|s| *s == stat);
+        let store = self.storage.as_transaction_output_provider();
+        // coinbase transaction is excluded from most of statistics
+        let transactions = || block.transactions.iter().skip(1).map(|tx| &tx.raw);
+
+        let mut response = GetBlockStatsResponse::default();
+        let total_size: u64 = transactions().map(|tx| tx.total_size() as u64).sum();
+        if wants("total_size") {
+            response.total_size = Some(total_size);
+        }
+        if wants("txs") {
+            response.txs = Some(block.transactions.len() as u64);
+        }
+        if wants("total_out") {
+            response.total_out = Some(transactions().map(|tx| tx.total_spends()).sum());
+        }
+        if BLOCK_FEE_STATS.iter().any(|stat| wants(stat)) {
+            // fee of transaction, spending unknown (e.g. pruned) output, can't be computed
+            let mut fees = Vec::with_capacity(block.transactions.len());
+            for tx in transactions() {
+                let fee = zebra_verification::checked_transaction_fee(store, ::std::usize::MAX, tx)
+                    .map_err(|err| match err {
+                        zebra_verification::TransactionError::Input(input_idx) => {
+                            transaction_output_not_found(
+                                tx.inputs[input_idx].previous_output.clone(),
+                            )
+                        }
+                        err => execution(err),
+                    })?;
+                fees.push(fee);
+            }
+            let total_fee: u64 = fees.iter().sum();
+            if wants("totalfee") {
+                response.totalfee = Some(total_fee);
+            }
+            if wants("avgfee") {
+                response.avgfee = Some(total_fee / ::std::cmp::max(fees.len(), 1) as u64);
+            }
+            if wants("minfee") {
+                response.minfee = Some(fees.iter().cloned().min().unwrap_or_default());
+            }
+            if wants("maxfee") {
+                response.maxfee = Some(fees.iter().cloned().max().unwrap_or_default());
+            }
+            if wants("avgfeerate") {
+                response.avgfeerate = Some(total_fee / ::std::cmp::max(total_size, 1));
+            }
+        }
+        if wants("ins") {
+            response.ins = Some(transactions().map(|tx| tx.inputs.len() as u64).sum());
+        }
+        if wants("outs") {
+            response.outs = Some(
+                block
+                    .transactions
+                    .iter()
+                    .map(|tx| tx.raw.outputs.len() as u64)
+                    .sum(),
+            );
+        }
+        if wants("subsidy") {
+            response.subsidy = Some(self.consensus.block_reward(height));
+        }
+        if wants("total_sigops") {
+            let bip16_active = block.header.raw.time >= self.consensus.bip16_time;
+            response.total_sigops = Some(
+                block
+                    .transactions
+                    .iter()
+                    .map(|tx| {
+                        zebra_verification::transaction_sigops(&tx.raw, store, bip16_active) as u64
                     })
-                    .collect(),
+                    .sum(),
+            );
+        }
+
+        Ok(response)
+    }
+
+    fn chain_tx_stats(
+        &self,
+        hash: GlobalH256,
+        block_count: u32,
+    ) -> Result<GetChainTxStatsResponse, Error> {
+        let height = self.block_height(hash.clone())?;
+        if block_count > 0 && block_count >= height {
+            return Err(invalid_params("nblocks", block_count));
+        }
+
+        let header = match self.storage.block_header(hash.clone().into()) {
+            Some(header) => header,
+            // this is possible during reorgs
+            None => return Err(block_not_found(hash.reversed())),
+        };
+        // the counter is missing for pruned blocks, canonized by previous versions of the database
+        let chain_tx_count = |hash: &GlobalH256| {
+            self.storage
+                .chain_tx_count(hash.clone().into())
+                .ok_or(execution(format!(
+                    "Transactions counter is not available for block {}",
+                    hash.reversed()
+                )))
+        };
+        let tx_count = chain_tx_count(&hash)?;
+
+        let mut response = GetChainTxStatsResponse {
+            time: header.raw.time,
+            txcount: tx_count,
+            window_final_block_hash: hash.reversed().into(),
+            window_block_count: block_count,
+            ..Default::default()
+        };
+        if block_count > 0 {
+            let window_start = self
+                .storage
+                .block_hash(height - block_count)
+                .ok_or(block_at_height_not_found(height - block_count))?;
+            let window_tx_count = tx_count - chain_tx_count(&window_start)?;
+            let store = self.storage.as_block_header_provider();
+            let window_interval =
+                zebra_verification::median_timestamp_inclusive(hash, store).saturating_sub(
+                    zebra_verification::median_timestamp_inclusive(window_start, store),
+                );
+
+            response.window_tx_count = Some(window_tx_count);
+            response.window_interval = Some(window_interval);
+            if window_interval > 0 {
+                response.txrate = Some(window_tx_count as f64 / window_interval as f64);
+            }
+        }
+
+        Ok(response)
+    }
+
+    fn address_utxos(&self, addresses: &[Address]) -> Result<Vec<AddressUtxo>, Error> {
+        if !self.storage.is_address_index_enabled() {
+            return Err(address_index_disabled());
+        }
+
+        // index and outputs are read from the snapshot, so that reorg can't be observed halfway
+        let storage = self.storage.snapshot();
+        let mut utxos = Vec::new();
+        for address in addresses {
+            for output in storage.address_outputs(&indexed_address(address), 0, u32::max_value()) {
+                let is_unspent = storage
+                    .transaction_meta(&output.outpoint.hash)
+                    .and_then(|meta| meta.is_spent(output.outpoint.index as usize))
+                    == Some(false);
+                if !is_unspent {
+                    continue;
+                }
+
+                let script = match storage.transaction_output(&output.outpoint, usize::max_value())
+                {
+                    Some(transaction_output) => transaction_output.script_pubkey,
+                    None => continue,
+                };
+
+                utxos.push(AddressUtxo {
+                    address: address.to_string(),
+                    txid: output.outpoint.hash.into(),
+                    output_index: output.outpoint.index,
+                    script: script.into(),
+                    satoshis: output.value,
+                    height: output.height,
+                });
+            }
+        }
+
+        Ok(utxos)
+    }
+
+    fn address_balance(&self, addresses: &[Address]) -> Result<GetAddressBalanceResponse, Error> {
+        if !self.storage.is_address_index_enabled() {
+            return Err(address_index_disabled());
+        }
+
+        let storage = self.storage.snapshot();
+        let mut received = 0u64;
+        let mut spent = 0u64;
+        for address in addresses {
+            let indexed_address = indexed_address(address);
+            received += storage
+                .address_outputs(&indexed_address, 0, u32::max_value())
+                .iter()
+                .map(|output| output.value)
+                .sum::<u64>();
+            spent += storage
+                .address_spends(&indexed_address, 0, u32::max_value())
+                .iter()
+                .map(|spend| spend.value)
+                .sum::<u64>();
+        }
+
+        Ok(GetAddressBalanceResponse {
+            balance: received.saturating_sub(spent),
+            received: received,
+        })
+    }
+
+    fn address_txids(
+        &self,
+        addresses: &[Address],
+        start: u32,
+        end: u32,
+    ) -> Result<Vec<GlobalH256>, Error> {
+        if !self.storage.is_address_index_enabled() {
+            return Err(address_index_disabled());
+        }
+
+        // index entries are keyed by height => only entries within the range are read
+        let storage = self.storage.snapshot();
+        let mut txids: Vec<(u32, GlobalH256)> = Vec::new();
+        for address in addresses {
+            let indexed_address = indexed_address(address);
+            txids.extend(
+                storage
+                    .address_outputs(&indexed_address, start, end)
+                    .into_iter()
+                    .map(|output| (output.height, output.outpoint.hash)),
+            );
+            txids.extend(
+                storage
+                    .address_spends(&indexed_address, start, end)
+                    .into_iter()
+                    .map(|spend| (spend.height, spend.txid)),
+            );
+        }
+
+        txids.sort_by_key(|&(height, _)| height);
+        let mut unique = HashSet::new();
+        Ok(txids
+            .into_iter()
+            .map(|(_, txid)| txid)
+            .filter(|txid| unique.insert(txid.clone()))
+            .collect())
+    }
+
+    fn contains_nullifier(&self, nullifier: EpochRef) -> bool {
+        self.storage.contains_nullifier(nullifier)
+    }
+
+    fn tree_state(&self, hash: GlobalH256) -> Option<GetTreeStateResponse> {
+        let sprout_root = match self.storage.sprout_block_root(&hash) {
+            Some(sprout_root) => sprout_root,
+            None => return None,
+        };
+        let sapling_root = match self.storage.sapling_block_root(&hash) {
+            Some(sapling_root) => sapling_root,
+            None => return None,
+        };
+        let sprout_tree = match self.storage.sprout_tree_at(&sprout_root) {
+            Some(sprout_tree) => sprout_tree,
+            None => return None,
+        };
+        let sapling_tree = match self.storage.sapling_tree_at(&sapling_root) {
+            Some(sapling_tree) => sapling_tree,
+            None => return None,
+        };
+
+        Some(GetTreeStateResponse {
+            hash: hash.reversed().into(),
+            height: self.storage.block_number(&hash),
+            sprout: CommitmentTreeState {
+                finalroot: sprout_root.reversed().into(),
+                finalstate: serialize(&sprout_tree).into(),
             },
-            version: transaction.raw.version,
-            coinbase: transaction.raw.is_coinbase(),
+            sapling: CommitmentTreeState {
+                finalroot: sapling_root.reversed().into(),
+                finalstate: serialize(&sapling_tree).into(),
+            },
+        })
+    }
+
+    fn blockchain_info(&self) -> GetBlockChainInfoResponse {
+        // all fields are read from the snapshot, so that they're describing the same best block
+        let storage = self.storage.snapshot();
+        let best_block = storage.best_block();
+        let best_time = storage.best_header().raw.time;
+        let genesis_time = storage
+            .block_header(0.into())
+            .map(|header| header.raw.time)
+            .unwrap_or(best_time);
+        let now = ::time::get_time().sec as u32;
+        // it is assumed that blocks are mined at the constant rate since genesis
+        let verification_progress = if now > genesis_time {
+            (best_time.saturating_sub(genesis_time) as f64 / (now - genesis_time) as f64).min(1.0)
+        } else {
+            1.0
+        };
+        let prune_height = storage.prune_height();
+
+        GetBlockChainInfoResponse {
+            chain: chain_name(self.consensus.network).into(),
+            blocks: best_block.number,
+            // synchronization state may lag behind the snapshot
+            headers: ::std::cmp::max(self.sync_state.best_headers_height(), best_block.number),
+            bestblockhash: best_block.hash.reversed().into(),
+            difficulty: self.best_block_difficulty(&*storage),
+            mediantime: zebra_verification::median_timestamp_inclusive(
+                best_block.hash.clone(),
+                storage.as_block_header_provider(),
+            ),
+            verificationprogress: verification_progress,
+            pruned: prune_height.is_some(),
+            pruneheight: prune_height,
+            size_on_disk: storage
+                .column_stats()
+                .into_iter()
+                .map(|stat| stat.approx_bytes)
+                .sum(),
+            upgrades: NetworkUpgrade::all()
+                .iter()
+                .filter(|upgrade| **upgrade != NetworkUpgrade::Sprout)
+                .map(|upgrade| {
+                    let activation_height = self.consensus.activation_height(*upgrade);
+                    let status = if best_block.number >= activation_height {
+                        NetworkUpgradeStatus::Active
+                    } else {
+                        NetworkUpgradeStatus::Pending
+                    };
+                    let info = NetworkUpgradeInfo {
+                        name: format!("{:?}", upgrade),
+                        activationheight: activation_height,
+                        status: status,
+                    };
+                    (format!("{:08x}", upgrade.branch_id()), info)
+                })
+                .collect(),
+        }
+    }
+
+    fn block_deltas(&self, hash: GlobalH256) -> Result<GetBlockDeltasResponse, Error> {
+        // block and spent outputs are read from the snapshot, so that reorg can't be observed halfway
+        let storage = self.storage.snapshot();
+        // transactions of the blocks below the prune height could have been pruned
+        let prune_height = storage.prune_height();
+        let is_pruned =
+            |height: u32| prune_height.map_or(false, |prune_height| height < prune_height);
+
+        let height = match storage.block_number(&hash) {
+            Some(height) => height,
+            None if storage.contains_block(hash.clone().into()) => {
+                return Err(block_of_side_branch(hash.reversed()))
+            }
+            None => return Err(block_not_found(hash.reversed())),
+        };
+        if is_pruned(height) {
+            return Err(block_deltas_unavailable());
+        }
+        let block = match storage.block(hash.clone().into()) {
+            Some(block) => block,
+            None => return Err(block_not_found(hash.reversed())),
+        };
+
+        let prevouts = block
+            .transactions
+            .iter()
+            .filter(|tx| !tx.raw.is_coinbase())
+            .flat_map(|tx| tx.raw.inputs.iter())
+            .map(|input| input.previous_output.clone())
+            .collect::<Vec<_>>();
+        // outputs, spent by the block, could have been pruned
+        let spends_pruned_output = prevouts.iter().any(|prevout| {
+            storage
+                .transaction_meta(&prevout.hash)
+                .map_or(false, |meta| is_pruned(meta.height()))
+        });
+        if spends_pruned_output {
+            return Err(block_deltas_unavailable());
+        }
+
+        let mut spent_outputs = storage
+            .as_transaction_output_provider()
+            .transaction_outputs(&prevouts)
+            .into_iter();
+
+        let network = self.keys_network();
+        // only the first destination is reported for multisig scripts
+        let script_address = |script_bytes: &GlobalBytes| {
+            let script: Script = script_bytes.clone().into();
+            script
+                .extract_destinations()
+                .unwrap_or(vec![])
+                .into_iter()
+                .next()
+                .map(|address| {
+                    Address {
+                        network: network,
+                        hash: address.hash,
+                        kind: address.kind,
+                    }
+                    .to_string()
+                })
+        };
+
+        let mut deltas = Vec::with_capacity(block.transactions.len());
+        for (tx_index, tx) in block.transactions.iter().enumerate() {
+            let is_coinbase = tx.raw.is_coinbase();
+            let mut inputs = Vec::with_capacity(tx.raw.inputs.len());
+            for (index, input) in tx.raw.inputs.iter().enumerate() {
+                let mut delta = InputDelta {
+                    index: index as u32,
+                    prevtxid: input.previous_output.hash.reversed().into(),
+                    prevout: input.previous_output.index,
+                    coinbase: is_coinbase,
+                    ..Default::default()
+                };
+                if !is_coinbase {
+                    let spent_output = match spent_outputs.next() {
+                        Some(Some(spent_output)) => spent_output,
+                        _ => return Err(transaction_output_not_found(&input.previous_output)),
+                    };
+                    delta.address = script_address(&spent_output.script_pubkey);
+                    delta.satoshis = -(spent_output.value as i64);
+                }
+                inputs.push(delta);
+            }
+
+            let outputs = tx
+                .raw
+                .outputs
+                .iter()
+                .enumerate()
+                .map(|(index, output)| OutputDelta {
+                    address: script_address(&output.script_pubkey),
+                    satoshis: output.value as i64,
+                    index: index as u32,
+                    script: output.script_pubkey.clone().into(),
+                })
+                .collect();
+
+            deltas.push(TransactionDeltas {
+                txid: tx.hash.reversed().into(),
+                index: tx_index as u32,
+                inputs: inputs,
+                outputs: outputs,
+            });
+        }
+
+        Ok(GetBlockDeltasResponse {
+            hash: hash.reversed().into(),
+            height: height,
+            confirmations: storage.best_block().number - height + 1,
+            time: block.header.raw.time,
+            deltas: deltas,
         })
     }
 }
@@ -207,10 +1037,32 @@ where
             .ok_or(block_at_height_not_found(height))
     }
 
+    fn block_height(&self, hash: H256) -> Result<u32, Error> {
+        let hash: GlobalH256 = hash.into();
+        self.core.block_height(hash.reversed())
+    }
+
+    fn block_hashes(&self, high_time: u32, low_time: u32) -> Result<Vec<H256>, Error> {
+        if low_time > high_time {
+            return Err(invalid_params("low", low_time));
+        }
+
+        Ok(self
+            .core
+            .block_hashes(high_time, low_time)
+            .into_iter()
+            .map(|h| h.reversed().into())
+            .collect())
+    }
+
     fn difficulty(&self) -> Result<f64, Error> {
         Ok(self.core.difficulty())
     }
 
+    fn next_work_required(&self) -> Result<u32, Error> {
+        Ok(self.core.next_work_required())
+    }
+
     fn block(&self, block: BlockRef, verbosity: Option<u8>) -> Result<GetBlockResponse, Error> {
         let global_hash = match block {
             BlockRef::Number(number) => self
@@ -231,25 +1083,27 @@ where
                 .map(GetBlockResponse::Raw)
                 .ok_or(block_not_found(global_hash.reversed())),
             // if verbosity is 1, returns an Object with information about the block.
-            None | Some(1) => {
-                let verbose_block = self.core.verbose_block(global_hash);
-                if let Some(mut verbose_block) = verbose_block {
-                    verbose_block.previousblockhash =
-                        verbose_block.previousblockhash.map(|h| h.reversed());
-                    verbose_block.nextblockhash = verbose_block.nextblockhash.map(|h| h.reversed());
-                    verbose_block.hash = verbose_block.hash.reversed();
-                    verbose_block.merkleroot = verbose_block.merkleroot.reversed();
-                    verbose_block.finalsaplingroot = verbose_block.finalsaplingroot.reversed();
+            None | Some(1) => self
+                .core
+                .verbose_block(global_hash)
+                .map(|mut verbose_block| {
+                    reverse_block_hashes(&mut verbose_block);
                     verbose_block.tx = verbose_block.tx.into_iter().map(|h| h.reversed()).collect();
-                    Some(GetBlockResponse::Verbose(verbose_block))
-                } else {
-                    None
-                }
-                .ok_or(block_not_found(global_hash.reversed()))
-            }
+                    GetBlockResponse::Verbose(verbose_block)
+                })
+                .ok_or(block_not_found(global_hash.reversed())),
             // if verbosity is 2, returns an Object with information about the block and information about each transaction.
-            // we do not (yet?) support getrawtransaction call => nothing to return
-            Some(2) => rpc_unimplemented!(),
+            Some(2) => self
+                .core
+                .verbose_block_with_transactions(global_hash)
+                .map(|mut verbose_block| {
+                    reverse_block_hashes(&mut verbose_block);
+                    for transaction in &mut verbose_block.tx {
+                        reverse_transaction_hashes(transaction);
+                    }
+                    GetBlockResponse::VerboseWithTransactions(verbose_block)
+                })
+                .ok_or(block_not_found(global_hash.reversed())),
             _ => Err(invalid_params("verbosity", verbosity)),
         }
     }
@@ -258,15 +1112,17 @@ where
         &self,
         transaction_hash: H256,
         out_index: u32,
-        _include_mempool: Option<bool>,
+        include_mempool: Option<bool>,
     ) -> Result<GetTxOutResponse, Error> {
-        // TODO: include_mempool
         let transaction_hash: GlobalH256 = transaction_hash.into();
         self.core
-            .verbose_transaction_out(OutPoint {
-                hash: transaction_hash.reversed(),
-                index: out_index,
-            })
+            .verbose_transaction_out(
+                OutPoint {
+                    hash: transaction_hash.reversed(),
+                    index: out_index,
+                },
+                include_mempool.unwrap_or_default(),
+            )
             .map(|mut response| {
                 response.bestblock = response.bestblock.reversed();
                 response
@@ -276,6 +1132,206 @@ where
     fn transaction_out_set_info(&self) -> Result<GetTxOutSetInfoResponse, Error> {
         rpc_unimplemented!()
     }
+
+    fn transaction_out_proof(
+        &self,
+        txids: Vec<H256>,
+        block_hash: Option<H256>,
+    ) -> Result<Bytes, Error> {
+        if txids.is_empty() {
+            return Err(invalid_params("txids", "empty"));
+        }
+
+        let mut unique = HashSet::new();
+        let mut global_txids = Vec::with_capacity(txids.len());
+        for txid in txids {
+            if !unique.insert(txid.clone()) {
+                return Err(invalid_params("txids", txid));
+            }
+            let global_txid: GlobalH256 = txid.into();
+            global_txids.push(global_txid.reversed());
+        }
+
+        let block_hash = block_hash.map(|hash| {
+            let global_hash: GlobalH256 = hash.into();
+            global_hash.reversed()
+        });
+        self.core
+            .transaction_out_proof(&global_txids, block_hash)
+            .map(Into::into)
+    }
+
+    fn verify_transaction_out_proof(&self, proof: Bytes) -> Result<Vec<H256>, Error> {
+        self.core
+            .verify_transaction_out_proof(&proof.0)
+            .map(|txids| txids.into_iter().map(|h| h.reversed().into()).collect())
+    }
+
+    fn verify_chain(&self, check_level: Option<u32>, blocks: Option<u32>) -> Result<bool, Error> {
+        let check_level = check_level.unwrap_or(DEFAULT_CHECK_LEVEL);
+        if check_level > MAX_CHECK_LEVEL {
+            return Err(invalid_params("checklevel", check_level));
+        }
+
+        Ok(self
+            .core
+            .verify_chain(check_level, blocks.unwrap_or(DEFAULT_CHECK_BLOCKS)))
+    }
+
+    fn db_info(&self) -> Result<GetDbInfoResponse, Error> {
+        Ok(self.core.db_info())
+    }
+
+    fn block_stats(
+        &self,
+        block: BlockRef,
+        stats: Option<Vec<String>>,
+    ) -> Result<GetBlockStatsResponse, Error> {
+        let global_hash = match block {
+            BlockRef::Number(number) => self
+                .core
+                .block_hash(number)
+                .ok_or(block_not_found(number))?,
+            BlockRef::Hash(hash) => {
+                let h: GlobalH256 = hash.into();
+                h.reversed()
+            }
+        };
+
+        let stats = stats.unwrap_or_default();
+        if let Some(stat) = stats
+            .iter()
+            .find(|stat| !BLOCK_STATS.iter().any(|s| s == stat))
+        {
+            return Err(invalid_params("stats", stat));
+        }
+
+        // all statistics are computed if nothing is requested
+        let stats: Vec<&str> = if stats.is_empty() {
+            BLOCK_STATS.to_vec()
+        } else {
+            stats.iter().map(|stat| stat.as_str()).collect()
+        };
+        self.core.block_stats(global_hash, &stats)
+    }
+
+    fn chain_tx_stats(
+        &self,
+        block_count: u32,
+        hash: Option<H256>,
+    ) -> Result<GetChainTxStatsResponse, Error> {
+        let global_hash = match hash {
+            Some(hash) => {
+                let h: GlobalH256 = hash.into();
+                h.reversed()
+            }
+            None => self.core.best_block_hash(),
+        };
+        self.core.chain_tx_stats(global_hash, block_count)
+    }
+
+    fn address_utxos(&self, addresses: Vec<String>) -> Result<Vec<AddressUtxo>, Error> {
+        let addresses = parse_addresses(addresses)?;
+        self.core.address_utxos(&addresses).map(|utxos| {
+            utxos
+                .into_iter()
+                .map(|mut utxo| {
+                    utxo.txid = utxo.txid.reversed();
+                    utxo
+                })
+                .collect()
+        })
+    }
+
+    fn address_balance(&self, addresses: Vec<String>) -> Result<GetAddressBalanceResponse, Error> {
+        let addresses = parse_addresses(addresses)?;
+        self.core.address_balance(&addresses)
+    }
+
+    fn address_txids(
+        &self,
+        addresses: Vec<String>,
+        start: Option<u32>,
+        end: Option<u32>,
+    ) -> Result<Vec<H256>, Error> {
+        let addresses = parse_addresses(addresses)?;
+        let start = start.unwrap_or(0);
+        let end = end.unwrap_or(u32::max_value());
+        if start > end {
+            return Err(invalid_params("end", end));
+        }
+
+        self.core
+            .address_txids(&addresses, start, end)
+            .map(|txids| txids.into_iter().map(|h| h.reversed().into()).collect())
+    }
+
+    fn nullifier(&self, nullifier: H256, pool: String) -> Result<bool, Error> {
+        let epoch = match pool.as_ref() {
+            "sprout" => EpochTag::Sprout,
+            "sapling" => EpochTag::Sapling,
+            _ => return Err(invalid_params("pool", pool)),
+        };
+
+        let nullifier: GlobalH256 = nullifier.into();
+        Ok(self
+            .core
+            .contains_nullifier(EpochRef::new(epoch, nullifier.reversed())))
+    }
+
+    fn tree_state(&self, hash: H256) -> Result<GetTreeStateResponse, Error> {
+        let global_hash: GlobalH256 = hash.clone().into();
+        self.core
+            .tree_state(global_hash.reversed())
+            .ok_or(block_not_found(hash))
+    }
+
+    fn blockchain_info(&self) -> Result<GetBlockChainInfoResponse, Error> {
+        Ok(self.core.blockchain_info())
+    }
+
+    fn block_deltas(&self, hash: H256) -> Result<GetBlockDeltasResponse, Error> {
+        let global_hash: GlobalH256 = hash.into();
+        self.core.block_deltas(global_hash.reversed())
+    }
+}
+
+/// Name of the network, displayed to the user.
+fn chain_name(network: Network) -> &'static str {
+    match network {
+        Network::Mainnet | Network::Other(_) => "main",
+        Network::Testnet => "test",
+        Network::Regtest | Network::Unitest => "regtest",
+    }
+}
+
+/// Parses transparent addresses, passed to the address index requests.
+fn parse_addresses(addresses: Vec<String>) -> Result<Vec<Address>, Error> {
+    addresses
+        .into_iter()
+        .map(|address| {
+            address
+                .parse()
+                .map_err(|_| invalid_params("addresses", address))
+        })
+        .collect()
+}
+
+/// Converts transparent address to the address index key.
+fn indexed_address(address: &Address) -> IndexedAddress {
+    match address.kind {
+        zebra_keys::Type::P2PKH => IndexedAddress::PubKeyHash(address.hash.clone()),
+        zebra_keys::Type::P2SH => IndexedAddress::ScriptHash(address.hash.clone()),
+    }
+}
+
+/// Reverses hashes of the verbose block (except transactions), as they're displayed to the user.
+fn reverse_block_hashes<T>(block: &mut VerboseBlock<T>) {
+    block.previousblockhash = block.previousblockhash.take().map(|h| h.reversed());
+    block.nextblockhash = block.nextblockhash.take().map(|h| h.reversed());
+    block.hash = block.hash.reversed();
+    block.merkleroot = block.merkleroot.reversed();
+    block.finalsaplingroot = block.finalsaplingroot.reversed();
 }
 
 #[cfg(test)]
@@ -285,19 +1341,28 @@ pub mod tests {
     use super::*;
     use jsonrpc_core::Error;
     use jsonrpc_core::IoHandler;
+    use std::collections::BTreeMap;
     use std::sync::Arc;
-    use v1::helpers::errors::block_not_found;
+    use v1::helpers::errors::{
+        address_index_disabled, block_not_found, block_of_side_branch, transaction_not_found,
+        transaction_output_not_found,
+    };
     use v1::traits::BlockChain;
     use v1::types::Bytes;
     use v1::types::ScriptType;
     use v1::types::H256;
     use v1::types::{GetTxOutResponse, TransactionOutputScript};
     use v1::types::{RawBlock, VerboseBlock};
+    use zebra_chain::IndexedBlock;
     use zebra_chain::OutPoint;
+    use zebra_chain::Transaction;
+    use zebra_db::kv::MemoryDatabase;
     use zebra_db::BlockChainDatabase;
+    use zebra_miner::NonZeroFeeCalculator;
     use zebra_network::Network;
     use zebra_primitives::bytes::Bytes as GlobalBytes;
     use zebra_primitives::hash::H256 as GlobalH256;
+    use zebra_sync::SynchronizationState;
 
     #[derive(Default)]
     struct SuccessBlockChainClientCore;
@@ -317,10 +1382,22 @@ pub mod tests {
             Some(zebra_test_data::genesis().hash())
         }
 
+        fn block_height(&self, _hash: GlobalH256) -> Result<u32, Error> {
+            Ok(2)
+        }
+
+        fn block_hashes(&self, _high_time: u32, _low_time: u32) -> Vec<GlobalH256> {
+            vec![zebra_test_data::genesis().hash()]
+        }
+
         fn difficulty(&self) -> f64 {
             1f64
         }
 
+        fn next_work_required(&self) -> u32 {
+            0x1f07ffff
+        }
+
         fn raw_block(&self, _hash: GlobalH256) -> Option<RawBlock> {
             let b2_bytes: GlobalBytes = "010000004860eb18bf1b1620e37e9490fc8a427514416fd75159ab86688e9a8300000000d5fdcc541e25de1c7a5addedf24858b8bb665c9f36ef744ee42c316022c90f9bb0bc6649ffff001d08d2bd610101000000010000000000000000000000000000000000000000000000000000000000000000ffffffff0704ffff001d010bffffffff0100f2052a010000004341047211a824f55b505228e4c3d5194c1fcfaa15a456abdf37f9b9d97a4040afc073dee6c89064984f03385237d92167c13e236446b417ab79a0fcae412ae3316b77ac00000000".into();
             Some(RawBlock::from(b2_bytes))
@@ -352,7 +1429,38 @@ pub mod tests {
             })
         }
 
-        fn verbose_transaction_out(&self, _prev_out: OutPoint) -> Result<GetTxOutResponse, Error> {
+        fn verbose_block_with_transactions(
+            &self,
+            hash: GlobalH256,
+        ) -> Option<VerboseBlock<VerboseTransaction>> {
+            let transaction: Transaction = "01000000000000000000".into();
+            let transaction = RawClientCore::do_decode_transaction(
+                &transaction.into(),
+                zebra_keys::Network::Mainnet,
+            );
+            self.verbose_block(hash).map(|block| VerboseBlock {
+                hash: block.hash,
+                confirmations: block.confirmations,
+                size: block.size,
+                height: block.height,
+                version: block.version,
+                merkleroot: block.merkleroot,
+                tx: vec![transaction],
+                time: block.time,
+                nonce: block.nonce,
+                bits: block.bits,
+                difficulty: block.difficulty,
+                previousblockhash: block.previousblockhash,
+                nextblockhash: block.nextblockhash,
+                finalsaplingroot: block.finalsaplingroot,
+            })
+        }
+
+        fn verbose_transaction_out(
+            &self,
+            _prev_out: OutPoint,
+            _include_mempool: bool,
+        ) -> Result<GetTxOutResponse, Error> {
             Ok(GetTxOutResponse {
                 bestblock: H256::from(0x56),
                 confirmations: 777,
@@ -371,6 +1479,176 @@ pub mod tests {
                 coinbase: false,
             })
         }
+        fn verify_chain(&self, _check_level: u32, _blocks: u32) -> bool {
+            true
+        }
+
+        fn transaction_out_proof(
+            &self,
+            _txids: &[GlobalH256],
+            _block_hash: Option<GlobalH256>,
+        ) -> Result<GlobalBytes, Error> {
+            Ok(vec![1, 2, 3].into())
+        }
+
+        fn verify_transaction_out_proof(&self, _proof: &[u8]) -> Result<Vec<GlobalH256>, Error> {
+            Ok(vec![GlobalH256::from(1)])
+        }
+
+        fn db_info(&self) -> GetDbInfoResponse {
+            GetDbInfoResponse {
+                columns: vec![
+                    DbColumnInfo {
+                        column: 0,
+                        keys: 2,
+                        bytes: 64,
+                    },
+                    DbColumnInfo {
+                        column: 1,
+                        keys: 0,
+                        bytes: 0,
+                    },
+                ],
+            }
+        }
+
+        fn block_stats(
+            &self,
+            _hash: GlobalH256,
+            stats: &[&str],
+        ) -> Result<GetBlockStatsResponse, Error> {
+            let wants = |stat: &str| stats.iter().any(|s| *s == stat);
+            Ok(GetBlockStatsResponse {
+                txs: if wants("txs") { Some(2) } else { None },
+                totalfee: if wants("totalfee") { Some(1000) } else { None },
+                subsidy: if wants("subsidy") {
+                    Some(1250000000)
+                } else {
+                    None
+                },
+                ..Default::default()
+            })
+        }
+
+        fn chain_tx_stats(
+            &self,
+            hash: GlobalH256,
+            block_count: u32,
+        ) -> Result<GetChainTxStatsResponse, Error> {
+            Ok(GetChainTxStatsResponse {
+                time: 1231469665,
+                txcount: 15,
+                window_final_block_hash: hash.reversed().into(),
+                window_block_count: block_count,
+                window_tx_count: Some(12),
+                window_interval: Some(100),
+                txrate: Some(0.12),
+            })
+        }
+
+        fn address_utxos(&self, addresses: &[Address]) -> Result<Vec<AddressUtxo>, Error> {
+            Ok(addresses
+                .iter()
+                .map(|address| AddressUtxo {
+                    address: address.to_string(),
+                    txid: H256::from(1),
+                    output_index: 0,
+                    script: Bytes::new(vec![0x51]),
+                    satoshis: 1000,
+                    height: 2,
+                })
+                .collect())
+        }
+
+        fn address_balance(
+            &self,
+            _addresses: &[Address],
+        ) -> Result<GetAddressBalanceResponse, Error> {
+            Ok(GetAddressBalanceResponse {
+                balance: 1000,
+                received: 3000,
+            })
+        }
+
+        fn address_txids(
+            &self,
+            _addresses: &[Address],
+            _start: u32,
+            _end: u32,
+        ) -> Result<Vec<GlobalH256>, Error> {
+            Ok(vec![GlobalH256::from(1), GlobalH256::from(2)])
+        }
+
+        fn contains_nullifier(&self, nullifier: EpochRef) -> bool {
+            nullifier.epoch() == EpochTag::Sapling
+        }
+
+        fn tree_state(&self, hash: GlobalH256) -> Option<GetTreeStateResponse> {
+            Some(GetTreeStateResponse {
+                hash: hash.reversed().into(),
+                height: Some(2),
+                sprout: CommitmentTreeState {
+                    finalroot: H256::from(3),
+                    finalstate: Bytes::new(vec![0x00, 0x00, 0x00]),
+                },
+                sapling: CommitmentTreeState {
+                    finalroot: H256::from(4),
+                    finalstate: Bytes::new(vec![0x00, 0x00, 0x00]),
+                },
+            })
+        }
+
+        fn blockchain_info(&self) -> GetBlockChainInfoResponse {
+            let mut upgrades = BTreeMap::new();
+            upgrades.insert(
+                "5ba81b19".into(),
+                NetworkUpgradeInfo {
+                    name: "Overwinter".into(),
+                    activationheight: 347500,
+                    status: NetworkUpgradeStatus::Pending,
+                },
+            );
+            GetBlockChainInfoResponse {
+                chain: "main".into(),
+                blocks: 1,
+                headers: 1,
+                bestblockhash: H256::from(1),
+                difficulty: 1.0,
+                mediantime: 1000,
+                verificationprogress: 0.5,
+                pruned: false,
+                pruneheight: None,
+                size_on_disk: 2048,
+                upgrades: upgrades,
+            }
+        }
+
+        fn block_deltas(&self, _hash: GlobalH256) -> Result<GetBlockDeltasResponse, Error> {
+            Ok(GetBlockDeltasResponse {
+                hash: H256::from(1),
+                height: 2,
+                confirmations: 1,
+                time: 1000,
+                deltas: vec![TransactionDeltas {
+                    txid: H256::from(3),
+                    index: 1,
+                    inputs: vec![InputDelta {
+                        address: Some("t1h8SqgtM3QM5e2M8EzhhT1yL2PXXtA6oqe".into()),
+                        satoshis: -1000,
+                        index: 0,
+                        prevtxid: H256::from(4),
+                        prevout: 5,
+                        coinbase: false,
+                    }],
+                    outputs: vec![OutputDelta {
+                        address: None,
+                        satoshis: 900,
+                        index: 0,
+                        script: Bytes::new(vec![0x51]),
+                    }],
+                }],
+            })
+        }
     }
 
     impl BlockChainClientCoreApi for ErrorBlockChainClientCore {
@@ -386,10 +1664,22 @@ pub mod tests {
             None
         }
 
+        fn block_height(&self, hash: GlobalH256) -> Result<u32, Error> {
+            Err(block_not_found(hash.reversed()))
+        }
+
+        fn block_hashes(&self, _high_time: u32, _low_time: u32) -> Vec<GlobalH256> {
+            vec![]
+        }
+
         fn difficulty(&self) -> f64 {
             1f64
         }
 
+        fn next_work_required(&self) -> u32 {
+            0x1f07ffff
+        }
+
         fn raw_block(&self, _hash: GlobalH256) -> Option<RawBlock> {
             None
         }
@@ -398,13 +1688,1582 @@ pub mod tests {
             None
         }
 
-        fn verbose_transaction_out(&self, prev_out: OutPoint) -> Result<GetTxOutResponse, Error> {
-            Err(block_not_found(prev_out.hash))
-        }
+        fn verbose_block_with_transactions(
+            &self,
+            _hash: GlobalH256,
+        ) -> Option<VerboseBlock<VerboseTransaction>> {
+            None
+        }
+
+        fn verbose_transaction_out(
+            &self,
+            prev_out: OutPoint,
+            _include_mempool: bool,
+        ) -> Result<GetTxOutResponse, Error> {
+            Err(block_not_found(prev_out.hash))
+        }
+        fn verify_chain(&self, _check_level: u32, _blocks: u32) -> bool {
+            false
+        }
+
+        fn transaction_out_proof(
+            &self,
+            txids: &[GlobalH256],
+            _block_hash: Option<GlobalH256>,
+        ) -> Result<GlobalBytes, Error> {
+            Err(transaction_not_found(txids[0].reversed()))
+        }
+
+        fn verify_transaction_out_proof(&self, _proof: &[u8]) -> Result<Vec<GlobalH256>, Error> {
+            Err(block_not_found(GlobalH256::from(1)))
+        }
+
+        fn db_info(&self) -> GetDbInfoResponse {
+            GetDbInfoResponse::default()
+        }
+
+        fn block_stats(
+            &self,
+            hash: GlobalH256,
+            _stats: &[&str],
+        ) -> Result<GetBlockStatsResponse, Error> {
+            Err(block_not_found(hash.reversed()))
+        }
+
+        fn chain_tx_stats(
+            &self,
+            hash: GlobalH256,
+            _block_count: u32,
+        ) -> Result<GetChainTxStatsResponse, Error> {
+            Err(block_not_found(hash.reversed()))
+        }
+
+        fn address_utxos(&self, _addresses: &[Address]) -> Result<Vec<AddressUtxo>, Error> {
+            Err(address_index_disabled())
+        }
+
+        fn address_balance(
+            &self,
+            _addresses: &[Address],
+        ) -> Result<GetAddressBalanceResponse, Error> {
+            Err(address_index_disabled())
+        }
+
+        fn address_txids(
+            &self,
+            _addresses: &[Address],
+            _start: u32,
+            _end: u32,
+        ) -> Result<Vec<GlobalH256>, Error> {
+            Err(address_index_disabled())
+        }
+
+        fn contains_nullifier(&self, _nullifier: EpochRef) -> bool {
+            false
+        }
+
+        fn tree_state(&self, _hash: GlobalH256) -> Option<GetTreeStateResponse> {
+            None
+        }
+
+        fn blockchain_info(&self) -> GetBlockChainInfoResponse {
+            GetBlockChainInfoResponse::default()
+        }
+
+        fn block_deltas(&self, hash: GlobalH256) -> Result<GetBlockDeltasResponse, Error> {
+            Err(block_not_found(hash.reversed()))
+        }
+    }
+
+    #[test]
+    fn best_block_hash_success() {
+        let client = BlockChainClient::new(SuccessBlockChainClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler
+            .handle_request_sync(
+                &(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "getbestblockhash",
+				"params": [],
+				"id": 1
+			}"#),
+            )
+            .unwrap();
+
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","result":"00040fe8ec8471911baa1db1266ea15dd06b4a8a5c453883c000b031973dce08","id":1}"#);
+    }
+
+    #[test]
+    fn block_count_success() {
+        let client = BlockChainClient::new(SuccessBlockChainClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler
+            .handle_request_sync(
+                &(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "getblockcount",
+				"params": [],
+				"id": 1
+			}"#),
+            )
+            .unwrap();
+
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","result":1,"id":1}"#);
+    }
+
+    #[test]
+    fn block_hash_success() {
+        let client = BlockChainClient::new(SuccessBlockChainClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler
+            .handle_request_sync(
+                &(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "getblockhash",
+				"params": [0],
+				"id": 1
+			}"#),
+            )
+            .unwrap();
+
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","result":"00040fe8ec8471911baa1db1266ea15dd06b4a8a5c453883c000b031973dce08","id":1}"#);
+    }
+
+    #[test]
+    fn block_hash_error() {
+        let client = BlockChainClient::new(ErrorBlockChainClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler
+            .handle_request_sync(
+                &(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "getblockhash",
+				"params": [0],
+				"id": 1
+			}"#),
+            )
+            .unwrap();
+
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","error":{"code":-32099,"message":"Block at given height is not found","data":"0"},"id":1}"#);
+    }
+
+    #[test]
+    fn block_height_success() {
+        let client = BlockChainClient::new(SuccessBlockChainClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler
+            .handle_request_sync(
+                &(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "getblockheight",
+				"params": ["0002a26c902619fc964443264feb16f1e3e2d71322fc53dcb81cc5d797e273ed"],
+				"id": 1
+			}"#),
+            )
+            .unwrap();
+
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","result":2,"id":1}"#);
+    }
+
+    #[test]
+    fn block_height_error() {
+        let client = BlockChainClient::new(ErrorBlockChainClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler
+            .handle_request_sync(
+                &(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "getblockheight",
+				"params": ["0002a26c902619fc964443264feb16f1e3e2d71322fc53dcb81cc5d797e273ed"],
+				"id": 1
+			}"#),
+            )
+            .unwrap();
+
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","error":{"code":-32099,"message":"Block with given hash is not found","data":"0002a26c902619fc964443264feb16f1e3e2d71322fc53dcb81cc5d797e273ed"},"id":1}"#);
+    }
+
+    #[test]
+    fn block_height_contents() {
+        let genesis = zebra_test_data::genesis();
+        let side_block = zebra_test_data::block_builder()
+            .header()
+            .parent(genesis.hash())
+            .nonce(1.into())
+            .build()
+            .build();
+        let side_block_hash = side_block.hash();
+        let storage = Arc::new(BlockChainDatabase::init_test_chain(vec![
+            genesis.into(),
+            zebra_test_data::block_h1().into(),
+        ]));
+        storage.insert(side_block.into()).unwrap();
+
+        let core = BlockChainClientCore::new(
+            ConsensusParams::new(Network::Mainnet),
+            storage.clone(),
+            Default::default(),
+            Arc::new(SynchronizationState::with_storage(storage)),
+        );
+
+        // canonical block
+        assert_eq!(core.block_height(zebra_test_data::block_h1().hash()), Ok(1));
+        // unknown block
+        assert_eq!(
+            core.block_height(GlobalH256::from(77)),
+            Err(block_not_found(GlobalH256::from(77).reversed()))
+        );
+        // side chain block
+        assert_eq!(
+            core.block_height(side_block_hash.clone()),
+            Err(block_of_side_branch(side_block_hash.reversed()))
+        );
+    }
+
+    #[test]
+    fn block_hashes_success() {
+        let client = BlockChainClient::new(SuccessBlockChainClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler
+            .handle_request_sync(
+                &(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "getblockhashes",
+				"params": [1231469744, 1231006505],
+				"id": 1
+			}"#),
+            )
+            .unwrap();
+
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","result":["00040fe8ec8471911baa1db1266ea15dd06b4a8a5c453883c000b031973dce08"],"id":1}"#);
+    }
+
+    #[test]
+    fn block_hashes_invalid_range() {
+        let client = BlockChainClient::new(SuccessBlockChainClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler
+            .handle_request_sync(
+                &(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "getblockhashes",
+				"params": [1231006505, 1231469744],
+				"id": 1
+			}"#),
+            )
+            .unwrap();
+
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"Couldn't parse parameters: low","data":"1231469744"},"id":1}"#);
+    }
+
+    #[test]
+    fn block_hashes_contents() {
+        // timestamps are not monotonic
+        let times = [100, 200, 150, 300, 400, 350, 500];
+        let mut blocks: Vec<IndexedBlock> = Vec::new();
+        let mut parent = GlobalH256::from(0);
+        for time in times.iter() {
+            let block: IndexedBlock = zebra_test_data::block_builder()
+                .header()
+                .parent(parent)
+                .time(*time)
+                .build()
+                .build()
+                .into();
+            parent = block.hash().clone();
+            blocks.push(block);
+        }
+        let hashes: Vec<GlobalH256> = blocks.iter().map(|b| b.hash().clone()).collect();
+        let storage = Arc::new(BlockChainDatabase::init_test_chain(blocks));
+        let core = BlockChainClientCore::new(
+            ConsensusParams::new(Network::Mainnet),
+            storage.clone(),
+            Default::default(),
+            Arc::new(SynchronizationState::with_storage(storage)),
+        );
+
+        assert_eq!(
+            core.block_hashes(300, 150),
+            vec![hashes[1].clone(), hashes[2].clone(), hashes[3].clone()]
+        );
+        // out-of-order block is found
+        assert_eq!(core.block_hashes(160, 120), vec![hashes[2].clone()]);
+        assert_eq!(
+            core.block_hashes(1000, 400),
+            vec![hashes[4].clone(), hashes[6].clone()]
+        );
+        assert!(core.block_hashes(99, 0).is_empty());
+        assert!(core.block_hashes(1000, 501).is_empty());
+    }
+
+    #[test]
+    fn difficulty_success() {
+        let client = BlockChainClient::new(SuccessBlockChainClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler
+            .handle_request_sync(
+                &(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "getdifficulty",
+				"params": [],
+				"id": 1
+			}"#),
+            )
+            .unwrap();
+
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","result":1.0,"id":1}"#);
+    }
+
+    #[test]
+    fn next_work_required_success() {
+        let client = BlockChainClient::new(SuccessBlockChainClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler
+            .handle_request_sync(
+                &(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "getnextworkrequired",
+				"params": [],
+				"id": 1
+			}"#),
+            )
+            .unwrap();
+
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","result":520617983,"id":1}"#);
+    }
+
+    #[test]
+    fn difficulty_and_next_work_required_contents() {
+        let storage = Arc::new(BlockChainDatabase::init_test_chain(vec![
+            zebra_test_data::genesis().into(),
+            zebra_test_data::block_h1().into(),
+            zebra_test_data::block_h2().into(),
+        ]));
+
+        let core = BlockChainClientCore::new(
+            ConsensusParams::new(Network::Mainnet),
+            storage.clone(),
+            Default::default(),
+            Arc::new(SynchronizationState::with_storage(storage)),
+        );
+
+        // mainnet blocks #1 and #2 are mined with the genesis difficulty
+        assert_eq!(core.difficulty(), 1.0);
+        // averaging window isn't filled yet => next block is mined with the minimal difficulty
+        assert_eq!(core.next_work_required(), 0x1f07ffff);
+    }
+
+    #[test]
+    fn verbose_block_contents() {
+        let storage = Arc::new(BlockChainDatabase::init_test_chain(vec![
+            zebra_test_data::genesis().into(),
+            zebra_test_data::block_h1().into(),
+            zebra_test_data::block_h2().into(),
+        ]));
+
+        let core = BlockChainClientCore::new(
+            ConsensusParams::new(Network::Mainnet),
+            storage.clone(),
+            Default::default(),
+            Arc::new(SynchronizationState::with_storage(storage)),
+        );
+
+        // get info on block #1:
+        // https://zcash.blockexplorer.com/block/0007bc227e1c57a4a70e237cad00e7b7ce565155ab49166bc57397a26d339283
+        let verbose_block = core.verbose_block(
+            "8392336da29773c56b1649ab555156ceb7e700ad7c230ea7a4571c7e22bc0700".into(),
+        );
+        assert_eq!(
+            verbose_block,
+            Some(VerboseBlock {
+                hash: "8392336da29773c56b1649ab555156ceb7e700ad7c230ea7a4571c7e22bc0700".into(),
+                confirmations: 2, // h1 + h2
+                size: 1617,
+                height: Some(1),
+                version: 4,
+                merkleroot: "0946edb9c083c9942d92305444527765fad789c438c717783276a9f7fbf61b85"
+                    .into(),
+                tx: vec!["0946edb9c083c9942d92305444527765fad789c438c717783276a9f7fbf61b85".into()],
+                time: 1477671596,
+                nonce: "7534e8cf161ff2e49d54bdb3bfbcde8cdbf2fc5963c9ec7d86aed4a67e975790".into(),
+                bits: 520617983,
+                difficulty: 1.0,
+                previousblockhash: Some(
+                    "08ce3d9731b000c08338455c8a4a6bd05da16e26b11daa1b917184ece80f0400".into()
+                ),
+                nextblockhash: Some(
+                    "ed73e297d7c51cb8dc53fc2213d7e2e3f116eb4f26434496fc1926906ca20200".into()
+                ),
+                finalsaplingroot:
+                    "0000000000000000000000000000000000000000000000000000000000000000".into(),
+            })
+        );
+
+        // get info on block #2:
+        // https://zcash.blockexplorer.com/block/0002a26c902619fc964443264feb16f1e3e2d71322fc53dcb81cc5d797e273ed
+        let verbose_block = core.verbose_block(
+            "ed73e297d7c51cb8dc53fc2213d7e2e3f116eb4f26434496fc1926906ca20200".into(),
+        );
+        assert_eq!(
+            verbose_block,
+            Some(VerboseBlock {
+                hash: "ed73e297d7c51cb8dc53fc2213d7e2e3f116eb4f26434496fc1926906ca20200".into(),
+                confirmations: 1, // h2
+                size: 1617,
+                height: Some(2),
+                version: 4,
+                merkleroot: "f4b084a7c2fc5a5aa2985f2bcb1d4a9a65562a589d628b0d869c5f1c8dd07489"
+                    .into(),
+                tx: vec!["f4b084a7c2fc5a5aa2985f2bcb1d4a9a65562a589d628b0d869c5f1c8dd07489".into()],
+                time: 1477671626,
+                nonce: "a5556cd346010000000000000000000000000000000000000000000000000002".into(),
+                bits: 520617983,
+                difficulty: 1.0,
+                previousblockhash: Some(
+                    "8392336da29773c56b1649ab555156ceb7e700ad7c230ea7a4571c7e22bc0700".into()
+                ),
+                nextblockhash: None,
+                finalsaplingroot:
+                    "0000000000000000000000000000000000000000000000000000000000000000".into(),
+            })
+        );
+    }
+
+    #[test]
+    fn verbose_block_with_transactions_contents() {
+        let b0: IndexedBlock = zebra_test_data::block_builder()
+            .header()
+            .build()
+            .transaction()
+            .coinbase()
+            .output()
+            .value(50)
+            .script_pubkey("76a914111111111111111111111111111111111111111188ac")
+            .build()
+            .build()
+            .build()
+            .into();
+        let b1: IndexedBlock = zebra_test_data::block_builder()
+            .transaction()
+            .coinbase()
+            .output()
+            .value(10)
+            .build()
+            .build()
+            .transaction()
+            .input()
+            .hash(b0.transactions[0].hash.clone())
+            .index(0)
+            .build()
+            .output()
+            .value(40)
+            .build()
+            .build()
+            .merkled_header()
+            .parent(b0.hash().clone())
+            .build()
+            .build()
+            .into();
+        let side_b1: IndexedBlock = zebra_test_data::block_builder()
+            .transaction()
+            .coinbase()
+            .output()
+            .value(20)
+            .build()
+            .build()
+            .merkled_header()
+            .parent(b0.hash().clone())
+            .build()
+            .build()
+            .into();
+        let b1_hash = b1.hash().clone();
+        let b1_time = b1.header.raw.time;
+        let side_b1_hash = side_b1.hash().clone();
+        let tx0_hash = b1.transactions[0].hash.clone();
+        let tx1_hash = b1.transactions[1].hash.clone();
+
+        let storage = Arc::new(BlockChainDatabase::open(MemoryDatabase::default()));
+        for block in vec![b0, b1] {
+            let hash = block.hash().clone();
+            storage.insert(block).unwrap();
+            storage.canonize(&hash).unwrap();
+        }
+        storage.insert(side_b1).unwrap();
+        let core = BlockChainClientCore::new(
+            ConsensusParams::new(Network::Mainnet),
+            storage.clone(),
+            Default::default(),
+            Arc::new(SynchronizationState::with_storage(storage)),
+        );
+
+        let verbose_block = core
+            .verbose_block_with_transactions(b1_hash.clone())
+            .unwrap();
+        assert_eq!(verbose_block.height, Some(1));
+        assert_eq!(verbose_block.tx.len(), 2);
+
+        // coinbase input doesn't spend anything
+        let coinbase = &verbose_block.tx[0];
+        assert_eq!(coinbase.txid, H256::from(tx0_hash));
+        assert_eq!(coinbase.vin[0].value, None);
+        assert!(coinbase.vin[0].addresses.is_empty());
+
+        // spent output is resolved
+        let transaction = &verbose_block.tx[1];
+        assert_eq!(transaction.txid, H256::from(tx1_hash));
+        assert_eq!(transaction.vin[0].value, Some(0.0000005));
+        assert_eq!(
+            transaction.vin[0].addresses,
+            vec![Address {
+                kind: zebra_keys::Type::P2PKH,
+                network: zebra_keys::Network::Mainnet,
+                hash: [0x11; 20].into(),
+            }]
+        );
+        assert_eq!(transaction.vout[0].value, 0.0000004);
+        assert_eq!(transaction.blockhash, Some(H256::from(b1_hash)));
+        assert_eq!(transaction.height, Some(1));
+        assert_eq!(transaction.confirmations, Some(1));
+        assert_eq!(transaction.blocktime, Some(b1_time));
+
+        // side chain block
+        let verbose_block = core.verbose_block_with_transactions(side_b1_hash).unwrap();
+        assert_eq!(verbose_block.height, None);
+        assert_eq!(verbose_block.confirmations, -1);
+        assert_eq!(verbose_block.tx[0].height, None);
+        assert_eq!(verbose_block.tx[0].confirmations, Some(-1));
+
+        // unknown block
+        assert_eq!(
+            core.verbose_block_with_transactions(GlobalH256::from(77)),
+            None
+        );
+    }
+
+    #[test]
+    fn raw_block_success() {
+        let client = BlockChainClient::new(SuccessBlockChainClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let expected = r#"{"jsonrpc":"2.0","result":"010000004860eb18bf1b1620e37e9490fc8a427514416fd75159ab86688e9a8300000000d5fdcc541e25de1c7a5addedf24858b8bb665c9f36ef744ee42c316022c90f9bb0bc6649ffff001d08d2bd610101000000010000000000000000000000000000000000000000000000000000000000000000ffffffff0704ffff001d010bffffffff0100f2052a010000004341047211a824f55b505228e4c3d5194c1fcfaa15a456abdf37f9b9d97a4040afc073dee6c89064984f03385237d92167c13e236446b417ab79a0fcae412ae3316b77ac00000000","id":1}"#;
+
+        let sample = handler
+            .handle_request_sync(
+                &(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "getblock",
+				"params": ["000000006a625f06636b8bb6ac7b960a8d03705d1ace08b1a19da3fdcc99ddbd", 0],
+				"id": 1
+			}"#),
+            )
+            .unwrap();
+        assert_eq!(&sample, expected);
+    }
+
+    #[test]
+    fn raw_block_error() {
+        let client = BlockChainClient::new(ErrorBlockChainClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler
+            .handle_request_sync(
+                &(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "getblock",
+				"params": ["000000006a625f06636b8bb6ac7b960a8d03705d1ace08b1a19da3fdcc99ddbd", 0],
+				"id": 1
+			}"#),
+            )
+            .unwrap();
+
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","error":{"code":-32099,"message":"Block with given hash is not found","data":"000000006a625f06636b8bb6ac7b960a8d03705d1ace08b1a19da3fdcc99ddbd"},"id":1}"#);
+    }
+
+    #[test]
+    fn verbose_block_success() {
+        let client = BlockChainClient::new(SuccessBlockChainClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let expected = r#"{"jsonrpc":"2.0","result":{"bits":486604799,"confirmations":1,"difficulty":1.0,"finalsaplingroot":"02000000000000000000000000000000000000000000000000000146d36c55a5","hash":"000000006a625f06636b8bb6ac7b960a8d03705d1ace08b1a19da3fdcc99ddbd","height":2,"merkleroot":"9b0fc92260312ce44e74ef369f5c66bbb85848f2eddd5a7a1cde251e54ccfdd5","nextblockhash":null,"nonce":"2a00000000000000000000000000000000000000000000000000000000000000","previousblockhash":"00000000839a8e6886ab5951d76f411475428afc90947ee320161bbf18eb6048","size":215,"time":1231469744,"tx":["9b0fc92260312ce44e74ef369f5c66bbb85848f2eddd5a7a1cde251e54ccfdd5"],"version":1},"id":1}"#;
+
+        let sample = handler
+            .handle_request_sync(
+                &(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "getblock",
+				"params": ["000000006a625f06636b8bb6ac7b960a8d03705d1ace08b1a19da3fdcc99ddbd",1],
+				"id": 1
+			}"#),
+            )
+            .unwrap();
+
+        assert_eq!(&sample, expected);
+
+        // try without optional parameter
+        let sample = handler
+            .handle_request_sync(
+                &(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "getblock",
+				"params": ["000000006a625f06636b8bb6ac7b960a8d03705d1ace08b1a19da3fdcc99ddbd"],
+				"id": 1
+			}"#),
+            )
+            .unwrap();
+        assert_eq!(&sample, expected);
+    }
+
+    #[test]
+    fn verbose_block_with_transactions_success() {
+        let client = BlockChainClient::new(SuccessBlockChainClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler
+            .handle_request_sync(
+                &(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "getblock",
+				"params": ["000000006a625f06636b8bb6ac7b960a8d03705d1ace08b1a19da3fdcc99ddbd", 2],
+				"id": 1
+			}"#),
+            )
+            .unwrap();
+
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","result":{"bits":486604799,"confirmations":1,"difficulty":1.0,"finalsaplingroot":"02000000000000000000000000000000000000000000000000000146d36c55a5","hash":"000000006a625f06636b8bb6ac7b960a8d03705d1ace08b1a19da3fdcc99ddbd","height":2,"merkleroot":"9b0fc92260312ce44e74ef369f5c66bbb85848f2eddd5a7a1cde251e54ccfdd5","nextblockhash":null,"nonce":"2a00000000000000000000000000000000000000000000000000000000000000","previousblockhash":"00000000839a8e6886ab5951d76f411475428afc90947ee320161bbf18eb6048","size":215,"time":1231469744,"tx":[{"hash":"d21633ba23f70118185227be58a63527675641ad37967e2aa461559f577aec43","hex":"01000000000000000000","locktime":0,"overwintered":false,"size":10,"txid":"d21633ba23f70118185227be58a63527675641ad37967e2aa461559f577aec43","version":1,"vin":[],"vjoinsplit":[],"vout":[]}],"version":1},"id":1}"#);
+    }
+
+    #[test]
+    fn verbose_block_with_transactions_error() {
+        let client = BlockChainClient::new(ErrorBlockChainClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler
+            .handle_request_sync(
+                &(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "getblock",
+				"params": ["000000006a625f06636b8bb6ac7b960a8d03705d1ace08b1a19da3fdcc99ddbd", 2],
+				"id": 1
+			}"#),
+            )
+            .unwrap();
+
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","error":{"code":-32099,"message":"Block with given hash is not found","data":"000000006a625f06636b8bb6ac7b960a8d03705d1ace08b1a19da3fdcc99ddbd"},"id":1}"#);
+    }
+
+    #[test]
+    fn verbose_block_error() {
+        let client = BlockChainClient::new(ErrorBlockChainClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler
+            .handle_request_sync(
+                &(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "getblock",
+				"params": ["000000006a625f06636b8bb6ac7b960a8d03705d1ace08b1a19da3fdcc99ddbd", 1],
+				"id": 1
+			}"#),
+            )
+            .unwrap();
+
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","error":{"code":-32099,"message":"Block with given hash is not found","data":"000000006a625f06636b8bb6ac7b960a8d03705d1ace08b1a19da3fdcc99ddbd"},"id":1}"#);
+    }
+
+    #[test]
+    fn verbose_transaction_out_contents() {
+        let storage = Arc::new(BlockChainDatabase::init_test_chain(vec![
+            zebra_test_data::genesis().into(),
+            zebra_test_data::block_h1().into(),
+        ]));
+        let core = BlockChainClientCore::new(
+            ConsensusParams::new(Network::Mainnet),
+            storage.clone(),
+            Default::default(),
+            Arc::new(SynchronizationState::with_storage(storage)),
+        );
+
+        // get info on tx from block#1:
+        // https://zcash.blockexplorer.com/tx/851bf6fbf7a976327817c738c489d7fa657752445430922d94c983c0b9ed4609
+        let verbose_transaction_out = core.verbose_transaction_out(
+            OutPoint {
+                hash: "0946edb9c083c9942d92305444527765fad789c438c717783276a9f7fbf61b85".into(),
+                index: 0,
+            },
+            false,
+        );
+        assert_eq!(verbose_transaction_out, Ok(GetTxOutResponse {
+				bestblock: "8392336da29773c56b1649ab555156ceb7e700ad7c230ea7a4571c7e22bc0700".into(),
+				confirmations: 1,
+				value: 0.0005,
+				script: TransactionOutputScript {
+					asm: "OP_PUSHBYTES_33 0x027a46eb513588b01b37ea24303f4b628afd12cc20df789fede0921e43cad3e875\nOP_CHECKSIG\n".to_owned(),
+					hex: Bytes::from("21027a46eb513588b01b37ea24303f4b628afd12cc20df789fede0921e43cad3e875ac"),
+					req_sigs: 1,
+					script_type: ScriptType::PubKey,
+					addresses: vec!["t1KstPVzcNEK4ZeauQ6cogoqxQBMDSiRnGr".into()]
+				},
+				version: 1,
+				coinbase: true
+			}));
+    }
+
+    #[test]
+    fn verbose_transaction_out_respects_memory_pool() {
+        let block_h1 = zebra_test_data::block_h1();
+        let storage = Arc::new(BlockChainDatabase::init_test_chain(vec![
+            zebra_test_data::genesis().into(),
+            block_h1.clone().into(),
+        ]));
+        let memory_pool: zebra_sync::MemoryPoolRef = Default::default();
+        let core = BlockChainClientCore::new(
+            ConsensusParams::new(Network::Mainnet),
+            storage.clone(),
+            memory_pool.clone(),
+            Arc::new(SynchronizationState::with_storage(storage)),
+        );
+
+        // memory pool transaction spends output of block#1 coinbase
+        let transaction: Transaction = zebra_test_data::TransactionBuilder::with_output(10)
+            .add_input(&block_h1.transactions[0], 0)
+            .into();
+        let transaction_hash = transaction.hash();
+        memory_pool
+            .write()
+            .insert_verified(transaction.into(), &NonZeroFeeCalculator)
+            .unwrap();
+
+        // when memory pool is ignored, output is unspent
+        let spent_out = OutPoint {
+            hash: block_h1.transactions[0].hash(),
+            index: 0,
+        };
+        assert_eq!(
+            core.verbose_transaction_out(spent_out.clone(), false)
+                .map(|response| response.confirmations),
+            Ok(1)
+        );
+        // when memory pool is included, output is spent
+        assert_eq!(
+            core.verbose_transaction_out(spent_out.clone(), true),
+            Err(transaction_output_not_found(spent_out))
+        );
+
+        // output of memory pool transaction is only known if memory pool is included
+        let mempool_out = OutPoint {
+            hash: transaction_hash.clone(),
+            index: 0,
+        };
+        assert_eq!(
+            core.verbose_transaction_out(mempool_out.clone(), false),
+            Err(transaction_not_found(transaction_hash))
+        );
+        let response = core.verbose_transaction_out(mempool_out, true).unwrap();
+        assert_eq!(response.confirmations, 0);
+        assert_eq!(response.bestblock, H256::from(block_h1.hash()));
+        assert!(!response.coinbase);
+    }
+
+    #[test]
+    fn transaction_out_success() {
+        let client = BlockChainClient::new(SuccessBlockChainClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler
+            .handle_request_sync(
+                &(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "gettxout",
+				"params": ["4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b", 0],
+				"id": 1
+			}"#),
+            )
+            .unwrap();
+
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","result":{"bestblock":"0000000000000000000000000000000000000000000000000000000000000056","coinbase":false,"confirmations":777,"scriptPubKey":{"addresses":["t2UNzUUx8mWBCRYPRezvA363EYXyEpHokyi","t2N9PH9Wk9xjqYg9iin1Ua3aekJqfAtE543"],"asm":"Hello, world!!!","hex":"01020304","reqSigs":777,"type":"multisig"},"value":100000.56,"version":33},"id":1}"#);
+    }
+
+    #[test]
+    fn transaction_out_failure() {
+        let client = BlockChainClient::new(ErrorBlockChainClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler
+            .handle_request_sync(
+                &(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "gettxout",
+				"params": ["4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b", 0],
+				"id": 1
+			}"#),
+            )
+            .unwrap();
+
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","error":{"code":-32099,"message":"Block with given hash is not found","data":"3ba3edfd7a7b12b27ac72c3e67768f617fc81bc3888a51323a9fb8aa4b1e5e4a"},"id":1}"#);
+    }
+
+    #[test]
+    fn verify_chain_success() {
+        let client = BlockChainClient::new(SuccessBlockChainClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler
+            .handle_request_sync(
+                &(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "verifychain",
+				"params": [],
+				"id": 1
+			}"#),
+            )
+            .unwrap();
+
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","result":true,"id":1}"#);
+    }
+
+    #[test]
+    fn verify_chain_failure() {
+        let client = BlockChainClient::new(ErrorBlockChainClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler
+            .handle_request_sync(
+                &(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "verifychain",
+				"params": [4, 10],
+				"id": 1
+			}"#),
+            )
+            .unwrap();
+
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","result":false,"id":1}"#);
+    }
+
+    #[test]
+    fn verify_chain_invalid_check_level() {
+        let client = BlockChainClient::new(SuccessBlockChainClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler
+            .handle_request_sync(
+                &(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "verifychain",
+				"params": [5],
+				"id": 1
+			}"#),
+            )
+            .unwrap();
+
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"Couldn't parse parameters: checklevel","data":"5"},"id":1}"#);
+    }
+
+    #[test]
+    fn verify_chain_contents() {
+        let storage = Arc::new(BlockChainDatabase::init_test_chain(vec![
+            zebra_test_data::genesis().into(),
+            zebra_test_data::block_h1().into(),
+        ]));
+        let core = BlockChainClientCore::new(
+            ConsensusParams::new(Network::Mainnet),
+            storage.clone(),
+            Default::default(),
+            Arc::new(SynchronizationState::with_storage(storage)),
+        );
+
+        assert!(core.verify_chain(4, 0));
+        assert!(core.verify_chain(DEFAULT_CHECK_LEVEL, DEFAULT_CHECK_BLOCKS));
+    }
+
+    #[test]
+    fn transaction_out_proof_success() {
+        let client = BlockChainClient::new(SuccessBlockChainClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler
+            .handle_request_sync(
+                &(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "gettxoutproof",
+				"params": [["0000000000000000000000000000000000000000000000000000000000000001"]],
+				"id": 1
+			}"#),
+            )
+            .unwrap();
+
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","result":"010203","id":1}"#);
+    }
+
+    #[test]
+    fn transaction_out_proof_duplicate_txid() {
+        let client = BlockChainClient::new(SuccessBlockChainClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler
+            .handle_request_sync(
+                &(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "gettxoutproof",
+				"params": [["0000000000000000000000000000000000000000000000000000000000000001", "0000000000000000000000000000000000000000000000000000000000000001"]],
+				"id": 1
+			}"#),
+            )
+            .unwrap();
+
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"Couldn't parse parameters: txids","data":"0000000000000000000000000000000000000000000000000000000000000001"},"id":1}"#);
+    }
+
+    #[test]
+    fn transaction_out_proof_error() {
+        let client = BlockChainClient::new(ErrorBlockChainClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler
+            .handle_request_sync(
+                &(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "gettxoutproof",
+				"params": [["0000000000000000000000000000000000000000000000000000000000000001"]],
+				"id": 1
+			}"#),
+            )
+            .unwrap();
+
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","error":{"code":-32096,"message":"Transaction with given hash is not found","data":"0000000000000000000000000000000000000000000000000000000000000001"},"id":1}"#);
+    }
+
+    #[test]
+    fn verify_transaction_out_proof_success() {
+        let client = BlockChainClient::new(SuccessBlockChainClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler
+            .handle_request_sync(
+                &(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "verifytxoutproof",
+				"params": ["010203"],
+				"id": 1
+			}"#),
+            )
+            .unwrap();
+
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","result":["0000000000000000000000000000000000000000000000000000000000000001"],"id":1}"#);
+    }
+
+    #[test]
+    fn verify_transaction_out_proof_error() {
+        let client = BlockChainClient::new(ErrorBlockChainClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler
+            .handle_request_sync(
+                &(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "verifytxoutproof",
+				"params": ["010203"],
+				"id": 1
+			}"#),
+            )
+            .unwrap();
+
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","error":{"code":-32099,"message":"Block with given hash is not found","data":"0100000000000000000000000000000000000000000000000000000000000000"},"id":1}"#);
+    }
+
+    #[test]
+    fn transaction_out_proof_contents() {
+        let b0: IndexedBlock = zebra_test_data::block_builder()
+            .header()
+            .build()
+            .transaction()
+            .coinbase()
+            .build()
+            .build()
+            .into();
+        let b1: IndexedBlock = zebra_test_data::block_builder()
+            .transaction()
+            .coinbase()
+            .output()
+            .value(10)
+            .build()
+            .build()
+            .transaction()
+            .output()
+            .value(20)
+            .build()
+            .build()
+            .transaction()
+            .output()
+            .value(30)
+            .build()
+            .build()
+            .merkled_header()
+            .parent(b0.hash().clone())
+            .build()
+            .build()
+            .into();
+        let b2: IndexedBlock = zebra_test_data::block_builder()
+            .transaction()
+            .coinbase()
+            .output()
+            .value(40)
+            .build()
+            .build()
+            .merkled_header()
+            .parent(b1.hash().clone())
+            .build()
+            .build()
+            .into();
+        let tx0_hash = b0.transactions[0].hash.clone();
+        let tx1_hash = b1.transactions[1].hash.clone();
+        let tx2_hash = b1.transactions[2].hash.clone();
+        let b1_hash = b1.hash().clone();
+
+        let storage = Arc::new(BlockChainDatabase::open(MemoryDatabase::default()));
+        for block in vec![b0, b1] {
+            let hash = block.hash().clone();
+            storage.insert(block).unwrap();
+            storage.canonize(&hash).unwrap();
+        }
+        let core = BlockChainClientCore::new(
+            ConsensusParams::new(Network::Mainnet),
+            storage.clone(),
+            Default::default(),
+            Arc::new(SynchronizationState::with_storage(storage)),
+        );
+
+        // block is located using transactions index
+        let proof = core
+            .transaction_out_proof(&[tx1_hash.clone()], None)
+            .unwrap();
+        assert_eq!(
+            core.verify_transaction_out_proof(&proof),
+            Ok(vec![tx1_hash.clone()])
+        );
+
+        // block is explicitly specified
+        let proof = core
+            .transaction_out_proof(&[tx2_hash.clone(), tx1_hash.clone()], Some(b1_hash))
+            .unwrap();
+        assert_eq!(
+            core.verify_transaction_out_proof(&proof),
+            Ok(vec![tx1_hash.clone(), tx2_hash.clone()])
+        );
+
+        // transactions are included in different blocks
+        assert_eq!(
+            core.transaction_out_proof(&[tx0_hash.clone(), tx1_hash.clone()], None),
+            Err(invalid_params(
+                "txids",
+                "transactions are included in different blocks"
+            ))
+        );
+        assert_eq!(
+            core.transaction_out_proof(&[tx0_hash.clone()], Some(b1_hash)),
+            Err(transaction_not_found(tx0_hash.reversed()))
+        );
+
+        // proof with invalid merkle root proves nothing
+        let mut merkle_block: types::MerkleBlock =
+            deserialize_payload(&proof, types::MerkleBlock::version()).unwrap();
+        merkle_block.hashes[0] = GlobalH256::from(1);
+        let invalid_proof =
+            serialize_payload(&merkle_block, types::MerkleBlock::version()).unwrap();
+        assert_eq!(
+            core.verify_transaction_out_proof(&invalid_proof),
+            Ok(vec![])
+        );
+
+        // proof of unknown block is rejected
+        let matches = vec![true].into_iter().collect();
+        let unknown_proof = serialize_payload(
+            &zebra_sync::build_merkle_block(&b2, matches),
+            types::MerkleBlock::version(),
+        )
+        .unwrap();
+        assert_eq!(
+            core.verify_transaction_out_proof(&unknown_proof),
+            Err(block_not_found(b2.hash().reversed()))
+        );
+    }
+
+    #[test]
+    fn db_info_success() {
+        let client = BlockChainClient::new(SuccessBlockChainClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler
+            .handle_request_sync(
+                &(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "getdbinfo",
+				"params": [],
+				"id": 1
+			}"#),
+            )
+            .unwrap();
+
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","result":{"columns":[{"bytes":64,"column":0,"keys":2},{"bytes":0,"column":1,"keys":0}]},"id":1}"#);
+    }
+
+    #[test]
+    fn db_info_contents() {
+        let storage = Arc::new(BlockChainDatabase::init_test_chain(vec![
+            zebra_test_data::genesis().into(),
+            zebra_test_data::block_h1().into(),
+        ]));
+        let core = BlockChainClientCore::new(
+            ConsensusParams::new(Network::Mainnet),
+            storage.clone(),
+            Default::default(),
+            Arc::new(SynchronizationState::with_storage(storage)),
+        );
+
+        let info = core.db_info();
+        let headers = info
+            .columns
+            .iter()
+            .find(|column| column.column == 2)
+            .unwrap();
+        assert_eq!(headers.keys, 2);
+    }
+
+    #[test]
+    fn block_stats_success() {
+        let client = BlockChainClient::new(SuccessBlockChainClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler
+            .handle_request_sync(
+                &(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "getblockstats",
+				"params": ["000000006a625f06636b8bb6ac7b960a8d03705d1ace08b1a19da3fdcc99ddbd", ["txs", "totalfee"]],
+				"id": 1
+			}"#),
+            )
+            .unwrap();
+
+        assert_eq!(
+            &sample,
+            r#"{"jsonrpc":"2.0","result":{"totalfee":1000,"txs":2},"id":1}"#
+        );
+
+        let sample = handler
+            .handle_request_sync(
+                &(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "getblockstats",
+				"params": ["000000006a625f06636b8bb6ac7b960a8d03705d1ace08b1a19da3fdcc99ddbd"],
+				"id": 1
+			}"#),
+            )
+            .unwrap();
+
+        assert_eq!(
+            &sample,
+            r#"{"jsonrpc":"2.0","result":{"subsidy":1250000000,"totalfee":1000,"txs":2},"id":1}"#
+        );
+    }
+
+    #[test]
+    fn block_stats_invalid_stat() {
+        let client = BlockChainClient::new(SuccessBlockChainClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler
+            .handle_request_sync(
+                &(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "getblockstats",
+				"params": ["000000006a625f06636b8bb6ac7b960a8d03705d1ace08b1a19da3fdcc99ddbd", ["txs", "unknown"]],
+				"id": 1
+			}"#),
+            )
+            .unwrap();
+
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"Couldn't parse parameters: stats","data":"\"unknown\""},"id":1}"#);
+    }
+
+    #[test]
+    fn block_stats_error() {
+        let client = BlockChainClient::new(ErrorBlockChainClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler
+            .handle_request_sync(
+                &(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "getblockstats",
+				"params": ["000000006a625f06636b8bb6ac7b960a8d03705d1ace08b1a19da3fdcc99ddbd"],
+				"id": 1
+			}"#),
+            )
+            .unwrap();
+
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","error":{"code":-32099,"message":"Block with given hash is not found","data":"000000006a625f06636b8bb6ac7b960a8d03705d1ace08b1a19da3fdcc99ddbd"},"id":1}"#);
+    }
+
+    #[test]
+    fn block_stats_contents() {
+        let b0 = zebra_test_data::block_builder()
+            .header()
+            .build()
+            .transaction()
+            .coinbase()
+            .output()
+            .value(10)
+            .build()
+            .build()
+            .transaction()
+            .output()
+            .value(50)
+            .build()
+            .build()
+            .build();
+        let b1 = zebra_test_data::block_builder()
+            .header()
+            .parent(b0.hash())
+            .build()
+            .transaction()
+            .coinbase()
+            .output()
+            .value(10)
+            .build()
+            .build()
+            .transaction()
+            .input()
+            .hash(b0.transactions[1].hash())
+            .index(0)
+            .build()
+            .output()
+            .value(30)
+            .build()
+            .output()
+            .value(15)
+            .build()
+            .build()
+            .build();
+        let b1_hash = b1.hash();
+        let spending_tx_size = b1.transactions[1].total_size() as u64;
+        let storage = Arc::new(BlockChainDatabase::init_test_chain(vec![
+            b0.into(),
+            b1.into(),
+        ]));
+        let consensus = ConsensusParams::new(Network::Mainnet);
+        let subsidy = consensus.block_reward(1);
+        let core = BlockChainClientCore::new(
+            consensus,
+            storage.clone(),
+            Default::default(),
+            Arc::new(SynchronizationState::with_storage(storage)),
+        );
+
+        let stats = core.block_stats(b1_hash.clone(), BLOCK_STATS).unwrap();
+        assert_eq!(
+            stats,
+            GetBlockStatsResponse {
+                total_size: Some(spending_tx_size),
+                txs: Some(2),
+                total_out: Some(45),
+                totalfee: Some(5),
+                avgfee: Some(5),
+                minfee: Some(5),
+                maxfee: Some(5),
+                avgfeerate: Some(5 / spending_tx_size),
+                ins: Some(1),
+                outs: Some(3),
+                subsidy: Some(subsidy),
+                total_sigops: Some(0),
+            }
+        );
+
+        let stats = core.block_stats(b1_hash, &["minfee", "outs"]).unwrap();
+        assert_eq!(
+            stats,
+            GetBlockStatsResponse {
+                minfee: Some(5),
+                outs: Some(3),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn block_stats_fee_of_pruned_output() {
+        let b0 = zebra_test_data::block_builder()
+            .header()
+            .build()
+            .transaction()
+            .coinbase()
+            .output()
+            .value(10)
+            .build()
+            .build()
+            .build();
+        let b1 = zebra_test_data::block_builder()
+            .header()
+            .parent(b0.hash())
+            .build()
+            .transaction()
+            .coinbase()
+            .output()
+            .value(10)
+            .build()
+            .build()
+            .transaction()
+            .input()
+            .hash(b0.transactions[0].hash())
+            .index(0)
+            .build()
+            .output()
+            .value(5)
+            .build()
+            .build()
+            .build();
+        let b1_hash = b1.hash();
+        let mut storage = BlockChainDatabase::init_test_chain(vec![b0.into(), b1.into()]);
+        storage.set_max_fork_route(0);
+        assert_eq!(storage.prune_below(1), Ok(1));
+        let storage = Arc::new(storage);
+        let core = BlockChainClientCore::new(
+            ConsensusParams::new(Network::Mainnet),
+            storage.clone(),
+            Default::default(),
+            Arc::new(SynchronizationState::with_storage(storage)),
+        );
+
+        // fee of transaction, spending pruned output, is unknown
+        assert!(core.block_stats(b1_hash.clone(), &["totalfee"]).is_err());
+        assert!(core.block_stats(b1_hash.clone(), BLOCK_STATS).is_err());
+
+        // other statistics are still available
+        let stats = core.block_stats(b1_hash, &["txs", "ins"]).unwrap();
+        assert_eq!(
+            stats,
+            GetBlockStatsResponse {
+                txs: Some(2),
+                ins: Some(1),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn chain_tx_stats_success() {
+        let client = BlockChainClient::new(SuccessBlockChainClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler
+            .handle_request_sync(
+                &(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "getchaintxstats",
+				"params": [3, "000000006a625f06636b8bb6ac7b960a8d03705d1ace08b1a19da3fdcc99ddbd"],
+				"id": 1
+			}"#),
+            )
+            .unwrap();
+
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","result":{"time":1231469665,"txcount":15,"txrate":0.12,"window_block_count":3,"window_final_block_hash":"000000006a625f06636b8bb6ac7b960a8d03705d1ace08b1a19da3fdcc99ddbd","window_interval":100,"window_tx_count":12},"id":1}"#);
+    }
+
+    #[test]
+    fn chain_tx_stats_error() {
+        let client = BlockChainClient::new(ErrorBlockChainClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler
+            .handle_request_sync(
+                &(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "getchaintxstats",
+				"params": [3, "000000006a625f06636b8bb6ac7b960a8d03705d1ace08b1a19da3fdcc99ddbd"],
+				"id": 1
+			}"#),
+            )
+            .unwrap();
+
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","error":{"code":-32099,"message":"Block with given hash is not found","data":"000000006a625f06636b8bb6ac7b960a8d03705d1ace08b1a19da3fdcc99ddbd"},"id":1}"#);
+    }
+
+    #[test]
+    fn chain_tx_stats_contents() {
+        // block at height `i` has `i + 1` transactions
+        let mut blocks: Vec<IndexedBlock> = Vec::new();
+        let mut parent = GlobalH256::from(0);
+        for i in 0..5 {
+            let mut builder = zebra_test_data::block_builder()
+                .header()
+                .parent(parent)
+                .time(1000 + 100 * i)
+                .build();
+            for j in 0..i + 1 {
+                builder = builder
+                    .transaction()
+                    .output()
+                    .value(u64::from(100 * i + j))
+                    .build()
+                    .build();
+            }
+            let block: IndexedBlock = builder.build().into();
+            parent = block.hash().clone();
+            blocks.push(block);
+        }
+        let hashes: Vec<GlobalH256> = blocks.iter().map(|b| b.hash().clone()).collect();
+        let storage = Arc::new(BlockChainDatabase::init_test_chain(blocks));
+        let core = BlockChainClientCore::new(
+            ConsensusParams::new(Network::Mainnet),
+            storage.clone(),
+            Default::default(),
+            Arc::new(SynchronizationState::with_storage(storage)),
+        );
+
+        // median times of blocks 1 and 4 are 1100 and 1200
+        assert_eq!(
+            core.chain_tx_stats(hashes[4].clone(), 3).unwrap(),
+            GetChainTxStatsResponse {
+                time: 1400,
+                txcount: 15,
+                window_final_block_hash: hashes[4].reversed().into(),
+                window_block_count: 3,
+                window_tx_count: Some(12),
+                window_interval: Some(100),
+                txrate: Some(0.12),
+            }
+        );
+        // median times of blocks 1 and 2 are the same
+        assert_eq!(
+            core.chain_tx_stats(hashes[2].clone(), 1).unwrap(),
+            GetChainTxStatsResponse {
+                time: 1200,
+                txcount: 6,
+                window_final_block_hash: hashes[2].reversed().into(),
+                window_block_count: 1,
+                window_tx_count: Some(3),
+                window_interval: Some(0),
+                txrate: None,
+            }
+        );
+        assert_eq!(
+            core.chain_tx_stats(hashes[0].clone(), 0).unwrap(),
+            GetChainTxStatsResponse {
+                time: 1000,
+                txcount: 1,
+                window_final_block_hash: hashes[0].reversed().into(),
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            core.chain_tx_stats(hashes[4].clone(), 4),
+            Err(invalid_params("nblocks", 4))
+        );
+        assert_eq!(
+            core.chain_tx_stats(GlobalH256::from(1), 0),
+            Err(block_not_found(GlobalH256::from(1).reversed()))
+        );
+    }
+
+    #[test]
+    fn address_utxos_success() {
+        let client = BlockChainClient::new(SuccessBlockChainClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler
+            .handle_request_sync(
+                &(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "getaddressutxos",
+				"params": [["t1h8SqgtM3QM5e2M8EzhhT1yL2PXXtA6oqe"]],
+				"id": 1
+			}"#),
+            )
+            .unwrap();
+
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","result":[{"address":"t1h8SqgtM3QM5e2M8EzhhT1yL2PXXtA6oqe","height":2,"outputIndex":0,"satoshis":1000,"script":"51","txid":"0000000000000000000000000000000000000000000000000000000000000001"}],"id":1}"#);
     }
 
     #[test]
-    fn best_block_hash_success() {
+    fn address_utxos_invalid_address() {
         let client = BlockChainClient::new(SuccessBlockChainClientCore::default());
         let mut handler = IoHandler::new();
         handler.extend_with(client.to_delegate());
@@ -414,19 +3273,19 @@ pub mod tests {
                 &(r#"
 			{
 				"jsonrpc": "2.0",
-				"method": "getbestblockhash",
-				"params": [],
+				"method": "getaddressutxos",
+				"params": [["t1h8SqgtM3QM5e2M8EzhhT1yL2PXXtA6oqe", "invalid"]],
 				"id": 1
 			}"#),
             )
             .unwrap();
 
-        assert_eq!(&sample, r#"{"jsonrpc":"2.0","result":"00040fe8ec8471911baa1db1266ea15dd06b4a8a5c453883c000b031973dce08","id":1}"#);
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"Couldn't parse parameters: addresses","data":"\"invalid\""},"id":1}"#);
     }
 
     #[test]
-    fn block_count_success() {
-        let client = BlockChainClient::new(SuccessBlockChainClientCore::default());
+    fn address_utxos_error() {
+        let client = BlockChainClient::new(ErrorBlockChainClientCore::default());
         let mut handler = IoHandler::new();
         handler.extend_with(client.to_delegate());
 
@@ -435,18 +3294,107 @@ pub mod tests {
                 &(r#"
 			{
 				"jsonrpc": "2.0",
-				"method": "getblockcount",
-				"params": [],
+				"method": "getaddressutxos",
+				"params": [["t1h8SqgtM3QM5e2M8EzhhT1yL2PXXtA6oqe"]],
 				"id": 1
 			}"#),
             )
             .unwrap();
 
-        assert_eq!(&sample, r#"{"jsonrpc":"2.0","result":1,"id":1}"#);
+        assert_eq!(
+            &sample,
+            r#"{"jsonrpc":"2.0","error":{"code":-32094,"message":"Address index is disabled"},"id":1}"#
+        );
     }
 
     #[test]
-    fn block_hash_success() {
+    fn address_utxos_contents() {
+        let address = Address {
+            kind: zebra_keys::Type::P2PKH,
+            network: zebra_keys::Network::Mainnet,
+            hash: 1.into(),
+        };
+        let p2pkh = "76a914010000000000000000000000000000000000000088ac";
+        let b0: IndexedBlock = zebra_test_data::block_builder()
+            .header()
+            .build()
+            .transaction()
+            .output()
+            .value(10)
+            .script_pubkey(p2pkh)
+            .build()
+            .output()
+            .value(20)
+            .script_pubkey(p2pkh)
+            .build()
+            .build()
+            .build()
+            .into();
+        let tx0_hash = b0.transactions[0].hash.clone();
+        let b1: IndexedBlock = zebra_test_data::block_builder()
+            .header()
+            .parent(b0.hash().clone())
+            .build()
+            .transaction()
+            .coinbase()
+            .build()
+            .transaction()
+            .input()
+            .hash(tx0_hash.clone())
+            .index(0)
+            .build()
+            .output()
+            .value(5)
+            .script_pubkey("51")
+            .build()
+            .build()
+            .build()
+            .into();
+
+        let mut storage = BlockChainDatabase::open(MemoryDatabase::default());
+        storage.set_address_index(true).unwrap();
+        for block in vec![b0, b1] {
+            let hash = block.hash().clone();
+            storage.insert(block).unwrap();
+            storage.canonize(&hash).unwrap();
+        }
+        let storage = Arc::new(storage);
+        let core = BlockChainClientCore::new(
+            ConsensusParams::new(Network::Mainnet),
+            storage.clone(),
+            Default::default(),
+            Arc::new(SynchronizationState::with_storage(storage)),
+        );
+
+        // first output is spent in the second block
+        assert_eq!(
+            core.address_utxos(&[address.clone()]),
+            Ok(vec![AddressUtxo {
+                address: address.to_string(),
+                txid: tx0_hash.into(),
+                output_index: 1,
+                script: Bytes::from(p2pkh),
+                satoshis: 20,
+                height: 0,
+            }])
+        );
+
+        // address index is disabled by default
+        let storage = Arc::new(BlockChainDatabase::init_test_chain(vec![]));
+        let core = BlockChainClientCore::new(
+            ConsensusParams::new(Network::Mainnet),
+            storage.clone(),
+            Default::default(),
+            Arc::new(SynchronizationState::with_storage(storage)),
+        );
+        assert_eq!(
+            core.address_utxos(&[address]),
+            Err(address_index_disabled())
+        );
+    }
+
+    #[test]
+    fn address_balance_success() {
         let client = BlockChainClient::new(SuccessBlockChainClientCore::default());
         let mut handler = IoHandler::new();
         handler.extend_with(client.to_delegate());
@@ -456,18 +3404,21 @@ pub mod tests {
                 &(r#"
 			{
 				"jsonrpc": "2.0",
-				"method": "getblockhash",
-				"params": [0],
+				"method": "getaddressbalance",
+				"params": [["t1h8SqgtM3QM5e2M8EzhhT1yL2PXXtA6oqe"]],
 				"id": 1
 			}"#),
             )
             .unwrap();
 
-        assert_eq!(&sample, r#"{"jsonrpc":"2.0","result":"00040fe8ec8471911baa1db1266ea15dd06b4a8a5c453883c000b031973dce08","id":1}"#);
+        assert_eq!(
+            &sample,
+            r#"{"jsonrpc":"2.0","result":{"balance":1000,"received":3000},"id":1}"#
+        );
     }
 
     #[test]
-    fn block_hash_error() {
+    fn address_balance_error() {
         let client = BlockChainClient::new(ErrorBlockChainClientCore::default());
         let mut handler = IoHandler::new();
         handler.extend_with(client.to_delegate());
@@ -477,18 +3428,21 @@ pub mod tests {
                 &(r#"
 			{
 				"jsonrpc": "2.0",
-				"method": "getblockhash",
-				"params": [0],
+				"method": "getaddressbalance",
+				"params": [["t1h8SqgtM3QM5e2M8EzhhT1yL2PXXtA6oqe"]],
 				"id": 1
 			}"#),
             )
             .unwrap();
 
-        assert_eq!(&sample, r#"{"jsonrpc":"2.0","error":{"code":-32099,"message":"Block at given height is not found","data":"0"},"id":1}"#);
+        assert_eq!(
+            &sample,
+            r#"{"jsonrpc":"2.0","error":{"code":-32094,"message":"Address index is disabled"},"id":1}"#
+        );
     }
 
     #[test]
-    fn difficulty_success() {
+    fn address_txids_success() {
         let client = BlockChainClient::new(SuccessBlockChainClientCore::default());
         let mut handler = IoHandler::new();
         handler.extend_with(client.to_delegate());
@@ -498,112 +3452,232 @@ pub mod tests {
                 &(r#"
 			{
 				"jsonrpc": "2.0",
-				"method": "getdifficulty",
-				"params": [],
+				"method": "getaddresstxids",
+				"params": [["t1h8SqgtM3QM5e2M8EzhhT1yL2PXXtA6oqe"], 0, 1000],
 				"id": 1
 			}"#),
             )
             .unwrap();
 
-        assert_eq!(&sample, r#"{"jsonrpc":"2.0","result":1.0,"id":1}"#);
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","result":["0000000000000000000000000000000000000000000000000000000000000001","0000000000000000000000000000000000000000000000000000000000000002"],"id":1}"#);
     }
 
     #[test]
-    fn verbose_block_contents() {
-        let storage = Arc::new(BlockChainDatabase::init_test_chain(vec![
-            zebra_test_data::genesis().into(),
-            zebra_test_data::block_h1().into(),
-            zebra_test_data::block_h2().into(),
-        ]));
+    fn address_txids_invalid_range() {
+        let client = BlockChainClient::new(SuccessBlockChainClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler
+            .handle_request_sync(
+                &(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "getaddresstxids",
+				"params": [["t1h8SqgtM3QM5e2M8EzhhT1yL2PXXtA6oqe"], 10, 5],
+				"id": 1
+			}"#),
+            )
+            .unwrap();
+
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"Couldn't parse parameters: end","data":"5"},"id":1}"#);
+    }
 
-        let core = BlockChainClientCore::new(ConsensusParams::new(Network::Mainnet), storage);
+    #[test]
+    fn address_balance_and_txids_contents() {
+        let address = Address {
+            kind: zebra_keys::Type::P2PKH,
+            network: zebra_keys::Network::Mainnet,
+            hash: 1.into(),
+        };
+        let p2pkh = "76a914010000000000000000000000000000000000000088ac";
+        let b0: IndexedBlock = zebra_test_data::block_builder()
+            .header()
+            .build()
+            .transaction()
+            .output()
+            .value(10)
+            .script_pubkey(p2pkh)
+            .build()
+            .output()
+            .value(20)
+            .script_pubkey(p2pkh)
+            .build()
+            .build()
+            .build()
+            .into();
+        let tx0_hash = b0.transactions[0].hash.clone();
+        let b1: IndexedBlock = zebra_test_data::block_builder()
+            .header()
+            .parent(b0.hash().clone())
+            .build()
+            .transaction()
+            .coinbase()
+            .build()
+            .transaction()
+            .input()
+            .hash(tx0_hash.clone())
+            .index(0)
+            .build()
+            .output()
+            .value(5)
+            .script_pubkey("51")
+            .build()
+            .build()
+            .build()
+            .into();
+        let tx1_hash = b1.transactions[1].hash.clone();
+        let b2: IndexedBlock = zebra_test_data::block_builder()
+            .header()
+            .parent(b1.hash().clone())
+            .build()
+            .transaction()
+            .output()
+            .value(3)
+            .script_pubkey(p2pkh)
+            .build()
+            .build()
+            .build()
+            .into();
+        let tx2_hash = b2.transactions[0].hash.clone();
 
-        // get info on block #1:
-        // https://zcash.blockexplorer.com/block/0007bc227e1c57a4a70e237cad00e7b7ce565155ab49166bc57397a26d339283
-        let verbose_block = core.verbose_block(
-            "8392336da29773c56b1649ab555156ceb7e700ad7c230ea7a4571c7e22bc0700".into(),
+        let mut storage = BlockChainDatabase::open(MemoryDatabase::default());
+        storage.set_address_index(true).unwrap();
+        for block in vec![b0, b1, b2] {
+            let hash = block.hash().clone();
+            storage.insert(block).unwrap();
+            storage.canonize(&hash).unwrap();
+        }
+        let storage = Arc::new(storage);
+        let core = BlockChainClientCore::new(
+            ConsensusParams::new(Network::Mainnet),
+            storage.clone(),
+            Default::default(),
+            Arc::new(SynchronizationState::with_storage(storage)),
         );
+
+        // first output is spent in the second block
         assert_eq!(
-            verbose_block,
-            Some(VerboseBlock {
-                hash: "8392336da29773c56b1649ab555156ceb7e700ad7c230ea7a4571c7e22bc0700".into(),
-                confirmations: 2, // h1 + h2
-                size: 1617,
-                height: Some(1),
-                version: 4,
-                merkleroot: "0946edb9c083c9942d92305444527765fad789c438c717783276a9f7fbf61b85"
-                    .into(),
-                tx: vec!["0946edb9c083c9942d92305444527765fad789c438c717783276a9f7fbf61b85".into()],
-                time: 1477671596,
-                nonce: "7534e8cf161ff2e49d54bdb3bfbcde8cdbf2fc5963c9ec7d86aed4a67e975790".into(),
-                bits: 520617983,
-                difficulty: 1.0,
-                previousblockhash: Some(
-                    "08ce3d9731b000c08338455c8a4a6bd05da16e26b11daa1b917184ece80f0400".into()
-                ),
-                nextblockhash: Some(
-                    "ed73e297d7c51cb8dc53fc2213d7e2e3f116eb4f26434496fc1926906ca20200".into()
-                ),
-                finalsaplingroot:
-                    "0000000000000000000000000000000000000000000000000000000000000000".into(),
+            core.address_balance(&[address.clone()]),
+            Ok(GetAddressBalanceResponse {
+                balance: 23,
+                received: 33,
             })
         );
+        assert_eq!(
+            core.address_txids(&[address.clone()], 0, u32::max_value()),
+            Ok(vec![tx0_hash.clone(), tx1_hash.clone(), tx2_hash.clone()])
+        );
+        assert_eq!(
+            core.address_txids(&[address.clone()], 1, 1),
+            Ok(vec![tx1_hash])
+        );
+        assert_eq!(
+            core.address_txids(&[address.clone()], 2, 10),
+            Ok(vec![tx2_hash])
+        );
+        assert_eq!(core.address_txids(&[address], 3, 10), Ok(vec![]));
+    }
 
-        // get info on block #2:
-        // https://zcash.blockexplorer.com/block/0002a26c902619fc964443264feb16f1e3e2d71322fc53dcb81cc5d797e273ed
-        let verbose_block = core.verbose_block(
-            "ed73e297d7c51cb8dc53fc2213d7e2e3f116eb4f26434496fc1926906ca20200".into(),
+    #[test]
+    fn address_balance_and_txids_spent_in_same_block() {
+        let address = Address {
+            kind: zebra_keys::Type::P2PKH,
+            network: zebra_keys::Network::Mainnet,
+            hash: 1.into(),
+        };
+        let p2pkh = "76a914010000000000000000000000000000000000000088ac";
+        let b0: IndexedBlock = zebra_test_data::block_builder()
+            .header()
+            .build()
+            .transaction()
+            .output()
+            .value(10)
+            .script_pubkey(p2pkh)
+            .build()
+            .build()
+            .build()
+            .into();
+        let tx0_hash = b0.transactions[0].hash.clone();
+        let mut tx1: Transaction = zebra_test_data::TransactionBuilder::with_output(7)
+            .add_input(&b0.transactions[0].raw, 0)
+            .into();
+        tx1.outputs[0].script_pubkey = p2pkh.into();
+        let tx2: Transaction = zebra_test_data::TransactionBuilder::with_output(4)
+            .add_input(&tx1, 0)
+            .into();
+        let tx1_hash = tx1.hash();
+        let tx2_hash = tx2.hash();
+        let b1: IndexedBlock = zebra_test_data::block_builder()
+            .header()
+            .parent(b0.hash().clone())
+            .build()
+            .transaction()
+            .coinbase()
+            .build()
+            .with_transaction(tx1)
+            .with_transaction(tx2)
+            .build()
+            .into();
+
+        let mut storage = BlockChainDatabase::open(MemoryDatabase::default());
+        storage.set_address_index(true).unwrap();
+        for block in vec![b0, b1] {
+            let hash = block.hash().clone();
+            storage.insert(block).unwrap();
+            storage.canonize(&hash).unwrap();
+        }
+        let storage = Arc::new(storage);
+        let core = BlockChainClientCore::new(
+            ConsensusParams::new(Network::Mainnet),
+            storage.clone(),
+            Default::default(),
+            Arc::new(SynchronizationState::with_storage(storage)),
         );
+
+        // output, created in the second block, is spent in the same block
         assert_eq!(
-            verbose_block,
-            Some(VerboseBlock {
-                hash: "ed73e297d7c51cb8dc53fc2213d7e2e3f116eb4f26434496fc1926906ca20200".into(),
-                confirmations: 1, // h2
-                size: 1617,
-                height: Some(2),
-                version: 4,
-                merkleroot: "f4b084a7c2fc5a5aa2985f2bcb1d4a9a65562a589d628b0d869c5f1c8dd07489"
-                    .into(),
-                tx: vec!["f4b084a7c2fc5a5aa2985f2bcb1d4a9a65562a589d628b0d869c5f1c8dd07489".into()],
-                time: 1477671626,
-                nonce: "a5556cd346010000000000000000000000000000000000000000000000000002".into(),
-                bits: 520617983,
-                difficulty: 1.0,
-                previousblockhash: Some(
-                    "8392336da29773c56b1649ab555156ceb7e700ad7c230ea7a4571c7e22bc0700".into()
-                ),
-                nextblockhash: None,
-                finalsaplingroot:
-                    "0000000000000000000000000000000000000000000000000000000000000000".into(),
+            core.address_balance(&[address.clone()]),
+            Ok(GetAddressBalanceResponse {
+                balance: 0,
+                received: 17,
             })
         );
+        assert_eq!(core.address_utxos(&[address.clone()]), Ok(vec![]));
+        assert_eq!(
+            core.address_txids(&[address.clone()], 0, u32::max_value()),
+            Ok(vec![tx0_hash, tx1_hash.clone(), tx2_hash.clone()])
+        );
+        assert_eq!(
+            core.address_txids(&[address], 1, 1),
+            Ok(vec![tx1_hash, tx2_hash])
+        );
     }
 
     #[test]
-    fn raw_block_success() {
+    fn nullifier_success() {
         let client = BlockChainClient::new(SuccessBlockChainClientCore::default());
         let mut handler = IoHandler::new();
         handler.extend_with(client.to_delegate());
 
-        let expected = r#"{"jsonrpc":"2.0","result":"010000004860eb18bf1b1620e37e9490fc8a427514416fd75159ab86688e9a8300000000d5fdcc541e25de1c7a5addedf24858b8bb665c9f36ef744ee42c316022c90f9bb0bc6649ffff001d08d2bd610101000000010000000000000000000000000000000000000000000000000000000000000000ffffffff0704ffff001d010bffffffff0100f2052a010000004341047211a824f55b505228e4c3d5194c1fcfaa15a456abdf37f9b9d97a4040afc073dee6c89064984f03385237d92167c13e236446b417ab79a0fcae412ae3316b77ac00000000","id":1}"#;
-
         let sample = handler
             .handle_request_sync(
                 &(r#"
 			{
 				"jsonrpc": "2.0",
-				"method": "getblock",
-				"params": ["000000006a625f06636b8bb6ac7b960a8d03705d1ace08b1a19da3fdcc99ddbd", 0],
+				"method": "getnullifier",
+				"params": ["0000000000000000000000000000000000000000000000000000000000000001", "sapling"],
 				"id": 1
 			}"#),
             )
             .unwrap();
-        assert_eq!(&sample, expected);
+
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","result":true,"id":1}"#);
     }
 
     #[test]
-    fn raw_block_error() {
-        let client = BlockChainClient::new(ErrorBlockChainClientCore::default());
+    fn nullifier_not_found() {
+        let client = BlockChainClient::new(SuccessBlockChainClientCore::default());
         let mut handler = IoHandler::new();
         handler.extend_with(client.to_delegate());
 
@@ -612,55 +3686,60 @@ pub mod tests {
                 &(r#"
 			{
 				"jsonrpc": "2.0",
-				"method": "getblock",
-				"params": ["000000006a625f06636b8bb6ac7b960a8d03705d1ace08b1a19da3fdcc99ddbd", 0],
+				"method": "getnullifier",
+				"params": ["0000000000000000000000000000000000000000000000000000000000000001", "sprout"],
 				"id": 1
 			}"#),
             )
             .unwrap();
 
-        assert_eq!(&sample, r#"{"jsonrpc":"2.0","error":{"code":-32099,"message":"Block with given hash is not found","data":"000000006a625f06636b8bb6ac7b960a8d03705d1ace08b1a19da3fdcc99ddbd"},"id":1}"#);
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","result":false,"id":1}"#);
     }
 
     #[test]
-    fn verbose_block_success() {
+    fn nullifier_invalid_pool() {
         let client = BlockChainClient::new(SuccessBlockChainClientCore::default());
         let mut handler = IoHandler::new();
         handler.extend_with(client.to_delegate());
 
-        let expected = r#"{"jsonrpc":"2.0","result":{"bits":486604799,"confirmations":1,"difficulty":1.0,"finalsaplingroot":"02000000000000000000000000000000000000000000000000000146d36c55a5","hash":"000000006a625f06636b8bb6ac7b960a8d03705d1ace08b1a19da3fdcc99ddbd","height":2,"merkleroot":"9b0fc92260312ce44e74ef369f5c66bbb85848f2eddd5a7a1cde251e54ccfdd5","nextblockhash":null,"nonce":"2a00000000000000000000000000000000000000000000000000000000000000","previousblockhash":"00000000839a8e6886ab5951d76f411475428afc90947ee320161bbf18eb6048","size":215,"time":1231469744,"tx":["9b0fc92260312ce44e74ef369f5c66bbb85848f2eddd5a7a1cde251e54ccfdd5"],"version":1},"id":1}"#;
-
         let sample = handler
             .handle_request_sync(
                 &(r#"
 			{
 				"jsonrpc": "2.0",
-				"method": "getblock",
-				"params": ["000000006a625f06636b8bb6ac7b960a8d03705d1ace08b1a19da3fdcc99ddbd",1],
+				"method": "getnullifier",
+				"params": ["0000000000000000000000000000000000000000000000000000000000000001", "orchard"],
 				"id": 1
 			}"#),
             )
             .unwrap();
 
-        assert_eq!(&sample, expected);
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"Couldn't parse parameters: pool","data":"\"orchard\""},"id":1}"#);
+    }
+
+    #[test]
+    fn tree_state_success() {
+        let client = BlockChainClient::new(SuccessBlockChainClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
 
-        // try without optional parameter
         let sample = handler
             .handle_request_sync(
                 &(r#"
 			{
 				"jsonrpc": "2.0",
-				"method": "getblock",
-				"params": ["000000006a625f06636b8bb6ac7b960a8d03705d1ace08b1a19da3fdcc99ddbd"],
+				"method": "z_gettreestate",
+				"params": ["0000000000000000000000000000000000000000000000000000000000000001"],
 				"id": 1
 			}"#),
             )
             .unwrap();
-        assert_eq!(&sample, expected);
+
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","result":{"hash":"0000000000000000000000000000000000000000000000000000000000000001","height":2,"sapling":{"finalroot":"0400000000000000000000000000000000000000000000000000000000000000","finalstate":"000000"},"sprout":{"finalroot":"0300000000000000000000000000000000000000000000000000000000000000","finalstate":"000000"}},"id":1}"#);
     }
 
     #[test]
-    fn verbose_block_error() {
+    fn tree_state_error() {
         let client = BlockChainClient::new(ErrorBlockChainClientCore::default());
         let mut handler = IoHandler::new();
         handler.extend_with(client.to_delegate());
@@ -670,48 +3749,127 @@ pub mod tests {
                 &(r#"
 			{
 				"jsonrpc": "2.0",
-				"method": "getblock",
-				"params": ["000000006a625f06636b8bb6ac7b960a8d03705d1ace08b1a19da3fdcc99ddbd", 1],
+				"method": "z_gettreestate",
+				"params": ["0000000000000000000000000000000000000000000000000000000000000001"],
 				"id": 1
 			}"#),
             )
             .unwrap();
 
-        assert_eq!(&sample, r#"{"jsonrpc":"2.0","error":{"code":-32099,"message":"Block with given hash is not found","data":"000000006a625f06636b8bb6ac7b960a8d03705d1ace08b1a19da3fdcc99ddbd"},"id":1}"#);
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","error":{"code":-32099,"message":"Block with given hash is not found","data":"0000000000000000000000000000000000000000000000000000000000000001"},"id":1}"#);
     }
 
     #[test]
-    fn verbose_transaction_out_contents() {
+    fn nullifier_and_tree_state_contents() {
+        let b0: IndexedBlock = zebra_test_data::block_builder()
+            .header()
+            .build()
+            .transaction()
+            .coinbase()
+            .build()
+            .build()
+            .into();
+        let b0_hash = b0.hash().clone();
+
+        let storage = BlockChainDatabase::open(MemoryDatabase::default());
+        storage.insert(b0).unwrap();
+        storage.canonize(&b0_hash).unwrap();
+        let storage = Arc::new(storage);
+        let core = BlockChainClientCore::new(
+            ConsensusParams::new(Network::Mainnet),
+            storage.clone(),
+            Default::default(),
+            Arc::new(SynchronizationState::with_storage(storage)),
+        );
+
+        // no shielded transactions => empty trees
+        let sprout_tree = zebra_storage::SproutTreeState::new();
+        let sapling_tree = zebra_storage::SaplingTreeState::new();
+        assert_eq!(
+            core.tree_state(b0_hash.clone()),
+            Some(GetTreeStateResponse {
+                hash: b0_hash.reversed().into(),
+                height: Some(0),
+                sprout: CommitmentTreeState {
+                    finalroot: sprout_tree.root().reversed().into(),
+                    finalstate: serialize(&sprout_tree).into(),
+                },
+                sapling: CommitmentTreeState {
+                    finalroot: GlobalH256::default().into(),
+                    finalstate: serialize(&sapling_tree).into(),
+                },
+            })
+        );
+        assert_eq!(core.tree_state(GlobalH256::from(1)), None);
+
+        assert!(!core.contains_nullifier(EpochRef::new(EpochTag::Sprout, GlobalH256::from(1))));
+        assert!(!core.contains_nullifier(EpochRef::new(EpochTag::Sapling, GlobalH256::from(1))));
+    }
+
+    #[test]
+    fn blockchain_info_success() {
+        let client = BlockChainClient::new(SuccessBlockChainClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler
+            .handle_request_sync(
+                &(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "getblockchaininfo",
+				"params": [],
+				"id": 1
+			}"#),
+            )
+            .unwrap();
+
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","result":{"bestblockhash":"0100000000000000000000000000000000000000000000000000000000000000","blocks":1,"chain":"main","difficulty":1.0,"headers":1,"mediantime":1000,"pruned":false,"size_on_disk":2048,"upgrades":{"5ba81b19":{"activationheight":347500,"name":"Overwinter","status":"pending"}},"verificationprogress":0.5},"id":1}"#);
+    }
+
+    #[test]
+    fn blockchain_info_contents() {
         let storage = Arc::new(BlockChainDatabase::init_test_chain(vec![
             zebra_test_data::genesis().into(),
             zebra_test_data::block_h1().into(),
         ]));
-        let core = BlockChainClientCore::new(ConsensusParams::new(Network::Mainnet), storage);
+        let sync_state = Arc::new(SynchronizationState::with_storage(storage.clone()));
+        let core = BlockChainClientCore::new(
+            ConsensusParams::new(Network::Mainnet),
+            storage,
+            Default::default(),
+            sync_state.clone(),
+        );
 
-        // get info on tx from block#1:
-        // https://zcash.blockexplorer.com/tx/851bf6fbf7a976327817c738c489d7fa657752445430922d94c983c0b9ed4609
-        let verbose_transaction_out = core.verbose_transaction_out(OutPoint {
-            hash: "0946edb9c083c9942d92305444527765fad789c438c717783276a9f7fbf61b85".into(),
-            index: 0,
-        });
-        assert_eq!(verbose_transaction_out, Ok(GetTxOutResponse {
-				bestblock: "8392336da29773c56b1649ab555156ceb7e700ad7c230ea7a4571c7e22bc0700".into(),
-				confirmations: 1,
-				value: 0.0005,
-				script: TransactionOutputScript {
-					asm: "OP_PUSHBYTES_33 0x027a46eb513588b01b37ea24303f4b628afd12cc20df789fede0921e43cad3e875\nOP_CHECKSIG\n".to_owned(),
-					hex: Bytes::from("21027a46eb513588b01b37ea24303f4b628afd12cc20df789fede0921e43cad3e875ac"),
-					req_sigs: 1,
-					script_type: ScriptType::PubKey,
-					addresses: vec!["t1KstPVzcNEK4ZeauQ6cogoqxQBMDSiRnGr".into()]
-				},
-				version: 1,
-				coinbase: true
-			}));
+        let info = core.blockchain_info();
+        assert_eq!(info.chain, "main");
+        assert_eq!(info.blocks, 1);
+        assert_eq!(info.headers, 1);
+        assert_eq!(info.difficulty, 1.0);
+        assert_eq!(
+            info.bestblockhash,
+            zebra_test_data::block_h1().hash().reversed().into()
+        );
+        assert!(info.verificationprogress > 0.0 && info.verificationprogress < 1.0);
+        assert!(!info.pruned);
+        assert_eq!(info.pruneheight, None);
+        assert!(info.size_on_disk > 0);
+        let overwinter = &info.upgrades["5ba81b19"];
+        assert_eq!(overwinter.name, "Overwinter");
+        assert_eq!(overwinter.activationheight, 347500);
+        assert_eq!(overwinter.status, NetworkUpgradeStatus::Pending);
+        // Sprout is not an upgrade
+        assert_eq!(info.upgrades.len(), NetworkUpgrade::all().len() - 1);
+
+        // headers of blocks that are not yet stored are also counted
+        sync_state.update_best_headers_height(10);
+        let info = core.blockchain_info();
+        assert_eq!(info.blocks, 1);
+        assert_eq!(info.headers, 10);
     }
 
     #[test]
-    fn transaction_out_success() {
+    fn block_deltas_success() {
         let client = BlockChainClient::new(SuccessBlockChainClientCore::default());
         let mut handler = IoHandler::new();
         handler.extend_with(client.to_delegate());
@@ -721,18 +3879,18 @@ pub mod tests {
                 &(r#"
 			{
 				"jsonrpc": "2.0",
-				"method": "gettxout",
-				"params": ["4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b", 0],
+				"method": "getblockdeltas",
+				"params": ["000000006a625f06636b8bb6ac7b960a8d03705d1ace08b1a19da3fdcc99ddbd"],
 				"id": 1
 			}"#),
             )
             .unwrap();
 
-        assert_eq!(&sample, r#"{"jsonrpc":"2.0","result":{"bestblock":"0000000000000000000000000000000000000000000000000000000000000056","coinbase":false,"confirmations":777,"scriptPubKey":{"addresses":["t2UNzUUx8mWBCRYPRezvA363EYXyEpHokyi","t2N9PH9Wk9xjqYg9iin1Ua3aekJqfAtE543"],"asm":"Hello, world!!!","hex":"01020304","reqSigs":777,"type":"multisig"},"value":100000.56,"version":33},"id":1}"#);
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","result":{"confirmations":1,"deltas":[{"index":1,"inputs":[{"address":"t1h8SqgtM3QM5e2M8EzhhT1yL2PXXtA6oqe","coinbase":false,"index":0,"prevout":5,"prevtxid":"0400000000000000000000000000000000000000000000000000000000000000","satoshis":-1000}],"outputs":[{"index":0,"satoshis":900,"script":"51"}],"txid":"0300000000000000000000000000000000000000000000000000000000000000"}],"hash":"0100000000000000000000000000000000000000000000000000000000000000","height":2,"time":1000},"id":1}"#);
     }
 
     #[test]
-    fn transaction_out_failure() {
+    fn block_deltas_error() {
         let client = BlockChainClient::new(ErrorBlockChainClientCore::default());
         let mut handler = IoHandler::new();
         handler.extend_with(client.to_delegate());
@@ -742,13 +3900,206 @@ pub mod tests {
                 &(r#"
 			{
 				"jsonrpc": "2.0",
-				"method": "gettxout",
-				"params": ["4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b", 0],
+				"method": "getblockdeltas",
+				"params": ["000000006a625f06636b8bb6ac7b960a8d03705d1ace08b1a19da3fdcc99ddbd"],
 				"id": 1
 			}"#),
             )
             .unwrap();
 
-        assert_eq!(&sample, r#"{"jsonrpc":"2.0","error":{"code":-32099,"message":"Block with given hash is not found","data":"3ba3edfd7a7b12b27ac72c3e67768f617fc81bc3888a51323a9fb8aa4b1e5e4a"},"id":1}"#);
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","error":{"code":-32099,"message":"Block with given hash is not found","data":"000000006a625f06636b8bb6ac7b960a8d03705d1ace08b1a19da3fdcc99ddbd"},"id":1}"#);
+    }
+
+    #[test]
+    fn block_deltas_contents() {
+        let p2pkh = "76a914010000000000000000000000000000000000000088ac";
+        let b0: IndexedBlock = zebra_test_data::block_builder()
+            .header()
+            .build()
+            .transaction()
+            .coinbase()
+            .output()
+            .value(10)
+            .build()
+            .build()
+            .transaction()
+            .output()
+            .value(50)
+            .script_pubkey(p2pkh)
+            .build()
+            .build()
+            .build()
+            .into();
+        let tx0_hash = b0.transactions[1].hash.clone();
+        let b1: IndexedBlock = zebra_test_data::block_builder()
+            .header()
+            .parent(b0.hash().clone())
+            .time(1000)
+            .build()
+            .transaction()
+            .coinbase()
+            .output()
+            .value(10)
+            .build()
+            .build()
+            .transaction()
+            .input()
+            .hash(tx0_hash.clone())
+            .index(0)
+            .build()
+            .output()
+            .value(30)
+            .script_pubkey("51")
+            .build()
+            .output()
+            .value(15)
+            .script_pubkey(p2pkh)
+            .build()
+            .build()
+            .build()
+            .into();
+        let b1_hash = b1.hash().clone();
+        let coinbase_hash = b1.transactions[0].hash.clone();
+        let tx1_hash = b1.transactions[1].hash.clone();
+        let storage = Arc::new(BlockChainDatabase::init_test_chain(vec![b0, b1]));
+        let core = BlockChainClientCore::new(
+            ConsensusParams::new(Network::Mainnet),
+            storage.clone(),
+            Default::default(),
+            Arc::new(SynchronizationState::with_storage(storage)),
+        );
+
+        assert_eq!(
+            core.block_deltas(b1_hash.clone()),
+            Ok(GetBlockDeltasResponse {
+                hash: b1_hash.reversed().into(),
+                height: 1,
+                confirmations: 1,
+                time: 1000,
+                deltas: vec![
+                    TransactionDeltas {
+                        txid: coinbase_hash.reversed().into(),
+                        index: 0,
+                        inputs: vec![InputDelta {
+                            address: None,
+                            satoshis: 0,
+                            index: 0,
+                            prevtxid: GlobalH256::default().into(),
+                            prevout: 0xffffffff,
+                            coinbase: true,
+                        }],
+                        outputs: vec![OutputDelta {
+                            address: None,
+                            satoshis: 10,
+                            index: 0,
+                            script: Bytes::default(),
+                        }],
+                    },
+                    TransactionDeltas {
+                        txid: tx1_hash.reversed().into(),
+                        index: 1,
+                        inputs: vec![InputDelta {
+                            address: Some("t1h8SqgtM3QM5e2M8EzhhT1yL2PXXtA6oqe".into()),
+                            satoshis: -50,
+                            index: 0,
+                            prevtxid: tx0_hash.reversed().into(),
+                            prevout: 0,
+                            coinbase: false,
+                        }],
+                        outputs: vec![
+                            OutputDelta {
+                                address: None,
+                                satoshis: 30,
+                                index: 0,
+                                script: Bytes::from("51"),
+                            },
+                            OutputDelta {
+                                address: Some("t1h8SqgtM3QM5e2M8EzhhT1yL2PXXtA6oqe".into()),
+                                satoshis: 15,
+                                index: 1,
+                                script: Bytes::from(p2pkh),
+                            },
+                        ],
+                    },
+                ],
+            })
+        );
+        assert_eq!(
+            core.block_deltas(GlobalH256::from(1)),
+            Err(block_not_found(GlobalH256::from(1).reversed()))
+        );
+    }
+
+    #[test]
+    fn block_deltas_of_pruned_database() {
+        let b0: IndexedBlock = zebra_test_data::block_builder()
+            .header()
+            .build()
+            .transaction()
+            .coinbase()
+            .output()
+            .value(10)
+            .build()
+            .build()
+            .transaction()
+            .output()
+            .value(50)
+            .build()
+            .build()
+            .build()
+            .into();
+        let tx0_hash = b0.transactions[1].hash.clone();
+        let b1: IndexedBlock = zebra_test_data::block_builder()
+            .header()
+            .parent(b0.hash().clone())
+            .build()
+            .transaction()
+            .coinbase()
+            .output()
+            .value(20)
+            .build()
+            .build()
+            .transaction()
+            .input()
+            .hash(tx0_hash)
+            .index(0)
+            .build()
+            .output()
+            .value(30)
+            .build()
+            .build()
+            .build()
+            .into();
+        let b2: IndexedBlock = zebra_test_data::block_builder()
+            .header()
+            .parent(b1.hash().clone())
+            .build()
+            .transaction()
+            .coinbase()
+            .output()
+            .value(40)
+            .build()
+            .build()
+            .build()
+            .into();
+        let b0_hash = b0.hash().clone();
+        let b1_hash = b1.hash().clone();
+        let b2_hash = b2.hash().clone();
+        let mut storage = BlockChainDatabase::init_test_chain(vec![b0, b1, b2]);
+        storage.set_max_fork_route(0);
+        storage.prune_below(1).unwrap();
+        let core = BlockChainClientCore::new(
+            ConsensusParams::new(Network::Mainnet),
+            storage.clone(),
+            Default::default(),
+            Arc::new(SynchronizationState::with_storage(storage)),
+        );
+
+        // block below the prune height
+        assert_eq!(core.block_deltas(b0_hash), Err(block_deltas_unavailable()));
+        // block spends output of the block below the prune height
+        assert_eq!(core.block_deltas(b1_hash), Err(block_deltas_unavailable()));
+        // block above the prune height, that doesn't spend pruned outputs
+        assert!(core.block_deltas(b2_hash).is_ok());
     }
 }
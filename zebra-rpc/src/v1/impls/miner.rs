@@ -1,29 +1,60 @@
 use jsonrpc_core::Error;
-use v1::helpers::errors::execution;
+use ser::{deserialize, Reader};
+use std::cmp;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::time::Duration;
+use v1::helpers::errors::{execution, invalid_params, transaction_not_found};
 use v1::traits::Miner;
-use v1::types::{BlockTemplate, BlockTemplateRequest};
+use v1::types::{
+    BlockTemplate, BlockTemplateRequest, EstimateSmartFeeResponse, GetMemPoolInfoResponse,
+    GetRawMemPoolResponse, MemPoolEntry, RawBlock, RescanBlockChainResponse, H256,
+};
+use zebra_chain::constants::SATOSHIS_IN_COIN;
+use zebra_chain::{Block, IndexedBlock};
 use zebra_keys::Address;
 use zebra_miner;
+use zebra_primitives::hash::H256 as GlobalH256;
 use zebra_sync;
 
+/// Maximal duration of the long poll `getblocktemplate` request (in seconds).
+const LONG_POLL_TIMEOUT_SECONDS: u64 = 60;
+
 pub struct MinerClient<T: MinerClientCoreApi> {
     core: T,
 }
 
 pub trait MinerClientCoreApi: Send + Sync + 'static {
     fn get_block_template(&self) -> Result<zebra_miner::BlockTemplate, String>;
+    fn wait_best_block_change(&self, best_block: &GlobalH256, timeout: Duration) -> bool;
+    fn submit_block(&self, block: IndexedBlock) -> zebra_sync::SubmitBlockResult;
+    fn memory_pool_information(&self) -> zebra_miner::MemoryPoolInformation;
+    fn memory_pool_entries(&self) -> Vec<zebra_miner::MemoryPoolEntryInformation>;
+    fn estimate_fee_rate(&self, target: u32) -> Option<u64>;
+    fn rescan_blockchain(&self, start_height: u32) -> Option<(u32, u32)>;
+    fn save_memory_pool(&self) -> Result<(), String>;
+    fn prioritise_transaction(&self, hash: &GlobalH256, fee_delta: i64) -> Result<bool, String>;
+    fn invalidate_block(&self, hash: &GlobalH256) -> Result<(), String>;
+    fn reconsider_block(&self, hash: &GlobalH256) -> Result<bool, String>;
+    fn precious_block(&self, hash: &GlobalH256) -> Result<(), String>;
 }
 
 pub struct MinerClientCore {
     local_sync_node: zebra_sync::LocalNodeRef,
     miner_address: Option<Address>,
+    memory_pool_path: PathBuf,
 }
 
 impl MinerClientCore {
-    pub fn new(local_sync_node: zebra_sync::LocalNodeRef, miner_address: Option<Address>) -> Self {
+    pub fn new(
+        local_sync_node: zebra_sync::LocalNodeRef,
+        miner_address: Option<Address>,
+        memory_pool_path: PathBuf,
+    ) -> Self {
         MinerClientCore {
             local_sync_node: local_sync_node,
             miner_address: miner_address,
+            memory_pool_path: memory_pool_path,
         }
     }
 }
@@ -35,6 +66,53 @@ impl MinerClientCoreApi for MinerClientCore {
             .ok_or_else(|| "miner address not set".into())
             .and_then(|miner_address| self.local_sync_node.get_block_template(miner_address))
     }
+
+    fn wait_best_block_change(&self, best_block: &GlobalH256, timeout: Duration) -> bool {
+        self.local_sync_node
+            .wait_best_block_change(best_block, timeout)
+    }
+
+    fn submit_block(&self, block: IndexedBlock) -> zebra_sync::SubmitBlockResult {
+        self.local_sync_node.submit_block(block)
+    }
+
+    fn memory_pool_information(&self) -> zebra_miner::MemoryPoolInformation {
+        self.local_sync_node.memory_pool_information()
+    }
+
+    fn memory_pool_entries(&self) -> Vec<zebra_miner::MemoryPoolEntryInformation> {
+        self.local_sync_node.memory_pool_entries()
+    }
+
+    fn estimate_fee_rate(&self, target: u32) -> Option<u64> {
+        self.local_sync_node.estimate_fee_rate(target)
+    }
+
+    fn rescan_blockchain(&self, start_height: u32) -> Option<(u32, u32)> {
+        self.local_sync_node.rescan_blockchain(start_height)
+    }
+
+    fn save_memory_pool(&self) -> Result<(), String> {
+        self.local_sync_node
+            .save_memory_pool(&self.memory_pool_path)
+            .map_err(|err| err.to_string())
+    }
+
+    fn prioritise_transaction(&self, hash: &GlobalH256, fee_delta: i64) -> Result<bool, String> {
+        self.local_sync_node.prioritise_transaction(hash, fee_delta)
+    }
+
+    fn invalidate_block(&self, hash: &GlobalH256) -> Result<(), String> {
+        self.local_sync_node.invalidate_block(hash)
+    }
+
+    fn reconsider_block(&self, hash: &GlobalH256) -> Result<bool, String> {
+        self.local_sync_node.reconsider_block(hash)
+    }
+
+    fn precious_block(&self, hash: &GlobalH256) -> Result<(), String> {
+        self.local_sync_node.precious_block(hash)
+    }
 }
 
 impl<T> MinerClient<T>
@@ -44,31 +122,210 @@ where
     pub fn new(core: T) -> Self {
         MinerClient { core: core }
     }
+
+    /// Long poll id of the block template: hash of the best block + number of memory pool transactions
+    fn long_poll_id(&self, block_template: &zebra_miner::BlockTemplate) -> String {
+        format!(
+            "{}{}",
+            block_template.previous_header_hash.to_reversed_str(),
+            self.core.memory_pool_information().transactions_count
+        )
+    }
+}
+
+/// Convert fee rate from satoshis per byte to ZEC per 1000 bytes
+fn fee_rate_per_kb(fee_rate: u64) -> f64 {
+    (fee_rate * 1000) as f64 / SATOSHIS_IN_COIN as f64
 }
 
 impl<T> Miner for MinerClient<T>
 where
     T: MinerClientCoreApi,
 {
-    fn get_block_template(&self, _request: BlockTemplateRequest) -> Result<BlockTemplate, Error> {
-        self.core
+    fn get_block_template(&self, request: BlockTemplateRequest) -> Result<BlockTemplate, Error> {
+        let mut block_template = try!(self
+            .core
             .get_block_template()
-            .map(Into::into)
+            .map_err(|err| execution(&err)));
+        let mut long_poll_id = self.long_poll_id(&block_template);
+
+        // if miner is working on the current template, wait until it is outdated
+        if request.longpollid.as_ref() == Some(&long_poll_id) {
+            self.core.wait_best_block_change(
+                &block_template.previous_header_hash,
+                Duration::from_secs(LONG_POLL_TIMEOUT_SECONDS),
+            );
+            block_template = try!(self
+                .core
+                .get_block_template()
+                .map_err(|err| execution(&err)));
+            long_poll_id = self.long_poll_id(&block_template);
+        }
+
+        let mut block_template: BlockTemplate = block_template.into();
+        block_template.longpollid = Some(long_poll_id);
+        Ok(block_template)
+    }
+
+    fn submit_block(&self, raw_block: RawBlock) -> Result<Option<String>, Error> {
+        let raw_block_data: Vec<u8> = raw_block.into();
+        let block: Block =
+            try!(deserialize(Reader::new(&raw_block_data)).map_err(|e| invalid_params("block", e)));
+        Ok(match self.core.submit_block(block.into()) {
+            zebra_sync::SubmitBlockResult::Accepted => None,
+            zebra_sync::SubmitBlockResult::Duplicate => Some("duplicate".into()),
+            zebra_sync::SubmitBlockResult::Inconclusive => Some("inconclusive".into()),
+            zebra_sync::SubmitBlockResult::Rejected(err) => Some(err),
+        })
+    }
+
+    fn memory_pool_info(&self) -> Result<GetMemPoolInfoResponse, Error> {
+        let information = self.core.memory_pool_information();
+        Ok(GetMemPoolInfoResponse {
+            size: information.transactions_count,
+            bytes: information.transactions_size_in_bytes,
+            usage: information.memory_usage,
+            mempoolminfee: information.minimum_fee_rate as f64 / SATOSHIS_IN_COIN as f64,
+        })
+    }
+
+    fn raw_memory_pool(&self, verbose: Option<bool>) -> Result<GetRawMemPoolResponse, Error> {
+        let entries = self.core.memory_pool_entries();
+        if !verbose.unwrap_or_default() {
+            return Ok(GetRawMemPoolResponse::Hashes(
+                entries
+                    .into_iter()
+                    .map(|entry| entry.hash.reversed().into())
+                    .collect(),
+            ));
+        }
+
+        Ok(GetRawMemPoolResponse::Verbose(
+            entries
+                .into_iter()
+                .map(|entry| {
+                    (
+                        entry.hash.reversed().into(),
+                        MemPoolEntry {
+                            size: entry.size,
+                            fee: entry.fee as f64 / SATOSHIS_IN_COIN as f64,
+                            modifiedfee: (entry.fee as i64 + entry.fee_delta) as f64
+                                / SATOSHIS_IN_COIN as f64,
+                            feedelta: entry.fee_delta as f64 / SATOSHIS_IN_COIN as f64,
+                            time: entry.time,
+                            height: entry.height,
+                            depends: entry
+                                .depends
+                                .into_iter()
+                                .map(|hash| hash.reversed().into())
+                                .collect(),
+                        },
+                    )
+                })
+                .collect::<BTreeMap<_, _>>(),
+        ))
+    }
+
+    fn estimate_fee(&self, blocks: u32) -> Result<f64, Error> {
+        Ok(self
+            .core
+            .estimate_fee_rate(blocks)
+            .map(fee_rate_per_kb)
+            .unwrap_or(-1f64))
+    }
+
+    fn estimate_smart_fee(&self, blocks: u32) -> Result<EstimateSmartFeeResponse, Error> {
+        let blocks = cmp::min(cmp::max(blocks, 1), zebra_miner::MAX_CONFIRMATION_TARGET);
+        Ok((blocks..zebra_miner::MAX_CONFIRMATION_TARGET + 1)
+            .filter_map(|target| {
+                self.core
+                    .estimate_fee_rate(target)
+                    .map(|fee_rate| EstimateSmartFeeResponse {
+                        feerate: Some(fee_rate_per_kb(fee_rate)),
+                        blocks: target,
+                    })
+            })
+            .nth(0)
+            .unwrap_or_else(|| EstimateSmartFeeResponse {
+                feerate: None,
+                blocks: blocks,
+            }))
+    }
+
+    fn rescan_blockchain(
+        &self,
+        start_height: Option<u32>,
+    ) -> Result<RescanBlockChainResponse, Error> {
+        let start_height = start_height.unwrap_or(0);
+        self.core
+            .rescan_blockchain(start_height)
+            .map(|(start_height, stop_height)| RescanBlockChainResponse {
+                start_height: start_height,
+                stop_height: stop_height,
+            })
+            .ok_or_else(|| invalid_params("start_height", start_height))
+    }
+
+    fn save_memory_pool(&self) -> Result<(), Error> {
+        self.core.save_memory_pool().map_err(|err| execution(&err))
+    }
+
+    fn prioritise_transaction(&self, hash: H256, fee_delta: i64) -> Result<bool, Error> {
+        let global_hash: GlobalH256 = hash.clone().into();
+        let is_found = self
+            .core
+            .prioritise_transaction(&global_hash.reversed(), fee_delta)
+            .map_err(|err| invalid_params("fee_delta", err))?;
+        if !is_found {
+            return Err(transaction_not_found(hash));
+        }
+
+        Ok(true)
+    }
+
+    fn invalidate_block(&self, hash: H256) -> Result<(), Error> {
+        let global_hash: GlobalH256 = hash.into();
+        self.core
+            .invalidate_block(&global_hash.reversed())
+            .map_err(|err| execution(&err))
+    }
+
+    fn reconsider_block(&self, hash: H256) -> Result<(), Error> {
+        let global_hash: GlobalH256 = hash.into();
+        // reconsidering block that has not been invalidated is a no-op
+        self.core
+            .reconsider_block(&global_hash.reversed())
+            .map(|_| ())
+            .map_err(|err| execution(&err))
+    }
+
+    fn precious_block(&self, hash: H256) -> Result<(), Error> {
+        let global_hash: GlobalH256 = hash.into();
+        self.core
+            .precious_block(&global_hash.reversed())
             .map_err(|err| execution(&err))
     }
 }
 
 #[cfg(test)]
 pub mod tests {
+    extern crate zebra_test_data;
+
     use super::*;
+    use hex::ToHex;
     use jsonrpc_core::IoHandler;
+    use ser::serialize;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
     use v1::traits::Miner;
     use zebra_chain;
     use zebra_miner;
     use zebra_primitives::hash::H256;
 
     #[derive(Default)]
-    struct SuccessMinerClientCore;
+    struct SuccessMinerClientCore {
+        best_block_waits: Arc<AtomicUsize>,
+    }
 
     impl MinerClientCoreApi for SuccessMinerClientCore {
         fn get_block_template(&self) -> Result<zebra_miner::BlockTemplate, String> {
@@ -78,6 +335,8 @@ pub mod tests {
                 previous_header_hash: H256::from(1),
                 final_sapling_root_hash: H256::from(2),
                 time: 33,
+                min_time: 22,
+                max_time: 66,
                 bits: 44.into(),
                 height: 55,
                 transactions: vec![tx.into()],
@@ -86,6 +345,108 @@ pub mod tests {
                 sigop_limit: 88,
             })
         }
+
+        fn wait_best_block_change(&self, _best_block: &GlobalH256, _timeout: Duration) -> bool {
+            self.best_block_waits.fetch_add(1, Ordering::SeqCst);
+            false
+        }
+
+        fn submit_block(&self, block: IndexedBlock) -> zebra_sync::SubmitBlockResult {
+            if block.header.hash == zebra_test_data::genesis().hash() {
+                zebra_sync::SubmitBlockResult::Duplicate
+            } else if block.header.hash == zebra_test_data::block_h1().hash() {
+                zebra_sync::SubmitBlockResult::Accepted
+            } else if block.header.hash == zebra_test_data::block_h2().hash() {
+                zebra_sync::SubmitBlockResult::Inconclusive
+            } else {
+                zebra_sync::SubmitBlockResult::Rejected("MerkleRoot".into())
+            }
+        }
+
+        fn memory_pool_information(&self) -> zebra_miner::MemoryPoolInformation {
+            zebra_miner::MemoryPoolInformation {
+                transactions_count: 2,
+                transactions_size_in_bytes: 300,
+                memory_usage: 1000,
+                minimum_fee_rate: 10_000,
+                min_relay_fee_rate: 100,
+            }
+        }
+
+        fn memory_pool_entries(&self) -> Vec<zebra_miner::MemoryPoolEntryInformation> {
+            vec![
+                zebra_miner::MemoryPoolEntryInformation {
+                    hash: H256::from(1),
+                    size: 100,
+                    fee: 10_000,
+                    fee_delta: 0,
+                    time: 33,
+                    height: 44,
+                    depends: vec![],
+                },
+                zebra_miner::MemoryPoolEntryInformation {
+                    hash: H256::from(2),
+                    size: 200,
+                    fee: 20_000,
+                    fee_delta: 10_000,
+                    time: 55,
+                    height: 66,
+                    depends: vec![H256::from(1)],
+                },
+            ]
+        }
+
+        fn estimate_fee_rate(&self, target: u32) -> Option<u64> {
+            if target >= 3 {
+                Some(10)
+            } else {
+                None
+            }
+        }
+
+        fn rescan_blockchain(&self, start_height: u32) -> Option<(u32, u32)> {
+            if start_height <= 10 {
+                Some((start_height, 10))
+            } else {
+                None
+            }
+        }
+
+        fn save_memory_pool(&self) -> Result<(), String> {
+            Ok(())
+        }
+
+        fn prioritise_transaction(
+            &self,
+            hash: &GlobalH256,
+            fee_delta: i64,
+        ) -> Result<bool, String> {
+            if fee_delta == i64::max_value() {
+                return Err("Accumulated fee delta is out of range".into());
+            }
+
+            Ok(*hash == H256::from(1))
+        }
+
+        fn invalidate_block(&self, hash: &GlobalH256) -> Result<(), String> {
+            if *hash == H256::from(1) {
+                Ok(())
+            } else {
+                Err("Block not found".into())
+            }
+        }
+
+        fn reconsider_block(&self, hash: &GlobalH256) -> Result<bool, String> {
+            Ok(*hash == H256::from(1))
+        }
+
+        fn precious_block(&self, hash: &GlobalH256) -> Result<(), String> {
+            if *hash == H256::from(1) {
+                Ok(())
+            } else {
+                Err("Block not found".into())
+            }
+        }
     }
 
     #[test]
@@ -108,6 +469,475 @@ pub mod tests {
 
         // direct hash is 0100000000000000000000000000000000000000000000000000000000000000
         // but client expects reverse hash
-        assert_eq!(&sample, r#"{"jsonrpc":"2.0","result":{"bits":44,"coinbasetxn":{"data":"00000000000000000000","depends":null,"fee":null,"hash":null,"required":false,"sigops":null},"curtime":33,"finalsaplingroothash":"0000000000000000000000000000000000000000000000000000000000000000","height":55,"mintime":null,"mutable":null,"noncerange":null,"previousblockhash":"0000000000000000000000000000000000000000000000000000000000000001","sigoplimit":88,"sizelimit":77,"target":"0000000000000000000000000000000000000000000000000000000000000000","transactions":[{"data":"00000000013ba3edfd7a7b12b27ac72c3e67768f617fc81bc3888a51323a9fb8aa4b1e5e4a0000000000000000000101000000000000000000000000","depends":null,"fee":null,"hash":null,"required":false,"sigops":null}],"version":777},"id":1}"#);
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","result":{"bits":44,"coinbasetxn":{"data":"00000000000000000000","depends":null,"fee":null,"hash":null,"required":false,"sigops":null},"curtime":33,"finalsaplingroothash":"0000000000000000000000000000000000000000000000000000000000000000","height":55,"longpollid":"00000000000000000000000000000000000000000000000000000000000000012","maxtime":66,"mintime":22,"mutable":null,"noncerange":null,"previousblockhash":"0000000000000000000000000000000000000000000000000000000000000001","sigoplimit":88,"sizelimit":77,"target":"0000000000000000000000000000000000000000000000000000000000000000","transactions":[{"data":"00000000013ba3edfd7a7b12b27ac72c3e67768f617fc81bc3888a51323a9fb8aa4b1e5e4a0000000000000000000101000000000000000000000000","depends":null,"fee":null,"hash":null,"required":false,"sigops":null}],"version":777},"id":1}"#);
+    }
+
+    #[test]
+    fn getblocktemplate_long_poll() {
+        let core = SuccessMinerClientCore::default();
+        let best_block_waits = core.best_block_waits.clone();
+        let client = MinerClient::new(core);
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        // outdated long poll id => template is returned immediately
+        let sample = handler
+            .handle_request_sync(
+                &(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "getblocktemplate",
+				"params": [{"longpollid": "00000000000000000000000000000000000000000000000000000000000000011"}],
+				"id": 1
+			}"#),
+            )
+            .unwrap();
+        assert!(sample.contains(
+            r#""longpollid":"00000000000000000000000000000000000000000000000000000000000000012""#
+        ));
+        assert_eq!(best_block_waits.load(Ordering::SeqCst), 0);
+
+        // current long poll id => waiting for best block change
+        let sample = handler
+            .handle_request_sync(
+                &(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "getblocktemplate",
+				"params": [{"longpollid": "00000000000000000000000000000000000000000000000000000000000000012"}],
+				"id": 1
+			}"#),
+            )
+            .unwrap();
+        assert!(sample.contains(
+            r#""longpollid":"00000000000000000000000000000000000000000000000000000000000000012""#
+        ));
+        assert_eq!(best_block_waits.load(Ordering::SeqCst), 1);
+    }
+
+    fn submit_block(handler: &IoHandler, block: zebra_chain::Block) -> String {
+        handler
+            .handle_request_sync(&format!(
+                r#"{{"jsonrpc": "2.0", "method": "submitblock", "params": ["{}"], "id": 1}}"#,
+                serialize(&block).to_hex::<String>()
+            ))
+            .unwrap()
+    }
+
+    #[test]
+    fn submitblock_result_codes() {
+        let client = MinerClient::new(SuccessMinerClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        assert_eq!(
+            submit_block(&handler, zebra_test_data::block_h1()),
+            r#"{"jsonrpc":"2.0","result":null,"id":1}"#
+        );
+        assert_eq!(
+            submit_block(&handler, zebra_test_data::genesis()),
+            r#"{"jsonrpc":"2.0","result":"duplicate","id":1}"#
+        );
+        assert_eq!(
+            submit_block(&handler, zebra_test_data::block_h2()),
+            r#"{"jsonrpc":"2.0","result":"inconclusive","id":1}"#
+        );
+        assert_eq!(
+            submit_block(&handler, zebra_test_data::block_h3()),
+            r#"{"jsonrpc":"2.0","result":"MerkleRoot","id":1}"#
+        );
+    }
+
+    #[test]
+    fn submitblock_invalid_block() {
+        let client = MinerClient::new(SuccessMinerClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler
+            .handle_request_sync(
+                &(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "submitblock",
+				"params": ["0102"],
+				"id": 1
+			}"#),
+            )
+            .unwrap();
+
+        assert!(sample.contains(r#""code":-32602"#));
+    }
+
+    #[test]
+    fn getmempoolinfo_accepted() {
+        let client = MinerClient::new(SuccessMinerClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler
+            .handle_request_sync(
+                &(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "getmempoolinfo",
+				"params": [],
+				"id": 1
+			}"#),
+            )
+            .unwrap();
+
+        assert_eq!(
+            &sample,
+            r#"{"jsonrpc":"2.0","result":{"bytes":300,"mempoolminfee":0.0001,"size":2,"usage":1000},"id":1}"#
+        );
+    }
+
+    #[test]
+    fn getrawmempool_accepted() {
+        let client = MinerClient::new(SuccessMinerClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler
+            .handle_request_sync(
+                &(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "getrawmempool",
+				"params": [],
+				"id": 1
+			}"#),
+            )
+            .unwrap();
+
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","result":["0000000000000000000000000000000000000000000000000000000000000001","0000000000000000000000000000000000000000000000000000000000000002"],"id":1}"#);
+    }
+
+    #[test]
+    fn getrawmempool_verbose_accepted() {
+        let client = MinerClient::new(SuccessMinerClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler
+            .handle_request_sync(
+                &(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "getrawmempool",
+				"params": [true],
+				"id": 1
+			}"#),
+            )
+            .unwrap();
+
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","result":{"0000000000000000000000000000000000000000000000000000000000000001":{"depends":[],"fee":0.0001,"feedelta":0.0,"height":44,"modifiedfee":0.0001,"size":100,"time":33},"0000000000000000000000000000000000000000000000000000000000000002":{"depends":["0000000000000000000000000000000000000000000000000000000000000001"],"fee":0.0002,"feedelta":0.0001,"height":66,"modifiedfee":0.0003,"size":200,"time":55}},"id":1}"#);
+    }
+
+    #[test]
+    fn estimatefee_accepted() {
+        let client = MinerClient::new(SuccessMinerClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler
+            .handle_request_sync(
+                &(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "estimatefee",
+				"params": [3],
+				"id": 1
+			}"#),
+            )
+            .unwrap();
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","result":0.0001,"id":1}"#);
+
+        let sample = handler
+            .handle_request_sync(
+                &(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "estimatefee",
+				"params": [1],
+				"id": 1
+			}"#),
+            )
+            .unwrap();
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","result":-1.0,"id":1}"#);
+    }
+
+    #[test]
+    fn estimatesmartfee_accepted() {
+        let client = MinerClient::new(SuccessMinerClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        // no data for target=1 => estimation for target=3 is returned
+        let sample = handler
+            .handle_request_sync(
+                &(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "estimatesmartfee",
+				"params": [1],
+				"id": 1
+			}"#),
+            )
+            .unwrap();
+        assert_eq!(
+            &sample,
+            r#"{"jsonrpc":"2.0","result":{"blocks":3,"feerate":0.0001},"id":1}"#
+        );
+    }
+
+    #[test]
+    fn rescanblockchain_accepted() {
+        let client = MinerClient::new(SuccessMinerClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler
+            .handle_request_sync(
+                &(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "rescanblockchain",
+				"params": [5],
+				"id": 1
+			}"#),
+            )
+            .unwrap();
+        assert_eq!(
+            &sample,
+            r#"{"jsonrpc":"2.0","result":{"start_height":5,"stop_height":10},"id":1}"#
+        );
+    }
+
+    #[test]
+    fn rescanblockchain_from_genesis() {
+        let client = MinerClient::new(SuccessMinerClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler
+            .handle_request_sync(
+                &(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "rescanblockchain",
+				"params": [],
+				"id": 1
+			}"#),
+            )
+            .unwrap();
+        assert_eq!(
+            &sample,
+            r#"{"jsonrpc":"2.0","result":{"start_height":0,"stop_height":10},"id":1}"#
+        );
+    }
+
+    #[test]
+    fn rescanblockchain_above_best_block() {
+        let client = MinerClient::new(SuccessMinerClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler
+            .handle_request_sync(
+                &(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "rescanblockchain",
+				"params": [11],
+				"id": 1
+			}"#),
+            )
+            .unwrap();
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"Couldn't parse parameters: start_height","data":"11"},"id":1}"#);
+    }
+
+    #[test]
+    fn savemempool_accepted() {
+        let client = MinerClient::new(SuccessMinerClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler
+            .handle_request_sync(
+                &(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "savemempool",
+				"params": [],
+				"id": 1
+			}"#),
+            )
+            .unwrap();
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","result":null,"id":1}"#);
+    }
+
+    #[test]
+    fn prioritisetransaction_accepted() {
+        let client = MinerClient::new(SuccessMinerClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler
+            .handle_request_sync(
+                &(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "prioritisetransaction",
+				"params": ["0000000000000000000000000000000000000000000000000000000000000001", 10000],
+				"id": 1
+			}"#),
+            )
+            .unwrap();
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","result":true,"id":1}"#);
+    }
+
+    #[test]
+    fn prioritisetransaction_not_found() {
+        let client = MinerClient::new(SuccessMinerClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler
+            .handle_request_sync(
+                &(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "prioritisetransaction",
+				"params": ["0000000000000000000000000000000000000000000000000000000000000002", -10000],
+				"id": 1
+			}"#),
+            )
+            .unwrap();
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","error":{"code":-32096,"message":"Transaction with given hash is not found","data":"0000000000000000000000000000000000000000000000000000000000000002"},"id":1}"#);
+    }
+
+    #[test]
+    fn prioritisetransaction_overflow() {
+        let client = MinerClient::new(SuccessMinerClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler
+            .handle_request_sync(
+                &(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "prioritisetransaction",
+				"params": ["0000000000000000000000000000000000000000000000000000000000000001", 9223372036854775807],
+				"id": 1
+			}"#),
+            )
+            .unwrap();
+        assert_eq!(
+            &sample,
+            r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"Couldn't parse parameters: fee_delta","data":"\"Accumulated fee delta is out of range\""},"id":1}"#
+        );
+    }
+
+    #[test]
+    fn invalidateblock_accepted() {
+        let client = MinerClient::new(SuccessMinerClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler
+            .handle_request_sync(
+                &(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "invalidateblock",
+				"params": ["0000000000000000000000000000000000000000000000000000000000000001"],
+				"id": 1
+			}"#),
+            )
+            .unwrap();
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","result":null,"id":1}"#);
+    }
+
+    #[test]
+    fn invalidateblock_not_found() {
+        let client = MinerClient::new(SuccessMinerClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler
+            .handle_request_sync(
+                &(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "invalidateblock",
+				"params": ["0000000000000000000000000000000000000000000000000000000000000002"],
+				"id": 1
+			}"#),
+            )
+            .unwrap();
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","error":{"code":-32015,"message":"Execution error.","data":"\"Block not found\""},"id":1}"#);
+    }
+
+    #[test]
+    fn reconsiderblock_accepted() {
+        let client = MinerClient::new(SuccessMinerClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler
+            .handle_request_sync(
+                &(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "reconsiderblock",
+				"params": ["0000000000000000000000000000000000000000000000000000000000000002"],
+				"id": 1
+			}"#),
+            )
+            .unwrap();
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","result":null,"id":1}"#);
+    }
+
+    #[test]
+    fn preciousblock_accepted() {
+        let client = MinerClient::new(SuccessMinerClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler
+            .handle_request_sync(
+                &(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "preciousblock",
+				"params": ["0000000000000000000000000000000000000000000000000000000000000001"],
+				"id": 1
+			}"#),
+            )
+            .unwrap();
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","result":null,"id":1}"#);
+    }
+
+    #[test]
+    fn preciousblock_not_found() {
+        let client = MinerClient::new(SuccessMinerClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler
+            .handle_request_sync(
+                &(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "preciousblock",
+				"params": ["0000000000000000000000000000000000000000000000000000000000000002"],
+				"id": 1
+			}"#),
+            )
+            .unwrap();
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","error":{"code":-32015,"message":"Execution error.","data":"\"Block not found\""},"id":1}"#);
     }
 }
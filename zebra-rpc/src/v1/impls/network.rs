@@ -3,16 +3,37 @@ use std::net::{IpAddr, SocketAddr};
 use std::sync::Arc;
 use v1::helpers::errors;
 use v1::traits::Network as NetworkRpc;
-use v1::types::{AddNodeOperation, NodeInfo};
+use v1::types::{
+    AddNodeOperation, BannedSubnet, ConnectedPeerInfo, GetNetTotalsResponse,
+    GetNetworkInfoResponse, NetworkInfo, NodeInfo, SetBanOperation,
+};
+use zebra_chain::constants::SATOSHIS_IN_COIN;
 use zebra_p2p;
+use zebra_sync;
 
 pub trait NetworkApi: Send + Sync + 'static {
     fn add_node(&self, socket_addr: SocketAddr) -> Result<(), zebra_p2p::NodeTableError>;
     fn remove_node(&self, socket_addr: SocketAddr) -> Result<(), zebra_p2p::NodeTableError>;
     fn connect(&self, socket_addr: SocketAddr);
+    fn disconnect_address(&self, socket_addr: SocketAddr) -> bool;
+    fn disconnect_peer(&self, peer_id: zebra_p2p::PeerId) -> bool;
     fn node_info(&self, node_addr: IpAddr) -> Result<NodeInfo, zebra_p2p::NodeTableError>;
     fn nodes_info(&self) -> Vec<NodeInfo>;
     fn connection_count(&self) -> usize;
+    fn network_info(&self) -> GetNetworkInfoResponse;
+    fn set_network_active(&self, active: bool);
+    fn peers_info(&self) -> Vec<ConnectedPeerInfo>;
+    fn ping(&self);
+    fn net_totals(&self) -> zebra_p2p::NetTotals;
+    fn ban(
+        &self,
+        subnet: zebra_p2p::IpSubnet,
+        ban_time: Option<u32>,
+    ) -> Result<(), zebra_p2p::BanListError>;
+    fn unban(&self, subnet: zebra_p2p::IpSubnet) -> Result<(), zebra_p2p::BanListError>;
+    fn banned(&self) -> Vec<zebra_p2p::BanEntry>;
+    fn clear_banned(&self);
+    fn stop(&self);
 }
 
 impl<T> NetworkRpc for NetworkClient<T>
@@ -40,6 +61,32 @@ where
         }
     }
 
+    fn disconnect_node(&self, node: Option<String>, node_id: Option<usize>) -> Result<(), Error> {
+        let disconnected = match (node, node_id) {
+            (Some(ref node), None) if !node.is_empty() => {
+                let addr = try!(node.parse().map_err(|_| errors::invalid_params(
+                    "node",
+                    "Invalid socket address format, should be ip:port (127.0.0.1:8008)"
+                )));
+                self.api.disconnect_address(addr)
+            }
+            (None, Some(node_id)) => self.api.disconnect_peer(node_id),
+            (Some(ref node), Some(node_id)) if node.is_empty() => self.api.disconnect_peer(node_id),
+            _ => {
+                return Err(errors::invalid_params(
+                    "node",
+                    "Either node address or node id must be specified",
+                ))
+            }
+        };
+
+        if disconnected {
+            Ok(())
+        } else {
+            Err(errors::node_not_connected())
+        }
+    }
+
     fn node_info(&self, _dns: bool, node_addr: Option<String>) -> Result<Vec<NodeInfo>, Error> {
         let node_addr: Option<String> = node_addr.into();
         Ok(match node_addr {
@@ -61,6 +108,70 @@ where
     fn connection_count(&self) -> Result<usize, Error> {
         Ok(self.api.connection_count())
     }
+
+    fn network_info(&self) -> Result<GetNetworkInfoResponse, Error> {
+        Ok(self.api.network_info())
+    }
+
+    fn set_network_active(&self, active: bool) -> Result<bool, Error> {
+        self.api.set_network_active(active);
+        Ok(active)
+    }
+
+    fn peer_info(&self) -> Result<Vec<ConnectedPeerInfo>, Error> {
+        Ok(self.api.peers_info())
+    }
+
+    fn ping(&self) -> Result<(), Error> {
+        self.api.ping();
+        Ok(())
+    }
+
+    fn net_totals(&self) -> Result<GetNetTotalsResponse, Error> {
+        let totals = self.api.net_totals();
+        let now = ::time::get_time();
+        Ok(GetNetTotalsResponse {
+            totalbytesrecv: totals.bytes_received,
+            totalbytessent: totals.bytes_sent,
+            timemillis: now.sec * 1000 + i64::from(now.nsec / 1_000_000),
+        })
+    }
+
+    fn set_ban(
+        &self,
+        subnet: String,
+        operation: SetBanOperation,
+        ban_time: Option<u32>,
+    ) -> Result<(), Error> {
+        let subnet = try!(subnet.parse().map_err(|_| errors::invalid_params(
+            "subnet",
+            "Invalid subnet format, should be ip or ip/prefix (192.168.0.0/24)"
+        )));
+        match operation {
+            SetBanOperation::Add => self
+                .api
+                .ban(subnet, ban_time)
+                .map_err(|_| errors::subnet_already_banned()),
+            SetBanOperation::Remove => self
+                .api
+                .unban(subnet)
+                .map_err(|_| errors::subnet_not_banned()),
+        }
+    }
+
+    fn list_banned(&self) -> Result<Vec<BannedSubnet>, Error> {
+        Ok(self.api.banned().into_iter().map(Into::into).collect())
+    }
+
+    fn clear_banned(&self) -> Result<(), Error> {
+        self.api.clear_banned();
+        Ok(())
+    }
+
+    fn stop(&self) -> Result<String, Error> {
+        self.api.stop();
+        Ok("zebra stopping".into())
+    }
 }
 
 pub struct NetworkClient<T: NetworkApi> {
@@ -78,17 +189,34 @@ where
 
 pub struct NetworkClientCore {
     p2p: Arc<zebra_p2p::Context>,
+    memory_pool: zebra_sync::MemoryPoolRef,
+    client_version: u32,
+    shutdown: zebra_p2p::Shutdown,
 }
 
 impl NetworkClientCore {
-    pub fn new(p2p: Arc<zebra_p2p::Context>) -> Self {
-        NetworkClientCore { p2p: p2p }
+    pub fn new(
+        p2p: Arc<zebra_p2p::Context>,
+        memory_pool: zebra_sync::MemoryPoolRef,
+        client_version: u32,
+        shutdown: zebra_p2p::Shutdown,
+    ) -> Self {
+        NetworkClientCore {
+            p2p: p2p,
+            memory_pool: memory_pool,
+            client_version: client_version,
+            shutdown: shutdown,
+        }
     }
 }
 
 impl NetworkApi for NetworkClientCore {
     fn add_node(&self, socket_addr: SocketAddr) -> Result<(), zebra_p2p::NodeTableError> {
-        self.p2p.add_node(socket_addr)
+        try!(self.p2p.add_node(socket_addr));
+        if !self.p2p.connections().addresses().contains(&socket_addr) {
+            zebra_p2p::Context::connect_added_node(self.p2p.clone(), socket_addr);
+        }
+        Ok(())
     }
 
     fn remove_node(&self, socket_addr: SocketAddr) -> Result<(), zebra_p2p::NodeTableError> {
@@ -96,7 +224,15 @@ impl NetworkApi for NetworkClientCore {
     }
 
     fn connect(&self, socket_addr: SocketAddr) {
-        zebra_p2p::Context::connect_normal(self.p2p.clone(), socket_addr);
+        zebra_p2p::Context::connect_added_node(self.p2p.clone(), socket_addr);
+    }
+
+    fn disconnect_address(&self, socket_addr: SocketAddr) -> bool {
+        self.p2p.disconnect_address(&socket_addr)
+    }
+
+    fn disconnect_peer(&self, peer_id: zebra_p2p::PeerId) -> bool {
+        self.p2p.disconnect_peer(peer_id)
     }
 
     fn node_info(&self, node_addr: IpAddr) -> Result<NodeInfo, zebra_p2p::NodeTableError> {
@@ -147,4 +283,466 @@ impl NetworkApi for NetworkClientCore {
     fn connection_count(&self) -> usize {
         self.p2p.connections().count()
     }
+
+    fn network_info(&self) -> GetNetworkInfoResponse {
+        let config = self.p2p.config();
+        let (connections_in, maxconnections_in) = self.p2p.inbound_connections();
+        let (connections_out, maxconnections_out) = self.p2p.outbound_connections();
+        let min_relay_fee_rate = self.memory_pool.read().min_relay_fee_rate();
+        let network_info = |name: &str, reachable: bool| NetworkInfo {
+            name: name.into(),
+            limited: !reachable,
+            reachable: reachable,
+        };
+
+        GetNetworkInfoResponse {
+            version: self.client_version,
+            subversion: config.connection.user_agent.clone(),
+            protocolversion: config.connection.protocol_version,
+            localservices: format!("{:016x}", u64::from(config.connection.services)),
+            connections: self.p2p.connections().count(),
+            connections_in: connections_in,
+            connections_out: connections_out,
+            maxconnections_in: maxconnections_in,
+            maxconnections_out: maxconnections_out,
+            networkactive: self.p2p.is_network_active(),
+            networks: vec![
+                network_info(
+                    "ipv4",
+                    config.internet_protocol != zebra_p2p::InternetProtocol::IpV6,
+                ),
+                network_info(
+                    "ipv6",
+                    config.internet_protocol != zebra_p2p::InternetProtocol::IpV4,
+                ),
+                // connections over tor are not supported (yet)
+                network_info("onion", false),
+            ],
+            relayfee: min_relay_fee_rate as f64 / SATOSHIS_IN_COIN as f64,
+            warnings: String::new(),
+        }
+    }
+
+    fn set_network_active(&self, active: bool) {
+        self.p2p.set_network_active(active)
+    }
+
+    fn peers_info(&self) -> Vec<ConnectedPeerInfo> {
+        self.p2p
+            .connections()
+            .statistics()
+            .into_iter()
+            .map(|statistics| {
+                let ban_score = self.p2p.ban_score(&statistics.info.address.ip());
+                let mut info: ConnectedPeerInfo = statistics.into();
+                info.banscore = Some(ban_score);
+                info
+            })
+            .collect()
+    }
+
+    fn ping(&self) {
+        self.p2p.ping_all()
+    }
+
+    fn net_totals(&self) -> zebra_p2p::NetTotals {
+        self.p2p.net_totals()
+    }
+
+    fn ban(
+        &self,
+        subnet: zebra_p2p::IpSubnet,
+        ban_time: Option<u32>,
+    ) -> Result<(), zebra_p2p::BanListError> {
+        self.p2p.ban(subnet, ban_time)
+    }
+
+    fn unban(&self, subnet: zebra_p2p::IpSubnet) -> Result<(), zebra_p2p::BanListError> {
+        self.p2p.unban(&subnet)
+    }
+
+    fn banned(&self) -> Vec<zebra_p2p::BanEntry> {
+        self.p2p.banned()
+    }
+
+    fn clear_banned(&self) {
+        self.p2p.clear_banned()
+    }
+
+    /// Connections are closed and the database is flushed by the node, once the event loop has stopped.
+    fn stop(&self) {
+        self.shutdown.request()
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use jsonrpc_core::IoHandler;
+
+    #[derive(Default)]
+    struct SuccessNetworkApi;
+
+    impl NetworkApi for SuccessNetworkApi {
+        fn add_node(&self, _socket_addr: SocketAddr) -> Result<(), zebra_p2p::NodeTableError> {
+            Ok(())
+        }
+
+        fn remove_node(&self, _socket_addr: SocketAddr) -> Result<(), zebra_p2p::NodeTableError> {
+            Ok(())
+        }
+
+        fn connect(&self, _socket_addr: SocketAddr) {}
+
+        fn disconnect_address(&self, socket_addr: SocketAddr) -> bool {
+            socket_addr.port() == 8233
+        }
+
+        fn disconnect_peer(&self, peer_id: zebra_p2p::PeerId) -> bool {
+            peer_id == 0
+        }
+
+        fn node_info(&self, _node_addr: IpAddr) -> Result<NodeInfo, zebra_p2p::NodeTableError> {
+            Err(zebra_p2p::NodeTableError::NoAddressInTable)
+        }
+
+        fn nodes_info(&self) -> Vec<NodeInfo> {
+            Vec::new()
+        }
+
+        fn connection_count(&self) -> usize {
+            1
+        }
+
+        fn network_info(&self) -> GetNetworkInfoResponse {
+            GetNetworkInfoResponse {
+                version: 10000,
+                subversion: "zebra".into(),
+                protocolversion: 170007,
+                localservices: "0000000000000001".into(),
+                connections: 1,
+                connections_in: 0,
+                connections_out: 1,
+                maxconnections_in: 10,
+                maxconnections_out: 10,
+                networkactive: true,
+                networks: vec![NetworkInfo {
+                    name: "ipv4".into(),
+                    limited: false,
+                    reachable: true,
+                }],
+                relayfee: 0.0001,
+                warnings: "".into(),
+            }
+        }
+
+        fn set_network_active(&self, _active: bool) {}
+
+        fn peers_info(&self) -> Vec<ConnectedPeerInfo> {
+            vec![ConnectedPeerInfo {
+                id: 0,
+                addr: "127.0.0.1:8233".into(),
+                inbound: false,
+                version: Some(170007),
+                subver: Some("/MagicBean:2.0.2/".into()),
+                services: Some("0000000000000001".into()),
+                startingheight: Some(500000),
+                banscore: None,
+                conntime: 1540000000,
+                bytessent: 1024,
+                bytesrecv: 2048,
+            }]
+        }
+
+        fn ping(&self) {}
+
+        fn net_totals(&self) -> zebra_p2p::NetTotals {
+            zebra_p2p::NetTotals {
+                bytes_sent: 1024,
+                bytes_received: 2048,
+            }
+        }
+
+        fn ban(
+            &self,
+            subnet: zebra_p2p::IpSubnet,
+            _ban_time: Option<u32>,
+        ) -> Result<(), zebra_p2p::BanListError> {
+            match subnet.prefix() {
+                32 => Err(zebra_p2p::BanListError::AlreadyBanned),
+                _ => Ok(()),
+            }
+        }
+
+        fn unban(&self, _subnet: zebra_p2p::IpSubnet) -> Result<(), zebra_p2p::BanListError> {
+            Err(zebra_p2p::BanListError::NotBanned)
+        }
+
+        fn banned(&self) -> Vec<zebra_p2p::BanEntry> {
+            vec![zebra_p2p::BanEntry {
+                subnet: "192.168.0.0/24".parse().unwrap(),
+                banned_until: 1540000000,
+            }]
+        }
+
+        fn clear_banned(&self) {}
+
+        fn stop(&self) {}
+    }
+
+    #[test]
+    fn getpeerinfo_success() {
+        let client = NetworkClient::new(SuccessNetworkApi::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler.handle_request_sync(&(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "getpeerinfo",
+				"params": [],
+				"id": 1
+			}"#)
+		).unwrap();
+
+        assert_eq!(r#"{"jsonrpc":"2.0","result":[{"addr":"127.0.0.1:8233","banscore":null,"bytesrecv":2048,"bytessent":1024,"conntime":1540000000,"id":0,"inbound":false,"services":"0000000000000001","startingheight":500000,"subver":"/MagicBean:2.0.2/","version":170007}],"id":1}"#, &sample);
+    }
+
+    #[test]
+    fn disconnectnode_success() {
+        let client = NetworkClient::new(SuccessNetworkApi::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler.handle_request_sync(&(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "disconnectnode",
+				"params": ["127.0.0.1:8233"],
+				"id": 1
+			}"#)
+		).unwrap();
+        assert_eq!(r#"{"jsonrpc":"2.0","result":null,"id":1}"#, &sample);
+
+        let sample = handler.handle_request_sync(&(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "disconnectnode",
+				"params": ["", 0],
+				"id": 1
+			}"#)
+		).unwrap();
+        assert_eq!(r#"{"jsonrpc":"2.0","result":null,"id":1}"#, &sample);
+    }
+
+    #[test]
+    fn disconnectnode_error() {
+        let client = NetworkClient::new(SuccessNetworkApi::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler.handle_request_sync(&(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "disconnectnode",
+				"params": ["127.0.0.1:8888"],
+				"id": 1
+			}"#)
+		).unwrap();
+        assert_eq!(r#"{"jsonrpc":"2.0","error":{"code":-32154,"message":"Node is not connected"},"id":1}"#, &sample);
+
+        let sample = handler.handle_request_sync(&(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "disconnectnode",
+				"params": ["127.0.0.1:8233", 0],
+				"id": 1
+			}"#)
+		).unwrap();
+        assert_eq!(r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"Couldn't parse parameters: node","data":"\"Either node address or node id must be specified\""},"id":1}"#, &sample);
+    }
+
+    #[test]
+    fn getnetworkinfo_success() {
+        let client = NetworkClient::new(SuccessNetworkApi::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler.handle_request_sync(&(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "getnetworkinfo",
+				"params": [],
+				"id": 1
+			}"#)
+		).unwrap();
+
+        assert_eq!(r#"{"jsonrpc":"2.0","result":{"connections":1,"connections_in":0,"connections_out":1,"localservices":"0000000000000001","maxconnections_in":10,"maxconnections_out":10,"networkactive":true,"networks":[{"limited":false,"name":"ipv4","reachable":true}],"protocolversion":170007,"relayfee":0.0001,"subversion":"zebra","version":10000,"warnings":""},"id":1}"#, &sample);
+    }
+
+    #[test]
+    fn setnetworkactive_success() {
+        let client = NetworkClient::new(SuccessNetworkApi::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler.handle_request_sync(&(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "setnetworkactive",
+				"params": [false],
+				"id": 1
+			}"#)
+		).unwrap();
+
+        assert_eq!(r#"{"jsonrpc":"2.0","result":false,"id":1}"#, &sample);
+    }
+
+    #[test]
+    fn ping_success() {
+        let client = NetworkClient::new(SuccessNetworkApi::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler.handle_request_sync(&(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "ping",
+				"params": [],
+				"id": 1
+			}"#)
+		).unwrap();
+
+        assert_eq!(r#"{"jsonrpc":"2.0","result":null,"id":1}"#, &sample);
+    }
+
+    #[test]
+    fn getnettotals_success() {
+        let client = NetworkClient::new(SuccessNetworkApi::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler.handle_request_sync(&(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "getnettotals",
+				"params": [],
+				"id": 1
+			}"#)
+		).unwrap();
+
+        let sample: ::serde_json::Value = ::serde_json::from_str(&sample).unwrap();
+        assert_eq!(sample["result"]["totalbytesrecv"], 2048);
+        assert_eq!(sample["result"]["totalbytessent"], 1024);
+        assert!(sample["result"]["timemillis"].as_i64().unwrap() > 0);
+    }
+
+    #[test]
+    fn setban_success() {
+        let client = NetworkClient::new(SuccessNetworkApi::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler.handle_request_sync(&(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "setban",
+				"params": ["192.168.0.0/24", "add", 3600],
+				"id": 1
+			}"#)
+		).unwrap();
+
+        assert_eq!(r#"{"jsonrpc":"2.0","result":null,"id":1}"#, &sample);
+    }
+
+    #[test]
+    fn setban_error() {
+        let client = NetworkClient::new(SuccessNetworkApi::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler.handle_request_sync(&(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "setban",
+				"params": ["192.168.0.1", "add"],
+				"id": 1
+			}"#)
+		).unwrap();
+        assert_eq!(r#"{"jsonrpc":"2.0","error":{"code":-32152,"message":"Subnet is already banned"},"id":1}"#, &sample);
+
+        let sample = handler.handle_request_sync(&(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "setban",
+				"params": ["192.168.0.1", "remove"],
+				"id": 1
+			}"#)
+		).unwrap();
+        assert_eq!(r#"{"jsonrpc":"2.0","error":{"code":-32153,"message":"Subnet is not banned"},"id":1}"#, &sample);
+
+        let sample = handler.handle_request_sync(&(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "setban",
+				"params": ["192.168.0.1/33", "add"],
+				"id": 1
+			}"#)
+		).unwrap();
+        assert_eq!(r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"Couldn't parse parameters: subnet","data":"\"Invalid subnet format, should be ip or ip/prefix (192.168.0.0/24)\""},"id":1}"#, &sample);
+    }
+
+    #[test]
+    fn listbanned_success() {
+        let client = NetworkClient::new(SuccessNetworkApi::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler.handle_request_sync(&(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "listbanned",
+				"params": [],
+				"id": 1
+			}"#)
+		).unwrap();
+
+        assert_eq!(r#"{"jsonrpc":"2.0","result":[{"address":"192.168.0.0/24","banned_until":1540000000}],"id":1}"#, &sample);
+    }
+
+    #[test]
+    fn clearbanned_success() {
+        let client = NetworkClient::new(SuccessNetworkApi::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler.handle_request_sync(&(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "clearbanned",
+				"params": [],
+				"id": 1
+			}"#)
+		).unwrap();
+
+        assert_eq!(r#"{"jsonrpc":"2.0","result":null,"id":1}"#, &sample);
+    }
+
+    #[test]
+    fn stop_success() {
+        let client = NetworkClient::new(SuccessNetworkApi::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler.handle_request_sync(&(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "stop",
+				"params": [],
+				"id": 1
+			}"#)
+		).unwrap();
+
+        assert_eq!(r#"{"jsonrpc":"2.0","result":"zebra stopping","id":1}"#, &sample);
+    }
 }
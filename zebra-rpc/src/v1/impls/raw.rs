@@ -1,62 +1,486 @@
+use base64;
+use global_script::{
+    verify_script, Builder as ScriptBuilder, Script, ScriptType, SighashBase, SighashCache,
+    TransactionSignatureChecker, VerificationFlags,
+};
+use hex::ToHex;
 use jsonrpc_core::Error;
-use ser::{deserialize, serialize, Reader};
-use v1::helpers::errors::{execution, invalid_params};
+use ser::{deserialize, serialize, CompactInteger, Reader, Stream};
+use std::collections::HashMap;
+use v1::helpers::errors::{
+    address_not_transparent_key, execution, invalid_params, private_key_not_available,
+    transaction_not_found,
+};
 use v1::traits::Raw;
 use v1::types::H256;
 use v1::types::{
-    GetRawTransactionResponse, RawTransaction, Transaction, TransactionInput, TransactionOutput,
-    TransactionOutputs,
+    Bytes, DecodeScriptResponse, GetRawTransactionResponse, PreviousTransactionOutput,
+    RawTransaction, SignRawTransactionError, SignRawTransactionResponse, SignedTransactionInput,
+    SignedTransactionOutput, TestMemPoolAcceptResponse, Transaction, TransactionInput,
+    TransactionInputScript, TransactionJoinSplit, TransactionOutput, TransactionOutputScript,
+    TransactionOutputs, TransactionShieldedOutput, TransactionShieldedSpend,
+    ValidateAddressResponse,
 };
+use zebra_chain::constants::SATOSHIS_IN_COIN;
 use zebra_chain::{
-    IndexedTransaction as GlobalIndexedTransaction, Transaction as GlobalTransaction,
-    SAPLING_TX_VERSION, SAPLING_TX_VERSION_GROUP_ID,
+    IndexedTransaction as GlobalIndexedTransaction, OutPoint, Transaction as GlobalTransaction,
+    TransactionOutput as GlobalTransactionOutput, BTC_TX_VERSION, OVERWINTER_TX_VERSION,
+    OVERWINTER_TX_VERSION_GROUP_ID, SAPLING_TX_VERSION, SAPLING_TX_VERSION_GROUP_ID,
 };
+use zebra_crypto::{dhash160, dhash256};
+use zebra_keys::{
+    Address, CompactSignature, KeyPair, Network as KeysNetwork, PaymentAddress, Private, Public,
+    Type as AddressType,
+};
+use zebra_miner::MemoryPool;
+use zebra_network::{ConsensusParams, Network, NetworkUpgrade};
 use zebra_primitives::bytes::Bytes as GlobalBytes;
-use zebra_primitives::hash::H256 as GlobalH256;
+use zebra_primitives::hash::{H256 as GlobalH256, H520 as GlobalH520};
+use zebra_storage::{
+    self, BlockHeaderProvider, BlockProvider, TransactionIndexProvider, TransactionMetaProvider,
+    TransactionProvider,
+};
 use zebra_sync;
 
 /// Default expiry height delta (best blocks number + height in blocks) for transactions
 /// created by `createrawtransaction` RPC.
 const DEFAULT_TX_EXPIRY_DELTA: u32 = 20;
 
+/// Expiry height of transaction must be less than this value.
+const TX_EXPIRY_HEIGHT_THRESHOLD: u32 = 500_000_000;
+
+/// Signature hash type flag, which allows anyone to add inputs to the signed transaction.
+const SIGHASH_ANYONECANPAY: u32 = 0x80;
+
+/// Magic string, which is prepended to the message before signing it.
+const MESSAGE_MAGIC: &'static str = "Zcash Signed Message:\n";
+
 pub struct RawClient<T: RawClientCoreApi> {
     core: T,
 }
 
 pub trait RawClientCoreApi: Send + Sync + 'static {
     fn accept_transaction(&self, transaction: GlobalTransaction) -> Result<GlobalH256, String>;
+    fn test_transactions(
+        &self,
+        transactions: Vec<GlobalTransaction>,
+        max_fee_rate: Option<u64>,
+    ) -> Vec<zebra_sync::TestTransactionResult>;
     fn create_raw_transaction(
         &self,
         inputs: Vec<TransactionInput>,
         outputs: TransactionOutputs,
         lock_time: Option<u32>,
         expiry_height: Option<u32>,
-    ) -> Result<GlobalTransaction, String>;
+    ) -> Result<GlobalTransaction, Error>;
+    fn sign_raw_transaction(
+        &self,
+        transaction: GlobalTransaction,
+        prev_outputs: HashMap<OutPoint, GlobalTransactionOutput>,
+        keys: Vec<KeyPair>,
+        sighash_type: u32,
+    ) -> Result<(GlobalTransaction, Vec<SignRawTransactionError>), String>;
+    fn raw_transaction(&self, hash: GlobalH256) -> Option<RawTransaction>;
+    fn verbose_transaction(&self, hash: GlobalH256) -> Option<Transaction>;
+    fn network(&self) -> KeysNetwork;
 }
 
 pub struct RawClientCore {
+    consensus: ConsensusParams,
     local_sync_node: zebra_sync::LocalNodeRef,
+    storage: zebra_storage::SharedStore,
 }
 
 impl RawClientCore {
-    pub fn new(local_sync_node: zebra_sync::LocalNodeRef) -> Self {
+    pub fn new(
+        consensus: ConsensusParams,
+        local_sync_node: zebra_sync::LocalNodeRef,
+        storage: zebra_storage::SharedStore,
+    ) -> Self {
         RawClientCore {
+            consensus: consensus,
             local_sync_node: local_sync_node,
+            storage: storage,
+        }
+    }
+
+    /// Find transaction in the memory pool or in the storage.
+    ///
+    /// Storage transactions are found using the transaction index. If the index is disabled,
+    /// only transactions with unspent outputs are found.
+    /// Returns transaction and hash of the block it is included in (None for memory pool transactions).
+    pub fn do_find_transaction(
+        storage: &zebra_storage::Store,
+        memory_pool: &MemoryPool,
+        hash: &GlobalH256,
+    ) -> Option<(GlobalIndexedTransaction, Option<GlobalH256>)> {
+        if let Some(transaction) = memory_pool.transaction(hash) {
+            return Some((transaction, None));
+        }
+
+        let block_hash = if storage.is_transaction_index_enabled() {
+            storage.transaction_block_hash(hash)
+        } else {
+            storage
+                .transaction_meta(hash)
+                .filter(|meta| !meta.is_fully_spent())
+                .and_then(|meta| storage.block_hash(meta.height()))
+        };
+
+        match block_hash {
+            Some(block_hash) => storage
+                .transaction(hash)
+                .map(|transaction| (transaction, Some(block_hash))),
+            None => None,
+        }
+    }
+
+    /// Returns verbose information on transaction, included in given block.
+    pub fn do_verbose_transaction(
+        storage: &zebra_storage::Store,
+        network: KeysNetwork,
+        transaction: GlobalIndexedTransaction,
+        block_hash: Option<GlobalH256>,
+    ) -> Transaction {
+        let block_time = block_hash
+            .and_then(|block_hash| storage.block_header(block_hash.into()))
+            .map(|header| header.raw.time);
+        let height = block_hash.and_then(|block_hash| storage.block_number(&block_hash));
+        let confirmations = match (block_hash, height) {
+            (Some(_), Some(height)) => Some((storage.best_block().number - height + 1) as i64),
+            // transaction is included in the side chain block
+            (Some(_), None) => Some(-1),
+            (None, _) => None,
+        };
+
+        let mut verbose_transaction = RawClientCore::do_decode_transaction(&transaction, network);
+        verbose_transaction.blockhash = block_hash.map(Into::into);
+        verbose_transaction.height = height;
+        verbose_transaction.confirmations = confirmations;
+        verbose_transaction.time = block_time;
+        verbose_transaction.blocktime = block_time;
+        verbose_transaction
+    }
+
+    /// Returns decoded transaction, which isn't associated with any block.
+    pub fn do_decode_transaction(
+        transaction: &GlobalIndexedTransaction,
+        network: KeysNetwork,
+    ) -> Transaction {
+        let raw = &transaction.raw;
+        let raw_transaction = serialize(raw);
+        let sapling = raw.sapling.as_ref();
+        Transaction {
+            size: raw_transaction.len(),
+            hex: raw_transaction.into(),
+            txid: transaction.hash.into(),
+            hash: transaction.hash.into(),
+            overwintered: raw.overwintered,
+            version: raw.version,
+            versiongroupid: if raw.overwintered {
+                Some(format!("{:08x}", raw.version_group_id))
+            } else {
+                None
+            },
+            locktime: raw.lock_time as i32,
+            expiryheight: if raw.overwintered {
+                Some(raw.expiry_height)
+            } else {
+                None
+            },
+            vin: raw
+                .inputs
+                .iter()
+                .map(|input| {
+                    let script_sig: Script = input.script_sig.clone().into();
+                    SignedTransactionInput {
+                        txid: input.previous_output.hash.into(),
+                        vout: input.previous_output.index,
+                        script_sig: TransactionInputScript {
+                            asm: script_sig.to_asm_string(),
+                            hex: input.script_sig.clone().into(),
+                        },
+                        sequence: input.sequence,
+                        value: None,
+                        addresses: vec![],
+                    }
+                })
+                .collect(),
+            vout: raw
+                .outputs
+                .iter()
+                .enumerate()
+                .map(|(index, output)| SignedTransactionOutput {
+                    value: 0.00000001f64 * (output.value as f64),
+                    n: index as u32,
+                    script: RawClientCore::do_decode_output_script(&output.script_pubkey, network),
+                })
+                .collect(),
+            vjoinsplit: raw
+                .join_split
+                .iter()
+                .flat_map(|join_split| join_split.descriptions.iter())
+                .map(|description| TransactionJoinSplit {
+                    vpub_old: 0.00000001f64 * (description.value_pub_old as f64),
+                    vpub_new: 0.00000001f64 * (description.value_pub_new as f64),
+                    anchor: GlobalH256::from(&description.anchor[..]).into(),
+                    nullifiers: description
+                        .nullifiers
+                        .iter()
+                        .map(|nullifier| GlobalH256::from(&nullifier[..]).into())
+                        .collect(),
+                    commitments: description
+                        .commitments
+                        .iter()
+                        .map(|commitment| GlobalH256::from(&commitment[..]).into())
+                        .collect(),
+                })
+                .collect(),
+            value_balance: sapling.map(|sapling| 0.00000001f64 * (sapling.balancing_value as f64)),
+            shielded_spends: sapling.map(|sapling| {
+                sapling
+                    .spends
+                    .iter()
+                    .map(|spend| TransactionShieldedSpend {
+                        cv: GlobalH256::from(&spend.value_commitment[..]).into(),
+                        anchor: GlobalH256::from(&spend.anchor[..]).into(),
+                        nullifier: GlobalH256::from(&spend.nullifier[..]).into(),
+                        rk: GlobalH256::from(&spend.randomized_key[..]).into(),
+                    })
+                    .collect()
+            }),
+            shielded_outputs: sapling.map(|sapling| {
+                sapling
+                    .outputs
+                    .iter()
+                    .map(|output| TransactionShieldedOutput {
+                        cv: GlobalH256::from(&output.value_commitment[..]).into(),
+                        cmu: GlobalH256::from(&output.note_commitment[..]).into(),
+                        ephemeral_key: GlobalH256::from(&output.ephemeral_key[..]).into(),
+                    })
+                    .collect()
+            }),
+            blockhash: None,
+            height: None,
+            confirmations: None,
+            time: None,
+            blocktime: None,
+        }
+    }
+
+    /// Returns decoded output script.
+    pub fn do_decode_output_script(
+        script_bytes: &GlobalBytes,
+        network: KeysNetwork,
+    ) -> TransactionOutputScript {
+        let script: Script = script_bytes.clone().into();
+        TransactionOutputScript {
+            asm: script.to_asm_string(),
+            hex: script_bytes.clone().into(),
+            req_sigs: script.num_signatures_required() as u32,
+            script_type: script.script_type().into(),
+            addresses: script
+                .extract_destinations()
+                .unwrap_or(vec![])
+                .into_iter()
+                .map(|address| Address {
+                    network: network,
+                    hash: address.hash,
+                    kind: address.kind,
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns decoded script, along with its P2SH address.
+    pub fn do_decode_script(
+        script_bytes: &GlobalBytes,
+        network: KeysNetwork,
+    ) -> DecodeScriptResponse {
+        let script: Script = script_bytes.clone().into();
+        let p2sh = if script.is_pay_to_script_hash() {
+            None
+        } else {
+            let address = Address {
+                network: network,
+                hash: dhash160(script_bytes),
+                kind: AddressType::P2SH,
+            };
+            Some(address.to_string())
+        };
+
+        DecodeScriptResponse {
+            script: RawClientCore::do_decode_output_script(script_bytes, network),
+            p2sh: p2sh,
+        }
+    }
+
+    /// Select consensus branch id that is used to compute signature hashes of transaction
+    /// that is going to be included in the block at given height.
+    pub fn select_consensus_branch_id(
+        consensus: &ConsensusParams,
+        height: u32,
+        transaction: &GlobalTransaction,
+    ) -> Result<u32, String> {
+        let active_upgrade = consensus.upgrade_active_at(height);
+        // consensus branch id isn't used to compute signature hashes of sprout transactions
+        if !transaction.overwintered {
+            return Ok(active_upgrade.branch_id());
+        }
+
+        // Sapling transactions format is used by all upgrades since Sapling
+        let is_valid_version_group = match transaction.version_group_id {
+            SAPLING_TX_VERSION_GROUP_ID => active_upgrade >= NetworkUpgrade::Sapling,
+            OVERWINTER_TX_VERSION_GROUP_ID => active_upgrade == NetworkUpgrade::Overwinter,
+            version_group_id => {
+                return Err(format!(
+                    "Unknown transaction version group id: {:#x}",
+                    version_group_id
+                ))
+            }
+        };
+        if !is_valid_version_group {
+            return Err(format!(
+                "Transaction version group id {:#x} is not valid at height {}",
+                transaction.version_group_id, height
+            ));
+        }
+
+        Ok(active_upgrade.branch_id())
+    }
+
+    pub fn do_sign_raw_transaction(
+        mut transaction: GlobalTransaction,
+        consensus_branch_id: u32,
+        prev_outputs: &HashMap<OutPoint, GlobalTransactionOutput>,
+        keys: &[KeyPair],
+        sighash_type: u32,
+    ) -> (GlobalTransaction, Vec<SignRawTransactionError>) {
+        let mut checker = TransactionSignatureChecker {
+            signer: transaction.clone().into(),
+            input_index: 0,
+            input_amount: 0,
+            consensus_branch_id: consensus_branch_id,
+            cache: SighashCache::default(),
+        };
+
+        let mut errors = Vec::new();
+        for (input_index, input) in transaction.inputs.iter_mut().enumerate() {
+            let sign_result = match prev_outputs.get(&input.previous_output) {
+                Some(prev_output) => {
+                    checker.input_index = input_index;
+                    checker.input_amount = prev_output.value;
+                    RawClientCore::sign_transaction_input(
+                        &mut checker,
+                        input.script_sig.clone(),
+                        prev_output,
+                        keys,
+                        sighash_type,
+                    )
+                }
+                None => Err("Input not found or already spent".into()),
+            };
+
+            match sign_result {
+                Ok(script_sig) => input.script_sig = script_sig,
+                Err(error) => errors.push(SignRawTransactionError {
+                    txid: input.previous_output.hash.reversed().into(),
+                    vout: input.previous_output.index,
+                    script_sig: input.script_sig.clone().into(),
+                    sequence: input.sequence,
+                    error: error,
+                }),
+            }
+        }
+
+        (transaction, errors)
+    }
+
+    /// Sign single transaction input (if possible) and then verify its script.
+    fn sign_transaction_input(
+        checker: &mut TransactionSignatureChecker,
+        script_sig: GlobalBytes,
+        prev_output: &GlobalTransactionOutput,
+        keys: &[KeyPair],
+        sighash_type: u32,
+    ) -> Result<GlobalBytes, String> {
+        let script_pubkey: Script = prev_output.script_pubkey.clone().into();
+        let script_type = script_pubkey.script_type();
+        let key_pair = match script_type {
+            ScriptType::PubKey | ScriptType::PubKeyHash => {
+                let destinations = script_pubkey
+                    .extract_destinations()
+                    .map_err(|e| e.to_string())?;
+                destinations.first().and_then(|destination| {
+                    keys.iter()
+                        .find(|key_pair| key_pair.public().address_hash() == destination.hash)
+                })
+            }
+            // only pay-to-pubkey and pay-to-pubkey-hash inputs are signed atm
+            _ => None,
+        };
+
+        let script_sig = match key_pair {
+            Some(key_pair) => {
+                let input_index = checker.input_index;
+                let input_amount = checker.input_amount;
+                let consensus_branch_id = checker.consensus_branch_id;
+                let hash = checker.signer.signature_hash(
+                    &mut checker.cache,
+                    Some(input_index),
+                    input_amount,
+                    &script_pubkey,
+                    sighash_type,
+                    consensus_branch_id,
+                );
+                let mut signature = key_pair.sign_deterministic(&hash).to_vec();
+                signature.push(sighash_type as u8);
+
+                let builder = ScriptBuilder::default().push_data(&signature);
+                let builder = match script_type {
+                    ScriptType::PubKeyHash => builder.push_data(key_pair.public()),
+                    _ => builder,
+                };
+                builder.into_bytes()
+            }
+            None => script_sig,
+        };
+
+        // verify script, even if input hasn't been signed - it could be signed earlier
+        let flags = VerificationFlags::default()
+            .verify_p2sh(true)
+            .verify_strictenc(true)
+            .verify_dersig(true);
+        match verify_script(&script_sig.clone().into(), &script_pubkey, &flags, checker) {
+            Ok(_) => Ok(script_sig),
+            Err(_) if key_pair.is_none() => match script_type {
+                ScriptType::PubKey | ScriptType::PubKeyHash => {
+                    Err("Private key for input is not found".into())
+                }
+                _ => Err("Unable to sign input with unsupported script type".into()),
+            },
+            Err(error) => Err(error.to_string()),
         }
     }
 
     pub fn do_create_raw_transaction(
+        consensus: &ConsensusParams,
         best_block_number: u32,
         inputs: Vec<TransactionInput>,
         outputs: TransactionOutputs,
         lock_time: Option<u32>,
         expiry_height: Option<u32>,
-    ) -> Result<GlobalTransaction, String> {
-        use global_script::Builder as ScriptBuilder;
-
-        // overwinter is active atm => assume that all new transactions are created for sapling era
-        let version = SAPLING_TX_VERSION;
-        let version_group_id = SAPLING_TX_VERSION_GROUP_ID;
+    ) -> Result<GlobalTransaction, Error> {
+        // transaction is created for the network upgrade, which is active at the next block
+        let next_block_number = best_block_number + 1;
+        let (overwintered, version, version_group_id) =
+            match consensus.upgrade_active_at(next_block_number) {
+                NetworkUpgrade::Sprout => (false, BTC_TX_VERSION, 0),
+                NetworkUpgrade::Overwinter => {
+                    (true, OVERWINTER_TX_VERSION, OVERWINTER_TX_VERSION_GROUP_ID)
+                }
+                _ => (true, SAPLING_TX_VERSION, SAPLING_TX_VERSION_GROUP_ID),
+            };
 
         // to make lock_time work at least one input must have sequence < SEQUENCE_FINAL
         let lock_time = lock_time.unwrap_or_default();
@@ -67,8 +491,22 @@ impl RawClientCore {
         };
 
         // by default we're creating transactions that are expired in DEFAULT_TX_EXPIRY_DELTA blocks
-        let expiry_height =
-            expiry_height.unwrap_or_else(|| best_block_number + DEFAULT_TX_EXPIRY_DELTA);
+        let expiry_height = match expiry_height {
+            Some(_) if !overwintered => {
+                return Err(execution(
+                    "Expiry height can only be used when Overwinter is active",
+                ))
+            }
+            Some(expiry_height) if expiry_height >= TX_EXPIRY_HEIGHT_THRESHOLD => {
+                return Err(execution(format!(
+                    "Expiry height must be less than {}",
+                    TX_EXPIRY_HEIGHT_THRESHOLD
+                )))
+            }
+            Some(expiry_height) => expiry_height,
+            None if overwintered => best_block_number + DEFAULT_TX_EXPIRY_DELTA,
+            None => 0,
+        };
 
         // prepare inputs
         let inputs: Vec<_> = inputs
@@ -84,14 +522,21 @@ impl RawClientCore {
             .collect();
 
         // prepare outputs
+        let max_money = consensus.max_transaction_value() as u64;
         let outputs: Vec<_> = outputs
             .outputs
             .into_iter()
             .map(|output| match output {
                 TransactionOutput::Address(with_address) => {
-                    let amount_in_satoshis = (with_address.amount
-                        * (zebra_chain::constants::SATOSHIS_IN_COIN as f64))
-                        as u64;
+                    let amount_in_satoshis = amount_to_satoshis(with_address.amount)
+                        .map_err(|e| invalid_params("outputs", e))?;
+                    if amount_in_satoshis > max_money {
+                        return Err(invalid_params(
+                            "outputs",
+                            format!("Amount out of range: {}", with_address.amount),
+                        ));
+                    }
+
                     let script = match with_address.address.kind {
                         zebra_keys::Type::P2PKH => {
                             ScriptBuilder::build_p2pkh(&with_address.address.hash)
@@ -101,27 +546,37 @@ impl RawClientCore {
                         }
                     };
 
-                    zebra_chain::TransactionOutput {
+                    Ok(zebra_chain::TransactionOutput {
                         value: amount_in_satoshis,
                         script_pubkey: script.to_bytes(),
-                    }
+                    })
                 }
                 TransactionOutput::ScriptData(with_script_data) => {
                     let script = ScriptBuilder::default()
                         .return_bytes(&*with_script_data.script_data)
                         .into_script();
 
-                    zebra_chain::TransactionOutput {
+                    Ok(zebra_chain::TransactionOutput {
                         value: 0,
                         script_pubkey: script.to_bytes(),
-                    }
+                    })
                 }
             })
-            .collect();
+            .collect::<Result<_, Error>>()?;
+
+        let total_amount = outputs
+            .iter()
+            .fold(0u64, |total, output| total.saturating_add(output.value));
+        if total_amount > max_money {
+            return Err(invalid_params(
+                "outputs",
+                "Total amount of outputs is out of range",
+            ));
+        }
 
         // now construct && serialize transaction
         let transaction = GlobalTransaction {
-            overwintered: true,
+            overwintered: overwintered,
             version: version,
             version_group_id: version_group_id,
             inputs: inputs,
@@ -141,14 +596,29 @@ impl RawClientCoreApi for RawClientCore {
             .accept_transaction(GlobalIndexedTransaction::from_raw(transaction))
     }
 
+    fn test_transactions(
+        &self,
+        transactions: Vec<GlobalTransaction>,
+        max_fee_rate: Option<u64>,
+    ) -> Vec<zebra_sync::TestTransactionResult> {
+        self.local_sync_node.test_transactions(
+            transactions
+                .into_iter()
+                .map(GlobalIndexedTransaction::from_raw)
+                .collect(),
+            max_fee_rate,
+        )
+    }
+
     fn create_raw_transaction(
         &self,
         inputs: Vec<TransactionInput>,
         outputs: TransactionOutputs,
         lock_time: Option<u32>,
         expiry_height: Option<u32>,
-    ) -> Result<GlobalTransaction, String> {
+    ) -> Result<GlobalTransaction, Error> {
         RawClientCore::do_create_raw_transaction(
+            &self.consensus,
             self.local_sync_node.best_block_number(),
             inputs,
             outputs,
@@ -156,6 +626,91 @@ impl RawClientCoreApi for RawClientCore {
             expiry_height,
         )
     }
+
+    fn sign_raw_transaction(
+        &self,
+        transaction: GlobalTransaction,
+        mut prev_outputs: HashMap<OutPoint, GlobalTransactionOutput>,
+        keys: Vec<KeyPair>,
+        sighash_type: u32,
+    ) -> Result<(GlobalTransaction, Vec<SignRawTransactionError>), String> {
+        let consensus_branch_id = RawClientCore::select_consensus_branch_id(
+            &self.consensus,
+            self.local_sync_node.best_block_number() + 1,
+            &transaction,
+        )?;
+
+        // outputs that are not provided by caller are searched in the memory pool && in the storage
+        for input in &transaction.inputs {
+            if !prev_outputs.contains_key(&input.previous_output) {
+                if let Some(output) = self
+                    .local_sync_node
+                    .transaction_output(&input.previous_output)
+                {
+                    prev_outputs.insert(input.previous_output.clone(), output);
+                }
+            }
+        }
+
+        Ok(RawClientCore::do_sign_raw_transaction(
+            transaction,
+            consensus_branch_id,
+            &prev_outputs,
+            &keys,
+            sighash_type,
+        ))
+    }
+
+    fn raw_transaction(&self, hash: GlobalH256) -> Option<RawTransaction> {
+        let memory_pool = self.local_sync_node.memory_pool();
+        let memory_pool = memory_pool.read();
+        RawClientCore::do_find_transaction(self.storage.as_store(), &*memory_pool, &hash)
+            .map(|(transaction, _)| serialize(&transaction.raw).into())
+    }
+
+    fn verbose_transaction(&self, hash: GlobalH256) -> Option<Transaction> {
+        let memory_pool = self.local_sync_node.memory_pool();
+        let memory_pool = memory_pool.read();
+        RawClientCore::do_find_transaction(self.storage.as_store(), &*memory_pool, &hash).map(
+            |(transaction, block_hash)| {
+                RawClientCore::do_verbose_transaction(
+                    self.storage.as_store(),
+                    self.network(),
+                    transaction,
+                    block_hash,
+                )
+            },
+        )
+    }
+
+    fn network(&self) -> KeysNetwork {
+        match self.consensus.network {
+            Network::Mainnet => KeysNetwork::Mainnet,
+            // there's no correct choices for Regtests && Other networks
+            // => let's just use Testnet addresses
+            _ => KeysNetwork::Testnet,
+        }
+    }
+}
+
+/// Converts amount (in coins) to satoshis. Fails if amount is negative, isn't finite, is too large
+/// to be represented exactly or has more than 8 decimal places.
+fn amount_to_satoshis(amount: f64) -> Result<u64, String> {
+    if !amount.is_finite() || amount < 0f64 {
+        return Err(format!("Invalid amount: {}", amount));
+    }
+
+    let satoshis = (amount * SATOSHIS_IN_COIN as f64).round();
+    // f64 only represents integers up to 2^53 exactly
+    if satoshis >= (1u64 << 53) as f64 {
+        return Err(format!("Amount out of range: {}", amount));
+    }
+    // amount with at most 8 decimal places is the closest f64 to satoshis / SATOSHIS_IN_COIN
+    if (satoshis / SATOSHIS_IN_COIN as f64).to_bits() != amount.to_bits() {
+        return Err(format!("Invalid amount precision: {}", amount));
+    }
+
+    Ok(satoshis as u64)
 }
 
 impl<T> RawClient<T>
@@ -182,6 +737,49 @@ where
             .map_err(|e| execution(e))
     }
 
+    fn test_mempool_accept(
+        &self,
+        raw_transactions: Vec<RawTransaction>,
+        max_fee_rate: Option<f64>,
+    ) -> Result<Vec<TestMemPoolAcceptResponse>, Error> {
+        let transactions = raw_transactions
+            .into_iter()
+            .map(|raw_transaction| {
+                let raw_transaction_data: Vec<u8> = raw_transaction.into();
+                deserialize(Reader::new(&raw_transaction_data))
+                    .map_err(|e| invalid_params("rawtxs", e))
+            })
+            .collect::<Result<Vec<GlobalTransaction>, _>>()?;
+        let max_fee_rate = match max_fee_rate {
+            Some(fee_rate) => {
+                Some(amount_to_satoshis(fee_rate).map_err(|e| invalid_params("maxfeerate", e))?)
+            }
+            None => None,
+        };
+
+        Ok(self
+            .core
+            .test_transactions(transactions, max_fee_rate)
+            .into_iter()
+            .map(|result| match result.result {
+                Ok(fee) => TestMemPoolAcceptResponse {
+                    txid: result.hash.reversed().into(),
+                    allowed: true,
+                    reject_reason: None,
+                    fees: Some(fee as f64 / SATOSHIS_IN_COIN as f64),
+                    vsize: Some(result.size),
+                },
+                Err(reason) => TestMemPoolAcceptResponse {
+                    txid: result.hash.reversed().into(),
+                    allowed: false,
+                    reject_reason: Some(reason),
+                    fees: None,
+                    vsize: None,
+                },
+            })
+            .collect())
+    }
+
     fn create_raw_transaction(
         &self,
         inputs: Vec<TransactionInput>,
@@ -200,33 +798,304 @@ where
 
         let transaction = self
             .core
-            .create_raw_transaction(inputs, outputs, lock_time, expiry_height)
-            .map_err(|e| execution(e))?;
+            .create_raw_transaction(inputs, outputs, lock_time, expiry_height)?;
         let transaction = serialize(&transaction);
         Ok(transaction.into())
     }
 
-    fn decode_raw_transaction(&self, _transaction: RawTransaction) -> Result<Transaction, Error> {
-        rpc_unimplemented!()
+    fn sign_raw_transaction(
+        &self,
+        raw_transaction: RawTransaction,
+        prev_outputs: Option<Vec<PreviousTransactionOutput>>,
+        private_keys: Option<Vec<String>>,
+        sighash_type: Option<String>,
+    ) -> Result<SignRawTransactionResponse, Error> {
+        let raw_transaction_data: Vec<u8> = raw_transaction.into();
+        let transaction =
+            try!(deserialize(Reader::new(&raw_transaction_data))
+                .map_err(|e| invalid_params("tx", e)));
+
+        let prev_outputs = prev_outputs
+            .unwrap_or_default()
+            .into_iter()
+            .map(|prev_output| {
+                let outpoint = OutPoint {
+                    hash: Into::<GlobalH256>::into(prev_output.txid).reversed(),
+                    index: prev_output.vout,
+                };
+                let output = GlobalTransactionOutput {
+                    value: amount_to_satoshis(prev_output.amount)
+                        .map_err(|e| invalid_params("prevtxs", e))?,
+                    script_pubkey: prev_output.script_pubkey.to_vec().into(),
+                };
+                Ok((outpoint, output))
+            })
+            .collect::<Result<_, Error>>()?;
+
+        let keys = try!(private_keys
+            .unwrap_or_default()
+            .into_iter()
+            .map(|private_key| {
+                private_key
+                    .parse::<Private>()
+                    .and_then(KeyPair::from_private)
+                    .map_err(|e| invalid_params("privkeys", e))
+            })
+            .collect::<Result<Vec<_>, _>>());
+
+        let sighash_type = match sighash_type.as_ref().map(String::as_str) {
+            None | Some("ALL") => u32::from(SighashBase::All),
+            Some("NONE") => u32::from(SighashBase::None),
+            Some("SINGLE") => u32::from(SighashBase::Single),
+            Some("ALL|ANYONECANPAY") => u32::from(SighashBase::All) | SIGHASH_ANYONECANPAY,
+            Some("NONE|ANYONECANPAY") => u32::from(SighashBase::None) | SIGHASH_ANYONECANPAY,
+            Some("SINGLE|ANYONECANPAY") => u32::from(SighashBase::Single) | SIGHASH_ANYONECANPAY,
+            Some(sighash_type) => return Err(invalid_params("sighashtype", sighash_type)),
+        };
+
+        let (transaction, errors) = self
+            .core
+            .sign_raw_transaction(transaction, prev_outputs, keys, sighash_type)
+            .map_err(|e| execution(e))?;
+        Ok(SignRawTransactionResponse {
+            hex: serialize(&transaction).into(),
+            complete: errors.is_empty(),
+            errors: if errors.is_empty() {
+                None
+            } else {
+                Some(errors)
+            },
+        })
+    }
+
+    fn decode_raw_transaction(&self, transaction: RawTransaction) -> Result<Transaction, Error> {
+        let transaction_data: Vec<u8> = transaction.into();
+        let transaction: GlobalTransaction =
+            try!(deserialize(Reader::new(&transaction_data)).map_err(|e| invalid_params("tx", e)));
+        let transaction = GlobalIndexedTransaction::from_raw(transaction);
+        let mut transaction =
+            RawClientCore::do_decode_transaction(&transaction, self.core.network());
+        reverse_transaction_hashes(&mut transaction);
+        Ok(transaction)
+    }
+
+    fn decode_script(&self, script: Bytes) -> Result<DecodeScriptResponse, Error> {
+        let script: GlobalBytes = script.to_vec().into();
+        let network = self.core.network();
+        Ok(RawClientCore::do_decode_script(&script, network))
     }
 
     fn get_raw_transaction(
         &self,
-        _hash: H256,
-        _verbose: Option<bool>,
+        hash: H256,
+        verbose: Option<bool>,
     ) -> Result<GetRawTransactionResponse, Error> {
-        rpc_unimplemented!()
+        let global_hash: GlobalH256 = hash.clone().into();
+        let global_hash = global_hash.reversed();
+        if !verbose.unwrap_or(false) {
+            return self
+                .core
+                .raw_transaction(global_hash)
+                .map(GetRawTransactionResponse::Raw)
+                .ok_or(transaction_not_found(hash));
+        }
+
+        let mut transaction = self
+            .core
+            .verbose_transaction(global_hash)
+            .ok_or(transaction_not_found(hash))?;
+        reverse_transaction_hashes(&mut transaction);
+        Ok(GetRawTransactionResponse::Verbose(transaction))
+    }
+
+    fn validate_address(&self, address: String) -> Result<ValidateAddressResponse, Error> {
+        Ok(validate_address(&address, self.core.network()))
+    }
+
+    fn z_validate_address(&self, address: String) -> Result<ValidateAddressResponse, Error> {
+        Ok(validate_address(&address, self.core.network()))
+    }
+
+    fn sign_message(
+        &self,
+        address: String,
+        message: String,
+        keys: Option<Vec<String>>,
+    ) -> Result<String, Error> {
+        let keys = keys.unwrap_or_default();
+        sign_message(&address, &message, &keys, self.core.network())
+    }
+
+    fn verify_message(
+        &self,
+        address: String,
+        signature: String,
+        message: String,
+    ) -> Result<bool, Error> {
+        verify_message(&address, &signature, &message, self.core.network())
+    }
+}
+
+/// Parses transparent P2PKH address of given network. Message signing only applies
+/// to addresses that are hashes of transparent public keys.
+fn parse_p2pkh_address(address: &str, network: KeysNetwork) -> Result<Address, Error> {
+    let payment_address: PaymentAddress =
+        try!(address.parse().map_err(|e| invalid_params("address", e)));
+    if payment_address.network() != network {
+        return Err(invalid_params("address", "Address of other network"));
+    }
+
+    match payment_address {
+        PaymentAddress::Transparent(ref transparent) if transparent.kind == AddressType::P2PKH => {
+            Ok(transparent.clone())
+        }
+        _ => Err(address_not_transparent_key(address)),
+    }
+}
+
+/// Returns hash of the message that is signed by `signmessage` and checked by `verifymessage`.
+fn message_hash(message: &str) -> GlobalH256 {
+    let mut stream = Stream::new();
+    stream
+        .append(&CompactInteger::from(MESSAGE_MAGIC.len()))
+        .append_slice(MESSAGE_MAGIC.as_bytes())
+        .append(&CompactInteger::from(message.len()))
+        .append_slice(message.as_bytes());
+    dhash256(&stream.out())
+}
+
+/// Signs the message with the key of the address, which must be one of given private keys.
+/// Returns base64-encoded compact signature.
+fn sign_message(
+    address: &str,
+    message: &str,
+    keys: &[String],
+    network: KeysNetwork,
+) -> Result<String, Error> {
+    let address = try!(parse_p2pkh_address(address, network));
+    for key in keys {
+        let private: Private = try!(key.parse().map_err(|e| invalid_params("privkeys", e)));
+        let key_pair =
+            try!(KeyPair::from_private(private).map_err(|e| invalid_params("privkeys", e)));
+        if key_pair.address().hash == address.hash {
+            let signature = key_pair.sign_compact(&message_hash(message));
+            return Ok(base64::encode(&*signature));
+        }
+    }
+
+    Err(private_key_not_available(address.to_string()))
+}
+
+/// Verifies that the message is signed with the key of the address.
+fn verify_message(
+    address: &str,
+    signature: &str,
+    message: &str,
+    network: KeysNetwork,
+) -> Result<bool, Error> {
+    let address = try!(parse_p2pkh_address(address, network));
+    let signature = try!(base64::decode(signature).map_err(|e| invalid_params("signature", e)));
+    if signature.len() != 65 {
+        return Ok(false);
+    }
+
+    let signature: CompactSignature = GlobalH520::from(&signature[..]).into();
+    match Public::recover_compact(&message_hash(message), &signature) {
+        Ok(public) => Ok(public.address_hash() == address.hash),
+        Err(_) => Ok(false),
+    }
+}
+
+/// Validates transparent or shielded address. Addresses of other networks are reported as invalid.
+fn validate_address(address: &str, network: KeysNetwork) -> ValidateAddressResponse {
+    let address: PaymentAddress = match address.parse() {
+        Ok(address) => address,
+        Err(_) => return ValidateAddressResponse::default(),
+    };
+    if address.network() != network {
+        return ValidateAddressResponse::default();
+    }
+
+    let mut response = ValidateAddressResponse {
+        isvalid: true,
+        address: Some(address.to_string()),
+        ..Default::default()
+    };
+    match address {
+        PaymentAddress::Transparent(ref address) => {
+            let is_script = address.kind == AddressType::P2SH;
+            response.address_type = Some(if is_script { "p2sh" } else { "p2pkh" }.into());
+            response.ismine = Some(false);
+            response.isscript = Some(is_script);
+        }
+        PaymentAddress::Sprout(_) => {
+            response.address_type = Some("sprout".into());
+        }
+        PaymentAddress::Sapling(ref address) => {
+            response.address_type = Some("sapling".into());
+            response.diversifier = Some(address.diversifier.to_hex::<String>());
+        }
+    }
+
+    response
+}
+
+/// Reverses hashes of the verbose transaction, as they're displayed to the user.
+pub fn reverse_transaction_hashes(transaction: &mut Transaction) {
+    transaction.txid = transaction.txid.reversed();
+    transaction.hash = transaction.hash.reversed();
+    transaction.blockhash = transaction.blockhash.take().map(|h| h.reversed());
+    for input in &mut transaction.vin {
+        input.txid = input.txid.reversed();
+    }
+    for join_split in &mut transaction.vjoinsplit {
+        join_split.anchor = join_split.anchor.reversed();
+        for nullifier in &mut join_split.nullifiers {
+            *nullifier = nullifier.reversed();
+        }
+        for commitment in &mut join_split.commitments {
+            *commitment = commitment.reversed();
+        }
+    }
+    if let Some(ref mut spends) = transaction.shielded_spends {
+        for spend in spends {
+            spend.cv = spend.cv.reversed();
+            spend.anchor = spend.anchor.reversed();
+            spend.nullifier = spend.nullifier.reversed();
+            spend.rk = spend.rk.reversed();
+        }
+    }
+    if let Some(ref mut outputs) = transaction.shielded_outputs {
+        for output in outputs {
+            output.cv = output.cv.reversed();
+            output.cmu = output.cmu.reversed();
+            output.ephemeral_key = output.ephemeral_key.reversed();
+        }
     }
 }
 
 #[cfg(test)]
 pub mod tests {
+    extern crate zebra_test_data;
+
     use super::*;
     use jsonrpc_core::IoHandler;
+    use std::collections::HashMap;
     use v1::traits::Raw;
-    use v1::types::{TransactionInput, TransactionOutputs};
-    use zebra_chain::Transaction;
+    use v1::types::{
+        RawTransaction, SignRawTransactionError, Transaction as VerboseTransaction,
+        TransactionInput, TransactionOutput, TransactionOutputWithAddress, TransactionOutputs,
+    };
+    use zebra_chain::{
+        IndexedBlock, OutPoint, Transaction, TransactionOutput as GlobalTransactionOutput,
+    };
+    use zebra_db::kv::MemoryDatabase;
+    use zebra_db::BlockChainDatabase;
+    use zebra_keys::KeyPair;
+    use zebra_miner::{MemoryPool, NonZeroFeeCalculator};
+    use zebra_network::{ConsensusParams, Network};
     use zebra_primitives::hash::H256 as GlobalH256;
+    use zebra_sync::TestTransactionResult;
 
     #[derive(Default)]
     struct SuccessRawClientCore;
@@ -238,15 +1107,91 @@ pub mod tests {
             Ok(transaction.hash())
         }
 
+        fn test_transactions(
+            &self,
+            transactions: Vec<Transaction>,
+            _max_fee_rate: Option<u64>,
+        ) -> Vec<TestTransactionResult> {
+            transactions
+                .into_iter()
+                .map(|transaction| TestTransactionResult {
+                    hash: transaction.hash(),
+                    size: 100,
+                    result: Ok(10000),
+                })
+                .collect()
+        }
+
         fn create_raw_transaction(
             &self,
             _inputs: Vec<TransactionInput>,
             _outputs: TransactionOutputs,
             _lock_time: Option<u32>,
             _expiry_height: Option<u32>,
-        ) -> Result<Transaction, String> {
+        ) -> Result<Transaction, Error> {
             Ok("0100000001ad9d38823d95f31dc6c0cb0724c11a3cf5a466ca4147254a10cd94aade6eb5b3230000006b483045022100b7683165c3ecd57b0c44bf6a0fb258dc08c328458321c8fadc2b9348d4e66bd502204fd164c58d1a949a4d39bb380f8f05c9f6b3e9417f06bf72e5c068428ca3578601210391c35ac5ee7cf82c5015229dcff89507f83f9b8c952b8fecfa469066c1cb44ccffffffff0170f30500000000001976a914801da3cb2ed9e44540f4b982bde07cd3fbae264288ac00000000".into())
         }
+
+        fn sign_raw_transaction(
+            &self,
+            transaction: Transaction,
+            _prev_outputs: HashMap<OutPoint, GlobalTransactionOutput>,
+            keys: Vec<KeyPair>,
+            _sighash_type: u32,
+        ) -> Result<(Transaction, Vec<SignRawTransactionError>), String> {
+            // transaction is signed if at least one key is provided
+            let errors = if keys.is_empty() {
+                transaction
+                    .inputs
+                    .iter()
+                    .map(|input| SignRawTransactionError {
+                        txid: input.previous_output.hash.reversed().into(),
+                        vout: input.previous_output.index,
+                        script_sig: input.script_sig.clone().into(),
+                        sequence: input.sequence,
+                        error: "error".into(),
+                    })
+                    .collect()
+            } else {
+                Vec::new()
+            };
+            Ok((transaction, errors))
+        }
+
+        fn raw_transaction(&self, _hash: GlobalH256) -> Option<RawTransaction> {
+            Some("deadbeef".into())
+        }
+
+        fn verbose_transaction(&self, _hash: GlobalH256) -> Option<VerboseTransaction> {
+            Some(VerboseTransaction {
+                hex: "deadbeef".into(),
+                txid: "0100000000000000000000000000000000000000000000000000000000000000".into(),
+                hash: "0100000000000000000000000000000000000000000000000000000000000000".into(),
+                size: 4,
+                overwintered: false,
+                version: 1,
+                versiongroupid: None,
+                locktime: 0,
+                expiryheight: None,
+                vin: vec![],
+                vout: vec![],
+                vjoinsplit: vec![],
+                value_balance: None,
+                shielded_spends: None,
+                shielded_outputs: None,
+                blockhash: Some(
+                    "0200000000000000000000000000000000000000000000000000000000000000".into(),
+                ),
+                height: Some(1),
+                confirmations: Some(2),
+                time: Some(1477671596),
+                blocktime: Some(1477671596),
+            })
+        }
+
+        fn network(&self) -> KeysNetwork {
+            KeysNetwork::Mainnet
+        }
     }
 
     impl RawClientCoreApi for ErrorRawClientCore {
@@ -254,15 +1199,52 @@ pub mod tests {
             Err("error".to_owned())
         }
 
+        fn test_transactions(
+            &self,
+            transactions: Vec<Transaction>,
+            _max_fee_rate: Option<u64>,
+        ) -> Vec<TestTransactionResult> {
+            transactions
+                .into_iter()
+                .map(|transaction| TestTransactionResult {
+                    hash: transaction.hash(),
+                    size: 100,
+                    result: Err("error".to_owned()),
+                })
+                .collect()
+        }
+
         fn create_raw_transaction(
             &self,
             _inputs: Vec<TransactionInput>,
             _outputs: TransactionOutputs,
             _lock_time: Option<u32>,
             _expiry_height: Option<u32>,
-        ) -> Result<Transaction, String> {
+        ) -> Result<Transaction, Error> {
+            Err(execution("error"))
+        }
+
+        fn sign_raw_transaction(
+            &self,
+            _transaction: Transaction,
+            _prev_outputs: HashMap<OutPoint, GlobalTransactionOutput>,
+            _keys: Vec<KeyPair>,
+            _sighash_type: u32,
+        ) -> Result<(Transaction, Vec<SignRawTransactionError>), String> {
             Err("error".to_owned())
         }
+
+        fn raw_transaction(&self, _hash: GlobalH256) -> Option<RawTransaction> {
+            None
+        }
+
+        fn verbose_transaction(&self, _hash: GlobalH256) -> Option<VerboseTransaction> {
+            None
+        }
+
+        fn network(&self) -> KeysNetwork {
+            KeysNetwork::Testnet
+        }
     }
 
     #[test]
@@ -303,6 +1285,60 @@ pub mod tests {
         assert_eq!(r#"{"jsonrpc":"2.0","error":{"code":-32015,"message":"Execution error.","data":"\"error\""},"id":1}"#, &sample);
     }
 
+    #[test]
+    fn testmempoolaccept_allowed() {
+        let client = RawClient::new(SuccessRawClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler.handle_request_sync(&(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "testmempoolaccept",
+				"params": [["00000000013ba3edfd7a7b12b27ac72c3e67768f617fc81bc3888a51323a9fb8aa4b1e5e4a0000000000000000000101000000000000000000000000"]],
+				"id": 1
+			}"#)
+		).unwrap();
+
+        assert_eq!(r#"{"jsonrpc":"2.0","result":[{"allowed":true,"fees":0.0001,"txid":"34acbc23f60064e0a6e45d9633f5efa5eb068188ff231050fec535d0ccef9107","vsize":100}],"id":1}"#, &sample);
+    }
+
+    #[test]
+    fn testmempoolaccept_rejected() {
+        let client = RawClient::new(ErrorRawClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler.handle_request_sync(&(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "testmempoolaccept",
+				"params": [["00000000013ba3edfd7a7b12b27ac72c3e67768f617fc81bc3888a51323a9fb8aa4b1e5e4a0000000000000000000101000000000000000000000000"], 0.1],
+				"id": 1
+			}"#)
+		).unwrap();
+
+        assert_eq!(r#"{"jsonrpc":"2.0","result":[{"allowed":false,"reject-reason":"error","txid":"34acbc23f60064e0a6e45d9633f5efa5eb068188ff231050fec535d0ccef9107"}],"id":1}"#, &sample);
+    }
+
+    #[test]
+    fn testmempoolaccept_invalid_max_fee_rate() {
+        let client = RawClient::new(SuccessRawClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler.handle_request_sync(&(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "testmempoolaccept",
+				"params": [["00000000013ba3edfd7a7b12b27ac72c3e67768f617fc81bc3888a51323a9fb8aa4b1e5e4a0000000000000000000101000000000000000000000000"], 0.000000001],
+				"id": 1
+			}"#)
+		).unwrap();
+
+        assert_eq!(r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"Couldn't parse parameters: maxfeerate","data":"\"Invalid amount precision: 0.000000001\""},"id":1}"#, &sample);
+    }
+
     #[test]
     fn createrawtransaction_success() {
         let client = RawClient::new(SuccessRawClientCore::default());
@@ -356,4 +1392,723 @@ pub mod tests {
 
         assert_eq!(r#"{"jsonrpc":"2.0","error":{"code":-32015,"message":"Execution error.","data":"\"error\""},"id":1}"#, &sample);
     }
+
+    #[test]
+    fn signrawtransaction_complete() {
+        let client = RawClient::new(SuccessRawClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler.handle_request_sync(&(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "signrawtransaction",
+				"params": ["00000000013ba3edfd7a7b12b27ac72c3e67768f617fc81bc3888a51323a9fb8aa4b1e5e4a0000000000000000000101000000000000000000000000", [], ["5HxWvvfubhXpYYpS3tJkw6fq9jE9j18THftkZjHHfmFiWtmAbrj"]],
+				"id": 1
+			}"#)
+		).unwrap();
+
+        assert_eq!(r#"{"jsonrpc":"2.0","result":{"complete":true,"hex":"00000000013ba3edfd7a7b12b27ac72c3e67768f617fc81bc3888a51323a9fb8aa4b1e5e4a0000000000000000000101000000000000000000000000"},"id":1}"#, &sample);
+    }
+
+    #[test]
+    fn signrawtransaction_incomplete() {
+        let client = RawClient::new(SuccessRawClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler.handle_request_sync(&(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "signrawtransaction",
+				"params": ["00000000013ba3edfd7a7b12b27ac72c3e67768f617fc81bc3888a51323a9fb8aa4b1e5e4a0000000000000000000101000000000000000000000000"],
+				"id": 1
+			}"#)
+		).unwrap();
+
+        assert_eq!(r#"{"jsonrpc":"2.0","result":{"complete":false,"errors":[{"error":"error","scriptSig":"","sequence":0,"txid":"4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b","vout":0}],"hex":"00000000013ba3edfd7a7b12b27ac72c3e67768f617fc81bc3888a51323a9fb8aa4b1e5e4a0000000000000000000101000000000000000000000000"},"id":1}"#, &sample);
+    }
+
+    #[test]
+    fn signrawtransaction_invalid_sighashtype() {
+        let client = RawClient::new(SuccessRawClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler.handle_request_sync(&(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "signrawtransaction",
+				"params": ["00000000013ba3edfd7a7b12b27ac72c3e67768f617fc81bc3888a51323a9fb8aa4b1e5e4a0000000000000000000101000000000000000000000000", null, null, "ANY"],
+				"id": 1
+			}"#)
+		).unwrap();
+
+        assert_eq!(r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"Couldn't parse parameters: sighashtype","data":"\"ANY\""},"id":1}"#, &sample);
+    }
+
+    #[test]
+    fn signrawtransaction_error() {
+        let client = RawClient::new(ErrorRawClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler.handle_request_sync(&(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "signrawtransaction",
+				"params": ["00000000013ba3edfd7a7b12b27ac72c3e67768f617fc81bc3888a51323a9fb8aa4b1e5e4a0000000000000000000101000000000000000000000000"],
+				"id": 1
+			}"#)
+		).unwrap();
+
+        assert_eq!(r#"{"jsonrpc":"2.0","error":{"code":-32015,"message":"Execution error.","data":"\"error\""},"id":1}"#, &sample);
+    }
+
+    #[test]
+    fn getrawtransaction_success() {
+        let client = RawClient::new(SuccessRawClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler.handle_request_sync(&(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "getrawtransaction",
+				"params": ["0000000000000000000000000000000000000000000000000000000000000001"],
+				"id": 1
+			}"#)
+		).unwrap();
+
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","result":"deadbeef","id":1}"#);
+    }
+
+    #[test]
+    fn getrawtransaction_verbose_success() {
+        let client = RawClient::new(SuccessRawClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler.handle_request_sync(&(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "getrawtransaction",
+				"params": ["0000000000000000000000000000000000000000000000000000000000000001", true],
+				"id": 1
+			}"#)
+		).unwrap();
+
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","result":{"blockhash":"0000000000000000000000000000000000000000000000000000000000000002","blocktime":1477671596,"confirmations":2,"hash":"0000000000000000000000000000000000000000000000000000000000000001","height":1,"hex":"deadbeef","locktime":0,"overwintered":false,"size":4,"time":1477671596,"txid":"0000000000000000000000000000000000000000000000000000000000000001","version":1,"vin":[],"vjoinsplit":[],"vout":[]},"id":1}"#);
+    }
+
+    #[test]
+    fn getrawtransaction_not_found() {
+        let client = RawClient::new(ErrorRawClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler.handle_request_sync(&(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "getrawtransaction",
+				"params": ["0000000000000000000000000000000000000000000000000000000000000001", true],
+				"id": 1
+			}"#)
+		).unwrap();
+
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","error":{"code":-32096,"message":"Transaction with given hash is not found","data":"0000000000000000000000000000000000000000000000000000000000000001"},"id":1}"#);
+    }
+
+    #[test]
+    fn decoderawtransaction_success() {
+        let client = RawClient::new(SuccessRawClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler.handle_request_sync(&(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "decoderawtransaction",
+				"params": ["01000000000000000000"],
+				"id": 1
+			}"#)
+		).unwrap();
+
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","result":{"hash":"d21633ba23f70118185227be58a63527675641ad37967e2aa461559f577aec43","hex":"01000000000000000000","locktime":0,"overwintered":false,"size":10,"txid":"d21633ba23f70118185227be58a63527675641ad37967e2aa461559f577aec43","version":1,"vin":[],"vjoinsplit":[],"vout":[]},"id":1}"#);
+    }
+
+    #[test]
+    fn decodescript_success() {
+        let client = RawClient::new(SuccessRawClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler.handle_request_sync(&(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "decodescript",
+				"params": ["76a914e6d1b5e5f5cd8f4e3bf1e3c8ab2d37ac6a8d7c2c88ac"],
+				"id": 1
+			}"#)
+		).unwrap();
+
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","result":{"addresses":["t1ev4eQB2wWiXFChNPY6QPMbvbEdGRM1wUK"],"asm":"OP_DUP OP_HASH160 e6d1b5e5f5cd8f4e3bf1e3c8ab2d37ac6a8d7c2c OP_EQUALVERIFY OP_CHECKSIG","hex":"76a914e6d1b5e5f5cd8f4e3bf1e3c8ab2d37ac6a8d7c2c88ac","p2sh":"t3WwNQdUQhA1XXkEFNn8Qf2wa3GzyDmoSeH","reqSigs":1,"type":"pubkeyhash"},"id":1}"#);
+    }
+
+    #[test]
+    fn decodescript_of_p2sh_script_has_no_p2sh_address() {
+        let script: GlobalBytes = "a9143b80842f4ea32806ce5e723a255ddd6490cfd28d87".into();
+        let decoded = RawClientCore::do_decode_script(&script, KeysNetwork::Mainnet);
+        assert_eq!(decoded.p2sh, None);
+        assert_eq!(decoded.script.addresses.len(), 1);
+    }
+
+    #[test]
+    fn validateaddress_transparent() {
+        let client = RawClient::new(SuccessRawClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler.handle_request_sync(&(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "validateaddress",
+				"params": ["t1h8SqgtM3QM5e2M8EzhhT1yL2PXXtA6oqe"],
+				"id": 1
+			}"#)
+		).unwrap();
+
+        assert_eq!(r#"{"jsonrpc":"2.0","result":{"address":"t1h8SqgtM3QM5e2M8EzhhT1yL2PXXtA6oqe","ismine":false,"isscript":false,"isvalid":true,"type":"p2pkh"},"id":1}"#, &sample);
+    }
+
+    #[test]
+    fn validateaddress_other_network() {
+        let client = RawClient::new(ErrorRawClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler.handle_request_sync(&(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "validateaddress",
+				"params": ["t1h8SqgtM3QM5e2M8EzhhT1yL2PXXtA6oqe"],
+				"id": 1
+			}"#)
+		).unwrap();
+
+        assert_eq!(r#"{"jsonrpc":"2.0","result":{"isvalid":false},"id":1}"#, &sample);
+    }
+
+    #[test]
+    fn validateaddress_invalid() {
+        let client = RawClient::new(SuccessRawClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler.handle_request_sync(&(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "validateaddress",
+				"params": ["t1h8SqgtM3QM5e2M8EzhhT1yL2PXXtA6oqf"],
+				"id": 1
+			}"#)
+		).unwrap();
+
+        assert_eq!(r#"{"jsonrpc":"2.0","result":{"isvalid":false},"id":1}"#, &sample);
+    }
+
+    #[test]
+    fn z_validateaddress_sapling() {
+        let client = RawClient::new(SuccessRawClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler.handle_request_sync(&(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "z_validateaddress",
+				"params": ["zs1qyqqqqqqqqqqqqqqqqpqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqk48wef"],
+				"id": 1
+			}"#)
+		).unwrap();
+
+        assert_eq!(r#"{"jsonrpc":"2.0","result":{"address":"zs1qyqqqqqqqqqqqqqqqqpqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqk48wef","diversifier":"0100000000000000000000","isvalid":true,"type":"sapling"},"id":1}"#, &sample);
+    }
+
+    #[test]
+    fn signmessage_success() {
+        let client = RawClient::new(SuccessRawClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler.handle_request_sync(&(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "signmessage",
+				"params": ["t1h8SqgtM3QM5e2M8EzhhT1yL2PXXtA6oqe", "Hello, Zcash!", ["5HxWvvfubhXpYYpS3tJkw6fq9jE9j18THftkZjHHfmFiWtmAbrj"]],
+				"id": 1
+			}"#)
+		).unwrap();
+
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","result":"HFdX97m+gbpJJ9UuVVrWZRrvJopA/AXawlmEHLyJ5PgMHBSrPN8kAwmHNhZbxmIIvftDcbi9epgNd+ev61psHVw=","id":1}"#);
+    }
+
+    #[test]
+    fn signmessage_private_key_not_available() {
+        let client = RawClient::new(SuccessRawClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler.handle_request_sync(&(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "signmessage",
+				"params": ["t1h8SqgtM3QM5e2M8EzhhT1yL2PXXtA6oqe", "Hello, Zcash!", []],
+				"id": 1
+			}"#)
+		).unwrap();
+
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","error":{"code":-32092,"message":"Private key for the address is not available","data":"\"t1h8SqgtM3QM5e2M8EzhhT1yL2PXXtA6oqe\""},"id":1}"#);
+    }
+
+    #[test]
+    fn verifymessage_success() {
+        let client = RawClient::new(SuccessRawClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler.handle_request_sync(&(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "verifymessage",
+				"params": ["t1h8SqgtM3QM5e2M8EzhhT1yL2PXXtA6oqe", "HFdX97m+gbpJJ9UuVVrWZRrvJopA/AXawlmEHLyJ5PgMHBSrPN8kAwmHNhZbxmIIvftDcbi9epgNd+ev61psHVw=", "Hello, Zcash!"],
+				"id": 1
+			}"#)
+		).unwrap();
+
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","result":true,"id":1}"#);
+    }
+
+    #[test]
+    fn verifymessage_shielded_address() {
+        let client = RawClient::new(SuccessRawClientCore::default());
+        let mut handler = IoHandler::new();
+        handler.extend_with(client.to_delegate());
+
+        let sample = handler.handle_request_sync(&(r#"
+			{
+				"jsonrpc": "2.0",
+				"method": "verifymessage",
+				"params": ["zc8MfnkbU4wMEyu6ayLkXccy1cWGGSjAyDCeLxsMpWgD3VQzZgt5ojdvkiD1TUuA1xCG1gFmYzHDUR1meP1KX5b9vLYdBYp", "HFdX97m+gbpJJ9UuVVrWZRrvJopA/AXawlmEHLyJ5PgMHBSrPN8kAwmHNhZbxmIIvftDcbi9epgNd+ev61psHVw=", "Hello, Zcash!"],
+				"id": 1
+			}"#)
+		).unwrap();
+
+        assert_eq!(&sample, r#"{"jsonrpc":"2.0","error":{"code":-32093,"message":"Address does not refer to transparent key","data":"\"zc8MfnkbU4wMEyu6ayLkXccy1cWGGSjAyDCeLxsMpWgD3VQzZgt5ojdvkiD1TUuA1xCG1gFmYzHDUR1meP1KX5b9vLYdBYp\""},"id":1}"#);
+    }
+
+    #[test]
+    fn verify_message_round_trip() {
+        let address = "t1h8SqgtM3QM5e2M8EzhhT1yL2PXXtA6oqe";
+        let keys = vec!["5HxWvvfubhXpYYpS3tJkw6fq9jE9j18THftkZjHHfmFiWtmAbrj".to_owned()];
+        let signature = sign_message(address, "message", &keys, KeysNetwork::Mainnet).unwrap();
+        assert_eq!(
+            verify_message(address, &signature, "message", KeysNetwork::Mainnet),
+            Ok(true)
+        );
+        assert_eq!(
+            verify_message(address, &signature, "other message", KeysNetwork::Mainnet),
+            Ok(false)
+        );
+        // the key of other address
+        assert_eq!(
+            verify_message(
+                "t1Xxa5ZVPKvs9bGMn7aWTiHjyHvR31XkUst",
+                &signature,
+                "message",
+                KeysNetwork::Mainnet
+            ),
+            Ok(false)
+        );
+        assert!(verify_message(address, &signature, "message", KeysNetwork::Testnet).is_err());
+    }
+
+    #[test]
+    fn validate_address_dispatches_on_encoding() {
+        let sprout = "zc8MfnkbU4wMEyu6ayLkXccy1cWGGSjAyDCeLxsMpWgD3VQzZgt5ojdvkiD1TUuA1xCG1gFmYzHDUR1meP1KX5b9vLYdBYp";
+        assert_eq!(
+            validate_address(sprout, KeysNetwork::Mainnet),
+            ValidateAddressResponse {
+                isvalid: true,
+                address: Some(sprout.into()),
+                address_type: Some("sprout".into()),
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            validate_address(sprout, KeysNetwork::Testnet),
+            ValidateAddressResponse::default()
+        );
+
+        // P2SH addresses are reported as scripts
+        let p2sh = zebra_keys::Address {
+            kind: AddressType::P2SH,
+            network: KeysNetwork::Testnet,
+            hash: 1.into(),
+        }
+        .to_string();
+        let response = validate_address(&p2sh, KeysNetwork::Testnet);
+        assert_eq!(response.address_type, Some("p2sh".into()));
+        assert_eq!(response.isscript, Some(true));
+    }
+
+    #[test]
+    fn select_consensus_branch_id_by_version_group_id() {
+        let consensus = ConsensusParams::new(Network::Mainnet);
+        let sapling_height = consensus.sapling_height;
+        let overwinter_height = consensus.overwinter_height;
+
+        let sapling_transaction = Transaction {
+            overwintered: true,
+            version: SAPLING_TX_VERSION,
+            version_group_id: SAPLING_TX_VERSION_GROUP_ID,
+            ..Default::default()
+        };
+        let overwinter_transaction = Transaction {
+            overwintered: true,
+            version: 3,
+            version_group_id: OVERWINTER_TX_VERSION_GROUP_ID,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            RawClientCore::select_consensus_branch_id(
+                &consensus,
+                sapling_height,
+                &sapling_transaction
+            ),
+            Ok(0x76b809bb)
+        );
+        assert_eq!(
+            RawClientCore::select_consensus_branch_id(
+                &consensus,
+                overwinter_height,
+                &overwinter_transaction
+            ),
+            Ok(0x5ba81b19)
+        );
+        assert!(RawClientCore::select_consensus_branch_id(
+            &consensus,
+            overwinter_height,
+            &sapling_transaction
+        )
+        .is_err());
+        assert!(RawClientCore::select_consensus_branch_id(
+            &consensus,
+            sapling_height,
+            &overwinter_transaction
+        )
+        .is_err());
+        assert_eq!(
+            RawClientCore::select_consensus_branch_id(
+                &consensus,
+                sapling_height,
+                &Transaction::default()
+            ),
+            Ok(0x76b809bb)
+        );
+        // Sapling transactions are signed with the branch id of the active upgrade
+        assert_eq!(
+            RawClientCore::select_consensus_branch_id(
+                &consensus,
+                consensus.blossom_height,
+                &sapling_transaction
+            ),
+            Ok(0x2bb40e60)
+        );
+        assert!(RawClientCore::select_consensus_branch_id(
+            &consensus,
+            consensus.blossom_height,
+            &overwinter_transaction
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn do_sign_raw_transaction_signs_inputs() {
+        let key_pair =
+            KeyPair::from_private("5HxWvvfubhXpYYpS3tJkw6fq9jE9j18THftkZjHHfmFiWtmAbrj".into())
+                .unwrap();
+        let other_key_pair =
+            KeyPair::from_private("5KC4ejrDjv152FGwP386VD1i2NYc5KkfSMyv1nGy1VGDxGHqVY3".into())
+                .unwrap();
+
+        let prevout = |index| OutPoint {
+            hash: GlobalH256::from(1),
+            index: index,
+        };
+        let mut prev_outputs = HashMap::new();
+        prev_outputs.insert(
+            prevout(0),
+            GlobalTransactionOutput {
+                value: 100_000,
+                script_pubkey: ScriptBuilder::build_p2pkh(&key_pair.public().address_hash())
+                    .to_bytes(),
+            },
+        );
+        prev_outputs.insert(
+            prevout(1),
+            GlobalTransactionOutput {
+                value: 200_000,
+                script_pubkey: ScriptBuilder::build_p2pkh(&other_key_pair.public().address_hash())
+                    .to_bytes(),
+            },
+        );
+
+        let input = |index| zebra_chain::TransactionInput {
+            previous_output: prevout(index),
+            script_sig: GlobalBytes::new(),
+            sequence: zebra_chain::constants::SEQUENCE_FINAL,
+        };
+        let transaction = Transaction {
+            overwintered: true,
+            version: SAPLING_TX_VERSION,
+            version_group_id: SAPLING_TX_VERSION_GROUP_ID,
+            inputs: vec![input(0), input(1), input(2)],
+            outputs: vec![GlobalTransactionOutput {
+                value: 250_000,
+                script_pubkey: ScriptBuilder::build_p2pkh(&key_pair.public().address_hash())
+                    .to_bytes(),
+            }],
+            ..Default::default()
+        };
+
+        let (signed_transaction, errors) = RawClientCore::do_sign_raw_transaction(
+            transaction,
+            0x76b809bb,
+            &prev_outputs,
+            &[key_pair],
+            u32::from(SighashBase::All),
+        );
+
+        // first input is signed && verified
+        assert!(!signed_transaction.inputs[0].script_sig.is_empty());
+        // there's no key for the second input && the third input is unknown
+        assert!(signed_transaction.inputs[1].script_sig.is_empty());
+        assert!(signed_transaction.inputs[2].script_sig.is_empty());
+        assert_eq!(
+            errors
+                .iter()
+                .map(|e| (e.vout, e.error.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                (1, "Private key for input is not found"),
+                (2, "Input not found or already spent"),
+            ]
+        );
+
+        // when signed again with the second key, signature of the first input is preserved
+        let (resigned_transaction, errors) = RawClientCore::do_sign_raw_transaction(
+            signed_transaction.clone(),
+            0x76b809bb,
+            &prev_outputs,
+            &[other_key_pair],
+            u32::from(SighashBase::All),
+        );
+        assert_eq!(
+            resigned_transaction.inputs[0].script_sig,
+            signed_transaction.inputs[0].script_sig
+        );
+        assert!(!resigned_transaction.inputs[1].script_sig.is_empty());
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].vout, 2);
+    }
+
+    fn create_raw_transaction_with_amount(
+        consensus: &ConsensusParams,
+        best_block_number: u32,
+        amount: f64,
+    ) -> Result<Transaction, Error> {
+        RawClientCore::do_create_raw_transaction(
+            consensus,
+            best_block_number,
+            vec![TransactionInput {
+                txid: GlobalH256::from(1).into(),
+                vout: 0,
+                sequence: None,
+            }],
+            TransactionOutputs {
+                outputs: vec![TransactionOutput::Address(TransactionOutputWithAddress {
+                    address: "t1h8SqgtM3QM5e2M8EzhhT1yL2PXXtA6oqe".parse().unwrap(),
+                    amount: amount,
+                })],
+            },
+            None,
+            None,
+        )
+    }
+
+    #[test]
+    fn do_create_raw_transaction_selects_version_by_network_upgrade() {
+        let consensus = ConsensusParams::new(Network::Mainnet);
+
+        let transaction =
+            create_raw_transaction_with_amount(&consensus, consensus.sapling_height - 1, 0.01)
+                .unwrap();
+        assert!(transaction.overwintered);
+        assert_eq!(transaction.version, SAPLING_TX_VERSION);
+        assert_eq!(transaction.version_group_id, SAPLING_TX_VERSION_GROUP_ID);
+        assert_eq!(
+            transaction.expiry_height,
+            consensus.sapling_height - 1 + DEFAULT_TX_EXPIRY_DELTA
+        );
+        assert_eq!(transaction.outputs[0].value, 1_000_000);
+
+        let transaction =
+            create_raw_transaction_with_amount(&consensus, consensus.overwinter_height - 1, 0.01)
+                .unwrap();
+        assert!(transaction.overwintered);
+        assert_eq!(transaction.version, OVERWINTER_TX_VERSION);
+        assert_eq!(transaction.version_group_id, OVERWINTER_TX_VERSION_GROUP_ID);
+
+        let transaction =
+            create_raw_transaction_with_amount(&consensus, consensus.overwinter_height - 2, 0.01)
+                .unwrap();
+        assert!(!transaction.overwintered);
+        assert_eq!(transaction.version, BTC_TX_VERSION);
+        assert_eq!(transaction.expiry_height, 0);
+    }
+
+    #[test]
+    fn amount_to_satoshis_validates_range_and_precision() {
+        assert_eq!(amount_to_satoshis(0.0), Ok(0));
+        assert_eq!(amount_to_satoshis(0.29), Ok(29_000_000));
+        assert_eq!(amount_to_satoshis(0.00000001), Ok(1));
+        assert_eq!(
+            amount_to_satoshis(20_999_999.99999999),
+            Ok(2_099_999_999_999_999)
+        );
+        assert!(amount_to_satoshis(-0.01).is_err());
+        assert!(amount_to_satoshis(0.000000015).is_err());
+        assert!(amount_to_satoshis(1e20).is_err());
+    }
+
+    #[test]
+    fn do_create_raw_transaction_rejects_invalid_amounts() {
+        let consensus = ConsensusParams::new(Network::Mainnet);
+        let best_block_number = consensus.sapling_height;
+
+        assert!(create_raw_transaction_with_amount(&consensus, best_block_number, -0.01).is_err());
+        assert!(
+            create_raw_transaction_with_amount(&consensus, best_block_number, 21_000_000.0).is_ok()
+        );
+        assert!(
+            create_raw_transaction_with_amount(&consensus, best_block_number, 21_000_000.01)
+                .is_err()
+        );
+        assert!(
+            create_raw_transaction_with_amount(&consensus, best_block_number, ::std::f64::NAN)
+                .is_err()
+        );
+        assert!(create_raw_transaction_with_amount(
+            &consensus,
+            best_block_number,
+            ::std::f64::INFINITY
+        )
+        .is_err());
+        assert!(
+            create_raw_transaction_with_amount(&consensus, best_block_number, 0.000000001)
+                .is_err()
+        );
+
+        let transaction =
+            create_raw_transaction_with_amount(&consensus, best_block_number, 0.29).unwrap();
+        assert_eq!(transaction.outputs[0].value, 29_000_000);
+    }
+
+    #[test]
+    fn do_find_transaction_respects_transaction_index() {
+        let b0: IndexedBlock = zebra_test_data::block_builder()
+            .header()
+            .time(100)
+            .build()
+            .transaction()
+            .coinbase()
+            .output()
+            .value(10)
+            .build()
+            .build()
+            .build()
+            .into();
+        let tx0_hash = b0.transactions[0].hash.clone();
+        let b1: IndexedBlock = zebra_test_data::block_builder()
+            .header()
+            .parent(b0.hash().clone())
+            .time(200)
+            .build()
+            .transaction()
+            .coinbase()
+            .output()
+            .value(1)
+            .build()
+            .build()
+            .transaction()
+            .input()
+            .hash(tx0_hash.clone())
+            .index(0)
+            .build()
+            .output()
+            .value(10)
+            .build()
+            .build()
+            .build()
+            .into();
+        let tx1_hash = b1.transactions[1].hash.clone();
+
+        let mut memory_pool = MemoryPool::new();
+        let mempool_tx: Transaction = zebra_test_data::TransactionBuilder::with_output(5)
+            .add_input(&b1.transactions[1].raw, 0)
+            .into();
+        let mempool_tx_hash = mempool_tx.hash();
+        memory_pool
+            .insert_verified(mempool_tx.into(), &NonZeroFeeCalculator)
+            .unwrap();
+
+        for transaction_index in vec![false, true] {
+            let mut storage = BlockChainDatabase::open(MemoryDatabase::default());
+            storage.set_transaction_index(transaction_index).unwrap();
+            for block in vec![b0.clone(), b1.clone()] {
+                let hash = block.hash().clone();
+                storage.insert(block).unwrap();
+                storage.canonize(&hash).unwrap();
+            }
+
+            // memory pool transactions are always found
+            let (_, block_hash) =
+                RawClientCore::do_find_transaction(&storage, &memory_pool, &mempool_tx_hash)
+                    .unwrap();
+            assert_eq!(block_hash, None);
+            // transactions with unspent outputs are always found
+            let (transaction, block_hash) =
+                RawClientCore::do_find_transaction(&storage, &memory_pool, &tx1_hash).unwrap();
+            assert_eq!(block_hash, Some(b1.hash().clone()));
+            // fully spent transactions are only found using the index
+            let found = RawClientCore::do_find_transaction(&storage, &memory_pool, &tx0_hash);
+            assert_eq!(found.is_some(), transaction_index);
+
+            let verbose = RawClientCore::do_verbose_transaction(
+                &storage,
+                KeysNetwork::Mainnet,
+                transaction,
+                block_hash,
+            );
+            assert_eq!(verbose.txid, H256::from(tx1_hash.clone()));
+            assert_eq!(verbose.blockhash, Some(H256::from(b1.hash().clone())));
+            assert_eq!(verbose.height, Some(1));
+            assert_eq!(verbose.confirmations, Some(1));
+            assert_eq!(verbose.blocktime, Some(200));
+            assert_eq!(verbose.vin[0].txid, H256::from(tx0_hash.clone()));
+            assert_eq!(verbose.vout.len(), 1);
+        }
+    }
 }
@@ -1,10 +1,18 @@
 use jsonrpc_core::Error;
 use jsonrpc_derive::rpc;
 
+use v1::types::AddressUtxo;
+use v1::types::GetAddressBalanceResponse;
+use v1::types::GetBlockChainInfoResponse;
+use v1::types::GetBlockDeltasResponse;
 use v1::types::GetBlockResponse;
+use v1::types::GetBlockStatsResponse;
+use v1::types::GetChainTxStatsResponse;
+use v1::types::GetDbInfoResponse;
+use v1::types::GetTreeStateResponse;
 use v1::types::GetTxOutResponse;
 use v1::types::GetTxOutSetInfoResponse;
-use v1::types::{BlockRef, H256};
+use v1::types::{BlockRef, Bytes, H256};
 
 /// Parity-bitcoin blockchain data interface.
 #[rpc]
@@ -21,16 +29,33 @@ pub trait BlockChain {
     /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "method": "getblockhash", "params": [0], "id":1 }' -H 'content-type: application/json' http://127.0.0.1:8332/
     #[rpc(name = "getblockhash")]
     fn block_hash(&self, u32) -> Result<H256, Error>;
-    /// Get proof-of-work difficulty for the next block as a multiple of the minimum difficulty
+    /// Get height of given canonical block.
+    /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "method": "getblockheight", "params": ["0002a26c902619fc964443264feb16f1e3e2d71322fc53dcb81cc5d797e273ed"], "id":1 }' -H 'content-type: application/json' http://127.0.0.1:8332/
+    #[rpc(name = "getblockheight")]
+    fn block_height(&self, H256) -> Result<u32, Error>;
+    /// Get hashes of canonical blocks with timestamps in given range (`low` <= time <= `high`), ordered by height.
+    /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "method": "getblockhashes", "params": [1477641360, 1477641000], "id":1 }' -H 'content-type: application/json' http://127.0.0.1:8332/
+    #[rpc(name = "getblockhashes")]
+    fn block_hashes(&self, u32, u32) -> Result<Vec<H256>, Error>;
+    /// Get proof-of-work difficulty of the best block as a multiple of the genesis block difficulty.
     /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "method": "getdifficulty", "params": [], "id":1 }' -H 'content-type: application/json' http://127.0.0.1:8332/
     #[rpc(name = "getdifficulty")]
     fn difficulty(&self) -> Result<f64, Error>;
+    /// Get compact proof-of-work target (`bits`) the next block must satisfy.
+    /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "method": "getnextworkrequired", "params": [], "id":1 }' -H 'content-type: application/json' http://127.0.0.1:8332/
+    #[rpc(name = "getnextworkrequired")]
+    fn next_work_required(&self) -> Result<u32, Error>;
     /// Get information on given block.
+    /// With verbosity 2, decoded transactions are returned instead of transactions ids.
     /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "method": "getblock", "params": ["0002a26c902619fc964443264feb16f1e3e2d71322fc53dcb81cc5d797e273ed", 0], "id":1 }' -H 'content-type: application/json' http://127.0.0.1:8332/
+    /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "method": "getblock", "params": ["0002a26c902619fc964443264feb16f1e3e2d71322fc53dcb81cc5d797e273ed", 2], "id":1 }' -H 'content-type: application/json' http://127.0.0.1:8332/
     /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "method": "getblock", "params": ["0002a26c902619fc964443264feb16f1e3e2d71322fc53dcb81cc5d797e273ed"], "id":1 }' -H 'content-type: application/json' http://127.0.0.1:8332/
     #[rpc(name = "getblock")]
     fn block(&self, BlockRef, Option<u8>) -> Result<GetBlockResponse, Error>;
     /// Get details about an unspent transaction output.
+    /// If `include_mempool` is true, outputs spent by memory pool transactions are reported as unavailable
+    /// and outputs of memory pool transactions are returned with zero confirmations.
+    /// Note that coinbase outputs could only be spent after they have reached coinbase maturity (100 confirmations).
     /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "method": "gettxout", "params": ["4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b", 0], "id":1 }' -H 'content-type: application/json' http://127.0.0.1:8332/
     #[rpc(name = "gettxout")]
     fn transaction_out(&self, H256, u32, Option<bool>) -> Result<GetTxOutResponse, Error>;
@@ -38,4 +63,70 @@ pub trait BlockChain {
     /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "method": "gettxoutsetinfo", "params": [], "id":1 }' -H 'content-type: application/json' http://127.0.0.1:8332/
     #[rpc(name = "gettxoutsetinfo")]
     fn transaction_out_set_info(&self) -> Result<GetTxOutSetInfoResponse, Error>;
+    /// Get serialized proof (`merkleblock`) that given transactions are included in the block.
+    /// The block is located using the transactions index, unless `blockhash` is specified.
+    /// All transactions must be included in the same block.
+    /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "method": "gettxoutproof", "params": [["c4eaa58879081de3c24a7b117ed2b28300e7ec4c4c1dff1d3f1268b7857a4ddb"]], "id":1 }' -H 'content-type: application/json' http://127.0.0.1:8332/
+    #[rpc(name = "gettxoutproof")]
+    fn transaction_out_proof(&self, Vec<H256>, Option<H256>) -> Result<Bytes, Error>;
+    /// Verify proof, returned by `gettxoutproof`, and get hashes of proven transactions.
+    /// Empty array is returned if proof is invalid.
+    /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "method": "verifytxoutproof", "params": ["040000000000000000000000000000000000000000000000000000000000000000000000db4d7a85b768123f1dff1d4c4cece70083b2d27e117b4ac2e31d087988a5eac4000000000000000000000000000000000000000000000000000000000000000090041358ffff071f5712000000000000000000000000000000000000000000000000000000000000fd4005000a889f00854b8665cd555f4656f68179d31ccadc1b1f7fb0952726313b16941da348284d67add4686121d4e3d930160c1348d8191c25f12b267a6a9c131b5031cbf8af1f79c9d513076a216ec87ed045fa966e01214ed83ca02dc1797270a454720d3206ac7d931a0a680c5c5e099057592570ca9bdf6058343958b31901fce1a15a4f38fd347750912e14004c73dfe588b903b6c03166582eeaf30529b14072a7b3079e3a684601b9b3024054201f7440b0ee9eb1a7120ff43f713735494aa27b1f8bab60d7f398bca14f6abb2adbf29b04099121438a7974b078a11635b594e9170f1086140b4173822dd697894483e1c6b4e8b8dcd5cb12ca4903bc61e108871d4d915a9093c18ac9b02b6716ce1013ca2c1174e319c1a570215bc9ab5f7564765f7be20524dc3fdf8aa356fd94d445e05ab165ad8bb4a0db096c097618c81098f91443c719416d39837af6de85015dca0de89462b1d8386758b2cf8a99e00953b308032ae44c35e05eb71842922eb69797f68813b59caf266cb6c213569ae3280505421a7e3a0a37fdf8e2ea354fc5422816655394a9454bac542a9298f176e211020d63dee6852c40de02267e2fc9d5e1ff2ad9309506f02a1a71a0501b16d0d36f70cdfd8de78116c0c506ee0b8ddfdeb561acadf31746b5a9dd32c21930884397fb1682164cb565cc14e089d66635a32618f7eb05fe05082b8a3fae620571660a6b89886eac53dec109d7cbb6930ca698a168f301a950be152da1be2b9e07516995e20baceebecb5579d7cdbc16d09f3a50cb3c7dffe33f26686d4ff3f8946ee6475e98cf7b3cf9062b6966e838f865ff3de5fb064a37a21da7bb8dfd2501a29e184f207caaba364f36f2329a77515dcb710e29ffbf73e2bbd773fab1f9a6b005567affff605c132e4e4dd69f36bd201005458cfbd2c658701eb2a700251cefd886b1e674ae816d3f719bac64be649c172ba27a4fd55947d95d53ba4cbc73de97b8af5ed4840b659370c556e7376457f51e5ebb66018849923db82c1c9a819f173cccdb8f3324b239609a300018d0fb094adf5bd7cbb3834c69e6d0b3798065c525b20f040e965e1a161af78ff7561cd874f5f1b75aa0bc77f720589e1b810f831eac5073e6dd46d00a2793f70f7427f0f798f2f53a67e615e65d356e66fe40609a958a05edb4c175bcc383ea0530e67ddbe479a898943c6e3074c6fcc252d6014de3a3d292b03f0d88d312fe221be7be7e3c59d07fa0f2f4029e364f1f355c5d01fa53770d0cd76d82bf7e60f6903bc1beb772e6fde4a70be51d9c7e03c8d6d8dfb361a234ba47c470fe630820bbd920715621b9fbedb49fcee165ead0875e6c2b1af16f50b5d6140cc981122fcbcf7c5a4e3772b3661b628e08380abc545957e59f634705b1bbde2f0b4e055a5ec5676d859be77e20962b645e051a880fddb0180b4555789e1f9344a436a84dc5579e2553f1e5fb0a599c137be36cabbed0319831fea3fddf94ddc7971e4bcf02cdc93294a9aab3e3b13e3b058235b4f4ec06ba4ceaa49d675b4ba80716f3bc6976b1fbf9c8bf1f3e3a4dc1cd83ef9cf816667fb94f1e923ff63fef072e6a19321e4812f96cb0ffa864da50ad74deb76917a336f31dce03ed5f0303aad5e6a83634f9fcc371096f8288b8f02ddded5ff1bb9d49331e4a84dbe1543164438fde9ad71dab024779dcdde0b6602b5ae0a6265c14b94edd83b37403f4b78fcd2ed555b596402c28ee81d87a909c4e8722b30c71ecdd861b05f61f8b1231795c76adba2fdefa451b283a5d527955b9f3de1b9828e7b2e74123dd47062ddcc09b05e7fa13cb2212a6fdbc65d7e852cec463ec6fd929f5b8483cf3052113b13dac91b69f49d1b7d1aec01c4a68e41ce1570100000001db4d7a85b768123f1dff1d4c4cece70083b2d27e117b4ac2e31d087988a5eac40101"], "id":1 }' -H 'content-type: application/json' http://127.0.0.1:8332/
+    #[rpc(name = "verifytxoutproof")]
+    fn verify_transaction_out_proof(&self, Bytes) -> Result<Vec<H256>, Error>;
+    /// Verify stored blockchain database.
+    /// `checklevel` (0-4, default 3) sets how thorough the verification is, `nblocks` (default 288, 0 = all)
+    /// sets how many best blocks are checked.
+    /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "method": "verifychain", "params": [3, 6], "id":1 }' -H 'content-type: application/json' http://127.0.0.1:8332/
+    #[rpc(name = "verifychain")]
+    fn verify_chain(&self, Option<u32>, Option<u32>) -> Result<bool, Error>;
+    /// Get approximate number of keys and size of every database column.
+    /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "method": "getdbinfo", "params": [], "id":1 }' -H 'content-type: application/json' http://127.0.0.1:8332/
+    #[rpc(name = "getdbinfo")]
+    fn db_info(&self) -> Result<GetDbInfoResponse, Error>;
+    /// Get statistics of given canonical block.
+    /// Optional `stats` array selects statistics to compute: total_size, txs, total_out, totalfee,
+    /// avgfee, minfee, maxfee, avgfeerate, ins, outs, subsidy, total_sigops. All are computed by default.
+    /// Fee statistics fail if any spent output can't be read (e.g. it has been pruned).
+    /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "method": "getblockstats", "params": ["0002a26c902619fc964443264feb16f1e3e2d71322fc53dcb81cc5d797e273ed", ["totalfee", "txs"]], "id":1 }' -H 'content-type: application/json' http://127.0.0.1:8332/
+    #[rpc(name = "getblockstats")]
+    fn block_stats(&self, BlockRef, Option<Vec<String>>) -> Result<GetBlockStatsResponse, Error>;
+    /// Get statistics about the total number and rate of transactions in the chain.
+    /// The window of `nblocks` blocks ends with canonical block `blockhash` (best block by default).
+    /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "method": "getchaintxstats", "params": [576], "id":1 }' -H 'content-type: application/json' http://127.0.0.1:8332/
+    /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "method": "getchaintxstats", "params": [576, "0002a26c902619fc964443264feb16f1e3e2d71322fc53dcb81cc5d797e273ed"], "id":1 }' -H 'content-type: application/json' http://127.0.0.1:8332/
+    #[rpc(name = "getchaintxstats")]
+    fn chain_tx_stats(&self, u32, Option<H256>) -> Result<GetChainTxStatsResponse, Error>;
+    /// Get unspent outputs, paying to given transparent addresses. Requires address index to be enabled.
+    /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "method": "getaddressutxos", "params": [["t1h8SqgtM3QM5e2M8EzhhT1yL2PXXtA6oqe"]], "id":1 }' -H 'content-type: application/json' http://127.0.0.1:8332/
+    #[rpc(name = "getaddressutxos")]
+    fn address_utxos(&self, Vec<String>) -> Result<Vec<AddressUtxo>, Error>;
+    /// Get balance of given transparent addresses. Requires address index to be enabled.
+    /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "method": "getaddressbalance", "params": [["t1h8SqgtM3QM5e2M8EzhhT1yL2PXXtA6oqe"]], "id":1 }' -H 'content-type: application/json' http://127.0.0.1:8332/
+    #[rpc(name = "getaddressbalance")]
+    fn address_balance(&self, Vec<String>) -> Result<GetAddressBalanceResponse, Error>;
+    /// Get hashes of transactions, spending from or paying to given transparent addresses within given heights range.
+    /// Requires address index to be enabled.
+    /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "method": "getaddresstxids", "params": [["t1h8SqgtM3QM5e2M8EzhhT1yL2PXXtA6oqe"], 0, 1000], "id":1 }' -H 'content-type: application/json' http://127.0.0.1:8332/
+    #[rpc(name = "getaddresstxids")]
+    fn address_txids(&self, Vec<String>, Option<u32>, Option<u32>) -> Result<Vec<H256>, Error>;
+    /// Check if given nullifier has been revealed by canonical transaction of `sprout` or `sapling` pool.
+    /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "method": "getnullifier", "params": ["1b45c5f57d39cf7a8ba4a2d4a0ee5bbc3f2e5b8cd52bd73a3b0e9d7c0ca3e2f1", "sapling"], "id":1 }' -H 'content-type: application/json' http://127.0.0.1:8332/
+    #[rpc(name = "getnullifier")]
+    fn nullifier(&self, H256, String) -> Result<bool, Error>;
+    /// Get Sprout and Sapling commitment trees after applying given block.
+    /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "method": "z_gettreestate", "params": ["0002a26c902619fc964443264feb16f1e3e2d71322fc53dcb81cc5d797e273ed"], "id":1 }' -H 'content-type: application/json' http://127.0.0.1:8332/
+    #[rpc(name = "z_gettreestate")]
+    fn tree_state(&self, H256) -> Result<GetTreeStateResponse, Error>;
+    /// Get summary of the blockchain state, including network upgrades activation status.
+    /// `size_on_disk` is estimated by the database engine, so it could be inaccurate.
+    /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "method": "getblockchaininfo", "params": [], "id":1 }' -H 'content-type: application/json' http://127.0.0.1:8332/
+    #[rpc(name = "getblockchaininfo")]
+    fn blockchain_info(&self) -> Result<GetBlockChainInfoResponse, Error>;
+    /// Get transparent inputs (along with spent outputs) and outputs of every transaction of given canonical block.
+    /// Spent outputs are read from the database, so this call is expensive. It requires either transaction index,
+    /// or database that has never been pruned.
+    /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "method": "getblockdeltas", "params": ["0002a26c902619fc964443264feb16f1e3e2d71322fc53dcb81cc5d797e273ed"], "id":1 }' -H 'content-type: application/json' http://127.0.0.1:8332/
+    #[rpc(name = "getblockdeltas")]
+    fn block_deltas(&self, H256) -> Result<GetBlockDeltasResponse, Error>;
 }
@@ -1,12 +1,71 @@
 use jsonrpc_core::Error;
 
-use v1::types::{BlockTemplate, BlockTemplateRequest};
+use v1::types::{
+    BlockTemplate, BlockTemplateRequest, EstimateSmartFeeResponse, GetMemPoolInfoResponse,
+    GetRawMemPoolResponse, RawBlock, RescanBlockChainResponse, H256,
+};
 
 /// Parity-bitcoin miner data interface.
 #[rpc]
 pub trait Miner {
     /// Get block template for mining.
+    /// If `longpollid` of the current template is passed, waits until the best block is changed (or timeout elapses).
     /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "method": "getblocktemplate", "params": [{"capabilities": ["coinbasetxn", "workid", "coinbase/append"]}], "id":1 }' -H 'content-type: application/json' http://127.0.0.1:8332/
     #[rpc(name = "getblocktemplate")]
     fn get_block_template(&self, BlockTemplateRequest) -> Result<BlockTemplate, Error>;
+    /// Submit new block to the network.
+    /// Returns null if block is accepted, "duplicate" if block is already known, "inconclusive" if block
+    /// is valid, but is not extending the best chain, or the verification error otherwise.
+    /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "method": "submitblock", "params": ["040000000000000000000000000000000000000000000000000000000000000000000000db4d7a85b768123f1dff1d4c4cece70083b2d27e117b4ac2e31d087988a5eac40000000000000000000000000000000000000000000000000000000000000000a5a7ec58ffff071f00000000000000000000000000000000000000000000000000000000000000000000000000fd400500"], "id":1 }' -H 'content-type: application/json' http://127.0.0.1:8332/
+    #[rpc(name = "submitblock")]
+    fn submit_block(&self, RawBlock) -> Result<Option<String>, Error>;
+    /// Get information on memory pool state.
+    /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "method": "getmempoolinfo", "params": [], "id":1 }' -H 'content-type: application/json' http://127.0.0.1:8332/
+    #[rpc(name = "getmempoolinfo")]
+    fn memory_pool_info(&self) -> Result<GetMemPoolInfoResponse, Error>;
+    /// Get hashes of all memory pool transactions (or verbose information on these transactions).
+    /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "method": "getrawmempool", "params": [], "id":1 }' -H 'content-type: application/json' http://127.0.0.1:8332/
+    /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "method": "getrawmempool", "params": [true], "id":1 }' -H 'content-type: application/json' http://127.0.0.1:8332/
+    #[rpc(name = "getrawmempool")]
+    fn raw_memory_pool(&self, Option<bool>) -> Result<GetRawMemPoolResponse, Error>;
+    /// Estimate fee rate (in ZEC/kB), required for transaction to be mined within given number of blocks.
+    /// Returns -1 if there's not enough data to make an estimation.
+    /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "method": "estimatefee", "params": [6], "id":1 }' -H 'content-type: application/json' http://127.0.0.1:8332/
+    #[rpc(name = "estimatefee")]
+    fn estimate_fee(&self, u32) -> Result<f64, Error>;
+    /// Estimate fee rate (in ZEC/kB), required for transaction to be mined within given number of blocks.
+    /// If there's not enough data for given target, estimation for the nearest greater target is returned.
+    /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "method": "estimatesmartfee", "params": [6], "id":1 }' -H 'content-type: application/json' http://127.0.0.1:8332/
+    #[rpc(name = "estimatesmartfee")]
+    fn estimate_smart_fee(&self, u32) -> Result<EstimateSmartFeeResponse, Error>;
+    /// Replay canonical blocks, starting from given height (or genesis) up to the best block, through
+    /// all installed synchronization listeners. Returns the rescanned heights range.
+    /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "method": "rescanblockchain", "params": [100000], "id":1 }' -H 'content-type: application/json' http://127.0.0.1:8332/
+    #[rpc(name = "rescanblockchain")]
+    fn rescan_blockchain(&self, Option<u32>) -> Result<RescanBlockChainResponse, Error>;
+    /// Write all memory pool transactions to the dump file, which is loaded on the next node start.
+    /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "method": "savemempool", "params": [], "id":1 }' -H 'content-type: application/json' http://127.0.0.1:8332/
+    #[rpc(name = "savemempool")]
+    fn save_memory_pool(&self) -> Result<(), Error>;
+    /// Add virtual fee delta (in satoshis) to the memory pool transaction. Delta is used when selecting
+    /// transactions for mining, but not when checking if transaction pays minimal relay fee.
+    /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "method": "prioritisetransaction", "params": ["7c3e7d8c5ed5c9aa7ad3da9b8e2aa1d9dcb6b2e0ba1b2aa8f24fba5ba82d7af3", 10000], "id":1 }' -H 'content-type: application/json' http://127.0.0.1:8332/
+    #[rpc(name = "prioritisetransaction")]
+    fn prioritise_transaction(&self, H256, i64) -> Result<bool, Error>;
+    /// Mark block as invalid. If block is in the best chain, the best chain is switched to the longest
+    /// valid stored chain. The block and its descendants are rejected until `reconsiderblock` is called.
+    /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "method": "invalidateblock", "params": ["000000006a625f06636b8bb6ac7b960a8d03705d1ace08b1a19da3fdcc99ddbd"], "id":1 }' -H 'content-type: application/json' http://127.0.0.1:8332/
+    #[rpc(name = "invalidateblock")]
+    fn invalidate_block(&self, H256) -> Result<(), Error>;
+    /// Remove invalid mark from the block (and its descendants), previously set by `invalidateblock`.
+    /// Best chain is switched back to the reconsidered blocks if their chain is longer.
+    /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "method": "reconsiderblock", "params": ["000000006a625f06636b8bb6ac7b960a8d03705d1ace08b1a19da3fdcc99ddbd"], "id":1 }' -H 'content-type: application/json' http://127.0.0.1:8332/
+    #[rpc(name = "reconsiderblock")]
+    fn reconsider_block(&self, H256) -> Result<(), Error>;
+    /// Treat block as if it was received before other blocks with the same chain length. If block is
+    /// the tip of the side chain of the same length, the best chain is switched to this side chain.
+    /// The effect is local and is not persisted across restarts.
+    /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "method": "preciousblock", "params": ["000000006a625f06636b8bb6ac7b960a8d03705d1ace08b1a19da3fdcc99ddbd"], "id":1 }' -H 'content-type: application/json' http://127.0.0.1:8332/
+    #[rpc(name = "preciousblock")]
+    fn precious_block(&self, H256) -> Result<(), Error>;
 }
@@ -1,5 +1,8 @@
 use jsonrpc_core::Error;
-use v1::types::{AddNodeOperation, NodeInfo};
+use v1::types::{
+    AddNodeOperation, BannedSubnet, ConnectedPeerInfo, GetNetTotalsResponse,
+    GetNetworkInfoResponse, NodeInfo, SetBanOperation,
+};
 
 /// Parity-bitcoin network interface
 #[rpc]
@@ -10,6 +13,11 @@ pub trait Network {
     /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "method": "addnode", "params": ["127.0.0.1:8888", "onetry"], "id":1 }' -H 'content-type: application/json' http://127.0.0.1:8332/
     #[rpc(name = "addnode")]
     fn add_node(&self, String, AddNodeOperation) -> Result<(), Error>;
+    /// Close connection to the node with given address (ip:port) or id (as reported by getpeerinfo)
+    /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "method": "disconnectnode", "params": ["127.0.0.1:8888"], "id":1 }' -H 'content-type: application/json' http://127.0.0.1:8332/
+    /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "method": "disconnectnode", "params": ["", 1], "id":1 }' -H 'content-type: application/json' http://127.0.0.1:8332/
+    #[rpc(name = "disconnectnode")]
+    fn disconnect_node(&self, Option<String>, Option<usize>) -> Result<(), Error>;
     /// Query node(s) info
     /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "id":"1", "method": "getaddednodeinfo", "params": [true] }' -H 'content-type: application/json' http://127.0.0.1:8332/
     /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "id":"1", "method": "getaddednodeinfo", "params": [true, "192.168.0.201"] }' -H 'content-type: application/json' http://127.0.0.1:8332/
@@ -19,4 +27,42 @@ pub trait Network {
     /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "id":"1", "method": "getconnectioncount", "params": [] }' -H 'content-type: application/json' http://127.0.0.1:8332/
     #[rpc(name = "getconnectioncount")]
     fn connection_count(&self) -> Result<usize, Error>;
+    /// Get information on the node and its network state
+    /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "id":"1", "method": "getnetworkinfo", "params": [] }' -H 'content-type: application/json' http://127.0.0.1:8332/
+    #[rpc(name = "getnetworkinfo")]
+    fn network_info(&self) -> Result<GetNetworkInfoResponse, Error>;
+    /// Pause (false) or resume (true) all p2p networking. Returns the new state.
+    /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "id":"1", "method": "setnetworkactive", "params": [false] }' -H 'content-type: application/json' http://127.0.0.1:8332/
+    #[rpc(name = "setnetworkactive")]
+    fn set_network_active(&self, bool) -> Result<bool, Error>;
+    /// Query information on connected peers
+    /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "id":"1", "method": "getpeerinfo", "params": [] }' -H 'content-type: application/json' http://127.0.0.1:8332/
+    #[rpc(name = "getpeerinfo")]
+    fn peer_info(&self) -> Result<Vec<ConnectedPeerInfo>, Error>;
+    /// Send ping request to all connected peers. Returns immediately, without waiting for responses.
+    /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "id":"1", "method": "ping", "params": [] }' -H 'content-type: application/json' http://127.0.0.1:8332/
+    #[rpc(name = "ping")]
+    fn ping(&self) -> Result<(), Error>;
+    /// Get network traffic statistics
+    /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "id":"1", "method": "getnettotals", "params": [] }' -H 'content-type: application/json' http://127.0.0.1:8332/
+    #[rpc(name = "getnettotals")]
+    fn net_totals(&self) -> Result<GetNetTotalsResponse, Error>;
+    /// Add/remove subnet to/from the ban list. Optional ban time is in seconds.
+    /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "method": "setban", "params": ["192.168.0.6", "add", 86400], "id":1 }' -H 'content-type: application/json' http://127.0.0.1:8332/
+    /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "method": "setban", "params": ["192.168.0.0/24", "add"], "id":1 }' -H 'content-type: application/json' http://127.0.0.1:8332/
+    /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "method": "setban", "params": ["192.168.0.0/24", "remove"], "id":1 }' -H 'content-type: application/json' http://127.0.0.1:8332/
+    #[rpc(name = "setban")]
+    fn set_ban(&self, String, SetBanOperation, Option<u32>) -> Result<(), Error>;
+    /// List all banned subnets
+    /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "id":"1", "method": "listbanned", "params": [] }' -H 'content-type: application/json' http://127.0.0.1:8332/
+    #[rpc(name = "listbanned")]
+    fn list_banned(&self) -> Result<Vec<BannedSubnet>, Error>;
+    /// Clear the ban list
+    /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "id":"1", "method": "clearbanned", "params": [] }' -H 'content-type: application/json' http://127.0.0.1:8332/
+    #[rpc(name = "clearbanned")]
+    fn clear_banned(&self) -> Result<(), Error>;
+    /// Stop the node. Returns immediately, while the node closes connections and flushes the database.
+    /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "id":"1", "method": "stop", "params": [] }' -H 'content-type: application/json' http://127.0.0.1:8332/
+    #[rpc(name = "stop")]
+    fn stop(&self) -> Result<String, Error>;
 }
@@ -1,10 +1,16 @@
 use jsonrpc_core::Error;
 
+use v1::types::Bytes;
+use v1::types::DecodeScriptResponse;
 use v1::types::GetRawTransactionResponse;
+use v1::types::PreviousTransactionOutput;
 use v1::types::RawTransaction;
+use v1::types::SignRawTransactionResponse;
+use v1::types::TestMemPoolAcceptResponse;
 use v1::types::Transaction;
 use v1::types::TransactionInput;
 use v1::types::TransactionOutputs;
+use v1::types::ValidateAddressResponse;
 use v1::types::H256;
 
 /// Parity-bitcoin raw data interface.
@@ -14,6 +20,16 @@ pub trait Raw {
     /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "method": "sendrawtransaction", "params": ["01000000010000000000000000000000000000000000000000000000000000000000000000ffffffff4d04ffff001d0104455468652054696d65732030332f4a616e2f32303039204368616e63656c6c6f72206f6e206272696e6b206f66207365636f6e64206261696c6f757420666f722062616e6b73ffffffff0100f2052a01000000434104678afdb0fe5548271967f1a67130b7105cd6a828e03909a67962e0ea1f61deb649f6bc3f4cef38c4f35504e51ec112de5c384df7ba0b8d578a4c702b6bf11d5fac00000000"], "id":1 }' -H 'content-type: application/json' http://127.0.0.1:8332/
     #[rpc(name = "sendrawtransaction")]
     fn send_raw_transaction(&self, RawTransaction) -> Result<H256, Error>;
+    /// Check whether raw transactions would be accepted to the memory pool, without adding them.
+    /// Transactions are checked as a package, so later transactions could spend outputs of
+    /// earlier ones. Optional second parameter is the maximal fee rate in ZEC/kB.
+    /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "method": "testmempoolaccept", "params": [["01000000010000000000000000000000000000000000000000000000000000000000000000ffffffff4d04ffff001d0104455468652054696d65732030332f4a616e2f32303039204368616e63656c6c6f72206f6e206272696e6b206f66207365636f6e64206261696c6f757420666f722062616e6b73ffffffff0100f2052a01000000434104678afdb0fe5548271967f1a67130b7105cd6a828e03909a67962e0ea1f61deb649f6bc3f4cef38c4f35504e51ec112de5c384df7ba0b8d578a4c702b6bf11d5fac00000000"], 0.1], "id":1 }' -H 'content-type: application/json' http://127.0.0.1:8332/
+    #[rpc(name = "testmempoolaccept")]
+    fn test_mempool_accept(
+        &self,
+        Vec<RawTransaction>,
+        Option<f64>,
+    ) -> Result<Vec<TestMemPoolAcceptResponse>, Error>;
     /// Create a transaction spending the given inputs and creating new outputs.
     /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "method": "createrawtransaction", "params": [[{"txid":"4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b","vout":0}],{"t1h8SqgtM3QM5e2M8EzhhT1yL2PXXtA6oqe":0.01}], "id":1 }' -H 'content-type: application/json' http://127.0.0.1:8332/
     #[rpc(name = "createrawtransaction")]
@@ -24,12 +40,44 @@ pub trait Raw {
         Option<u32>,
         Option<u32>,
     ) -> Result<RawTransaction, Error>;
+    /// Sign inputs of the raw transaction, using given private keys.
+    /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "method": "signrawtransaction", "params": ["00000000013ba3edfd7a7b12b27ac72c3e67768f617fc81bc3888a51323a9fb8aa4b1e5e4a0000000000000000000101000000000000000000000000", [{"txid":"4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b","vout":0,"scriptPubKey":"76a914e6d1b5e5f5cd8f4e3bf1e3c8ab2d37ac6a8d7c2c88ac","amount":0.02}], ["5HxWvvfubhXpYYpS3tJkw6fq9jE9j18THftkZjHHfmFiWtmAbrj"], "ALL"], "id":1 }' -H 'content-type: application/json' http://127.0.0.1:8332/
+    #[rpc(name = "signrawtransaction")]
+    fn sign_raw_transaction(
+        &self,
+        RawTransaction,
+        Option<Vec<PreviousTransactionOutput>>,
+        Option<Vec<String>>,
+        Option<String>,
+    ) -> Result<SignRawTransactionResponse, Error>;
     /// Return an object representing the serialized, hex-encoded transaction.
     /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "method": "decoderawtransaction", "params": ["01000000010000000000000000000000000000000000000000000000000000000000000000ffffffff4d04ffff001d0104455468652054696d65732030332f4a616e2f32303039204368616e63656c6c6f72206f6e206272696e6b206f66207365636f6e64206261696c6f757420666f722062616e6b73ffffffff0100f2052a01000000434104678afdb0fe5548271967f1a67130b7105cd6a828e03909a67962e0ea1f61deb649f6bc3f4cef38c4f35504e51ec112de5c384df7ba0b8d578a4c702b6bf11d5fac00000000"], "id":1 }' -H 'content-type: application/json' http://127.0.0.1:8332/
     #[rpc(name = "decoderawtransaction")]
     fn decode_raw_transaction(&self, RawTransaction) -> Result<Transaction, Error>;
-    /// Return the raw transaction data.
-    /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "method": "getrawtransaction", "params": ["4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b"], "id":1 }' -H 'content-type: application/json' http://127.0.0.1:8332/
+    /// Decode a hex-encoded script.
+    /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "method": "decodescript", "params": ["76a914e6d1b5e5f5cd8f4e3bf1e3c8ab2d37ac6a8d7c2c88ac"], "id":1 }' -H 'content-type: application/json' http://127.0.0.1:8332/
+    #[rpc(name = "decodescript")]
+    fn decode_script(&self, Bytes) -> Result<DecodeScriptResponse, Error>;
+    /// Return the raw transaction data. Unless --txindex is enabled, only memory pool transactions
+    /// and transactions with unspent outputs are found.
+    /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "method": "getrawtransaction", "params": ["c4eaa58879081de3c24a7b117ed2b28300e7ec4c4c1dff1d3f1268b7857a4ddb", true], "id":1 }' -H 'content-type: application/json' http://127.0.0.1:8332/
     #[rpc(name = "getrawtransaction")]
     fn get_raw_transaction(&self, H256, Option<bool>) -> Result<GetRawTransactionResponse, Error>;
+    /// Return information about the given transparent, Sprout or Sapling address.
+    /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "method": "validateaddress", "params": ["t1h8SqgtM3QM5e2M8EzhhT1yL2PXXtA6oqe"], "id":1 }' -H 'content-type: application/json' http://127.0.0.1:8332/
+    #[rpc(name = "validateaddress")]
+    fn validate_address(&self, String) -> Result<ValidateAddressResponse, Error>;
+    /// Return information about the given transparent, Sprout or Sapling address. Same as `validateaddress`.
+    /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "method": "z_validateaddress", "params": ["zs1qyqqqqqqqqqqqqqqqqpqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqk48wef"], "id":1 }' -H 'content-type: application/json' http://127.0.0.1:8332/
+    #[rpc(name = "z_validateaddress")]
+    fn z_validate_address(&self, String) -> Result<ValidateAddressResponse, Error>;
+    /// Sign a message with the private key of the transparent P2PKH address. The key must be
+    /// present in the given private keys list. Returns base64-encoded compact signature.
+    /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "method": "signmessage", "params": ["t1h8SqgtM3QM5e2M8EzhhT1yL2PXXtA6oqe", "Hello, Zcash!", ["5HxWvvfubhXpYYpS3tJkw6fq9jE9j18THftkZjHHfmFiWtmAbrj"]], "id":1 }' -H 'content-type: application/json' http://127.0.0.1:8332/
+    #[rpc(name = "signmessage")]
+    fn sign_message(&self, String, String, Option<Vec<String>>) -> Result<String, Error>;
+    /// Verify a message, signed with the private key of the transparent P2PKH address.
+    /// @curl-example: curl --data-binary '{"jsonrpc": "2.0", "method": "verifymessage", "params": ["t1h8SqgtM3QM5e2M8EzhhT1yL2PXXtA6oqe", "HFdX97m+gbpJJ9UuVVrWZRrvJopA/AXawlmEHLyJ5PgMHBSrPN8kAwmHNhZbxmIIvftDcbi9epgNd+ev61psHVw=", "Hello, Zcash!"], "id":1 }' -H 'content-type: application/json' http://127.0.0.1:8332/
+    #[rpc(name = "verifymessage")]
+    fn verify_message(&self, String, String, String) -> Result<bool, Error>;
 }
@@ -0,0 +1,42 @@
+use super::bytes::Bytes;
+use super::hash::H256;
+
+/// Unspent transaction output, paying to the transparent address
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct AddressUtxo {
+    /// Address the output is paying to
+    pub address: String,
+    /// Transaction hash
+    pub txid: H256,
+    /// Output index
+    #[serde(rename = "outputIndex")]
+    pub output_index: u32,
+    /// Output script
+    pub script: Bytes,
+    /// Output value (in satoshis)
+    pub satoshis: u64,
+    /// Height of the block, containing the transaction
+    pub height: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn address_utxo_serialize() {
+        let utxo = AddressUtxo {
+            address: "t1h8SqgtM3QM5e2M8EzhhT1yL2PXXtA6oqe".into(),
+            txid: H256::from(1),
+            output_index: 2,
+            script: Bytes::new(vec![0x51]),
+            satoshis: 1000,
+            height: 3,
+        };
+        assert_eq!(
+            serde_json::to_string(&utxo).unwrap(),
+            r#"{"address":"t1h8SqgtM3QM5e2M8EzhhT1yL2PXXtA6oqe","txid":"0100000000000000000000000000000000000000000000000000000000000000","outputIndex":2,"script":"51","satoshis":1000,"height":3}"#
+        );
+    }
+}
@@ -21,6 +21,8 @@ pub struct BlockTemplate {
     pub target: H256,
     /// The minimum timestamp appropriate for next block time in seconds since epoch (Jan 1 1970 GMT)
     pub mintime: Option<i64>,
+    /// The maximum timestamp appropriate for next block time in seconds since epoch (Jan 1 1970 GMT)
+    pub maxtime: Option<i64>,
     /// List of ways the block template may be changed, e.g. 'time', 'transactions', 'prevblock'
     pub mutable: Option<Vec<String>>,
     /// A range of valid nonces (constant 00000000ffffffff)
@@ -35,6 +37,8 @@ pub struct BlockTemplate {
     pub bits: u32,
     /// The height of the next block
     pub height: u32,
+    /// Id to be used in the long poll request
+    pub longpollid: Option<String>,
 }
 
 /// Transaction data as included in `BlockTemplate`
@@ -63,6 +67,8 @@ impl From<zebra_miner::BlockTemplate> for BlockTemplate {
             version: block.version,
             previousblockhash: block.previous_header_hash.reversed().into(),
             curtime: block.time,
+            mintime: Some(block.min_time as i64),
+            maxtime: Some(block.max_time as i64),
             bits: block.bits.into(),
             height: block.height,
             transactions: block.transactions.into_iter().map(Into::into).collect(),
@@ -147,6 +153,7 @@ mod tests {
 			coinbasetxn: None,
 			target: H256::default(),
 			mintime: None,
+			maxtime: None,
 			mutable: None,
 			noncerange: None,
 			sigoplimit: None,
@@ -154,7 +161,8 @@ mod tests {
 			curtime: 100,
 			bits: 200,
 			height: 300,
-		}).unwrap(), r#"{"version":0,"previousblockhash":"0000000000000000000000000000000000000000000000000000000000000000","finalsaplingroothash":"0000000000000000000000000000000000000000000000000000000000000000","transactions":[],"coinbasetxn":null,"target":"0000000000000000000000000000000000000000000000000000000000000000","mintime":null,"mutable":null,"noncerange":null,"sigoplimit":null,"sizelimit":null,"curtime":100,"bits":200,"height":300}"#);
+			longpollid: None,
+		}).unwrap(), r#"{"version":0,"previousblockhash":"0000000000000000000000000000000000000000000000000000000000000000","finalsaplingroothash":"0000000000000000000000000000000000000000000000000000000000000000","transactions":[],"coinbasetxn":null,"target":"0000000000000000000000000000000000000000000000000000000000000000","mintime":null,"maxtime":null,"mutable":null,"noncerange":null,"sigoplimit":null,"sizelimit":null,"curtime":100,"bits":200,"height":300,"longpollid":null}"#);
         assert_eq!(serde_json::to_string(&BlockTemplate {
 			version: 0,
 			previousblockhash: H256::from(10),
@@ -177,6 +185,7 @@ mod tests {
 			}),
 			target: H256::from(100),
 			mintime: Some(7),
+			maxtime: Some(8),
 			mutable: Some(vec!["afg".to_owned()]),
 			noncerange: Some("00000000ffffffff".to_owned()),
 			sigoplimit: Some(45),
@@ -184,13 +193,14 @@ mod tests {
 			curtime: 100,
 			bits: 200,
 			height: 300,
-		}).unwrap(), r#"{"version":0,"previousblockhash":"0a00000000000000000000000000000000000000000000000000000000000000","finalsaplingroothash":"0b00000000000000000000000000000000000000000000000000000000000000","transactions":[{"data":"00010203","hash":null,"depends":null,"fee":null,"sigops":null,"required":false}],"coinbasetxn":{"data":"555555","hash":"3700000000000000000000000000000000000000000000000000000000000000","depends":[1],"fee":300,"sigops":400,"required":true},"target":"6400000000000000000000000000000000000000000000000000000000000000","mintime":7,"mutable":["afg"],"noncerange":"00000000ffffffff","sigoplimit":45,"sizelimit":449,"curtime":100,"bits":200,"height":300}"#);
+			longpollid: Some("0a0000000000000000000000000000000000000000000000000000000000000010".to_owned()),
+		}).unwrap(), r#"{"version":0,"previousblockhash":"0a00000000000000000000000000000000000000000000000000000000000000","finalsaplingroothash":"0b00000000000000000000000000000000000000000000000000000000000000","transactions":[{"data":"00010203","hash":null,"depends":null,"fee":null,"sigops":null,"required":false}],"coinbasetxn":{"data":"555555","hash":"3700000000000000000000000000000000000000000000000000000000000000","depends":[1],"fee":300,"sigops":400,"required":true},"target":"6400000000000000000000000000000000000000000000000000000000000000","mintime":7,"maxtime":8,"mutable":["afg"],"noncerange":"00000000ffffffff","sigoplimit":45,"sizelimit":449,"curtime":100,"bits":200,"height":300,"longpollid":"0a0000000000000000000000000000000000000000000000000000000000000010"}"#);
     }
 
     #[test]
     fn block_template_deserialize() {
         assert_eq!(
-			serde_json::from_str::<BlockTemplate>(r#"{"version":0,"previousblockhash":"0000000000000000000000000000000000000000000000000000000000000000","finalsaplingroothash":"0000000000000000000000000000000000000000000000000000000000000000","transactions":[],"coinbasetxn":null,"target":"0000000000000000000000000000000000000000000000000000000000000000","mintime":null,"mutable":null,"noncerange":null,"sigoplimit":null,"sizelimit":null,"curtime":100,"bits":200,"height":300}"#).unwrap(),
+			serde_json::from_str::<BlockTemplate>(r#"{"version":0,"previousblockhash":"0000000000000000000000000000000000000000000000000000000000000000","finalsaplingroothash":"0000000000000000000000000000000000000000000000000000000000000000","transactions":[],"coinbasetxn":null,"target":"0000000000000000000000000000000000000000000000000000000000000000","mintime":null,"maxtime":null,"mutable":null,"noncerange":null,"sigoplimit":null,"sizelimit":null,"curtime":100,"bits":200,"height":300,"longpollid":null}"#).unwrap(),
 			BlockTemplate {
 				version: 0,
 				previousblockhash: H256::default(),
@@ -199,6 +209,7 @@ mod tests {
 				coinbasetxn: None,
 				target: H256::default(),
 				mintime: None,
+				maxtime: None,
 				mutable: None,
 				noncerange: None,
 				sigoplimit: None,
@@ -206,9 +217,10 @@ mod tests {
 				curtime: 100,
 				bits: 200,
 				height: 300,
+				longpollid: None,
 			});
         assert_eq!(
-			serde_json::from_str::<BlockTemplate>(r#"{"version":0,"previousblockhash":"0a00000000000000000000000000000000000000000000000000000000000000","finalsaplingroothash":"0b00000000000000000000000000000000000000000000000000000000000000","transactions":[{"data":"00010203","hash":null,"depends":null,"fee":null,"sigops":null,"required":false}],"coinbasetxn":{"data":"555555","hash":"3700000000000000000000000000000000000000000000000000000000000000","depends":[1],"fee":300,"sigops":400,"required":true},"target":"6400000000000000000000000000000000000000000000000000000000000000","mintime":7,"mutable":["afg"],"noncerange":"00000000ffffffff","sigoplimit":45,"sizelimit":449,"curtime":100,"bits":200,"height":300}"#).unwrap(),
+			serde_json::from_str::<BlockTemplate>(r#"{"version":0,"previousblockhash":"0a00000000000000000000000000000000000000000000000000000000000000","finalsaplingroothash":"0b00000000000000000000000000000000000000000000000000000000000000","transactions":[{"data":"00010203","hash":null,"depends":null,"fee":null,"sigops":null,"required":false}],"coinbasetxn":{"data":"555555","hash":"3700000000000000000000000000000000000000000000000000000000000000","depends":[1],"fee":300,"sigops":400,"required":true},"target":"6400000000000000000000000000000000000000000000000000000000000000","mintime":7,"maxtime":8,"mutable":["afg"],"noncerange":"00000000ffffffff","sigoplimit":45,"sizelimit":449,"curtime":100,"bits":200,"height":300,"longpollid":"0a0000000000000000000000000000000000000000000000000000000000000010"}"#).unwrap(),
 			BlockTemplate {
 				version: 0,
 				previousblockhash: H256::from(10),
@@ -231,6 +243,7 @@ mod tests {
 				}),
 				target: H256::from(100),
 				mintime: Some(7),
+				maxtime: Some(8),
 				mutable: Some(vec!["afg".to_owned()]),
 				noncerange: Some("00000000ffffffff".to_owned()),
 				sigoplimit: Some(45),
@@ -238,6 +251,7 @@ mod tests {
 				curtime: 100,
 				bits: 200,
 				height: 300,
+				longpollid: Some("0a0000000000000000000000000000000000000000000000000000000000000010".to_owned()),
 			});
     }
 }
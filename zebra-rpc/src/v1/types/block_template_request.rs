@@ -24,6 +24,9 @@ pub struct BlockTemplateRequest {
     pub mode: Option<BlockTemplateRequestMode>,
     /// Capabilities, supported by client
     pub capabilities: Option<HashSet<String>>,
+    /// Long poll id of the previous template. If it is still valid, the request
+    /// is blocked until the best block is changed (or timeout elapses)
+    pub longpollid: Option<String>,
 }
 
 #[cfg(test)]
@@ -59,15 +62,16 @@ mod tests {
     fn block_template_request_serialize() {
         assert_eq!(
             serde_json::to_string(&BlockTemplateRequest::default()).unwrap(),
-            r#"{"mode":null,"capabilities":null}"#
+            r#"{"mode":null,"capabilities":null,"longpollid":null}"#
         );
         assert_eq!(
             serde_json::to_string(&BlockTemplateRequest {
                 mode: Some(BlockTemplateRequestMode::Template),
                 capabilities: Some(vec!["a".to_owned()].into_iter().collect()),
+                longpollid: Some("b".to_owned()),
             })
             .unwrap(),
-            r#"{"mode":"template","capabilities":["a"]}"#
+            r#"{"mode":"template","capabilities":["a"],"longpollid":"b"}"#
         );
     }
 
@@ -79,16 +83,18 @@ mod tests {
             BlockTemplateRequest {
                 mode: None,
                 capabilities: None,
+                longpollid: None,
             }
         );
         assert_eq!(
             serde_json::from_str::<BlockTemplateRequest>(
-                r#"{"mode":"template","capabilities":["a"]}"#
+                r#"{"mode":"template","capabilities":["a"],"longpollid":"b"}"#
             )
             .unwrap(),
             BlockTemplateRequest {
                 mode: Some(BlockTemplateRequestMode::Template),
                 capabilities: Some(vec!["a".to_owned()].into_iter().collect()),
+                longpollid: Some("b".to_owned()),
             }
         );
     }
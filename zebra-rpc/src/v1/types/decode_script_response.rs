@@ -0,0 +1,37 @@
+use super::transaction::TransactionOutputScript;
+
+/// Response to decodescript RPC request.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct DecodeScriptResponse {
+    /// Decoded script
+    #[serde(flatten)]
+    pub script: TransactionOutputScript,
+    /// P2SH address of the script (not reported if the script is P2SH itself)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub p2sh: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+    use v1::types::ScriptType;
+
+    #[test]
+    fn decode_script_response_serialize() {
+        let response = DecodeScriptResponse {
+            script: TransactionOutputScript {
+                asm: "OP_1\n".into(),
+                hex: "51".into(),
+                req_sigs: 0,
+                script_type: ScriptType::NonStandard,
+                addresses: vec![],
+            },
+            p2sh: Some("t3Vz22vK5z2LcKEdg16Yv4FFneEL1zg9ojd".into()),
+        };
+        assert_eq!(
+            serde_json::to_string(&response).unwrap(),
+            r#"{"asm":"OP_1\n","hex":"51","reqSigs":0,"type":"nonstandard","addresses":[],"p2sh":"t3Vz22vK5z2LcKEdg16Yv4FFneEL1zg9ojd"}"#
+        );
+    }
+}
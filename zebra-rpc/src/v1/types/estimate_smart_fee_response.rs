@@ -0,0 +1,35 @@
+/// Response to estimatesmartfee RPC request
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct EstimateSmartFeeResponse {
+    /// Estimated fee rate in ZEC/kB. None if there's not enough data to make an estimation
+    pub feerate: Option<f64>,
+    /// Block number where estimate was found
+    pub blocks: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn estimate_smart_fee_response_serialize() {
+        let response = EstimateSmartFeeResponse {
+            feerate: Some(0.0001),
+            blocks: 2,
+        };
+        assert_eq!(
+            serde_json::to_string(&response).unwrap(),
+            r#"{"feerate":0.0001,"blocks":2}"#
+        );
+
+        let response = EstimateSmartFeeResponse {
+            feerate: None,
+            blocks: 25,
+        };
+        assert_eq!(
+            serde_json::to_string(&response).unwrap(),
+            r#"{"feerate":null,"blocks":25}"#
+        );
+    }
+}
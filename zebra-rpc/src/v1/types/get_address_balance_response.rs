@@ -0,0 +1,26 @@
+/// Response to getaddressbalance RPC request.
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct GetAddressBalanceResponse {
+    /// Current balance (in satoshis)
+    pub balance: u64,
+    /// Total value ever received by the addresses (in satoshis)
+    pub received: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn address_balance_serialize() {
+        let balance = GetAddressBalanceResponse {
+            balance: 1000,
+            received: 3000,
+        };
+        assert_eq!(
+            serde_json::to_string(&balance).unwrap(),
+            r#"{"balance":1000,"received":3000}"#
+        );
+    }
+}
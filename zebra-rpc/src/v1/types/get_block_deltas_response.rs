@@ -0,0 +1,100 @@
+use super::bytes::Bytes;
+use super::hash::H256;
+
+/// Response to getblockdeltas RPC request.
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct GetBlockDeltasResponse {
+    /// Block hash
+    pub hash: H256,
+    /// Block height
+    pub height: u32,
+    /// Number of confirmations
+    pub confirmations: u32,
+    /// Block time
+    pub time: u32,
+    /// Transparent balance changes of every block transaction
+    pub deltas: Vec<TransactionDeltas>,
+}
+
+/// Transparent balance changes of single transaction.
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct TransactionDeltas {
+    /// Transaction hash
+    pub txid: H256,
+    /// Index of the transaction in the block
+    pub index: u32,
+    /// Transaction inputs
+    pub inputs: Vec<InputDelta>,
+    /// Transaction outputs
+    pub outputs: Vec<OutputDelta>,
+}
+
+/// Transaction input along with the output it spends.
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct InputDelta {
+    /// Address of the spent output (missing for coinbase input and non-standard scripts)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    /// Negated value of the spent output (in satoshis)
+    pub satoshis: i64,
+    /// Input index
+    pub index: u32,
+    /// Hash of the transaction of the spent output
+    pub prevtxid: H256,
+    /// Index of the spent output
+    pub prevout: u32,
+    /// True if this is the input of coinbase transaction, which isn't spending anything
+    pub coinbase: bool,
+}
+
+/// Transaction output.
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct OutputDelta {
+    /// Address the output is paying to (missing for non-standard scripts)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    /// Output value (in satoshis)
+    pub satoshis: i64,
+    /// Output index
+    pub index: u32,
+    /// Output script
+    pub script: Bytes,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn block_deltas_serialize() {
+        let deltas = GetBlockDeltasResponse {
+            hash: H256::from(1),
+            height: 2,
+            confirmations: 3,
+            time: 4,
+            deltas: vec![TransactionDeltas {
+                txid: H256::from(5),
+                index: 1,
+                inputs: vec![InputDelta {
+                    address: Some("t1h8SqgtM3QM5e2M8EzhhT1yL2PXXtA6oqe".into()),
+                    satoshis: -1000,
+                    index: 0,
+                    prevtxid: H256::from(6),
+                    prevout: 7,
+                    coinbase: false,
+                }],
+                outputs: vec![OutputDelta {
+                    address: None,
+                    satoshis: 900,
+                    index: 0,
+                    script: Bytes::new(vec![0x51]),
+                }],
+            }],
+        };
+        assert_eq!(
+            serde_json::to_string(&deltas).unwrap(),
+            r#"{"hash":"0100000000000000000000000000000000000000000000000000000000000000","height":2,"confirmations":3,"time":4,"deltas":[{"txid":"0500000000000000000000000000000000000000000000000000000000000000","index":1,"inputs":[{"address":"t1h8SqgtM3QM5e2M8EzhhT1yL2PXXtA6oqe","satoshis":-1000,"index":0,"prevtxid":"0600000000000000000000000000000000000000000000000000000000000000","prevout":7,"coinbase":false}],"outputs":[{"satoshis":900,"index":0,"script":"51"}]}]}"#
+        );
+    }
+}
@@ -1,5 +1,6 @@
 use super::block::RawBlock;
 use super::hash::H256;
+use super::transaction::Transaction;
 use serde::{Serialize, Serializer};
 
 /// Response to getblock RPC request
@@ -9,11 +10,13 @@ pub enum GetBlockResponse {
     Raw(RawBlock),
     /// When asking for verbose response
     Verbose(VerboseBlock),
+    /// When asking for verbose response with decoded transactions
+    VerboseWithTransactions(VerboseBlock<Transaction>),
 }
 
 /// Verbose block information
 #[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
-pub struct VerboseBlock {
+pub struct VerboseBlock<T = H256> {
     /// Block hash
     pub hash: H256,
     /// Number of confirmations. -1 if block is on the side chain
@@ -29,8 +32,8 @@ pub struct VerboseBlock {
     pub merkleroot: H256,
     /// The root of the Sapling commitment tree after applying this block.
     pub finalsaplingroot: H256,
-    /// Transactions ids
-    pub tx: Vec<H256>,
+    /// Transactions ids (or decoded transactions)
+    pub tx: Vec<T>,
     /// Block time in seconds since epoch (Jan 1 1970 GMT)
     pub time: u32,
     /// Block nonce
@@ -53,6 +56,9 @@ impl Serialize for GetBlockResponse {
         match *self {
             GetBlockResponse::Raw(ref raw_block) => raw_block.serialize(serializer),
             GetBlockResponse::Verbose(ref verbose_block) => verbose_block.serialize(serializer),
+            GetBlockResponse::VerboseWithTransactions(ref verbose_block) => {
+                verbose_block.serialize(serializer)
+            }
         }
     }
 }
@@ -66,7 +72,7 @@ mod tests {
 
     #[test]
     fn verbose_block_serialize() {
-        let block = VerboseBlock::default();
+        let block: VerboseBlock = VerboseBlock::default();
         assert_eq!(serde_json::to_string(&block).unwrap(), r#"{"hash":"0000000000000000000000000000000000000000000000000000000000000000","confirmations":0,"size":0,"height":null,"version":0,"merkleroot":"0000000000000000000000000000000000000000000000000000000000000000","finalsaplingroot":"0000000000000000000000000000000000000000000000000000000000000000","tx":[],"time":0,"nonce":"0000000000000000000000000000000000000000000000000000000000000000","bits":0,"difficulty":0.0,"previousblockhash":null,"nextblockhash":null}"#);
 
         let block = VerboseBlock {
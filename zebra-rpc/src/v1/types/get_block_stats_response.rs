@@ -0,0 +1,66 @@
+/// Response to getblockstats RPC request.
+/// Only requested statistics are included into the response.
+/// Coinbase transaction is excluded from all statistics, except for `txs`, `outs` and `total_sigops`.
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct GetBlockStatsResponse {
+    /// Total size of all transactions
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_size: Option<u64>,
+    /// Number of transactions (including coinbase)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub txs: Option<u64>,
+    /// Total value of all transparent outputs (in satoshis)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_out: Option<u64>,
+    /// Total fee paid by transactions (in satoshis)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub totalfee: Option<u64>,
+    /// Average transaction fee (in satoshis)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avgfee: Option<u64>,
+    /// Minimal transaction fee (in satoshis)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minfee: Option<u64>,
+    /// Maximal transaction fee (in satoshis)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maxfee: Option<u64>,
+    /// Average fee rate (in satoshis per byte)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avgfeerate: Option<u64>,
+    /// Number of transparent inputs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ins: Option<u64>,
+    /// Number of transparent outputs (including coinbase outputs)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub outs: Option<u64>,
+    /// Block subsidy (in satoshis)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subsidy: Option<u64>,
+    /// Total number of signature operations (including coinbase)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_sigops: Option<u64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn block_stats_serialize() {
+        let stats = GetBlockStatsResponse {
+            txs: Some(2),
+            totalfee: Some(1000),
+            subsidy: Some(1_250_000_000),
+            ..Default::default()
+        };
+        assert_eq!(
+            serde_json::to_string(&stats).unwrap(),
+            r#"{"txs":2,"totalfee":1000,"subsidy":1250000000}"#
+        );
+        assert_eq!(
+            serde_json::to_string(&GetBlockStatsResponse::default()).unwrap(),
+            r#"{}"#
+        );
+    }
+}
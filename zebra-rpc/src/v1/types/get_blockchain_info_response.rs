@@ -0,0 +1,96 @@
+use super::hash::H256;
+use std::collections::BTreeMap;
+
+/// Response to getblockchaininfo RPC request.
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct GetBlockChainInfoResponse {
+    /// Name of the network (main, test, regtest)
+    pub chain: String,
+    /// Height of the best block
+    pub blocks: u32,
+    /// Height of the best known header
+    pub headers: u32,
+    /// Hash of the best block
+    pub bestblockhash: H256,
+    /// Proof-of-work difficulty of the best block as a multiple of the genesis block difficulty
+    pub difficulty: f64,
+    /// Median time of the best block
+    pub mediantime: u32,
+    /// Estimated verification progress [0..1], based on the best block timestamp
+    pub verificationprogress: f64,
+    /// True if transactions of some blocks have been pruned from the database
+    pub pruned: bool,
+    /// Height of the lowest block with all transactions stored (only returned if `pruned` is true)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pruneheight: Option<u32>,
+    /// Approximate size of the database
+    pub size_on_disk: u64,
+    /// Network upgrades, keyed by their consensus branch id
+    pub upgrades: BTreeMap<String, NetworkUpgradeInfo>,
+}
+
+/// Activation information of single network upgrade.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct NetworkUpgradeInfo {
+    /// Name of the upgrade
+    pub name: String,
+    /// Height at which the upgrade activates
+    pub activationheight: u32,
+    /// Status of the upgrade at the best block
+    pub status: NetworkUpgradeStatus,
+}
+
+/// Network upgrade status.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+pub enum NetworkUpgradeStatus {
+    /// Upgrade is active at the best block
+    #[serde(rename = "active")]
+    Active,
+    /// Upgrade is not yet active at the best block
+    #[serde(rename = "pending")]
+    Pending,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn blockchain_info_serialize() {
+        let mut upgrades = BTreeMap::new();
+        upgrades.insert(
+            "5ba81b19".to_owned(),
+            NetworkUpgradeInfo {
+                name: "Overwinter".to_owned(),
+                activationheight: 347500,
+                status: NetworkUpgradeStatus::Pending,
+            },
+        );
+        let mut info = GetBlockChainInfoResponse {
+            chain: "main".to_owned(),
+            blocks: 1,
+            headers: 1,
+            bestblockhash: H256::from(1),
+            difficulty: 1.0,
+            mediantime: 1000,
+            verificationprogress: 0.5,
+            pruned: false,
+            pruneheight: None,
+            size_on_disk: 2048,
+            upgrades: upgrades,
+        };
+        assert_eq!(
+            serde_json::to_string(&info).unwrap(),
+            r#"{"chain":"main","blocks":1,"headers":1,"bestblockhash":"0100000000000000000000000000000000000000000000000000000000000000","difficulty":1.0,"mediantime":1000,"verificationprogress":0.5,"pruned":false,"size_on_disk":2048,"upgrades":{"5ba81b19":{"name":"Overwinter","activationheight":347500,"status":"pending"}}}"#
+        );
+
+        info.pruned = true;
+        info.pruneheight = Some(1);
+        info.upgrades.clear();
+        assert_eq!(
+            serde_json::to_string(&info).unwrap(),
+            r#"{"chain":"main","blocks":1,"headers":1,"bestblockhash":"0100000000000000000000000000000000000000000000000000000000000000","difficulty":1.0,"mediantime":1000,"verificationprogress":0.5,"pruned":true,"pruneheight":1,"size_on_disk":2048,"upgrades":{}}"#
+        );
+    }
+}
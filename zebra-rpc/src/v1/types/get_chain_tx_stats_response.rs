@@ -0,0 +1,58 @@
+use super::hash::H256;
+
+/// Response to getchaintxstats RPC request.
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct GetChainTxStatsResponse {
+    /// Timestamp of the final block of the window
+    pub time: u32,
+    /// Total number of transactions in the chain up to and including the final block of the window
+    pub txcount: u64,
+    /// Hash of the final block of the window
+    pub window_final_block_hash: H256,
+    /// Size of the window in number of blocks
+    pub window_block_count: u32,
+    /// Number of transactions in the window (only returned if `window_block_count` is > 0)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub window_tx_count: Option<u64>,
+    /// Difference between median times of the final and the first blocks of the window, in seconds
+    /// (only returned if `window_block_count` is > 0)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub window_interval: Option<u32>,
+    /// Average number of transactions per second in the window (only returned if `window_interval` is > 0)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub txrate: Option<f64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn chain_tx_stats_serialize() {
+        let response = GetChainTxStatsResponse {
+            time: 1000,
+            txcount: 15,
+            window_final_block_hash: H256::from(1),
+            window_block_count: 3,
+            window_tx_count: Some(12),
+            window_interval: Some(100),
+            txrate: Some(0.12),
+        };
+        assert_eq!(
+            serde_json::to_string(&response).unwrap(),
+            r#"{"time":1000,"txcount":15,"window_final_block_hash":"0100000000000000000000000000000000000000000000000000000000000000","window_block_count":3,"window_tx_count":12,"window_interval":100,"txrate":0.12}"#
+        );
+
+        let response = GetChainTxStatsResponse {
+            time: 1000,
+            txcount: 15,
+            window_final_block_hash: H256::from(1),
+            ..Default::default()
+        };
+        assert_eq!(
+            serde_json::to_string(&response).unwrap(),
+            r#"{"time":1000,"txcount":15,"window_final_block_hash":"0100000000000000000000000000000000000000000000000000000000000000","window_block_count":0}"#
+        );
+    }
+}
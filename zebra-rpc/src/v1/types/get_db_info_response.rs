@@ -0,0 +1,45 @@
+/// Response to getdbinfo RPC request
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct GetDbInfoResponse {
+    /// Statistics of database columns
+    pub columns: Vec<DbColumnInfo>,
+}
+
+/// Statistics of single database column
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct DbColumnInfo {
+    /// Column index
+    pub column: u32,
+    /// Approximate number of keys in the column
+    pub keys: u64,
+    /// Approximate size of keys and values in the column
+    pub bytes: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn db_info_serialize() {
+        let info = GetDbInfoResponse {
+            columns: vec![
+                DbColumnInfo {
+                    column: 0,
+                    keys: 3,
+                    bytes: 100,
+                },
+                DbColumnInfo {
+                    column: 1,
+                    keys: 0,
+                    bytes: 0,
+                },
+            ],
+        };
+        assert_eq!(
+            serde_json::to_string(&info).unwrap(),
+            r#"{"columns":[{"column":0,"keys":3,"bytes":100},{"column":1,"keys":0,"bytes":0}]}"#
+        );
+    }
+}
@@ -0,0 +1,33 @@
+/// Response to getmempoolinfo RPC request
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct GetMemPoolInfoResponse {
+    /// Current number of transactions in the memory pool
+    pub size: usize,
+    /// Total serialized size (in bytes) of all transactions in the memory pool
+    pub bytes: usize,
+    /// Total (estimated) in-memory size (in bytes) of the memory pool, including
+    /// transactions and all memory pool indexes
+    pub usage: usize,
+    /// Minimal fee rate (in ZEC/kB) for transaction to be accepted to the memory pool
+    pub mempoolminfee: f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn mempool_info_serialize() {
+        let info = GetMemPoolInfoResponse {
+            size: 2,
+            bytes: 500,
+            usage: 1024,
+            mempoolminfee: 0.0001,
+        };
+        assert_eq!(
+            serde_json::to_string(&info).unwrap(),
+            r#"{"size":2,"bytes":500,"usage":1024,"mempoolminfee":0.0001}"#
+        );
+    }
+}
@@ -0,0 +1,29 @@
+/// Response to getnettotals RPC request
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct GetNetTotalsResponse {
+    /// Total number of bytes, received from all peers
+    pub totalbytesrecv: u64,
+    /// Total number of bytes, sent to all peers
+    pub totalbytessent: u64,
+    /// Current time in milliseconds since epoch (Jan 1 1970 GMT)
+    pub timemillis: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn net_totals_serialize() {
+        let totals = GetNetTotalsResponse {
+            totalbytesrecv: 2048,
+            totalbytessent: 1024,
+            timemillis: 1540000000000,
+        };
+        assert_eq!(
+            serde_json::to_string(&totals).unwrap(),
+            r#"{"totalbytesrecv":2048,"totalbytessent":1024,"timemillis":1540000000000}"#
+        );
+    }
+}
@@ -0,0 +1,74 @@
+/// Response to getnetworkinfo RPC request
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct GetNetworkInfoResponse {
+    /// Node client version
+    pub version: u32,
+    /// Node user agent
+    pub subversion: String,
+    /// Protocol version, used by the node
+    pub protocolversion: u32,
+    /// Services offered by the node (hex-encoded)
+    pub localservices: String,
+    /// Number of connections (both inbound and outbound)
+    pub connections: usize,
+    /// Number of inbound connections
+    pub connections_in: u32,
+    /// Number of outbound connections
+    pub connections_out: u32,
+    /// Maximal number of inbound connections
+    pub maxconnections_in: u32,
+    /// Maximal number of outbound connections (excluding connections to manually added nodes)
+    pub maxconnections_out: u32,
+    /// False if networking is paused with setnetworkactive
+    pub networkactive: bool,
+    /// Information on networks, supported by the node
+    pub networks: Vec<NetworkInfo>,
+    /// Minimal fee rate (in ZEC/kB) for transactions to be relayed
+    pub relayfee: f64,
+    /// Active network warnings
+    pub warnings: String,
+}
+
+/// Information on network, supported by the node
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct NetworkInfo {
+    /// Network name: ipv4, ipv6 or onion
+    pub name: String,
+    /// True if connections to this network are disabled by the node configuration
+    pub limited: bool,
+    /// True if peers from this network could be reached
+    pub reachable: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn network_info_serialize() {
+        let info = GetNetworkInfoResponse {
+            version: 10000,
+            subversion: "zebra".into(),
+            protocolversion: 170007,
+            localservices: "0000000000000001".into(),
+            connections: 8,
+            connections_in: 3,
+            connections_out: 5,
+            maxconnections_in: 10,
+            maxconnections_out: 10,
+            networkactive: true,
+            networks: vec![NetworkInfo {
+                name: "onion".into(),
+                limited: true,
+                reachable: false,
+            }],
+            relayfee: 0.0001,
+            warnings: "".into(),
+        };
+        assert_eq!(
+            serde_json::to_string(&info).unwrap(),
+            r#"{"version":10000,"subversion":"zebra","protocolversion":170007,"localservices":"0000000000000001","connections":8,"connections_in":3,"connections_out":5,"maxconnections_in":10,"maxconnections_out":10,"networkactive":true,"networks":[{"name":"onion","limited":true,"reachable":false}],"relayfee":0.0001,"warnings":""}"#
+        );
+    }
+}
@@ -0,0 +1,72 @@
+use super::hash::H256;
+use serde::{Serialize, Serializer};
+use std::collections::BTreeMap;
+
+/// Response to getrawmempool RPC request
+#[derive(Debug, PartialEq)]
+pub enum GetRawMemPoolResponse {
+    /// When asking for short response
+    Hashes(Vec<H256>),
+    /// When asking for verbose response
+    Verbose(BTreeMap<H256, MemPoolEntry>),
+}
+
+/// Verbose memory pool transaction information
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct MemPoolEntry {
+    /// Transaction size in bytes
+    pub size: usize,
+    /// Transaction fee in ZEC
+    pub fee: f64,
+    /// Transaction fee with fee delta, used for mining priority, in ZEC
+    pub modifiedfee: f64,
+    /// Fee delta, set by prioritisetransaction, in ZEC
+    pub feedelta: f64,
+    /// Local time transaction entered pool in seconds since epoch (Jan 1 1970 GMT)
+    pub time: u32,
+    /// Block height when transaction entered pool
+    pub height: u32,
+    /// Unconfirmed transactions used as inputs for this transaction
+    pub depends: Vec<H256>,
+}
+
+impl Serialize for GetRawMemPoolResponse {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match *self {
+            GetRawMemPoolResponse::Hashes(ref hashes) => hashes.serialize(serializer),
+            GetRawMemPoolResponse::Verbose(ref entries) => entries.serialize(serializer),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::hash::H256;
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn raw_mempool_serialize() {
+        let response = GetRawMemPoolResponse::Hashes(vec![H256::from(1), H256::from(2)]);
+        assert_eq!(serde_json::to_string(&response).unwrap(), r#"["0100000000000000000000000000000000000000000000000000000000000000","0200000000000000000000000000000000000000000000000000000000000000"]"#);
+
+        let mut entries = BTreeMap::new();
+        entries.insert(
+            H256::from(2),
+            MemPoolEntry {
+                size: 100,
+                fee: 0.0001,
+                modifiedfee: 0.0002,
+                feedelta: 0.0001,
+                time: 33,
+                height: 44,
+                depends: vec![H256::from(1)],
+            },
+        );
+        let response = GetRawMemPoolResponse::Verbose(entries);
+        assert_eq!(serde_json::to_string(&response).unwrap(), r#"{"0200000000000000000000000000000000000000000000000000000000000000":{"size":100,"fee":0.0001,"modifiedfee":0.0002,"feedelta":0.0001,"time":33,"height":44,"depends":["0100000000000000000000000000000000000000000000000000000000000000"]}}"#);
+    }
+}
@@ -0,0 +1,50 @@
+use super::bytes::Bytes;
+use super::hash::H256;
+
+/// Commitment tree state of single shielded pool.
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct CommitmentTreeState {
+    /// Root of the commitment tree after applying the block
+    pub finalroot: H256,
+    /// Serialized commitment tree after applying the block
+    pub finalstate: Bytes,
+}
+
+/// Response to z_gettreestate RPC request.
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct GetTreeStateResponse {
+    /// Block hash
+    pub hash: H256,
+    /// Block height (None if block is not in the canonical chain)
+    pub height: Option<u32>,
+    /// Sprout commitment tree state
+    pub sprout: CommitmentTreeState,
+    /// Sapling commitment tree state
+    pub sapling: CommitmentTreeState,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn tree_state_serialize() {
+        let response = GetTreeStateResponse {
+            hash: H256::from(1),
+            height: Some(2),
+            sprout: CommitmentTreeState {
+                finalroot: H256::from(3),
+                finalstate: vec![0x00, 0x00, 0x00].into(),
+            },
+            sapling: CommitmentTreeState {
+                finalroot: H256::from(4),
+                finalstate: vec![0x01].into(),
+            },
+        };
+        assert_eq!(
+            serde_json::to_string(&response).unwrap(),
+            r#"{"hash":"0100000000000000000000000000000000000000000000000000000000000000","height":2,"sprout":{"finalroot":"0300000000000000000000000000000000000000000000000000000000000000","finalstate":"000000"},"sapling":{"finalroot":"0400000000000000000000000000000000000000000000000000000000000000","finalstate":"01"}}"#
+        );
+    }
+}
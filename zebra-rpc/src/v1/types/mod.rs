@@ -1,31 +1,76 @@
 pub mod address;
+mod address_utxo;
 mod block;
 mod block_template;
 mod block_template_request;
 mod bytes;
+mod decode_script_response;
+mod estimate_smart_fee_response;
+mod get_address_balance_response;
+mod get_block_deltas_response;
 mod get_block_response;
+mod get_block_stats_response;
+mod get_blockchain_info_response;
+mod get_chain_tx_stats_response;
+mod get_db_info_response;
+mod get_mempool_info_response;
+mod get_net_totals_response;
+mod get_network_info_response;
+mod get_raw_mempool_response;
+mod get_tree_state_response;
 mod get_tx_out_response;
 mod get_tx_out_set_info_response;
 mod hash;
 mod nodes;
+mod rescan_blockchain_response;
 mod script;
+mod sign_raw_transaction_response;
+mod test_mempool_accept_response;
 mod transaction;
 mod uint;
+mod validate_address_response;
 
+pub use self::address_utxo::AddressUtxo;
 pub use self::block::{BlockRef, RawBlock};
 pub use self::block_template::{BlockTemplate, BlockTemplateTransaction};
 pub use self::block_template_request::{BlockTemplateRequest, BlockTemplateRequestMode};
 pub use self::bytes::Bytes;
+pub use self::decode_script_response::DecodeScriptResponse;
+pub use self::estimate_smart_fee_response::EstimateSmartFeeResponse;
+pub use self::get_address_balance_response::GetAddressBalanceResponse;
+pub use self::get_block_deltas_response::{
+    GetBlockDeltasResponse, InputDelta, OutputDelta, TransactionDeltas,
+};
 pub use self::get_block_response::{GetBlockResponse, VerboseBlock};
+pub use self::get_block_stats_response::GetBlockStatsResponse;
+pub use self::get_blockchain_info_response::{
+    GetBlockChainInfoResponse, NetworkUpgradeInfo, NetworkUpgradeStatus,
+};
+pub use self::get_chain_tx_stats_response::GetChainTxStatsResponse;
+pub use self::get_db_info_response::{DbColumnInfo, GetDbInfoResponse};
+pub use self::get_mempool_info_response::GetMemPoolInfoResponse;
+pub use self::get_net_totals_response::GetNetTotalsResponse;
+pub use self::get_network_info_response::{GetNetworkInfoResponse, NetworkInfo};
+pub use self::get_raw_mempool_response::{GetRawMemPoolResponse, MemPoolEntry};
+pub use self::get_tree_state_response::{CommitmentTreeState, GetTreeStateResponse};
 pub use self::get_tx_out_response::GetTxOutResponse;
 pub use self::get_tx_out_set_info_response::GetTxOutSetInfoResponse;
 pub use self::hash::{H160, H256};
-pub use self::nodes::{AddNodeOperation, NodeInfo};
+pub use self::nodes::{
+    AddNodeOperation, BannedSubnet, ConnectedPeerInfo, NodeInfo, SetBanOperation,
+};
+pub use self::rescan_blockchain_response::RescanBlockChainResponse;
 pub use self::script::ScriptType;
+pub use self::sign_raw_transaction_response::{
+    SignRawTransactionError, SignRawTransactionResponse,
+};
+pub use self::test_mempool_accept_response::TestMemPoolAcceptResponse;
 pub use self::transaction::{
-    GetRawTransactionResponse, RawTransaction, SignedTransactionInput, SignedTransactionOutput,
-    Transaction, TransactionInput, TransactionInputScript, TransactionOutput,
-    TransactionOutputScript, TransactionOutputWithAddress, TransactionOutputWithScriptData,
-    TransactionOutputs,
+    GetRawTransactionResponse, PreviousTransactionOutput, RawTransaction, SignedTransactionInput,
+    SignedTransactionOutput, Transaction, TransactionInput, TransactionInputScript,
+    TransactionJoinSplit, TransactionOutput, TransactionOutputScript, TransactionOutputWithAddress,
+    TransactionOutputWithScriptData, TransactionOutputs, TransactionShieldedOutput,
+    TransactionShieldedSpend,
 };
 pub use self::uint::U256;
+pub use self::validate_address_response::ValidateAddressResponse;
@@ -1,7 +1,7 @@
 use serde::de::Unexpected;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt;
-use zebra_p2p::{Direction, PeerInfo};
+use zebra_p2p::{BanEntry, Direction, PeerInfo, PeerStatistics};
 
 #[derive(Debug, PartialEq)]
 pub enum AddNodeOperation {
@@ -43,6 +43,62 @@ impl<'a> Deserialize<'a> for AddNodeOperation {
     }
 }
 
+#[derive(Debug, PartialEq)]
+pub enum SetBanOperation {
+    Add,
+    Remove,
+}
+
+impl<'a> Deserialize<'a> for SetBanOperation {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'a>,
+    {
+        use serde::de::Visitor;
+
+        struct DummyVisitor;
+
+        impl<'b> Visitor<'b> for DummyVisitor {
+            type Value = SetBanOperation;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a ban operation string")
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<SetBanOperation, E>
+            where
+                E: ::serde::de::Error,
+            {
+                match value {
+                    "add" => Ok(SetBanOperation::Add),
+                    "remove" => Ok(SetBanOperation::Remove),
+                    _ => Err(E::invalid_value(Unexpected::Str(value), &self)),
+                }
+            }
+        }
+
+        deserializer.deserialize_identifier(DummyVisitor)
+    }
+}
+
+/// Banned subnet
+#[derive(Debug, Serialize, PartialEq)]
+pub struct BannedSubnet {
+    /// Banned subnet (ip or ip/prefix)
+    pub address: String,
+    /// Ban expiration time in seconds since epoch (Jan 1 1970 GMT)
+    pub banned_until: i64,
+}
+
+impl From<BanEntry> for BannedSubnet {
+    fn from(entry: BanEntry) -> Self {
+        BannedSubnet {
+            address: entry.subnet.to_string(),
+            banned_until: entry.banned_until,
+        }
+    }
+}
+
 #[derive(Serialize)]
 pub struct NodeInfoAddress {
     address: String,
@@ -84,3 +140,73 @@ impl Serialize for NodeInfoAddressConnectionType {
         }
     }
 }
+
+/// Information on connected peer
+#[derive(Debug, Serialize, PartialEq)]
+pub struct ConnectedPeerInfo {
+    /// Peer index
+    pub id: usize,
+    /// Peer address (ip:port)
+    pub addr: String,
+    /// True if connection is inbound
+    pub inbound: bool,
+    /// Peer protocol version
+    pub version: Option<u32>,
+    /// Peer user agent
+    pub subver: Option<String>,
+    /// Services offered by peer (hex-encoded)
+    pub services: Option<String>,
+    /// Height of the best peer block at the time of connection
+    pub startingheight: Option<i32>,
+    /// Peer ban score
+    pub banscore: Option<u32>,
+    /// Connection time in seconds since epoch (Jan 1 1970 GMT)
+    pub conntime: u32,
+    /// Total number of bytes sent to peer
+    pub bytessent: u64,
+    /// Total number of bytes received from peer
+    pub bytesrecv: u64,
+}
+
+impl From<PeerStatistics> for ConnectedPeerInfo {
+    fn from(statistics: PeerStatistics) -> Self {
+        let version_message = statistics.info.version_message;
+        ConnectedPeerInfo {
+            id: statistics.info.id,
+            addr: format!("{}", statistics.info.address),
+            inbound: statistics.info.direction == Direction::Inbound,
+            version: Some(statistics.info.version),
+            subver: version_message.user_agent(),
+            services: Some(format!("{:016x}", u64::from(version_message.services()))),
+            startingheight: version_message.start_height(),
+            banscore: None,
+            conntime: statistics.info.connection_time,
+            bytessent: statistics.bytes_sent,
+            bytesrecv: statistics.bytes_received,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn connected_peer_info_serialize() {
+        let info = ConnectedPeerInfo {
+            id: 1,
+            addr: "127.0.0.1:8233".into(),
+            inbound: true,
+            version: Some(170007),
+            subver: None,
+            services: Some("0000000000000001".into()),
+            startingheight: None,
+            banscore: None,
+            conntime: 100,
+            bytessent: 200,
+            bytesrecv: 300,
+        };
+        assert_eq!(serde_json::to_string(&info).unwrap(), r#"{"id":1,"addr":"127.0.0.1:8233","inbound":true,"version":170007,"subver":null,"services":"0000000000000001","startingheight":null,"banscore":null,"conntime":100,"bytessent":200,"bytesrecv":300}"#);
+    }
+}
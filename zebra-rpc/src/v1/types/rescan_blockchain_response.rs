@@ -0,0 +1,26 @@
+/// Response to rescanblockchain RPC request.
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct RescanBlockChainResponse {
+    /// Height of the first rescanned block
+    pub start_height: u32,
+    /// Height of the last rescanned block
+    pub stop_height: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn rescan_blockchain_serialize() {
+        let response = RescanBlockChainResponse {
+            start_height: 10,
+            stop_height: 20,
+        };
+        assert_eq!(
+            serde_json::to_string(&response).unwrap(),
+            r#"{"start_height":10,"stop_height":20}"#
+        );
+    }
+}
@@ -0,0 +1,63 @@
+use super::bytes::Bytes;
+use super::hash::H256;
+use super::transaction::RawTransaction;
+
+/// Input of transaction, which hasn't been signed by `signrawtransaction`
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct SignRawTransactionError {
+    /// Previous transaction id
+    pub txid: H256,
+    /// Previous transaction output index
+    pub vout: u32,
+    /// Input script
+    #[serde(rename = "scriptSig")]
+    pub script_sig: Bytes,
+    /// Sequence number
+    pub sequence: u32,
+    /// Verification or signing error
+    pub error: String,
+}
+
+/// Response to signrawtransaction RPC request
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct SignRawTransactionResponse {
+    /// Hex-encoded signed transaction
+    pub hex: RawTransaction,
+    /// True if all transaction inputs are signed
+    pub complete: bool,
+    /// Inputs that haven't been signed. Omitted when transaction is completely signed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub errors: Option<Vec<SignRawTransactionError>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn sign_raw_transaction_response_serialize() {
+        let response = SignRawTransactionResponse {
+            hex: Bytes::new(vec![0x01, 0x02]),
+            complete: true,
+            errors: None,
+        };
+        assert_eq!(
+            serde_json::to_string(&response).unwrap(),
+            r#"{"hex":"0102","complete":true}"#
+        );
+
+        let response = SignRawTransactionResponse {
+            hex: Bytes::new(vec![0x01, 0x02]),
+            complete: false,
+            errors: Some(vec![SignRawTransactionError {
+                txid: H256::from(7),
+                vout: 1,
+                script_sig: Bytes::new(vec![]),
+                sequence: 0xffffffff,
+                error: "error".into(),
+            }]),
+        };
+        assert_eq!(serde_json::to_string(&response).unwrap(), r#"{"hex":"0102","complete":false,"errors":[{"txid":"0700000000000000000000000000000000000000000000000000000000000000","vout":1,"scriptSig":"","sequence":4294967295,"error":"error"}]}"#);
+    }
+}
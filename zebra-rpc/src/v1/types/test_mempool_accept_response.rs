@@ -0,0 +1,52 @@
+use super::hash::H256;
+
+/// Response to testmempoolaccept RPC request (one entry for every tested transaction)
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct TestMemPoolAcceptResponse {
+    /// Transaction hash
+    pub txid: H256,
+    /// Would the transaction be accepted to the memory pool?
+    pub allowed: bool,
+    /// Reason of transaction rejection. Only present when transaction isn't allowed
+    #[serde(rename = "reject-reason", skip_serializing_if = "Option::is_none")]
+    pub reject_reason: Option<String>,
+    /// Transaction fee in ZEC. Only present when transaction is allowed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fees: Option<f64>,
+    /// Transaction size in bytes. Only present when transaction is allowed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vsize: Option<usize>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn test_mempool_accept_response_serialize() {
+        let response = TestMemPoolAcceptResponse {
+            txid: "0100000000000000000000000000000000000000000000000000000000000000".into(),
+            allowed: true,
+            reject_reason: None,
+            fees: Some(0.0001),
+            vsize: Some(250),
+        };
+        assert_eq!(
+            serde_json::to_string(&response).unwrap(),
+            r#"{"txid":"0100000000000000000000000000000000000000000000000000000000000000","allowed":true,"fees":0.0001,"vsize":250}"#
+        );
+
+        let response = TestMemPoolAcceptResponse {
+            txid: "0100000000000000000000000000000000000000000000000000000000000000".into(),
+            allowed: false,
+            reject_reason: Some("Transaction pays no fee".into()),
+            fees: None,
+            vsize: None,
+        };
+        assert_eq!(
+            serde_json::to_string(&response).unwrap(),
+            r#"{"txid":"0100000000000000000000000000000000000000000000000000000000000000","allowed":false,"reject-reason":"Transaction pays no fee"}"#
+        );
+    }
+}
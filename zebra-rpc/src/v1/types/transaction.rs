@@ -21,6 +21,20 @@ pub struct TransactionInput {
     pub sequence: Option<u32>,
 }
 
+/// Previous transaction output, spent by transaction that is signed by `signrawtransaction`
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct PreviousTransactionOutput {
+    /// Previous transaction id
+    pub txid: H256,
+    /// Previous transaction output index
+    pub vout: u32,
+    /// Previous transaction output script
+    #[serde(rename = "scriptPubKey")]
+    pub script_pubkey: Bytes,
+    /// Previous transaction output value in ZEC
+    pub amount: f64,
+}
+
 /// Transaction output of form "address": amount
 #[derive(Debug, PartialEq)]
 pub struct TransactionOutputWithAddress {
@@ -91,6 +105,16 @@ pub struct SignedTransactionInput {
     pub script_sig: TransactionInputScript,
     /// Sequence number
     pub sequence: u32,
+    /// Value of the spent output in ZEC (only if the spent output is resolved)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<f64>,
+    /// Addresses of the spent output (only if the spent output is resolved)
+    #[serde(
+        with = "types::address::vec",
+        default,
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub addresses: Vec<Address>,
 }
 
 /// Signed transaction output
@@ -105,6 +129,46 @@ pub struct SignedTransactionOutput {
     pub script: TransactionOutputScript,
 }
 
+/// JoinSplit description summary
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct TransactionJoinSplit {
+    /// Value that the JoinSplit removes from the transparent value pool, in ZEC
+    pub vpub_old: f64,
+    /// Value that the JoinSplit inserts into the transparent value pool, in ZEC
+    pub vpub_new: f64,
+    /// Merkle root of the Sprout note commitment tree
+    pub anchor: H256,
+    /// Nullifiers of the input notes
+    pub nullifiers: Vec<H256>,
+    /// Note commitments of the output notes
+    pub commitments: Vec<H256>,
+}
+
+/// Sapling spend description summary
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct TransactionShieldedSpend {
+    /// Value commitment of the input note
+    pub cv: H256,
+    /// Merkle root of the Sapling note commitment tree
+    pub anchor: H256,
+    /// Nullifier of the input note
+    pub nullifier: H256,
+    /// Randomized public key
+    pub rk: H256,
+}
+
+/// Sapling output description summary
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct TransactionShieldedOutput {
+    /// Value commitment of the output note
+    pub cv: H256,
+    /// Note commitment of the output note
+    pub cmu: H256,
+    /// Ephemeral public key
+    #[serde(rename = "ephemeralKey")]
+    pub ephemeral_key: H256,
+}
+
 /// Transaction
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
 pub struct Transaction {
@@ -116,22 +180,48 @@ pub struct Transaction {
     pub hash: H256,
     /// The serialized transaction size
     pub size: usize,
+    /// Is transaction overwintered?
+    pub overwintered: bool,
     /// The version
     pub version: i32,
+    /// Hex-encoded version group id (only for overwintered transactions)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub versiongroupid: Option<String>,
     /// The lock time
     pub locktime: i32,
+    /// Height after which transaction can't be mined (only for overwintered transactions)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expiryheight: Option<u32>,
     /// Transaction inputs
     pub vin: Vec<SignedTransactionInput>,
     /// Transaction outputs
     pub vout: Vec<SignedTransactionOutput>,
-    /// Hash of the block this transaction is included in
-    pub blockhash: H256,
-    /// Number of confirmations of this transaction
-    pub confirmations: u32,
+    /// JoinSplit descriptions
+    pub vjoinsplit: Vec<TransactionJoinSplit>,
+    /// Net value of Sapling spends minus outputs, in ZEC (only for Sapling transactions)
+    #[serde(rename = "valueBalance", skip_serializing_if = "Option::is_none")]
+    pub value_balance: Option<f64>,
+    /// Sapling spend descriptions (only for Sapling transactions)
+    #[serde(rename = "vShieldedSpend", skip_serializing_if = "Option::is_none")]
+    pub shielded_spends: Option<Vec<TransactionShieldedSpend>>,
+    /// Sapling output descriptions (only for Sapling transactions)
+    #[serde(rename = "vShieldedOutput", skip_serializing_if = "Option::is_none")]
+    pub shielded_outputs: Option<Vec<TransactionShieldedOutput>>,
+    /// Hash of the block this transaction is included in (None for memory pool transactions)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blockhash: Option<H256>,
+    /// Height of the block this transaction is included in (None if block isn't canonical)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height: Option<u32>,
+    /// Number of confirmations of this transaction. -1 if block is on the side chain
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confirmations: Option<i64>,
     /// The transaction time in seconds since epoch (Jan 1 1970 GMT)
-    pub time: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time: Option<u32>,
     /// The block time in seconds since epoch (Jan 1 1970 GMT)
-    pub blocktime: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blocktime: Option<u32>,
 }
 
 /// Return value of `getrawtransaction` method
@@ -266,6 +356,20 @@ mod tests {
 			txinput);
     }
 
+    #[test]
+    fn previous_transaction_output_deserialize() {
+        let output = PreviousTransactionOutput {
+            txid: H256::from(7),
+            vout: 33,
+            script_pubkey: Bytes::new(vec![0x76, 0xa9]),
+            amount: 0.5,
+        };
+
+        assert_eq!(
+			serde_json::from_str::<PreviousTransactionOutput>(r#"{"txid":"0700000000000000000000000000000000000000000000000000000000000000","vout":33,"scriptPubKey":"76a9","amount":0.5}"#).unwrap(),
+			output);
+    }
+
     #[test]
     fn transaction_outputs_serialize() {
         let txout = TransactionOutputs {
@@ -384,6 +488,8 @@ mod tests {
                 hex: Bytes::new(vec![1, 2, 3, 4]),
             },
             sequence: 123,
+            value: None,
+            addresses: vec![],
         };
         assert_eq!(serde_json::to_string(&txin).unwrap(), r#"{"txid":"4d00000000000000000000000000000000000000000000000000000000000000","vout":13,"script_sig":{"asm":"Hello, world!!!","hex":"01020304"},"sequence":123}"#);
     }
@@ -398,6 +504,8 @@ mod tests {
                 hex: Bytes::new(vec![1, 2, 3, 4]),
             },
             sequence: 123,
+            value: None,
+            addresses: vec![],
         };
         assert_eq!(
 			serde_json::from_str::<SignedTransactionInput>(r#"{"txid":"4d00000000000000000000000000000000000000000000000000000000000000","vout":13,"script_sig":{"asm":"Hello, world!!!","hex":"01020304"},"sequence":123}"#).unwrap(),
@@ -451,16 +559,51 @@ mod tests {
             txid: H256::from(4),
             hash: H256::from(5),
             size: 33,
+            overwintered: false,
+            version: 55,
+            versiongroupid: None,
+            locktime: 66,
+            expiryheight: None,
+            vin: vec![],
+            vout: vec![],
+            vjoinsplit: vec![],
+            value_balance: None,
+            shielded_spends: None,
+            shielded_outputs: None,
+            blockhash: Some(H256::from(6)),
+            height: Some(7),
+            confirmations: Some(77),
+            time: Some(88),
+            blocktime: Some(99),
+        };
+        assert_eq!(serde_json::to_string(&tx).unwrap(), r#"{"hex":"deadbeef","txid":"0400000000000000000000000000000000000000000000000000000000000000","hash":"0500000000000000000000000000000000000000000000000000000000000000","size":33,"overwintered":false,"version":55,"locktime":66,"vin":[],"vout":[],"vjoinsplit":[],"blockhash":"0600000000000000000000000000000000000000000000000000000000000000","height":7,"confirmations":77,"time":88,"blocktime":99}"#);
+    }
+
+    #[test]
+    fn memory_pool_transaction_serialize() {
+        let tx = Transaction {
+            hex: "DEADBEEF".into(),
+            txid: H256::from(4),
+            hash: H256::from(5),
+            size: 33,
+            overwintered: false,
             version: 55,
+            versiongroupid: None,
             locktime: 66,
+            expiryheight: None,
             vin: vec![],
             vout: vec![],
-            blockhash: H256::from(6),
-            confirmations: 77,
-            time: 88,
-            blocktime: 99,
+            vjoinsplit: vec![],
+            value_balance: None,
+            shielded_spends: None,
+            shielded_outputs: None,
+            blockhash: None,
+            height: None,
+            confirmations: None,
+            time: None,
+            blocktime: None,
         };
-        assert_eq!(serde_json::to_string(&tx).unwrap(), r#"{"hex":"deadbeef","txid":"0400000000000000000000000000000000000000000000000000000000000000","hash":"0500000000000000000000000000000000000000000000000000000000000000","size":33,"version":55,"locktime":66,"vin":[],"vout":[],"blockhash":"0600000000000000000000000000000000000000000000000000000000000000","confirmations":77,"time":88,"blocktime":99}"#);
+        assert_eq!(serde_json::to_string(&tx).unwrap(), r#"{"hex":"deadbeef","txid":"0400000000000000000000000000000000000000000000000000000000000000","hash":"0500000000000000000000000000000000000000000000000000000000000000","size":33,"overwintered":false,"version":55,"locktime":66,"vin":[],"vout":[],"vjoinsplit":[]}"#);
     }
 
     #[test]
@@ -470,17 +613,25 @@ mod tests {
             txid: H256::from(4),
             hash: H256::from(5),
             size: 33,
+            overwintered: false,
             version: 55,
+            versiongroupid: None,
             locktime: 66,
+            expiryheight: None,
             vin: vec![],
             vout: vec![],
-            blockhash: H256::from(6),
-            confirmations: 77,
-            time: 88,
-            blocktime: 99,
+            vjoinsplit: vec![],
+            value_balance: None,
+            shielded_spends: None,
+            shielded_outputs: None,
+            blockhash: Some(H256::from(6)),
+            height: Some(7),
+            confirmations: Some(77),
+            time: Some(88),
+            blocktime: Some(99),
         };
         assert_eq!(
-			serde_json::from_str::<Transaction>(r#"{"hex":"deadbeef","txid":"0400000000000000000000000000000000000000000000000000000000000000","hash":"0500000000000000000000000000000000000000000000000000000000000000","size":33,"version":55,"locktime":66,"vin":[],"vout":[],"blockhash":"0600000000000000000000000000000000000000000000000000000000000000","confirmations":77,"time":88,"blocktime":99}"#).unwrap(),
+			serde_json::from_str::<Transaction>(r#"{"hex":"deadbeef","txid":"0400000000000000000000000000000000000000000000000000000000000000","hash":"0500000000000000000000000000000000000000000000000000000000000000","size":33,"overwintered":false,"version":55,"locktime":66,"vin":[],"vout":[],"vjoinsplit":[],"blockhash":"0600000000000000000000000000000000000000000000000000000000000000","height":7,"confirmations":77,"time":88,"blocktime":99}"#).unwrap(),
 			tx);
     }
 }
@@ -0,0 +1,48 @@
+/// Response to validateaddress and z_validateaddress RPC requests.
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct ValidateAddressResponse {
+    /// Is the address valid for the node network?
+    pub isvalid: bool,
+    /// The validated address
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    /// Address type: p2pkh, p2sh, sprout or sapling
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub address_type: Option<String>,
+    /// Is the address owned by the node? Only reported for transparent addresses and always false, since there's no wallet
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ismine: Option<bool>,
+    /// Is this a script (P2SH) address? Only reported for transparent addresses
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub isscript: Option<bool>,
+    /// Hex-encoded diversifier. Only reported for Sapling addresses
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diversifier: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn validate_address_serialize() {
+        assert_eq!(
+            serde_json::to_string(&ValidateAddressResponse::default()).unwrap(),
+            r#"{"isvalid":false}"#
+        );
+
+        let response = ValidateAddressResponse {
+            isvalid: true,
+            address: Some("t1h8SqgtM3QM5e2M8EzhhT1yL2PXXtA6oqe".into()),
+            address_type: Some("p2pkh".into()),
+            ismine: Some(false),
+            isscript: Some(false),
+            diversifier: None,
+        };
+        assert_eq!(
+            serde_json::to_string(&response).unwrap(),
+            r#"{"isvalid":true,"address":"t1h8SqgtM3QM5e2M8EzhhT1yL2PXXtA6oqe","type":"p2pkh","ismine":false,"isscript":false}"#
+        );
+    }
+}
@@ -0,0 +1,141 @@
+extern crate base64;
+extern crate jsonrpc_core;
+extern crate zebra_rpc;
+
+use jsonrpc_core::{Compatibility, MetaIoHandler, Value};
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::time::Duration;
+use zebra_rpc::{start_http, Credentials, RequestFilter, RpcAuth, Server};
+
+fn start_server(max_request_size: usize, max_batch_size: usize, auth: RpcAuth) -> Server {
+    let mut handler = MetaIoHandler::new(Compatibility::V2, RequestFilter::new(max_batch_size));
+    handler.add_method("ping", |_| Ok(Value::String("pong".into())));
+    handler.add_method("stop", |_| Ok(Value::Null));
+    start_http(
+        &"127.0.0.1:0".parse().unwrap(),
+        None,
+        None,
+        max_request_size,
+        auth,
+        handler,
+    )
+    .unwrap()
+}
+
+fn password_auth() -> RpcAuth {
+    RpcAuth {
+        credentials: Some(Credentials {
+            user: "user".into(),
+            password: "password".into(),
+        }),
+        public_methods: None,
+    }
+}
+
+/// Sends raw HTTP request with given declared body length and returns the whole response.
+fn post(server: &Server, content_length: usize, body: &str) -> String {
+    post_with_headers(server, content_length, "", body)
+}
+
+fn post_with_headers(server: &Server, content_length: usize, headers: &str, body: &str) -> String {
+    let mut stream = TcpStream::connect(server.address()).unwrap();
+    stream
+        .set_read_timeout(Some(Duration::from_secs(10)))
+        .unwrap();
+    write!(
+        stream,
+        "POST / HTTP/1.1\r\nHost: 127.0.0.1\r\nContent-Type: application/json\r\nContent-Length: {}\r\n{}Connection: close\r\n\r\n{}",
+        content_length,
+        headers,
+        body
+    )
+    .unwrap();
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    response
+}
+
+fn ping_batch(size: usize) -> String {
+    let calls = (0..size)
+        .map(|id| {
+            format!(
+                r#"{{"jsonrpc":"2.0","method":"ping","params":[],"id":{}}}"#,
+                id
+            )
+        })
+        .collect::<Vec<_>>();
+    format!("[{}]", calls.join(","))
+}
+
+#[test]
+fn batch_within_limit_is_dispatched() {
+    let server = start_server(1024 * 1024, 10, RpcAuth::default());
+    let body = ping_batch(10);
+    let response = post(&server, body.len(), &body);
+    assert!(response.starts_with("HTTP/1.1 200"));
+    assert_eq!(response.matches(r#""result":"pong""#).count(), 10);
+}
+
+#[test]
+fn oversized_batch_is_rejected() {
+    let server = start_server(1024 * 1024, 10, RpcAuth::default());
+    let body = ping_batch(10_000);
+    let response = post(&server, body.len(), &body);
+    assert!(response.contains(r#""error":{"code":-32089,"message":"Batch request exceeds allowed maximum number of calls","data":10}"#));
+    assert!(!response.contains("pong"));
+}
+
+#[test]
+fn oversized_request_is_rejected_before_body_is_read() {
+    let server = start_server(1024, 10, RpcAuth::default());
+    // body is never sent, so the response is only received if the server doesn't wait for it
+    let response = post(&server, 1024 * 1024 * 1024, "");
+    assert!(response.starts_with("HTTP/1.1 413"));
+    assert!(response.contains(r#""error":{"code":-32090,"message":"Request body exceeds allowed maximum size","data":1024}"#));
+}
+
+#[test]
+fn unauthorized_call_is_rejected() {
+    let server = start_server(1024 * 1024, 10, password_auth());
+    let body = r#"{"jsonrpc":"2.0","method":"ping","params":[],"id":1}"#;
+
+    let response = post(&server, body.len(), body);
+    assert!(response.starts_with("HTTP/1.1 401"));
+    assert!(!response.contains("pong"));
+
+    let wrong_password = format!("Authorization: Basic {}\r\n", base64::encode("user:wrong"));
+    let response = post_with_headers(&server, body.len(), &wrong_password, body);
+    assert!(response.starts_with("HTTP/1.1 401"));
+    assert!(!response.contains("pong"));
+
+    let password = format!(
+        "Authorization: Basic {}\r\n",
+        base64::encode("user:password")
+    );
+    let response = post_with_headers(&server, body.len(), &password, body);
+    assert!(response.starts_with("HTTP/1.1 200"));
+    assert!(response.contains(r#""result":"pong""#));
+}
+
+#[test]
+fn only_public_methods_are_available_without_credentials() {
+    let mut auth = password_auth();
+    auth.public_methods = Some(vec!["ping".to_owned()].into_iter().collect());
+    let server = start_server(1024 * 1024, 10, auth);
+    let body = r#"[{"jsonrpc":"2.0","method":"ping","params":[],"id":1},{"jsonrpc":"2.0","method":"stop","params":[],"id":2}]"#;
+
+    let response = post(&server, body.len(), body);
+    assert!(response.starts_with("HTTP/1.1 200"));
+    assert!(response.contains(r#""result":"pong""#));
+    assert!(response.contains(r#""error":{"code":-32088,"message":"Method is not allowed without credentials","data":"\"stop\""}"#));
+
+    let password = format!(
+        "Authorization: Basic {}\r\n",
+        base64::encode("user:password")
+    );
+    let response = post_with_headers(&server, body.len(), &password, body);
+    assert!(response.contains(r#""result":"pong""#));
+    assert!(response.contains(r#""result":null"#));
+}
@@ -87,6 +87,11 @@ impl VerificationFlags {
         self
     }
 
+    pub fn verify_low_s(mut self, value: bool) -> Self {
+        self.verify_low_s = value;
+        self
+    }
+
     pub fn verify_nulldummy(mut self, value: bool) -> Self {
         self.verify_nulldummy = value;
         self
@@ -138,8 +138,9 @@ fn is_low_der_signature(sig: &[u8]) -> Result<(), Error> {
         return Err(Error::SignatureDer);
     }
 
-    let signature: Signature = sig.into();
-    if !signature.check_low_s() {
+    // the last byte is the sighash type
+    let signature: Signature = sig[..sig.len() - 1].into();
+    if !signature.is_low_s() {
         return Err(Error::SignatureHighS);
     }
 
@@ -1887,6 +1888,14 @@ mod tests {
         let output: Script = "410411db93e1dcdb8a016b49840f8c53bc1eb68a382e97b1482ecad7b148a6909a5cb2e0eaddfb84ccf9744464f82e160bfa9b8b64f9d4c03f999b8643f656b412a3ac".into();
         let flags = VerificationFlags::default().verify_p2sh(true);
         assert_eq!(verify_script(&input, &output, &flags, &mut checker), Ok(()));
+
+        let flags = VerificationFlags::default()
+            .verify_p2sh(true)
+            .verify_low_s(true);
+        assert_eq!(
+            verify_script(&input, &output, &flags, &mut checker),
+            Err(Error::SignatureHighS)
+        );
     }
 
     // https://blockchain.info/rawtx/fb0a1d8d34fa5537e461ac384bac761125e1bfa7fec286fa72511240fa66864d
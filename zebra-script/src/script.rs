@@ -2,8 +2,9 @@
 
 use bytes::Bytes;
 use std::{fmt, ops};
+use zebra_crypto::dhash160;
 use zebra_keys::{self, AddressHash, Public};
-use {Error, Opcode};
+use {Error, Num, Opcode};
 
 /// Maximum number of bytes pushable to the stack
 pub const MAX_SCRIPT_ELEMENT_SIZE: usize = 520;
@@ -139,15 +140,14 @@ impl Script {
             return false;
         }
 
+        // both m and n of m-of-n multisig must be in 1..16 range
         let siglen = match self.get_opcode(0) {
-            Ok(Opcode::OP_0) => 0,
-            Ok(o) if o >= Opcode::OP_1 && o <= Opcode::OP_16 => o as u8 - (Opcode::OP_1 as u8 - 1),
+            Ok(o) if o.is_within_op_n() => o.decode_op_n(),
             _ => return false,
         };
 
         let keylen = match self.get_opcode(self.data.len() - 2) {
-            Ok(Opcode::OP_0) => 0,
-            Ok(o) if o >= Opcode::OP_1 && o <= Opcode::OP_16 => o as u8 - (Opcode::OP_1 as u8 - 1),
+            Ok(o) if o.is_within_op_n() => o.decode_op_n(),
             _ => return false,
         };
 
@@ -178,6 +178,31 @@ impl Script {
         keys == keylen
     }
 
+    /// Returns number of required signatures (m), number of public keys (n) and hashes of
+    /// public keys of the m-of-n multisig script. Returns None if it isn't a multisig script.
+    pub fn multisig_params(&self) -> Option<(u8, u8, Vec<AddressHash>)> {
+        if !self.is_multisig_script() {
+            return None;
+        }
+
+        let required = self.data[0] - (Opcode::OP_1 as u8 - 1);
+        let total = self.data[self.data.len() - 2] - (Opcode::OP_1 as u8 - 1);
+        let hashes = self
+            .iter()
+            .skip(1)
+            .take(total as usize)
+            .map(|instruction| {
+                let data = instruction
+                    .expect("checked by is_multisig_script; qed")
+                    .data
+                    .expect("checked by is_multisig_script; qed");
+                dhash160(data)
+            })
+            .collect();
+        Some((required, total, hashes))
+    }
+
+    /// Test for null data (`OP_RETURN <data>`) scripts. Empty `OP_RETURN` is also null data.
     pub fn is_null_data_script(&self) -> bool {
         // TODO: optimise it
         !self.data.is_empty()
@@ -257,6 +282,29 @@ impl Script {
         true
     }
 
+    /// Validates that script is well-formed && isn't provably unspendable.
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.data.len() > MAX_SCRIPT_SIZE {
+            return Err(Error::ScriptSize);
+        }
+
+        if !self.data.is_empty() && self.data[0] == Opcode::OP_RETURN as u8 {
+            return Err(Error::ReturnOpcode);
+        }
+
+        // fails if script contains unknown opcode or truncated push
+        for instruction in self.iter() {
+            instruction?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns true if outputs, locked by this script, could be spent.
+    pub fn is_spendable(&self) -> bool {
+        self.validate().is_ok()
+    }
+
     pub fn script_type(&self) -> ScriptType {
         if self.is_pay_to_public_key() {
             ScriptType::PubKey
@@ -287,6 +335,38 @@ impl Script {
         }
     }
 
+    /// Returns script disassembly, formatted the same way as `asm` field of bitcoind RPC.
+    /// Pushes of up to 4 bytes are rendered as numbers, larger pushes are rendered as hex.
+    pub fn to_asm_string(&self) -> String {
+        let mut parts = Vec::new();
+        for instruction in self.iter() {
+            let instruction = match instruction {
+                Ok(instruction) => instruction,
+                Err(_) => {
+                    parts.push("[error]".to_owned());
+                    break;
+                }
+            };
+
+            let part = match instruction.data {
+                Some(data) if data.len() <= 4 => {
+                    let num = Num::from_slice(data, false, 4)
+                        .expect("data.len() <= 4 && non-minimal encoding is allowed; qed");
+                    i64::from(num).to_string()
+                }
+                Some(data) => format!("{:?}", Bytes::from(data.to_vec())),
+                None if instruction.opcode == Opcode::OP_1NEGATE => "-1".to_owned(),
+                None if instruction.opcode.is_within_op_n() => {
+                    instruction.opcode.decode_op_n().to_string()
+                }
+                None => format!("{:?}", instruction.opcode),
+            };
+            parts.push(part);
+        }
+
+        parts.join(" ")
+    }
+
     pub fn sigops_count(&self, serialized_script: bool) -> usize {
         let mut last_opcode = Opcode::OP_0;
         let mut total = 0;
@@ -318,13 +398,10 @@ impl Script {
     }
 
     pub fn num_signatures_required(&self) -> u8 {
-        if self.is_multisig_script() {
-            return match self.data[0] {
-                x if x == Opcode::OP_0 as u8 => 0,
-                x => x - (Opcode::OP_1 as u8) + 1,
-            };
+        match self.multisig_params() {
+            Some((required, _, _)) => required,
+            None => 1,
         }
-        return 1;
     }
 
     pub fn extract_destinations(&self) -> Result<Vec<ScriptAddress>, zebra_keys::Error> {
@@ -341,20 +418,10 @@ impl Script {
             ScriptType::PubKeyHash => Ok(vec![ScriptAddress::new_p2pkh(self.data[3..23].into())]),
             ScriptType::ScriptHash => Ok(vec![ScriptAddress::new_p2sh(self.data[2..22].into())]),
             ScriptType::Multisig => {
-                let mut addresses: Vec<ScriptAddress> = Vec::new();
-                let mut pc = 1;
-                while pc < self.len() - 2 {
-                    let instruction = self
-                        .get_instruction(pc)
-                        .expect("this method depends on previous check in script_type()");
-                    let data = instruction
-                        .data
-                        .expect("this method depends on previous check in script_type()");
-                    let address = Public::from_slice(data)?.address_hash();
-                    addresses.push(ScriptAddress::new_p2pkh(address));
-                    pc += instruction.step;
-                }
-                Ok(addresses)
+                let (_, _, hashes) = self
+                    .multisig_params()
+                    .expect("this method depends on previous check in script_type()");
+                Ok(hashes.into_iter().map(ScriptAddress::new_p2pkh).collect())
             }
             ScriptType::NullData => Ok(vec![]),
         }
@@ -487,9 +554,9 @@ impl fmt::Display for Script {
 
 #[cfg(test)]
 mod tests {
-    use super::{Script, ScriptAddress, ScriptType, MAX_SCRIPT_ELEMENT_SIZE};
+    use super::{Script, ScriptAddress, ScriptType, MAX_SCRIPT_ELEMENT_SIZE, MAX_SCRIPT_SIZE};
     use zebra_keys::{Address, Public};
-    use {Builder, Opcode};
+    use {Builder, Error, Opcode};
 
     #[test]
     fn test_is_pay_to_script_hash() {
@@ -499,6 +566,25 @@ mod tests {
         assert!(!script2.is_pay_to_script_hash());
     }
 
+    #[test]
+    fn test_script_validate() {
+        let p2pkh: Script = "76a914aab76ba4877d696590d94ea3e02948b55294815188ac".into();
+        assert_eq!(p2pkh.validate(), Ok(()));
+        assert!(p2pkh.is_spendable());
+
+        let null_data: Script = "6a0401020304".into();
+        assert_eq!(null_data.validate(), Err(Error::ReturnOpcode));
+        assert!(!null_data.is_spendable());
+
+        // OP_PUSHBYTES_20 followed by 3 bytes only
+        let truncated_push: Script = "76a914aab76b".into();
+        assert_eq!(truncated_push.validate(), Err(Error::BadOpcode));
+        assert!(!truncated_push.is_spendable());
+
+        let oversized: Script = vec![Opcode::OP_NOP as u8; MAX_SCRIPT_SIZE + 1].into();
+        assert_eq!(oversized.validate(), Err(Error::ScriptSize));
+    }
+
     #[test]
     fn test_script_debug() {
         use std::fmt::Write;
@@ -528,6 +614,38 @@ OP_ADD
         assert_eq!(script.to_string(), s.to_string());
     }
 
+    #[test]
+    fn test_script_to_asm_string() {
+        let p2pkh: Script = "76a914aab76ba4877d696590d94ea3e02948b55294815188ac".into();
+        assert_eq!(
+            p2pkh.to_asm_string(),
+            "OP_DUP OP_HASH160 aab76ba4877d696590d94ea3e02948b552948151 OP_EQUALVERIFY OP_CHECKSIG"
+        );
+
+        let p2sh: Script = "a9143b80842f4ea32806ce5e723a255ddd6490cfd28d87".into();
+        assert_eq!(
+            p2sh.to_asm_string(),
+            "OP_HASH160 3b80842f4ea32806ce5e723a255ddd6490cfd28d OP_EQUAL"
+        );
+
+        let multisig: Script = "51210279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f817982102c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee552ae".into();
+        assert_eq!(
+            multisig.to_asm_string(),
+            "1 0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798 02c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5 2 OP_CHECKMULTISIG"
+        );
+    }
+
+    #[test]
+    fn test_script_to_asm_string_numbers_and_errors() {
+        // OP_0 OP_1NEGATE OP_16 PUSH(0x81) PUSH(0xffff00) OP_RETURN
+        let script: Script = "004f60018103ffff006a".into();
+        assert_eq!(script.to_asm_string(), "0 -1 16 -1 65535 OP_RETURN");
+
+        // OP_PUSHBYTES_20 followed by 3 bytes only
+        let truncated_push: Script = "76a914aab76b".into();
+        assert_eq!(truncated_push.to_asm_string(), "OP_DUP OP_HASH160 [error]");
+    }
+
     #[test]
     fn test_script_is_multisig() {
         let script: Script = "524104a882d414e478039cd5b52a92ffb13dd5e6bd4515497439dffd691a0f12af9575fa349b5694ed3155b136f09e63975a1700c9f4d4df849323dac06cf3bd6458cd41046ce31db9bdd543e72fe3039a1f1c047dab87037c36a669ff90e28da1848f640de68c2fe913d363a51154a0c62d7adea1b822d05035077418267b1a1379790187410411ffd36c70776538d079fbae117dc38effafb33304af83ce4894589747aee1ef992f63280567f52f5ba870678b4ab4ff6c8ea600bd217870a8b4f1f09f3a8e8353ae".into();
@@ -678,6 +796,81 @@ OP_ADD
         );
     }
 
+    #[test]
+    fn test_multisig_params() {
+        let pubkey1 = Public::from_slice(&[1; 33]).unwrap();
+        let pubkey2 = Public::from_slice(&[2; 33]).unwrap();
+        let pubkey3 = Public::from_slice(&[3; 65]).unwrap();
+
+        let one_of_two = Builder::default()
+            .push_opcode(Opcode::OP_1)
+            .push_bytes(&pubkey1)
+            .push_bytes(&pubkey2)
+            .push_opcode(Opcode::OP_2)
+            .push_opcode(Opcode::OP_CHECKMULTISIG)
+            .into_script();
+        assert_eq!(one_of_two.script_type(), ScriptType::Multisig);
+        assert_eq!(
+            one_of_two.multisig_params(),
+            Some((1, 2, vec![pubkey1.address_hash(), pubkey2.address_hash()]))
+        );
+        assert_eq!(one_of_two.num_signatures_required(), 1);
+
+        let two_of_three = Builder::default()
+            .push_opcode(Opcode::OP_2)
+            .push_bytes(&pubkey1)
+            .push_bytes(&pubkey2)
+            .push_bytes(&pubkey3)
+            .push_opcode(Opcode::OP_3)
+            .push_opcode(Opcode::OP_CHECKMULTISIG)
+            .into_script();
+        assert_eq!(two_of_three.script_type(), ScriptType::Multisig);
+        assert_eq!(
+            two_of_three.multisig_params(),
+            Some((
+                2,
+                3,
+                vec![
+                    pubkey1.address_hash(),
+                    pubkey2.address_hash(),
+                    pubkey3.address_hash(),
+                ]
+            ))
+        );
+        assert_eq!(
+            two_of_three.extract_destinations(),
+            Ok(vec![
+                ScriptAddress::new_p2pkh(pubkey1.address_hash()),
+                ScriptAddress::new_p2pkh(pubkey2.address_hash()),
+                ScriptAddress::new_p2pkh(pubkey3.address_hash()),
+            ])
+        );
+
+        // 0-of-1 multisig is not standard
+        let zero_of_one = Builder::default()
+            .push_opcode(Opcode::OP_0)
+            .push_bytes(&pubkey1)
+            .push_opcode(Opcode::OP_1)
+            .push_opcode(Opcode::OP_CHECKMULTISIG)
+            .into_script();
+        assert_eq!(zero_of_one.script_type(), ScriptType::NonStandard);
+        assert_eq!(zero_of_one.multisig_params(), None);
+    }
+
+    #[test]
+    fn test_null_data_script_type() {
+        let empty: Script = "6a".into();
+        assert_eq!(empty.script_type(), ScriptType::NullData);
+        assert_eq!(empty.extract_destinations(), Ok(vec![]));
+
+        let with_data: Script = "6a0401020304".into();
+        assert_eq!(with_data.script_type(), ScriptType::NullData);
+
+        // OP_RETURN followed by non-push opcode
+        let not_push_only: Script = "6a76".into();
+        assert_eq!(not_push_only.script_type(), ScriptType::NonStandard);
+    }
+
     #[test]
     fn test_num_signatures_required() {
         let script = Builder::default()
@@ -258,8 +258,9 @@ impl Deserializable for Bytes {
     where
         T: io::Read,
     {
-        let len = try!(reader.read::<CompactInteger>());
-        let mut bytes = Bytes::new_with_len(len.into());
+        let len: usize = try!(reader.read::<CompactInteger>()).into();
+        try!(reader.check_declared_len(len, 1));
+        let mut bytes = Bytes::new_with_len(len);
         try!(reader.read_slice(&mut bytes));
         Ok(bytes)
     }
@@ -11,6 +11,10 @@ mod stream;
 
 pub use compact_integer::CompactInteger;
 pub use list::List;
-pub use reader::{deserialize, deserialize_iterator, Deserializable, Error, ReadIterator, Reader};
-pub use stream::{serialize, serialize_list, serialized_list_size, Serializable, Stream};
+pub use reader::{
+    deserialize, deserialize_iterator, Deserializable, Error, ReadIterator, Reader, ReaderLimits,
+};
+pub use stream::{
+    serialize, serialize_into, serialize_list, serialized_list_size, Serializable, Stream,
+};
 pub use zebra_primitives::{bytes, compact, hash};
@@ -1,5 +1,5 @@
 use compact_integer::CompactInteger;
-use std::{io, marker};
+use std::{io, marker, mem};
 
 pub fn deserialize<R, T>(buffer: R) -> Result<T, Error>
 where
@@ -48,11 +48,22 @@ pub trait Deserializable {
         T: io::Read;
 }
 
+/// Limits of the declared lengths of lists and byte arrays, read by the `Reader`.
+/// Declared lengths are checked before any memory is allocated for the data.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReaderLimits {
+    /// Maximal number of elements in a single list (or bytes in a single byte array).
+    pub max_elements: usize,
+    /// Maximal number of bytes that could be allocated for a single list or byte array.
+    pub max_bytes: usize,
+}
+
 /// Bitcoin structures reader.
 #[derive(Debug)]
 pub struct Reader<T> {
     buffer: T,
     peeked: Option<u8>,
+    limits: Option<ReaderLimits>,
 }
 
 impl<'a> Reader<&'a [u8]> {
@@ -61,6 +72,17 @@ impl<'a> Reader<&'a [u8]> {
         Reader {
             buffer: buffer,
             peeked: None,
+            limits: None,
+        }
+    }
+
+    /// Creates reader for slice of bytes, which rejects lists and byte arrays with
+    /// declared lengths that are exceeding given limits.
+    pub fn with_limits(buffer: &'a [u8], limits: ReaderLimits) -> Self {
+        Reader {
+            buffer: buffer,
+            peeked: None,
+            limits: Some(limits),
         }
     }
 }
@@ -95,6 +117,7 @@ where
         Reader {
             buffer: read,
             peeked: None,
+            limits: None,
         }
     }
 
@@ -110,7 +133,9 @@ where
         T: Deserializable,
         F: FnMut(&[u8]),
     {
+        let limits = self.limits;
         let mut reader = Reader::from_read(Proxy::new(self, proxy));
+        reader.limits = limits;
         T::deserialize(&mut reader)
     }
 
@@ -136,11 +161,25 @@ where
         io::Read::read_exact(self, bytes).map_err(|_| Error::UnexpectedEnd)
     }
 
+    /// Checks that list of `len` elements of `element_size` bytes each fits the reader limits.
+    pub fn check_declared_len(&self, len: usize, element_size: usize) -> Result<(), Error> {
+        match self.limits {
+            Some(ref limits)
+                if len > limits.max_elements
+                    || len.saturating_mul(element_size) > limits.max_bytes =>
+            {
+                Err(Error::MalformedData)
+            }
+            _ => Ok(()),
+        }
+    }
+
     pub fn read_list<T>(&mut self) -> Result<Vec<T>, Error>
     where
         T: Deserializable,
     {
         let len: usize = try!(self.read::<CompactInteger>()).into();
+        try!(self.check_declared_len(len, mem::size_of::<T>()));
         let mut result = Vec::with_capacity(len);
 
         for _ in 0..len {
@@ -158,6 +197,7 @@ where
         if len > max {
             return Err(Error::MalformedData);
         }
+        try!(self.check_declared_len(len, mem::size_of::<T>()));
 
         let mut result = Vec::with_capacity(len);
 
@@ -176,6 +216,7 @@ where
         if len != expected_len {
             return Err(Error::MalformedData);
         }
+        try!(self.check_declared_len(len, mem::size_of::<T>()));
 
         let mut result = Vec::with_capacity(len);
 
@@ -248,3 +289,48 @@ where
         Ok(len)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Error, Reader, ReaderLimits};
+    use bytes::Bytes;
+    use hash::H256;
+
+    const LIMITS: ReaderLimits = ReaderLimits {
+        max_elements: 1000,
+        max_bytes: 1024,
+    };
+
+    #[test]
+    fn declared_length_over_limits_is_rejected() {
+        // list of 10_000_000 elements
+        let huge_list = [0xfe, 0x80, 0x96, 0x98, 0x00];
+        assert_eq!(
+            Reader::with_limits(&huge_list, LIMITS).read_list::<H256>(),
+            Err(Error::MalformedData)
+        );
+        assert_eq!(
+            Reader::with_limits(&huge_list, LIMITS).read::<Bytes>(),
+            Err(Error::MalformedData)
+        );
+
+        // 100 elements fit max_elements, but not max_bytes
+        let mut list = vec![100u8];
+        list.extend_from_slice(&[0u8; 3200]);
+        assert_eq!(
+            Reader::with_limits(&list, LIMITS).read_list::<H256>(),
+            Err(Error::MalformedData)
+        );
+        assert_eq!(Reader::new(&list).read_list::<H256>().unwrap().len(), 100);
+
+        let mut list = vec![32u8];
+        list.extend_from_slice(&[0u8; 1024]);
+        assert_eq!(
+            Reader::with_limits(&list, LIMITS)
+                .read_list::<H256>()
+                .unwrap()
+                .len(),
+            32
+        );
+    }
+}
@@ -3,6 +3,7 @@ use bytes::Bytes;
 use compact_integer::CompactInteger;
 use std::borrow::Borrow;
 use std::io::{self, Write};
+use std::mem;
 
 pub fn serialize<T>(t: &T) -> Bytes
 where
@@ -13,6 +14,17 @@ where
     stream.out()
 }
 
+/// Serializes the struct and appends it to the end of the `buffer`.
+/// Allows callers to reuse the same buffer for serialization of many structs.
+pub fn serialize_into<T>(buffer: &mut Vec<u8>, t: &T)
+where
+    T: Serializable,
+{
+    let mut stream = Stream::new_in(mem::replace(buffer, Vec::new()));
+    stream.append(t);
+    *buffer = stream.into_buffer();
+}
+
 pub fn serialize_list<T, K>(t: &[K]) -> Bytes
 where
     T: Serializable,
@@ -61,6 +73,11 @@ impl Stream {
         Stream { buffer: Vec::new() }
     }
 
+    /// New stream, which appends serialized data to the end of given buffer.
+    pub fn new_in(buffer: Vec<u8>) -> Self {
+        Stream { buffer: buffer }
+    }
+
     /// Serializes the struct and appends it to the end of stream.
     pub fn append<T>(&mut self, t: &T) -> &mut Self
     where
@@ -94,6 +111,11 @@ impl Stream {
     pub fn out(self) -> Bytes {
         self.buffer.into()
     }
+
+    /// Full stream, as the underlying buffer.
+    pub fn into_buffer(self) -> Vec<u8> {
+        self.buffer
+    }
 }
 
 impl Write for Stream {
@@ -107,3 +129,32 @@ impl Write for Stream {
         self.buffer.flush()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{serialize, serialize_into, Stream};
+    use bytes::Bytes;
+    use hash::H256;
+
+    #[test]
+    fn serialize_into_appends_to_buffer() {
+        let hash = H256::from(7);
+        let data: Bytes = "0102".into();
+
+        let mut buffer = vec![0xff];
+        serialize_into(&mut buffer, &hash);
+        serialize_into(&mut buffer, &data);
+        let mut expected = vec![0xff];
+        expected.extend_from_slice(&serialize(&hash));
+        expected.extend_from_slice(&serialize(&data));
+        assert_eq!(buffer, expected);
+
+        buffer.clear();
+        serialize_into(&mut buffer, &data);
+        assert_eq!(buffer, serialize(&data).take());
+
+        let mut stream = Stream::new_in(vec![0xff]);
+        stream.append(&data);
+        assert_eq!(stream.out(), "ff020102".into());
+    }
+}
@@ -0,0 +1,53 @@
+use syn;
+
+/// Returns the length, declared with `#[serialization(len = N)]` field attribute.
+///
+/// The attribute is only supported for `[u8; N]` fields, which are serialized as exactly N bytes,
+/// without length prefix. Length of these fields is guaranteed by their type, so serialization
+/// never has to deal with values of unexpected length.
+pub fn fixed_len(field: &syn::Field) -> Option<usize> {
+    let len = field
+        .attrs
+        .iter()
+        .filter_map(|attr| match attr.value {
+            syn::MetaItem::List(ref name, ref items) if name == "serialization" => Some(items),
+            _ => None,
+        })
+        .flat_map(|items| items.iter())
+        .map(|item| match *item {
+            syn::NestedMetaItem::MetaItem(syn::MetaItem::NameValue(
+                ref name,
+                syn::Lit::Int(len, _),
+            )) if name == "len" => len as usize,
+            _ => panic!("unsupported serialization attribute, expected #[serialization(len = N)]"),
+        })
+        .next();
+
+    let len = match len {
+        Some(len) => len,
+        None => return None,
+    };
+
+    let is_fixed = match field.ty {
+        syn::Ty::Array(ref item, syn::ConstExpr::Lit(syn::Lit::Int(array_len, _))) => {
+            is_u8(item) && array_len as usize == len
+        }
+        _ => false,
+    };
+
+    if !is_fixed {
+        panic!(
+            "#[serialization(len = {})] is only supported for [u8; {}] fields",
+            len, len
+        );
+    }
+
+    Some(len)
+}
+
+fn is_u8(ty: &syn::Ty) -> bool {
+    match *ty {
+        syn::Ty::Path(None, ref path) => path.segments.len() == 1 && path.segments[0].ident == "u8",
+        _ => false,
+    }
+}
@@ -1,3 +1,4 @@
+use attr::fixed_len;
 use {quote, syn};
 
 pub fn impl_deserializable(ast: &syn::DeriveInput) -> quote::Tokens {
@@ -59,6 +60,16 @@ fn deserialize_field(index: usize, field: &syn::Field) -> quote::Tokens {
 
     let id = syn::Ident::new(ident.to_string());
 
+    if let Some(len) = fixed_len(field) {
+        return quote! {
+            #id: {
+                let mut buffer = [0u8; #len];
+                reader.read_slice(&mut buffer)?;
+                buffer
+            },
+        };
+    }
+
     match field.ty {
         syn::Ty::Path(_, ref path) => {
             let ident = &path
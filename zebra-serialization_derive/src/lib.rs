@@ -3,6 +3,7 @@ extern crate syn;
 #[macro_use]
 extern crate quote;
 
+mod attr;
 mod de;
 mod ser;
 
@@ -10,7 +11,7 @@ use de::impl_deserializable;
 use proc_macro::TokenStream;
 use ser::impl_serializable;
 
-#[proc_macro_derive(Serializable)]
+#[proc_macro_derive(Serializable, attributes(serialization))]
 pub fn serializable(input: TokenStream) -> TokenStream {
     let s = input.to_string();
     let ast = syn::parse_derive_input(&s).unwrap();
@@ -18,7 +19,7 @@ pub fn serializable(input: TokenStream) -> TokenStream {
     gen.parse().unwrap()
 }
 
-#[proc_macro_derive(Deserializable)]
+#[proc_macro_derive(Deserializable, attributes(serialization))]
 pub fn deserializable(input: TokenStream) -> TokenStream {
     let s = input.to_string();
     let ast = syn::parse_derive_input(&s).unwrap();
@@ -1,3 +1,4 @@
+use attr::fixed_len;
 use {quote, syn};
 
 pub fn impl_serializable(ast: &syn::DeriveInput) -> quote::Tokens {
@@ -70,6 +71,10 @@ fn serialize_field_size(index: usize, field: &syn::Field) -> quote::Tokens {
 
     let id = syn::Ident::new(format!("self.{}", ident));
 
+    if let Some(len) = fixed_len(field) {
+        return quote! { #len };
+    }
+
     match field.ty {
         syn::Ty::Path(_, ref path) => {
             let ident = &path
@@ -102,6 +107,10 @@ fn serialize_field(index: usize, field: &syn::Field) -> quote::Tokens {
 
     let id = syn::Ident::new(format!("self.{}", ident));
 
+    if let Some(len) = fixed_len(field) {
+        return quote! { stream.append_slice(&#id[..]); };
+    }
+
     match field.ty {
         syn::Ty::Path(_, ref path) => {
             let ident = &path
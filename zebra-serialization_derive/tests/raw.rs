@@ -2,7 +2,8 @@ extern crate zebra_serialization;
 #[macro_use]
 extern crate zebra_serialization_derive;
 
-use zebra_serialization::{deserialize, serialize};
+use zebra_serialization::bytes::Bytes;
+use zebra_serialization::{deserialize, serialize, Error, Serializable};
 
 #[derive(Debug, PartialEq, Serializable, Deserializable)]
 struct Foo {
@@ -67,3 +68,36 @@ fn test_bar_serialize() {
     let d = deserialize(expected.as_ref()).unwrap();
     assert_eq!(bar, d);
 }
+
+#[derive(Debug, PartialEq, Serializable, Deserializable)]
+struct Baz {
+    a: u8,
+    #[serialization(len = 3)]
+    b: [u8; 3],
+    c: Vec<u8>,
+    #[serialization(len = 4)]
+    d: [u8; 4],
+}
+
+#[test]
+fn test_baz_fixed_len_serialize() {
+    let baz = Baz {
+        a: 1,
+        b: [2, 3, 4],
+        c: vec![5, 6],
+        d: [7, 8, 9, 10],
+    };
+
+    // fixed length fields have no length prefix
+    let expected: Bytes = vec![1u8, 2, 3, 4, 2, 5, 6, 7, 8, 9, 10].into();
+
+    let result = serialize(&baz);
+    assert_eq!(result, expected);
+    assert_eq!(baz.serialized_size(), expected.len());
+
+    let d = deserialize(expected.as_ref()).unwrap();
+    assert_eq!(baz, d);
+
+    let truncated: Result<Baz, _> = deserialize(&expected[..10]);
+    assert_eq!(truncated, Err(Error::UnexpectedEnd));
+}
@@ -0,0 +1,123 @@
+//! Transparent address index
+
+use hash::{H160, H256};
+use ser::{Deserializable, Error as ReaderError, Reader, Serializable, Stream};
+use std::io;
+use zebra_chain::OutPoint;
+
+/// Transparent address, which outputs are indexed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IndexedAddress {
+    /// Pay to public key hash address
+    PubKeyHash(H160),
+    /// Pay to script hash address
+    ScriptHash(H160),
+}
+
+impl Serializable for IndexedAddress {
+    fn serialize(&self, stream: &mut Stream) {
+        match *self {
+            IndexedAddress::PubKeyHash(ref hash) => stream.append(&0u8).append(hash),
+            IndexedAddress::ScriptHash(ref hash) => stream.append(&1u8).append(hash),
+        };
+    }
+}
+
+impl Deserializable for IndexedAddress {
+    fn deserialize<T>(reader: &mut Reader<T>) -> Result<Self, ReaderError>
+    where
+        T: io::Read,
+    {
+        match reader.read::<u8>()? {
+            0 => Ok(IndexedAddress::PubKeyHash(reader.read()?)),
+            1 => Ok(IndexedAddress::ScriptHash(reader.read()?)),
+            _ => Err(ReaderError::MalformedData),
+        }
+    }
+}
+
+/// Canonical transaction output, paying to indexed address.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AddressOutput {
+    /// Reference to the output
+    pub outpoint: OutPoint,
+    /// Height of the block, containing the output
+    pub height: u32,
+    /// Output value
+    pub value: u64,
+}
+
+impl Serializable for AddressOutput {
+    fn serialize(&self, stream: &mut Stream) {
+        stream
+            .append(&self.outpoint)
+            .append(&self.height)
+            .append(&self.value);
+    }
+}
+
+impl Deserializable for AddressOutput {
+    fn deserialize<T>(reader: &mut Reader<T>) -> Result<Self, ReaderError>
+    where
+        T: io::Read,
+    {
+        Ok(AddressOutput {
+            outpoint: reader.read()?,
+            height: reader.read()?,
+            value: reader.read()?,
+        })
+    }
+}
+
+/// Canonical transaction input, spending output, paying to indexed address.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AddressSpend {
+    /// Hash of the spending transaction
+    pub txid: H256,
+    /// Height of the block, containing the spending transaction
+    pub height: u32,
+    /// Reference to the spent output
+    pub prevout: OutPoint,
+    /// Value of the spent output
+    pub value: u64,
+}
+
+impl Serializable for AddressSpend {
+    fn serialize(&self, stream: &mut Stream) {
+        stream
+            .append(&self.txid)
+            .append(&self.height)
+            .append(&self.prevout)
+            .append(&self.value);
+    }
+}
+
+impl Deserializable for AddressSpend {
+    fn deserialize<T>(reader: &mut Reader<T>) -> Result<Self, ReaderError>
+    where
+        T: io::Read,
+    {
+        Ok(AddressSpend {
+            txid: reader.read()?,
+            height: reader.read()?,
+            prevout: reader.read()?,
+            value: reader.read()?,
+        })
+    }
+}
+
+pub trait AddressIndexProvider: Send + Sync {
+    /// Returns true if the address index is maintained by the storage.
+    fn is_address_index_enabled(&self) -> bool;
+
+    /// Returns canonical outputs (both spent and unspent), paying to given address, from blocks
+    /// of [start; end] heights range, ordered by height.
+    /// Always returns empty list if the address index is disabled.
+    fn address_outputs(&self, address: &IndexedAddress, start: u32, end: u32)
+        -> Vec<AddressOutput>;
+
+    /// Returns canonical inputs, spending outputs of given address, from blocks of [start; end]
+    /// heights range, ordered by height.
+    /// Always returns empty list if the address index is disabled.
+    fn address_spends(&self, address: &IndexedAddress, start: u32, end: u32) -> Vec<AddressSpend>;
+}
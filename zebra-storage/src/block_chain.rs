@@ -6,6 +6,10 @@ pub trait ForkChain {
     /// Returns forks underlaying store.
     fn store(&self) -> &Store;
 
+    /// Decanonizes best block of the fork. Returns hash of the decanonized block.
+    /// Canon chain is not affected until the fork is flushed.
+    fn decanonize(&self) -> Result<H256, Error>;
+
     /// Flush fork changes to canon chain.
     /// Should not be used directly from outside of `BlockChain`.
     fn flush(&self) -> Result<(), Error>;
@@ -26,6 +30,21 @@ pub trait BlockChain {
 
     /// Checks block origin
     fn block_origin(&self, header: &IndexedBlockHeader) -> Result<BlockOrigin, Error>;
+
+    /// Marks block as precious, so that its chain is preferred over other chains of the same length.
+    /// Returns `SideChainBecomingCanonChain` if best chain should be switched to the block chain
+    fn precious_block(&self, block_hash: &H256) -> Result<BlockOrigin, Error>;
+
+    /// Checks origin of the already stored block. Returns `KnownBlock` if block is canonical
+    fn stored_block_origin(&self, block_hash: &H256) -> Result<BlockOrigin, Error>;
+
+    /// Returns route to decanonize given canonical block and all its descendants.
+    /// Fails with `AncientFork` if the route is longer than the max fork route
+    fn rollback_origin(&self, block_hash: &H256) -> Result<SideChainOrigin, Error>;
+
+    /// Writes all changes, buffered in memory, to the persistent storage.
+    /// Waits for the (de)canonization in progress to complete
+    fn flush(&self) -> Result<(), Error>;
 }
 
 pub trait Forkable {
@@ -19,8 +19,13 @@ pub trait BlockProvider: BlockHeaderProvider {
     fn block_hash(&self, number: u32) -> Option<H256>;
 
     /// resolves deserialized block body by block reference (number/hash)
+    /// pruned block is returned without transactions
     fn block(&self, block_ref: BlockRef) -> Option<IndexedBlock>;
 
+    /// resolves serialized block and serialized block header by block reference (number/hash)
+    /// returns None for pruned blocks
+    fn block_and_header_bytes(&self, block_ref: BlockRef) -> Option<(Bytes, Bytes)>;
+
     /// returns true if store contains given block
     fn contains_block(&self, block_ref: BlockRef) -> bool {
         self.block_header_bytes(block_ref).is_some()
@@ -31,4 +36,21 @@ pub trait BlockProvider: BlockHeaderProvider {
 
     /// returns all transactions in the block by block reference (number/hash)
     fn block_transactions(&self, block_ref: BlockRef) -> Vec<IndexedTransaction>;
+
+    /// returns number of transactions in the canonical chain up to and including given block
+    /// returns None for non-canonical blocks and if the counter is not maintained by the store
+    fn chain_tx_count(&self, _block_ref: BlockRef) -> Option<u64> {
+        None
+    }
+
+    /// returns true if transactions of given block have been pruned from the store
+    fn is_pruned(&self, _block_ref: BlockRef) -> bool {
+        false
+    }
+
+    /// returns height, below which transactions of canonical blocks could have been pruned
+    /// returns None if the store has never been pruned
+    fn prune_height(&self) -> Option<u32> {
+        None
+    }
 }
@@ -0,0 +1,10 @@
+/// Size statistics of single database column
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ColumnStat {
+    /// Column index
+    pub col: u32,
+    /// Approximate number of keys in the column
+    pub approx_keys: u64,
+    /// Approximate size of keys and values in the column (in bytes)
+    pub approx_bytes: u64,
+}
@@ -15,6 +15,12 @@ pub enum Error {
     /// Invalid block
     #[display(fmt = "Cannot decanonize block (invalid database state)")]
     CannotDecanonize,
+    /// Transactions of the block have been pruned
+    #[display(fmt = "Block transactions have been pruned")]
+    PrunedBlock,
+    /// Index can't be enabled, because it would miss already stored blocks
+    #[display(fmt = "Index can only be enabled on an empty database")]
+    IncompleteIndex,
 }
 
 impl From<Error> for String {
@@ -13,6 +13,7 @@ extern crate zebra_serialization as ser;
 extern crate lazy_static;
 extern crate zebra_network;
 
+mod address_index;
 mod best_block;
 mod block_ancestors;
 mod block_chain;
@@ -21,6 +22,7 @@ mod block_iterator;
 mod block_origin;
 mod block_provider;
 mod block_ref;
+mod column_stat;
 mod duplex_store;
 mod error;
 mod nullifier_tracker;
@@ -32,6 +34,7 @@ mod tree_state_provider;
 
 pub use zebra_primitives::{bytes, hash};
 
+pub use address_index::{AddressIndexProvider, AddressOutput, AddressSpend, IndexedAddress};
 pub use best_block::BestBlock;
 pub use block_ancestors::BlockAncestors;
 pub use block_chain::{BlockChain, ForkChain, Forkable};
@@ -39,14 +42,15 @@ pub use block_iterator::BlockIterator;
 pub use block_origin::{BlockOrigin, SideChainOrigin};
 pub use block_provider::{BlockHeaderProvider, BlockProvider};
 pub use block_ref::BlockRef;
+pub use column_stat::ColumnStat;
 pub use duplex_store::{DuplexTransactionOutputProvider, NoopStore};
 pub use error::Error;
 pub use nullifier_tracker::NullifierTracker;
 pub use store::{AsSubstore, CanonStore, SharedStore, Store};
 pub use transaction_meta::TransactionMeta;
 pub use transaction_provider::{
-    CachedTransactionOutputProvider, TransactionMetaProvider, TransactionOutputProvider,
-    TransactionProvider,
+    CachedTransactionOutputProvider, TransactionIndexProvider, TransactionMetaProvider,
+    TransactionOutputProvider, TransactionProvider,
 };
 pub use tree_state::{
     Dim as TreeDim, SaplingTreeState, SproutTreeState, TreeState, H32 as H32TreeDim,
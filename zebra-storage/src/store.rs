@@ -1,8 +1,11 @@
+use hash::H256;
+use std::collections::HashSet;
 use std::sync::Arc;
-use zebra_chain::IndexedBlockHeader;
+use zebra_chain::{IndexedBlockHeader, OutPoint, TransactionOutput};
 use {
-    BestBlock, BlockChain, BlockHeaderProvider, BlockProvider, Forkable, NullifierTracker,
-    TransactionMetaProvider, TransactionOutputProvider, TransactionProvider, TreeStateProvider,
+    AddressIndexProvider, BestBlock, BlockChain, BlockHeaderProvider, BlockProvider, BlockRef,
+    ColumnStat, Forkable, NullifierTracker, TransactionIndexProvider, TransactionMetaProvider,
+    TransactionOutputProvider, TransactionProvider, TreeStateProvider,
 };
 
 pub trait CanonStore: Store + Forkable {
@@ -16,6 +19,103 @@ pub trait Store: AsSubstore {
 
     /// get best header
     fn best_header(&self) -> IndexedBlockHeader;
+
+    /// get size statistics of the database columns
+    fn column_stats(&self) -> Vec<ColumnStat>;
+
+    /// get read-only snapshot of the storage, which is not affected by subsequent writes.
+    /// Snapshot doesn't block writes, but makes them slower, so it should be dropped asap
+    fn snapshot<'a>(&'a self) -> Box<Store + 'a>;
+
+    /// get the lowest common ancestor of two blocks by walking both chains' `previous_header_hash` links.
+    /// returns None if any of the blocks is unknown or if chains have no common ancestor
+    fn fork_point(&self, a: &H256, b: &H256) -> Option<H256> {
+        if !self.contains_block(BlockRef::Hash(a.clone()))
+            || !self.contains_block(BlockRef::Hash(b.clone()))
+        {
+            return None;
+        }
+
+        // chains are walked in turns, so that the walk is bounded by the longest branch
+        // (and not by the height of the chain). Every block is visited at most once,
+        // so the walk terminates even if corrupted data contains cycles
+        let mut visited = [HashSet::new(), HashSet::new()];
+        let mut next = [Some(a.clone()), Some(b.clone())];
+        while next[0].is_some() || next[1].is_some() {
+            for side in 0..2 {
+                let hash = match next[side].take() {
+                    Some(hash) => hash,
+                    None => continue,
+                };
+
+                if visited[1 - side].contains(&hash) {
+                    return Some(hash);
+                }
+
+                if !visited[side].insert(hash.clone()) {
+                    continue;
+                }
+
+                next[side] = self
+                    .block_header(BlockRef::Hash(hash))
+                    .map(|header| header.raw.previous_header_hash);
+            }
+        }
+
+        None
+    }
+
+    /// scan unspent outputs of the canon chain transactions, starting from block `from_block`.
+    /// `callback` is called for every unspent output, which `script_pubkey` is accepted by `matcher`.
+    /// If `callback` returns false, the scan is interrupted and the number of the block to resume
+    /// the scan from is returned (outputs of this block could be reported again).
+    /// returns None if the scan has reached the best block
+    fn scan_utxos(
+        &self,
+        from_block: u32,
+        matcher: &Fn(&[u8]) -> bool,
+        callback: &mut FnMut(OutPoint, TransactionOutput) -> bool,
+    ) -> Option<u32> {
+        let best_block_number = self.best_block().number;
+        for block_number in from_block..best_block_number + 1 {
+            let block_hash = match self.block_hash(block_number) {
+                Some(block_hash) => block_hash,
+                None => break,
+            };
+
+            for transaction_hash in self.block_transaction_hashes(BlockRef::Hash(block_hash)) {
+                // duplicate transactions (bip30) are only reported at the block their meta is pointing to
+                let meta = match self.transaction_meta(&transaction_hash) {
+                    Some(meta) => meta,
+                    None => continue,
+                };
+                if meta.height() != block_number || meta.is_fully_spent() {
+                    continue;
+                }
+
+                let transaction = match self.transaction(&transaction_hash) {
+                    Some(transaction) => transaction,
+                    None => continue,
+                };
+
+                for (index, output) in transaction.raw.outputs.into_iter().enumerate() {
+                    if meta.is_spent(index) != Some(false) || !matcher(&output.script_pubkey) {
+                        continue;
+                    }
+
+                    let outpoint = OutPoint {
+                        hash: transaction_hash.clone(),
+                        index: index as u32,
+                    };
+                    if !callback(outpoint, output) {
+                        return Some(block_number);
+                    }
+                }
+            }
+        }
+
+        None
+    }
 }
 
 /// Allows casting Arc<Store> to reference to any substore type
@@ -26,6 +126,8 @@ pub trait AsSubstore:
     + TransactionMetaProvider
     + TransactionOutputProvider
     + NullifierTracker
+    + AddressIndexProvider
+    + TransactionIndexProvider
 {
     fn as_block_provider(&self) -> &BlockProvider;
 
@@ -40,6 +142,10 @@ pub trait AsSubstore:
     fn as_nullifier_tracker(&self) -> &NullifierTracker;
 
     fn as_tree_state_provider(&self) -> &TreeStateProvider;
+
+    fn as_address_index_provider(&self) -> &AddressIndexProvider;
+
+    fn as_transaction_index_provider(&self) -> &TransactionIndexProvider;
 }
 
 impl<T> AsSubstore for T
@@ -50,7 +156,9 @@ where
         + TransactionMetaProvider
         + TransactionOutputProvider
         + NullifierTracker
-        + TreeStateProvider,
+        + TreeStateProvider
+        + AddressIndexProvider
+        + TransactionIndexProvider,
 {
     fn as_block_provider(&self) -> &BlockProvider {
         &*self
@@ -79,6 +187,14 @@ where
     fn as_tree_state_provider(&self) -> &TreeStateProvider {
         &*self
     }
+
+    fn as_address_index_provider(&self) -> &AddressIndexProvider {
+        &*self
+    }
+
+    fn as_transaction_index_provider(&self) -> &TransactionIndexProvider {
+        &*self
+    }
 }
 
 pub type SharedStore = Arc<CanonStore + Send + Sync>;
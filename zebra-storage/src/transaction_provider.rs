@@ -19,6 +19,17 @@ pub trait TransactionProvider {
     fn transaction(&self, hash: &H256) -> Option<IndexedTransaction>;
 }
 
+/// Optional index of blocks, containing transactions.
+pub trait TransactionIndexProvider: Send + Sync {
+    /// Returns true if the transaction index is maintained by the storage.
+    fn is_transaction_index_enabled(&self) -> bool;
+
+    /// Returns hash of the block, containing given transaction. Canonical block is preferred
+    /// if the transaction is included in several blocks.
+    /// Always returns None if the transaction index is disabled.
+    fn transaction_block_hash(&self, hash: &H256) -> Option<H256>;
+}
+
 /// Should be used to get canon chain transaction outputs.
 pub trait TransactionOutputProvider: Send + Sync {
     /// Returns transaction output.
@@ -28,6 +39,15 @@ pub trait TransactionOutputProvider: Send + Sync {
         transaction_index: usize,
     ) -> Option<TransactionOutput>;
 
+    /// Returns transaction outputs, referenced by given outpoints.
+    /// Outputs are read one-by-one, unless the provider supports batched reads.
+    fn transaction_outputs(&self, outpoints: &[OutPoint]) -> Vec<Option<TransactionOutput>> {
+        outpoints
+            .iter()
+            .map(|outpoint| self.transaction_output(outpoint, ::std::usize::MAX))
+            .collect()
+    }
+
     /// Returns true if we know that output is double spent.
     fn is_spent(&self, outpoint: &OutPoint) -> bool;
 }
@@ -56,6 +76,27 @@ impl<'a> CachedTransactionOutputProvider<'a> {
             cached_outputs: RwLock::new(HashMap::new()),
         }
     }
+
+    /// Read all not-yet-cached outputs, referenced by given outpoints, with a single batch
+    /// and cache them.
+    pub fn prefetch(&self, outpoints: &[OutPoint]) {
+        let missing_outpoints = {
+            let cached_outputs = self.cached_outputs.read();
+            outpoints
+                .iter()
+                .filter(|outpoint| !cached_outputs.contains_key(outpoint))
+                .cloned()
+                .collect::<Vec<_>>()
+        };
+        if missing_outpoints.is_empty() {
+            return;
+        }
+
+        let outputs = self.backend.transaction_outputs(&missing_outpoints);
+        self.cached_outputs
+            .write()
+            .extend(missing_outpoints.into_iter().zip(outputs.into_iter()));
+    }
 }
 
 impl<'a> TransactionOutputProvider for CachedTransactionOutputProvider<'a> {
@@ -82,3 +123,86 @@ impl<'a> TransactionOutputProvider for CachedTransactionOutputProvider<'a> {
         self.backend.is_spent(outpoint)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{CachedTransactionOutputProvider, TransactionOutputProvider};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use zebra_chain::{OutPoint, TransactionOutput};
+
+    #[derive(Default)]
+    struct CountingOutputProvider {
+        single_reads: AtomicUsize,
+        batch_reads: AtomicUsize,
+    }
+
+    impl TransactionOutputProvider for CountingOutputProvider {
+        fn transaction_output(
+            &self,
+            outpoint: &OutPoint,
+            _transaction_index: usize,
+        ) -> Option<TransactionOutput> {
+            self.single_reads.fetch_add(1, Ordering::SeqCst);
+            test_output(outpoint)
+        }
+
+        fn transaction_outputs(&self, outpoints: &[OutPoint]) -> Vec<Option<TransactionOutput>> {
+            self.batch_reads.fetch_add(1, Ordering::SeqCst);
+            outpoints.iter().map(test_output).collect()
+        }
+
+        fn is_spent(&self, _outpoint: &OutPoint) -> bool {
+            false
+        }
+    }
+
+    /// Outputs with zero index are unknown.
+    fn test_output(outpoint: &OutPoint) -> Option<TransactionOutput> {
+        match outpoint.index {
+            0 => None,
+            index => Some(TransactionOutput {
+                value: index as u64,
+                script_pubkey: Default::default(),
+            }),
+        }
+    }
+
+    fn outpoints() -> Vec<OutPoint> {
+        (0..10)
+            .map(|index| OutPoint {
+                hash: 1.into(),
+                index: index,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn lazy_cache_misses_on_every_first_read() {
+        let backend = CountingOutputProvider::default();
+        let cache = CachedTransactionOutputProvider::new(&backend);
+        for outpoint in outpoints() {
+            cache.transaction_output(&outpoint, 0);
+        }
+
+        assert_eq!(backend.single_reads.load(Ordering::SeqCst), 10);
+        assert_eq!(backend.batch_reads.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn prefetched_cache_hits_on_every_read() {
+        let backend = CountingOutputProvider::default();
+        let cache = CachedTransactionOutputProvider::new(&backend);
+        cache.prefetch(&outpoints());
+        for outpoint in outpoints() {
+            assert_eq!(
+                cache.transaction_output(&outpoint, 0),
+                test_output(&outpoint)
+            );
+        }
+        // already cached outputs are not read again
+        cache.prefetch(&outpoints());
+
+        assert_eq!(backend.single_reads.load(Ordering::SeqCst), 0);
+        assert_eq!(backend.batch_reads.load(Ordering::SeqCst), 1);
+    }
+}
@@ -271,6 +271,59 @@ impl<D: Dim, H: TreeHash> TreeState<D, H> {
     pub fn empty_root() -> H256 {
         H::empty()[D::HEIGHT]
     }
+
+    /// Computes authentication path (sibling hashes from leaf to root) of the commitment at
+    /// given position in the tree, built from given commitments.
+    ///
+    /// Tree state only keeps the frontier of the tree (D::HEIGHT hashes at most), which is
+    /// enough to append new commitments and to compute the root, but not to build a witness
+    /// for an arbitrary commitment. Instead of storing all tree nodes, the path is recomputed
+    /// from all commitments of the tree, which costs O(commitments.len()) hash combinations.
+    /// Returns None if position is out of commitments range, or if commitments don't fit the tree.
+    pub fn authentication_path(commitments: &[H256], position: usize) -> Option<Vec<H256>> {
+        if position >= commitments.len() || commitments.len() as u64 > 1u64 << D::HEIGHT {
+            return None;
+        }
+
+        let mut path = Vec::with_capacity(D::HEIGHT);
+        let mut level = commitments.to_vec();
+        let mut position = position;
+        for depth in 0..D::HEIGHT {
+            let sibling = level.get(position ^ 1).unwrap_or(&H::empty()[depth]);
+            path.push(*sibling);
+
+            level = level
+                .chunks(2)
+                .map(|pair| {
+                    let right = pair.get(1).unwrap_or(&H::empty()[depth]);
+                    H::combine(&pair[0], right, depth)
+                })
+                .collect();
+            position >>= 1;
+        }
+
+        Some(path)
+    }
+
+    /// Computes root of the tree, given commitment, its position and authentication path.
+    pub fn root_from_authentication_path(
+        commitment: &H256,
+        position: usize,
+        path: &[H256],
+    ) -> H256 {
+        let mut position = position;
+        let mut root = *commitment;
+        for (depth, sibling) in path.iter().enumerate() {
+            root = if position & 1 == 0 {
+                H::combine(&root, sibling, depth)
+            } else {
+                H::combine(sibling, &root, depth)
+            };
+            position >>= 1;
+        }
+
+        root
+    }
 }
 
 pub type SproutTreeState = TreeState<H29, SproutTreeHash>;
@@ -608,6 +661,47 @@ mod tests {
             .is_err());
     }
 
+    #[test]
+    fn authentication_path_leads_to_root() {
+        let mut sprout_tree = TestSproutTreeState::new();
+        let mut sapling_tree = TestSaplingTreeState::new();
+        for (i, commitment) in TEST_COMMITMENTS.iter().enumerate() {
+            sprout_tree.append(*commitment).unwrap();
+            sapling_tree.append(*commitment).unwrap();
+
+            let commitments = &TEST_COMMITMENTS[..i + 1];
+            for position in 0..commitments.len() {
+                let path = TestSproutTreeState::authentication_path(commitments, position).unwrap();
+                assert_eq!(path.len(), H4::HEIGHT);
+                assert_eq!(
+                    TestSproutTreeState::root_from_authentication_path(
+                        &commitments[position],
+                        position,
+                        &path
+                    ),
+                    sprout_tree.root()
+                );
+
+                let path =
+                    TestSaplingTreeState::authentication_path(commitments, position).unwrap();
+                assert_eq!(
+                    TestSaplingTreeState::root_from_authentication_path(
+                        &commitments[position],
+                        position,
+                        &path
+                    ),
+                    sapling_tree.root()
+                );
+            }
+
+            // commitment isn't in the tree
+            assert_eq!(
+                TestSproutTreeState::authentication_path(commitments, commitments.len()),
+                None
+            );
+        }
+    }
+
     #[test]
     fn serde() {
         let mut tree = TestSproutTreeState::new();
@@ -747,4 +841,49 @@ mod tests {
             assert_eq!(actual_root, *expected_root);
         }
     }
+    pub struct H8;
+
+    impl Dim for H8 {
+        const HEIGHT: usize = 8;
+    }
+
+    /// Computes root of the tree with given commitments, combining all nodes level by level.
+    fn naive_root<D: Dim, H: TreeHash>(commitments: &[H256]) -> H256 {
+        let mut level = commitments.to_vec();
+        for depth in 0..D::HEIGHT {
+            level = level
+                .chunks(2)
+                .map(|pair| {
+                    let right = pair.get(1).unwrap_or(&H::empty()[depth]);
+                    H::combine(&pair[0], right, depth)
+                })
+                .collect();
+        }
+
+        level.get(0).cloned().unwrap_or(H::empty()[D::HEIGHT])
+    }
+
+    #[test]
+    fn frontier_root_matches_naive_root() {
+        let mut sprout_tree = TreeState::<H8, SproutTreeHash>::new();
+        let mut commitments = Vec::new();
+        for i in 0..1usize << H8::HEIGHT {
+            let commitment = H256::from(i as u8);
+            sprout_tree.append(commitment).unwrap();
+            commitments.push(commitment);
+            assert_eq!(
+                sprout_tree.root(),
+                naive_root::<H8, SproutTreeHash>(&commitments)
+            );
+        }
+
+        let mut sapling_tree = TestSaplingTreeState::new();
+        for i in 0..TEST_COMMITMENTS.len() {
+            sapling_tree.append(TEST_COMMITMENTS[i]).unwrap();
+            assert_eq!(
+                sapling_tree.root(),
+                naive_root::<H4, SaplingTreeHash>(&TEST_COMMITMENTS[..i + 1])
+            );
+        }
+    }
 }
@@ -10,6 +10,9 @@ pub trait TreeStateProvider: Send + Sync {
 
     fn sapling_block_root(&self, block_hash: &H256) -> Option<H256>;
 
+    /// Hash of the canonical block at given height.
+    fn canon_block_hash(&self, height: u32) -> Option<H256>;
+
     fn sprout_tree_at_block(&self, block_hash: &H256) -> Option<SproutTreeState> {
         self.sprout_block_root(block_hash)
             .and_then(|h| self.sprout_tree_at(&h))
@@ -19,4 +22,16 @@ pub trait TreeStateProvider: Send + Sync {
         self.sapling_block_root(block_hash)
             .and_then(|h| self.sapling_tree_at(&h))
     }
+
+    /// Sprout tree after applying canonical block at given height.
+    fn sprout_tree_at_height(&self, height: u32) -> Option<SproutTreeState> {
+        self.canon_block_hash(height)
+            .and_then(|h| self.sprout_tree_at_block(&h))
+    }
+
+    /// Sapling tree after applying canonical block at given height.
+    fn sapling_tree_at_height(&self, height: u32) -> Option<SaplingTreeState> {
+        self.canon_block_hash(height)
+            .and_then(|h| self.sapling_tree_at_block(&h))
+    }
 }
@@ -10,6 +10,7 @@ use synchronization_verifier::{
 use types::{PeerIndex, StorageRef};
 use utils::OrphanBlocksPool;
 use zebra_chain;
+use zebra_message::types::reject::RejectCode;
 use zebra_network::ConsensusParams;
 use zebra_primitives::hash::H256;
 use zebra_storage;
@@ -99,6 +100,11 @@ impl BlocksWriter {
 
         Ok(())
     }
+
+    /// Returns true if there are appended blocks, waiting for their parents.
+    pub fn has_orphaned_blocks(&self) -> bool {
+        self.orphaned_blocks_pool.len() != 0
+    }
 }
 
 impl BlocksWriterSink {
@@ -148,7 +154,7 @@ impl TransactionVerificationSink for BlocksWriterSink {
         unreachable!("not intended to verify transactions")
     }
 
-    fn on_transaction_verification_error(&self, _err: &str, _hash: &H256) {
+    fn on_transaction_verification_error(&self, _err: &str, _code: RejectCode, _hash: &H256) {
         unreachable!("not intended to verify transactions")
     }
 }
@@ -185,6 +191,7 @@ mod tests {
         VerificationParameters {
             verification_level: VerificationLevel::FULL,
             verification_edge: 0u8.into(),
+            verification_threshold: None,
         }
     }
 
@@ -296,6 +303,7 @@ mod tests {
             VerificationParameters {
                 verification_level: VerificationLevel::NO_VERIFICATION,
                 verification_edge: 0u8.into(),
+                verification_threshold: None,
             },
         );
         assert_eq!(blocks_target.append_block(b1.into()), Ok(()));
@@ -194,6 +194,21 @@ impl InboundSyncConnection for InboundConnection {
     fn on_notfound(&self, message: types::NotFound) {
         self.node.on_notfound(self.peer_index, message);
     }
+
+    fn on_reject(&self, message: types::Reject) {
+        debug!(
+            target: "sync",
+            "Peer#{} has rejected '{}' message{}: {:?} {}",
+            self.peer_index,
+            message.message,
+            message
+                .data
+                .map(|hash| format!(" with {}", hash.to_reversed_str()))
+                .unwrap_or_default(),
+            message.ccode,
+            message.reason
+        );
+    }
 }
 
 #[cfg(test)]
@@ -326,7 +341,11 @@ pub mod tests {
                 .entry("notfound".to_owned())
                 .or_insert(0) += 1;
         }
+        fn send_reject(&self, _message: &types::Reject) {
+            *self.messages.lock().entry("reject".to_owned()).or_insert(0) += 1;
+        }
         fn ignored(&self, _id: RequestId) {}
+        fn note_misbehavior(&self, _score: u32) {}
         fn close(&self) {}
     }
 }
@@ -38,10 +38,18 @@ mod synchronization_verifier;
 mod types;
 mod utils;
 
+pub use local_node::{SubmitBlockResult, TestTransactionResult};
 pub use types::LocalNodeRef;
+pub use types::MemoryPoolRef;
 pub use types::PeersRef;
+pub use types::SynchronizationStateRef;
+pub use utils::{
+    build_merkle_block, parse_merkle_block, ParsedPartialMerkleTree, SynchronizationState,
+};
 
 use parking_lot::RwLock;
+use std::cmp::min;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
 use zebra_network::{ConsensusParams, Network};
 use zebra_primitives::hash::H256;
@@ -66,6 +74,83 @@ pub struct VerificationParameters {
     /// Blocks verification edge: all blocks before this are validated using verification_level.
     /// All blocks after this (inclusive) are validated using VerificationLevel::Full level.
     pub verification_edge: H256,
+    /// Blocks verification threshold. When set, verification_edge is ignored: all blocks below
+    /// threshold height are validated using verification_level. All other blocks are validated
+    /// using VerificationLevel::Full level.
+    pub verification_threshold: Option<VerificationThreshold>,
+}
+
+/// Default number of blocks near the best known header, which are always fully verified (~2 days).
+pub const DEFAULT_VERIFICATION_TIP_DISTANCE: u32 = 2304;
+
+/// Dynamically-updated blocks verification threshold (like `assumevalid`).
+/// Clones are sharing the same threshold, so it could be updated while the node is running.
+#[derive(Debug, Clone)]
+pub struct VerificationThreshold {
+    /// Threshold height.
+    height: Arc<AtomicUsize>,
+    /// Blocks, which are closer than this to the best known header, are fully verified.
+    tip_distance: u32,
+    /// Synchronization state to read the best known header height from.
+    sync_state: Option<SynchronizationStateRef>,
+    /// True if the last block has been validated using VerificationLevel::Full level.
+    full_verification_selected: Arc<AtomicBool>,
+}
+
+impl VerificationThreshold {
+    /// Create new verification threshold.
+    pub fn new(height: u32, tip_distance: u32) -> Self {
+        VerificationThreshold {
+            height: Arc::new(AtomicUsize::new(height as usize)),
+            tip_distance: tip_distance,
+            sync_state: None,
+            full_verification_selected: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Get threshold height.
+    pub fn height(&self) -> u32 {
+        self.height.load(Ordering::Relaxed) as u32
+    }
+
+    /// Update threshold height. Blocks, which are already verified, are not affected.
+    pub fn set_height(&self, height: u32) {
+        self.height.store(height as usize, Ordering::Relaxed);
+    }
+
+    /// Get height, starting from which blocks are fully verified. It is the threshold height,
+    /// lowered so that blocks near the best known header are always fully verified.
+    pub fn effective_height(&self) -> u32 {
+        match self.sync_state {
+            Some(ref sync_state) => min(
+                self.height(),
+                sync_state
+                    .best_headers_height()
+                    .saturating_sub(self.tip_distance),
+            ),
+            None => self.height(),
+        }
+    }
+
+    /// Returns true if the last block has been validated using VerificationLevel::Full level.
+    pub fn is_full_verification_selected(&self) -> bool {
+        self.full_verification_selected.load(Ordering::Relaxed)
+    }
+
+    /// Follow the best known header height of given synchronization state.
+    fn with_sync_state(mut self, sync_state: SynchronizationStateRef) -> Self {
+        self.sync_state = Some(sync_state);
+        self
+    }
+
+    /// Select verification mode for the block at given height.
+    fn select_full_verification(&self, height: Option<u32>) -> bool {
+        // blocks with unknown parent are always fully verified
+        let is_full_verification = height.map_or(true, |height| height >= self.effective_height());
+        self.full_verification_selected
+            .store(is_full_verification, Ordering::Relaxed);
+        is_full_verification
+    }
 }
 
 /// Synchronization events listener
@@ -74,6 +159,34 @@ pub trait SyncListener: Send + 'static {
     fn synchronization_state_switched(&self, is_synchronizing: bool);
     /// Called when new best storage block is inserted
     fn best_storage_block_inserted(&self, block_hash: &H256);
+    /// Called when block is removed from the main chain during reorganization.
+    /// Blocks are disconnected one-by-one, starting from the old best block. All `block_disconnected`
+    /// calls for a reorganization precede the `best_storage_block_inserted` call for the new best block.
+    fn block_disconnected(&self, _block_hash: &H256) {}
+    /// Called when block is added to the main chain. Blocks are connected one-by-one, starting from
+    /// the oldest block. The last `block_connected` call precedes the `best_storage_block_inserted` call.
+    fn block_connected(&self, _block_hash: &H256) {}
+    /// Called once per reorganization with hashes of all blocks, removed from the main chain
+    /// (old best block goes first) and all blocks, added to the main chain (new best block goes last)
+    fn chain_reorganized(&self, _disconnected: &[H256], _connected: &[H256]) {}
+}
+
+/// Replays canonical blocks in [start_height; stop_height] range through the listener by calling
+/// `best_storage_block_inserted` for every block (in order). Blocks above the best block are ignored.
+/// Caller must ensure that no blocks are (de)canonized during the rescan. `LocalNode::rescan_blockchain`
+/// rescans blocks in batches, holding the synchronization client core lock (which blocks new blocks
+/// insertion) while rescanning every batch.
+pub fn rescan_blockchain(
+    db: &zebra_storage::SharedStore,
+    listener: &SyncListener,
+    start_height: u32,
+    stop_height: u32,
+) {
+    let blocks = zebra_storage::BlockIterator::new(start_height, 1, db.as_block_header_provider())
+        .take((stop_height + 1).saturating_sub(start_height) as usize);
+    for (_, header) in blocks {
+        listener.best_storage_block_inserted(&header.hash);
+    }
 }
 
 /// Create blocks writer.
@@ -98,6 +211,9 @@ pub fn create_local_sync_node(
     db: zebra_storage::SharedStore,
     peers: PeersRef,
     verification_params: VerificationParameters,
+    relay_transactions: bool,
+    memory_pool_max_size: usize,
+    min_relay_fee_rate: u64,
 ) -> LocalNodeRef {
     use local_node::LocalNode as SyncNode;
     use synchronization_chain::Chain as SyncChain;
@@ -109,17 +225,27 @@ pub fn create_local_sync_node(
     use synchronization_server::ServerImpl;
     use synchronization_verifier::AsyncVerifier;
     use types::SynchronizationStateRef;
-    use utils::SynchronizationState;
-    use zebra_miner::MemoryPool;
+    use utils::{FeeEstimatorListener, SynchronizationState};
+    use zebra_miner::{FeeEstimator, MemoryPool};
 
     let network = consensus.network;
     let sync_client_config = SynchronizationConfig {
         // during regtests, peer is providing us with bad blocks => we shouldn't close connection because of this
         close_connection_on_bad_block: network != Network::Regtest,
+        relay_transactions: relay_transactions,
     };
 
-    let memory_pool = Arc::new(RwLock::new(MemoryPool::new()));
+    let mut memory_pool = MemoryPool::with_max_size(memory_pool_max_size);
+    memory_pool.set_min_relay_fee_rate(min_relay_fee_rate);
+    let memory_pool = Arc::new(RwLock::new(memory_pool));
+    let fee_estimator = Arc::new(RwLock::new(FeeEstimator::default()));
     let sync_state = SynchronizationStateRef::new(SynchronizationState::with_storage(db.clone()));
+    let verification_params = VerificationParameters {
+        verification_threshold: verification_params
+            .verification_threshold
+            .map(|threshold| threshold.with_sync_state(sync_state.clone())),
+        ..verification_params
+    };
     let sync_chain = SyncChain::new(db.clone(), memory_pool.clone());
 
     let light_chain_verifier = Arc::new(ChainVerifier::new(db.clone(), consensus.clone()));
@@ -161,15 +287,22 @@ pub fn create_local_sync_node(
         light_verifier,
         heavy_verifier,
     );
-    Arc::new(SyncNode::new(
+    let local_node = Arc::new(SyncNode::new(
         consensus,
-        db,
-        memory_pool,
+        db.clone(),
+        memory_pool.clone(),
+        fee_estimator.clone(),
         peers,
         sync_state,
         sync_client,
         sync_server,
-    ))
+    ));
+    local_node.install_sync_listener(Box::new(FeeEstimatorListener::new(
+        db,
+        memory_pool,
+        fee_estimator,
+    )));
+    local_node
 }
 
 /// Create inbound synchronization connections factory for given local sync node.
@@ -1,22 +1,39 @@
 use futures::{finished, lazy};
 use parking_lot::{Condvar, Mutex};
+use std::collections::HashSet;
+use std::io;
+use std::path::Path;
 use std::sync::Arc;
+use std::time::Duration;
 use synchronization_client::Client;
 use synchronization_peers::{BlockAnnouncementType, TransactionAnnouncementType};
 use synchronization_server::{Server, ServerTask};
 use synchronization_verifier::TransactionVerificationSink;
 use time;
 use types::{
-    BlockHeight, ClientRef, MemoryPoolRef, PeerIndex, PeersRef, RequestId, ServerRef, StorageRef,
-    SyncListenerRef, SynchronizationStateRef,
+    BlockHeight, ClientRef, FeeEstimatorRef, MemoryPoolRef, PeerIndex, PeersRef, RequestId,
+    ServerRef, StorageRef, SyncListenerRef, SynchronizationStateRef,
+};
+use utils::{BestBlockWaiter, MemoryPoolTransactionOutputProvider};
+use zebra_chain::{
+    IndexedBlock, IndexedBlockHeader, IndexedTransaction, OutPoint, TransactionOutput,
 };
-use zebra_chain::{IndexedBlock, IndexedBlockHeader, IndexedTransaction};
 use zebra_keys::Address;
 use zebra_message::types;
+use zebra_message::types::reject::RejectCode;
 use zebra_miner::BlockAssembler;
 use zebra_miner::BlockTemplate;
+use zebra_miner::{
+    transaction_fee, FeeCalculator, MemoryPool, MemoryPoolEntryInformation, MemoryPoolInformation,
+};
 use zebra_network::ConsensusParams;
 use zebra_primitives::hash::H256;
+use zebra_storage::{
+    BlockOrigin, BlockRef, DuplexTransactionOutputProvider, TransactionOutputProvider,
+};
+use zebra_verification::{
+    BackwardsCompatibleChainVerifier as ChainVerifier, TransactionError, VerificationLevel, Verify,
+};
 
 /// Local synchronization node
 pub struct LocalNode<U: Server, V: Client> {
@@ -26,6 +43,8 @@ pub struct LocalNode<U: Server, V: Client> {
     storage: StorageRef,
     /// Memory pool reference
     memory_pool: MemoryPoolRef,
+    /// Fee estimator reference
+    fee_estimator: FeeEstimatorRef,
     /// Synchronization peers
     peers: PeersRef,
     /// Shared synchronization state
@@ -34,6 +53,32 @@ pub struct LocalNode<U: Server, V: Client> {
     client: ClientRef<V>,
     /// Synchronization server
     server: ServerRef<U>,
+    /// Best block change waiter
+    best_block_waiter: BestBlockWaiter,
+}
+
+/// Result of block submission
+#[derive(Debug, Clone, PartialEq)]
+pub enum SubmitBlockResult {
+    /// Block is valid and has been scheduled for insertion into the best chain
+    Accepted,
+    /// Block is already known
+    Duplicate,
+    /// Block is valid, but it is not extending the best chain
+    Inconclusive,
+    /// Block has failed verification
+    Rejected(String),
+}
+
+/// Result of the memory pool acceptance test
+#[derive(Debug, Clone, PartialEq)]
+pub struct TestTransactionResult {
+    /// Transaction hash
+    pub hash: H256,
+    /// Transaction size
+    pub size: usize,
+    /// Transaction fee if transaction would be accepted, or the reject reason otherwise
+    pub result: Result<u64, String>,
 }
 
 /// Transaction accept verification sink
@@ -58,19 +103,25 @@ where
         consensus: ConsensusParams,
         storage: StorageRef,
         memory_pool: MemoryPoolRef,
+        fee_estimator: FeeEstimatorRef,
         peers: PeersRef,
         state: SynchronizationStateRef,
         client: ClientRef<V>,
         server: ServerRef<U>,
     ) -> Self {
+        let best_block_waiter = BestBlockWaiter::new(storage.best_block().hash);
+        client.install_sync_listener(Box::new(best_block_waiter.clone()));
+
         LocalNode {
             consensus: consensus,
             storage: storage,
             memory_pool: memory_pool,
+            fee_estimator: fee_estimator,
             peers: peers,
             state: state,
             client: client,
             server: server,
+            best_block_waiter: best_block_waiter,
         }
     }
 
@@ -79,6 +130,11 @@ where
         self.state.clone()
     }
 
+    /// Return shared reference to memory pool.
+    pub fn memory_pool(&self) -> MemoryPoolRef {
+        self.memory_pool.clone()
+    }
+
     /// When new peer connects to the node
     pub fn on_connect(&self, peer_index: PeerIndex, peer_name: String, version: types::Version) {
         trace!(target: "sync", "Starting new sync session with peer#{}: {}", peer_index, peer_name);
@@ -127,6 +183,40 @@ where
         self.client.on_block(peer_index, block);
     }
 
+    /// When block is submitted locally (i.e. by miner)
+    pub fn submit_block(&self, block: IndexedBlock) -> SubmitBlockResult {
+        trace!(target: "sync", "Block submitted. Block hash: {}", block.header.hash.to_reversed_str());
+
+        if self
+            .storage
+            .contains_block(BlockRef::Hash(block.header.hash.clone()))
+        {
+            return SubmitBlockResult::Duplicate;
+        }
+
+        if self.client.is_invalidated_block(&block.header) {
+            return SubmitBlockResult::Rejected("Block has been invalidated".into());
+        }
+
+        let verifier = ChainVerifier::new(self.storage.clone(), self.consensus.clone());
+        if let Err(err) = verifier.verify(VerificationLevel::FULL, &block) {
+            return SubmitBlockResult::Rejected(format!("{:?}", err));
+        }
+
+        let is_side_chain_block = match self.storage.block_origin(&block.header) {
+            Ok(BlockOrigin::SideChain(_)) => true,
+            _ => false,
+        };
+
+        self.client.on_submitted_block(block);
+
+        if is_side_chain_block {
+            SubmitBlockResult::Inconclusive
+        } else {
+            SubmitBlockResult::Accepted
+        }
+    }
+
     /// When notfound is received
     pub fn on_notfound(&self, peer_index: PeerIndex, message: types::NotFound) {
         trace!(target: "sync", "Got `notfound` message from peer#{}", peer_index);
@@ -179,7 +269,10 @@ where
     /// When peer updates bloom filter for connection
     pub fn on_filteradd(&self, peer_index: PeerIndex, message: types::FilterAdd) {
         trace!(target: "sync", "Got `filteradd` message from peer#{}", peer_index);
-        self.peers.update_bloom_filter(peer_index, message);
+        if !self.peers.update_bloom_filter(peer_index, message) {
+            self.peers
+                .misbehaving(peer_index, "Got 'filteradd' message without loaded filter");
+        }
     }
 
     /// When peer removes bloom filter from connection
@@ -221,6 +314,116 @@ where
         sink_data.wait()
     }
 
+    /// Check if transactions would be accepted to the memory pool, without accepting them.
+    /// Transactions are checked as a package, so every transaction could spend outputs of
+    /// previous package transactions. Neither memory pool, nor storage are modified.
+    /// `max_fee_rate` is the maximal fee rate (in satoshis per 1000 bytes).
+    pub fn test_transactions(
+        &self,
+        transactions: Vec<IndexedTransaction>,
+        max_fee_rate: Option<u64>,
+    ) -> Vec<TestTransactionResult> {
+        let verifier = ChainVerifier::new(self.storage.clone(), self.consensus.clone());
+        let height = self.storage.best_block().number + 1;
+        let time = time::get_time().sec as u32;
+        // memory pool lock isn't held during verification: required data is read when needed
+        let min_relay_fee_rate = self.memory_pool.read().min_relay_fee_rate();
+
+        // accepted package transactions are kept in the separate memory pool
+        let mut package = MemoryPool::new();
+        package.set_min_relay_fee_rate(min_relay_fee_rate);
+        let mut package_sprout_nullifiers = HashSet::new();
+        let mut package_sapling_nullifiers = HashSet::new();
+        let mut results = Vec::with_capacity(transactions.len());
+        for transaction in transactions {
+            let hash = transaction.hash.clone();
+            let size = transaction.raw.total_size();
+            let sprout_nullifiers = transaction
+                .raw
+                .join_split
+                .iter()
+                .flat_map(|join_split| join_split.descriptions.iter())
+                .flat_map(|description| description.nullifiers.iter())
+                .map(|nullifier| H256::from(&nullifier[..]))
+                .collect::<Vec<_>>();
+            let sapling_nullifiers = transaction
+                .raw
+                .sapling
+                .iter()
+                .flat_map(|sapling| sapling.spends.iter())
+                .map(|spend| H256::from(&spend.nullifier[..]))
+                .collect::<Vec<_>>();
+
+            let is_in_memory_pool = self.memory_pool.read().contains(&hash);
+            let result = if is_in_memory_pool || package.contains(&hash) {
+                Err("Transaction is already in memory pool".into())
+            } else if let Some(nullifier) = sprout_nullifiers
+                .iter()
+                .find(|nullifier| package_sprout_nullifiers.contains(*nullifier))
+            {
+                Err(format!(
+                    "{:?}",
+                    TransactionError::JoinSplitDeclared(nullifier.clone())
+                ))
+            } else if let Some(nullifier) = sapling_nullifiers
+                .iter()
+                .find(|nullifier| package_sapling_nullifiers.contains(*nullifier))
+            {
+                Err(format!(
+                    "{:?}",
+                    TransactionError::SaplingDeclared(nullifier.clone())
+                ))
+            } else {
+                // memory pool outputs, spent by the transaction, are copied here
+                MemoryPoolTransactionOutputProvider::for_transaction(
+                    self.storage.clone(),
+                    &self.memory_pool,
+                    &transaction.raw,
+                )
+                .map_err(|err| format!("{:?}", err))
+                .and_then(|pool_provider| {
+                    let fee = {
+                        let prevout_provider =
+                            DuplexTransactionOutputProvider::new(&package, &pool_provider);
+                        verifier
+                            .verify_mempool_transaction(
+                                self.storage.as_block_header_provider(),
+                                &prevout_provider,
+                                height,
+                                time,
+                                &transaction,
+                                min_relay_fee_rate,
+                            )
+                            .map_err(|err| format!("{:?}", err))?;
+                        let fee = transaction_fee(&prevout_provider, &transaction.raw);
+                        let min_relay_fee = package.min_relay_fee(size);
+                        check_transaction_fee(fee, size, min_relay_fee, max_fee_rate)?
+                    };
+                    Ok((pool_provider, fee))
+                })
+            };
+
+            let result = result.and_then(|(pool_provider, fee)| {
+                package
+                    .insert_verified(transaction, &FeeCalculator(&pool_provider))
+                    .map(|_| fee)
+                    .map_err(|err| format!("{:?}", err))
+            });
+            if result.is_ok() {
+                package_sprout_nullifiers.extend(sprout_nullifiers);
+                package_sapling_nullifiers.extend(sapling_nullifiers);
+            }
+
+            results.push(TestTransactionResult {
+                hash: hash,
+                size: size,
+                result: result,
+            });
+        }
+
+        results
+    }
+
     /// Get block template for mining
     pub fn get_block_template(&self, miner_address: &Address) -> Result<BlockTemplate, String> {
         let max_block_size = self.consensus.max_block_size();
@@ -239,15 +442,146 @@ where
         )
     }
 
+    /// Wait until best block is changed from `best_block` or until timeout elapses.
+    /// Returns true if best block has been changed.
+    pub fn wait_best_block_change(&self, best_block: &H256, timeout: Duration) -> bool {
+        self.best_block_waiter.wait(best_block, timeout)
+    }
+
+    /// Get information on memory pool state
+    pub fn memory_pool_information(&self) -> MemoryPoolInformation {
+        self.memory_pool.read().information()
+    }
+
+    /// Get information on all memory pool transactions
+    pub fn memory_pool_entries(&self) -> Vec<MemoryPoolEntryInformation> {
+        self.memory_pool.read().entries_information()
+    }
+
+    /// Add virtual fee delta to the memory pool transaction.
+    /// Returns false if transaction is not in the memory pool and error if accumulated delta overflows.
+    pub fn prioritise_transaction(&self, hash: &H256, fee_delta: i64) -> Result<bool, String> {
+        self.memory_pool
+            .write()
+            .prioritise_transaction(hash, fee_delta)
+    }
+
+    /// Write all memory pool transactions to the dump file at given path
+    pub fn save_memory_pool(&self, path: &Path) -> Result<(), io::Error> {
+        self.memory_pool.read().dump(path)
+    }
+
+    /// Read transactions from the memory pool dump file and insert them back to the memory pool.
+    /// Every transaction is verified again => transactions that are no longer valid (i.e. those
+    /// spending outputs, which have been spent while the node was offline) are discarded.
+    /// Returns number of transactions, accepted to the memory pool.
+    pub fn load_memory_pool(&self, path: &Path) -> Result<usize, io::Error> {
+        let mut accepted = 0;
+        for dumped in MemoryPool::load(path)? {
+            let transaction: IndexedTransaction = dumped.transaction.into();
+            match self.accept_transaction(transaction) {
+                Ok(hash) => {
+                    self.memory_pool.write().restore_dumped_metadata(
+                        &hash,
+                        dumped.time,
+                        dumped.fee_delta,
+                    );
+                    accepted += 1;
+                }
+                Err(err) => {
+                    trace!(target: "sync", "Discarding transaction from memory pool dump: {}", err)
+                }
+            }
+        }
+
+        Ok(accepted)
+    }
+
+    /// Estimate fee rate (in satoshis per byte), required for transaction to be mined within `target` blocks
+    pub fn estimate_fee_rate(&self, target: u32) -> Option<u64> {
+        self.fee_estimator.read().estimate_fee_rate(target)
+    }
+
+    /// Get unspent transaction output, either from the memory pool or from the storage
+    pub fn transaction_output(&self, prevout: &OutPoint) -> Option<TransactionOutput> {
+        let memory_pool = &*self.memory_pool.read();
+        let provider = DuplexTransactionOutputProvider::new(
+            memory_pool,
+            self.storage.as_transaction_output_provider(),
+        );
+        if provider.is_spent(prevout) {
+            return None;
+        }
+
+        provider.transaction_output(prevout, usize::max_value())
+    }
+
     /// Get best synced (and stored) block number.
     pub fn best_block_number(&self) -> BlockHeight {
         self.state.best_storage_block_height()
     }
 
+    /// Stop blocks and transactions verification. Returns when the verification in progress
+    /// is completed. Queued and new blocks and transactions are never verified.
+    pub fn stop_verification(&self) {
+        self.client.stop_verification();
+    }
+
     /// Install synchronization events listener
     pub fn install_sync_listener(&self, listener: SyncListenerRef) {
         self.client.install_sync_listener(listener);
     }
+
+    /// Replay canonical blocks, starting from given height, through all installed listeners.
+    /// Blocks are replayed in batches, so that new blocks are processed while rescanning.
+    /// Returns the rescanned heights range or None if `start_height` is above the best block height.
+    pub fn rescan_blockchain(
+        &self,
+        start_height: BlockHeight,
+    ) -> Option<(BlockHeight, BlockHeight)> {
+        self.client.rescan_blockchain(start_height)
+    }
+
+    /// Mark block as invalid. If the block is canonical, the best chain is switched to the longest
+    /// valid stored chain. The block and its descendants are rejected until the block is reconsidered.
+    pub fn invalidate_block(&self, hash: &H256) -> Result<(), String> {
+        self.client.invalidate_block(hash)
+    }
+
+    /// Remove invalid mark from the block and its descendants, switching the best chain back to
+    /// them if their chain is longer. Returns false if block has not been invalidated.
+    pub fn reconsider_block(&self, hash: &H256) -> Result<bool, String> {
+        self.client.reconsider_block(hash)
+    }
+
+    /// Prefer chain of the block over other chains of the same length, switching the best
+    /// chain if required. Preference is not persisted across restarts.
+    pub fn precious_block(&self, hash: &H256) -> Result<(), String> {
+        self.client.precious_block(hash)
+    }
+}
+
+/// Check fee of the transaction, which is about to be accepted to the memory pool
+fn check_transaction_fee(
+    fee: u64,
+    size: usize,
+    min_relay_fee: u64,
+    max_fee_rate: Option<u64>,
+) -> Result<u64, String> {
+    // zero fee transactions are never accepted to the memory pool
+    if fee == 0 {
+        return Err("Transaction pays no fee".into());
+    }
+    if fee < min_relay_fee {
+        return Err("Transaction fee is below minimal relay fee".into());
+    }
+
+    match max_fee_rate {
+        Some(max_fee_rate) if fee * 1000 / size as u64 > max_fee_rate => {
+            Err("Transaction fee rate exceeds maximal fee rate".into())
+        }
+        _ => Ok(fee),
+    }
 }
 
 impl TransactionAcceptSink {
@@ -278,7 +612,7 @@ impl TransactionVerificationSink for TransactionAcceptSink {
         self.data.waiter.notify_all();
     }
 
-    fn on_transaction_verification_error(&self, err: &str, _hash: &H256) {
+    fn on_transaction_verification_error(&self, err: &str, _code: RejectCode, _hash: &H256) {
         *self.data.result.lock() = Some(Err(err.to_owned()));
         self.data.waiter.notify_all();
     }
@@ -288,7 +622,7 @@ impl TransactionVerificationSink for TransactionAcceptSink {
 pub mod tests {
     extern crate zebra_test_data;
 
-    use super::LocalNode;
+    use super::{check_transaction_fee, LocalNode, SubmitBlockResult, TestTransactionResult};
     use parking_lot::RwLock;
     use std::iter::repeat;
     use std::sync::Arc;
@@ -303,13 +637,14 @@ pub mod tests {
     use synchronization_verifier::tests::DummyVerifier;
     use types::SynchronizationStateRef;
     use utils::SynchronizationState;
-    use zebra_chain::Transaction;
+    use zebra_chain::{IndexedBlock, Transaction};
     use zebra_db::BlockChainDatabase;
     use zebra_message::common::{InventoryType, InventoryVector};
     use zebra_message::types;
-    use zebra_miner::MemoryPool;
+    use zebra_miner::{FeeEstimator, MemoryPool, NonZeroFeeCalculator};
     use zebra_network::{ConsensusParams, Network};
     use zebra_primitives::bytes::Bytes;
+    use zebra_storage::{BlockChain, BlockRef};
 
     pub fn default_filterload() -> types::FilterLoad {
         types::FilterLoad {
@@ -343,6 +678,7 @@ pub mod tests {
         let server = Arc::new(DummyServer::new());
         let config = Config {
             close_connection_on_bad_block: true,
+            relay_transactions: true,
         };
         let client_core = SynchronizationClientCore::new(
             config,
@@ -368,6 +704,7 @@ pub mod tests {
             ConsensusParams::new(Network::Mainnet),
             storage,
             memory_pool,
+            Arc::new(RwLock::new(FeeEstimator::default())),
             sync_peers,
             sync_state,
             client,
@@ -451,4 +788,198 @@ pub mod tests {
 
         assert_eq!(executor.take_tasks(), vec![]);
     }
+
+    #[test]
+    fn local_node_submit_block_accepted() {
+        let (_, _, local_node) = create_local_node(None);
+
+        let block = zebra_test_data::block_h1();
+        let block_hash = block.hash();
+        assert_eq!(
+            local_node.submit_block(block.into()),
+            SubmitBlockResult::Accepted
+        );
+        assert!(local_node
+            .storage
+            .contains_block(BlockRef::Hash(block_hash)));
+    }
+
+    #[test]
+    fn local_node_submit_block_duplicate() {
+        let (_, _, local_node) = create_local_node(None);
+
+        let genesis = zebra_test_data::genesis();
+        assert_eq!(
+            local_node.submit_block(genesis.into()),
+            SubmitBlockResult::Duplicate
+        );
+    }
+
+    #[test]
+    fn local_node_submit_block_rejected() {
+        let (_, _, local_node) = create_local_node(None);
+
+        // parent of this block is unknown
+        let block = zebra_test_data::block_h2();
+        assert_eq!(
+            local_node.submit_block(block.into()),
+            SubmitBlockResult::Rejected("Database(UnknownParent)".into())
+        );
+    }
+
+    #[test]
+    fn local_node_invalidates_best_block() {
+        let (_, _, local_node) = create_local_node(None);
+
+        let block = zebra_test_data::block_h1();
+        let block_hash = block.hash();
+        assert_eq!(
+            local_node.submit_block(block.clone().into()),
+            SubmitBlockResult::Accepted
+        );
+        assert_eq!(local_node.best_block_number(), 1);
+
+        // chain is rolled back by one block
+        assert_eq!(local_node.invalidate_block(&block_hash), Ok(()));
+        assert_eq!(local_node.best_block_number(), 0);
+        assert_eq!(local_node.storage.block_number(&block_hash), None);
+
+        // descendant of invalidated block is refused until the block is reconsidered
+        let child = zebra_test_data::block_builder()
+            .header()
+            .parent(block_hash.clone())
+            .build()
+            .build();
+        assert_eq!(
+            local_node.submit_block(child.into()),
+            SubmitBlockResult::Rejected("Block has been invalidated".into())
+        );
+        assert_eq!(local_node.reconsider_block(&block_hash), Ok(true));
+        assert_eq!(local_node.best_block_number(), 1);
+    }
+
+    #[test]
+    fn local_node_tests_transactions_without_accepting() {
+        let (_, _, local_node) = create_local_node(None);
+
+        let genesis = zebra_test_data::genesis();
+        let tx1: Transaction = zebra_test_data::TransactionBuilder::with_output(1)
+            .add_input(&genesis.transactions[0], 0)
+            .into();
+        let tx2: Transaction = zebra_test_data::TransactionBuilder::with_output(1)
+            .add_input(&tx1, 0)
+            .into();
+        local_node
+            .memory_pool
+            .write()
+            .insert_verified(tx1.clone().into(), &NonZeroFeeCalculator)
+            .unwrap();
+
+        let results = local_node.test_transactions(vec![tx1.clone().into(), tx2.into()], None);
+        assert_eq!(
+            results[0],
+            TestTransactionResult {
+                hash: tx1.hash(),
+                size: tx1.total_size(),
+                result: Err("Transaction is already in memory pool".into()),
+            }
+        );
+        // unsigned transaction is rejected
+        assert!(results[1].result.is_err());
+        // memory pool is not modified
+        assert_eq!(local_node.memory_pool_information().transactions_count, 1);
+    }
+
+    #[test]
+    fn local_node_tests_transactions_package() {
+        let (_, _, local_node) = create_local_node(None);
+
+        // block#1 contains spendable non-coinbase output
+        let b1: IndexedBlock = zebra_test_data::block_builder()
+            .header()
+            .parent(zebra_test_data::genesis().hash())
+            .build()
+            .transaction()
+            .coinbase()
+            .output()
+            .value(50_000_000)
+            .build()
+            .build()
+            .derived_transaction(0, 0)
+            .output()
+            .value(40_000_000)
+            .build()
+            .build()
+            .build()
+            .into();
+        local_node.storage.insert(b1.clone()).unwrap();
+        local_node.storage.canonize(b1.hash()).unwrap();
+
+        // package: tx2 spends output of tx1, which spends output from block#1
+        let package = zebra_test_data::block_builder()
+            .header()
+            .parent(b1.hash().clone())
+            .build()
+            .transaction()
+            .input()
+            .hash(b1.transactions[1].hash.clone())
+            .index(0)
+            .build()
+            .output()
+            .value(30_000_000)
+            .build()
+            .build()
+            .derived_transaction(0, 0)
+            .output()
+            .value(20_000_000)
+            .build()
+            .build()
+            .build()
+            .transactions;
+        let tx1 = package[0].clone();
+        let tx2 = package[1].clone();
+
+        let results =
+            local_node.test_transactions(vec![tx1.clone().into(), tx2.clone().into()], None);
+        assert_eq!(
+            results,
+            vec![
+                TestTransactionResult {
+                    hash: tx1.hash(),
+                    size: tx1.total_size(),
+                    result: Ok(10_000_000),
+                },
+                TestTransactionResult {
+                    hash: tx2.hash(),
+                    size: tx2.total_size(),
+                    result: Ok(10_000_000),
+                },
+            ]
+        );
+
+        // child alone is rejected, because its parent is unknown
+        let results = local_node.test_transactions(vec![tx2.into()], None);
+        assert!(results[0].result.is_err());
+        // memory pool is not modified
+        assert_eq!(local_node.memory_pool_information().transactions_count, 0);
+    }
+
+    #[test]
+    fn check_transaction_fee_works() {
+        assert_eq!(
+            check_transaction_fee(0, 100, 0, None),
+            Err("Transaction pays no fee".into())
+        );
+        assert_eq!(check_transaction_fee(100, 100, 0, None), Ok(100));
+        assert_eq!(check_transaction_fee(100, 100, 100, None), Ok(100));
+        assert_eq!(
+            check_transaction_fee(99, 100, 100, None),
+            Err("Transaction fee is below minimal relay fee".into())
+        );
+        assert_eq!(check_transaction_fee(100, 100, 0, Some(1000)), Ok(100));
+        assert_eq!(
+            check_transaction_fee(100, 100, 0, Some(999)),
+            Err("Transaction fee rate exceeds maximal fee rate".into())
+        );
+    }
 }
@@ -1,12 +1,14 @@
 use linked_hash_map::LinkedHashMap;
-use std::collections::{HashSet, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::fmt;
 use types::{BlockHeight, MemoryPoolRef, StorageRef};
 use utils::{BestHeadersChain, BestHeadersChainInformation, HashPosition, HashQueueChain};
 use zebra_chain::{
     IndexedBlock, IndexedBlockHeader, IndexedTransaction, OutPoint, TransactionOutput,
 };
-use zebra_miner::{FeeCalculator, MemoryPoolInformation, MemoryPoolOrderingStrategy};
+use zebra_miner::{
+    FeeCalculator, MemoryPoolInformation, MemoryPoolInsertError, MemoryPoolOrderingStrategy,
+};
 use zebra_primitives::bytes::Bytes;
 use zebra_primitives::hash::H256;
 use zebra_storage;
@@ -25,6 +27,8 @@ const NUMBER_OF_QUEUES: usize = 3;
 pub struct BlockInsertionResult {
     /// Hashes of blocks, which were canonized during this insertion procedure. Order matters
     pub canonized_blocks_hashes: Vec<H256>,
+    /// Hashes of blocks, which were decanonized during this insertion procedure. Order matters (old best block goes first)
+    pub decanonized_blocks_hashes: Vec<H256>,
     /// Transaction to 'reverify'. Order matters
     pub transactions_to_reverify: Vec<IndexedTransaction>,
 }
@@ -40,6 +44,14 @@ impl fmt::Debug for BlockInsertionResult {
                     .map(H256::reversed)
                     .collect::<Vec<_>>(),
             )
+            .field(
+                "decanonized_blocks_hashes",
+                &self
+                    .decanonized_blocks_hashes
+                    .iter()
+                    .map(H256::reversed)
+                    .collect::<Vec<_>>(),
+            )
             .field("transactions_to_reverify", &self.transactions_to_reverify)
             .finish()
     }
@@ -50,6 +62,7 @@ impl BlockInsertionResult {
     pub fn with_canonized_blocks(canonized_blocks_hashes: Vec<H256>) -> Self {
         BlockInsertionResult {
             canonized_blocks_hashes: canonized_blocks_hashes,
+            decanonized_blocks_hashes: Vec::new(),
             transactions_to_reverify: Vec::new(),
         }
     }
@@ -129,6 +142,10 @@ pub struct Chain {
     memory_pool: MemoryPoolRef,
     /// Blocks that have been marked as dead-ends
     dead_end_blocks: HashSet<H256>,
+    /// Blocks that have been explicitly invalidated (mapped to the invalidated ancestor)
+    invalidated_blocks: HashMap<H256, H256>,
+    /// Tips of the side chains, stored since the node start
+    side_chain_tips: HashSet<H256>,
 }
 
 impl BlockState {
@@ -171,6 +188,8 @@ impl Chain {
             verifying_transactions: LinkedHashMap::new(),
             memory_pool: memory_pool,
             dead_end_blocks: HashSet::new(),
+            invalidated_blocks: HashMap::new(),
+            side_chain_tips: HashSet::new(),
         }
     }
 
@@ -398,6 +417,147 @@ impl Chain {
         self.dead_end_blocks.insert(hash.clone());
     }
 
+    /// Get hash of the invalidated block, which is either this block or one of its ancestors
+    pub fn invalidated_block_root(&self, header: &IndexedBlockHeader) -> Option<H256> {
+        if self.invalidated_blocks.is_empty() {
+            return None;
+        }
+
+        let mut hash = header.hash.clone();
+        let mut parent_hash = header.raw.previous_header_hash.clone();
+        loop {
+            if let Some(root) = self.invalidated_blocks.get(&hash) {
+                return Some(root.clone());
+            }
+            // canonical blocks are never invalidated
+            if self.storage.block_number(&parent_hash).is_some() {
+                return None;
+            }
+
+            hash = parent_hash;
+            parent_hash = match self.storage.block_header(hash.clone().into()) {
+                Some(header) => header.raw.previous_header_hash,
+                None => return self.invalidated_blocks.get(&hash).cloned(),
+            };
+        }
+    }
+
+    /// Mark block as invalid. If block is canonical, best chain is switched to the longest
+    /// valid side chain, or rolled back to the block parent. Block and its descendants are
+    /// refused until the block is reconsidered
+    pub fn invalidate_block(
+        &mut self,
+        hash: &H256,
+    ) -> Result<BlockInsertionResult, zebra_storage::Error> {
+        // side chain blocks are only marked
+        if self.storage.block_number(hash).is_none() {
+            self.invalidated_blocks.insert(hash.clone(), hash.clone());
+            return Ok(BlockInsertionResult::default());
+        }
+
+        // fails if there are too many blocks to decanonize
+        let rollback_origin = self.storage.rollback_origin(hash)?;
+        for block_hash in &rollback_origin.decanonized_route {
+            self.invalidated_blocks
+                .insert(block_hash.clone(), hash.clone());
+        }
+
+        let origin = match self.best_valid_side_chain() {
+            Some(ref origin) if origin.block_number > rollback_origin.block_number => {
+                origin.clone()
+            }
+            _ => rollback_origin,
+        };
+        match self.reset_best_chain(origin) {
+            Ok(result) => Ok(result),
+            Err(err) => {
+                // storage is not modified => block is still valid
+                self.invalidated_blocks
+                    .retain(|_, block_root| *block_root != *hash);
+                Err(err)
+            }
+        }
+    }
+
+    /// Remove invalid mark from the block and its descendants. If there's a stored side chain,
+    /// which is longer than the best chain, best chain is switched to it.
+    /// Returns None if block has not been invalidated
+    pub fn reconsider_block(
+        &mut self,
+        hash: &H256,
+    ) -> Result<Option<BlockInsertionResult>, zebra_storage::Error> {
+        let root = match self.invalidated_blocks.get(hash) {
+            Some(root) => root.clone(),
+            None => return Ok(None),
+        };
+
+        self.invalidated_blocks
+            .retain(|_, block_root| *block_root != root);
+
+        match self.best_valid_side_chain() {
+            Some(ref origin) if origin.block_number > self.best_storage_block.number => {
+                self.reset_best_chain(origin.clone()).map(Some)
+            }
+            _ => Ok(Some(BlockInsertionResult::default())),
+        }
+    }
+
+    /// Select the longest stored side chain without invalidated blocks.
+    /// Returned origin is computed against the current best block and ends with the side chain tip.
+    /// Side chains, which can't become canonical (e.g. forked too far from the best chain), are forgotten
+    fn best_valid_side_chain(&mut self) -> Option<zebra_storage::SideChainOrigin> {
+        let mut best_origin: Option<zebra_storage::SideChainOrigin> = None;
+        let mut ancient_tips = Vec::new();
+        for tip in &self.side_chain_tips {
+            let mut origin = match self.storage.stored_block_origin(tip) {
+                Ok(zebra_storage::BlockOrigin::SideChain(origin))
+                | Ok(zebra_storage::BlockOrigin::SideChainBecomingCanonChain(origin)) => origin,
+                Ok(_) => continue,
+                Err(_) => {
+                    ancient_tips.push(tip.clone());
+                    continue;
+                }
+            };
+            origin.canonized_route.push(tip.clone());
+
+            let is_valid = self
+                .storage
+                .block_hash(origin.ancestor)
+                .into_iter()
+                .chain(origin.canonized_route.iter().cloned())
+                .all(|block_hash| !self.invalidated_blocks.contains_key(&block_hash));
+            let is_better = best_origin
+                .as_ref()
+                .map(|best_origin| origin.block_number > best_origin.block_number)
+                .unwrap_or(true);
+            if is_valid && is_better {
+                best_origin = Some(origin);
+            }
+        }
+
+        for tip in ancient_tips {
+            self.side_chain_tips.remove(&tip);
+        }
+
+        best_origin
+    }
+
+    /// Switch best chain to the given origin, forgetting all in-memory blocks
+    fn reset_best_chain(
+        &mut self,
+        origin: zebra_storage::SideChainOrigin,
+    ) -> Result<BlockInsertionResult, zebra_storage::Error> {
+        let result = self.reorganize(origin)?;
+
+        // all in-memory blocks are descendants of the old best storage block
+        self.forget_all_blocks_with_state(BlockState::Scheduled);
+        self.forget_all_blocks_with_state(BlockState::Requested);
+        self.forget_all_blocks_with_state(BlockState::Verifying);
+        self.headers_chain = BestHeadersChain::new(self.best_storage_block.hash.clone());
+
+        Ok(result)
+    }
+
     /// Insert new best block to storage
     pub fn insert_best_block(
         &mut self,
@@ -444,101 +604,29 @@ impl Chain {
                         memory_pool.remove_by_prevout(&tx_input.previous_output);
                     }
                 }
+                // + remove transactions which have expired
+                memory_pool.on_block(self.best_storage_block.number);
                 // no transactions to reverify, because we have just appended new transactions to the blockchain
 
                 Ok(BlockInsertionResult {
                     canonized_blocks_hashes: vec![block.hash().clone()],
+                    decanonized_blocks_hashes: Vec::new(),
                     transactions_to_reverify: Vec::new(),
                 })
             }
             // case 2: block has been added to the side branch with reorganization to this branch
             zebra_storage::BlockOrigin::SideChainBecomingCanonChain(origin) => {
-                let fork = self.storage.fork(origin.clone())?;
-                fork.store().insert(block.clone())?;
-                fork.store().canonize(block.hash())?;
-                self.storage.switch_to_fork(fork)?;
-
-                // remember new best block hash
-                self.best_storage_block = self.storage.best_block();
-
-                // remove inserted block + handle possible reorganization in headers chain
-                // TODO: mk, not sure if we need both of those params
-                self.headers_chain
-                    .block_inserted_to_storage(block.hash(), &self.best_storage_block.hash);
-
-                // all transactions from this block were accepted
-                // + all transactions from previous blocks of this fork were accepted
-                // => delete accepted transactions from verification queue and from the memory pool
-                let this_block_transactions_hashes = block
-                    .transactions
-                    .iter()
-                    .map(|tx| tx.hash.clone())
-                    .collect::<Vec<_>>();
-                let mut canonized_blocks_hashes = origin.canonized_route.clone();
-                let new_main_blocks_transactions_hashes = origin
-                    .canonized_route
-                    .into_iter()
-                    .flat_map(|block_hash| self.storage.block_transaction_hashes(block_hash.into()))
-                    .collect::<Vec<_>>();
-
-                let mut memory_pool = self.memory_pool.write();
-                for transaction_accepted in this_block_transactions_hashes
-                    .into_iter()
-                    .chain(new_main_blocks_transactions_hashes.into_iter())
-                {
-                    memory_pool.remove_by_hash(&transaction_accepted);
-                    self.verifying_transactions.remove(&transaction_accepted);
-                }
-
-                // reverify all transactions from old main branch' blocks
-                let old_main_blocks_transactions = origin
-                    .decanonized_route
-                    .into_iter()
-                    .flat_map(|block_hash| self.storage.block_transactions(block_hash.into()))
-                    .collect::<Vec<_>>();
-
-                trace!(target: "sync", "insert_best_block, old_main_blocks_transactions: {:?}",
-					   old_main_blocks_transactions.iter().map(|tx| tx.hash.reversed()).collect::<Vec<H256>>());
-
-                // reverify memory pool transactions, sorted by timestamp
-                let memory_pool_transactions_count = memory_pool.information().transactions_count;
-                let memory_pool_transactions: Vec<IndexedTransaction> = memory_pool
-                    .remove_n_with_strategy(
-                        memory_pool_transactions_count,
-                        MemoryPoolOrderingStrategy::ByTimestamp,
-                    )
-                    .into_iter()
-                    .map(|t| t.into())
-                    .collect();
-
-                // reverify verifying transactions
-                let verifying_transactions: Vec<IndexedTransaction> = self
-                    .verifying_transactions
-                    .iter()
-                    .map(|(_, t)| t.clone())
-                    .collect();
-                self.verifying_transactions.clear();
-
-                canonized_blocks_hashes.push(block.hash().clone());
-
-                let result = BlockInsertionResult {
-                    canonized_blocks_hashes: canonized_blocks_hashes,
-                    // order matters: db transactions, then ordered mempool transactions, then ordered verifying transactions
-                    transactions_to_reverify: old_main_blocks_transactions
-                        .into_iter()
-                        .chain(memory_pool_transactions.into_iter())
-                        .chain(verifying_transactions.into_iter())
-                        .collect(),
-                };
-
-                trace!(target: "sync", "result: {:?}", result);
-
-                Ok(result)
+                let block_hash = block.hash().clone();
+                self.storage.insert(block)?;
+                self.switch_to_side_chain(origin, &block_hash)
             }
             // case 3: block has been added to the side branch without reorganization to this branch
             zebra_storage::BlockOrigin::SideChain(_origin) => {
                 let block_hash = block.hash().clone();
+                let parent_hash = block.header.raw.previous_header_hash.clone();
                 self.storage.insert(block)?;
+                self.side_chain_tips.remove(&parent_hash);
+                self.side_chain_tips.insert(block_hash.clone());
 
                 // remove inserted block + handle possible reorganization in headers chain
                 // TODO: mk, not sure if it's needed here at all
@@ -552,6 +640,124 @@ impl Chain {
         }
     }
 
+    /// Mark stored block as precious, so that its chain is preferred over other chains of the same
+    /// length. If block is the tip of such side chain, best chain is switched to this side chain
+    pub fn precious_block(
+        &mut self,
+        hash: &H256,
+    ) -> Result<BlockInsertionResult, zebra_storage::Error> {
+        match self.storage.precious_block(hash)? {
+            zebra_storage::BlockOrigin::SideChainBecomingCanonChain(origin) => {
+                self.switch_to_side_chain(origin, hash)
+            }
+            _ => Ok(BlockInsertionResult::default()),
+        }
+    }
+
+    /// Switch best chain to the side chain, ending with given stored block
+    fn switch_to_side_chain(
+        &mut self,
+        mut origin: zebra_storage::SideChainOrigin,
+        block_hash: &H256,
+    ) -> Result<BlockInsertionResult, zebra_storage::Error> {
+        origin.canonized_route.push(block_hash.clone());
+        let result = self.reorganize(origin)?;
+
+        // remove inserted block + handle possible reorganization in headers chain
+        // TODO: mk, not sure if we need both of those params
+        self.headers_chain
+            .block_inserted_to_storage(block_hash, &self.best_storage_block.hash);
+
+        Ok(result)
+    }
+
+    /// Decanonize and canonize blocks of the origin routes with a single storage update
+    fn reorganize(
+        &mut self,
+        origin: zebra_storage::SideChainOrigin,
+    ) -> Result<BlockInsertionResult, zebra_storage::Error> {
+        let fork = self.storage.fork(origin.clone())?;
+        self.storage.switch_to_fork(fork)?;
+
+        // remember new best block hash
+        self.best_storage_block = self.storage.best_block();
+
+        // canonized blocks are not side chain tips anymore, but old best block is
+        for block_hash in &origin.canonized_route {
+            self.side_chain_tips.remove(block_hash);
+        }
+        if let Some(old_best_block_hash) = origin.decanonized_route.last() {
+            self.side_chain_tips.insert(old_best_block_hash.clone());
+        }
+
+        // all transactions from this block were accepted
+        // + all transactions from previous blocks of this fork were accepted
+        // => delete accepted transactions from verification queue and from the memory pool
+        let canonized_blocks_hashes = origin.canonized_route;
+        let new_main_blocks_transactions_hashes = canonized_blocks_hashes
+            .iter()
+            .flat_map(|block_hash| {
+                self.storage
+                    .block_transaction_hashes(block_hash.clone().into())
+            })
+            .collect::<Vec<_>>();
+
+        let mut memory_pool = self.memory_pool.write();
+        for transaction_accepted in new_main_blocks_transactions_hashes {
+            memory_pool.remove_by_hash(&transaction_accepted);
+            self.verifying_transactions.remove(&transaction_accepted);
+        }
+        // expired transactions are not reverified
+        memory_pool.on_block(self.best_storage_block.number);
+
+        // blocks are decanonized starting from the old best block
+        let decanonized_blocks_hashes = origin.decanonized_route.iter().rev().cloned().collect();
+
+        // reverify all transactions from old main branch' blocks
+        let old_main_blocks_transactions = origin
+            .decanonized_route
+            .into_iter()
+            .flat_map(|block_hash| self.storage.block_transactions(block_hash.into()))
+            .collect::<Vec<_>>();
+
+        trace!(target: "sync", "reorganize, old_main_blocks_transactions: {:?}",
+			   old_main_blocks_transactions.iter().map(|tx| tx.hash.reversed()).collect::<Vec<H256>>());
+
+        // reverify memory pool transactions, sorted by timestamp
+        let memory_pool_transactions_count = memory_pool.information().transactions_count;
+        let memory_pool_transactions: Vec<IndexedTransaction> = memory_pool
+            .remove_n_with_strategy(
+                memory_pool_transactions_count,
+                MemoryPoolOrderingStrategy::ByTimestamp,
+            )
+            .into_iter()
+            .map(|t| t.into())
+            .collect();
+
+        // reverify verifying transactions
+        let verifying_transactions: Vec<IndexedTransaction> = self
+            .verifying_transactions
+            .iter()
+            .map(|(_, t)| t.clone())
+            .collect();
+        self.verifying_transactions.clear();
+
+        let result = BlockInsertionResult {
+            canonized_blocks_hashes: canonized_blocks_hashes,
+            decanonized_blocks_hashes: decanonized_blocks_hashes,
+            // order matters: db transactions, then ordered mempool transactions, then ordered verifying transactions
+            transactions_to_reverify: old_main_blocks_transactions
+                .into_iter()
+                .chain(memory_pool_transactions.into_iter())
+                .chain(verifying_transactions.into_iter())
+                .collect(),
+        };
+
+        trace!(target: "sync", "result: {:?}", result);
+
+        Ok(result)
+    }
+
     /// Forget in-memory block
     pub fn forget_block(&mut self, hash: &H256) -> HashPosition {
         self.headers_chain.remove(hash);
@@ -702,20 +908,22 @@ impl Chain {
         })
     }
 
-    /// Insert transaction to memory pool
-    pub fn insert_verified_transaction(&mut self, transaction: IndexedTransaction) {
+    /// Insert transaction to memory pool. Returns error if transaction is not accepted
+    pub fn insert_verified_transaction(
+        &mut self,
+        transaction: IndexedTransaction,
+    ) -> Result<(), MemoryPoolInsertError> {
         // we have verified transaction, but possibly this transaction replaces
-        // existing transaction from memory pool
-        // => remove previous transactions before
+        // existing transactions from memory pool
+        // => previous transactions are removed if replacement is allowed
         let mut memory_pool = self.memory_pool.write();
-        for input in &transaction.raw.inputs {
-            memory_pool.remove_by_prevout(&input.previous_output);
-        }
-        // now insert transaction itself
-        memory_pool.insert_verified(
-            transaction,
-            &FeeCalculator(self.storage.as_transaction_output_provider()),
-        );
+        memory_pool.set_best_block_height(self.best_storage_block.number);
+        memory_pool
+            .replace_verified(
+                transaction,
+                &FeeCalculator(self.storage.as_transaction_output_provider()),
+            )
+            .map(|_| ())
     }
 
     /// Calculate block locator hashes for hash queue
@@ -1154,7 +1362,7 @@ mod tests {
         let tx1_hash = tx1.hash();
         let tx2_hash = tx2.hash();
         chain.verify_transaction(tx1.into());
-        chain.insert_verified_transaction(tx2.into());
+        chain.insert_verified_transaction(tx2.into()).unwrap();
 
         assert_eq!(
             chain.transaction_state(&genesis_block.transactions[0].hash()),
@@ -1211,7 +1419,7 @@ mod tests {
         let db = Arc::new(BlockChainDatabase::init_test_chain(vec![b0.into()]));
         let mut chain = Chain::new(db, Arc::new(RwLock::new(MemoryPool::new())));
         chain.verify_transaction(tx1.into());
-        chain.insert_verified_transaction(tx2.into());
+        chain.insert_verified_transaction(tx2.into()).unwrap();
 
         // only one transaction is in the memory pool
         assert_eq!(chain.information().transactions.transactions_count, 1);
@@ -1224,6 +1432,40 @@ mod tests {
         assert!(!chain.forget_verifying_transaction(&tx2_hash));
     }
 
+    #[test]
+    fn chain_expired_transaction_is_removed_on_block_insert() {
+        let b0 = zebra_test_data::block_builder()
+            .header()
+            .build()
+            .transaction()
+            .coinbase()
+            .output()
+            .value(10)
+            .build()
+            .build()
+            .build();
+        let b1 = zebra_test_data::block_builder()
+            .header()
+            .parent(b0.hash())
+            .build()
+            .transaction()
+            .coinbase()
+            .build()
+            .build();
+        let mut tx: Transaction =
+            zebra_test_data::TransactionBuilder::with_input(&b0.transactions[0], 0).into();
+        tx.expiry_height = 1;
+
+        let db = Arc::new(BlockChainDatabase::init_test_chain(vec![b0.into()]));
+        let mut chain = Chain::new(db, Arc::new(RwLock::new(MemoryPool::new())));
+        chain.insert_verified_transaction(tx.into()).unwrap();
+        assert_eq!(chain.information().transactions.transactions_count, 1);
+
+        // transaction can't be mined after block 1 => it is removed from the memory pool
+        chain.insert_best_block(b1.into()).expect("block accepted");
+        assert_eq!(chain.information().transactions.transactions_count, 0);
+    }
+
     #[test]
     fn chain_forget_verifying_transaction_with_children() {
         let test_chain = &mut zebra_test_data::ChainBuilder::new();
@@ -1279,10 +1521,18 @@ mod tests {
             zebra_test_data::block_h2().into(),
         ]));
         let mut chain = Chain::new(db, Arc::new(RwLock::new(MemoryPool::new())));
-        chain.insert_verified_transaction(test_chain.at(0).into());
-        chain.insert_verified_transaction(test_chain.at(1).into());
-        chain.insert_verified_transaction(test_chain.at(2).into());
-        chain.insert_verified_transaction(test_chain.at(3).into());
+        chain
+            .insert_verified_transaction(test_chain.at(0).into())
+            .unwrap();
+        chain
+            .insert_verified_transaction(test_chain.at(1).into())
+            .unwrap();
+        chain
+            .insert_verified_transaction(test_chain.at(2).into())
+            .unwrap();
+        chain
+            .insert_verified_transaction(test_chain.at(3).into())
+            .unwrap();
 
         let chain_transactions = chain.transactions_hashes_with_state(TransactionState::InMemory);
         assert!(chain_transactions.contains(&test_chain.at(0).hash()));
@@ -1332,7 +1582,7 @@ mod tests {
         let db = Arc::new(BlockChainDatabase::init_test_chain(vec![b0.into()]));
         let mut chain = Chain::new(db, Arc::new(RwLock::new(MemoryPool::new())));
         chain.verify_transaction(tx1.into());
-        chain.insert_verified_transaction(tx2.into());
+        chain.insert_verified_transaction(tx2.into()).unwrap();
 
         // no reorg
         let result = chain.insert_best_block(b1.into()).expect("no error");
@@ -1443,9 +1693,9 @@ mod tests {
         ]));
         let mut chain = Chain::new(db, Arc::new(RwLock::new(MemoryPool::new())));
 
-        chain.insert_verified_transaction(tx3.into());
-        chain.insert_verified_transaction(tx4.into());
-        chain.insert_verified_transaction(tx5.into());
+        chain.insert_verified_transaction(tx3.into()).unwrap();
+        chain.insert_verified_transaction(tx4.into()).unwrap();
+        chain.insert_verified_transaction(tx5.into()).unwrap();
 
         assert_eq!(
             chain
@@ -1496,6 +1746,10 @@ mod tests {
             insert_result.canonized_blocks_hashes,
             vec![b3.hash(), b4.hash(), b5.hash()]
         );
+        assert_eq!(
+            insert_result.decanonized_blocks_hashes,
+            vec![b2.hash(), b1.hash()]
+        );
         assert_eq!(chain.information().transactions.transactions_count, 0); // tx3, tx4, tx5 are added to the database
     }
 
@@ -1527,7 +1781,9 @@ mod tests {
             zebra_test_data::genesis().into(),
         ]));
         let mut chain = Chain::new(db, Arc::new(RwLock::new(MemoryPool::new())));
-        chain.insert_verified_transaction(tx2.clone().into());
+        chain
+            .insert_verified_transaction(tx2.clone().into())
+            .unwrap();
         // insert verified block with tx1
         chain.insert_best_block(b0.into()).expect("no error");
         // => tx2 is removed from memory pool, but tx3 remains
@@ -1558,10 +1814,180 @@ mod tests {
             zebra_test_data::block_h1().into(),
         ]));
         let mut chain = Chain::new(db, Arc::new(RwLock::new(MemoryPool::new())));
-        chain.insert_verified_transaction(data_chain.at(0).into());
-        chain.insert_verified_transaction(data_chain.at(1).into());
+        chain
+            .insert_verified_transaction(data_chain.at(0).into())
+            .unwrap();
+        chain
+            .insert_verified_transaction(data_chain.at(1).into())
+            .unwrap();
         assert_eq!(chain.information().transactions.transactions_count, 2);
-        chain.insert_verified_transaction(data_chain.at(2).into());
+        chain
+            .insert_verified_transaction(data_chain.at(2).into())
+            .unwrap();
         assert_eq!(chain.information().transactions.transactions_count, 2); // tx was replaced
     }
+
+    #[test]
+    fn invalidated_best_block_is_rolled_back() {
+        let genesis = zebra_test_data::genesis();
+        let b1 = zebra_test_data::block_h1();
+        let db = Arc::new(BlockChainDatabase::init_test_chain(vec![
+            genesis.clone().into(),
+            b1.clone().into(),
+        ]));
+        let mut chain = Chain::new(db.clone(), Arc::new(RwLock::new(MemoryPool::new())));
+        assert_eq!(chain.best_storage_block().number, 1);
+
+        let result = chain.invalidate_block(&b1.hash()).expect("no error");
+        assert_eq!(result.decanonized_blocks_hashes, vec![b1.hash()]);
+        assert_eq!(result.transactions_to_reverify.len(), b1.transactions.len());
+        assert_eq!(chain.best_storage_block().number, 0);
+        assert_eq!(db.best_block().hash, genesis.hash());
+        assert_eq!(
+            chain.invalidated_block_root(&b1.block_header.clone().into()),
+            Some(b1.hash())
+        );
+
+        // reconsidered block becomes canonical again
+        let result = chain.reconsider_block(&b1.hash()).expect("no error");
+        assert_eq!(
+            result.map(|result| result.canonized_blocks_hashes),
+            Some(vec![b1.hash()])
+        );
+        assert_eq!(chain.reconsider_block(&b1.hash()), Ok(None));
+        assert_eq!(
+            chain.invalidated_block_root(&b1.block_header.clone().into()),
+            None
+        );
+        assert_eq!(db.best_block().hash, b1.hash());
+    }
+
+    #[test]
+    fn invalidated_best_block_switches_to_valid_side_chain() {
+        let genesis = zebra_test_data::genesis();
+        let b1 = zebra_test_data::block_builder()
+            .header()
+            .parent(genesis.hash())
+            .build()
+            .build();
+        let b2 = zebra_test_data::block_builder()
+            .header()
+            .parent(b1.hash())
+            .build()
+            .build();
+        // side chain, forked from the invalidated block
+        let c2 = zebra_test_data::block_builder()
+            .header()
+            .parent(b1.hash())
+            .nonce(1.into())
+            .build()
+            .build();
+        // side chain, forked from genesis
+        let s1 = zebra_test_data::block_builder()
+            .header()
+            .parent(genesis.hash())
+            .nonce(1.into())
+            .build()
+            .build();
+        let s2 = zebra_test_data::block_builder()
+            .header()
+            .parent(s1.hash())
+            .build()
+            .build();
+
+        let db = Arc::new(BlockChainDatabase::init_test_chain(vec![genesis
+            .clone()
+            .into()]));
+        let mut chain = Chain::new(db.clone(), Arc::new(RwLock::new(MemoryPool::new())));
+        for block in vec![&b1, &b2, &c2, &s1, &s2] {
+            chain
+                .insert_best_block(block.clone().into())
+                .expect("no error");
+        }
+        assert_eq!(chain.best_storage_block().hash, b2.hash());
+
+        let result = chain.invalidate_block(&b1.hash()).expect("no error");
+        assert_eq!(result.canonized_blocks_hashes, vec![s1.hash(), s2.hash()]);
+        assert_eq!(result.decanonized_blocks_hashes, vec![b2.hash(), b1.hash()]);
+        assert_eq!(chain.best_storage_block().hash, s2.hash());
+        assert_eq!(db.best_block().hash, s2.hash());
+
+        // reconsidered chain is not longer than the best chain
+        let result = chain.reconsider_block(&b1.hash()).expect("no error");
+        assert_eq!(result, Some(BlockInsertionResult::default()));
+        assert_eq!(chain.best_storage_block().hash, s2.hash());
+    }
+
+    #[test]
+    fn invalidation_deeper_than_max_fork_route_is_refused() {
+        let genesis = zebra_test_data::genesis();
+        let b1 = zebra_test_data::block_h1();
+        let b2 = zebra_test_data::block_builder()
+            .header()
+            .parent(b1.hash())
+            .build()
+            .build();
+        let mut db = BlockChainDatabase::init_test_chain(vec![
+            genesis.clone().into(),
+            b1.clone().into(),
+            b2.clone().into(),
+        ]);
+        db.set_max_fork_route(1);
+        let db = Arc::new(db);
+        let mut chain = Chain::new(db.clone(), Arc::new(RwLock::new(MemoryPool::new())));
+
+        assert_eq!(
+            chain.invalidate_block(&b1.hash()),
+            Err(zebra_storage::Error::AncientFork)
+        );
+        assert_eq!(
+            chain.invalidated_block_root(&b1.block_header.clone().into()),
+            None
+        );
+        assert_eq!(chain.best_storage_block().hash, b2.hash());
+        assert_eq!(db.best_block().hash, b2.hash());
+    }
+
+    #[test]
+    fn precious_block_switches_to_side_chain_of_the_same_length() {
+        let genesis = zebra_test_data::genesis();
+        let b1 = zebra_test_data::block_builder()
+            .header()
+            .parent(genesis.hash())
+            .build()
+            .build();
+        let s1 = zebra_test_data::block_builder()
+            .header()
+            .parent(genesis.hash())
+            .nonce(1.into())
+            .build()
+            .build();
+
+        let db = Arc::new(BlockChainDatabase::init_test_chain(vec![genesis
+            .clone()
+            .into()]));
+        let mut chain = Chain::new(db.clone(), Arc::new(RwLock::new(MemoryPool::new())));
+        chain
+            .insert_best_block(b1.clone().into())
+            .expect("no error");
+        assert_eq!(
+            chain
+                .insert_best_block(s1.clone().into())
+                .expect("no error"),
+            BlockInsertionResult::default()
+        );
+        assert_eq!(chain.best_storage_block().hash, b1.hash());
+
+        // canonical block is already preferred
+        assert_eq!(
+            chain.precious_block(&b1.hash()).expect("no error"),
+            BlockInsertionResult::default()
+        );
+
+        let result = chain.precious_block(&s1.hash()).expect("no error");
+        assert_eq!(result.canonized_blocks_hashes, vec![s1.hash()]);
+        assert_eq!(result.decanonized_blocks_hashes, vec![b1.hash()]);
+        assert_eq!(chain.best_storage_block().hash, s1.hash());
+        assert_eq!(db.best_block().number, 1);
+    }
 }
@@ -1,11 +1,15 @@
 use parking_lot::Mutex;
+use std::collections::VecDeque;
 use std::sync::Arc;
 use synchronization_client_core::{ClientCore, SynchronizationClientCore};
 use synchronization_executor::TaskExecutor;
 use synchronization_verifier::{TransactionVerificationSink, Verifier};
-use types::{ClientCoreRef, EmptyBoxFuture, PeerIndex, SyncListenerRef, SynchronizationStateRef};
+use types::{
+    BlockHeight, ClientCoreRef, EmptyBoxFuture, PeerIndex, SyncListenerRef, SynchronizationStateRef,
+};
 use zebra_chain::{IndexedBlock, IndexedBlockHeader, IndexedTransaction};
 use zebra_message::types;
+use zebra_primitives::hash::H256;
 
 #[cfg_attr(feature = "cargo-clippy", allow(doc_markdown))]
 ///! TODO: update with headers-first corrections
@@ -127,6 +131,7 @@ pub trait Client: Send + Sync + 'static {
     fn on_headers(&self, peer_index: PeerIndex, headers: Vec<IndexedBlockHeader>);
     fn on_block(&self, peer_index: PeerIndex, block: IndexedBlock);
     fn on_transaction(&self, peer_index: PeerIndex, transaction: IndexedTransaction);
+    fn on_submitted_block(&self, block: IndexedBlock);
     fn on_notfound(&self, peer_index: PeerIndex, message: types::NotFound);
     fn after_peer_nearly_blocks_verified(&self, peer_index: PeerIndex, future: EmptyBoxFuture);
     fn accept_transaction(
@@ -135,6 +140,12 @@ pub trait Client: Send + Sync + 'static {
         sink: Box<TransactionVerificationSink>,
     ) -> Result<(), String>;
     fn install_sync_listener(&self, listener: SyncListenerRef);
+    fn rescan_blockchain(&self, start_height: BlockHeight) -> Option<(BlockHeight, BlockHeight)>;
+    fn invalidate_block(&self, hash: &H256) -> Result<(), String>;
+    fn reconsider_block(&self, hash: &H256) -> Result<bool, String>;
+    fn is_invalidated_block(&self, header: &IndexedBlockHeader) -> bool;
+    fn precious_block(&self, hash: &H256) -> Result<(), String>;
+    fn stop_verification(&self);
 }
 
 /// Synchronization client facade
@@ -202,6 +213,21 @@ where
         }
     }
 
+    fn on_submitted_block(&self, block: IndexedBlock) {
+        {
+            let _verification_lock = self.heavy_verification_lock.lock();
+            let block_to_verify = self.core.lock().on_submitted_block(block);
+            if let Some(block) = block_to_verify {
+                self.heavy_verifier.verify_block(block);
+            }
+        }
+
+        let mut client = self.core.lock();
+        if !client.try_switch_to_saturated_state() {
+            client.execute_synchronization_tasks(None, None);
+        }
+    }
+
     fn on_transaction(&self, peer_index: PeerIndex, transaction: IndexedTransaction) {
         // block can become:
         // ignored, orphaned => no verification should occur
@@ -250,6 +276,57 @@ where
     fn install_sync_listener(&self, listener: SyncListenerRef) {
         self.core.lock().install_sync_listener(listener);
     }
+
+    fn rescan_blockchain(&self, start_height: BlockHeight) -> Option<(BlockHeight, BlockHeight)> {
+        // blocks, inserted after this point, are passed to listeners by regular notifications
+        let mut stop_block = self.core.lock().chain().best_storage_block();
+        if start_height > stop_block.number {
+            return None;
+        }
+
+        // core is only locked while single batch is rescanned => blocks processing isn't stalled
+        let mut next_height = start_height;
+        while let Some(height) = self
+            .core
+            .lock()
+            .rescan_blockchain_batch(next_height, &mut stop_block)
+        {
+            next_height = height;
+        }
+
+        Some((start_height, stop_block.number))
+    }
+
+    fn invalidate_block(&self, hash: &H256) -> Result<(), String> {
+        let transactions_to_verify = try!(self.core.lock().invalidate_block(hash));
+        self.verify_transactions(transactions_to_verify);
+        Ok(())
+    }
+
+    fn reconsider_block(&self, hash: &H256) -> Result<bool, String> {
+        match try!(self.core.lock().reconsider_block(hash)) {
+            Some(transactions_to_verify) => {
+                self.verify_transactions(transactions_to_verify);
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    fn is_invalidated_block(&self, header: &IndexedBlockHeader) -> bool {
+        self.core.lock().is_invalidated_block(header)
+    }
+
+    fn precious_block(&self, hash: &H256) -> Result<(), String> {
+        let transactions_to_verify = try!(self.core.lock().precious_block(hash));
+        self.verify_transactions(transactions_to_verify);
+        Ok(())
+    }
+
+    fn stop_verification(&self) {
+        self.light_verifier.stop();
+        self.heavy_verifier.stop();
+    }
 }
 
 impl<T, U> SynchronizationClient<T, U>
@@ -272,4 +349,13 @@ where
             heavy_verifier: heavy_verifier,
         })
     }
+
+    /// Verify transactions, resurrected after best chain change
+    fn verify_transactions(&self, mut transactions: VecDeque<IndexedTransaction>) {
+        let next_block_height = self.shared_state.best_storage_block_height() + 1;
+        while let Some(tx) = transactions.pop_front() {
+            self.heavy_verifier
+                .verify_transaction(next_block_height, tx);
+        }
+    }
 }
@@ -26,8 +26,11 @@ use utils::{AverageSpeedMeter, HashPosition, OrphanBlocksPool, OrphanTransaction
 use zebra_chain::{IndexedBlock, IndexedBlockHeader, IndexedTransaction};
 use zebra_message::common::{InventoryType, InventoryVector};
 use zebra_message::types;
-use zebra_miner::transaction_fee_rate;
+use zebra_message::types::reject::RejectCode;
+use zebra_message::Payload;
+use zebra_miner::{transaction_fee_rate, MemoryPoolInsertError, ReplaceResult};
 use zebra_primitives::hash::H256;
+use zebra_storage::BestBlock;
 
 /// Approximate maximal number of blocks hashes in scheduled queue.
 const MAX_SCHEDULED_HASHES: BlockHeight = 4 * 1024;
@@ -55,6 +58,8 @@ const MIN_HEADERS_DUPLICATION_INTERVAL_S: f64 = 2_f64;
 const MAX_BLOCKS_IN_DUPLICATE_REQUEST: BlockHeight = 4;
 /// Minimal number of blocks in duplicate requests.
 const MIN_BLOCKS_IN_DUPLICATE_REQUEST: BlockHeight = 8;
+/// Maximal number of blocks, rescanned while the core is locked.
+const MAX_BLOCKS_IN_RESCAN_BATCH: BlockHeight = 1_000;
 
 /// Information on current synchronization state.
 #[cfg(test)]
@@ -92,6 +97,7 @@ pub trait ClientCore {
         peer_index: PeerIndex,
         transaction: IndexedTransaction,
     ) -> Option<VecDeque<IndexedTransaction>>;
+    fn on_submitted_block(&mut self, block: IndexedBlock) -> Option<PartiallyVerifiedBlock>;
     fn on_notfound(&mut self, peer_index: PeerIndex, message: types::NotFound);
     fn after_peer_nearly_blocks_verified(&mut self, peer_index: PeerIndex, future: EmptyBoxFuture);
     fn accept_transaction(
@@ -100,6 +106,18 @@ pub trait ClientCore {
         sink: Box<TransactionVerificationSink>,
     ) -> Result<VecDeque<IndexedTransaction>, String>;
     fn install_sync_listener(&mut self, listener: SyncListenerRef);
+    fn rescan_blockchain_batch(
+        &mut self,
+        start_height: BlockHeight,
+        stop_block: &mut BestBlock,
+    ) -> Option<BlockHeight>;
+    fn invalidate_block(&mut self, hash: &H256) -> Result<VecDeque<IndexedTransaction>, String>;
+    fn reconsider_block(
+        &mut self,
+        hash: &H256,
+    ) -> Result<Option<VecDeque<IndexedTransaction>>, String>;
+    fn is_invalidated_block(&self, header: &IndexedBlockHeader) -> bool;
+    fn precious_block(&mut self, hash: &H256) -> Result<VecDeque<IndexedTransaction>, String>;
     fn execute_synchronization_tasks(
         &mut self,
         forced_blocks_requests: Option<Vec<H256>>,
@@ -113,6 +131,8 @@ pub trait ClientCore {
 pub struct Config {
     /// If true, connection to peer who has provided us with bad block is closed
     pub close_connection_on_bad_block: bool,
+    /// If false, transactions are never requested from, accepted from or relayed to peers
+    pub relay_transactions: bool,
 }
 
 /// Synchronization client.
@@ -141,6 +161,8 @@ pub struct SynchronizationClientCore<T: TaskExecutor> {
     verifying_blocks_futures: HashMap<PeerIndex, (HashSet<H256>, Vec<EmptyBoxFuture>)>,
     /// Verifying transactions futures
     verifying_transactions_sinks: HashMap<H256, Box<TransactionVerificationSink>>,
+    /// Verifying transactions by peer
+    verifying_transactions_by_peer: HashMap<H256, PeerIndex>,
     /// Hashes of items we do not want to relay after verification is completed
     do_not_relay: HashSet<H256>,
     /// Block processing speed meter
@@ -149,8 +171,8 @@ pub struct SynchronizationClientCore<T: TaskExecutor> {
     sync_speed_meter: AverageSpeedMeter,
     /// Configuration
     config: Config,
-    /// Synchronization events listener
-    listener: Option<SyncListenerRef>,
+    /// Synchronization events listeners
+    listeners: Vec<SyncListenerRef>,
     /// Time of last duplicated blocks request.
     last_dup_time: f64,
     /// Last NEW headers receival timestamp.
@@ -236,6 +258,10 @@ where
     }
 
     fn on_disconnect(&mut self, peer_index: PeerIndex) {
+        // there's no one to send reject messages to
+        self.verifying_transactions_by_peer
+            .retain(|_, peer| *peer != peer_index);
+
         // sync tasks from these peers must be executed by other peers
         let peer_tasks = self.peers_tasks.reset_blocks_tasks(peer_index);
         self.peers_tasks.disconnect(peer_index);
@@ -251,7 +277,8 @@ where
                 match item.inv_type {
                     // check that transaction is unknown to us
                     InventoryType::MessageTx => {
-                        self.chain.transaction_state(&item.hash) == TransactionState::Unknown
+                        self.config.relay_transactions
+                            && self.chain.transaction_state(&item.hash) == TransactionState::Unknown
                             && !self.orphaned_transactions_pool.contains(&item.hash)
                     }
                     // check that block is unknown to us
@@ -339,7 +366,7 @@ where
         // find first unknown header position
         // optimization: normally, the first header will be unknown
         let headers_in_message = headers.len();
-        let headers = self.find_unknown_headers(headers);
+        let mut headers = self.find_unknown_headers(headers);
         if headers.is_empty() {
             trace!(target: "sync", "Ignoring {} known headers from peer#{}", headers_in_message, peer_index);
             // but this peer is still useful for synchronization
@@ -362,6 +389,7 @@ where
             return None;
         }
 
+        let mut first_invalidated_index = None;
         for (header_index, header) in headers.iter().enumerate() {
             // check that this header is direct child of previous header
             if header.raw.previous_header_hash != last_known_hash {
@@ -404,9 +432,30 @@ where
                 }
             }
 
+            // invalidated blocks (and their descendants) are not requested until reconsidered
+            if let Some(invalidated_hash) = self.chain.invalidated_block_root(header) {
+                trace!(
+                    target: "sync",
+                    "Ignoring {} headers from peer#{} starting from {}: invalidated block {}",
+                    headers.len() - header_index,
+                    peer_index,
+                    header.hash.to_reversed_str(),
+                    invalidated_hash.to_reversed_str()
+                );
+                first_invalidated_index = Some(header_index);
+                break;
+            }
+
             last_known_hash = header.hash;
         }
 
+        if let Some(first_invalidated_index) = first_invalidated_index {
+            headers.truncate(first_invalidated_index);
+            if headers.is_empty() {
+                return None;
+            }
+        }
+
         // report progress
         trace!(target: "sync", "New {} headers from peer#{}. First {:?}, last: {:?}",
             headers.len(),
@@ -436,6 +485,13 @@ where
         self.peers_tasks
             .on_block_received(peer_index, &block.header.hash);
 
+        // invalidated blocks (and their descendants) are ignored until reconsidered
+        if let Some(invalidated_hash) = self.chain.invalidated_block_root(&block.header) {
+            trace!(target: "sync", "Ignoring block {} from peer#{}: invalidated block {}", block.header.hash.to_reversed_str(), peer_index, invalidated_hash.to_reversed_str());
+            self.chain.forget_block(&block.header.hash);
+            return None;
+        }
+
         // prepare list of blocks to verify + make all required changes to the chain
         let mut result = None;
         let block_state = self.chain.block_state(&block.header.hash);
@@ -595,11 +651,51 @@ where
         result
     }
 
+    fn on_submitted_block(&mut self, block: IndexedBlock) -> Option<PartiallyVerifiedBlock> {
+        if self.is_invalidated_block(&block.header) {
+            return None;
+        }
+
+        // submitted block is only processed if we know nothing about it yet and its parent is known
+        match (
+            self.chain.block_state(&block.header.hash),
+            self.chain
+                .block_state(&block.header.raw.previous_header_hash),
+        ) {
+            (BlockState::Unknown, BlockState::VerifyingHeader)
+            | (BlockState::Unknown, BlockState::Verifying)
+            | (BlockState::Unknown, BlockState::Stored) => {
+                // block has been fully verified before submission
+                self.chain.verify_block(block.header.clone());
+                Some(PartiallyVerifiedBlock::Verified(block))
+            }
+            _ => None,
+        }
+    }
+
     fn on_transaction(
         &mut self,
         peer_index: PeerIndex,
         transaction: IndexedTransaction,
     ) -> Option<VecDeque<IndexedTransaction>> {
+        // ignore transactions from peers if transactions relay is disabled
+        if !self.config.relay_transactions {
+            trace!(target: "sync", "Ignoring transaction {} from peer#{} as transactions relay is disabled", transaction.hash.to_reversed_str(), peer_index);
+            return None;
+        }
+
+        // transaction is already confirmed in the best chain => peer has relayed it before
+        // the block has reached it (benign race) => ignore it without penalizing the peer
+        if self
+            .chain
+            .storage()
+            .transaction_meta(&transaction.hash)
+            .is_some()
+        {
+            trace!(target: "sync", "Ignoring already confirmed transaction {} from peer#{}", transaction.hash.to_reversed_str(), peer_index);
+            return None;
+        }
+
         // check if this transaction is already known
         if self.orphaned_transactions_pool.contains(&transaction.hash)
             || self.chain.transaction_state(&transaction.hash) != TransactionState::Unknown
@@ -692,9 +788,85 @@ where
     }
 
     fn install_sync_listener(&mut self, listener: SyncListenerRef) {
-        // currently single, single-setup listener is supported
-        assert!(self.listener.is_none());
-        self.listener = Some(listener);
+        self.listeners.push(listener);
+    }
+
+    fn rescan_blockchain_batch(
+        &mut self,
+        start_height: BlockHeight,
+        stop_block: &mut BestBlock,
+    ) -> Option<BlockHeight> {
+        // blocks are only inserted into the storage while the core is locked
+        // => best chain can't change while we're rescanning the batch
+        let storage = self.chain.storage();
+        if storage.block_number(&stop_block.hash) != Some(stop_block.number) {
+            // blocks above the fork point have been passed to listeners on reorganization
+            let best_block = storage.best_block();
+            *stop_block = storage
+                .fork_point(&stop_block.hash, &best_block.hash)
+                .and_then(|hash| {
+                    storage.block_number(&hash).map(|number| BestBlock {
+                        number: number,
+                        hash: hash,
+                    })
+                })
+                .unwrap_or(best_block);
+        }
+        if start_height > stop_block.number {
+            return None;
+        }
+
+        let batch_stop_height = min(
+            stop_block.number,
+            start_height.saturating_add(MAX_BLOCKS_IN_RESCAN_BATCH - 1),
+        );
+        for listener in &self.listeners {
+            ::rescan_blockchain(&storage, &**listener, start_height, batch_stop_height);
+        }
+
+        Some(batch_stop_height + 1)
+    }
+
+    fn invalidate_block(&mut self, hash: &H256) -> Result<VecDeque<IndexedTransaction>, String> {
+        if !self.chain.storage().contains_block(hash.clone().into()) {
+            return Err("Block not found".into());
+        }
+        if self.chain.block_hash(0).as_ref() == Some(hash) {
+            return Err("Genesis block can't be invalidated".into());
+        }
+
+        let result = self
+            .chain
+            .invalidate_block(hash)
+            .map_err(|err| format!("{:?}", err))?;
+        Ok(self.on_best_chain_changed(result))
+    }
+
+    fn reconsider_block(
+        &mut self,
+        hash: &H256,
+    ) -> Result<Option<VecDeque<IndexedTransaction>>, String> {
+        let result = self
+            .chain
+            .reconsider_block(hash)
+            .map_err(|err| format!("{:?}", err))?;
+        Ok(result.map(|result| self.on_best_chain_changed(result)))
+    }
+
+    fn is_invalidated_block(&self, header: &IndexedBlockHeader) -> bool {
+        self.chain.invalidated_block_root(header).is_some()
+    }
+
+    fn precious_block(&mut self, hash: &H256) -> Result<VecDeque<IndexedTransaction>, String> {
+        if !self.chain.storage().contains_block(hash.clone().into()) {
+            return Err("Block not found".into());
+        }
+
+        let result = self
+            .chain
+            .precious_block(hash)
+            .map_err(|err| format!("{:?}", err))?;
+        Ok(self.on_best_chain_changed(result))
     }
 
     /// Schedule new synchronization tasks, if any.
@@ -998,10 +1170,10 @@ where
     }
 
     /// Process failed transaction verification
-    fn on_transaction_verification_error(&self, err: &str, hash: &H256) {
+    fn on_transaction_verification_error(&self, err: &str, code: RejectCode, hash: &H256) {
         self.core
             .lock()
-            .on_transaction_verification_error(err, hash)
+            .on_transaction_verification_error(err, code, hash)
     }
 }
 
@@ -1030,11 +1202,12 @@ where
             verifying_blocks_by_peer: HashMap::new(),
             verifying_blocks_futures: HashMap::new(),
             verifying_transactions_sinks: HashMap::new(),
+            verifying_transactions_by_peer: HashMap::new(),
             do_not_relay: HashSet::new(),
             block_speed_meter: AverageSpeedMeter::with_inspect_items(SYNC_SPEED_BLOCKS_TO_INSPECT),
             sync_speed_meter: AverageSpeedMeter::with_inspect_items(BLOCKS_SPEED_BLOCKS_TO_INSPECT),
             config: config,
-            listener: None,
+            listeners: Vec::new(),
             last_dup_time: 0f64,
             new_headers_receival_timestamp: 0f64,
             last_headers_request_best_number: 0,
@@ -1126,13 +1299,66 @@ where
         }
     }
 
+    /// Update shared state and notify listeners after blocks have been (de)canonized
+    fn on_storage_best_block_changed(&self, result: &BlockInsertionResult) {
+        // update shared state
+        self.shared_state
+            .update_best_storage_block_height(self.chain.best_storage_block().number);
+        self.shared_state
+            .update_best_headers_height(self.chain.best_block_header().number);
+
+        // notify listeners
+        if !result.decanonized_blocks_hashes.is_empty() {
+            for listener in &self.listeners {
+                for disconnected_block_hash in &result.decanonized_blocks_hashes {
+                    listener.block_disconnected(disconnected_block_hash);
+                }
+                listener.chain_reorganized(
+                    &result.decanonized_blocks_hashes,
+                    &result.canonized_blocks_hashes,
+                );
+            }
+        }
+        if let Some(best_block_hash) = result.canonized_blocks_hashes.last() {
+            for listener in &self.listeners {
+                for connected_block_hash in &result.canonized_blocks_hashes {
+                    listener.block_connected(connected_block_hash);
+                }
+                listener.best_storage_block_inserted(best_block_hash);
+            }
+        }
+    }
+
+    /// Process best chain change, made outside of the block verification.
+    /// Returns transactions to reverify
+    fn on_best_chain_changed(
+        &mut self,
+        result: BlockInsertionResult,
+    ) -> VecDeque<IndexedTransaction> {
+        self.on_storage_best_block_changed(&result);
+
+        // do not relay resurrected transactions again
+        let mut transactions_to_verify = VecDeque::new();
+        for tx in result.transactions_to_reverify {
+            if let Some(tx_orphans) = self.process_peer_transaction(None, tx, false) {
+                transactions_to_verify.extend(tx_orphans);
+            }
+        }
+
+        // continue with synchronization
+        self.execute_synchronization_tasks(None, None);
+
+        transactions_to_verify
+    }
+
     /// Process new peer transaction
     fn process_peer_transaction(
         &mut self,
-        _peer_index: Option<PeerIndex>,
+        peer_index: Option<PeerIndex>,
         transaction: IndexedTransaction,
         relay: bool,
     ) -> Option<VecDeque<IndexedTransaction>> {
+        let hash = transaction.hash.clone();
         match self.try_append_transaction(transaction.clone(), relay) {
             Err(AppendTransactionError::Orphan(unknown_parents)) => {
                 self.orphaned_transactions_pool
@@ -1140,7 +1366,13 @@ where
                 None
             }
             Err(AppendTransactionError::Synchronizing) => None,
-            Ok(transactions) => Some(transactions),
+            Ok(transactions) => {
+                // remember the peer to send reject message if transaction is invalid
+                if let Some(peer_index) = peer_index {
+                    self.verifying_transactions_by_peer.insert(hash, peer_index);
+                }
+                Some(transactions)
+            }
         }
     }
 
@@ -1248,7 +1480,7 @@ where
             return;
         }
 
-        if let Some(ref listener) = self.listener {
+        for listener in &self.listeners {
             listener.synchronization_state_switched(true);
         }
 
@@ -1262,7 +1494,7 @@ where
             return;
         }
 
-        if let Some(ref listener) = self.listener {
+        for listener in &self.listeners {
             listener.synchronization_state_switched(false);
         }
 
@@ -1276,7 +1508,7 @@ where
             return;
         }
 
-        if let Some(ref listener) = self.listener {
+        for listener in &self.listeners {
             listener.synchronization_state_switched(false);
         }
 
@@ -1353,6 +1585,8 @@ where
                 headers[headers.len() - 1].hash.to_reversed_str(),
             );
             self.chain.schedule_blocks_headers(headers);
+            self.shared_state
+                .update_best_headers_height(self.chain.best_block_header().number);
         }
 
         // switch to synchronization state
@@ -1431,16 +1665,7 @@ where
             }
         } {
             Ok(insert_result) => {
-                // update shared state
-                self.shared_state
-                    .update_best_storage_block_height(self.chain.best_storage_block().number);
-
-                // notify listener
-                if let Some(best_block_hash) = insert_result.canonized_blocks_hashes.last() {
-                    if let Some(ref listener) = self.listener {
-                        listener.best_storage_block_inserted(best_block_hash);
-                    }
-                }
+                self.on_storage_best_block_changed(&insert_result);
 
                 // awake threads, waiting for this block insertion
                 self.awake_waiting_threads(&block_hash);
@@ -1492,6 +1717,16 @@ where
 
         // close connection with this peer
         if let Some(peer_index) = self.verifying_blocks_by_peer.get(hash) {
+            self.executor.execute(Task::Reject(
+                *peer_index,
+                types::Reject::with_hash(
+                    types::Block::command(),
+                    RejectCode::Invalid,
+                    err.to_owned(),
+                    hash.clone(),
+                ),
+            ));
+
             if self.config.close_connection_on_bad_block {
                 self.peers.dos(
                     *peer_index,
@@ -1505,6 +1740,8 @@ where
         // forget for this block and all its children
         // headers are also removed as they all are invalid
         self.chain.forget_block_with_children(hash);
+        self.shared_state
+            .update_best_headers_height(self.chain.best_block_header().number);
 
         // mark failed block as dead end (this branch won't be synchronized)
         self.chain.mark_dead_end_block(hash);
@@ -1518,17 +1755,30 @@ where
 
     fn on_transaction_verification_success(&mut self, transaction: IndexedTransaction) {
         // remove flags
-        let needs_relay = !self.do_not_relay.remove(&transaction.hash);
+        let needs_relay =
+            !self.do_not_relay.remove(&transaction.hash) && self.config.relay_transactions;
 
         // insert transaction to the memory pool
         // remove transaction from verification queue
         // if it is not in the queue => it was removed due to error or reorganization
         if !self.chain.forget_verifying_transaction(&transaction.hash) {
+            self.verifying_transactions_by_peer
+                .remove(&transaction.hash);
             return;
         }
 
         // transaction was in verification queue => insert to memory pool
-        self.chain.insert_verified_transaction(transaction.clone());
+        // transaction, rejected by the memory pool, is neither relayed nor reported as accepted
+        if let Err(err) = self.chain.insert_verified_transaction(transaction.clone()) {
+            self.on_transaction_verification_error(
+                &format!("{:?}", err),
+                memory_pool_reject_code(&err),
+                &transaction.hash,
+            );
+            return;
+        }
+        self.verifying_transactions_by_peer
+            .remove(&transaction.hash);
 
         // calculate transaction fee rate
         let transaction_fee_rate = transaction_fee_rate(&self.chain, &transaction.raw);
@@ -1547,18 +1797,26 @@ where
         }
     }
 
-    fn on_transaction_verification_error(&mut self, err: &str, hash: &H256) {
+    fn on_transaction_verification_error(&mut self, err: &str, code: RejectCode, hash: &H256) {
         warn!(target: "sync", "Transaction {} verification failed with error {:?}", hash.to_reversed_str(), err);
 
         // remove flags
         self.do_not_relay.remove(hash);
 
+        // let the peer know that transaction is rejected
+        if let Some(peer_index) = self.verifying_transactions_by_peer.remove(hash) {
+            self.executor.execute(Task::Reject(
+                peer_index,
+                types::Reject::with_hash(types::Tx::command(), code, err.to_owned(), hash.clone()),
+            ));
+        }
+
         // forget for this transaction and all its children
         self.chain.forget_verifying_transaction_with_children(hash);
 
         // call verification future, if any
         if let Some(future_sink) = self.verifying_transactions_sinks.remove(hash) {
-            future_sink.on_transaction_verification_error(err, hash);
+            future_sink.on_transaction_verification_error(err, code, hash);
         }
     }
 
@@ -1626,6 +1884,20 @@ impl Default for BlocksRequestLimits {
     }
 }
 
+/// Returns reject code (BIP61) for the transaction that hasn't been accepted to the memory pool
+fn memory_pool_reject_code(error: &MemoryPoolInsertError) -> RejectCode {
+    match *error {
+        MemoryPoolInsertError::InsufficientFee { .. }
+        | MemoryPoolInsertError::MemoryPoolFull { .. }
+        | MemoryPoolInsertError::Evicted
+        | MemoryPoolInsertError::Replacement(ReplaceResult::InsufficientFee { .. })
+        | MemoryPoolInsertError::Replacement(ReplaceResult::InsufficientFeeRate { .. }) => {
+            RejectCode::InsuficientFee
+        }
+        MemoryPoolInsertError::Replacement(_) => RejectCode::Duplicate,
+    }
+}
+
 #[cfg(test)]
 pub mod tests {
     extern crate zebra_test_data;
@@ -1646,8 +1918,9 @@ pub mod tests {
     use zebra_chain::{Block, IndexedBlock, Transaction};
     use zebra_db::BlockChainDatabase;
     use zebra_message::common::InventoryVector;
+    use zebra_message::types::reject::RejectCode;
     use zebra_message::{types, Services};
-    use zebra_miner::MemoryPool;
+    use zebra_miner::{MemoryPool, MemoryPoolInsertError, ReplaceResult};
     use zebra_network::{ConsensusParams, Network};
     use zebra_primitives::hash::H256;
     use zebra_verification::BackwardsCompatibleChainVerifier as ChainVerifier;
@@ -1656,6 +1929,9 @@ pub mod tests {
     struct DummySyncListenerData {
         pub is_synchronizing: bool,
         pub best_blocks: Vec<H256>,
+        pub disconnected_blocks: Vec<H256>,
+        pub connected_blocks: Vec<H256>,
+        pub reorganizations: Vec<(Vec<H256>, Vec<H256>)>,
     }
 
     struct DummySyncListener {
@@ -1676,6 +1952,24 @@ pub mod tests {
         fn best_storage_block_inserted(&self, block_hash: &H256) {
             self.data.lock().best_blocks.push(block_hash.clone());
         }
+
+        fn block_disconnected(&self, block_hash: &H256) {
+            self.data
+                .lock()
+                .disconnected_blocks
+                .push(block_hash.clone());
+        }
+
+        fn block_connected(&self, block_hash: &H256) {
+            self.data.lock().connected_blocks.push(block_hash.clone());
+        }
+
+        fn chain_reorganized(&self, disconnected: &[H256], connected: &[H256]) {
+            self.data
+                .lock()
+                .reorganizations
+                .push((disconnected.to_vec(), connected.to_vec()));
+        }
     }
 
     fn storage_with_block1() -> StorageRef {
@@ -1707,6 +2001,7 @@ pub mod tests {
         let executor = DummyTaskExecutor::new();
         let config = Config {
             close_connection_on_bad_block: true,
+            relay_transactions: true,
         };
 
         let chain_verifier = Arc::new(ChainVerifier::new(
@@ -2637,6 +2932,89 @@ pub mod tests {
         );
     }
 
+    #[test]
+    fn reject_is_sent_when_transaction_verification_fails() {
+        let input_tx = zebra_test_data::block_h1().transactions[0].clone();
+        let tx1: Transaction = zebra_test_data::TransactionBuilder::with_input(&input_tx, 0)
+            .set_output(100)
+            .into();
+        let tx1_hash = tx1.hash();
+
+        let mut dummy_verifier = DummyVerifier::default();
+        dummy_verifier.error_when_verifying(tx1_hash.clone(), "simulated");
+
+        let (executor, _, sync) = create_sync(Some(storage_with_block1()), Some(dummy_verifier));
+        sync.on_transaction(1, tx1.into());
+
+        let tasks = executor.take_tasks();
+        assert!(tasks.contains(&Task::Reject(
+            1,
+            types::Reject::with_hash("tx", RejectCode::Invalid, "simulated".into(), tx1_hash),
+        )));
+    }
+
+    #[test]
+    fn verifying_transactions_are_forgotten_on_peer_disconnect() {
+        let (_, core, _) = create_sync(None, None);
+        {
+            let mut core = core.lock();
+            core.verifying_transactions_by_peer.insert(H256::from(1), 1);
+            core.verifying_transactions_by_peer.insert(H256::from(2), 2);
+            core.on_disconnect(1);
+        }
+
+        let core = core.lock();
+        assert!(!core
+            .verifying_transactions_by_peer
+            .contains_key(&H256::from(1)));
+        assert!(core
+            .verifying_transactions_by_peer
+            .contains_key(&H256::from(2)));
+    }
+
+    #[test]
+    fn transaction_is_not_relayed_when_rejected_by_memory_pool() {
+        let input_tx = zebra_test_data::block_h1().transactions[0].clone();
+        let tx1: Transaction = zebra_test_data::TransactionBuilder::with_input(&input_tx, 0)
+            .set_output(100)
+            .into();
+        let tx1_hash = tx1.hash();
+        let tx2: Transaction = zebra_test_data::TransactionBuilder::with_input(&input_tx, 0)
+            .set_output(50)
+            .into();
+        let tx2_hash = tx2.hash();
+
+        let (executor, core, sync) = create_sync(Some(storage_with_block1()), None);
+        sync.on_transaction(1, tx1.into());
+        executor.take_tasks();
+
+        // tx2 is double spending output of tx1, which doesn't signal replaceability
+        sync.on_transaction(2, tx2.into());
+
+        let reason = format!(
+            "{:?}",
+            MemoryPoolInsertError::Replacement(ReplaceResult::NotReplaceable(tx1_hash))
+        );
+        let tasks = executor.take_tasks();
+        assert!(tasks.contains(&Task::Reject(
+            2,
+            types::Reject::with_hash("tx", RejectCode::Duplicate, reason, tx2_hash),
+        )));
+        assert!(core.lock().verifying_transactions_by_peer.is_empty());
+        assert!(!tasks.iter().any(|task| match *task {
+            Task::RelayNewTransaction(..) => true,
+            _ => false,
+        }));
+        assert_eq!(
+            core.lock()
+                .information()
+                .chain
+                .transactions
+                .transactions_count,
+            1
+        );
+    }
+
     #[test]
     fn transaction_is_orphaned_when_input_is_unknown() {
         let (_, core, sync) = create_sync(None, None);
@@ -2864,9 +3242,13 @@ pub mod tests {
 
     #[test]
     fn relay_new_transaction_when_in_saturated_state() {
-        let (executor, _, sync) = create_sync(None, None);
+        let (executor, _, sync) = create_sync(Some(storage_with_block1()), None);
 
-        let tx: Transaction = zebra_test_data::TransactionBuilder::with_output(20).into();
+        let input_tx = zebra_test_data::block_h1().transactions[0].clone();
+        let tx: Transaction = zebra_test_data::TransactionBuilder::with_input(&input_tx, 0)
+            .set_output(20)
+            .into();
+        let fee_rate = (input_tx.outputs[0].value - 20) / tx.total_size() as u64;
 
         sync.on_connect(1);
         executor.take_tasks();
@@ -2874,7 +3256,106 @@ pub mod tests {
         sync.on_transaction(2, tx.clone().into());
 
         let tasks = executor.take_tasks();
-        assert_eq!(tasks, vec![Task::RelayNewTransaction(tx.into(), 0)]);
+        assert_eq!(tasks, vec![Task::RelayNewTransaction(tx.into(), fee_rate)]);
+    }
+
+    #[test]
+    fn transaction_below_min_relay_fee_is_rejected() {
+        let (executor, core, sync) = create_sync(None, None);
+
+        // transaction without inputs pays zero fee
+        let tx: Transaction = zebra_test_data::TransactionBuilder::with_output(20).into();
+        let tx_hash = tx.hash();
+        let required = ::std::cmp::max(1, MemoryPool::new().min_relay_fee(tx.total_size()));
+
+        sync.on_connect(1);
+        executor.take_tasks();
+
+        sync.on_transaction(2, tx.into());
+
+        let reason = format!(
+            "{:?}",
+            MemoryPoolInsertError::InsufficientFee {
+                required: required,
+                actual: 0,
+            }
+        );
+        let tasks = executor.take_tasks();
+        assert!(tasks.contains(&Task::Reject(
+            2,
+            types::Reject::with_hash("tx", RejectCode::InsuficientFee, reason, tx_hash),
+        )));
+        assert!(!tasks.iter().any(|task| match *task {
+            Task::RelayNewTransaction(..) => true,
+            _ => false,
+        }));
+        assert_eq!(
+            core.lock()
+                .information()
+                .chain
+                .transactions
+                .transactions_count,
+            0
+        );
+    }
+
+    #[test]
+    fn confirmed_transaction_from_peer_is_ignored() {
+        let (executor, core, sync) = create_sync(Some(storage_with_block1()), None);
+        core.lock()
+            .peers
+            .insert(0, Services::default(), DummyOutboundSyncConnection::new());
+        executor.take_tasks();
+
+        // peer relays transaction from block#1
+        let tx = zebra_test_data::block_h1().transactions[0].clone();
+        sync.on_transaction(0, tx.into());
+
+        // transaction is dropped
+        assert_eq!(executor.take_tasks(), vec![]);
+        assert_eq!(
+            core.lock()
+                .information()
+                .chain
+                .transactions
+                .transactions_count,
+            0
+        );
+        // peer isn't penalized
+        assert!(core.lock().peers.enumerate().contains(&0));
+    }
+
+    #[test]
+    fn transactions_from_peers_are_ignored_when_relay_is_disabled() {
+        let (executor, core, sync) = create_sync(Some(storage_with_block1()), None);
+        core.lock().config.relay_transactions = false;
+
+        let input_tx = zebra_test_data::block_h1().transactions[0].clone();
+        let tx: Transaction = zebra_test_data::TransactionBuilder::with_input(&input_tx, 0)
+            .set_output(100)
+            .into();
+
+        sync.on_connect(1);
+        executor.take_tasks();
+
+        // transaction announcement is ignored
+        sync.on_inventory(
+            1,
+            types::Inv::with_inventory(vec![InventoryVector::tx(tx.hash())]),
+        );
+        assert_eq!(executor.take_tasks(), vec![]);
+
+        // transaction itself is ignored
+        sync.on_transaction(1, tx.into());
+        assert_eq!(executor.take_tasks(), vec![]);
+        assert_eq!(
+            core.lock()
+                .information()
+                .chain
+                .transactions
+                .transactions_count,
+            0
+        );
     }
 
     #[test]
@@ -3339,6 +3820,154 @@ pub mod tests {
         assert!(mempool.write().remove_by_hash(&tx3.hash()).is_some());
     }
 
+    #[test]
+    fn sync_listener_receives_single_reorganization_notification() {
+        let genesis = zebra_test_data::genesis();
+        let storage = Arc::new(BlockChainDatabase::init_test_chain(vec![
+            zebra_test_data::genesis().into(),
+        ]));
+
+        let (_, _, sync) = create_sync(Some(storage), None);
+        let data = Arc::new(Mutex::new(DummySyncListenerData::default()));
+        sync.install_sync_listener(Box::new(DummySyncListener::new(data.clone())));
+
+        let fork1 = zebra_test_data::build_n_empty_blocks_from(3, 100, &genesis.block_header);
+        let fork2 = zebra_test_data::build_n_empty_blocks_from(4, 200, &genesis.block_header);
+
+        sync.on_headers(
+            1,
+            fork1
+                .iter()
+                .map(|block| block.block_header.clone().into())
+                .collect(),
+        );
+        sync.on_headers(
+            2,
+            fork2
+                .iter()
+                .map(|block| block.block_header.clone().into())
+                .collect(),
+        );
+
+        // genesis -> fork1[0] -> fork1[1] -> fork1[2] is the main chain
+        for block in &fork1 {
+            sync.on_block(1, block.clone().into());
+        }
+        for block in &fork2[..3] {
+            sync.on_block(2, block.clone().into());
+        }
+        assert!(data.lock().reorganizations.is_empty());
+        assert!(data.lock().disconnected_blocks.is_empty());
+        let best_blocks_before_reorg = data.lock().best_blocks.len();
+        let connected_blocks_before_reorg = data.lock().connected_blocks.len();
+
+        // genesis -> fork2[0] -> fork2[1] -> fork2[2] -> fork2[3] becomes the main chain
+        sync.on_block(2, fork2[3].clone().into());
+        assert_eq!(
+            data.lock().reorganizations,
+            vec![(
+                vec![fork1[2].hash(), fork1[1].hash(), fork1[0].hash()],
+                vec![
+                    fork2[0].hash(),
+                    fork2[1].hash(),
+                    fork2[2].hash(),
+                    fork2[3].hash()
+                ],
+            )]
+        );
+        assert_eq!(
+            data.lock().disconnected_blocks,
+            vec![fork1[2].hash(), fork1[1].hash(), fork1[0].hash()]
+        );
+        assert_eq!(
+            data.lock().connected_blocks[connected_blocks_before_reorg..].to_vec(),
+            vec![
+                fork2[0].hash(),
+                fork2[1].hash(),
+                fork2[2].hash(),
+                fork2[3].hash()
+            ]
+        );
+        assert_eq!(data.lock().best_blocks.len(), best_blocks_before_reorg + 1);
+        assert_eq!(data.lock().best_blocks.last(), Some(&fork2[3].hash()));
+    }
+
+    #[test]
+    fn sync_listener_receives_rescanned_blocks() {
+        let (_, _, sync) = create_sync(Some(storage_with_block1()), None);
+        let data = Arc::new(Mutex::new(DummySyncListenerData::default()));
+        sync.install_sync_listener(Box::new(DummySyncListener::new(data.clone())));
+
+        assert_eq!(sync.rescan_blockchain(0), Some((0, 1)));
+        assert_eq!(
+            data.lock().best_blocks,
+            vec![
+                zebra_test_data::genesis().hash(),
+                zebra_test_data::block_h1().hash()
+            ]
+        );
+
+        assert_eq!(sync.rescan_blockchain(1), Some((1, 1)));
+        assert_eq!(data.lock().best_blocks.len(), 3);
+
+        // nothing to rescan above the best block
+        assert_eq!(sync.rescan_blockchain(2), None);
+        assert_eq!(data.lock().best_blocks.len(), 3);
+    }
+
+    #[test]
+    fn rescan_stops_at_fork_point_after_reorganization() {
+        let genesis = zebra_test_data::genesis();
+        let storage = Arc::new(BlockChainDatabase::init_test_chain(vec![
+            zebra_test_data::genesis().into(),
+        ]));
+
+        let (_, core, sync) = create_sync(Some(storage), None);
+        let fork1 = zebra_test_data::build_n_empty_blocks_from(3, 100, &genesis.block_header);
+        let fork2 = zebra_test_data::build_n_empty_blocks_from(4, 200, &genesis.block_header);
+        sync.on_headers(
+            1,
+            fork1
+                .iter()
+                .map(|block| block.block_header.clone().into())
+                .collect(),
+        );
+        sync.on_headers(
+            2,
+            fork2
+                .iter()
+                .map(|block| block.block_header.clone().into())
+                .collect(),
+        );
+        for block in &fork1 {
+            sync.on_block(1, block.clone().into());
+        }
+        for block in &fork2[..3] {
+            sync.on_block(2, block.clone().into());
+        }
+
+        // rescan is started when fork1[2] is the best block
+        let mut stop_block = core.lock().chain().best_storage_block();
+        assert_eq!(stop_block.hash, fork1[2].hash());
+
+        // genesis -> fork2[0] -> fork2[1] -> fork2[2] -> fork2[3] becomes the main chain
+        sync.on_block(2, fork2[3].clone().into());
+
+        // fork2 blocks have been passed to listeners on reorganization => only genesis is rescanned
+        let data = Arc::new(Mutex::new(DummySyncListenerData::default()));
+        sync.install_sync_listener(Box::new(DummySyncListener::new(data.clone())));
+        assert_eq!(
+            core.lock().rescan_blockchain_batch(0, &mut stop_block),
+            Some(1)
+        );
+        assert_eq!(stop_block.hash, genesis.hash());
+        assert_eq!(data.lock().best_blocks, vec![genesis.hash()]);
+        assert_eq!(
+            core.lock().rescan_blockchain_batch(1, &mut stop_block),
+            None
+        );
+    }
+
     #[test]
     fn sync_listener_calls() {
         let (_, _, sync) = create_sync(None, None);
@@ -3444,4 +4073,34 @@ pub mod tests {
         assert_eq!(sync.chain().block_state(&hash1), BlockState::Stored);
         assert_eq!(sync.chain().block_state(&hash2), BlockState::Stored); // pre-fix: Verifying
     }
+
+    #[test]
+    fn reconsidered_block_is_restored() {
+        let (_, core, sync) = create_sync(Some(storage_with_block1()), None);
+        let block1: Block = zebra_test_data::block_h1();
+        let hash1 = block1.hash();
+        let block2 = zebra_test_data::block_builder()
+            .header()
+            .parent(hash1.clone())
+            .build()
+            .build();
+
+        // invalidated best block is kept in the storage as a side chain block
+        assert_eq!(sync.invalidate_block(&hash1), Ok(()));
+        assert_eq!(core.lock().chain().best_storage_block().number, 0);
+        assert_eq!(core.lock().chain().block_state(&hash1), BlockState::Stored);
+
+        // => descendant of invalidated block is ignored
+        sync.on_headers(0, vec![block2.block_header.clone().into()]);
+        assert_eq!(
+            core.lock().chain().block_state(&block2.hash()),
+            BlockState::Unknown
+        );
+
+        // reconsidered block becomes canonical again
+        assert_eq!(sync.reconsider_block(&hash1), Ok(true));
+        assert_eq!(sync.reconsider_block(&hash1), Ok(false));
+        assert_eq!(core.lock().chain().best_storage_block().number, 1);
+        assert_eq!(core.lock().chain().best_storage_block().hash, hash1);
+    }
 }
@@ -31,6 +31,8 @@ pub enum Task {
     Transaction(PeerIndex, IndexedTransaction),
     /// Send notfound
     NotFound(PeerIndex, types::NotFound),
+    /// Send reject
+    Reject(PeerIndex, types::Reject),
     /// Send inventory
     Inventory(PeerIndex, types::Inv),
     /// Send headers
@@ -123,6 +125,13 @@ impl LocalSynchronizationTaskExecutor {
         }
     }
 
+    fn execute_reject(&self, peer_index: PeerIndex, reject: types::Reject) {
+        if let Some(connection) = self.peers.connection(peer_index) {
+            trace!(target: "sync", "Sending reject of '{}' message to peer#{}: {}", reject.message, peer_index, reject.reason);
+            connection.send_reject(&reject);
+        }
+    }
+
     fn execute_inventory(&self, peer_index: PeerIndex, inventory: types::Inv) {
         if let Some(connection) = self.peers.connection(peer_index) {
             trace!(target: "sync", "Sending inventory to peer#{} with {} items", peer_index, inventory.inventory.len());
@@ -147,7 +156,21 @@ impl LocalSynchronizationTaskExecutor {
 
     fn execute_relay_block(&self, block: IndexedBlock) {
         for peer_index in self.peers.enumerate() {
-            match self.peers.filter_block(peer_index, &block) {
+            let announcement_type = self.peers.filter_block(peer_index, &block);
+            if let BlockAnnouncementType::DoNotAnnounce = announcement_type {
+                continue;
+            }
+
+            // peers with loaded bloom filter are receiving merkleblock + matched transactions
+            if let Some(artefacts) = self.peers.build_merkle_block(peer_index, &block) {
+                self.execute_merkleblock(peer_index, block.hash().clone(), artefacts.merkleblock);
+                for transaction in artefacts.matching_transactions {
+                    self.execute_transaction(peer_index, transaction);
+                }
+                continue;
+            }
+
+            match announcement_type {
                 BlockAnnouncementType::SendInventory => {
                     self.execute_inventory(
                         peer_index,
@@ -201,6 +224,7 @@ impl TaskExecutor for LocalSynchronizationTaskExecutor {
                 self.execute_transaction(peer_index, transaction)
             }
             Task::NotFound(peer_index, notfound) => self.execute_notfound(peer_index, notfound),
+            Task::Reject(peer_index, reject) => self.execute_reject(peer_index, reject),
             Task::Inventory(peer_index, inventory) => self.execute_inventory(peer_index, inventory),
             Task::Headers(peer_index, headers, request_id) => {
                 self.execute_headers(peer_index, headers, request_id)
@@ -298,6 +322,43 @@ pub mod tests {
         );
     }
 
+    #[test]
+    fn relay_new_block_with_bloom_filter() {
+        let peers = Arc::new(PeersImpl::default());
+        let executor = LocalSynchronizationTaskExecutor::new(peers.clone());
+
+        let tx1: Transaction = zebra_test_data::TransactionBuilder::with_output(10).into();
+        let tx2: Transaction = zebra_test_data::TransactionBuilder::with_output(20).into();
+        let tx2_hash = tx2.hash();
+        let block: IndexedBlock = zebra_test_data::block_builder()
+            .header()
+            .parent(zebra_test_data::genesis().hash())
+            .build()
+            .with_transaction(tx1)
+            .with_transaction(tx2)
+            .build()
+            .into();
+
+        // peer#1 wants tx2
+        let c1 = DummyOutboundSyncConnection::new();
+        peers.insert(1, Services::default(), c1.clone());
+        peers.set_bloom_filter(1, default_filterload());
+        peers.update_bloom_filter(1, make_filteradd(&*tx2_hash));
+        // peer#2 has default behaviour (no filter)
+        let c2 = DummyOutboundSyncConnection::new();
+        peers.insert(2, Services::default(), c2.clone());
+
+        executor.execute(Task::RelayNewBlock(block));
+
+        let mut messages = c1.messages.lock();
+        assert_eq!(*messages.entry("merkleblock".to_owned()).or_insert(0), 1);
+        assert_eq!(*messages.entry("transaction".to_owned()).or_insert(0), 1);
+        assert_eq!(*messages.entry("inventory".to_owned()).or_insert(0), 0);
+        let mut messages = c2.messages.lock();
+        assert_eq!(*messages.entry("merkleblock".to_owned()).or_insert(0), 0);
+        assert_eq!(*messages.entry("inventory".to_owned()).or_insert(0), 1);
+    }
+
     #[test]
     fn relay_new_transaction_with_bloom_filter() {
         let peers = Arc::new(PeersImpl::default());
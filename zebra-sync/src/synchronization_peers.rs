@@ -7,6 +7,11 @@ use zebra_message::{types, Services};
 use zebra_p2p::OutboundSyncConnectionRef;
 use zebra_primitives::hash::H256;
 
+/// Ban score, added to the peer when it is disconnected due to misbehavior
+const MISBEHAVING_BAN_SCORE: u32 = 50;
+/// Ban score, added to the peer when it is disconnected due to detected DOS attempt
+const DOS_BAN_SCORE: u32 = 100;
+
 /// Block announcement type
 #[derive(Debug, Clone, Copy)]
 pub enum BlockAnnouncementType {
@@ -65,8 +70,8 @@ pub trait PeersContainer {
 pub trait PeersFilters {
     /// Set up bloom filter for the connection
     fn set_bloom_filter(&self, peer_index: PeerIndex, filter: types::FilterLoad);
-    /// Update bloom filter for the connection
-    fn update_bloom_filter(&self, peer_index: PeerIndex, filter: types::FilterAdd);
+    /// Update bloom filter for the connection. Returns false if connection has no loaded filter
+    fn update_bloom_filter(&self, peer_index: PeerIndex, filter: types::FilterAdd) -> bool;
     /// Clear bloom filter for the connection
     fn clear_bloom_filter(&self, peer_index: PeerIndex);
     /// Set up fee filter for the connection
@@ -80,6 +85,13 @@ pub trait PeersFilters {
         transaction: &IndexedTransaction,
         transaction_fee_rate: Option<u64>,
     ) -> TransactionAnnouncementType;
+    /// Is memory pool transaction passing filters for the connection
+    fn filter_mempool_transaction(
+        &self,
+        peer_index: PeerIndex,
+        transaction: &IndexedTransaction,
+        transaction_fee_rate: Option<u64>,
+    ) -> bool;
     /// Remember known hash
     fn hash_known_as(&self, peer_index: PeerIndex, hash: H256, hash_type: KnownHashType);
     /// Is given hash known by peer as hash of given type
@@ -184,6 +196,7 @@ impl PeersContainer for PeersImpl {
     fn misbehaving(&self, peer_index: PeerIndex, reason: &str) {
         if let Some(peer) = self.peers.write().remove(&peer_index) {
             warn!(target: "sync", "Disconnecting from peer#{} due to misbehavior: {}", peer_index, reason);
+            peer.connection.note_misbehavior(MISBEHAVING_BAN_SCORE);
             peer.connection.close();
         }
     }
@@ -191,6 +204,7 @@ impl PeersContainer for PeersImpl {
     fn dos(&self, peer_index: PeerIndex, reason: &str) {
         if let Some(peer) = self.peers.write().remove(&peer_index) {
             warn!(target: "sync", "Disconnecting from peer#{} due to DoS: {}", peer_index, reason);
+            peer.connection.note_misbehavior(DOS_BAN_SCORE);
             peer.connection.close();
         }
     }
@@ -203,10 +217,12 @@ impl PeersFilters for PeersImpl {
         }
     }
 
-    fn update_bloom_filter(&self, peer_index: PeerIndex, filter: types::FilterAdd) {
-        if let Some(peer) = self.peers.write().get_mut(&peer_index) {
-            peer.filter.add(filter);
-        }
+    fn update_bloom_filter(&self, peer_index: PeerIndex, filter: types::FilterAdd) -> bool {
+        self.peers
+            .write()
+            .get_mut(&peer_index)
+            .map(|peer| peer.filter.add(filter))
+            .unwrap_or(false)
     }
 
     fn clear_bloom_filter(&self, peer_index: PeerIndex) {
@@ -249,6 +265,30 @@ impl PeersFilters for PeersImpl {
         TransactionAnnouncementType::DoNotAnnounce
     }
 
+    fn filter_mempool_transaction(
+        &self,
+        peer_index: PeerIndex,
+        transaction: &IndexedTransaction,
+        transaction_fee_rate: Option<u64>,
+    ) -> bool {
+        self.peers
+            .read()
+            .get(&peer_index)
+            .map(|peer| {
+                // light clients, which do not want transactions to be relayed, only get
+                // transactions matching their bloom filter
+                let is_relay_allowed = match peer.transaction_announcement_type {
+                    TransactionAnnouncementType::SendInventory => true,
+                    TransactionAnnouncementType::DoNotAnnounce => peer.filter.is_bloom_filter_set(),
+                };
+                is_relay_allowed
+                    && peer
+                        .filter
+                        .filter_transaction(transaction, transaction_fee_rate)
+            })
+            .unwrap_or(false)
+    }
+
     fn hash_known_as(&self, peer_index: PeerIndex, hash: H256, hash_type: KnownHashType) {
         if let Some(peer) = self.peers.write().get_mut(&peer_index) {
             peer.filter.hash_known_as(hash, hash_type)
@@ -6,8 +6,9 @@ use std::sync::Arc;
 use std::thread;
 use synchronization_executor::{Task, TaskExecutor};
 use types::{BlockHeight, ExecutorRef, MemoryPoolRef, PeerIndex, PeersRef, RequestId, StorageRef};
-use zebra_chain::IndexedTransaction;
+use zebra_chain::{IndexedBlock, IndexedTransaction};
 use zebra_message::{common, types};
+use zebra_miner::MemoryPoolOrderingStrategy;
 use zebra_primitives::hash::H256;
 
 /// Synchronization server task
@@ -308,7 +309,7 @@ where
                 }
             }
             common::InventoryType::MessageBlock => {
-                if let Some(block) = self.storage.block(next_item.hash.clone().into()) {
+                if let Some(block) = self.full_block(&next_item.hash) {
                     trace!(target: "sync", "'getblocks' response to peer#{} is ready with block {}", peer_index, next_item.hash.to_reversed_str());
                     self.executor.execute(Task::Block(peer_index, block));
                 } else {
@@ -316,7 +317,7 @@ where
                 }
             }
             common::InventoryType::MessageFilteredBlock => {
-                if let Some(block) = self.storage.block(next_item.hash.clone().into()) {
+                if let Some(block) = self.full_block(&next_item.hash) {
                     let message_artefacts = self.peers.build_merkle_block(peer_index, &block);
                     if let Some(message_artefacts) = message_artefacts {
                         // send merkleblock first
@@ -346,6 +347,15 @@ where
         Some(ServerTask::ReversedGetData(peer_index, message, notfound))
     }
 
+    /// Read block with all its transactions. Pruned blocks can't be served to peers.
+    fn full_block(&self, hash: &H256) -> Option<IndexedBlock> {
+        if self.storage.is_pruned(hash.clone().into()) {
+            return None;
+        }
+
+        self.storage.block(hash.clone().into())
+    }
+
     fn serve_get_blocks(&self, peer_index: PeerIndex, message: types::GetBlocks) {
         if let Some(block_height) =
             self.locate_best_common_block(&message.hash_stop, &message.block_locator_hashes)
@@ -413,19 +423,27 @@ where
         let inventory: Vec<_> = self
             .memory_pool
             .read()
-            .get_transactions_ids()
-            .into_iter()
-            .map(common::InventoryVector::tx)
+            .iter(MemoryPoolOrderingStrategy::ByTimestamp)
+            .filter(|entry| {
+                let transaction =
+                    IndexedTransaction::new(entry.hash.clone(), entry.transaction.clone());
+                let fee_rate = entry.miner_fee / entry.size as u64;
+                self.peers
+                    .filter_mempool_transaction(peer_index, &transaction, Some(fee_rate))
+            })
+            .map(|entry| common::InventoryVector::tx(entry.hash.clone()))
             .collect();
         // empty inventory messages are invalid according to regtests, while empty headers messages are valid
         if !inventory.is_empty() {
             trace!(target: "sync", "'mempool' response to peer#{} is ready with {} transactions", peer_index, inventory.len());
-            self.executor.execute(Task::Inventory(
-                peer_index,
-                types::Inv::with_inventory(inventory),
-            ));
+            for inventory in inventory.chunks(types::INV_MAX_INVENTORY_LEN) {
+                self.executor.execute(Task::Inventory(
+                    peer_index,
+                    types::Inv::with_inventory(inventory.to_vec()),
+                ));
+            }
         } else {
-            trace!(target: "sync", "'mempool' request from peer#{} is ignored as pool is empty", peer_index);
+            trace!(target: "sync", "'mempool' request from peer#{} is ignored as there are no transactions to announce", peer_index);
         }
     }
 
@@ -472,8 +490,11 @@ pub mod tests {
     use std::sync::Arc;
     use synchronization_executor::tests::DummyTaskExecutor;
     use synchronization_executor::Task;
-    use synchronization_peers::{PeersContainer, PeersFilters, PeersImpl};
+    use synchronization_peers::{
+        PeersContainer, PeersFilters, PeersImpl, PeersOptions, TransactionAnnouncementType,
+    };
     use types::{ExecutorRef, MemoryPoolRef, PeerIndex, PeersRef, StorageRef};
+    use utils::KnownHashType;
     use zebra_chain::Transaction;
     use zebra_db::BlockChainDatabase;
     use zebra_message::common::{InventoryType, InventoryVector, Services};
@@ -550,6 +571,60 @@ pub mod tests {
         );
     }
 
+    #[test]
+    fn server_getdata_responds_notfound_when_block_is_pruned() {
+        let b0 = zebra_test_data::block_builder()
+            .header()
+            .build()
+            .transaction()
+            .output()
+            .value(1)
+            .build()
+            .build()
+            .build();
+        let b1 = zebra_test_data::block_builder()
+            .header()
+            .parent(b0.hash())
+            .build()
+            .transaction()
+            .input()
+            .hash(b0.transactions[0].hash())
+            .index(0)
+            .build()
+            .build()
+            .build();
+        let b0_hash = b0.hash();
+        let mut storage = BlockChainDatabase::init_test_chain(vec![b0.into(), b1.into()]);
+        storage.set_max_fork_route(0);
+        assert_eq!(storage.prune_below(1), Ok(1));
+
+        let executor = DummyTaskExecutor::new();
+        let server = ServerImpl::new(
+            Arc::new(PeersImpl::default()),
+            Arc::new(storage),
+            Arc::new(RwLock::new(MemoryPool::new())),
+            executor.clone(),
+        );
+        // when asking for pruned block
+        let inventory = vec![InventoryVector {
+            inv_type: InventoryType::MessageBlock,
+            hash: b0_hash,
+        }];
+        server.execute(ServerTask::GetData(
+            0,
+            types::GetData::with_inventory(inventory.clone()),
+        ));
+        // => respond with notfound
+        let tasks = DummyTaskExecutor::wait_tasks(executor);
+        assert_eq!(
+            tasks,
+            vec![Task::NotFound(
+                0,
+                types::NotFound::with_inventory(inventory)
+            )]
+        );
+    }
+
     #[test]
     fn server_getdata_responds_block_when_block_is_found() {
         let (_, _, executor, _, server) = create_synchronization_server();
@@ -690,13 +765,15 @@ pub mod tests {
 
     #[test]
     fn server_mempool_responds_inventory_when_non_empty_memory_pool() {
-        let (_, memory_pool, executor, _, server) = create_synchronization_server();
+        let (_, memory_pool, executor, peers, server) = create_synchronization_server();
+        peers.insert(0, Services::default(), DummyOutboundSyncConnection::new());
         // when memory pool is non-empty
         let transaction = Transaction::default();
         let transaction_hash = transaction.hash();
         memory_pool
             .write()
-            .insert_verified(transaction.into(), &NonZeroFeeCalculator);
+            .insert_verified(transaction.into(), &NonZeroFeeCalculator)
+            .unwrap();
         // when asking for memory pool transactions ids
         server.execute(ServerTask::Mempool(0));
         // => respond with inventory
@@ -711,6 +788,59 @@ pub mod tests {
         );
     }
 
+    #[test]
+    fn server_mempool_responds_filtered_inventory() {
+        let (_, memory_pool, executor, peers, server) = create_synchronization_server();
+        let tx1: Transaction = zebra_test_data::TransactionBuilder::with_output(10).into();
+        let tx1_hash = tx1.hash();
+        let tx2: Transaction = zebra_test_data::TransactionBuilder::with_output(20).into();
+        let tx2_hash = tx2.hash();
+        memory_pool
+            .write()
+            .insert_verified(tx1.into(), &NonZeroFeeCalculator)
+            .unwrap();
+        memory_pool
+            .write()
+            .insert_verified(tx2.into(), &NonZeroFeeCalculator)
+            .unwrap();
+
+        // light client, which do not want transactions to be relayed
+        peers.insert(0, Services::default(), DummyOutboundSyncConnection::new());
+        peers.set_transaction_announcement_type(0, TransactionAnnouncementType::DoNotAnnounce);
+        // peer, which already knows tx1
+        peers.insert(1, Services::default(), DummyOutboundSyncConnection::new());
+        peers.hash_known_as(1, tx1_hash.clone(), KnownHashType::Transaction);
+
+        // => light client gets nothing until bloom filter is set
+        server.execute(ServerTask::Mempool(0));
+        let tasks = DummyTaskExecutor::wait_tasks_for(executor.clone(), 100);
+        assert_eq!(tasks, vec![]);
+
+        // => light client gets transactions, matching its bloom filter
+        peers.set_bloom_filter(0, default_filterload());
+        peers.update_bloom_filter(0, make_filteradd(&*tx2_hash));
+        server.execute(ServerTask::Mempool(0));
+        let tasks = DummyTaskExecutor::wait_tasks(executor.clone());
+        assert_eq!(
+            tasks,
+            vec![Task::Inventory(
+                0,
+                types::Inv::with_inventory(vec![InventoryVector::tx(tx2_hash.clone())])
+            )]
+        );
+
+        // => peer does not get transactions it already knows
+        server.execute(ServerTask::Mempool(1));
+        let tasks = DummyTaskExecutor::wait_tasks(executor);
+        assert_eq!(
+            tasks,
+            vec![Task::Inventory(
+                1,
+                types::Inv::with_inventory(vec![InventoryVector::tx(tx2_hash)])
+            )]
+        );
+    }
+
     #[test]
     fn server_getdata_responds_notfound_when_transaction_is_inaccessible() {
         let (_, _, executor, _, server) = create_synchronization_server();
@@ -749,7 +879,8 @@ pub mod tests {
         {
             memory_pool
                 .write()
-                .insert_verified(tx_verified.clone().into(), &NonZeroFeeCalculator);
+                .insert_verified(tx_verified.clone().into(), &NonZeroFeeCalculator)
+                .unwrap();
         }
         // when asking for known in-memory transaction
         let inventory = vec![InventoryVector {
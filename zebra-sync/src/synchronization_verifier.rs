@@ -8,11 +8,12 @@ use time::get_time;
 use types::{BlockHeight, MemoryPoolRef, PeerIndex, StorageRef};
 use utils::MemoryPoolTransactionOutputProvider;
 use zebra_chain::{IndexedBlock, IndexedBlockHeader, IndexedTransaction};
+use zebra_message::types::reject::RejectCode;
 use zebra_network::ConsensusParams;
 use zebra_primitives::hash::H256;
 use zebra_verification::{
     BackwardsCompatibleChainVerifier as ChainVerifier, Error as VerificationError,
-    VerificationLevel, Verify as VerificationVerify,
+    TransactionError, VerificationLevel, Verify as VerificationVerify,
 };
 use VerificationParameters;
 
@@ -23,6 +24,8 @@ pub enum PartiallyVerifiedBlock {
     NotVerified(IndexedBlock),
     /// Block that has its header pre-verified (mind that AcceptHeader isn't called).
     HeaderPreVerified(IndexedBlock),
+    /// Block that has been fully verified already (i.e. locally submitted block).
+    Verified(IndexedBlock),
 }
 
 /// Headers verification events sink
@@ -51,8 +54,8 @@ pub trait BlockVerificationSink: Send + Sync + 'static {
 pub trait TransactionVerificationSink: Send + Sync + 'static {
     /// When transaction verification has completed successfully.
     fn on_transaction_verification_success(&self, transaction: IndexedTransaction);
-    /// When transaction verification has failed.
-    fn on_transaction_verification_error(&self, err: &str, hash: &H256);
+    /// When transaction verification has failed. Reject code is the one to report to the peer.
+    fn on_transaction_verification_error(&self, err: &str, code: RejectCode, hash: &H256);
 }
 
 /// Verification events sink
@@ -82,6 +85,9 @@ pub trait Verifier: Send + Sync + 'static {
     fn verify_block(&self, block: PartiallyVerifiedBlock);
     /// Verify transaction
     fn verify_transaction(&self, height: BlockHeight, transaction: IndexedTransaction);
+    /// Stop verification. Returns when the task in progress (if any) is completed.
+    /// Queued and new tasks are ignored
+    fn stop(&self) {}
 }
 
 /// Asynchronous synchronization verifier
@@ -89,13 +95,17 @@ pub struct AsyncVerifier {
     /// Verification work transmission channel.
     verification_work_sender: Mutex<Sender<VerificationTask>>,
     /// Verification thread.
-    verification_worker_thread: Option<thread::JoinHandle<()>>,
+    verification_worker_thread: Mutex<Option<thread::JoinHandle<()>>>,
+    /// True if verification has been stopped && queued tasks must be ignored.
+    is_stopped: Arc<AtomicBool>,
 }
 
 /// Chain verifier wrapper to deal with verification parameters.
 pub struct ChainVerifierWrapper {
     /// Original verifier.
     pub verifier: Arc<ChainVerifier>,
+    /// Storage reference.
+    storage: StorageRef,
     /// Verification parameters.
     verification_params: VerificationParameters,
     /// True if we have passed verification edge && full verification is required.
@@ -107,7 +117,8 @@ impl PartiallyVerifiedBlock {
     pub fn hash(&self) -> &H256 {
         match *self {
             PartiallyVerifiedBlock::NotVerified(ref block)
-            | PartiallyVerifiedBlock::HeaderPreVerified(ref block) => block.hash(),
+            | PartiallyVerifiedBlock::HeaderPreVerified(ref block)
+            | PartiallyVerifiedBlock::Verified(ref block) => block.hash(),
         }
     }
 }
@@ -117,6 +128,7 @@ impl From<PartiallyVerifiedBlock> for IndexedBlock {
         match block {
             PartiallyVerifiedBlock::NotVerified(block) => block,
             PartiallyVerifiedBlock::HeaderPreVerified(block) => block,
+            PartiallyVerifiedBlock::Verified(block) => block,
         }
     }
 }
@@ -140,6 +152,7 @@ impl ChainVerifierWrapper {
         );
         ChainVerifierWrapper {
             verifier: verifier,
+            storage: storage.clone(),
             verification_params: verification_params,
             enforce_full_verification: enforce_full_verification,
         }
@@ -153,23 +166,49 @@ impl ChainVerifierWrapper {
         self.verifier.verify_block_header(header)
     }
 
-    /// Verify block.
-    pub fn verify_block(&self, block: &PartiallyVerifiedBlock) -> Result<(), VerificationError> {
-        let enforce_full_verification =
-            if block.hash() == &self.verification_params.verification_edge {
+    /// Select verification level for the block.
+    pub fn select_verification_level(&self, block: &PartiallyVerifiedBlock) -> VerificationLevel {
+        let enforce_full_verification = match self.verification_params.verification_threshold {
+            Some(ref threshold) => {
+                let block = match *block {
+                    PartiallyVerifiedBlock::NotVerified(ref block)
+                    | PartiallyVerifiedBlock::HeaderPreVerified(ref block)
+                    | PartiallyVerifiedBlock::Verified(ref block) => block,
+                };
+                let height = self
+                    .storage
+                    .block_number(&block.header.raw.previous_header_hash)
+                    .map(|parent_height| parent_height + 1);
+                threshold.select_full_verification(height)
+            }
+            None if block.hash() == &self.verification_params.verification_edge => {
                 self.enforce_full_verification
                     .store(true, Ordering::Relaxed);
                 true
-            } else {
-                self.enforce_full_verification.load(Ordering::Relaxed)
-            };
+            }
+            None => self.enforce_full_verification.load(Ordering::Relaxed),
+        };
 
-        // select base verification level
-        let mut verification_level = if enforce_full_verification {
+        if enforce_full_verification {
             VerificationLevel::FULL
         } else {
             self.verification_params.verification_level
-        };
+        }
+    }
+
+    /// Returns true if the last block (or, when verification edge is used, all following blocks)
+    /// has been validated using VerificationLevel::Full level.
+    pub fn is_full_verification_selected(&self) -> bool {
+        match self.verification_params.verification_threshold {
+            Some(ref threshold) => threshold.is_full_verification_selected(),
+            None => self.enforce_full_verification.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Verify block.
+    pub fn verify_block(&self, block: &PartiallyVerifiedBlock) -> Result<(), VerificationError> {
+        // select base verification level
+        let mut verification_level = self.select_verification_level(block);
 
         // update verification level with hints, if necessary
         let block = match *block {
@@ -178,6 +217,8 @@ impl ChainVerifierWrapper {
                 verification_level.insert(VerificationLevel::HINT_HEADER_PRE_VERIFIED);
                 block
             }
+            // block has passed full verification => verifying it again is pointless
+            PartiallyVerifiedBlock::Verified(_) => return Ok(()),
         };
 
         self.verifier.verify(verification_level, block)
@@ -205,9 +246,11 @@ impl AsyncVerifier {
         verification_params: VerificationParameters,
     ) -> Self {
         let (verification_work_sender, verification_work_receiver) = channel();
+        let is_stopped = Arc::new(AtomicBool::new(false));
+        let worker_is_stopped = is_stopped.clone();
         AsyncVerifier {
             verification_work_sender: Mutex::new(verification_work_sender),
-            verification_worker_thread: Some(
+            verification_worker_thread: Mutex::new(Some(
                 thread::Builder::new()
                     .name(thread_name)
                     .spawn(move || {
@@ -219,10 +262,12 @@ impl AsyncVerifier {
                             memory_pool,
                             verifier,
                             verification_work_receiver,
+                            worker_is_stopped,
                         )
                     })
                     .expect("Error creating sync verification thread"),
-            ),
+            )),
+            is_stopped: is_stopped,
         }
     }
 
@@ -233,8 +278,13 @@ impl AsyncVerifier {
         memory_pool: MemoryPoolRef,
         verifier: ChainVerifierWrapper,
         work_receiver: Receiver<VerificationTask>,
+        is_stopped: Arc<AtomicBool>,
     ) {
         while let Some(task) = work_receiver.recv().ok() {
+            if is_stopped.load(Ordering::SeqCst) {
+                break;
+            }
+
             if !AsyncVerifier::execute_single_task(&sink, &storage, &memory_pool, &verifier, task) {
                 break;
             }
@@ -290,6 +340,7 @@ impl AsyncVerifier {
                     }
                 }
                 VerificationTask::VerifyTransaction(height, transaction) => {
+                    let min_relay_fee_rate = memory_pool.read().min_relay_fee_rate();
                     // output provider must check previous outputs in both storage && memory pool
                     match MemoryPoolTransactionOutputProvider::for_transaction(
                         storage.clone(),
@@ -299,6 +350,7 @@ impl AsyncVerifier {
                         Err(e) => {
                             sink.on_transaction_verification_error(
                                 &format!("{:?}", e),
+                                transaction_reject_code(&e),
                                 &transaction.hash,
                             );
                             continue; // with new verification sub-task
@@ -311,12 +363,14 @@ impl AsyncVerifier {
                                 height,
                                 time,
                                 &transaction,
+                                min_relay_fee_rate,
                             ) {
                                 Ok(_) => {
                                     sink.on_transaction_verification_success(transaction.into())
                                 }
                                 Err(e) => sink.on_transaction_verification_error(
                                     &format!("{:?}", e),
+                                    transaction_reject_code(&e),
                                     &transaction.hash,
                                 ),
                             }
@@ -329,11 +383,18 @@ impl AsyncVerifier {
 
         true
     }
+
+    /// Sends task to the verification thread. Tasks are ignored once verification is stopped.
+    fn send_task(&self, task: VerificationTask) {
+        if self.verification_work_sender.lock().send(task).is_err() {
+            trace!(target: "sync", "Ignoring verification task: verification thread is stopped");
+        }
+    }
 }
 
 impl Drop for AsyncVerifier {
     fn drop(&mut self) {
-        if let Some(join_handle) = self.verification_worker_thread.take() {
+        if let Some(join_handle) = self.verification_worker_thread.lock().take() {
             {
                 let verification_work_sender = self.verification_work_sender.lock();
                 // ignore send error here <= destructing anyway
@@ -346,24 +407,27 @@ impl Drop for AsyncVerifier {
 
 impl Verifier for AsyncVerifier {
     fn verify_headers(&self, peer: PeerIndex, headers: Vec<IndexedBlockHeader>) {
-        self.verification_work_sender
-            .lock()
-            .send(VerificationTask::VerifyHeaders(peer, headers))
-            .expect("Verification thread have the same lifetime as `AsyncVerifier`");
+        self.send_task(VerificationTask::VerifyHeaders(peer, headers));
     }
 
     fn verify_block(&self, block: PartiallyVerifiedBlock) {
-        self.verification_work_sender
-            .lock()
-            .send(VerificationTask::VerifyBlock(block))
-            .expect("Verification thread have the same lifetime as `AsyncVerifier`");
+        self.send_task(VerificationTask::VerifyBlock(block));
     }
 
     fn verify_transaction(&self, height: BlockHeight, transaction: IndexedTransaction) {
-        self.verification_work_sender
-            .lock()
-            .send(VerificationTask::VerifyTransaction(height, transaction))
-            .expect("Verification thread have the same lifetime as `AsyncVerifier`");
+        self.send_task(VerificationTask::VerifyTransaction(height, transaction));
+    }
+
+    fn stop(&self) {
+        if let Some(join_handle) = self.verification_worker_thread.lock().take() {
+            self.is_stopped.store(true, Ordering::SeqCst);
+            // ignore send error here <= thread has already stopped
+            let _ = self
+                .verification_work_sender
+                .lock()
+                .send(VerificationTask::Stop);
+            join_handle.join().expect("Clean shutdown.");
+        }
     }
 }
 
@@ -425,6 +489,21 @@ where
     }
 }
 
+/// Returns reject code (BIP61) for the transaction verification error
+pub fn transaction_reject_code(error: &TransactionError) -> RejectCode {
+    match *error {
+        TransactionError::UsingSpentOutput(..)
+        | TransactionError::UnspentTransactionWithTheSameHash
+        | TransactionError::JoinSplitDeclared(_)
+        | TransactionError::SaplingDeclared(_) => RejectCode::Duplicate,
+        TransactionError::DustOutput(_) => RejectCode::Dust,
+        TransactionError::MinSize
+        | TransactionError::Sigops(_)
+        | TransactionError::SigopsP2SH(_) => RejectCode::Nonstandard,
+        _ => RejectCode::Invalid,
+    }
+}
+
 #[cfg(test)]
 pub mod tests {
     extern crate zebra_test_data;
@@ -439,6 +518,7 @@ pub mod tests {
     use synchronization_client_core::CoreVerificationSink;
     use synchronization_executor::tests::DummyTaskExecutor;
     use types::{BlockHeight, MemoryPoolRef, PeerIndex, StorageRef};
+    use utils::SynchronizationState;
     use zebra_chain::{IndexedBlock, IndexedBlockHeader, IndexedTransaction};
     use zebra_db::BlockChainDatabase;
     use zebra_network::{ConsensusParams, Network};
@@ -448,7 +528,7 @@ pub mod tests {
         BackwardsCompatibleChainVerifier as ChainVerifier, Error as VerificationError,
         TransactionError, VerificationLevel,
     };
-    use VerificationParameters;
+    use {VerificationParameters, VerificationThreshold};
 
     #[derive(Default)]
     pub struct DummyVerifier {
@@ -480,6 +560,7 @@ pub mod tests {
                 VerificationParameters {
                     verification_level: VerificationLevel::FULL,
                     verification_edge: 0u8.into(),
+                    verification_threshold: None,
                 },
             ));
         }
@@ -526,7 +607,11 @@ pub mod tests {
         fn verify_transaction(&self, _height: BlockHeight, transaction: IndexedTransaction) {
             match self.sink {
                 Some(ref sink) => match self.errors.get(&transaction.hash) {
-                    Some(err) => sink.on_transaction_verification_error(&err, &transaction.hash),
+                    Some(err) => sink.on_transaction_verification_error(
+                        &err,
+                        RejectCode::Invalid,
+                        &transaction.hash,
+                    ),
                     None => {
                         if self.actual_checks.contains(&transaction.hash) {
                             let next_block_height =
@@ -566,6 +651,7 @@ pub mod tests {
                 VerificationParameters {
                     verification_level: VerificationLevel::NO_VERIFICATION,
                     verification_edge: zebra_test_data::genesis().hash(),
+                    verification_threshold: None,
                 }
             )
             .enforce_full_verification
@@ -580,6 +666,7 @@ pub mod tests {
             VerificationParameters {
                 verification_level: VerificationLevel::NO_VERIFICATION,
                 verification_edge: zebra_test_data::block_h1().hash(),
+                verification_threshold: None,
             },
         );
         assert_eq!(
@@ -594,6 +681,73 @@ pub mod tests {
         );
     }
 
+    #[test]
+    fn verifier_wrapper_selects_level_using_threshold() {
+        let storage: StorageRef = Arc::new(BlockChainDatabase::init_test_chain(vec![
+            zebra_test_data::genesis().into(),
+        ]));
+        let verifier = Arc::new(ChainVerifier::new(
+            storage.clone(),
+            ConsensusParams::new(Network::Unitest),
+        ));
+        let threshold = VerificationThreshold::new(2, 10);
+        let wrapper = |threshold: &VerificationThreshold| {
+            ChainVerifierWrapper::new(
+                verifier.clone(),
+                &storage,
+                VerificationParameters {
+                    verification_level: VerificationLevel::NO_VERIFICATION,
+                    verification_edge: zebra_test_data::block_h1().hash(),
+                    verification_threshold: Some(threshold.clone()),
+                },
+            )
+        };
+
+        // block#1 is below threshold => light verification
+        let block: IndexedBlock = zebra_test_data::block_h1().into();
+        let block: PartiallyVerifiedBlock = block.into();
+        let light_wrapper = wrapper(&threshold);
+        assert_eq!(
+            light_wrapper.select_verification_level(&block),
+            VerificationLevel::NO_VERIFICATION
+        );
+        assert!(!light_wrapper.is_full_verification_selected());
+
+        // threshold is lowered => block#1 is at threshold => full verification
+        threshold.set_height(1);
+        assert_eq!(
+            light_wrapper.select_verification_level(&block),
+            VerificationLevel::FULL
+        );
+        assert!(light_wrapper.is_full_verification_selected());
+        assert!(threshold.is_full_verification_selected());
+
+        // block#1 is near the best known header => full verification
+        threshold.set_height(100);
+        let sync_state = Arc::new(SynchronizationState::with_storage(storage.clone()));
+        sync_state.update_best_headers_height(5);
+        let tip_wrapper = wrapper(&threshold.clone().with_sync_state(sync_state.clone()));
+        assert_eq!(
+            tip_wrapper.select_verification_level(&block),
+            VerificationLevel::FULL
+        );
+
+        // more headers are known => block#1 is far from the tip => light verification
+        sync_state.update_best_headers_height(20);
+        assert_eq!(
+            tip_wrapper.select_verification_level(&block),
+            VerificationLevel::NO_VERIFICATION
+        );
+        assert!(!tip_wrapper.is_full_verification_selected());
+
+        // parent of block#2 is unknown => full verification
+        let block: IndexedBlock = zebra_test_data::block_h2().into();
+        assert_eq!(
+            tip_wrapper.select_verification_level(&block.into()),
+            VerificationLevel::FULL
+        );
+    }
+
     #[test]
     fn verification_level_header_accept_incorrect_transaction() {
         let consensus = ConsensusParams::new(Network::Unitest);
@@ -657,6 +811,7 @@ pub mod tests {
             VerificationParameters {
                 verification_level: VerificationLevel::HEADER,
                 verification_edge: 1.into(),
+                verification_threshold: None,
             },
         );
         assert_eq!(
@@ -671,6 +826,7 @@ pub mod tests {
             VerificationParameters {
                 verification_level: VerificationLevel::FULL,
                 verification_edge: 1.into(),
+                verification_threshold: None,
             },
         );
         assert_eq!(
@@ -704,6 +860,7 @@ pub mod tests {
             VerificationParameters {
                 verification_level: VerificationLevel::NO_VERIFICATION,
                 verification_edge: 1.into(),
+                verification_threshold: None,
             },
         );
         assert_eq!(wrapper.verify_block(&bad_block.clone().into()), Ok(()));
@@ -715,11 +872,18 @@ pub mod tests {
             VerificationParameters {
                 verification_level: VerificationLevel::FULL,
                 verification_edge: 1.into(),
+                verification_threshold: None,
             },
         );
         assert_eq!(
-            wrapper.verify_block(&bad_block.into()),
+            wrapper.verify_block(&bad_block.clone().into()),
             Err(VerificationError::Empty)
         );
+
+        // Ok(()) when block has been verified before
+        assert_eq!(
+            wrapper.verify_block(&PartiallyVerifiedBlock::Verified(bad_block)),
+            Ok(())
+        );
     }
 }
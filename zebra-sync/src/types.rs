@@ -9,7 +9,7 @@ use synchronization_peers::Peers;
 use synchronization_server::ServerImpl;
 use synchronization_verifier::AsyncVerifier;
 use utils::SynchronizationState;
-use zebra_miner::MemoryPool;
+use zebra_miner::{FeeEstimator, MemoryPool};
 use zebra_storage;
 
 pub use utils::BlockHeight;
@@ -29,6 +29,9 @@ pub type StorageRef = zebra_storage::SharedStore;
 /// Reference to memory pool
 pub type MemoryPoolRef = Arc<RwLock<MemoryPool>>;
 
+/// Reference to fee estimator
+pub type FeeEstimatorRef = Arc<RwLock<FeeEstimator>>;
+
 /// Shared synchronization state reference
 pub type SynchronizationStateRef = Arc<SynchronizationState>;
 
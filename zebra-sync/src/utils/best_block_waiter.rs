@@ -0,0 +1,90 @@
+use parking_lot::{Condvar, Mutex};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use zebra_primitives::hash::H256;
+use SyncListener;
+
+/// Synchronization listener, which wakes up threads, waiting for the best block change
+#[derive(Clone)]
+pub struct BestBlockWaiter {
+    data: Arc<BestBlockWaiterData>,
+}
+
+struct BestBlockWaiterData {
+    /// Hash of the current best storage block
+    best_block: Mutex<H256>,
+    /// Waiting threads are notified using this condvar
+    waiter: Condvar,
+}
+
+impl BestBlockWaiter {
+    pub fn new(best_block: H256) -> Self {
+        BestBlockWaiter {
+            data: Arc::new(BestBlockWaiterData {
+                best_block: Mutex::new(best_block),
+                waiter: Condvar::new(),
+            }),
+        }
+    }
+
+    /// Wait until best block is changed from `best_block` or until timeout elapses.
+    /// Returns true if best block has been changed.
+    pub fn wait(&self, best_block: &H256, timeout: Duration) -> bool {
+        let deadline = Instant::now() + timeout;
+        let mut current_best_block = self.data.best_block.lock();
+        while *current_best_block == *best_block {
+            if self
+                .data
+                .waiter
+                .wait_until(&mut current_best_block, deadline)
+                .timed_out()
+            {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+impl SyncListener for BestBlockWaiter {
+    fn synchronization_state_switched(&self, _is_synchronizing: bool) {}
+
+    fn best_storage_block_inserted(&self, block_hash: &H256) {
+        *self.data.best_block.lock() = block_hash.clone();
+        self.data.waiter.notify_all();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BestBlockWaiter;
+    use std::thread;
+    use std::time::Duration;
+    use zebra_primitives::hash::H256;
+    use SyncListener;
+
+    #[test]
+    fn best_block_waiter_times_out() {
+        let waiter = BestBlockWaiter::new(H256::from(1));
+        assert!(!waiter.wait(&H256::from(1), Duration::from_millis(10)));
+    }
+
+    #[test]
+    fn best_block_waiter_returns_if_best_block_is_already_changed() {
+        let waiter = BestBlockWaiter::new(H256::from(2));
+        assert!(waiter.wait(&H256::from(1), Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn best_block_waiter_wakes_up_on_best_block_change() {
+        let waiter = BestBlockWaiter::new(H256::from(1));
+        let listener = waiter.clone();
+        let handle = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(10));
+            listener.best_storage_block_inserted(&H256::from(2));
+        });
+        assert!(waiter.wait(&H256::from(1), Duration::from_secs(60)));
+        handle.join().unwrap();
+    }
+}
@@ -62,11 +62,15 @@ impl BloomFilter {
         self.bloom = Some(Mutex::new(BloomFilterData::with_filter_load(message)));
     }
 
-    /// Adds given data to current filter, so that new transactions can be accepted
-    pub fn update_bloom_filter(&self, message: types::FilterAdd) {
-        if let Some(ref bloom) = self.bloom {
-            let mut b = bloom.lock();
-            b.insert(&message.data);
+    /// Adds given data to current filter, so that new transactions can be accepted.
+    /// Returns false if there's no filter to update.
+    pub fn update_bloom_filter(&self, message: types::FilterAdd) -> bool {
+        match self.bloom {
+            Some(ref bloom) => {
+                bloom.lock().insert(&message.data);
+                true
+            }
+            None => false,
         }
     }
 
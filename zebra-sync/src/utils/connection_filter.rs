@@ -1,11 +1,8 @@
 use bit_vec::BitVec;
 use synchronization_peers::MerkleBlockArtefacts;
-use utils::{
-    build_partial_merkle_tree, BloomFilter, FeeRateFilter, KnownHashFilter, KnownHashType,
-};
+use utils::{build_merkle_block, BloomFilter, FeeRateFilter, KnownHashFilter, KnownHashType};
 use zebra_chain::{IndexedBlock, IndexedTransaction};
 use zebra_message::types;
-use zebra_primitives::bytes::Bytes;
 use zebra_primitives::hash::H256;
 
 /// Filter, which controls data relayed over connection.
@@ -48,14 +45,19 @@ impl ConnectionFilter {
             && self.bloom_filter.filter_transaction(transaction)
     }
 
+    /// Is bloom filter loaded for this connection
+    pub fn is_bloom_filter_set(&self) -> bool {
+        self.bloom_filter.is_set()
+    }
+
     /// Load filter
     pub fn load(&mut self, message: types::FilterLoad) {
         self.bloom_filter.set_bloom_filter(message);
     }
 
-    /// Add filter
-    pub fn add(&mut self, message: types::FilterAdd) {
-        self.bloom_filter.update_bloom_filter(message);
+    /// Add filter. Returns false if there's no loaded filter to update
+    pub fn add(&mut self, message: types::FilterAdd) -> bool {
+        self.bloom_filter.update_bloom_filter(message)
     }
 
     /// Clear filter
@@ -75,57 +77,21 @@ impl ConnectionFilter {
             return None;
         }
 
-        // prepare result
-        let all_len = block.transactions.len();
-        let mut result = MerkleBlockArtefacts {
-            merkleblock: types::MerkleBlock {
-                block_header: block.header.raw.clone(),
-                total_transactions: all_len as u32,
-                hashes: Vec::default(),
-                flags: Bytes::default(),
-            },
-            matching_transactions: Vec::new(),
-        };
-
-        // calculate hashes && match flags for all transactions
-        let (all_hashes, all_flags) = block.transactions.iter().fold(
-            (
-                Vec::<H256>::with_capacity(all_len),
-                BitVec::with_capacity(all_len),
-            ),
-            |(mut all_hashes, mut all_flags), t| {
-                let flag = self.bloom_filter.filter_transaction(t);
-                all_flags.push(flag);
-                all_hashes.push(t.hash.clone());
-                if flag {
-                    result.matching_transactions.push(t.clone());
-                }
-                (all_hashes, all_flags)
-            },
-        );
+        // calculate match flags for all transactions
+        let mut matching_transactions = Vec::new();
+        let mut all_flags = BitVec::with_capacity(block.transactions.len());
+        for t in &block.transactions {
+            let flag = self.bloom_filter.filter_transaction(t);
+            all_flags.push(flag);
+            if flag {
+                matching_transactions.push(t.clone());
+            }
+        }
 
-        // build partial merkle tree
-        let partial_merkle_tree = build_partial_merkle_tree(all_hashes, all_flags);
-        result.merkleblock.hashes.extend(partial_merkle_tree.hashes);
-        // to_bytes() converts [true, false, true] to 0b10100000
-        // while protocol requires [true, false, true] to be serialized as 0x00000101
-        result.merkleblock.flags = partial_merkle_tree
-            .flags
-            .to_bytes()
-            .into_iter()
-            .map(|b| {
-                ((b & 0b10000000) >> 7)
-                    | ((b & 0b01000000) >> 5)
-                    | ((b & 0b00100000) >> 3)
-                    | ((b & 0b00010000) >> 1)
-                    | ((b & 0b00001000) << 1)
-                    | ((b & 0b00000100) << 3)
-                    | ((b & 0b00000010) << 5)
-                    | ((b & 0b00000001) << 7)
-            })
-            .collect::<Vec<u8>>()
-            .into();
-        Some(result)
+        Some(MerkleBlockArtefacts {
+            merkleblock: build_merkle_block(block, all_flags),
+            matching_transactions: matching_transactions,
+        })
     }
 }
 
@@ -136,7 +102,7 @@ pub mod tests {
     use super::ConnectionFilter;
     use std::iter::repeat;
     use utils::KnownHashType;
-    use zebra_chain::IndexedTransaction;
+    use zebra_chain::{IndexedBlock, IndexedTransaction, Transaction};
     use zebra_message::types;
     use zebra_primitives::bytes::Bytes;
 
@@ -216,4 +182,65 @@ pub mod tests {
         filter.clear();
         assert!(filter.filter_transaction(&tx, None));
     }
+
+    #[test]
+    fn filter_add_requires_loaded_filter() {
+        let mut filter = ConnectionFilter::default();
+        let filteradd = || types::FilterAdd {
+            data: vec![1, 2, 3].into(),
+        };
+        assert!(!filter.add(filteradd()));
+        filter.load(types::FilterLoad {
+            filter: Bytes::from(repeat(0u8).take(1024).collect::<Vec<_>>()),
+            hash_functions: 10,
+            tweak: 5,
+            flags: types::FilterFlags::None,
+        });
+        assert!(filter.add(filteradd()));
+        filter.clear();
+        assert!(!filter.add(filteradd()));
+    }
+
+    #[test]
+    fn filter_builds_merkle_block_for_matched_transactions() {
+        let tx1: Transaction = zebra_test_data::TransactionBuilder::with_output(10).into();
+        let tx2: Transaction = zebra_test_data::TransactionBuilder::with_output(20).into();
+        let tx3: Transaction = zebra_test_data::TransactionBuilder::with_output(30).into();
+        let (tx1_hash, tx2_hash) = (tx1.hash(), tx2.hash());
+        let block: IndexedBlock = zebra_test_data::block_builder()
+            .header()
+            .parent(zebra_test_data::genesis().hash())
+            .build()
+            .with_transaction(tx1)
+            .with_transaction(tx2.clone())
+            .with_transaction(tx3)
+            .build()
+            .into();
+
+        // merkle block is only built when bloom filter is loaded
+        let mut filter = ConnectionFilter::default();
+        assert!(filter.build_merkle_block(&block).is_none());
+
+        filter.load(types::FilterLoad {
+            filter: Bytes::from(repeat(0u8).take(1024).collect::<Vec<_>>()),
+            hash_functions: 10,
+            tweak: 5,
+            flags: types::FilterFlags::None,
+        });
+        filter.add(types::FilterAdd {
+            data: (&*tx2_hash as &[u8]).into(),
+        });
+
+        let artefacts = filter.build_merkle_block(&block).unwrap();
+        assert_eq!(
+            artefacts.matching_transactions,
+            vec![IndexedTransaction::from(tx2)]
+        );
+        assert_eq!(artefacts.merkleblock.block_header, block.header.raw);
+        assert_eq!(artefacts.merkleblock.total_transactions, 3);
+        // tree is traversed: root(1) -> left(1) -> tx1(0), tx2(1) -> right(0)
+        assert_eq!(artefacts.merkleblock.hashes.len(), 3);
+        assert_eq!(artefacts.merkleblock.hashes[..2], [tx1_hash, tx2_hash]);
+        assert_eq!(artefacts.merkleblock.flags, vec![0b00001011].into());
+    }
 }
@@ -0,0 +1,90 @@
+use types::{FeeEstimatorRef, MemoryPoolRef, StorageRef};
+use zebra_primitives::hash::H256;
+use zebra_storage::BlockRef;
+use SyncListener;
+
+/// Synchronization listener, which updates fee estimator when block is added to the main chain
+pub struct FeeEstimatorListener {
+    /// Storage reference
+    storage: StorageRef,
+    /// Memory pool reference
+    memory_pool: MemoryPoolRef,
+    /// Fee estimator reference
+    fee_estimator: FeeEstimatorRef,
+}
+
+impl FeeEstimatorListener {
+    pub fn new(
+        storage: StorageRef,
+        memory_pool: MemoryPoolRef,
+        fee_estimator: FeeEstimatorRef,
+    ) -> Self {
+        FeeEstimatorListener {
+            storage: storage,
+            memory_pool: memory_pool,
+            fee_estimator: fee_estimator,
+        }
+    }
+}
+
+impl SyncListener for FeeEstimatorListener {
+    fn synchronization_state_switched(&self, _is_synchronizing: bool) {}
+
+    fn best_storage_block_inserted(&self, _block_hash: &H256) {}
+
+    fn block_connected(&self, block_hash: &H256) {
+        let height = match self.storage.block_number(block_hash) {
+            Some(height) => height,
+            None => return,
+        };
+
+        let transactions = self
+            .storage
+            .block_transaction_hashes(BlockRef::Hash(block_hash.clone()));
+        self.fee_estimator
+            .write()
+            .on_block(height, &transactions, &*self.memory_pool.read());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate zebra_test_data;
+
+    use super::FeeEstimatorListener;
+    use parking_lot::RwLock;
+    use std::sync::Arc;
+    use zebra_db::BlockChainDatabase;
+    use zebra_miner::{FeeEstimator, MemoryPool, NonZeroFeeCalculator};
+    use SyncListener;
+
+    #[test]
+    fn fee_estimator_listener_updates_estimator() {
+        let genesis = zebra_test_data::genesis();
+        let b1 = zebra_test_data::block_h1();
+        let b1_hash = b1.hash();
+        let tx1 = b1.transactions[0].clone();
+        let tx1_hash = tx1.hash();
+
+        let storage = Arc::new(BlockChainDatabase::init_test_chain(vec![genesis.into()]));
+        let memory_pool = Arc::new(RwLock::new(MemoryPool::new()));
+        let fee_estimator = Arc::new(RwLock::new(FeeEstimator::default()));
+        let listener =
+            FeeEstimatorListener::new(storage.clone(), memory_pool.clone(), fee_estimator.clone());
+
+        // transaction enters memory pool before the block is mined
+        memory_pool
+            .write()
+            .insert_verified(tx1.into(), &NonZeroFeeCalculator)
+            .unwrap();
+        listener.block_connected(&storage.best_block().hash);
+        assert_eq!(fee_estimator.read().estimate_fee_rate(1), None);
+
+        // block is mined => transaction is removed from the pool
+        memory_pool.write().remove_by_hash(&tx1_hash);
+        storage.insert(b1.into()).unwrap();
+        storage.canonize(&b1_hash).unwrap();
+        listener.block_connected(&b1_hash);
+        assert!(fee_estimator.read().estimate_fee_rate(1).is_some());
+    }
+}
@@ -28,7 +28,7 @@ impl MemoryPoolTransactionOutputProvider {
         let memory_pool = memory_pool.read();
         let check_result = memory_pool.check_double_spend(transaction);
         match check_result {
-            // input of transaction is already spent by another final transaction from memory pool
+            // input of transaction is already spent by another final (and non-replaceable) transaction from memory pool
             DoubleSpendCheckResult::DoubleSpend(_, hash, index) => {
                 Err(TransactionError::UsingSpentOutput(hash, index))
             }
@@ -48,7 +48,7 @@ impl MemoryPoolTransactionOutputProvider {
                     .collect(),
                 nonfinal_spends: None,
             }),
-            // there are non-final transactions, which are spending same inputs in memory pool
+            // there are non-final (or replaceable) transactions, which are spending same inputs in memory pool
             DoubleSpendCheckResult::NonFinalDoubleSpend(nonfinal_spends) => {
                 Ok(MemoryPoolTransactionOutputProvider {
                     storage_provider: storage,
@@ -156,13 +156,16 @@ mod tests {
         {
             memory_pool
                 .write()
-                .insert_verified(dchain.at(0).into(), &NonZeroFeeCalculator);
+                .insert_verified(dchain.at(0).into(), &NonZeroFeeCalculator)
+                .unwrap();
             memory_pool
                 .write()
-                .insert_verified(dchain.at(1).into(), &NonZeroFeeCalculator);
+                .insert_verified(dchain.at(1).into(), &NonZeroFeeCalculator)
+                .unwrap();
             memory_pool
                 .write()
-                .insert_verified(dchain.at(2).into(), &NonZeroFeeCalculator);
+                .insert_verified(dchain.at(2).into(), &NonZeroFeeCalculator)
+                .unwrap();
         }
 
         // when inserting t3:
@@ -1,7 +1,9 @@
 mod average_speed_meter;
+mod best_block_waiter;
 mod best_headers_chain;
 mod bloom_filter;
 mod connection_filter;
+mod fee_estimator_listener;
 mod fee_rate_filter;
 mod hash_queue;
 mod known_hash_filter;
@@ -12,16 +14,21 @@ mod partial_merkle_tree;
 mod synchronization_state;
 
 pub use self::average_speed_meter::AverageSpeedMeter;
+pub use self::best_block_waiter::BestBlockWaiter;
 pub use self::best_headers_chain::{BestHeadersChain, Information as BestHeadersChainInformation};
 pub use self::bloom_filter::BloomFilter;
 pub use self::connection_filter::ConnectionFilter;
+pub use self::fee_estimator_listener::FeeEstimatorListener;
 pub use self::fee_rate_filter::FeeRateFilter;
 pub use self::hash_queue::{HashPosition, HashQueue, HashQueueChain};
 pub use self::known_hash_filter::{KnownHashFilter, KnownHashType};
 pub use self::memory_pool_transaction_provider::MemoryPoolTransactionOutputProvider;
 pub use self::orphan_blocks_pool::OrphanBlocksPool;
 pub use self::orphan_transactions_pool::{OrphanTransaction, OrphanTransactionsPool};
-pub use self::partial_merkle_tree::{build_partial_merkle_tree, PartialMerkleTree};
+pub use self::partial_merkle_tree::{
+    build_merkle_block, build_partial_merkle_tree, parse_merkle_block, ParsedPartialMerkleTree,
+    PartialMerkleTree,
+};
 pub use self::synchronization_state::SynchronizationState;
 
 /// Block height type
@@ -1,8 +1,12 @@
 use bit_vec::BitVec;
 use std::cmp::min;
-use zebra_chain::merkle_node_hash;
+use zebra_chain::{merkle_node_hash, IndexedBlock};
+use zebra_message::types;
 use zebra_primitives::hash::H256;
 
+/// Maximal number of transactions in the block (60 is the lower bound for the serialized transaction size)
+const MAX_BLOCK_TRANSACTIONS: usize = 2_000_000 / 60;
+
 /// Partial merkle tree
 pub struct PartialMerkleTree {
     /// Total number of transactions
@@ -14,7 +18,6 @@ pub struct PartialMerkleTree {
 }
 
 /// Partial merkle tree parse result
-#[cfg(test)]
 pub struct ParsedPartialMerkleTree {
     /// Merkle root
     pub root: H256,
@@ -30,13 +33,70 @@ pub fn build_partial_merkle_tree(tx_hashes: Vec<H256>, tx_matches: BitVec) -> Pa
 }
 
 /// Parse partial merkle tree
-#[cfg(test)]
 pub fn parse_partial_merkle_tree(
     tree: PartialMerkleTree,
 ) -> Result<ParsedPartialMerkleTree, String> {
     PartialMerkleTreeBuilder::parse(tree)
 }
 
+/// Build `merkleblock`, proving that matched transactions are included in the block
+pub fn build_merkle_block(block: &IndexedBlock, tx_matches: BitVec) -> types::MerkleBlock {
+    let tx_hashes = block
+        .transactions
+        .iter()
+        .map(|tx| tx.hash.clone())
+        .collect();
+    let partial_merkle_tree = build_partial_merkle_tree(tx_hashes, tx_matches);
+    types::MerkleBlock {
+        block_header: block.header.raw.clone(),
+        total_transactions: partial_merkle_tree.tx_count as u32,
+        hashes: partial_merkle_tree.hashes,
+        // to_bytes() converts [true, false, true] to 0b10100000
+        // while protocol requires [true, false, true] to be serialized as 0x00000101
+        flags: partial_merkle_tree
+            .flags
+            .to_bytes()
+            .into_iter()
+            .map(reverse_bits)
+            .collect::<Vec<u8>>()
+            .into(),
+    }
+}
+
+/// Parse partial merkle tree of the `merkleblock`
+pub fn parse_merkle_block(
+    merkle_block: &types::MerkleBlock,
+) -> Result<ParsedPartialMerkleTree, String> {
+    let tx_count = merkle_block.total_transactions as usize;
+    if tx_count > MAX_BLOCK_TRANSACTIONS {
+        return Err("too many transactions".into());
+    }
+
+    let flags = merkle_block
+        .flags
+        .iter()
+        .cloned()
+        .map(reverse_bits)
+        .collect::<Vec<u8>>();
+    parse_partial_merkle_tree(PartialMerkleTree::new(
+        tx_count,
+        merkle_block.hashes.clone(),
+        BitVec::from_bytes(&flags),
+    ))
+}
+
+/// Reverse bits order of the flags byte
+fn reverse_bits(b: u8) -> u8 {
+    ((b & 0b10000000) >> 7)
+        | ((b & 0b01000000) >> 5)
+        | ((b & 0b00100000) >> 3)
+        | ((b & 0b00010000) >> 1)
+        | ((b & 0b00001000) << 1)
+        | ((b & 0b00000100) << 3)
+        | ((b & 0b00000010) << 5)
+        | ((b & 0b00000001) << 7)
+}
+
 /// Service structure to construct `merkleblock` message.
 struct PartialMerkleTreeBuilder {
     /// All transactions length.
@@ -62,7 +122,6 @@ impl PartialMerkleTree {
     }
 }
 
-#[cfg(test)]
 impl ParsedPartialMerkleTree {
     pub fn new(root: H256, hashes: Vec<H256>, flags: BitVec) -> Self {
         ParsedPartialMerkleTree {
@@ -92,7 +151,6 @@ impl PartialMerkleTreeBuilder {
         )
     }
 
-    #[cfg(test)]
     /// Parse partial merkle tree as described here:
     /// https://bitcoin.org/en/developer-reference#parsing-a-merkleblock-message
     pub fn parse(tree: PartialMerkleTree) -> Result<ParsedPartialMerkleTree, String> {
@@ -117,7 +175,6 @@ impl PartialMerkleTreeBuilder {
         self.build_branch(tree_height, 0)
     }
 
-    #[cfg(test)]
     fn parse_tree(&mut self) -> Result<H256, String> {
         if self.all_len == 0 {
             return Err("no transactions".into());
@@ -136,7 +193,8 @@ impl PartialMerkleTreeBuilder {
         let merkle_root =
             try!(self.parse_branch(tree_height, 0, &mut matches_used, &mut hashes_used));
 
-        if matches_used != self.matches.len() {
+        // serialized matches are padded to the whole byte
+        if (matches_used + 7) / 8 != (self.matches.len() + 7) / 8 {
             return Err("not all matches used".into());
         }
         if hashes_used != self.hashes.len() {
@@ -168,7 +226,6 @@ impl PartialMerkleTreeBuilder {
         }
     }
 
-    #[cfg(test)]
     fn parse_branch(
         &mut self,
         height: usize,
@@ -185,7 +242,7 @@ impl PartialMerkleTreeBuilder {
 
         if height == 0 || !flag {
             // we're at the leaf level || there is no match
-            if *hashes_used > self.hashes.len() {
+            if *hashes_used >= self.hashes.len() {
                 return Err("all hashes used".into());
             }
 
@@ -251,8 +308,11 @@ impl PartialMerkleTreeBuilder {
 mod tests {
     extern crate zebra_test_data;
 
-    use super::{build_partial_merkle_tree, parse_partial_merkle_tree};
-    use zebra_chain::{merkle_root, Transaction};
+    use super::{
+        build_merkle_block, build_partial_merkle_tree, parse_merkle_block,
+        parse_partial_merkle_tree,
+    };
+    use zebra_chain::{merkle_root, IndexedBlock, Transaction};
     use zebra_primitives::hash::H256;
 
     #[test]
@@ -298,4 +358,36 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn merkle_block_roundtrip() {
+        use bit_vec::BitVec;
+
+        let transactions: Vec<Transaction> = (0..5)
+            .map(|n| zebra_test_data::TransactionBuilder::with_version(n).into())
+            .collect();
+        let hashes: Vec<_> = transactions.iter().map(|t| t.hash()).collect();
+        let block: IndexedBlock = zebra_test_data::block_builder()
+            .with_transactions(transactions)
+            .merkled_header()
+            .build()
+            .build()
+            .into();
+
+        let mut matches = BitVec::from_elem(5, false);
+        matches.set(1, true);
+        matches.set(4, true);
+        let mut merkle_block = build_merkle_block(&block, matches.clone());
+        assert_eq!(merkle_block.total_transactions, 5);
+
+        // flags are padded to the whole byte
+        let parsed = parse_merkle_block(&merkle_block).unwrap();
+        assert_eq!(parsed.root, block.header.raw.merkle_root_hash);
+        assert_eq!(parsed.hashes, vec![hashes[1].clone(), hashes[4].clone()]);
+        assert_eq!(parsed.flags, matches);
+
+        // truncated proof is rejected
+        merkle_block.hashes.pop();
+        assert!(parse_merkle_block(&merkle_block).is_err());
+    }
 }
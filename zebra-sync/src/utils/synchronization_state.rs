@@ -12,6 +12,8 @@ pub struct SynchronizationState {
     is_synchronizing: AtomicBool,
     /// Height of best block in the storage
     best_storage_block_height: AtomicUsize,
+    /// Height of best known block header
+    best_headers_height: AtomicUsize,
 }
 
 impl SynchronizationState {
@@ -20,6 +22,7 @@ impl SynchronizationState {
         SynchronizationState {
             is_synchronizing: AtomicBool::new(false),
             best_storage_block_height: AtomicUsize::new(best_storage_block_height as usize),
+            best_headers_height: AtomicUsize::new(best_storage_block_height as usize),
         }
     }
 
@@ -39,6 +42,15 @@ impl SynchronizationState {
         self.best_storage_block_height
             .store(height as usize, Ordering::SeqCst);
     }
+
+    pub fn best_headers_height(&self) -> BlockHeight {
+        self.best_headers_height.load(Ordering::SeqCst) as BlockHeight
+    }
+
+    pub fn update_best_headers_height(&self, height: BlockHeight) {
+        self.best_headers_height
+            .store(height as usize, Ordering::SeqCst);
+    }
 }
 
 impl InboundSyncConnectionState for SynchronizationState {
@@ -184,6 +184,13 @@ impl TransactionBuilder {
         self
     }
 
+    pub fn set_sequence(mut self, sequence: u32) -> Self {
+        for input in &mut self.transaction.inputs {
+            input.sequence = sequence;
+        }
+        self
+    }
+
     pub fn lock(mut self) -> Self {
         self.transaction.inputs[0].sequence = 0;
         self.transaction.lock_time = 500000;
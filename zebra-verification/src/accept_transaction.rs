@@ -21,7 +21,7 @@ use zebra_storage::{
     DuplexTransactionOutputProvider, EpochRef, EpochTag, NullifierTracker, TransactionMetaProvider,
     TransactionOutputProvider, TreeStateProvider,
 };
-use {checked_transaction_fee, VerificationLevel};
+use {checked_transaction_fee, dust_threshold, VerificationLevel};
 
 pub struct TransactionAcceptor<'a> {
     pub version: TransactionVersion<'a>,
@@ -112,6 +112,7 @@ impl<'a> TransactionAcceptor<'a> {
 pub struct MemoryPoolTransactionAcceptor<'a> {
     pub version: TransactionVersion<'a>,
     pub size: TransactionSize<'a>,
+    pub dust: TransactionDust<'a>,
     pub expiry: TransactionExpiry<'a>,
     pub missing_inputs: TransactionMissingInputs<'a>,
     pub maturity: TransactionMaturity<'a>,
@@ -136,6 +137,7 @@ impl<'a> MemoryPoolTransactionAcceptor<'a> {
         time: u32,
         deployments: &'a BlockDeployments<'a>,
         tree_state_provider: &'a TreeStateProvider,
+        min_relay_fee_rate: u64,
     ) -> Self {
         trace!(target: "verification", "Mempool-Tx verification {}", transaction.hash.to_reversed_str());
         let transaction_index = 0;
@@ -143,6 +145,7 @@ impl<'a> MemoryPoolTransactionAcceptor<'a> {
         MemoryPoolTransactionAcceptor {
             version: TransactionVersion::new(transaction, consensus, height),
             size: TransactionSize::new(transaction, consensus, height),
+            dust: TransactionDust::new(transaction, min_relay_fee_rate),
             expiry: TransactionExpiry::new(transaction, consensus, height),
             missing_inputs: TransactionMissingInputs::new(
                 transaction,
@@ -188,6 +191,7 @@ impl<'a> MemoryPoolTransactionAcceptor<'a> {
         // Tx pool validation is not strictly a matter of consensus.
         self.version.check()?;
         self.size.check()?;
+        self.dust.check()?;
         self.expiry.check()?;
         self.missing_inputs.check()?;
         self.maturity.check()?;
@@ -294,22 +298,19 @@ impl<'a> TransactionMaturity<'a> {
 
     fn check(&self) -> Result<(), TransactionError> {
         // TODO: this is should also fail when we are trying to spend current block coinbase
-        let immature_spend = self.transaction.raw.inputs.iter().any(|input| {
-            match self.store.transaction_meta(&input.previous_output.hash) {
-                Some(ref meta)
-                    if meta.is_coinbase() && self.height < meta.height() + COINBASE_MATURITY =>
-                {
-                    true
-                }
-                _ => false,
+        for input in &self.transaction.raw.inputs {
+            let required_height = match self.store.transaction_meta(&input.previous_output.hash) {
+                Some(ref meta) if meta.is_coinbase() => meta.height() + COINBASE_MATURITY,
+                _ => continue,
+            };
+            if self.height < required_height {
+                return Err(TransactionError::PrematureCoinbaseSpend {
+                    required_height: required_height,
+                });
             }
-        });
-
-        if immature_spend {
-            Err(TransactionError::Maturity)
-        } else {
-            Ok(())
         }
+
+        Ok(())
     }
 }
 
@@ -405,7 +406,7 @@ impl<'a> TransactionEval<'a> {
         let verify_sigpushonly = false;
         let verify_cleanstack = false;
 
-        let consensus_branch_id = params.consensus_branch_id(height);
+        let consensus_branch_id = params.consensus_branch_id_at(height);
 
         TransactionEval {
             transaction: transaction,
@@ -548,6 +549,34 @@ impl<'a> TransactionSize<'a> {
     }
 }
 
+/// Check that transaction doesn't create uneconomical (dust) transparent outputs.
+pub struct TransactionDust<'a> {
+    transaction: CanonTransaction<'a>,
+    min_relay_fee_rate: u64,
+}
+
+impl<'a> TransactionDust<'a> {
+    fn new(transaction: CanonTransaction<'a>, min_relay_fee_rate: u64) -> Self {
+        TransactionDust {
+            transaction: transaction,
+            min_relay_fee_rate: min_relay_fee_rate,
+        }
+    }
+
+    fn check(&self) -> Result<(), TransactionError> {
+        let dust_output = self
+            .transaction
+            .raw
+            .outputs
+            .iter()
+            .position(|output| output.value < dust_threshold(output, self.min_relay_fee_rate));
+        match dust_output {
+            Some(index) => Err(TransactionError::DustOutput(index)),
+            None => Ok(()),
+        }
+    }
+}
+
 /// Check that transaction isn't expired.
 pub struct TransactionExpiry<'a> {
     transaction: CanonTransaction<'a>,
@@ -829,9 +858,12 @@ mod tests {
     extern crate zebra_test_data;
 
     use super::*;
-    use zebra_chain::{Sapling, Transaction, BTC_TX_VERSION};
+    use zebra_chain::{
+        IndexedTransaction, Sapling, Transaction, TransactionOutput, BTC_TX_VERSION,
+    };
     use zebra_db::BlockChainDatabase;
     use zebra_network::{ConsensusParams, Network};
+    use zebra_primitives::bytes::Bytes;
     use zebra_script::{
         verify_script, Script, TransactionInputSigner, TransactionSignatureChecker,
         VerificationFlags,
@@ -968,6 +1000,74 @@ mod tests {
         );
     }
 
+    #[test]
+    fn transaction_maturity_works() {
+        let genesis = zebra_test_data::genesis();
+        let storage = BlockChainDatabase::init_test_chain(vec![genesis.clone().into()]);
+        let tx = zebra_test_data::TransactionBuilder::with_input(&genesis.transactions[0], 0)
+            .set_output(1)
+            .into();
+
+        // genesis coinbase can't be spent until it is mature
+        assert_eq!(
+            TransactionMaturity::new(CanonTransaction::new(&tx), &storage, COINBASE_MATURITY - 1)
+                .check(),
+            Err(TransactionError::PrematureCoinbaseSpend {
+                required_height: COINBASE_MATURITY,
+            })
+        );
+        assert_eq!(
+            TransactionMaturity::new(CanonTransaction::new(&tx), &storage, COINBASE_MATURITY)
+                .check(),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn transaction_dust_works() {
+        let p2pkh_script: Bytes = "76a914404371705fa9bd789a2fcd52d2c580b65d35549d88ac".into();
+        let tx_with_outputs = |outputs: Vec<(u64, Bytes)>| -> IndexedTransaction {
+            Transaction {
+                outputs: outputs
+                    .into_iter()
+                    .map(|(value, script_pubkey)| TransactionOutput {
+                        value: value,
+                        script_pubkey: script_pubkey,
+                    })
+                    .collect(),
+                ..Default::default()
+            }
+            .into()
+        };
+
+        // output at the dust threshold is accepted
+        let tx = tx_with_outputs(vec![
+            (1_000, p2pkh_script.clone()),
+            (546, p2pkh_script.clone()),
+        ]);
+        assert_eq!(
+            TransactionDust::new(CanonTransaction::new(&tx), 1000).check(),
+            Ok(())
+        );
+
+        // output below the dust threshold is rejected
+        let tx = tx_with_outputs(vec![
+            (1_000, p2pkh_script.clone()),
+            (545, p2pkh_script.clone()),
+        ]);
+        assert_eq!(
+            TransactionDust::new(CanonTransaction::new(&tx), 1000).check(),
+            Err(TransactionError::DustOutput(1))
+        );
+
+        // zero value null data output is accepted
+        let tx = tx_with_outputs(vec![(0, "6a0401020304".into())]);
+        assert_eq!(
+            TransactionDust::new(CanonTransaction::new(&tx), 1000).check(),
+            Ok(())
+        );
+    }
+
     #[test]
     fn transaction_version_works() {
         let consensus = ConsensusParams::new(Network::Mainnet);
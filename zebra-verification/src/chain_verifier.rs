@@ -5,6 +5,8 @@ use accept_transaction::MemoryPoolTransactionAcceptor;
 use canon::{CanonBlock, CanonTransaction};
 use deployments::{BlockDeployments, Deployments};
 use error::{Error, TransactionError};
+use expiry::check_expiry;
+use verify_block::BlockVerifier;
 use verify_chain::ChainVerifier;
 use verify_header::HeaderVerifier;
 use verify_transaction::MemoryPoolTransactionVerifier;
@@ -12,7 +14,8 @@ use zebra_chain::{IndexedBlock, IndexedBlockHeader, IndexedTransaction};
 use zebra_network::ConsensusParams;
 use zebra_storage::{
     BlockHeaderProvider, BlockOrigin, CachedTransactionOutputProvider,
-    DuplexTransactionOutputProvider, NoopStore, SharedStore, TransactionOutputProvider,
+    DuplexTransactionOutputProvider, ForkChain, NoopStore, SharedStore, SideChainOrigin, Store,
+    TransactionOutputProvider,
 };
 use {VerificationLevel, Verify};
 
@@ -59,6 +62,21 @@ impl BackwardsCompatibleChainVerifier {
             block_origin,
         );
 
+        let block_number = match block_origin {
+            BlockOrigin::KnownBlock => None,
+            BlockOrigin::CanonChain { block_number } => Some(block_number),
+            BlockOrigin::SideChain(ref origin)
+            | BlockOrigin::SideChainBecomingCanonChain(ref origin) => Some(origin.block_number),
+        };
+        if let Some(expected) = block_number.and_then(|n| self.consensus.checkpoints.hash_at(n)) {
+            if expected != block.hash() {
+                return Err(Error::CheckpointMismatch {
+                    expected: expected.clone(),
+                    actual: block.hash().clone(),
+                });
+            }
+        }
+
         let canon_block = CanonBlock::new(block);
         match block_origin {
             BlockOrigin::KnownBlock => {
@@ -69,9 +87,12 @@ impl BackwardsCompatibleChainVerifier {
                 );
             }
             BlockOrigin::CanonChain { block_number } => {
+                let verification_level =
+                    self.checkpointed_verification_level(verification_level, block_number);
                 let tx_out_provider = CachedTransactionOutputProvider::new(
                     self.store.as_store().as_transaction_output_provider(),
                 );
+                prefetch_previous_outputs(&tx_out_provider, block);
                 let tx_meta_provider = self.store.as_store().as_transaction_meta_provider();
                 let header_provider = self.store.as_store().as_block_header_provider();
                 let tree_state_provider = self.store.as_store().as_tree_state_provider();
@@ -98,66 +119,10 @@ impl BackwardsCompatibleChainVerifier {
                 chain_acceptor.check()?;
             }
             BlockOrigin::SideChain(origin) => {
-                let block_number = origin.block_number;
-                let fork = self.store.fork(origin)?;
-                let tx_out_provider = CachedTransactionOutputProvider::new(
-                    fork.store().as_transaction_output_provider(),
-                );
-                let tx_meta_provider = fork.store().as_transaction_meta_provider();
-                let header_provider = fork.store().as_block_header_provider();
-                let tree_state_provider = fork.store().as_tree_state_provider();
-                let nullifier_tracker = fork.store().as_nullifier_tracker();
-                let deployments = BlockDeployments::new(
-                    &self.deployments,
-                    block_number,
-                    header_provider,
-                    &self.consensus,
-                );
-                let chain_acceptor = ChainAcceptor::new(
-                    &tx_out_provider,
-                    tx_meta_provider,
-                    header_provider,
-                    tree_state_provider,
-                    nullifier_tracker,
-                    &self.consensus,
-                    verification_level,
-                    canon_block,
-                    block_number,
-                    block.header.raw.time,
-                    &deployments,
-                );
-                chain_acceptor.check()?;
+                self.accept_side_chain_block(origin, canon_block, verification_level)?;
             }
             BlockOrigin::SideChainBecomingCanonChain(origin) => {
-                let block_number = origin.block_number;
-                let fork = self.store.fork(origin)?;
-                let tx_out_provider = CachedTransactionOutputProvider::new(
-                    fork.store().as_transaction_output_provider(),
-                );
-                let tx_meta_provider = fork.store().as_transaction_meta_provider();
-                let header_provider = fork.store().as_block_header_provider();
-                let tree_state_provider = fork.store().as_tree_state_provider();
-                let nullifier_tracker = fork.store().as_nullifier_tracker();
-                let deployments = BlockDeployments::new(
-                    &self.deployments,
-                    block_number,
-                    header_provider,
-                    &self.consensus,
-                );
-                let chain_acceptor = ChainAcceptor::new(
-                    &tx_out_provider,
-                    tx_meta_provider,
-                    header_provider,
-                    tree_state_provider,
-                    nullifier_tracker,
-                    &self.consensus,
-                    verification_level,
-                    canon_block,
-                    block_number,
-                    block.header.raw.time,
-                    &deployments,
-                );
-                chain_acceptor.check()?;
+                self.accept_side_chain_block(origin, canon_block, verification_level)?;
             }
         };
 
@@ -168,6 +133,195 @@ impl BackwardsCompatibleChainVerifier {
         Ok(())
     }
 
+    /// Select verification level for the canon chain block at given height.
+    ///
+    /// Transaction scripts of blocks at or below the last checkpoint are not checked if the block
+    /// is built on top of the checkpointed chain.
+    fn checkpointed_verification_level(
+        &self,
+        verification_level: VerificationLevel,
+        block_number: u32,
+    ) -> VerificationLevel {
+        if !verification_level.intersects(VerificationLevel::FULL) {
+            return verification_level;
+        }
+
+        let checkpoints = &self.consensus.checkpoints;
+        match checkpoints.last_height() {
+            Some(last_height) if block_number <= last_height => (),
+            _ => return verification_level,
+        }
+
+        // checkpoint at block height has been already checked by the caller
+        let is_checkpointed_chain = match checkpoints.last_at_or_below(block_number) {
+            Some((height, _)) if height == block_number => true,
+            Some((height, hash)) => self.store.block_hash(height).as_ref() == Some(hash),
+            None => false,
+        };
+        if !is_checkpointed_chain {
+            return verification_level;
+        }
+
+        let mut verification_level = verification_level;
+        verification_level.remove(VerificationLevel::FULL);
+        verification_level.insert(VerificationLevel::HEADER);
+        verification_level
+    }
+
+    /// Accept block against the fork, described by `origin`.
+    fn accept_side_chain_block(
+        &self,
+        origin: SideChainOrigin,
+        canon_block: CanonBlock,
+        verification_level: VerificationLevel,
+    ) -> Result<(), Error> {
+        let block_number = origin.block_number;
+        let fork = self.store.fork(origin)?;
+        self.accept_block_at(fork.store(), block_number, canon_block, verification_level)
+    }
+
+    /// Verifies block against given state of the chain, where best block is its parent.
+    fn accept_block_at(
+        &self,
+        store: &Store,
+        block_number: u32,
+        canon_block: CanonBlock,
+        verification_level: VerificationLevel,
+    ) -> Result<(), Error> {
+        let tx_out_provider =
+            CachedTransactionOutputProvider::new(store.as_transaction_output_provider());
+        prefetch_previous_outputs(&tx_out_provider, canon_block.raw());
+        let tx_meta_provider = store.as_transaction_meta_provider();
+        let header_provider = store.as_block_header_provider();
+        let tree_state_provider = store.as_tree_state_provider();
+        let nullifier_tracker = store.as_nullifier_tracker();
+        let deployments = BlockDeployments::new(
+            &self.deployments,
+            block_number,
+            header_provider,
+            &self.consensus,
+        );
+        let chain_acceptor = ChainAcceptor::new(
+            &tx_out_provider,
+            tx_meta_provider,
+            header_provider,
+            tree_state_provider,
+            nullifier_tracker,
+            &self.consensus,
+            verification_level,
+            canon_block,
+            block_number,
+            canon_block.header.raw.time,
+            &deployments,
+        );
+        chain_acceptor.check()
+    }
+
+    /// Verify integrity of `blocks` best blocks of the stored chain (or all blocks if `blocks` is 0).
+    /// Verification stops at the first pruned block.
+    ///
+    /// Every block is read from the database and its header hash is checked against the stored key.
+    /// Check levels:
+    /// 2) merkle root of the block is recomputed;
+    /// 3) block is pre-verified (genesis block is skipped);
+    /// 4) block is fully verified against the database state at its parent (genesis block is skipped).
+    pub fn verify_stored_chain(&self, check_level: u32, blocks: u32) -> Result<(), Error> {
+        let best_block = self.store.best_block();
+        let blocks = if blocks == 0 || blocks > best_block.number + 1 {
+            best_block.number + 1
+        } else {
+            blocks
+        };
+
+        // single fork is shared by all blocks: verified block is decanonized in the fork, so that
+        // its best block becomes the parent of the verified block. Fork is never flushed
+        let fork = if check_level >= 4 {
+            Some(self.store.fork(SideChainOrigin {
+                ancestor: best_block.number,
+                canonized_route: Vec::new(),
+                decanonized_route: Vec::new(),
+                block_number: best_block.number + 1,
+            })?)
+        } else {
+            None
+        };
+
+        for block_number in (best_block.number + 1 - blocks..best_block.number + 1).rev() {
+            // transactions of pruned blocks are not available => nothing to verify
+            if self.store.is_pruned(block_number.into()) {
+                break;
+            }
+
+            let block_hash = self.store.block_hash(block_number);
+            let block = block_hash
+                .clone()
+                .and_then(|block_hash| self.store.block(block_hash.into()));
+            let result = match block {
+                Some(block) => {
+                    let fork = fork.as_ref().map(|fork| &**fork);
+                    self.verify_stored_block(check_level, block_number, &block, fork)
+                }
+                None => Err(Error::StoredBlockCorrupted),
+            };
+
+            if let Err(error) = result {
+                warn!(
+                    target: "verification",
+                    "Stored block {} at height {} has failed verification: {:?}",
+                    block_hash.map(|hash| hash.to_reversed_str()).unwrap_or_default(),
+                    block_number,
+                    error,
+                );
+                return Err(error);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn verify_stored_block(
+        &self,
+        check_level: u32,
+        block_number: u32,
+        block: &IndexedBlock,
+        fork: Option<&ForkChain>,
+    ) -> Result<(), Error> {
+        if block.header.raw.hash() != block.header.hash
+            || block.transactions.iter().any(|tx| tx.raw.hash() != tx.hash)
+        {
+            return Err(Error::StoredBlockCorrupted);
+        }
+
+        if check_level >= 2 && block.merkle_root() != block.header.raw.merkle_root_hash {
+            return Err(Error::MerkleRoot);
+        }
+
+        if block_number == 0 {
+            return Ok(());
+        }
+
+        if let Some(fork) = fork {
+            if fork.decanonize()? != block.header.hash {
+                return Err(Error::StoredBlockCorrupted);
+            }
+        }
+
+        if check_level >= 3 {
+            BlockVerifier::new(block, &self.consensus).check()?;
+        }
+
+        if let Some(fork) = fork {
+            self.accept_block_at(
+                fork.store(),
+                block_number,
+                CanonBlock::new(block),
+                VerificationLevel::FULL,
+            )?;
+        }
+
+        Ok(())
+    }
+
     pub fn verify_block_header(&self, header: &IndexedBlockHeader) -> Result<(), Error> {
         let current_time = ::time::get_time().sec as u32;
         let header_verifier = HeaderVerifier::new(header, &self.consensus, current_time);
@@ -181,6 +335,7 @@ impl BackwardsCompatibleChainVerifier {
         height: u32,
         time: u32,
         transaction: &IndexedTransaction,
+        min_relay_fee_rate: u64,
     ) -> Result<(), TransactionError>
     where
         T: TransactionOutputProvider,
@@ -195,6 +350,13 @@ impl BackwardsCompatibleChainVerifier {
         let tx_verifier = MemoryPoolTransactionVerifier::new(&transaction, &self.consensus);
         try!(tx_verifier.check());
 
+        // transaction is verified against the next block => best block is at height - 1
+        try!(check_expiry(
+            &transaction.raw,
+            height.saturating_sub(1),
+            &self.consensus
+        ));
+
         let canon_tx = CanonTransaction::new(&transaction);
         // now let's do full verification
         let noop = NoopStore;
@@ -209,6 +371,7 @@ impl BackwardsCompatibleChainVerifier {
             time,
             &deployments,
             self.store.as_tree_state_provider(),
+            min_relay_fee_rate,
         );
         tx_acceptor.check()
     }
@@ -227,15 +390,36 @@ impl Verify for BackwardsCompatibleChainVerifier {
     }
 }
 
+/// Read outputs, spent by the block transactions, before transactions are verified in parallel.
+fn prefetch_previous_outputs(
+    tx_out_provider: &CachedTransactionOutputProvider,
+    block: &IndexedBlock,
+) {
+    let prevouts = block
+        .transactions
+        .iter()
+        .filter(|tx| !tx.raw.is_coinbase())
+        .flat_map(|tx| {
+            tx.raw
+                .inputs
+                .iter()
+                .map(|input| input.previous_output.clone())
+        })
+        .collect::<Vec<_>>();
+    tx_out_provider.prefetch(&prevouts);
+}
+
 #[cfg(test)]
 mod tests {
     extern crate zebra_test_data;
 
     use super::BackwardsCompatibleChainVerifier as ChainVerifier;
+    use constants::COINBASE_MATURITY;
+    use hash::H256;
     use std::sync::Arc;
     use zebra_chain::IndexedBlock;
     use zebra_db::BlockChainDatabase;
-    use zebra_network::{ConsensusParams, Network};
+    use zebra_network::{Checkpoints, ConsensusParams, Network};
     use zebra_script;
     use zebra_storage::Error as DBError;
     use {Error, TransactionError, VerificationLevel, Verify};
@@ -315,7 +499,12 @@ mod tests {
 
         let verifier = ChainVerifier::new(Arc::new(storage), consensus);
 
-        let expected = Err(Error::Transaction(1, TransactionError::Maturity));
+        let expected = Err(Error::Transaction(
+            1,
+            TransactionError::PrematureCoinbaseSpend {
+                required_height: COINBASE_MATURITY,
+            },
+        ));
 
         assert_eq!(
             expected,
@@ -650,4 +839,136 @@ mod tests {
             verifier.verify(VerificationLevel::FULL, &block.into())
         );
     }
+
+    #[test]
+    fn verify_stored_chain_smoky() {
+        let storage = Arc::new(BlockChainDatabase::init_test_chain(vec![
+            zebra_test_data::genesis().into(),
+            zebra_test_data::block_h1().into(),
+        ]));
+        let verifier = ChainVerifier::new(storage, ConsensusParams::new(Network::Mainnet));
+        assert_eq!(verifier.verify_stored_chain(4, 0), Ok(()));
+        assert_eq!(verifier.verify_stored_chain(4, 1), Ok(()));
+        assert_eq!(verifier.verify_stored_chain(4, 100), Ok(()));
+    }
+
+    #[test]
+    fn verify_stored_chain_merkle_root() {
+        let genesis = zebra_test_data::block_builder()
+            .transaction()
+            .coinbase()
+            .output()
+            .value(1)
+            .build()
+            .build()
+            .header()
+            .build()
+            .build();
+        let storage = Arc::new(BlockChainDatabase::init_test_chain(vec![genesis.into()]));
+        let verifier = ChainVerifier::new(storage, ConsensusParams::new(Network::Unitest));
+        assert_eq!(verifier.verify_stored_chain(1, 0), Ok(()));
+        assert_eq!(verifier.verify_stored_chain(2, 0), Err(Error::MerkleRoot));
+    }
+
+    #[test]
+    fn checkpointed_blocks_are_verified_without_scripts() {
+        let consensus = ConsensusParams::new(Network::Unitest);
+        let mut blocks: Vec<IndexedBlock> = vec![zebra_test_data::genesis().into()];
+        let mut rolling_hash = blocks[0].hash().clone();
+        for i in 1..101 {
+            let next_block = zebra_test_data::block_builder()
+                .transaction()
+                .coinbase()
+                .founder_reward(&consensus, i)
+                .version(i as i32)
+                .output()
+                .value(5000000000)
+                .build()
+                .build()
+                .merkled_header()
+                .parent(rolling_hash.clone())
+                .bits(Network::Unitest.max_bits().into())
+                .time(consensus.pow_target_spacing * 7 * i)
+                .build()
+                .build();
+            rolling_hash = next_block.hash();
+            blocks.push(next_block.into());
+        }
+
+        let coinbase_transaction_hash = blocks[1].transactions[0].hash.clone();
+        let checkpointed_hash = blocks[1].hash().clone();
+        let storage = Arc::new(BlockChainDatabase::init_test_chain(blocks));
+        let bad_transaction_block: IndexedBlock = zebra_test_data::block_builder()
+            .transaction()
+            .coinbase()
+            .founder_reward(&consensus, 101)
+            .output()
+            .value(50)
+            .build()
+            .build()
+            .transaction()
+            .input()
+            .hash(coinbase_transaction_hash)
+            .build()
+            .output()
+            .value(1000)
+            .build()
+            .build()
+            .merkled_header()
+            .parent(rolling_hash)
+            .bits(Network::Unitest.max_bits().into())
+            .time(consensus.pow_target_spacing * 7 * 102)
+            .build()
+            .build()
+            .into();
+
+        // no checkpoints => scripts are checked
+        let verifier = ChainVerifier::new(storage.clone(), ConsensusParams::new(Network::Unitest));
+        assert_eq!(
+            verifier.verify(VerificationLevel::FULL, &bad_transaction_block),
+            Err(Error::Transaction(
+                1,
+                TransactionError::Signature(0, zebra_script::Error::InvalidStackOperation)
+            ))
+        );
+
+        // block is checkpointed => scripts are not checked
+        let mut consensus = ConsensusParams::new(Network::Unitest);
+        consensus.checkpoints = Checkpoints::new(vec![(101, bad_transaction_block.hash().clone())]);
+        let verifier = ChainVerifier::new(storage.clone(), consensus);
+        assert_eq!(
+            verifier.verify(VerificationLevel::FULL, &bad_transaction_block),
+            Ok(())
+        );
+
+        // block is below the last checkpoint and its ancestor is checkpointed => scripts are not checked
+        let mut consensus = ConsensusParams::new(Network::Unitest);
+        consensus.checkpoints =
+            Checkpoints::new(vec![(1, checkpointed_hash), (200, H256::from(1))]);
+        let verifier = ChainVerifier::new(storage.clone(), consensus);
+        assert_eq!(
+            verifier.verify(VerificationLevel::FULL, &bad_transaction_block),
+            Ok(())
+        );
+
+        // block is below the last checkpoint, but its ancestor isn't checkpointed => scripts are checked
+        let mut consensus = ConsensusParams::new(Network::Unitest);
+        consensus.checkpoints = Checkpoints::new(vec![(1, H256::from(2)), (200, H256::from(1))]);
+        let verifier = ChainVerifier::new(storage.clone(), consensus);
+        assert!(verifier
+            .verify(VerificationLevel::FULL, &bad_transaction_block)
+            .is_err());
+
+        // block hash doesn't match the checkpoint
+        let mut consensus = ConsensusParams::new(Network::Unitest);
+        consensus.checkpoints = Checkpoints::new(vec![(101, H256::from(1))]);
+        let verifier = ChainVerifier::new(storage, consensus);
+        assert_eq!(
+            verifier.verify(VerificationLevel::FULL, &bad_transaction_block),
+            Err(Error::CheckpointMismatch {
+                expected: H256::from(1),
+                actual: bad_transaction_block.hash().clone(),
+            })
+        );
+    }
 }
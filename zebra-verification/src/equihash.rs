@@ -1,5 +1,6 @@
-use zebra_chain::BlockHeader;
+use zebra_chain::{BlockHeader, EquihashSolution};
 use zebra_crypto::Blake2b;
+use zebra_primitives::hash::H256;
 
 /// Verify equihash solution of the block header.
 pub fn verify_block_equihash_solution(params: (u32, u32), header: &BlockHeader) -> bool {
@@ -9,7 +10,30 @@ pub fn verify_block_equihash_solution(params: (u32, u32), header: &BlockHeader)
         "Wrong equihash parameters specified in consensus",
     );
 
-    verify_equihash_solution::<OnChainEquihash>(&header.equihash_input(), header.solution.as_ref())
+    verify_solution::<OnChainEquihash>(&header.equihash_input(), header.solution.as_ref())
+}
+
+/// Verify equihash solution of the serialized block header.
+///
+/// The `header_without_solution` is the serialized header, excluding both nonce and solution.
+/// Only the (N = 200, K = 9) instance used by all Zcash chains is supported => false is returned
+/// for any other parameters.
+pub fn verify_equihash_solution(
+    n: u32,
+    k: u32,
+    header_without_solution: &[u8],
+    nonce: &H256,
+    solution: &EquihashSolution,
+) -> bool {
+    if (n, k) != (OnChainEquihash::N, OnChainEquihash::K) {
+        return false;
+    }
+
+    let mut input = Vec::with_capacity(header_without_solution.len() + nonce.len());
+    input.extend_from_slice(header_without_solution);
+    input.extend_from_slice(&**nonce);
+
+    verify_solution::<OnChainEquihash>(&input, solution.as_ref())
 }
 
 /// Equihash algorithm instance.
@@ -75,7 +99,7 @@ impl Equihash for OnChainEquihash {
 }
 
 /// Verify equihash solution.
-fn verify_equihash_solution<Algorithm: Equihash>(input: &[u8], solution: &[u8]) -> bool {
+fn verify_solution<Algorithm: Equihash>(input: &[u8], solution: &[u8]) -> bool {
     // prepare Blake2b context with personalization
     let mut context = Blake2b::with_params(
         Algorithm::HASH_SIZE,
@@ -441,22 +465,19 @@ mod tests {
     #[test]
     fn verify_equihash_solution_works() {
         let (input, solution) = get_valid_equihash_solution();
-        assert!(verify_equihash_solution::<TestEquihash>(&input, &solution));
+        assert!(verify_solution::<TestEquihash>(&input, &solution));
     }
 
     #[test]
     fn no_ambiguity_in_equihash_minimal_solution() {
         let (input, solution) = get_valid_equihash_solution();
         // Make sure it starts off valid...
-        assert!(verify_equihash_solution::<TestEquihash>(&input, &solution));
+        assert!(verify_solution::<TestEquihash>(&input, &solution));
         // ...but any single bit flip will invalidate it.
         for i in 0..(solution.len() * 8) {
             let mut solution_flip = solution.clone();
             solution_flip[i / 8] ^= 1 << (i % 8);
-            assert!(!verify_equihash_solution::<TestEquihash>(
-                &input,
-                &solution_flip
-            ));
+            assert!(!verify_solution::<TestEquihash>(&input, &solution_flip));
         }
     }
 
@@ -468,4 +489,33 @@ mod tests {
             &block.block_header
         ));
     }
+
+    #[test]
+    fn test_equihash_on_real_header_bytes() {
+        let header = zebra_test_data::block_h170().block_header;
+        let input = header.equihash_input();
+        let header_without_solution = &input[..input.len() - 32];
+
+        assert!(verify_equihash_solution(
+            200,
+            9,
+            header_without_solution,
+            &header.nonce,
+            &header.solution
+        ));
+        assert!(!verify_equihash_solution(
+            200,
+            9,
+            header_without_solution,
+            &H256::from(1),
+            &header.solution
+        ));
+        assert!(!verify_equihash_solution(
+            96,
+            5,
+            header_without_solution,
+            &header.nonce,
+            &header.solution
+        ));
+    }
 }
@@ -65,6 +65,10 @@ pub enum Error {
     FailedToAppendSaplingCommitmentNote(String),
     /// Invalid value of sapling final root hash in the block header.
     InvalidFinalSaplingRootHash { expected: H256, actual: H256 },
+    /// Stored block is missing or its data doesn't match the stored hashes.
+    StoredBlockCorrupted,
+    /// Block hash doesn't match the checkpoint at the block height.
+    CheckpointMismatch { expected: H256, actual: H256 },
 }
 
 impl From<DBError> for Error {
@@ -92,8 +96,9 @@ pub enum TransactionError {
     MemoryPoolCoinbase,
     /// Not found corresponding output for transaction input
     Input(usize),
-    /// Referenced coinbase output for the transaction input is not mature enough
-    Maturity,
+    /// Referenced coinbase output for the transaction input is not mature enough.
+    /// The lowest height of the block, where the output could be spent, is provided.
+    PrematureCoinbaseSpend { required_height: u32 },
     /// Signature invalid for given input
     Signature(usize, SignatureError),
     /// Unknown previous transaction referenced
@@ -126,6 +131,8 @@ pub enum TransactionError {
     ExpiryHeightTooHigh,
     /// Sapling with empty spends && outputs has non-empty balance.
     EmptySaplingHasBalance,
+    /// Sapling balance is out of [-max_transaction_value; max_transaction_value] range.
+    SaplingBalanceOutOfRange,
     /// Both value_pub_old && value_pub_new in join split description are non-zero.
     JoinSplitBothPubsNonZero,
     /// Transaction has duplicate inputs. Inputs indexes are provided.
@@ -142,16 +149,22 @@ pub enum TransactionError {
     JoinSplitVersionInvalid,
     /// Invalid join split signature
     JoinSplitSignature(::zebra_crypto::Error),
+    /// Transaction has join split descriptions, but join split public key is missing.
+    JoinSplitPubKeyMissing,
     /// Transaction sapling verification has failed.
     InvalidSapling,
     /// Sapling nullifier already revealed earlier in the chain.
     SaplingDeclared(H256),
     /// Transaction is expired.
     Expired,
+    /// Transaction expiry height is too far from the current best block.
+    ExpiryHeightTooFar,
     /// Transaction overwintered flag is invalid.
     InvalidOverwintered,
     /// Invalid joinsplit statement
     InvalidJoinSplit(usize),
     /// Unknown anchor used in join split
     UnknownAnchor(H256),
+    /// Transaction output value is below the dust threshold. Output index is provided.
+    DustOutput(usize),
 }
@@ -0,0 +1,84 @@
+use zebra_chain::Transaction;
+use zebra_network::ConsensusParams;
+use TransactionError;
+
+/// Check that transaction expiry height is sane relative to the current best block.
+///
+/// Transaction must not be expired at the next block, and its expiry height must not be
+/// further than `consensus.max_transaction_expiry_delta()` blocks from the current best block.
+/// Transactions without expiry height (non-overwintered, coinbase, or zero expiry height) are always accepted.
+pub fn check_expiry(
+    tx: &Transaction,
+    tip_height: u32,
+    consensus: &ConsensusParams,
+) -> Result<(), TransactionError> {
+    if !tx.overwintered || tx.expiry_height == 0 || tx.is_coinbase() {
+        return Ok(());
+    }
+
+    if tx.expiry_height <= tip_height {
+        return Err(TransactionError::Expired);
+    }
+
+    if tx.expiry_height - tip_height > consensus.max_transaction_expiry_delta() {
+        return Err(TransactionError::ExpiryHeightTooFar);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate zebra_test_data;
+
+    use super::*;
+    use zebra_network::Network;
+
+    #[test]
+    fn check_expiry_rejects_expired_transaction() {
+        let consensus = ConsensusParams::new(Network::Mainnet);
+        let tx = zebra_test_data::TransactionBuilder::overwintered()
+            .set_expiry_height(1000)
+            .into();
+        assert_eq!(
+            check_expiry(&tx, 1000, &consensus),
+            Err(TransactionError::Expired)
+        );
+        assert_eq!(
+            check_expiry(&tx, 2000, &consensus),
+            Err(TransactionError::Expired)
+        );
+    }
+
+    #[test]
+    fn check_expiry_rejects_far_future_expiry() {
+        let consensus = ConsensusParams::new(Network::Mainnet);
+        let tx = zebra_test_data::TransactionBuilder::overwintered()
+            .set_expiry_height(1000 + consensus.max_transaction_expiry_delta() + 1)
+            .into();
+        assert_eq!(
+            check_expiry(&tx, 1000, &consensus),
+            Err(TransactionError::ExpiryHeightTooFar)
+        );
+    }
+
+    #[test]
+    fn check_expiry_accepts_reasonable_expiry() {
+        let consensus = ConsensusParams::new(Network::Mainnet);
+        let tx = zebra_test_data::TransactionBuilder::overwintered()
+            .set_expiry_height(1020)
+            .into();
+        assert_eq!(check_expiry(&tx, 1000, &consensus), Ok(()));
+
+        let tx = zebra_test_data::TransactionBuilder::overwintered()
+            .set_expiry_height(1000 + consensus.max_transaction_expiry_delta())
+            .into();
+        assert_eq!(check_expiry(&tx, 1000, &consensus), Ok(()));
+
+        // zero expiry height means that transaction never expires
+        let tx = zebra_test_data::TransactionBuilder::overwintered()
+            .set_expiry_height(0)
+            .into();
+        assert_eq!(check_expiry(&tx, 1000, &consensus), Ok(()));
+    }
+}
@@ -1,7 +1,13 @@
-use zebra_chain::Transaction;
+use ser::Serializable;
+use zebra_chain::{Transaction, TransactionOutput};
+use zebra_script::Script;
 use zebra_storage::TransactionOutputProvider;
 use TransactionError;
 
+/// Size of the input, spending P2PKH output: outpoint, script length, script (signature and
+/// compressed public key) and sequence.
+const P2PKH_INPUT_SIZE: u64 = 32 + 4 + 1 + 107 + 4;
+
 /// Compute miner fee for given transaction.
 ///
 /// Returns None if overflow/underflow happens during computation. Missed prevout
@@ -77,6 +83,26 @@ pub fn checked_transaction_fee(
     }
 }
 
+/// Compute minimal value of the transparent output, which isn't considered dust.
+///
+/// Output is dust if spending it costs more than a third of its value at given relay fee rate
+/// (in satoshis per 1000 bytes). Null data outputs are never considered dust.
+/// Mirrors `CTxOut::GetDustThreshold` of zcashd.
+pub fn dust_threshold(output: &TransactionOutput, min_relay_fee_rate: u64) -> u64 {
+    let script: Script = output.script_pubkey.clone().into();
+    if script.is_null_data_script() {
+        return 0;
+    }
+
+    let spending_size = output.serialized_size() as u64 + P2PKH_INPUT_SIZE;
+    let spending_fee = match min_relay_fee_rate * spending_size / 1000 {
+        // non-zero fee rate is never rounded down to zero fee
+        0 if min_relay_fee_rate != 0 => 1,
+        fee => fee,
+    };
+    3 * spending_fee
+}
+
 #[cfg(test)]
 mod tests {
     extern crate zebra_test_data;
@@ -139,4 +165,22 @@ mod tests {
             Ok(500_000)
         );
     }
+
+    #[test]
+    fn test_dust_threshold() {
+        let p2pkh_output = TransactionOutput {
+            value: 0,
+            script_pubkey: "76a914404371705fa9bd789a2fcd52d2c580b65d35549d88ac".into(),
+        };
+        assert_eq!(dust_threshold(&p2pkh_output, 1000), 546);
+        assert_eq!(dust_threshold(&p2pkh_output, 100), 54);
+        assert_eq!(dust_threshold(&p2pkh_output, 1), 3);
+        assert_eq!(dust_threshold(&p2pkh_output, 0), 0);
+
+        let null_data_output = TransactionOutput {
+            value: 0,
+            script_pubkey: "6a0401020304".into(),
+        };
+        assert_eq!(dust_threshold(&null_data_output, 1000), 0);
+    }
 }
@@ -85,6 +85,7 @@ pub mod constants;
 mod deployments;
 mod equihash;
 mod error;
+mod expiry;
 mod fee;
 mod sapling;
 mod sigops;
@@ -124,8 +125,10 @@ pub use verify_transaction::{MemoryPoolTransactionVerifier, TransactionVerifier}
 
 pub use chain_verifier::BackwardsCompatibleChainVerifier;
 pub use deployments::Deployments;
+pub use equihash::verify_equihash_solution;
 pub use error::{Error, TransactionError};
-pub use fee::checked_transaction_fee;
+pub use expiry::check_expiry;
+pub use fee::{checked_transaction_fee, dust_threshold};
 pub use sigops::transaction_sigops;
 pub use timestamp::{median_timestamp, median_timestamp_inclusive};
 pub use tree_cache::TreeCache;
@@ -4,6 +4,11 @@ use error::TransactionError;
 use zebra_chain::hash::H256;
 use zebra_storage::{SaplingTreeState, SproutTreeState, TreeStateProvider};
 
+/// Cache of Sprout commitment trees, that are created while verifying join splits
+/// of the single transaction.
+///
+/// Every interstitial tree must stay available until the transaction is verified, because
+/// any following join split may use its root as an anchor. So the cache is never pruned.
 #[derive(Clone)]
 pub struct TreeCache<'a> {
     persistent: &'a TreeStateProvider,
@@ -30,6 +35,10 @@ impl TreeStateProvider for NoPersistentStorage {
     fn sapling_block_root(&self, _block_hash: &H256) -> Option<H256> {
         None
     }
+
+    fn canon_block_hash(&self, _height: u32) -> Option<H256> {
+        None
+    }
 }
 
 impl<'a> TreeCache<'a> {
@@ -47,11 +56,12 @@ impl<'a> TreeCache<'a> {
         }
     }
 
+    /// Appends commitments to the tree with given root. Returns root of the updated tree.
     pub fn continue_root(
         &mut self,
         root: &H256,
         commitments: &[[u8; 32]; 2],
-    ) -> Result<(), TransactionError> {
+    ) -> Result<H256, TransactionError> {
         let mut tree = match self.interstitial.get(root) {
             Some(tree) => tree.clone(),
             None => self
@@ -65,8 +75,73 @@ impl<'a> TreeCache<'a> {
         tree.append(commitments[1].into())
             .expect("Unrecoverable error: merkle tree full");
 
-        self.interstitial.insert(tree.root(), tree);
+        let new_root = tree.root();
+        self.interstitial.insert(new_root, tree);
+
+        Ok(new_root)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TreeCache;
+    use error::TransactionError;
+    use zebra_chain::hash::H256;
+    use zebra_storage::{SaplingTreeState, SproutTreeState, TreeStateProvider};
+
+    struct EmptyTreeStorage;
+
+    impl TreeStateProvider for EmptyTreeStorage {
+        fn sprout_tree_at(&self, root: &H256) -> Option<SproutTreeState> {
+            if *root == SproutTreeState::empty_root() {
+                Some(SproutTreeState::new())
+            } else {
+                None
+            }
+        }
+
+        fn sapling_tree_at(&self, _root: &H256) -> Option<SaplingTreeState> {
+            None
+        }
+
+        fn sprout_block_root(&self, _block_hash: &H256) -> Option<H256> {
+            None
+        }
+
+        fn sapling_block_root(&self, _block_hash: &H256) -> Option<H256> {
+            None
+        }
+
+        fn canon_block_hash(&self, _height: u32) -> Option<H256> {
+            None
+        }
+    }
+
+    #[test]
+    fn tree_cache_keeps_all_interstitial_roots() {
+        let storage = EmptyTreeStorage;
+        let mut cache = TreeCache::new(&storage);
+
+        let empty_root = SproutTreeState::empty_root();
+        let first_root = cache
+            .continue_root(&empty_root, &[[1; 32], [2; 32]])
+            .unwrap();
+        let mut root = first_root;
+        for i in 0..2000u32 {
+            let commitment = [(i % 256) as u8; 32];
+            root = cache
+                .continue_root(&root, &[commitment, commitment])
+                .unwrap();
+        }
 
-        Ok(())
+        // the first interstitial root is still known, no matter how many trees were created since
+        assert_eq!(cache.interstitial.len(), 2001);
+        assert!(cache
+            .continue_root(&first_root, &[[3; 32], [4; 32]])
+            .is_ok());
+        assert_eq!(
+            cache.continue_root(&H256::from(1), &[[3; 32], [4; 32]]),
+            Err(TransactionError::UnknownAnchor(H256::from(1)))
+        );
     }
 }
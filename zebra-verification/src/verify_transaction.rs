@@ -4,8 +4,8 @@ use ser::Serializable;
 use sigops::transaction_sigops;
 use std::{collections::HashMap, ops};
 use zebra_chain::{
-    IndexedTransaction, BTC_TX_VERSION, OVERWINTER_TX_VERSION, OVERWINTER_TX_VERSION_GROUP_ID,
-    SAPLING_TX_VERSION_GROUP_ID,
+    IndexedTransaction, JoinSplitProof, BTC_TX_VERSION, OVERWINTER_TX_VERSION,
+    OVERWINTER_TX_VERSION_GROUP_ID, SAPLING_TX_VERSION, SAPLING_TX_VERSION_GROUP_ID,
 };
 use zebra_network::ConsensusParams;
 use zebra_storage::NoopStore;
@@ -41,7 +41,7 @@ impl<'a> TransactionVerifier<'a> {
             ),
             non_transparent_coinbase: TransactionNonTransparentCoinbase::new(transaction),
             size: TransactionAbsoluteSize::new(transaction, consensus),
-            sapling: TransactionSapling::new(transaction),
+            sapling: TransactionSapling::new(transaction, consensus),
             join_split: TransactionJoinSplit::new(transaction),
             output_value_overflow: TransactionOutputValueOverflow::new(transaction, consensus),
             input_value_overflow: TransactionInputValueOverflow::new(transaction, consensus),
@@ -100,7 +100,7 @@ impl<'a> MemoryPoolTransactionVerifier<'a> {
             is_coinbase: TransactionMemoryPoolCoinbase::new(transaction),
             size: TransactionAbsoluteSize::new(transaction, consensus),
             sigops: TransactionSigops::new(transaction, consensus.max_block_sigops()),
-            sapling: TransactionSapling::new(transaction),
+            sapling: TransactionSapling::new(transaction, consensus),
             join_split: TransactionJoinSplit::new(transaction),
             output_value_overflow: TransactionOutputValueOverflow::new(transaction, consensus),
             input_value_overflow: TransactionInputValueOverflow::new(transaction, consensus),
@@ -362,15 +362,26 @@ impl<'a> TransactionNonTransparentCoinbase<'a> {
 /// Check that transaction sapling is well-formed.
 pub struct TransactionSapling<'a> {
     transaction: &'a IndexedTransaction,
+    max_value: i64,
 }
 
 impl<'a> TransactionSapling<'a> {
-    fn new(transaction: &'a IndexedTransaction) -> Self {
-        TransactionSapling { transaction }
+    fn new(transaction: &'a IndexedTransaction, consensus: &'a ConsensusParams) -> Self {
+        TransactionSapling {
+            transaction,
+            max_value: consensus.max_transaction_value(),
+        }
     }
 
     fn check(&self) -> Result<(), TransactionError> {
         if let Some(ref sapling) = self.transaction.raw.sapling {
+            // sapling balance should be within money range (this also excludes i64::MIN, which
+            // can't be used to compute binding verification key)
+            if sapling.balancing_value < -self.max_value || sapling.balancing_value > self.max_value
+            {
+                return Err(TransactionError::SaplingBalanceOutOfRange);
+            }
+
             // sapling balance should be zero if spends and outputs are empty
             if sapling.balancing_value != 0
                 && sapling.spends.is_empty()
@@ -400,10 +411,23 @@ impl<'a> TransactionJoinSplit<'a> {
                 return Err(TransactionError::JoinSplitVersionInvalid);
             }
 
-            for desc in &join_split.descriptions {
+            // joinSplitPubKey && joinSplitSig are serialized only when there are descriptions
+            if !join_split.descriptions.is_empty() && join_split.pubkey.is_zero() {
+                return Err(TransactionError::JoinSplitPubKeyMissing);
+            }
+
+            // Sapling transactions are using Groth16 proofs, older transactions are using PHGR13
+            let use_groth = self.transaction.raw.overwintered
+                && self.transaction.raw.version >= SAPLING_TX_VERSION;
+            for (idx, desc) in join_split.descriptions.iter().enumerate() {
                 if desc.value_pub_old != 0 && desc.value_pub_new != 0 {
                     return Err(TransactionError::JoinSplitBothPubsNonZero);
                 }
+
+                match (use_groth, &desc.zkproof) {
+                    (true, &JoinSplitProof::Groth(_)) | (false, &JoinSplitProof::PHGR(_)) => (),
+                    _ => return Err(TransactionError::InvalidJoinSplit(idx)),
+                }
             }
         }
 
@@ -637,9 +661,11 @@ mod tests {
         TransactionOutputValueOverflow, TransactionSapling, TransactionVersion,
     };
     use error::TransactionError;
+    use ser::{deserialize, serialize};
     use zebra_chain::{
-        JoinSplit, JoinSplitDescription, Sapling, BTC_TX_VERSION, OVERWINTER_TX_VERSION,
-        OVERWINTER_TX_VERSION_GROUP_ID, SAPLING_TX_VERSION_GROUP_ID,
+        JoinSplit, JoinSplitDescription, JoinSplitProof, Sapling, Transaction, BTC_TX_VERSION,
+        OVERWINTER_TX_VERSION, OVERWINTER_TX_VERSION_GROUP_ID, SAPLING_TX_VERSION,
+        SAPLING_TX_VERSION_GROUP_ID,
     };
     use zebra_network::{ConsensusParams, Network};
 
@@ -1128,6 +1154,9 @@ mod tests {
 
     #[test]
     fn transaction_sapling_works() {
+        let consensus = ConsensusParams::new(Network::Mainnet);
+        let max_value = consensus.max_transaction_value();
+
         assert_eq!(
             TransactionSapling::new(
                 &zebra_test_data::TransactionBuilder::with_sapling(Sapling {
@@ -1135,7 +1164,8 @@ mod tests {
                     spends: vec![Default::default()],
                     ..Default::default()
                 })
-                .into()
+                .into(),
+                &consensus
             )
             .check(),
             Ok(())
@@ -1148,7 +1178,8 @@ mod tests {
                     outputs: vec![Default::default()],
                     ..Default::default()
                 })
-                .into()
+                .into(),
+                &consensus
             )
             .check(),
             Ok(())
@@ -1162,7 +1193,8 @@ mod tests {
                     spends: vec![Default::default()],
                     ..Default::default()
                 })
-                .into()
+                .into(),
+                &consensus
             )
             .check(),
             Ok(())
@@ -1174,26 +1206,77 @@ mod tests {
                     balancing_value: 100,
                     ..Default::default()
                 })
-                .into()
+                .into(),
+                &consensus
             )
             .check(),
             Err(TransactionError::EmptySaplingHasBalance)
         );
+
+        assert_eq!(
+            TransactionSapling::new(
+                &zebra_test_data::TransactionBuilder::with_sapling(Sapling {
+                    balancing_value: -max_value,
+                    spends: vec![Default::default()],
+                    ..Default::default()
+                })
+                .into(),
+                &consensus
+            )
+            .check(),
+            Ok(())
+        );
+
+        assert_eq!(
+            TransactionSapling::new(
+                &zebra_test_data::TransactionBuilder::with_sapling(Sapling {
+                    balancing_value: max_value + 1,
+                    spends: vec![Default::default()],
+                    ..Default::default()
+                })
+                .into(),
+                &consensus
+            )
+            .check(),
+            Err(TransactionError::SaplingBalanceOutOfRange)
+        );
+
+        assert_eq!(
+            TransactionSapling::new(
+                &zebra_test_data::TransactionBuilder::with_sapling(Sapling {
+                    balancing_value: ::std::i64::MIN,
+                    outputs: vec![Default::default()],
+                    ..Default::default()
+                })
+                .into(),
+                &consensus
+            )
+            .check(),
+            Err(TransactionError::SaplingBalanceOutOfRange)
+        );
     }
 
     #[test]
     fn transaction_join_split_works() {
+        let sapling_transaction = || {
+            zebra_test_data::TransactionBuilder::overwintered()
+                .set_version(SAPLING_TX_VERSION)
+                .set_version_group_id(SAPLING_TX_VERSION_GROUP_ID)
+        };
+
         assert_eq!(
             TransactionJoinSplit::new(
-                &zebra_test_data::TransactionBuilder::with_join_split(JoinSplit {
-                    descriptions: vec![JoinSplitDescription {
-                        value_pub_old: 100,
-                        value_pub_new: 0,
+                &sapling_transaction()
+                    .set_join_split(JoinSplit {
+                        descriptions: vec![JoinSplitDescription {
+                            value_pub_old: 100,
+                            value_pub_new: 0,
+                            ..Default::default()
+                        }],
+                        pubkey: 1.into(),
                         ..Default::default()
-                    }],
-                    ..Default::default()
-                })
-                .into()
+                    })
+                    .into()
             )
             .check(),
             Ok(())
@@ -1201,15 +1284,17 @@ mod tests {
 
         assert_eq!(
             TransactionJoinSplit::new(
-                &zebra_test_data::TransactionBuilder::with_join_split(JoinSplit {
-                    descriptions: vec![JoinSplitDescription {
-                        value_pub_old: 0,
-                        value_pub_new: 100,
+                &sapling_transaction()
+                    .set_join_split(JoinSplit {
+                        descriptions: vec![JoinSplitDescription {
+                            value_pub_old: 0,
+                            value_pub_new: 100,
+                            ..Default::default()
+                        }],
+                        pubkey: 1.into(),
                         ..Default::default()
-                    }],
-                    ..Default::default()
-                })
-                .into()
+                    })
+                    .into()
             )
             .check(),
             Ok(())
@@ -1217,19 +1302,92 @@ mod tests {
 
         assert_eq!(
             TransactionJoinSplit::new(
-                &zebra_test_data::TransactionBuilder::with_join_split(JoinSplit {
-                    descriptions: vec![JoinSplitDescription {
-                        value_pub_old: 100,
-                        value_pub_new: 100,
+                &sapling_transaction()
+                    .set_join_split(JoinSplit {
+                        descriptions: vec![JoinSplitDescription {
+                            value_pub_old: 100,
+                            value_pub_new: 100,
+                            ..Default::default()
+                        }],
+                        pubkey: 1.into(),
                         ..Default::default()
-                    }],
-                    ..Default::default()
-                })
-                .into()
+                    })
+                    .into()
             )
             .check(),
             Err(TransactionError::JoinSplitBothPubsNonZero)
         );
+
+        // Groth16 proof in pre-Sapling transaction
+        assert_eq!(
+            TransactionJoinSplit::new(
+                &zebra_test_data::TransactionBuilder::with_version(2)
+                    .set_join_split(JoinSplit {
+                        descriptions: vec![Default::default(), Default::default()],
+                        pubkey: 1.into(),
+                        ..Default::default()
+                    })
+                    .into()
+            )
+            .check(),
+            Err(TransactionError::InvalidJoinSplit(0))
+        );
+
+        // PHGR13 proof in Sapling transaction
+        assert_eq!(
+            TransactionJoinSplit::new(
+                &sapling_transaction()
+                    .set_join_split(JoinSplit {
+                        descriptions: vec![
+                            Default::default(),
+                            JoinSplitDescription {
+                                zkproof: JoinSplitProof::PHGR([0; 296]),
+                                ..Default::default()
+                            },
+                        ],
+                        pubkey: 1.into(),
+                        ..Default::default()
+                    })
+                    .into()
+            )
+            .check(),
+            Err(TransactionError::InvalidJoinSplit(1))
+        );
+    }
+
+    #[test]
+    fn transaction_join_split_without_pubkey_is_rejected() {
+        let transaction: Transaction = zebra_test_data::TransactionBuilder::with_version(2)
+            .set_join_split(JoinSplit {
+                descriptions: vec![JoinSplitDescription {
+                    zkproof: JoinSplitProof::PHGR([0; 296]),
+                    ..Default::default()
+                }],
+                pubkey: 1.into(),
+                ..Default::default()
+            })
+            .into();
+        let serialized = serialize(&transaction);
+
+        let transaction: Transaction = deserialize(&serialized[..]).unwrap();
+        assert_eq!(
+            TransactionJoinSplit::new(&transaction.clone().into()).check(),
+            Ok(())
+        );
+
+        // strip joinSplitPubKey from the serialized transaction
+        let pubkey_offset = serialized.len() - 64 - 32;
+        let mut stripped = serialized.to_vec();
+        for byte in &mut stripped[pubkey_offset..pubkey_offset + 32] {
+            *byte = 0;
+        }
+
+        let transaction: Transaction = deserialize(&stripped[..]).unwrap();
+        assert!(transaction.join_split.as_ref().unwrap().pubkey.is_zero());
+        assert_eq!(
+            TransactionJoinSplit::new(&transaction.into()).check(),
+            Err(TransactionError::JoinSplitPubKeyMissing)
+        );
     }
 
     #[test]
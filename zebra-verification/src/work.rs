@@ -53,12 +53,12 @@ pub fn work_required(
         .expect("self.height != 0; qed");
 
     // Special difficulty rule for testnet:
-    // If the new block's timestamp is more than 6 * 2.5 minutes
+    // If the new block's timestamp is more than 6 * target spacing (2.5 minutes before Blossom)
     // then allow mining of a min-difficulty block.
     if let Some(allow_min_difficulty_after_height) = consensus.pow_allow_min_difficulty_after_height
     {
         if height >= allow_min_difficulty_after_height {
-            if time > parent_header.raw.time + consensus.pow_target_spacing * 6 {
+            if time > parent_header.raw.time + consensus.pow_target_spacing_at(height) * 6 {
                 return max_bits;
             }
         }
@@ -86,33 +86,42 @@ pub fn work_required(
     let parent_mtp = median_timestamp_inclusive(parent_hash, store);
     let oldest_mtp = median_timestamp_inclusive(oldest_hash, store);
 
-    calculate_work_required(bits_avg, parent_mtp, oldest_mtp, consensus, max_bits)
+    calculate_work_required(
+        bits_avg, parent_mtp, oldest_mtp, height, consensus, max_bits,
+    )
 }
 
+/// Target spacing is halved since Blossom activation, so timespans are computed
+/// for the height of the block being validated.
 fn calculate_work_required(
     bits_avg: U256,
     parent_mtp: u32,
     oldest_mtp: u32,
+    height: u32,
     consensus: &ConsensusParams,
     max_bits: Compact,
 ) -> Compact {
+    let averaging_window_timespan = consensus.averaging_window_timespan(height);
+    let min_actual_timespan = consensus.min_actual_timespan(height);
+    let max_actual_timespan = consensus.max_actual_timespan(height);
+
     // Limit adjustment step
     // Use medians to prevent time-warp attacks
     let actual_timespan = parent_mtp - oldest_mtp;
 
-    let mut actual_timespan = consensus.averaging_window_timespan() as i64
-        + (actual_timespan as i64 - consensus.averaging_window_timespan() as i64) / 4;
+    let mut actual_timespan = averaging_window_timespan as i64
+        + (actual_timespan as i64 - averaging_window_timespan as i64) / 4;
 
-    if actual_timespan < consensus.min_actual_timespan() as i64 {
-        actual_timespan = consensus.min_actual_timespan() as i64;
+    if actual_timespan < min_actual_timespan as i64 {
+        actual_timespan = min_actual_timespan as i64;
     }
-    if actual_timespan > consensus.max_actual_timespan() as i64 {
-        actual_timespan = consensus.max_actual_timespan() as i64;
+    if actual_timespan > max_actual_timespan as i64 {
+        actual_timespan = max_actual_timespan as i64;
     }
 
     // Retarget
     let actual_timespan = actual_timespan as u32;
-    let mut bits_new = bits_avg / consensus.averaging_window_timespan().into();
+    let mut bits_new = bits_avg / averaging_window_timespan.into();
     bits_new = bits_new * actual_timespan.into();
 
     if bits_new > max_bits.into() {
@@ -207,6 +216,96 @@ mod tests {
         assert_eq!(expected, actual);
     }
 
+    #[test]
+    fn main_chain_first_retarget_works() {
+        let consensus = ConsensusParams::new(Network::Mainnet);
+        let max_bits: Compact = Network::Mainnet.max_bits().into();
+
+        // mainnet blocks #0..#3 are followed by blocks mined twice faster than the target spacing
+        let mut header_provider = MemoryBlockHeaderProvider::default();
+        header_provider.insert(zebra_test_data::block_h0().block_header);
+        header_provider.insert(zebra_test_data::block_h1().block_header);
+        header_provider.insert(zebra_test_data::block_h2().block_header);
+        header_provider.insert(zebra_test_data::block_h3().block_header);
+        while header_provider.next_height() <= consensus.pow_averaging_window {
+            let header = BlockHeader {
+                time: header_provider.last().time + consensus.pow_target_spacing / 2,
+                bits: max_bits,
+                version: 4,
+                previous_header_hash: header_provider.last().hash(),
+                merkle_root_hash: 0.into(),
+                nonce: 0.into(),
+                final_sapling_root: Default::default(),
+                solution: Default::default(),
+            };
+
+            // averaging window isn't filled yet => blocks are mined with the minimal difficulty
+            assert_eq!(
+                work_required(
+                    header_provider.last().hash(),
+                    header.time,
+                    header_provider.next_height(),
+                    &header_provider,
+                    &consensus
+                ),
+                max_bits
+            );
+            header_provider.insert(header);
+        }
+
+        // averaging window includes the ~8 hours gap between genesis and block#1 => minimal difficulty
+        assert_eq!(
+            header_provider.next_height(),
+            consensus.pow_averaging_window + 1
+        );
+        assert_eq!(
+            work_required(
+                header_provider.last().hash(),
+                header_provider.next_time(),
+                header_provider.next_height(),
+                &header_provider,
+                &consensus
+            ),
+            max_bits
+        );
+        let header = BlockHeader {
+            time: header_provider.next_time(),
+            bits: max_bits,
+            version: 4,
+            previous_header_hash: header_provider.last().hash(),
+            merkle_root_hash: 0.into(),
+            nonce: 0.into(),
+            final_sapling_root: Default::default(),
+            solution: Default::default(),
+        };
+        header_provider.insert(header);
+
+        // genesis has left the averaging window => difficulty rises
+        let height = header_provider.next_height();
+        let expected = calculate_work_required(
+            max_bits.into(),
+            median_timestamp_inclusive(header_provider.last().hash(), &header_provider),
+            median_timestamp_inclusive(header_provider.by_height[1].hash(), &header_provider),
+            height,
+            &consensus,
+            max_bits,
+        );
+        let actual = work_required(
+            header_provider.last().hash(),
+            header_provider.next_time(),
+            height,
+            &header_provider,
+            &consensus,
+        );
+        assert_eq!(actual, expected);
+
+        // adjustment is limited by pow_max_adjust_up
+        let mut bits_expected: U256 = max_bits.into();
+        bits_expected = bits_expected / consensus.averaging_window_timespan(height).into();
+        bits_expected = bits_expected * consensus.min_actual_timespan(height).into();
+        assert_eq!(actual, bits_expected.into());
+    }
+
     // original test link:
     // https://github.com/Bitcoin-ABC/bitcoin-abc/blob/d8eac91f8d16716eed0ad11ccac420122280bb13/src/test/pow_tests.cpp#L193
     #[test]
@@ -257,6 +356,7 @@ mod tests {
                 header_provider.by_height[first_block as usize].hash(),
                 &header_provider,
             ),
+            header_provider.next_height(),
             &consensus,
             max_bits.into(),
         );
@@ -271,8 +371,10 @@ mod tests {
 
         // Result should be unchanged, modulo integer division precision loss
         let mut bits_expected: U256 = Compact::new(0x1e7fffff).into();
-        bits_expected = bits_expected / consensus.averaging_window_timespan().into();
-        bits_expected = bits_expected * consensus.averaging_window_timespan().into();
+        let averaging_window_timespan =
+            consensus.averaging_window_timespan(header_provider.next_height());
+        bits_expected = bits_expected / averaging_window_timespan.into();
+        bits_expected = bits_expected * averaging_window_timespan.into();
         assert_eq!(
             work_required(
                 header_provider.last().hash(),
@@ -302,6 +404,7 @@ mod tests {
                 header_provider.by_height[first_block as usize].hash(),
                 &header_provider,
             ),
+            header_provider.next_height(),
             &consensus,
             max_bits.into(),
         );
@@ -343,6 +446,7 @@ mod tests {
                 header_provider.by_height[first_block as usize].hash(),
                 &header_provider,
             ),
+            header_provider.next_height(),
             &consensus,
             max_bits.into(),
         );
@@ -369,6 +473,7 @@ mod tests {
                 header_provider.by_height[first_block as usize].hash(),
                 &header_provider,
             ),
+            header_provider.next_height(),
             &consensus,
             max_bits.into(),
         );
@@ -381,4 +486,93 @@ mod tests {
         );
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn work_required_at_blossom_activation() {
+        let mut consensus = ConsensusParams::new(Network::Mainnet);
+        let bits = Compact::new(0x1e7fffff);
+
+        // blocks are mined with pre-Blossom target spacing
+        let mut header_provider = MemoryBlockHeaderProvider::default();
+        header_provider.insert(BlockHeader {
+            time: 1269211443,
+            bits: bits,
+            version: 0,
+            previous_header_hash: 0.into(),
+            merkle_root_hash: 0.into(),
+            nonce: 0.into(),
+            final_sapling_root: Default::default(),
+            solution: Default::default(),
+        });
+        for i in 1..2 * consensus.pow_averaging_window + 1 {
+            let header = BlockHeader {
+                time: header_provider.last().time + consensus.pow_target_spacing,
+                bits: bits,
+                version: 0,
+                previous_header_hash: header_provider.by_height[i as usize - 1].hash(),
+                merkle_root_hash: 0.into(),
+                nonce: 0.into(),
+                final_sapling_root: Default::default(),
+                solution: Default::default(),
+            };
+            header_provider.insert(header);
+        }
+
+        // Blossom is activated at the next block
+        let height = header_provider.next_height();
+        let time = header_provider.next_time();
+        consensus.blossom_height = height + 1;
+        let pre_blossom = work_required(
+            header_provider.last().hash(),
+            time,
+            height,
+            &header_provider,
+            &consensus,
+        );
+        consensus.blossom_height = height;
+        let post_blossom = work_required(
+            header_provider.last().hash(),
+            time,
+            height,
+            &header_provider,
+            &consensus,
+        );
+
+        // difficulty is unchanged while blocks are mined with the target spacing
+        let mut bits_expected: U256 = bits.into();
+        let averaging_window_timespan =
+            consensus.pow_averaging_window * consensus.pow_target_spacing;
+        bits_expected = bits_expected / averaging_window_timespan.into();
+        bits_expected = bits_expected * averaging_window_timespan.into();
+        assert_eq!(pre_blossom, bits_expected.into());
+
+        // blocks are mined twice slower than the post-Blossom target spacing => difficulty drops
+        let pre_blossom_target: U256 = pre_blossom.into();
+        let post_blossom_target: U256 = post_blossom.into();
+        assert!(post_blossom_target > pre_blossom_target);
+
+        // min-difficulty blocks are allowed after 6 post-Blossom target spacings
+        consensus.pow_allow_min_difficulty_after_height = Some(0);
+        let time = header_provider.last().time + 6 * consensus.pow_target_spacing_at(height) + 1;
+        assert_eq!(
+            work_required(
+                header_provider.last().hash(),
+                time,
+                height,
+                &header_provider,
+                &consensus
+            ),
+            Network::Mainnet.max_bits().into()
+        );
+        consensus.blossom_height = height + 1;
+        assert!(
+            work_required(
+                header_provider.last().hash(),
+                time,
+                height,
+                &header_provider,
+                &consensus
+            ) != Network::Mainnet.max_bits().into()
+        );
+    }
 }
@@ -1,36 +1,149 @@
 use clap::ArgMatches;
 use config::Config;
-use util::init_db;
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use util::{import_state_path, init_db};
+use zebra_chain::IndexedBlock;
+use zebra_import::{BlkDir, BlkPosition, BlkStream};
+use zebra_storage::SharedStore;
 use zebra_sync::{create_sync_blocks_writer, Error};
 
+/// Default number of seconds between import progress reports.
+const DEFAULT_PROGRESS_INTERVAL: u64 = 10;
+
+/// Source of imported blocks.
+enum BlocksSource {
+    /// Blocks, piped to the standard input.
+    Stdin(BlkStream<io::Stdin>),
+    /// Blocks from the zcashd database directory.
+    Dir(PathBuf, BlkDir),
+}
+
+impl BlocksSource {
+    fn next_block(&mut self) -> Option<Result<IndexedBlock, String>> {
+        let block = match *self {
+            BlocksSource::Stdin(ref mut stream) => stream.next(),
+            BlocksSource::Dir(_, ref mut dir) => dir.next(),
+        };
+        block.map(|blk| blk.map(|blk| blk.block).map_err(|err| format!("{:?}", err)))
+    }
+
+    /// Returns import state that could be used to resume import from the next block.
+    fn state(&self) -> Option<ImportState> {
+        match *self {
+            BlocksSource::Stdin(_) => None,
+            BlocksSource::Dir(ref path, ref dir) => dir.position().map(|position| ImportState {
+                blk_dir: path.clone(),
+                position: position,
+            }),
+        }
+    }
+}
+
+/// Position of the last imported block in the zcashd database.
+struct ImportState {
+    /// Path of the zcashd database directory.
+    blk_dir: PathBuf,
+    /// Position right after the last imported block.
+    position: BlkPosition,
+}
+
+impl ImportState {
+    /// Read import state from the file. Returns None if the file is missing or malformed.
+    fn load(path: &Path) -> Option<Self> {
+        let mut contents = String::new();
+        if File::open(path)
+            .and_then(|mut file| file.read_to_string(&mut contents))
+            .is_err()
+        {
+            return None;
+        }
+
+        let mut lines = contents.lines();
+        match (lines.next(), lines.next(), lines.next().map(str::parse)) {
+            (Some(blk_dir), Some(blk_file), Some(Ok(offset))) => Some(ImportState {
+                blk_dir: blk_dir.into(),
+                position: BlkPosition {
+                    path: blk_file.into(),
+                    offset: offset,
+                },
+            }),
+            _ => None,
+        }
+    }
+
+    /// Write import state to the file.
+    fn save(&self, path: &Path) -> Result<(), io::Error> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let mut file = File::create(path)?;
+        writeln!(file, "{}", self.blk_dir.display())?;
+        writeln!(file, "{}", self.position.path.display())?;
+        writeln!(file, "{}", self.position.offset)
+    }
+}
+
 pub fn import(cfg: Config, matches: &ArgMatches) -> Result<(), String> {
     try!(init_db(&cfg));
 
-    let blk_path = matches
-        .value_of("PATH")
-        .expect("PATH is required in cli.yml; qed");
-    let blk_dir = ::zebra_import::open_blk_dir(blk_path)
+    let progress_interval = match matches.value_of("progress-interval") {
+        Some(s) => s
+            .parse()
+            .map_err(|_| "Invalid progress-interval".to_owned())?,
+        None => DEFAULT_PROGRESS_INTERVAL,
+    };
+    let progress_interval = Duration::from_secs(progress_interval);
+
+    let state_path = import_state_path(&cfg);
+    let mut blocks = if matches.is_present("stdin") {
+        let blk_stream = ::zebra_import::open_blk_stream(io::stdin(), cfg.network.magic());
+        BlocksSource::Stdin(blk_stream)
+    } else {
+        let blk_path = matches
+            .value_of("PATH")
+            .expect("PATH is required in cli.yml unless --stdin is specified; qed");
+        let blk_path = fs::canonicalize(blk_path)
+            .map_err(|err| format!("Failed to open import directory: {}", err))?;
+        let strict = matches.is_present("strict");
+        let blk_dir = match ImportState::load(&state_path) {
+            Some(ref state) if state.blk_dir == blk_path => {
+                info!(
+                    "Resuming import from {:?} at offset {}",
+                    state.position.path, state.position.offset
+                );
+                ::zebra_import::resume_blk_dir(
+                    &blk_path,
+                    cfg.network.magic(),
+                    strict,
+                    &state.position,
+                )
+            }
+            _ => ::zebra_import::open_blk_dir(&blk_path, cfg.network.magic(), strict),
+        }
         .map_err(|err| format!("Failed to open import directory: {}", err))?;
+        BlocksSource::Dir(blk_path, blk_dir)
+    };
 
+    let db = cfg.db.clone();
     let mut writer = create_sync_blocks_writer(cfg.db, cfg.consensus, cfg.verification_params);
     let mut counter = 0;
     let mut previous_hash = None;
-    for blk in blk_dir {
-        // TODO: verify magic!
-        let blk = blk.map_err(|err| {
+    let mut last_report = Instant::now();
+    let mut last_report_counter = 0;
+    while let Some(block) = blocks.next_block() {
+        let block = block.map_err(|err| {
             format!(
-                "Cannot read block: {:?}. Previous block: {:?}",
+                "Cannot read block: {}. Previous block: {:?}",
                 err, previous_hash
             )
         })?;
-        let blk_hash = blk.block.hash().reversed();
-        match writer.append_block(blk.block) {
-            Ok(_) => {
-                counter += 1;
-                if counter % 1000 == 0 {
-                    info!(target: "sync", "Imported {} blocks", counter);
-                }
-            }
+        let blk_hash = block.hash().reversed();
+        match writer.append_block(block) {
+            Ok(_) => counter += 1,
             Err(Error::TooManyOrphanBlocks) => {
                 return Err("Too many orphan (unordered) blocks".into())
             }
@@ -43,9 +156,55 @@ pub fn import(cfg: Config, matches: &ArgMatches) -> Result<(), String> {
         }
 
         previous_hash = Some(blk_hash);
+
+        let elapsed = last_report.elapsed();
+        if elapsed >= progress_interval {
+            let elapsed_secs =
+                elapsed.as_secs() as f64 + f64::from(elapsed.subsec_nanos()) / 1_000_000_000f64;
+            info!(
+                target: "sync",
+                "Imported {} blocks ({:.2} blocks/sec). Best block: {}",
+                counter,
+                (counter - last_report_counter) as f64 / elapsed_secs,
+                db.best_block().number
+            );
+            // blocks, waiting for their parents, would be lost on restart => do not skip them
+            if !writer.has_orphaned_blocks() {
+                save_import_state(&db, &blocks, &state_path);
+            }
+
+            last_report = Instant::now();
+            last_report_counter = counter;
+        }
+    }
+
+    if !writer.has_orphaned_blocks() {
+        save_import_state(&db, &blocks, &state_path);
     }
 
-    info!("Finished import of {} blocks", counter);
+    info!(
+        "Finished import of {} blocks. Best block: {}",
+        counter,
+        db.best_block().number
+    );
 
     Ok(())
 }
+
+/// Saves import state, once all imported blocks are written to the persistent storage.
+fn save_import_state(db: &SharedStore, blocks: &BlocksSource, state_path: &Path) {
+    if let Some(state) = blocks.state() {
+        // otherwise blocks, buffered in memory, would be skipped on resume after a crash
+        if let Err(err) = db.flush() {
+            warn!(
+                "Failed to flush database, import state is not saved: {}",
+                err
+            );
+            return;
+        }
+
+        if let Err(err) = state.save(state_path) {
+            warn!("Failed to save import state to {:?}: {}", state_path, err);
+        }
+    }
+}
@@ -5,7 +5,7 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::{channel, Receiver, Sender};
 use std::sync::Arc;
 use std::thread;
-use util::{init_db, node_table_path};
+use util::{init_db, memory_pool_path, node_table_path};
 use zebra_p2p;
 use zebra_primitives::hash::H256;
 use zebra_sync::{
@@ -89,15 +89,20 @@ impl Drop for BlockNotifier {
 
 pub fn start(cfg: config::Config) -> Result<(), String> {
     let mut el = zebra_p2p::event_loop();
+    let (shutdown, on_shutdown) = zebra_p2p::shutdown_signal();
 
     init_db(&cfg)?;
 
     let nodes_path = node_table_path(&cfg);
+    let memory_pool_path = memory_pool_path(&cfg);
 
     let p2p_cfg = zebra_p2p::Config {
         threads: cfg.p2p_threads,
-        inbound_connections: cfg.inbound_connections,
-        outbound_connections: cfg.outbound_connections,
+        max_inbound: cfg.max_inbound,
+        inbound_connections_per_ip: cfg.inbound_connections_per_ip,
+        ban_score_threshold: cfg.ban_score_threshold,
+        ban_time: cfg.ban_time,
+        max_outbound: cfg.max_outbound,
         connection: zebra_p2p::NetConfig {
             protocol_version: ZCASH_PROTOCOL_VERSION,
             protocol_minimum: ZCASH_PROTOCOL_MINIMUM,
@@ -106,11 +111,17 @@ pub fn start(cfg: config::Config) -> Result<(), String> {
             services: cfg.services,
             user_agent: cfg.user_agent,
             start_height: 0,
-            relay: true,
+            relay: cfg.relay_transactions,
         },
         peers: cfg.connect.map_or_else(|| vec![], |x| vec![x]),
         seeds: cfg.seednodes,
+        dns_seeds: cfg.dns_seeds,
+        fixed_seeds: cfg.fixed_seeds,
+        whitelist: cfg.whitelist,
         node_table_path: nodes_path,
+        node_table_max_size: cfg.node_table_max_size,
+        node_table_save_interval: cfg.node_table_save_interval,
+        feeler_interval: cfg.feeler_interval,
         preferable_services: cfg.services,
         internet_protocol: cfg.internet_protocol,
     };
@@ -121,6 +132,9 @@ pub fn start(cfg: config::Config) -> Result<(), String> {
         cfg.db.clone(),
         sync_peers.clone(),
         cfg.verification_params,
+        cfg.relay_transactions,
+        cfg.memory_pool_max_size,
+        cfg.min_relay_fee_rate,
     );
     let sync_connection_factory =
         create_sync_connection_factory(sync_peers.clone(), local_sync_node.clone());
@@ -129,20 +143,44 @@ pub fn start(cfg: config::Config) -> Result<(), String> {
         local_sync_node.install_sync_listener(Box::new(BlockNotifier::new(block_notify_command)));
     }
 
+    if memory_pool_path.exists() {
+        match local_sync_node.load_memory_pool(&memory_pool_path) {
+            Ok(accepted) => {
+                info!(target: "zebra", "Loaded {} transactions from memory pool dump", accepted)
+            }
+            Err(err) => warn!(target: "zebra", "Failed to load memory pool dump: {}", err),
+        }
+    }
+
     let p2p = try!(
         zebra_p2p::P2P::new(p2p_cfg, sync_connection_factory, el.handle())
             .map_err(|x| x.to_string())
     );
+    let db = cfg.db.clone();
     let rpc_deps = rpc::Dependencies {
         consensus: cfg.consensus,
         storage: cfg.db,
-        local_sync_node: local_sync_node,
+        local_sync_node: local_sync_node.clone(),
         p2p_context: p2p.context().clone(),
         miner_address: cfg.miner_address,
+        memory_pool_path: memory_pool_path,
+        shutdown: shutdown.clone(),
     };
     let _rpc_server = try!(rpc::new_http(cfg.rpc_config, rpc_deps));
 
     try!(p2p.run().map_err(|_| "Failed to start p2p module"));
-    el.run(zebra_p2p::forever()).unwrap();
+    try!(el
+        .run(on_shutdown)
+        .map_err(|_| "All shutdown handles have been dropped"));
+
+    info!(target: "zebra", "Shutting down");
+    p2p.context().set_network_active(false);
+    // wait for the block in verification to be inserted => queued blocks are lost, but
+    // the database isn't written while it is flushed
+    local_sync_node.stop_verification();
+    try!(db
+        .flush()
+        .map_err(|err| format!("Failed to flush database: {}", err)));
+    info!(target: "zebra", "Database is flushed");
     Ok(())
 }
@@ -1,16 +1,24 @@
 use clap;
 use rpc::HttpConfiguration as RpcHttpConfig;
 use rpc_apis::ApiSet;
-use seednodes::{zcash_seednodes, zcash_testnet_seednodes};
+use seednodes::{
+    zcash_dns_seeds, zcash_fixed_seeds, zcash_testnet_dns_seeds, zcash_testnet_fixed_seeds,
+};
+use std::collections::HashSet;
 use std::net;
-use util::open_db;
+use util::{open_db, rpc_cookie_path};
+use zebra_chain::constants::SATOSHIS_IN_COIN;
 use zebra_keys::Address;
 use zebra_message::Services;
+use zebra_miner::DEFAULT_MIN_RELAY_FEE_RATE;
 use zebra_network::{ConsensusParams, Network};
 use zebra_p2p::InternetProtocol;
 use zebra_primitives::hash::H256;
+use zebra_rpc::Credentials;
 use zebra_storage;
-use zebra_sync::VerificationParameters;
+use zebra_sync::{
+    VerificationParameters, VerificationThreshold, DEFAULT_VERIFICATION_TIP_DISTANCE,
+};
 use zebra_verification::VerificationLevel;
 use {REGTEST_USER_AGENT, USER_AGENT};
 
@@ -22,9 +30,18 @@ pub struct Config {
     pub connect: Option<net::SocketAddr>,
     pub host: net::IpAddr,
     pub seednodes: Vec<String>,
+    pub dns_seeds: Vec<String>,
+    pub fixed_seeds: Vec<net::SocketAddr>,
+    pub whitelist: Vec<net::IpAddr>,
     pub quiet: bool,
-    pub inbound_connections: u32,
-    pub outbound_connections: u32,
+    pub max_inbound: u32,
+    pub inbound_connections_per_ip: u32,
+    pub ban_score_threshold: u32,
+    pub ban_time: u32,
+    pub node_table_max_size: usize,
+    pub node_table_save_interval: u64,
+    pub feeler_interval: u64,
+    pub max_outbound: u32,
     pub p2p_threads: usize,
     pub db_cache: usize,
     pub data_dir: Option<String>,
@@ -32,12 +49,22 @@ pub struct Config {
     pub internet_protocol: InternetProtocol,
     pub rpc_config: RpcHttpConfig,
     pub block_notify_command: Option<String>,
+    pub relay_transactions: bool,
+    pub memory_pool_max_size: usize,
+    pub min_relay_fee_rate: u64,
     pub verification_params: VerificationParameters,
     pub db: zebra_storage::SharedStore,
     pub miner_address: Option<Address>,
 }
 
 pub const DEFAULT_DB_CACHE: usize = 512;
+pub const DEFAULT_INBOUND_CONNECTIONS_PER_IP: u32 = 3;
+pub const DEFAULT_BAN_SCORE_THRESHOLD: u32 = 100;
+pub const DEFAULT_BAN_TIME: u32 = 60 * 60 * 24;
+pub const DEFAULT_NODE_TABLE_MAX_SIZE: usize = 1000;
+pub const DEFAULT_NODE_TABLE_SAVE_INTERVAL: u64 = 10;
+pub const DEFAULT_FEELER_INTERVAL: u64 = 120;
+pub const DEFAULT_MAX_MEMPOOL_SIZE: usize = 300;
 
 pub fn parse(matches: &clap::ArgMatches) -> Result<Config, String> {
     let db_cache = match matches.value_of("db-cache") {
@@ -52,7 +79,9 @@ pub fn parse(matches: &clap::ArgMatches) -> Result<Config, String> {
         None => None,
     };
 
-    let db = open_db(&data_dir, db_cache);
+    let address_index = matches.is_present("addressindex");
+    let transaction_index = matches.is_present("txindex");
+    let db = open_db(&data_dir, db_cache, address_index, transaction_index)?;
 
     let quiet = matches.is_present("quiet");
     let network = match (matches.is_present("testnet"), matches.is_present("regtest")) {
@@ -64,11 +93,59 @@ pub fn parse(matches: &clap::ArgMatches) -> Result<Config, String> {
 
     let consensus = ConsensusParams::new(network);
 
-    let (in_connections, out_connections) = match network {
+    let (default_max_inbound, default_max_outbound) = match network {
         Network::Testnet | Network::Mainnet | Network::Other(_) => (10, 10),
         Network::Regtest | Network::Unitest => (1, 0),
     };
 
+    let max_inbound = match matches.value_of("max-inbound") {
+        Some(s) => s.parse().map_err(|_| "Invalid max-inbound".to_owned())?,
+        None => default_max_inbound,
+    };
+
+    let max_outbound = match matches.value_of("max-outbound") {
+        Some(s) => s.parse().map_err(|_| "Invalid max-outbound".to_owned())?,
+        None => default_max_outbound,
+    };
+
+    let in_connections_per_ip = match matches.value_of("inbound-connections-per-ip") {
+        Some(s) => s
+            .parse()
+            .map_err(|_| "Invalid inbound-connections-per-ip".to_owned())?,
+        None => DEFAULT_INBOUND_CONNECTIONS_PER_IP,
+    };
+
+    let ban_score_threshold = match matches.value_of("ban-score") {
+        Some(s) => s.parse().map_err(|_| "Invalid ban-score".to_owned())?,
+        None => DEFAULT_BAN_SCORE_THRESHOLD,
+    };
+
+    let ban_time = match matches.value_of("ban-time") {
+        Some(s) => s.parse().map_err(|_| "Invalid ban-time".to_owned())?,
+        None => DEFAULT_BAN_TIME,
+    };
+
+    let node_table_max_size = match matches.value_of("node-table-size") {
+        Some(s) => s
+            .parse()
+            .map_err(|_| "Invalid node-table-size".to_owned())?,
+        None => DEFAULT_NODE_TABLE_MAX_SIZE,
+    };
+
+    let node_table_save_interval = match matches.value_of("node-table-interval") {
+        Some(s) => s
+            .parse()
+            .map_err(|_| "Invalid node-table-interval".to_owned())?,
+        None => DEFAULT_NODE_TABLE_SAVE_INTERVAL,
+    };
+
+    let feeler_interval = match matches.value_of("feeler-interval") {
+        Some(s) => s
+            .parse()
+            .map_err(|_| "Invalid feeler-interval".to_owned())?,
+        None => DEFAULT_FEELER_INTERVAL,
+    };
+
     let p2p_threads = match network {
         Network::Testnet | Network::Mainnet | Network::Other(_) => 4,
         Network::Regtest | Network::Unitest => 1,
@@ -100,14 +177,31 @@ pub fn parse(matches: &clap::ArgMatches) -> Result<Config, String> {
 
     let seednodes: Vec<String> = match matches.value_of("seednode") {
         Some(s) => vec![s.parse().map_err(|_| "Invalid seednode".to_owned())?],
-        None => match network {
-            Network::Mainnet => zcash_seednodes().into_iter().map(Into::into).collect(),
-            Network::Testnet => zcash_testnet_seednodes()
-                .into_iter()
-                .map(Into::into)
-                .collect(),
-            Network::Other(_) | Network::Regtest | Network::Unitest => Vec::new(),
-        },
+        None => Vec::new(),
+    };
+
+    let no_dns_seed = matches.is_present("no-dns-seed");
+    let (dns_seeds, fixed_seeds) = match network {
+        _ if no_dns_seed => (Vec::new(), Vec::new()),
+        Network::Mainnet => (zcash_dns_seeds(), zcash_fixed_seeds()),
+        Network::Testnet => (zcash_testnet_dns_seeds(), zcash_testnet_fixed_seeds()),
+        Network::Other(_) | Network::Regtest | Network::Unitest => (Vec::new(), Vec::new()),
+    };
+    let dns_seeds: Vec<String> = dns_seeds
+        .into_iter()
+        .map(|seed| format!("{}:{}", seed, network.port()))
+        .collect();
+    let fixed_seeds: Vec<net::SocketAddr> = fixed_seeds
+        .into_iter()
+        .map(|seed| {
+            seed.parse()
+                .expect("fixed seeds are valid socket addresses; qed")
+        })
+        .collect();
+
+    let whitelist: Vec<net::IpAddr> = match matches.value_of("whitelist") {
+        Some(s) => vec![s.parse().map_err(|_| "Invalid whitelist IP".to_owned())?],
+        None => Vec::new(),
     };
 
     let only_net = match matches.value_of("only-net") {
@@ -125,7 +219,7 @@ pub fn parse(matches: &clap::ArgMatches) -> Result<Config, String> {
         },
     };
 
-    let rpc_config = parse_rpc_config(network, matches)?;
+    let rpc_config = parse_rpc_config(network, &data_dir, matches)?;
 
     let block_notify_command = match matches.value_of("blocknotify") {
         Some(s) => Some(
@@ -135,6 +229,28 @@ pub fn parse(matches: &clap::ArgMatches) -> Result<Config, String> {
         None => None,
     };
 
+    let relay_transactions = !matches.is_present("no-tx-relay");
+
+    let max_mempool: usize = match matches.value_of("maxmempool") {
+        Some(s) => s
+            .parse()
+            .map_err(|_| "Invalid maxmempool - should be number in MB".to_owned())?,
+        None => DEFAULT_MAX_MEMPOOL_SIZE,
+    };
+
+    let min_relay_fee_rate = match matches.value_of("minrelaytxfee") {
+        Some(s) => {
+            let fee_rate: f64 = s
+                .parse()
+                .map_err(|_| "Invalid minrelaytxfee - should be number in ZEC/kB".to_owned())?;
+            if !fee_rate.is_finite() || fee_rate < 0.0 {
+                return Err("Invalid minrelaytxfee - should be non-negative number".into());
+            }
+            (fee_rate * SATOSHIS_IN_COIN as f64).round() as u64
+        }
+        None => DEFAULT_MIN_RELAY_FEE_RATE,
+    };
+
     let services = Services::default().with_network(true);
 
     let verification_level = match matches.value_of("verification-level") {
@@ -155,6 +271,19 @@ pub fn parse(matches: &clap::ArgMatches) -> Result<Config, String> {
         _ => network.default_verification_edge(),
     };
 
+    let verification_threshold = match matches.value_of("verification-threshold") {
+        Some(s) if verification_level != VerificationLevel::FULL => {
+            let height: u32 = s
+                .parse()
+                .map_err(|_| "Invalid verification threshold".to_owned())?;
+            Some(VerificationThreshold::new(
+                height,
+                DEFAULT_VERIFICATION_TIP_DISTANCE,
+            ))
+        }
+        _ => None,
+    };
+
     let miner_address = match matches.value_of("miner-address") {
         Some(s) => Some(
             s.parse()
@@ -172,8 +301,17 @@ pub fn parse(matches: &clap::ArgMatches) -> Result<Config, String> {
         connect: connect,
         host: host,
         seednodes: seednodes,
-        inbound_connections: in_connections,
-        outbound_connections: out_connections,
+        dns_seeds: dns_seeds,
+        fixed_seeds: fixed_seeds,
+        whitelist: whitelist,
+        max_inbound: max_inbound,
+        inbound_connections_per_ip: in_connections_per_ip,
+        ban_score_threshold: ban_score_threshold,
+        ban_time: ban_time,
+        node_table_max_size: node_table_max_size,
+        node_table_save_interval: node_table_save_interval,
+        feeler_interval: feeler_interval,
+        max_outbound: max_outbound,
         p2p_threads: p2p_threads,
         db_cache: db_cache,
         data_dir: data_dir,
@@ -181,9 +319,13 @@ pub fn parse(matches: &clap::ArgMatches) -> Result<Config, String> {
         internet_protocol: only_net,
         rpc_config: rpc_config,
         block_notify_command: block_notify_command,
+        relay_transactions: relay_transactions,
+        memory_pool_max_size: max_mempool * 1024 * 1024,
+        min_relay_fee_rate: min_relay_fee_rate,
         verification_params: VerificationParameters {
             verification_level: verification_level,
             verification_edge: verification_edge,
+            verification_threshold: verification_threshold,
         },
         db: db,
         miner_address: miner_address,
@@ -192,7 +334,11 @@ pub fn parse(matches: &clap::ArgMatches) -> Result<Config, String> {
     Ok(config)
 }
 
-fn parse_rpc_config(network: Network, matches: &clap::ArgMatches) -> Result<RpcHttpConfig, String> {
+fn parse_rpc_config(
+    network: Network,
+    data_dir: &Option<String>,
+    matches: &clap::ArgMatches,
+) -> Result<RpcHttpConfig, String> {
     let mut config = RpcHttpConfig::with_port(network.rpc_port());
     config.enabled = !matches.is_present("no-jsonrpc");
     if !config.enabled {
@@ -224,6 +370,44 @@ fn parse_rpc_config(network: Network, matches: &clap::ArgMatches) -> Result<RpcH
             .parse()
             .map_err(|_| "Invalid JSON RPC hosts".to_owned())?]);
     }
+    if let Some(max_request_size) = matches.value_of("jsonrpc-max-request-size") {
+        config.max_request_size = max_request_size
+            .parse()
+            .map_err(|_| "Invalid JSON RPC max request size".to_owned())?;
+    }
+    if let Some(max_batch_size) = matches.value_of("jsonrpc-max-batch-size") {
+        config.max_batch_size = max_batch_size
+            .parse()
+            .map_err(|_| "Invalid JSON RPC max batch size".to_owned())?;
+    }
+    match (
+        matches.value_of("jsonrpc-user"),
+        matches.value_of("jsonrpc-password"),
+        matches.is_present("jsonrpc-cookie"),
+    ) {
+        (None, None, false) => (),
+        (None, None, true) => config.cookie_path = Some(rpc_cookie_path(data_dir)),
+        (Some(user), Some(password), false) => {
+            config.auth.credentials = Some(Credentials {
+                user: user.to_owned(),
+                password: password.to_owned(),
+            })
+        }
+        (_, _, true) => {
+            return Err("JSON RPC cookie can't be used along with user and password".into())
+        }
+        _ => return Err("Both JSON RPC user and password must be specified".into()),
+    }
+    if let Some(methods) = matches.value_of("jsonrpc-public-methods") {
+        config.auth.public_methods = Some(
+            methods
+                .split(',')
+                .map(str::trim)
+                .filter(|method| !method.is_empty())
+                .map(str::to_owned)
+                .collect::<HashSet<_>>(),
+        );
+    }
 
     Ok(config)
 }
@@ -5,6 +5,7 @@ extern crate log;
 extern crate app_dirs;
 extern crate env_logger;
 extern crate libc;
+extern crate rand;
 
 extern crate zebra_chain;
 extern crate zebra_db;
@@ -12,6 +13,7 @@ extern crate zebra_import;
 extern crate zebra_keys;
 extern crate zebra_logs;
 extern crate zebra_message;
+extern crate zebra_miner;
 extern crate zebra_network;
 extern crate zebra_p2p;
 extern crate zebra_primitives;
@@ -42,6 +44,18 @@ pub const USER_AGENT: &'static str = "zebra";
 pub const REGTEST_USER_AGENT: &'static str = "/Satoshi:0.12.1/";
 pub const LOG_INFO: &'static str = "sync=info";
 
+/// Client version (10000 * major + 100 * minor + patch), reported by getnetworkinfo.
+pub fn client_version() -> u32 {
+    let component = |value: &str| {
+        value
+            .parse::<u32>()
+            .expect("cargo sets numeric version components; qed")
+    };
+    10_000 * component(env!("CARGO_PKG_VERSION_MAJOR"))
+        + 100 * component(env!("CARGO_PKG_VERSION_MINOR"))
+        + component(env!("CARGO_PKG_VERSION_PATCH"))
+}
+
 fn main() {
     // Always print backtrace on panic.
     ::std::env::set_var("RUST_BACKTRACE", "1");
@@ -1,11 +1,18 @@
+use rand::os::OsRng;
+use rand::Rng;
 use rpc_apis::{self, ApiSet};
-use std::io;
+use std::fs::{self, File};
+use std::io::{self, Write};
 use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use zebra_keys::Address;
 use zebra_network::ConsensusParams;
 use zebra_p2p;
-use zebra_rpc::{start_http, Compatibility, MetaIoHandler, Server};
+use zebra_rpc::{
+    start_http, Compatibility, Credentials, MetaIoHandler, RequestFilter, RpcAuth, RpcMeta, Server,
+    DEFAULT_MAX_BATCH_SIZE, DEFAULT_MAX_REQUEST_SIZE,
+};
 use zebra_storage;
 use zebra_sync;
 
@@ -15,6 +22,8 @@ pub struct Dependencies {
     pub storage: zebra_storage::SharedStore,
     pub p2p_context: Arc<zebra_p2p::Context>,
     pub miner_address: Option<Address>,
+    pub memory_pool_path: PathBuf,
+    pub shutdown: zebra_p2p::Shutdown,
 }
 
 #[derive(Debug, PartialEq)]
@@ -25,6 +34,15 @@ pub struct HttpConfiguration {
     pub apis: ApiSet,
     pub cors: Option<Vec<String>>,
     pub hosts: Option<Vec<String>>,
+    /// Maximal size of the request body (in bytes).
+    pub max_request_size: usize,
+    /// Maximal number of calls in the single batch request.
+    pub max_batch_size: usize,
+    /// Credentials and methods, available without credentials.
+    pub auth: RpcAuth,
+    /// Path of the cookie file. If set, random credentials are generated on startup
+    /// and saved to this file.
+    pub cookie_path: Option<PathBuf>,
 }
 
 impl HttpConfiguration {
@@ -36,33 +54,91 @@ impl HttpConfiguration {
             apis: ApiSet::default(),
             cors: None,
             hosts: Some(Vec::new()),
+            max_request_size: DEFAULT_MAX_REQUEST_SIZE,
+            max_batch_size: DEFAULT_MAX_BATCH_SIZE,
+            auth: RpcAuth::default(),
+            cookie_path: None,
         }
     }
 }
 
-pub fn new_http(conf: HttpConfiguration, deps: Dependencies) -> Result<Option<Server>, String> {
+/// Running HTTP JSON-RPC server.
+pub struct HttpServer {
+    _server: Server,
+    /// Cookie file, removed after the server is stopped.
+    _cookie: Option<Cookie>,
+}
+
+/// RPC cookie file, removed when dropped.
+struct Cookie {
+    path: PathBuf,
+}
+
+impl Drop for Cookie {
+    fn drop(&mut self) {
+        if let Err(err) = fs::remove_file(&self.path) {
+            warn!(
+                "Failed to remove RPC cookie file {}: {}",
+                self.path.display(),
+                err
+            );
+        }
+    }
+}
+
+pub fn new_http(conf: HttpConfiguration, deps: Dependencies) -> Result<Option<HttpServer>, String> {
     if !conf.enabled {
         return Ok(None);
     }
 
+    let mut auth = conf.auth;
+    let cookie = match conf.cookie_path {
+        Some(cookie_path) => {
+            auth.credentials = Some(try!(write_cookie(&cookie_path)));
+            Some(Cookie { path: cookie_path })
+        }
+        None => None,
+    };
+
     let url = format!("{}:{}", conf.interface, conf.port);
     let addr = try!(url
         .parse()
         .map_err(|_| format!("Invalid JSONRPC listen host/port given: {}", url)));
-    Ok(Some(try!(setup_http_rpc_server(
-        &addr, conf.cors, conf.hosts, conf.apis, deps
-    ))))
+    let server = try!(setup_http_rpc_server(
+        &addr,
+        conf.cors,
+        conf.hosts,
+        conf.max_request_size,
+        conf.max_batch_size,
+        auth,
+        conf.apis,
+        deps
+    ));
+    Ok(Some(HttpServer {
+        _server: server,
+        _cookie: cookie,
+    }))
 }
 
 pub fn setup_http_rpc_server(
     url: &SocketAddr,
     cors_domains: Option<Vec<String>>,
     allowed_hosts: Option<Vec<String>>,
+    max_request_size: usize,
+    max_batch_size: usize,
+    auth: RpcAuth,
     apis: ApiSet,
     deps: Dependencies,
 ) -> Result<Server, String> {
-    let server = setup_rpc_server(apis, deps);
-    let start_result = start_http(url, cors_domains, allowed_hosts, server);
+    let server = setup_rpc_server(max_batch_size, apis, deps);
+    let start_result = start_http(
+        url,
+        cors_domains,
+        allowed_hosts,
+        max_request_size,
+        auth,
+        server,
+    );
     match start_result {
 		Err(ref err) if err.kind() == io::ErrorKind::AddrInUse => {
 			Err(format!("RPC address {} is already in use, make sure that another instance of a Zcash node is not running or change the address using the --jsonrpc-port and --jsonrpc-interface options.", url))
@@ -72,10 +148,65 @@ pub fn setup_http_rpc_server(
 	}
 }
 
-fn setup_rpc_server(apis: ApiSet, deps: Dependencies) -> MetaIoHandler<()> {
+fn setup_rpc_server(
+    max_batch_size: usize,
+    apis: ApiSet,
+    deps: Dependencies,
+) -> MetaIoHandler<RpcMeta, RequestFilter> {
     rpc_apis::setup_rpc(
-        MetaIoHandler::with_compatibility(Compatibility::Both),
+        MetaIoHandler::new(Compatibility::Both, RequestFilter::new(max_batch_size)),
         apis,
         deps,
     )
 }
+
+/// Generates random credentials and saves them to the cookie file, readable by local RPC clients.
+fn write_cookie(path: &Path) -> Result<Credentials, String> {
+    let mut rng =
+        try!(OsRng::new().map_err(|err| format!("Failed to generate RPC cookie: {}", err)));
+    let password = rng
+        .gen_iter::<u8>()
+        .take(32)
+        .map(|byte| format!("{:02x}", byte))
+        .collect::<String>();
+    let credentials = Credentials {
+        user: "__cookie__".into(),
+        password: password,
+    };
+
+    let mut file = try!(create_cookie_file(path).map_err(|err| format!(
+        "Failed to create RPC cookie file {}: {}",
+        path.display(),
+        err
+    )));
+    try!(
+        write!(file, "{}:{}", credentials.user, credentials.password).map_err(|err| format!(
+            "Failed to write RPC cookie file {}: {}",
+            path.display(),
+            err
+        ))
+    );
+    Ok(credentials)
+}
+
+/// Creates (or truncates) the cookie file, which is only accessible by the owner.
+#[cfg(unix)]
+fn create_cookie_file(path: &Path) -> Result<File, io::Error> {
+    use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+
+    let file = fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(path)?;
+    // mode is only applied to the new file => also restrict the file, left by the previous run
+    file.set_permissions(fs::Permissions::from_mode(0o600))?;
+    Ok(file)
+}
+
+/// Creates (or truncates) the cookie file.
+#[cfg(not(unix))]
+fn create_cookie_file(path: &Path) -> Result<File, io::Error> {
+    File::create(path)
+}
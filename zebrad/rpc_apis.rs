@@ -1,7 +1,7 @@
 use rpc::Dependencies;
 use std::collections::HashSet;
 use std::str::FromStr;
-use zebra_rpc::MetaIoHandler;
+use zebra_rpc::{MetaIoHandler, RequestFilter, RpcMeta};
 
 #[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
 pub enum Api {
@@ -53,21 +53,27 @@ impl ApiSet {
 }
 
 pub fn setup_rpc(
-    mut handler: MetaIoHandler<()>,
+    mut handler: MetaIoHandler<RpcMeta, RequestFilter>,
     apis: ApiSet,
     deps: Dependencies,
-) -> MetaIoHandler<()> {
+) -> MetaIoHandler<RpcMeta, RequestFilter> {
     use zebra_rpc::v1::*;
 
     for api in apis.list_apis() {
         match api {
             Api::Raw => handler.extend_with(
-                RawClient::new(RawClientCore::new(deps.local_sync_node.clone())).to_delegate(),
+                RawClient::new(RawClientCore::new(
+                    deps.consensus.clone(),
+                    deps.local_sync_node.clone(),
+                    deps.storage.clone(),
+                ))
+                .to_delegate(),
             ),
             Api::Miner => handler.extend_with(
                 MinerClient::new(MinerClientCore::new(
                     deps.local_sync_node.clone(),
                     deps.miner_address.clone(),
+                    deps.memory_pool_path.clone(),
                 ))
                 .to_delegate(),
             ),
@@ -75,11 +81,19 @@ pub fn setup_rpc(
                 BlockChainClient::new(BlockChainClientCore::new(
                     deps.consensus.clone(),
                     deps.storage.clone(),
+                    deps.local_sync_node.memory_pool(),
+                    deps.local_sync_node.sync_state(),
                 ))
                 .to_delegate(),
             ),
             Api::Network => handler.extend_with(
-                NetworkClient::new(NetworkClientCore::new(deps.p2p_context.clone())).to_delegate(),
+                NetworkClient::new(NetworkClientCore::new(
+                    deps.p2p_context.clone(),
+                    deps.local_sync_node.memory_pool(),
+                    ::client_version(),
+                    deps.shutdown.clone(),
+                ))
+                .to_delegate(),
             ),
         }
     }
@@ -1,11 +1,22 @@
-pub fn zcash_seednodes() -> Vec<&'static str> {
-    vec![
-        "dnsseed.z.cash:8233",
-        "dnsseed.str4d.xyz:8233",
-        "dnsseed.znodes.org:8233",
-    ]
+/// DNS seeds of the Zcash mainnet. Resolved addresses are used with the default network port.
+pub fn zcash_dns_seeds() -> Vec<&'static str> {
+    vec!["dnsseed.z.cash", "dnsseed.str4d.xyz", "dnsseed.znodes.org"]
 }
 
-pub fn zcash_testnet_seednodes() -> Vec<&'static str> {
-    vec!["dnsseed.testnet.z.cash:18233"]
+/// DNS seeds of the Zcash testnet.
+pub fn zcash_testnet_dns_seeds() -> Vec<&'static str> {
+    vec!["dnsseed.testnet.z.cash"]
+}
+
+/// Nodes of the Zcash mainnet, used when none of DNS seeds could be resolved.
+/// zcashd ships no fixed seeds (`vFixedSeeds` is cleared for every network), so the list is
+/// empty until a vetted list of long-running nodes is available.
+pub fn zcash_fixed_seeds() -> Vec<&'static str> {
+    vec![]
+}
+
+/// Nodes of the Zcash testnet, used when none of DNS seeds could be resolved.
+/// Empty for the same reason as `zcash_fixed_seeds`.
+pub fn zcash_testnet_fixed_seeds() -> Vec<&'static str> {
+    vec![]
 }
@@ -1,4 +1,4 @@
-use app_dirs::{app_dir, AppDataType};
+use app_dirs::{app_dir, app_root, AppDataType};
 use config::Config;
 use std::fs::create_dir_all;
 use std::path::PathBuf;
@@ -7,15 +7,21 @@ use zebra_db;
 use zebra_storage;
 use APP_INFO;
 
-pub fn open_db(data_dir: &Option<String>, db_cache: usize) -> zebra_storage::SharedStore {
+pub fn open_db(
+    data_dir: &Option<String>,
+    db_cache: usize,
+    address_index: bool,
+    transaction_index: bool,
+) -> Result<zebra_storage::SharedStore, String> {
     let db_path = match *data_dir {
         Some(ref data_dir) => custom_path(&data_dir, "db"),
         None => app_dir(AppDataType::UserData, &APP_INFO, "db").expect("Failed to get app dir"),
     };
-    Arc::new(
-        zebra_db::BlockChainDatabase::open_at_path(db_path, db_cache)
-            .expect("Failed to open database"),
-    )
+    let mut db = zebra_db::BlockChainDatabase::open_at_path(db_path, db_cache)
+        .expect("Failed to open database");
+    db.set_address_index(address_index)?;
+    db.set_transaction_index(transaction_index)?;
+    Ok(Arc::new(db))
 }
 
 pub fn node_table_path(cfg: &Config) -> PathBuf {
@@ -23,10 +29,43 @@ pub fn node_table_path(cfg: &Config) -> PathBuf {
         Some(ref data_dir) => custom_path(&data_dir, "p2p"),
         None => app_dir(AppDataType::UserData, &APP_INFO, "p2p").expect("Failed to get app dir"),
     };
-    node_table.push("nodes.csv");
+    node_table.push("nodes.dat");
     node_table
 }
 
+pub fn memory_pool_path(cfg: &Config) -> PathBuf {
+    let mut memory_pool = match cfg.data_dir {
+        Some(ref data_dir) => custom_path(&data_dir, "mempool"),
+        None => {
+            app_dir(AppDataType::UserData, &APP_INFO, "mempool").expect("Failed to get app dir")
+        }
+    };
+    memory_pool.push("mempool.dat");
+    memory_pool
+}
+
+pub fn import_state_path(cfg: &Config) -> PathBuf {
+    let mut import_state = match cfg.data_dir {
+        Some(ref data_dir) => custom_path(&data_dir, "import"),
+        None => app_dir(AppDataType::UserData, &APP_INFO, "import").expect("Failed to get app dir"),
+    };
+    import_state.push("import.dat");
+    import_state
+}
+
+pub fn rpc_cookie_path(data_dir: &Option<String>) -> PathBuf {
+    let mut cookie = match *data_dir {
+        Some(ref data_dir) => {
+            let path = PathBuf::from(data_dir);
+            create_dir_all(&path).expect("Failed to get app dir");
+            path
+        }
+        None => app_root(AppDataType::UserData, &APP_INFO).expect("Failed to get app dir"),
+    };
+    cookie.push(".cookie");
+    cookie
+}
+
 pub fn init_db(cfg: &Config) -> Result<(), String> {
     // insert genesis block if db is empty
     let genesis_block = cfg.network.genesis_block();